@@ -1,15 +1,167 @@
 import {
-  PublicKey, Keypair, TransactionInstruction, SYSVAR_CLOCK_PUBKEY,
+  Connection, PublicKey, Keypair, TransactionInstruction, SYSVAR_CLOCK_PUBKEY,
   SYSVAR_SLOT_HASHES_PUBKEY,
   SYSVAR_INSTRUCTIONS_PUBKEY,
+  SystemProgram, Ed25519Program, Transaction, TransactionMessage, VersionedTransaction,
+  AddressLookupTableAccount, ComputeBudgetProgram, PACKET_DATA_SIZE,
 } from "@solana/web3.js";
-import { OracleJob, CrossbarClient, IOracleFeed } from "@switchboard-xyz/common";
+import { OracleJob, CrossbarClient, IOracleFeed, FeedHash } from "@switchboard-xyz/common";
 import * as sb from "@switchboard-xyz/on-demand";
 import { getDefaultQueue } from "@switchboard-xyz/on-demand";
 import { createHash } from "crypto";
 
 // The deployed Pinocchio program ID.
-export const PROGRAM_ID = new PublicKey("Hiy3MrT746mmcEGDRyomPFCG1quUgLRYvUTxijWPshJH");
+// Reassigned by `selectProgramId` to the id registered for the detected cluster.
+export let PROGRAM_ID = new PublicKey("Hiy3MrT746mmcEGDRyomPFCG1quUgLRYvUTxijWPshJH");
+
+export type Cluster = "mainnet" | "devnet" | "testnet" | "localnet";
+
+// Genesis hash of each public cluster; any other genesis is a local validator.
+const GENESIS_HASHES: Record<string, Cluster> = {
+  "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d": "mainnet",
+  "EtWTRABZaYq6iMfeYKouRuZWdNs7nLQbRHwXBuajRjMp": "devnet",
+  "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY": "testnet",
+};
+
+// Program id deployed on each cluster, mirroring the `[programs]` config of the
+// Rust binaries. Edit for your own deployments.
+export const PROGRAM_IDS: Partial<Record<Cluster, PublicKey>> = {
+  mainnet: PROGRAM_ID,
+  devnet: PROGRAM_ID,
+  localnet: PROGRAM_ID,
+};
+
+export async function detectCluster(connection: Connection): Promise<Cluster> {
+  return GENESIS_HASHES[await connection.getGenesisHash()] ?? "localnet";
+}
+
+// Point the SDK at the program deployed on the cluster `connection` serves,
+// so transactions can't target another cluster's deployment. Throws when the
+// cluster has no registered deployment or doesn't match `ACTIVE_PROFILE`
+// (the mainnet profile only runs on mainnet, the others anywhere else).
+export async function selectProgramId(connection: Connection): Promise<PublicKey> {
+  const cluster = await detectCluster(connection);
+  const programId = PROGRAM_IDS[cluster];
+  if (!programId) throw new Error(`no program id registered for ${cluster}`);
+  if ((cluster === "mainnet") !== (ACTIVE_PROFILE.name === "mainnet")) {
+    throw new Error(`the RPC endpoint serves ${cluster}, ORACLE_PROFILE is ${ACTIVE_PROFILE.name}`);
+  }
+  PROGRAM_ID = programId;
+  return programId;
+}
+
+// Deployment profiles, mirroring `shared/src/profiles.rs`.
+// The Range URL is part of the feed definition, so these values must match the
+// profile the program was built with or the feed ids will differ.
+export type ProfileName = "mainnet" | "devnet" | "staging";
+
+// How the provider's score is extracted from the response, mirroring
+// `ResponseTemplate` in `shared/src/feed.rs` (part of the feed id).
+export type Extraction = { jsonPath: string } | { regex: string; group: number };
+
+export interface ResponseTemplate {
+  accept: string;
+  extraction: Extraction;
+}
+
+// Range's JSON response, `{"riskScore": <0–10>, ...}`.
+export const RANGE_JSON: ResponseTemplate = {
+  accept: "application/json",
+  extraction: { jsonPath: "$.riskScore" },
+};
+
+// The parse task of a job.
+function extractionTask(extraction: Extraction) {
+  return "jsonPath" in extraction
+    ? { jsonParseTask: { path: extraction.jsonPath } }
+    : { regexExtractTask: { pattern: extraction.regex, groupNumber: extraction.group } };
+}
+
+export interface Profile {
+  name: ProfileName;
+  queue: PublicKey;
+  // Switchboard On-Demand program owning the queue.
+  switchboardProgram: PublicKey;
+  rangeRiskUrl: string;
+  rangeNetwork: string;
+  response: ResponseTemplate;
+  verifierMaxAge: number;
+  maxQuoteAgeSlots: number;
+  // Fewest oracle signatures the programs accept per quote.
+  minOracleSamples: number;
+  // Largest quote instruction and feed count the programs accept.
+  maxQuoteDataLen: number;
+  maxQuoteFeeds: number;
+  // Whether the programs accept the constant test feed (`getTestFeed`).
+  testFeed: boolean;
+  // Lowest scores the programs tag "warn" and "critical" (see `shared/src/severity.rs`).
+  severityBands: SeverityBands;
+}
+
+export interface SeverityBands {
+  warn: number;
+  critical: number;
+}
+
+export const PROFILES: Record<ProfileName, Profile> = {
+  mainnet: {
+    name: "mainnet",
+    queue: new PublicKey("A43DyUGA7s8eXPxqEjJY6EBu1KKbNgfxF8h17VAHn13w"),
+    switchboardProgram: new PublicKey("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv"),
+    rangeRiskUrl: "https://api.range.org/v1/risk/address",
+    rangeNetwork: "solana",
+    response: RANGE_JSON,
+    verifierMaxAge: 30,
+    maxQuoteAgeSlots: 50,
+    minOracleSamples: 1,
+    maxQuoteDataLen: 1024,
+    maxQuoteFeeds: 8,
+    testFeed: false,
+    severityBands: { warn: 40, critical: 70 },
+  },
+  devnet: {
+    name: "devnet",
+    queue: new PublicKey("EYiAmGSdsQTuCw413V5BzaruWuCCSDgTPtBGvLkXHbe7"),
+    switchboardProgram: new PublicKey("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2"),
+    rangeRiskUrl: "https://api.range.org/v1/risk/address",
+    rangeNetwork: "solana",
+    response: RANGE_JSON,
+    verifierMaxAge: 30,
+    maxQuoteAgeSlots: 50,
+    minOracleSamples: 1,
+    maxQuoteDataLen: 1024,
+    maxQuoteFeeds: 8,
+    testFeed: true,
+    severityBands: { warn: 40, critical: 70 },
+  },
+  // Devnet queue with mainnet freshness limits.
+  staging: {
+    name: "staging",
+    queue: new PublicKey("EYiAmGSdsQTuCw413V5BzaruWuCCSDgTPtBGvLkXHbe7"),
+    switchboardProgram: new PublicKey("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2"),
+    rangeRiskUrl: "https://api.range.org/v1/risk/address",
+    rangeNetwork: "solana",
+    response: RANGE_JSON,
+    verifierMaxAge: 30,
+    maxQuoteAgeSlots: 50,
+    minOracleSamples: 1,
+    maxQuoteDataLen: 1024,
+    maxQuoteFeeds: 8,
+    testFeed: false,
+    severityBands: { warn: 40, critical: 70 },
+  },
+};
+
+// Selected with the `ORACLE_PROFILE` env var, defaults to devnet like the programs.
+export const ACTIVE_PROFILE: Profile = PROFILES[(process.env.ORACLE_PROFILE ?? "devnet") as ProfileName];
+
+// Deployment domain folded into feed names, must match the
+// `RISK_ORACLE_FEED_DOMAIN` the program was built with (see `shared/src/feed.rs`).
+export const FEED_DOMAIN: string | undefined = process.env.RISK_ORACLE_FEED_DOMAIN;
+
+export function feedName(base: string): string {
+  return FEED_DOMAIN ? `${base} @${FEED_DOMAIN}` : base;
+}
 
 // 8-byte Anchor discriminator for the instruction "verify_risk_score_feed"
 function ixDiscriminator(name: string): Buffer {
@@ -19,31 +171,360 @@ function ixDiscriminator(name: string): Buffer {
   return hash.subarray(0, 8);
 }
 const VERIFY_RISK_SCORE_FEED_IX = ixDiscriminator("verify_risk_score_feed");
+const VERIFY_RISK_SCORE_FEED_COMPACT_IX = ixDiscriminator("verify_risk_score_feed_compact");
+const VERIFY_RISK_SCORE_FEED_WITH_DEADLINE_IX = ixDiscriminator("verify_risk_score_feed_with_deadline");
+const INITIALIZE_CONFIG_IX = ixDiscriminator("initialize_config");
+const SET_FEATURE_FLAGS_IX = ixDiscriminator("set_feature_flags");
+const SET_RISK_THRESHOLD_IX = ixDiscriminator("set_risk_threshold");
+const SCHEDULE_THRESHOLD_RAMP_IX = ixDiscriminator("schedule_threshold_ramp");
+const SET_EMERGENCY_THRESHOLD_IX = ixDiscriminator("set_emergency_threshold");
+const CLEAR_EMERGENCY_THRESHOLD_IX = ixDiscriminator("clear_emergency_threshold");
+const EXTEND_CONFIG_IX = ixDiscriminator("extend_config");
+const VERIFY_RISK_SCORE_FEED_FOR_ATA_IX = ixDiscriminator("verify_risk_score_feed_for_ata");
+const VERIFY_COMPOSITE_RISK_SCORE_IX = ixDiscriminator("verify_composite_risk_score");
+const CHECK_OR_VERIFY_SCORE_IX = ixDiscriminator("check_or_verify_score");
+const RECONCILE_IX = ixDiscriminator("reconcile");
+const VERIFY_RISK_SCORE_FEED_PINNED_IX = ixDiscriminator("verify_risk_score_feed_pinned");
+const INITIALIZE_REGISTRY_IX = ixDiscriminator("initialize_registry");
+const REGISTER_FEED_IX = ixDiscriminator("register_feed");
+const UNREGISTER_FEED_IX = ixDiscriminator("unregister_feed");
+const SET_FEED_EXPIRY_IX = ixDiscriminator("set_feed_expiry");
+const SET_TEMPLATE_VARIABLES_IX = ixDiscriminator("set_template_variables");
+const REGISTER_FEED_TEMPLATE_IX = ixDiscriminator("register_feed_template");
+const DERIVE_FEED_ID_IX = ixDiscriminator("derive_feed_id");
+const VERIFY_RISK_SCORE_FEED_CACHED_IX = ixDiscriminator("verify_risk_score_feed_cached");
+const VERIFY_TEST_FEED_IX = ixDiscriminator("verify_test_feed");
+const HEALTH_CHECK_IX = ixDiscriminator("health_check");
+const VERIFY_RISK_SCORE_WITH_FLAG_IX = ixDiscriminator("verify_risk_score_with_flag");
+const VERIFY_RISK_SCORE_WITH_AS_OF_IX = ixDiscriminator("verify_risk_score_with_as_of");
+const CONSUME_VERIFIED_SCORE_IX = ixDiscriminator("consume_verified_score");
+const CLOSE_RECEIPT_IX = ixDiscriminator("close_receipt");
+const CREATE_QUOTE_ACCOUNT_IX = ixDiscriminator("create_quote_account");
+const WRITE_QUOTE_ACCOUNT_IX = ixDiscriminator("write_quote_account");
+const VERIFY_RISK_SCORE_ACCOUNT_IX = ixDiscriminator("verify_risk_score_account");
+const CLOSE_QUOTE_ACCOUNT_IX = ixDiscriminator("close_quote_account");
+const CHECK_RISK_SCORE_TWA_IX = ixDiscriminator("check_risk_score_twa");
+const IMPORT_DENYLIST_IX = ixDiscriminator("import_denylist");
+const REMOVE_FROM_DENYLIST_IX = ixDiscriminator("remove_from_denylist");
+const SET_DENYLIST_ROOT_IX = ixDiscriminator("set_denylist_root");
+const CHECK_DENYLIST_IX = ixDiscriminator("check_denylist");
+const SET_FRESHNESS_SLA_IX = ixDiscriminator("set_freshness_sla");
+const IS_WITHIN_SLA_IX = ixDiscriminator("is_within_sla");
+const SET_ACCESS_LIST_IX = ixDiscriminator("set_access_list");
+const SET_PROGRAM_DENYLIST_IX = ixDiscriminator("set_program_denylist");
+const FLAG_PROGRAM_IX = ixDiscriminator("flag_program");
+const UNFLAG_PROGRAM_IX = ixDiscriminator("unflag_program");
+const SCREEN_PROGRAMS_IX = ixDiscriminator("screen_programs");
+const VERIFY_CONFIDENTIAL_RISK_SCORE_IX = ixDiscriminator("verify_confidential_risk_score");
+const REVEAL_SCORE_IX = ixDiscriminator("reveal_score");
+const VERIFY_THRESHOLD_PROOF_IX = ixDiscriminator("verify_threshold_proof");
+const VERIFY_RISK_SCORE_BATCH_IX = ixDiscriminator("verify_risk_score_batch");
+const GET_CACHED_SCORE_IX = ixDiscriminator("get_cached_score");
+const GET_CONFIG_IX = ixDiscriminator("get_config");
+const DESCRIBE_IX = ixDiscriminator("describe");
+const REGISTER_INTEGRATOR_IX = ixDiscriminator("register_integrator");
+const TOP_UP_INTEGRATOR_IX = ixDiscriminator("top_up_integrator");
+const COLLECT_INTEGRATOR_FEES_IX = ixDiscriminator("collect_integrator_fees");
+const SET_RISK_CEILING_IX = ixDiscriminator("set_risk_ceiling");
+const UPDATE_RISK_CEILING_IX = ixDiscriminator("update_risk_ceiling");
+const SET_SCORE_DECAY_IX = ixDiscriminator("set_score_decay");
+const UPDATE_DENYLIST_SEGMENT_IX = ixDiscriminator("update_denylist_segment");
+const SET_ADDRESS_POLICY_IX = ixDiscriminator("set_address_policy");
+const REMOVE_ADDRESS_POLICY_IX = ixDiscriminator("remove_address_policy");
+const SET_COMPLIANCE_OFFICER_IX = ixDiscriminator("set_compliance_officer");
+const REDEEM_BYPASS_VOUCHER_IX = ixDiscriminator("redeem_bypass_voucher");
+
+// Maximum number of addresses per `import_denylist` instruction.
+export const MAX_DENYLIST_BATCH = 16;
+
+// Maximum number of programs in the confidential score access list.
+export const MAX_ACCESS_LIST_ENTRIES = 16;
+
+// Maximum number of programs in the program denylist.
+export const MAX_DENIED_PROGRAMS = 16;
+
+// Maximum number of query accounts per `verify_risk_score_batch` instruction.
+export const MAX_BATCH_ADDRESSES = 64;
+
+// Bits of `Config.flags`, mirroring `state::feature_flags` in the program.
+export const FeatureFlags = {
+  STRICT_SYSVAR_CHECKS: 1n << 0n,
+  ANTI_REPLAY: 1n << 1n,
+  THRESHOLD_GATING: 1n << 2n,
+  FEE_COLLECTION: 1n << 3n,
+  DENYLIST: 1n << 4n,
+  INTEGRATOR_ACCOUNTING: 1n << 5n,
+  STRICT_QUOTE_FEEDS: 1n << 6n,
+  EXCLUSIVE_THRESHOLD: 1n << 7n,
+  PROGRAM_DENYLIST: 1n << 8n,
+  FEED_REQUEST_V2_IDS: 1n << 9n,
+  RISK_CEILINGS: 1n << 10n,
+  ADDRESS_POLICIES: 1n << 11n,
+  SEGMENTED_DENYLIST: 1n << 12n,
+  SCORE_DECAY: 1n << 13n,
+} as const;
+
+// PDA seeds, mirroring `risk_oracle_shared::seeds` (`*_SEED`).
+export const SEEDS = {
+  CONFIG: "config",
+  REPLAY: "replay",
+  CACHE: "cache",
+  REGISTRY: "registry",
+  FEED_ID: "feed_id",
+  MARKER: "marker",
+  RECEIPT: "receipt",
+  HISTORY: "history",
+  DENYLIST: "denylist",
+  DENYLIST_ROOT: "denylist_root",
+  DENYLIST_SEGMENT: "denylist_segment",
+  SLA: "sla",
+  CONFIDENTIAL: "confidential",
+  THRESHOLD_PROOF: "threshold_proof",
+  ACCESS_LIST: "access_list",
+  PROGRAM_DENYLIST: "program_denylist",
+  INTEGRATOR: "integrator",
+  COMPLIANCE_OFFICER: "compliance_officer",
+  BYPASS: "bypass",
+  TEMPLATE_VARIABLES: "template_variables",
+  QUOTE_ACCOUNT: "quote_account",
+  RISK_CEILING: "risk_ceiling",
+  ADDRESS_POLICY: "address_policy",
+  SCORE_DECAY: "score_decay",
+} as const;
+
+export function findConfigAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from(SEEDS.CONFIG)], PROGRAM_ID)[0];
+}
+
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+// The program's ProgramData account, holding its upgrade authority.
+export function findProgramDataAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync([PROGRAM_ID.toBuffer()], BPF_LOADER_UPGRADEABLE_ID)[0];
+}
+
+export function findReplayRecordAddress(query_account: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.REPLAY), query_account.toBuffer()],
+    PROGRAM_ID
+  )[0];
+}
+
+export function findRegistryAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from(SEEDS.REGISTRY)], PROGRAM_ID)[0];
+}
+
+export function findFeedIdRecordAddress(query_account: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.FEED_ID), query_account.toBuffer()],
+    PROGRAM_ID
+  )[0];
+}
+
+export function findScoreCacheAddress(query_account: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.CACHE), query_account.toBuffer()],
+    PROGRAM_ID
+  )[0];
+}
+
+export function findQuoteMarkerAddress(query_account: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.MARKER), query_account.toBuffer()],
+    PROGRAM_ID,
+  )[0];
+}
+
+export function findReceiptAddress(query_account: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.RECEIPT), query_account.toBuffer()],
+    PROGRAM_ID,
+  )[0];
+}
+
+export function findScoreHistoryAddress(query_account: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.HISTORY), query_account.toBuffer()],
+    PROGRAM_ID,
+  )[0];
+}
+
+// Bits of `sha256(address)` picking the packed denylist segment of an address,
+// mirroring `risk_oracle_shared::denylist_segment`.
+export const DENYLIST_SEGMENT_PREFIX_BITS = 12;
+
+// Packed denylist segment `address` is listed in.
+export function denylistSegmentOf(address: PublicKey): number {
+  const hash = createHash("sha256").update(address.toBuffer()).digest();
+  return hash.readUInt16BE(0) >> (16 - DENYLIST_SEGMENT_PREFIX_BITS);
+}
+
+// Keyed by the big-endian segment index, see `denylistSegmentOf`.
+export function findDenylistSegmentAddress(segment: number): PublicKey {
+  const index = Buffer.alloc(2);
+  index.writeUInt16BE(segment);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.DENYLIST_SEGMENT), index],
+    PROGRAM_ID,
+  )[0];
+}
+
+// Keyed by the screened wallet (the owner for token accounts).
+export function findDenylistEntryAddress(address: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.DENYLIST), address.toBuffer()],
+    PROGRAM_ID,
+  )[0];
+}
+
+export function findDenylistRootAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from(SEEDS.DENYLIST_ROOT)], PROGRAM_ID)[0];
+}
+
+export function findFreshnessSlaAddress(query_account: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.SLA), query_account.toBuffer()],
+    PROGRAM_ID,
+  )[0];
+}
+
+export function findConfidentialScoreAddress(query_account: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.CONFIDENTIAL), query_account.toBuffer()],
+    PROGRAM_ID,
+  )[0];
+}
+
+export function findThresholdProofAddress(query_account: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.THRESHOLD_PROOF), query_account.toBuffer()],
+    PROGRAM_ID,
+  )[0];
+}
+
+export function findAccessListAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from(SEEDS.ACCESS_LIST)], PROGRAM_ID)[0];
+}
+
+export function findProgramDenylistAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from(SEEDS.PROGRAM_DENYLIST)], PROGRAM_ID)[0];
+}
+
+export function findScoreDecayAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from(SEEDS.SCORE_DECAY)], PROGRAM_ID)[0];
+}
+
+// Keyed by the consuming program, not the query account.
+export function findIntegratorAddress(consumer: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.INTEGRATOR), consumer.toBuffer()],
+    PROGRAM_ID,
+  )[0];
+}
+
+// Keyed by the consuming program, not the query account.
+export function findRiskCeilingAddress(consumer: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.RISK_CEILING), consumer.toBuffer()],
+    PROGRAM_ID,
+  )[0];
+}
+
+// Keyed by the screened wallet. For token accounts, this is the owner.
+export function findAddressPolicyAddress(address: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.ADDRESS_POLICY), address.toBuffer()],
+    PROGRAM_ID,
+  )[0];
+}
+
+// Keyed by the `FeedSchema` tag of the template.
+export function findTemplateVariablesAddress(schema: number): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from(SEEDS.TEMPLATE_VARIABLES), Buffer.from([schema])], PROGRAM_ID)[0];
+}
+
+export function findComplianceOfficerAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from(SEEDS.COMPLIANCE_OFFICER)], PROGRAM_ID)[0];
+}
+
+// Keyed by the owner and its own id for the account.
+export function findQuoteAccountAddress(owner: PublicKey, id: bigint): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.QUOTE_ACCOUNT), owner.toBuffer(), u64Le(id)],
+    PROGRAM_ID,
+  )[0];
+}
+
+// Keyed by the screened wallet and the voucher nonce.
+export function findBypassRecordAddress(address: PublicKey, nonce: bigint): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.BYPASS), address.toBuffer(), u64Le(nonce)],
+    PROGRAM_ID,
+  )[0];
+}
+
+function u64Le(value: bigint): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(value);
+  return buf;
+}
+
+// Address screened by the example when none is given.
+export const DEFAULT_QUERY_ACCOUNT = new PublicKey("5PAhQiYdLBd6SVdjzBQDxUAEFyDdF5ExNPQfcscnPRj5");
+
+const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+const TOKEN_ACCOUNT_LEN = 165;
+
+// Resolve the wallet the program will actually screen for `queryAccount`,
+// mirroring `shared/src/query_account.rs`:
+//   - wallets (system owned, funded or not) are screened as is
+//   - token accounts are screened through their owner
+//   - programs and other program-owned accounts are rejected on-chain
+// The feed must be built for the returned address or the feed ids won't match.
+export async function resolveScreenedAddress(connection: Connection, queryAccount: PublicKey): Promise<PublicKey> {
+  const info = await connection.getAccountInfo(queryAccount);
+  if (!info || info.owner.equals(PublicKey.default)) return queryAccount;
+  if (info.executable) throw new Error(`${queryAccount.toBase58()} is a program`);
+
+  const isTokenAccount =
+    (info.owner.equals(TOKEN_PROGRAM_ID) && info.data.length === TOKEN_ACCOUNT_LEN) ||
+    (info.owner.equals(TOKEN_2022_PROGRAM_ID) &&
+      (info.data.length === TOKEN_ACCOUNT_LEN || info.data[TOKEN_ACCOUNT_LEN] === 2));
+  if (!isTokenAccount) throw new Error(`${queryAccount.toBase58()} is a program-owned account`);
+
+  return new PublicKey(info.data.subarray(32, 64));
+}
 
 // Example Oracle Job to fetch Range Risk Score for a given address
 // The oracle job uses a HTTP task to fetch the risk score from Range API
-// and then parses the JSON response to extract the riskScore field.
+// and then parses the response to extract the riskScore field (the JSON path
+// `$.riskScore` with the default `RANGE_JSON` template).
 // The riskScore is then multiplied by 10 to convert it to a scale of 0-100
 // and bounded between 0 and 100.
 // The API key is passed as a variable override to the oracle job.
 //
 // Note that this job is designed to be used with the Pinocchio program
 // which neeeds to match the feed hash on-chain to ensure the integrity of the data.
-export function getRangeRiskScoreJob(): OracleJob {
+export function getRangeRiskScoreJob(
+  address: PublicKey = DEFAULT_QUERY_ACCOUNT,
+  network: string = ACTIVE_PROFILE.rangeNetwork,
+  response: ResponseTemplate = ACTIVE_PROFILE.response,
+): OracleJob {
   const job = OracleJob.fromObject({
     tasks: [
       {
         httpTask: {
-          url: "https://api.range.org/v1/risk/address?address=5PAhQiYdLBd6SVdjzBQDxUAEFyDdF5ExNPQfcscnPRj5&network=solana",
+          url: `${ACTIVE_PROFILE.rangeRiskUrl}?address=${address.toBase58()}&network=${network}`,
           headers: [
-            { key: "accept", value: "application/json" },
+            { key: "accept", value: response.accept },
             // Resolved on-oracle by Variable Override
             { key: "X-API-KEY", value: "${RANGE_API_KEY}" },
           ],
         },
       },
       // Only accept numeric riskScore >= 0; null => no match => failure so no Risk
-      { jsonParseTask: { path: "$.riskScore" } },
+      extractionTask(response.extraction),
       { multiplyTask: { scalar: 10 } }, // 0–10 => 0–100
       {
         boundTask: {
@@ -58,6 +539,90 @@ export function getRangeRiskScoreJob(): OracleJob {
   return job;
 }
 
+// Range networks sharing Solana's base58 address format, bit `i` of a network
+// set selects `COMPOSITE_NETWORKS[i]` (mirrors `NetworkSet` in `shared/src/feed.rs`).
+const COMPOSITE_NETWORKS = ["solana", "eclipse"];
+export const Networks = {
+  SOLANA: 1 << 0,
+  ECLIPSE: 1 << 1,
+} as const;
+
+// Composite feed: one job wrapping the per-network jobs in a `maxTask`, so the
+// oracle reports the highest score across networks.
+export function getCompositeRiskScoreFeed(address: PublicKey, networks: number): IOracleFeed {
+  const jobs = COMPOSITE_NETWORKS
+    .filter((_, bit) => (networks & (1 << bit)) !== 0)
+    .map((network) => getRangeRiskScoreJob(address, network));
+
+  return {
+    name: feedName("Composite Risk Score"),
+    jobs: [OracleJob.fromObject({ tasks: [{ maxTask: { jobs } }] })],
+    minJobResponses: 1,
+    minOracleSamples: 1,
+    maxJobRangePct: 100,
+  };
+}
+
+// Score and blacklist flag from one Range response (mirrors
+// `feed::score_and_flag_feeds`): both feeds share the HTTP call and a
+// `cacheTask` parsing it into two variables, and each reads one of them.
+// Quote them together, in this order.
+export function getScoreAndFlagFeeds(address: PublicKey): [IOracleFeed, IOracleFeed] {
+  return getScoreAndCompanionFeeds(address, "Blacklist Flag", "RANGE_BLACKLISTED", "$.isBlacklisted");
+}
+
+// Score and the Unix time Range computed it at, from one Range response
+// (mirrors `feed::score_and_as_of_feeds`), built like `getScoreAndFlagFeeds`.
+// Quote them together, in this order.
+export function getScoreAndAsOfFeeds(address: PublicKey): [IOracleFeed, IOracleFeed] {
+  return getScoreAndCompanionFeeds(address, "Risk Score As-Of", "RANGE_SCORED_AT", "$.timestamp");
+}
+
+function getScoreAndCompanionFeeds(
+  address: PublicKey,
+  name: string,
+  variable: string,
+  path: string,
+): [IOracleFeed, IOracleFeed] {
+  const [httpTask, ...scoreTasks] = getRangeRiskScoreJob(address).tasks;
+  const cacheTask = {
+    cacheTask: {
+      cacheItems: [
+        { variableName: "RANGE_RISK_SCORE", job: { tasks: scoreTasks } },
+        { variableName: variable, job: { tasks: [{ jsonParseTask: { path } }] } },
+      ],
+    },
+  };
+  const feed = (name: string, variable: string): IOracleFeed => ({
+    name: feedName(name),
+    jobs: [
+      OracleJob.fromObject({
+        tasks: [httpTask, cacheTask, { valueTask: { big: `\${${variable}}` } }],
+      }),
+    ],
+    minJobResponses: 1,
+    minOracleSamples: 1,
+    maxJobRangePct: 100,
+  });
+  return [feed("Risk Score (shared)", "RANGE_RISK_SCORE"), feed(name, variable)];
+}
+
+// Score always reported by the test feed (`feed::TEST_SCORE`).
+export const TEST_SCORE = 42;
+
+// Constant feed for integration tests (mirrors `feed::test_feed`): a single
+// `valueTask`, no HTTP call and no API key. Only accepted by devnet builds
+// (`ACTIVE_PROFILE.testFeed`).
+export function getTestFeed(): IOracleFeed {
+  return {
+    name: feedName("Risk Score Test"),
+    jobs: [OracleJob.fromObject({ tasks: [{ valueTask: { value: TEST_SCORE } }] })],
+    minJobResponses: 1,
+    minOracleSamples: 1,
+    maxJobRangePct: 100,
+  };
+}
+
 // Fetch a signed oracle quote **and** build the Ed25519 signature verification
 // Flow:
 // 1) Choose the queue (devnet in this example)
@@ -67,13 +632,22 @@ export function getRangeRiskScoreJob(): OracleJob {
 //    and passing `variableOverrides` so oracles can resolve `${RANGE_API_KEY}`
 //
 // The returned `sigVerifyIx` is the Ed25519 signature verification
-export async function getOracleJobSignature(payer: Keypair): Promise<{ queue_account: PublicKey; sigVerifyIx: TransactionInstruction }> {
+//
+// Pass `customFeed` (e.g. `getCompositeRiskScoreFeed`) to quote another template,
+// or several feeds (e.g. `getScoreAndFlagFeeds`) to quote them together.
+export async function getOracleJobSignature(
+  payer: Keypair,
+  screenedAddress: PublicKey = DEFAULT_QUERY_ACCOUNT,
+  customFeed?: IOracleFeed | IOracleFeed[],
+): Promise<{ queue_account: PublicKey; sigVerifyIx: TransactionInstruction }> {
   const { gateway, rpcUrl } = await sb.AnchorUtils.loadEnv();
 
   // Get the queue for the network you're deploying on
   //
-  // Devnet queue (use `getDefaultQueue(rpcUrl)` for mainnet)
-  let queue = await sb.getDefaultDevnetQueue(rpcUrl);
+  // Mainnet uses `getDefaultQueue(rpcUrl)`, devnet and staging the devnet queue
+  let queue = ACTIVE_PROFILE.name === "mainnet"
+    ? await getDefaultQueue(rpcUrl)
+    : await sb.getDefaultDevnetQueue(rpcUrl);
   let queue_account = queue.pubkey;
 
 
@@ -86,9 +660,9 @@ export async function getOracleJobSignature(payer: Keypair): Promise<{ queue_acc
 
   // Build  IOracleFeed (feed proto) from your job(s)
   // Keep values minimal and consistent; defaults vs explicit values can change the hash.
-  const feed: IOracleFeed = {
-    name: "Risk Score",
-    jobs: [getRangeRiskScoreJob()],
+  const feed: IOracleFeed | IOracleFeed[] = customFeed ?? {
+    name: feedName("Risk Score"),
+    jobs: [getRangeRiskScoreJob(screenedAddress)],
     minJobResponses: 1,
     minOracleSamples: 1,
     maxJobRangePct: 100,
@@ -105,7 +679,7 @@ export async function getOracleJobSignature(payer: Keypair): Promise<{ queue_acc
   // - `instructionIdx` tells the Ed25519 progr am where to put the sig verify in the tx
   const sigVerifyIx = await queue.fetchQuoteIx(
     crossbar_client,
-    [feed],
+    Array.isArray(feed) ? feed : [feed],
     {
       variableOverrides: { RANGE_API_KEY: process.env.RANGE_API_KEY! },
       numSignatures: 1,
@@ -115,27 +689,1983 @@ export async function getOracleJobSignature(payer: Keypair): Promise<{ queue_acc
   return { queue_account, sigVerifyIx };
 }
 
-// Build the instruction to call your on-chain program
-// This instruction passes the accounts your program needs:
+// Approximate slot duration, to express validity windows in seconds.
+export const SLOT_DURATION_MS = 400;
+
+// Default safety margin of `assertQuoteValid`/`getFreshOracleJobSignature`:
+// time for the transaction to land.
+export const DEFAULT_QUOTE_BUFFER_SLOTS = 10;
+
+// Slot of a quote instruction (`sigVerifyIx`): the u64 LE right after the last
+// signed message, key or signature of the Ed25519 instruction.
+export function quoteSlot(sigVerifyIx: TransactionInstruction): bigint {
+  const data = sigVerifyIx.data;
+  const count = data.length > 0 ? data[0] : 0;
+  let end = 2 + count * 14;
+  for (let i = 0; i < count && 2 + i * 14 + 14 <= data.length; i++) {
+    const at = 2 + i * 14;
+    const signatureOffset = data.readUInt16LE(at);
+    const publicKeyOffset = data.readUInt16LE(at + 4);
+    const messageOffset = data.readUInt16LE(at + 8);
+    const messageSize = data.readUInt16LE(at + 10);
+    end = Math.max(end, messageOffset + messageSize, publicKeyOffset + 32, signatureOffset + 64);
+  }
+  if (count === 0 || data.length < end + 8) {
+    throw new Error("the quote instruction carries no slot");
+  }
+  return data.readBigUInt64LE(end);
+}
+
+// How long a quote stays acceptable on-chain at `currentSlot`.
+//   - maxAgeSlots: the tighter of the profile's `verifierMaxAge` and
+//     `maxQuoteAgeSlots`, the oldest quote both checks accept
+//   - remainingSlots: slots left before the quote is rejected, negative once
+//     it is
+//   - remainingSeconds: the same at `SLOT_DURATION_MS`
+export interface QuoteValidity {
+  quoteSlot: bigint;
+  currentSlot: bigint;
+  maxAgeSlots: bigint;
+  remainingSlots: bigint;
+  remainingSeconds: number;
+}
+
+export function quoteValidity(
+  slot: bigint,
+  currentSlot: bigint,
+  profile: Profile = ACTIVE_PROFILE,
+): QuoteValidity {
+  const maxAgeSlots = BigInt(Math.min(profile.verifierMaxAge, profile.maxQuoteAgeSlots));
+  const remainingSlots = slot + maxAgeSlots - currentSlot;
+  return {
+    quoteSlot: slot,
+    currentSlot,
+    maxAgeSlots,
+    remainingSlots,
+    remainingSeconds: (Number(remainingSlots) * SLOT_DURATION_MS) / 1000,
+  };
+}
+
+// Refuses to send a quote that would leave less than `bufferSlots` of
+// validity at the current slot; returns the validity otherwise.
+export async function assertQuoteValid(
+  connection: Connection,
+  sigVerifyIx: TransactionInstruction,
+  bufferSlots: number = DEFAULT_QUOTE_BUFFER_SLOTS,
+): Promise<QuoteValidity> {
+  const validity = quoteValidity(quoteSlot(sigVerifyIx), BigInt(await connection.getSlot()));
+  if (validity.remainingSlots < BigInt(bufferSlots)) {
+    throw new Error(
+      `quote from slot ${validity.quoteSlot} has ${validity.remainingSlots} slots ` +
+        `(~${validity.remainingSeconds.toFixed(1)}s) of validity left, below the ` +
+        `${bufferSlots}-slot buffer`,
+    );
+  }
+  return validity;
+}
+
+// `getOracleJobSignature`, refetching (at most `attempts` times in all) while
+// the quote would leave less than `bufferSlots` of validity.
+export async function getFreshOracleJobSignature(
+  connection: Connection,
+  payer: Keypair,
+  screenedAddress: PublicKey = DEFAULT_QUERY_ACCOUNT,
+  customFeed?: IOracleFeed | IOracleFeed[],
+  opts: { bufferSlots?: number; attempts?: number } = {},
+): Promise<{ queue_account: PublicKey; sigVerifyIx: TransactionInstruction; validity: QuoteValidity }> {
+  const attempts = opts.attempts ?? 3;
+  for (let attempt = 1; ; attempt++) {
+    const quote = await getOracleJobSignature(payer, screenedAddress, customFeed);
+    try {
+      const validity = await assertQuoteValid(connection, quote.sigVerifyIx, opts.bufferSlots);
+      return { ...quote, validity };
+    } catch (err) {
+      if (attempt >= attempts) throw err;
+    }
+  }
+}
+
+// A risk threshold tagged with its unit: `native` is Range's 0–10 scale,
+// `score` the feed's 0–100 scale. The program converts it on-chain.
+export type RiskThreshold = { native: number } | { score: number };
+
+// Borsh encoding of the `RiskThreshold` enum: variant index, then the value.
+function encodeRiskThreshold(threshold: RiskThreshold): Buffer {
+  return "native" in threshold
+    ? Buffer.from([0, threshold.native])
+    : Buffer.from([1, threshold.score]);
+}
+
+export interface ConfigParams {
+  treasury: PublicKey;
+  flags: bigint;
+  riskThreshold: RiskThreshold;
+  feeLamports: bigint;
+}
+
+// Create the singleton config PDA, `authority` becomes its owner. Only the
+// program's upgrade authority can.
+export function buildInitializeConfigIx(authority: PublicKey, params: ConfigParams): TransactionInstruction {
+  const data = Buffer.concat([
+    INITIALIZE_CONFIG_IX,
+    params.treasury.toBuffer(),
+    u64Le(params.flags),
+    encodeRiskThreshold(params.riskThreshold),
+    u64Le(params.feeLamports),
+  ]);
+
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: true }, // config
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // program
+      { pubkey: findProgramDataAddress(), isSigner: false, isWritable: false }, // program_data
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data,
+  });
+}
+
+// Replace the enabled feature flags (see `FeatureFlags`).
+export function buildSetFeatureFlagsIx(authority: PublicKey, flags: bigint): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: true }, // config
+      { pubkey: authority, isSigner: true, isWritable: false }, // authority
+    ],
+    data: Buffer.concat([SET_FEATURE_FLAGS_IX, u64Le(flags)]),
+  });
+}
+
+// Replace the threshold used by threshold gating.
+export function buildSetRiskThresholdIx(authority: PublicKey, threshold: RiskThreshold): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: true }, // config
+      { pubkey: authority, isSigner: true, isWritable: false }, // authority
+    ],
+    data: Buffer.concat([SET_RISK_THRESHOLD_IX, encodeRiskThreshold(threshold)]),
+  });
+}
+
+// Phase `threshold` in linearly between `startSlot` and `endSlot`, starting
+// from the threshold scheduled now. `buildSetRiskThresholdIx` cancels it.
+export function buildScheduleThresholdRampIx(
+  authority: PublicKey,
+  threshold: RiskThreshold,
+  startSlot: bigint,
+  endSlot: bigint,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: true }, // config
+      { pubkey: authority, isSigner: true, isWritable: false }, // authority
+    ],
+    data: Buffer.concat([
+      SCHEDULE_THRESHOLD_RAMP_IX,
+      encodeRiskThreshold(threshold),
+      u64Le(startSlot),
+      u64Le(endSlot),
+    ]),
+  });
+}
+
+// Longest an emergency threshold lasts from when it is set (~1 day).
+export const MAX_EMERGENCY_SLOTS = 216_000n;
+
+// Clamp gating down to `threshold` for every consumer, whatever the feature
+// flags, until `expirySlot` (at most `MAX_EMERGENCY_SLOTS` ahead). Signed by
+// the config authority.
+export function buildSetEmergencyThresholdIx(
+  authority: PublicKey,
+  threshold: RiskThreshold,
+  expirySlot: bigint,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: true }, // config
+      { pubkey: authority, isSigner: true, isWritable: false }, // authority
+    ],
+    data: Buffer.concat([SET_EMERGENCY_THRESHOLD_IX, encodeRiskThreshold(threshold), u64Le(expirySlot)]),
+  });
+}
+
+// Lift the emergency threshold before it expires.
+export function buildClearEmergencyThresholdIx(authority: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: true }, // config
+      { pubkey: authority, isSigner: true, isWritable: false }, // authority
+    ],
+    data: CLEAR_EMERGENCY_THRESHOLD_IX,
+  });
+}
+
+// Grow a config created before the emergency threshold to the current layout.
+// The authority pays the extra rent.
+export function buildExtendConfigIx(authority: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: true }, // config
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: EXTEND_CONFIG_IX,
+  });
+}
+
+// Accounts only needed when the matching feature flags are enabled.
+//   - payer: pays the fee and the replay record rent (ANTI_REPLAY, FEE_COLLECTION)
+//   - treasury: the configured fee receiver (FEE_COLLECTION)
+//   - antiReplay: pass the per-address replay record (ANTI_REPLAY)
+//   - registry: pass the feed registry (pinned mode)
+//   - feedIdRecord: pass the per-address feed id record (hybrid mode)
+//   - quoteMarker: record the result for `consume_verified_score` (needs payer)
+//   - receipt: write a verification receipt for CPI consumers (needs payer)
+//   - history: append the score to the address history (needs payer)
+//   - denylistAddress: screened wallet whose denylist entry to pass (DENYLIST)
+//   - integrator: consuming program whose usage account to meter
+//     (INTEGRATOR_ACCOUNTING); this program when called directly, the caller
+//     through CPI
+//   - programDenylist: pass the program denylist (PROGRAM_DENYLIST)
+//   - riskCeiling: consuming program whose risk ceiling to pass
+//     (RISK_CEILINGS), as for `integrator`
+//   - addressPolicy: screened wallet whose address policy to pass
+//     (ADDRESS_POLICIES), as for `denylistAddress`
+//   - denylistSegment: screened wallet whose packed denylist segment to pass
+//     (SEGMENTED_DENYLIST), as for `denylistAddress`
+export interface VerifyOptions {
+  payer?: PublicKey;
+  treasury?: PublicKey;
+  antiReplay?: boolean;
+  registry?: boolean;
+  feedIdRecord?: boolean;
+  quoteMarker?: boolean;
+  receipt?: boolean;
+  history?: boolean;
+  denylistAddress?: PublicKey;
+  integrator?: PublicKey;
+  programDenylist?: boolean;
+  riskCeiling?: PublicKey;
+  addressPolicy?: PublicKey;
+  denylistSegment?: PublicKey;
+}
+
+// Accounts of the verify instructions (compact layout):
+//   - config (feature flags)
 //   - queue (to verify the quote)
-//   - sysvars (clock, slot hashes, instructions)
+//   - sysvars (slot hashes, instructions); the program reads the slot with
+//     `Clock::get()`, so no clock account is passed
 //   - query_account (the address you want to fetch the risk score for)
+//   - the optional accounts from `VerifyOptions`; Anchor expects the program id
+//     in the slot of an omitted optional account
+function verifyKeys(queue: PublicKey, query_account: PublicKey, opts: VerifyOptions) {
+  const none = { pubkey: PROGRAM_ID, isSigner: false, isWritable: false };
+  const needsSystemProgram = opts.payer !== undefined;
+
+  return [
+    { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+    { pubkey: queue, isSigner: false, isWritable: false }, // queue
+    { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false }, // slothashes_sysvar_info
+    { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions_sysvar_info
+    { pubkey: query_account, isSigner: false, isWritable: false }, // query_account_info
+    opts.payer ? { pubkey: opts.payer, isSigner: true, isWritable: true } : none, // payer
+    opts.antiReplay
+      ? { pubkey: findReplayRecordAddress(query_account), isSigner: false, isWritable: true }
+      : none, // replay_record
+    opts.treasury ? { pubkey: opts.treasury, isSigner: false, isWritable: true } : none, // treasury
+    needsSystemProgram
+      ? { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }
+      : none, // system_program
+    opts.registry
+      ? { pubkey: findRegistryAddress(), isSigner: false, isWritable: false }
+      : none, // registry
+    opts.feedIdRecord
+      ? { pubkey: findFeedIdRecordAddress(query_account), isSigner: false, isWritable: false }
+      : none, // feed_id_record
+    opts.quoteMarker
+      ? { pubkey: findQuoteMarkerAddress(query_account), isSigner: false, isWritable: true }
+      : none, // quote_marker
+    opts.receipt
+      ? { pubkey: findReceiptAddress(query_account), isSigner: false, isWritable: true }
+      : none, // receipt
+    opts.history
+      ? { pubkey: findScoreHistoryAddress(query_account), isSigner: false, isWritable: true }
+      : none, // history
+    opts.denylistAddress
+      ? { pubkey: findDenylistEntryAddress(opts.denylistAddress), isSigner: false, isWritable: false }
+      : none, // denylist_entry
+    opts.integrator
+      ? { pubkey: findIntegratorAddress(opts.integrator), isSigner: false, isWritable: true }
+      : none, // integrator
+    opts.programDenylist
+      ? { pubkey: findProgramDenylistAddress(), isSigner: false, isWritable: false }
+      : none, // program_denylist
+    opts.riskCeiling
+      ? { pubkey: findRiskCeilingAddress(opts.riskCeiling), isSigner: false, isWritable: false }
+      : none, // risk_ceiling
+    opts.addressPolicy
+      ? { pubkey: findAddressPolicyAddress(opts.addressPolicy), isSigner: false, isWritable: false }
+      : none, // address_policy
+    opts.denylistSegment
+      ? {
+          pubkey: findDenylistSegmentAddress(denylistSegmentOf(opts.denylistSegment)),
+          isSigner: false,
+          isWritable: false,
+        }
+      : none, // denylist_segment
+  ];
+}
+
+// Build the instruction to call your on-chain program, with the original
+// account layout (clock sysvar after the queue) and the config after the query
+// account. Registry and feed id record modes are only available with the
+// compact layout.
 //
 // Note: no data is sent to the program in this example, just the descriminator.
-export function buildGetRiskScoreIx(queue: PublicKey, query_account: PublicKey): TransactionInstruction {
-  const data = VERIFY_RISK_SCORE_FEED_IX;
+export function buildGetRiskScoreIx(queue: PublicKey, query_account: PublicKey, opts: VerifyOptions = {}): TransactionInstruction {
+  const [config, ...keys] = verifyKeys(queue, query_account, opts).slice(0, 9);
+  keys.splice(1, 0, { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false }); // clock_sysvar_info
+  keys.splice(5, 0, config);
+
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys,
+    data: VERIFY_RISK_SCORE_FEED_IX, // no data to send just descriminator
+  });
+}
+
+// Same as `buildGetRiskScoreIx` without the clock account, one key smaller.
+export function buildGetRiskScoreCompactIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  opts: VerifyOptions = {},
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: verifyKeys(queue, query_account, opts),
+    data: VERIFY_RISK_SCORE_FEED_COMPACT_IX,
+  });
+}
+
+// `buildGetRiskScoreCompactIx` bound to a deadline: the program rejects the
+// instruction once the slot is past `notAfterSlot`, even with a fresh quote.
+export function buildGetRiskScoreWithDeadlineIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  notAfterSlot: bigint,
+  opts: VerifyOptions = {},
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: verifyKeys(queue, query_account, opts),
+    data: Buffer.concat([VERIFY_RISK_SCORE_FEED_WITH_DEADLINE_IX, u64Le(notAfterSlot)]),
+  });
+}
+
+// Verify a quote of both `getScoreAndFlagFeeds(query_account)` feeds (score
+// first); fails with `AddressBlacklisted` when the flag is set.
+export function buildVerifyRiskScoreWithFlagIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  opts: VerifyOptions = {},
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: verifyKeys(queue, query_account, opts),
+    data: VERIFY_RISK_SCORE_WITH_FLAG_IX,
+  });
+}
+
+// Verify a quote of both `getScoreAndAsOfFeeds(query_account)` feeds (score
+// first). The return data is the Unix time Range computed the score at
+// (i64), the quote slot (u64), then the verified score.
+export function buildVerifyRiskScoreWithAsOfIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  opts: VerifyOptions = {},
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: verifyKeys(queue, query_account, opts),
+    data: VERIFY_RISK_SCORE_WITH_AS_OF_IX,
+  });
+}
+
+// Verify a quote of `getTestFeed()` for `query_account` (devnet builds only).
+export function buildVerifyTestFeedIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  opts: VerifyOptions = {},
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: verifyKeys(queue, query_account, opts),
+    data: VERIFY_TEST_FEED_IX,
+  });
+}
+
+// Verify a quote of `getTestFeed()` end to end and emit a `Heartbeat` event
+// (devnet builds only). `payer` signs and is recorded in the heartbeat.
+export function buildHealthCheckIx(queue: PublicKey, payer: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: queue, isSigner: false, isWritable: false }, // queue
+      { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false }, // slothashes
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+      { pubkey: payer, isSigner: true, isWritable: false }, // payer
+    ],
+    data: HEALTH_CHECK_IX,
+  });
+}
+
+// ATA mode: `wallet` is passed as `query_account`, the program derives its
+// associated token account for `mint` and screens the wallet (build the feed
+// for the wallet, not the ATA).
+export function buildGetRiskScoreForAtaIx(
+  queue: PublicKey,
+  wallet: PublicKey,
+  mint: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  opts: VerifyOptions = {},
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: verifyKeys(queue, wallet, opts),
+    data: Buffer.concat([VERIFY_RISK_SCORE_FEED_FOR_ATA_IX, mint.toBuffer(), tokenProgram.toBuffer()]),
+  });
+}
 
+// Verify the composite feed (see `getCompositeRiskScoreFeed`) and store the
+// score with its network provenance in the query account's cache PDA, in its
+// history when `history` is set, and records the refresh against its
+// freshness SLA when `freshnessSla` is set (the SLA account must exist).
+export function buildVerifyCompositeRiskScoreIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  payer: PublicKey,
+  networks: number,
+  history: boolean = false,
+  freshnessSla: boolean = false,
+): TransactionInstruction {
   return new TransactionInstruction({
     programId: PROGRAM_ID,
     keys: [
-      // payer_info
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
       { pubkey: queue, isSigner: false, isWritable: false }, // queue
-      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false }, // clock_sysvar_info
-      { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false }, // slothashes_sysvar_info
-      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions_sysvar_info
-      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account_info
+      { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false }, // slothashes
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findScoreCacheAddress(query_account), isSigner: false, isWritable: true }, // cache
+      { pubkey: payer, isSigner: true, isWritable: true }, // payer
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+      history
+        ? { pubkey: findScoreHistoryAddress(query_account), isSigner: false, isWritable: true }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // history
+      freshnessSla
+        ? { pubkey: findFreshnessSlaAddress(query_account), isSigner: false, isWritable: true }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // freshness_sla
+    ],
+    data: Buffer.concat([VERIFY_COMPOSITE_RISK_SCORE_IX, Buffer.from([networks])]),
+  });
+}
+
+// One instruction whatever the cache state: uses the cached composite score
+// when its quote is at most `maxAgeSlots` old, otherwise verifies the quote of
+// `getCompositeRiskScoreFeed` (attach it at index 0) and refreshes the cache.
+// Check the cache age first (`getCachedScoreView`) to skip fetching a quote.
+// Pass `scoreDecay` once the program enables SCORE_DECAY: cached scores then
+// gate with the penalty of their age, and `riskCeiling` (the consuming
+// program) once it enables RISK_CEILINGS.
+export function buildCheckOrVerifyScoreIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  payer: PublicKey,
+  networks: number,
+  maxAgeSlots: number | bigint,
+  history: boolean = false,
+  freshnessSla: boolean = false,
+  scoreDecay: boolean = false,
+  riskCeiling?: PublicKey,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: queue, isSigner: false, isWritable: false }, // queue
+      { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false }, // slothashes
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findScoreCacheAddress(query_account), isSigner: false, isWritable: true }, // cache
+      { pubkey: payer, isSigner: true, isWritable: true }, // payer
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+      history
+        ? { pubkey: findScoreHistoryAddress(query_account), isSigner: false, isWritable: true }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // history
+      freshnessSla
+        ? { pubkey: findFreshnessSlaAddress(query_account), isSigner: false, isWritable: true }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // freshness_sla
+      scoreDecay
+        ? { pubkey: findScoreDecayAddress(), isSigner: false, isWritable: false }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // score_decay
+      riskCeiling
+        ? { pubkey: findRiskCeilingAddress(riskCeiling), isSigner: false, isWritable: false }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // risk_ceiling
+    ],
+    data: Buffer.concat([
+      CHECK_OR_VERIFY_SCORE_IX,
+      Buffer.from([networks]),
+      u64Le(BigInt(maxAgeSlots)),
+    ]),
+  });
+}
 
+// Cross-check the query account's cache against the quote of the feed it was
+// last verified against (attach it at index 0). A score more than `tolerance`
+// away emits `CacheDivergence`, and is overwritten when `correct` is set.
+export function buildReconcileIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  payer: PublicKey,
+  tolerance: number,
+  correct: boolean = false,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: queue, isSigner: false, isWritable: false }, // queue
+      { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false }, // slothashes
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+      { pubkey: findScoreCacheAddress(query_account), isSigner: false, isWritable: true }, // cache
+      { pubkey: payer, isSigner: true, isWritable: false }, // payer
+    ],
+    data: Buffer.concat([RECONCILE_IX, Buffer.from([tolerance, correct ? 1 : 0])]),
+  });
+}
+
+// Create the feed registry, signed by the config authority.
+export function buildInitializeRegistryIx(authority: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findRegistryAddress(), isSigner: false, isWritable: true }, // registry
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: INITIALIZE_REGISTRY_IX,
+  });
+}
+
+function updateRegistryKeys(authority: PublicKey) {
+  return [
+    { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+    { pubkey: findRegistryAddress(), isSigner: false, isWritable: true }, // registry
+    { pubkey: authority, isSigner: true, isWritable: false }, // authority
+  ];
+}
+
+// Feed template tags, mirroring `feed::schema` in the shared crate.
+export const FeedSchema = {
+  RISK_SCORE_V1: 1,
+  COMPOSITE_RISK_SCORE_V1: 2,
+  PASS_FAIL_V1: 3,
+  SCORE_AND_FLAG_V1: 4,
+  SCORE_AND_AS_OF_V1: 5,
+} as const;
+
+// Template that produced a registered feed id: its schema tag and the first 8
+// bytes of the length-delimited feed encoding (zero-padded).
+export interface FeedProvenance {
+  schema: number;
+  encodedPrefix: Buffer;
+}
+
+// Borsh `Option<FeedProvenance>`.
+function encodeOptionalProvenance(provenance?: FeedProvenance): Buffer {
+  if (!provenance) return Buffer.from([0]);
+  const prefix = Buffer.alloc(8);
+  provenance.encodedPrefix.copy(prefix, 0, 0, 8);
+  return Buffer.concat([Buffer.from([1, provenance.schema]), prefix]);
+}
+
+// Pin the feed id of `address` (compute it with `computeFeedId`), optionally
+// recording which template produced it. The authority pays for the
+// registry's growth.
+export function buildRegisterFeedIx(
+  authority: PublicKey,
+  address: PublicKey,
+  feedId: Buffer,
+  provenance?: FeedProvenance,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findRegistryAddress(), isSigner: false, isWritable: true }, // registry
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([
+      REGISTER_FEED_IX,
+      address.toBuffer(),
+      feedId,
+      encodeOptionalProvenance(provenance),
+    ]),
+  });
+}
+
+// Variable override names (`${NAME}` placeholders) the feeds of each
+// template use, mirroring `feed_variables::template_variables`.
+export const TEMPLATE_VARIABLES: Record<number, string[]> = {
+  [FeedSchema.RISK_SCORE_V1]: ["RANGE_API_KEY"],
+  [FeedSchema.COMPOSITE_RISK_SCORE_V1]: ["RANGE_API_KEY"],
+  [FeedSchema.PASS_FAIL_V1]: ["RANGE_API_KEY"],
+  [FeedSchema.SCORE_AND_FLAG_V1]: ["RANGE_API_KEY", "RANGE_RISK_SCORE", "RANGE_BLACKLISTED"],
+  [FeedSchema.SCORE_AND_AS_OF_V1]: ["RANGE_API_KEY", "RANGE_RISK_SCORE", "RANGE_SCORED_AT"],
+};
+
+// Set the variable names the feeds of template `schema` may use, signed by
+// the config authority.
+export function buildSetTemplateVariablesIx(
+  authority: PublicKey,
+  schema: number,
+  names: string[] = TEMPLATE_VARIABLES[schema] ?? [],
+): TransactionInstruction {
+  const encodedNames = names.map((name) => {
+    const bytes = Buffer.from(name, "utf8");
+    return Buffer.concat([u32Le(bytes.length), bytes]);
+  });
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findTemplateVariablesAddress(schema), isSigner: false, isWritable: true }, // template_variables
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([SET_TEMPLATE_VARIABLES_IX, Buffer.from([schema]), u32Le(names.length), ...encodedNames]),
+  });
+}
+
+// Pin a feed of `address` from its length-delimited protobuf encoding (the
+// bytes `computeFeedId` hashes) rather than its id. The program derives the
+// id and fails with `UndeclaredFeedVariable` when a `${NAME}` placeholder
+// isn't a variable of template `schema`, catching a misspelled override
+// before it turns into a `FeedMismatch`.
+export function buildRegisterFeedTemplateIx(
+  authority: PublicKey,
+  address: PublicKey,
+  schema: number,
+  encoded: Buffer,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findRegistryAddress(), isSigner: false, isWritable: true }, // registry
+      { pubkey: findTemplateVariablesAddress(schema), isSigner: false, isWritable: false }, // template_variables
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([
+      REGISTER_FEED_TEMPLATE_IX,
+      address.toBuffer(),
+      Buffer.from([schema]),
+      u32Le(encoded.length),
+      encoded,
+    ]),
+  });
+}
+
+export function buildUnregisterFeedIx(authority: PublicKey, feedId: Buffer): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: updateRegistryKeys(authority),
+    data: Buffer.concat([UNREGISTER_FEED_IX, feedId]),
+  });
+}
+
+// Retire the template of a registered feed: verifications against `feedId`
+// fail with `FeedExpired` from `expiresAtSlot` on (a future slot). Omit it to
+// lift the expiry.
+export function buildSetFeedExpiryIx(
+  authority: PublicKey,
+  feedId: Buffer,
+  expiresAtSlot?: bigint,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: updateRegistryKeys(authority),
+    data: Buffer.concat([
+      SET_FEED_EXPIRY_IX,
+      feedId,
+      expiresAtSlot === undefined
+        ? Buffer.from([0])
+        : Buffer.concat([Buffer.from([1]), u64Le(expiresAtSlot)]),
+    ]),
+  });
+}
+
+// Feed id of `feed`: SHA-256 of its length-delimited protobuf encoding, the
+// id the program derives unless `FeatureFlags.FEED_REQUEST_V2_IDS` is enabled.
+export function computeFeedId(feed: IOracleFeed): Buffer {
+  return FeedHash.computeOracleFeedId(feed);
+}
+
+// Pinned mode: verify against a registered feed id, skipping the on-chain
+// derivation. `feedId` must be registered for the screened wallet.
+export function buildGetRiskScorePinnedIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  feedId: Buffer,
+  opts: VerifyOptions = {},
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: verifyKeys(queue, query_account, { ...opts, registry: true }),
+    data: Buffer.concat([VERIFY_RISK_SCORE_FEED_PINNED_IX, feedId]),
+  });
+}
+
+// Hybrid mode, step 1: derive the feed id of `query_account` on-chain once
+// and store it in its PDA (permissionless, `payer` funds the rent).
+export function buildDeriveFeedIdIx(query_account: PublicKey, payer: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findFeedIdRecordAddress(query_account), isSigner: false, isWritable: true }, // feed_id_record
+      { pubkey: payer, isSigner: true, isWritable: true }, // payer
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: DERIVE_FEED_ID_IX,
+  });
+}
+
+// Hybrid mode, step 2: verify against the stored feed id.
+export function buildGetRiskScoreCachedIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  opts: VerifyOptions = {},
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: verifyKeys(queue, query_account, { ...opts, feedIdRecord: true }),
+    data: VERIFY_RISK_SCORE_FEED_CACHED_IX,
+  });
+}
+
+// Read the score verified by an earlier verify instruction of the same
+// transaction (built with `quoteMarker: true`) without re-running the quote
+// verifier. Returns the score as return data.
+export function buildConsumeVerifiedScoreIx(query_account: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findQuoteMarkerAddress(query_account), isSigner: false, isWritable: false }, // quote_marker
+    ],
+    data: CONSUME_VERIFIED_SCORE_IX,
+  });
+}
+
+// Close the expired verification receipt of `query_account` (permissionless),
+// refunding `rentPayer`, the payer of the verification that wrote it.
+export function buildCloseReceiptIx(query_account: PublicKey, rentPayer: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findReceiptAddress(query_account), isSigner: false, isWritable: true }, // receipt
+      { pubkey: rentPayer, isSigner: false, isWritable: true }, // rent_payer
+    ],
+    data: CLOSE_RECEIPT_IX,
+  });
+}
+
+// Create the empty quote account `id` of `owner`, who pays its rent and is
+// the only one able to write or close it.
+export function buildCreateQuoteAccountIx(owner: PublicKey, id: bigint): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findQuoteAccountAddress(owner, id), isSigner: false, isWritable: true }, // quote_account
+      { pubkey: owner, isSigner: true, isWritable: true }, // owner
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([CREATE_QUOTE_ACCOUNT_IX, u64Le(id)]),
+  });
+}
+
+// Post the quote of the transaction (instruction 0) to the quote account `id`
+// of `owner`, once verified. It must be newer than the posted one.
+export function buildWriteQuoteAccountIx(queue: PublicKey, owner: PublicKey, id: bigint): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: queue, isSigner: false, isWritable: false }, // queue
+      { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false }, // slothashes
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+      { pubkey: findQuoteAccountAddress(owner, id), isSigner: false, isWritable: true }, // quote_account
+      { pubkey: owner, isSigner: true, isWritable: false }, // owner
+    ],
+    data: WRITE_QUOTE_ACCOUNT_IX,
+  });
+}
+
+// Verify the risk score of `query_account` against the quote posted to
+// `quoteAccount`, at most `maxAgeSlots` old: no quote instruction needed.
+// Returns the score, quote slot and oracle samples as return data. Pass
+// `riskCeiling` (the consuming program) under RISK_CEILINGS.
+export function buildVerifyRiskScoreAccountIx(
+  quoteAccount: PublicKey,
+  query_account: PublicKey,
+  maxAgeSlots: bigint,
+  riskCeiling?: PublicKey,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: quoteAccount, isSigner: false, isWritable: false }, // quote_account
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      riskCeiling
+        ? { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // instructions
+      riskCeiling
+        ? { pubkey: findRiskCeilingAddress(riskCeiling), isSigner: false, isWritable: false }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // risk_ceiling
+    ],
+    data: Buffer.concat([VERIFY_RISK_SCORE_ACCOUNT_IX, u64Le(maxAgeSlots)]),
+  });
+}
+
+// Close the quote account `id` of `owner`, refunding its rent.
+export function buildCloseQuoteAccountIx(owner: PublicKey, id: bigint): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findQuoteAccountAddress(owner, id), isSigner: false, isWritable: true }, // quote_account
+      { pubkey: owner, isSigner: true, isWritable: true }, // owner
+    ],
+    data: CLOSE_QUOTE_ACCOUNT_IX,
+  });
+}
+
+// How a quote reaches the verification: the Ed25519 quote instruction in the
+// verifying transaction itself ("instruction"), or posted once to a quote
+// account by `write_quote_account` and read from there ("account").
+export type QuoteDelivery = "instruction" | "account";
+
+export interface QuoteDeliveryRequest {
+  payer: PublicKey;
+  queue: PublicKey;
+  // The quote, as returned by `getOracleJobSignature`.
+  sigVerifyIx: TransactionInstruction;
+  // Query accounts verified against the quote, at most `maxQuoteFeeds`.
+  queryAccounts: PublicKey[];
+  // Transactions expected to verify against this same quote, 1 by default.
+  reuse?: number;
+  // The caller's other instructions in the verifying transaction, counted in
+  // its size.
+  otherIxs?: TransactionInstruction[];
+  // Forces the mode; the decision still reports why the SDK would pick.
+  override?: QuoteDelivery;
+}
+
+// Outcome of `chooseQuoteDelivery`.
+//   - recommended: the mode the SDK picks, `reason` telling why
+//   - instructionTxSize: bytes of the verifying transaction carrying the quote
+//   - accountTxSize: bytes of a verifying transaction reading a quote account
+export interface QuoteDeliveryDecision {
+  mode: QuoteDelivery;
+  recommended: QuoteDelivery;
+  reason: string;
+  overridden: boolean;
+  instructionTxSize: number;
+  accountTxSize: number;
+}
+
+// Serialized size of a legacy transaction of `instructions` paid by `payer`,
+// without building it (`Transaction.serialize` throws past `PACKET_DATA_SIZE`).
+export function estimateTransactionSize(payer: PublicKey, instructions: TransactionInstruction[]): number {
+  const message = new Transaction({
+    feePayer: payer,
+    blockhash: PublicKey.default.toBase58(),
+    lastValidBlockHeight: 0,
+  })
+    .add(...instructions)
+    .compileMessage();
+  const compactLen = (n: number) => (n < 0x80 ? 1 : n < 0x4000 ? 2 : 3);
+  const signatures = message.header.numRequiredSignatures;
+  const keys = message.accountKeys.length;
+  const instructionBytes = message.instructions.reduce(
+    (sum, ix, i) =>
+      sum + 1 + compactLen(ix.accounts.length) + ix.accounts.length
+        + compactLen(instructions[i].data.length) + instructions[i].data.length,
+    0,
+  );
+  return compactLen(signatures) + 64 * signatures + 3 + compactLen(keys) + 32 * keys + 32
+    + compactLen(message.instructions.length) + instructionBytes;
+}
+
+// Verifying instructions of `queryAccounts` for `mode`, the quote instruction
+// first in "instruction" mode. In "account" mode the quote must have been
+// posted to `quoteAccount` (see `buildWriteQuoteAccountIx`), at most
+// `maxAgeSlots` before.
+export function buildQuoteDeliveryIxs(
+  mode: QuoteDelivery,
+  queue: PublicKey,
+  sigVerifyIx: TransactionInstruction,
+  queryAccounts: PublicKey[],
+  account: { quoteAccount: PublicKey; maxAgeSlots: bigint } = {
+    quoteAccount: PublicKey.default,
+    maxAgeSlots: 0n,
+  },
+): TransactionInstruction[] {
+  if (mode === "account") {
+    return queryAccounts.map((queryAccount) =>
+      buildVerifyRiskScoreAccountIx(account.quoteAccount, queryAccount, account.maxAgeSlots),
+    );
+  }
+  const verifyIx = queryAccounts.length === 1
+    ? buildGetRiskScoreCompactIx(queue, queryAccounts[0])
+    : buildVerifyRiskScoreBatchIx(queue, queryAccounts);
+  return [sigVerifyIx, verifyIx];
+}
+
+// Picks how to deliver a quote to the verification of `queryAccounts`:
+//   1. a quote reused by several transactions goes to a quote account, so the
+//      Ed25519 signatures are verified once rather than in every transaction
+//   2. a verifying transaction that wouldn't fit `PACKET_DATA_SIZE` with the
+//      quote instruction reads it from a quote account instead
+//   3. otherwise the quote instruction, with no account to post or pay for
+// `override` replaces the pick; the estimates and reason are still reported.
+export function chooseQuoteDelivery(request: QuoteDeliveryRequest, profile: Profile = ACTIVE_PROFILE): QuoteDeliveryDecision {
+  const { payer, queue, sigVerifyIx, queryAccounts } = request;
+  if (queryAccounts.length === 0 || queryAccounts.length > profile.maxQuoteFeeds) {
+    throw new Error(`a quote covers 1 to ${profile.maxQuoteFeeds} addresses, got ${queryAccounts.length}`);
+  }
+  const reuse = request.reuse ?? 1;
+  const otherIxs = request.otherIxs ?? [];
+  const size = (mode: QuoteDelivery) =>
+    estimateTransactionSize(payer, [
+      ...buildQuoteDeliveryIxs(mode, queue, sigVerifyIx, queryAccounts, {
+        // Any key distinct from the others: only the size matters.
+        quoteAccount: findQuoteAccountAddress(payer, 0n),
+        maxAgeSlots: 0n,
+      }),
+      ...otherIxs,
+    ]);
+  const instructionTxSize = size("instruction");
+  const accountTxSize = size("account");
+
+  let recommended: QuoteDelivery;
+  let reason: string;
+  if (reuse > 1) {
+    recommended = "account";
+    reason = `the quote is reused by ${reuse} transactions: one Ed25519 verification instead of ${reuse}`;
+  } else if (instructionTxSize > PACKET_DATA_SIZE) {
+    recommended = "account";
+    reason = `the transaction with the quote instruction is ${instructionTxSize} bytes, over ${PACKET_DATA_SIZE}`;
+  } else {
+    recommended = "instruction";
+    reason = `one transaction of ${instructionTxSize} bytes carries the quote, no quote account needed`;
+  }
+  const mode = request.override ?? recommended;
+  const txSize = mode === "account" ? accountTxSize : instructionTxSize;
+  if (txSize > PACKET_DATA_SIZE) {
+    throw new Error(
+      `the verifying transaction is ${txSize} bytes in ${mode} mode, over ${PACKET_DATA_SIZE}: ` +
+        "verify fewer addresses per transaction",
+    );
+  }
+  return {
+    mode,
+    recommended,
+    reason,
+    overridden: mode !== recommended,
+    instructionTxSize,
+    accountTxSize,
+  };
+}
+
+// Gate on the time-weighted average of the recorded scores of `query_account`
+// over the last `windowSlots` slots (returned as return data). Pass
+// `riskCeiling` (the consuming program) under RISK_CEILINGS.
+export function buildCheckRiskScoreTwaIx(
+  query_account: PublicKey,
+  windowSlots: bigint,
+  riskCeiling?: PublicKey,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findScoreHistoryAddress(query_account), isSigner: false, isWritable: false }, // history
+      riskCeiling
+        ? { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // instructions
+      riskCeiling
+        ? { pubkey: findRiskCeilingAddress(riskCeiling), isSigner: false, isWritable: false }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // risk_ceiling
+    ],
+    data: Buffer.concat([CHECK_RISK_SCORE_TWA_IX, u64Le(windowSlots)]),
+  });
+}
+
+// Set the freshness SLA of `query_account`, signed by the config authority.
+export function buildSetFreshnessSlaIx(
+  authority: PublicKey,
+  query_account: PublicKey,
+  maxAgeSlots: bigint,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findFreshnessSlaAddress(query_account), isSigner: false, isWritable: true }, // freshness_sla
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([SET_FRESHNESS_SLA_IX, u64Le(maxAgeSlots)]),
+  });
+}
+
+// Whether the cache of `query_account` was refreshed within its SLA (returned
+// as return data). Writes nothing, simulate it.
+export function buildIsWithinSlaIx(query_account: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findFreshnessSlaAddress(query_account), isSigner: false, isWritable: false }, // freshness_sla
+    ],
+    data: IS_WITHIN_SLA_IX,
+  });
+}
+
+// Replace the programs allowed to reveal confidential scores (at most
+// `MAX_ACCESS_LIST_ENTRIES`), signed by the config authority.
+export function buildSetAccessListIx(authority: PublicKey, consumers: PublicKey[]): TransactionInstruction {
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(consumers.length);
+
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findAccessListAddress(), isSigner: false, isWritable: true }, // access_list
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([SET_ACCESS_LIST_IX, len, ...consumers.map((consumer) => consumer.toBuffer())]),
+  });
+}
+
+// Replace the programs no instruction of a verified transaction may target
+// (at most `MAX_DENIED_PROGRAMS`), signed by the config authority. Checked
+// once `FeatureFlags.PROGRAM_DENYLIST` is enabled.
+export function buildSetProgramDenylistIx(authority: PublicKey, programs: PublicKey[]): TransactionInstruction {
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(programs.length);
+
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findProgramDenylistAddress(), isSigner: false, isWritable: true }, // program_denylist
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([SET_PROGRAM_DENYLIST_IX, len, ...programs.map((program) => program.toBuffer())]),
+  });
+}
+
+// Add one program to the program denylist, creating the account on first use.
+export function buildFlagProgramIx(authority: PublicKey, program: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findProgramDenylistAddress(), isSigner: false, isWritable: true }, // program_denylist
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([FLAG_PROGRAM_IX, program.toBuffer()]),
+  });
+}
+
+export function buildUnflagProgramIx(authority: PublicKey, program: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findProgramDenylistAddress(), isSigner: false, isWritable: true }, // program_denylist
+      { pubkey: authority, isSigner: true, isWritable: false }, // authority
+    ],
+    data: Buffer.concat([UNFLAG_PROGRAM_IX, program.toBuffer()]),
+  });
+}
+
+// Fail the transaction when one of its instructions targets a program of the
+// program denylist, whatever the feature flags. Screens the transaction's
+// shape without a quote.
+export function buildScreenProgramsIx(): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findProgramDenylistAddress(), isSigner: false, isWritable: false }, // program_denylist
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+    ],
+    data: SCREEN_PROGRAMS_IX,
+  });
+}
+
+// Confidential mode: verify the composite feed but store only a commitment to
+// the score under `salt` (32 random bytes, keep them to hand to access-listed
+// consumers). The quote in the transaction itself stays public. Pass
+// `riskCeiling` (the consuming program) under RISK_CEILINGS.
+export function buildVerifyConfidentialRiskScoreIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  payer: PublicKey,
+  networks: number,
+  salt: Buffer,
+  riskCeiling?: PublicKey,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: queue, isSigner: false, isWritable: false }, // queue
+      { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false }, // slothashes
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findConfidentialScoreAddress(query_account), isSigner: false, isWritable: true }, // confidential_score
+      { pubkey: payer, isSigner: true, isWritable: true }, // payer
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+      riskCeiling
+        ? { pubkey: findRiskCeilingAddress(riskCeiling), isSigner: false, isWritable: false }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // risk_ceiling
+    ],
+    data: Buffer.concat([VERIFY_CONFIDENTIAL_RISK_SCORE_IX, Buffer.from([networks]), salt]),
+  });
+}
+
+// Return `score` (as return data) when it opens the confidential score
+// commitment of `query_account` with `salt`. Only succeeds when invoked by an
+// access-listed program.
+export function buildRevealScoreIx(
+  query_account: PublicKey,
+  score: number,
+  salt: Buffer,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findAccessListAddress(), isSigner: false, isWritable: false }, // access_list
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findConfidentialScoreAddress(query_account), isSigner: false, isWritable: false }, // confidential_score
+    ],
+    data: Buffer.concat([REVEAL_SCORE_IX, Buffer.from([score]), salt]),
+  });
+}
+
+// Threshold proof mode: verify the risk score feed but record (and return)
+// only whether the score is at most the configured threshold, with a
+// commitment to the score under `salt` (32 random bytes).
+export function buildVerifyThresholdProofIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  payer: PublicKey,
+  salt: Buffer,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: queue, isSigner: false, isWritable: false }, // queue
+      { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false }, // slothashes
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findThresholdProofAddress(query_account), isSigner: false, isWritable: true }, // threshold_proof
+      { pubkey: payer, isSigner: true, isWritable: true }, // payer
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([VERIFY_THRESHOLD_PROOF_IX, salt]),
+  });
+}
+
+// Verify the risk score feeds of `queryAccounts` (at most
+// `MAX_BATCH_ADDRESSES`) against one quote carrying them all. Without
+// `partial` the instruction fails on the first address the quote doesn't carry
+// or threshold gating rejects; with `partial` it succeeds and returns which
+// addresses matched and passed, see `decodeBatchVerification`. Pass
+// `riskCeiling` (the consuming program) under RISK_CEILINGS.
+export function buildVerifyRiskScoreBatchIx(
+  queue: PublicKey,
+  queryAccounts: PublicKey[],
+  partial: boolean = false,
+  riskCeiling?: PublicKey,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: queue, isSigner: false, isWritable: false }, // queue
+      { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false }, // slothashes
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+      riskCeiling
+        ? { pubkey: findRiskCeilingAddress(riskCeiling), isSigner: false, isWritable: false }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // risk_ceiling
+      ...queryAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })), // query accounts, in order
+    ],
+    data: Buffer.concat([VERIFY_RISK_SCORE_BATCH_IX, Buffer.from([partial ? 1 : 0])]),
+  });
+}
+
+// `BatchVerification` returned by `verify_risk_score_batch`, per query
+// account in the order they were passed.
+export interface BatchVerification {
+  // Whether the quote carries the address's feed with enough oracle samples.
+  matched: boolean[];
+  // Whether the matched score is within the configured threshold.
+  passed: boolean[];
+  quoteSlot: bigint;
+}
+
+// Decode the return data of `verify_risk_score_batch` for `count` query
+// accounts. The runtime drops trailing zero bytes, so short data is padded.
+export function decodeBatchVerification(returnData: Buffer, count: number): BatchVerification {
+  const data = returnData.length >= 24 ? returnData : Buffer.concat([returnData, Buffer.alloc(24 - returnData.length)]);
+  const matched = data.readBigUInt64LE(0);
+  const passed = data.readBigUInt64LE(8);
+  const bit = (bitmap: bigint, index: number) => ((bitmap >> BigInt(index)) & 1n) === 1n;
+  return {
+    matched: Array.from({ length: count }, (_, index) => bit(matched, index)),
+    passed: Array.from({ length: count }, (_, index) => bit(passed, index)),
+    quoteSlot: data.readBigUInt64LE(16),
+  };
+}
+
+// View returning the cached score of `query_account` with the values the
+// program derives from it, see `getCachedScoreView`. With `freshnessSla`, the
+// SLA is checked too (the SLA account must exist).
+export function buildGetCachedScoreIx(query_account: PublicKey, freshnessSla: boolean = false): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+      { pubkey: findScoreCacheAddress(query_account), isSigner: false, isWritable: false }, // cache
+      freshnessSla
+        ? { pubkey: findFreshnessSlaAddress(query_account), isSigner: false, isWritable: false }
+        : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }, // freshness_sla
+    ],
+    data: GET_CACHED_SCORE_IX,
+  });
+}
+
+// View returning the config, see `getConfigView`.
+export function buildGetConfigIx(): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [{ pubkey: findConfigAddress(), isSigner: false, isWritable: false }], // config
+    data: GET_CONFIG_IX,
+  });
+}
+
+// View returning the config as self-describing entries, see `getDescription`.
+export function buildDescribeIx(): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [{ pubkey: findConfigAddress(), isSigner: false, isWritable: false }], // config
+    data: DESCRIBE_IX,
+  });
+}
+
+// `Momentum` in the program, by Borsh variant index.
+export const MOMENTUM = ["unknown", "stable", "improving", "deteriorating"] as const;
+export type Momentum = (typeof MOMENTUM)[number];
+
+// `Severity` of the score events, by Borsh variant index.
+export const SEVERITY = ["info", "warn", "critical"] as const;
+export type Severity = (typeof SEVERITY)[number];
+
+// `ScoreProvenance` in the program: where a cached score comes from and the
+// scale it is on (`SCORE_SCHEMA_VERSION` 1: 0–100, Range's 0–10 score × 10).
+export interface ScoreProvenance {
+  // 1: Range.
+  provider: number;
+  // Feed template tag (`feed::schema`), 2 for the composite feed.
+  template: number;
+  schemaVersion: number;
+  quoteSlot: bigint;
+  oracleSamples: number;
+}
+
+// `CachedScoreView` returned by `get_cached_score`.
+export interface CachedScoreView {
+  address: PublicKey;
+  score: number;
+  networks: number;
+  momentum: Momentum;
+  quoteSlot: bigint;
+  updatedSlot: bigint;
+  // Slots since the cache was written.
+  ageSlots: bigint;
+  // Whether threshold gating, as configured now, lets the score through.
+  passesThreshold: boolean;
+  provenance: ScoreProvenance;
+  // Fee payer of the last update.
+  updatedBy: PublicKey;
+  // Only set when the SLA account was passed.
+  withinSla?: boolean;
+}
+
+// `ConfigView` returned by `get_config`.
+export interface ConfigView {
+  authority: PublicKey;
+  treasury: PublicKey;
+  flags: bigint;
+  riskThreshold: number;
+  feeLamports: bigint;
+  maxQuoteAgeSlots: bigint;
+  minOracleSamples: number;
+  emergencyThreshold: number;
+  emergencyExpirySlot: bigint;
+  // Threshold the gates compare against at the simulated slot.
+  effectiveThreshold: number;
+}
+
+// Simulate a view instruction and return its return data. `payer` must exist
+// but signs nothing. The runtime drops trailing zero bytes of return data, so
+// the result is zero-padded back to `minLen`.
+async function simulateView(
+  connection: Connection,
+  payer: PublicKey,
+  ix: TransactionInstruction,
+  minLen: number,
+): Promise<Buffer> {
+  const { blockhash } = await connection.getLatestBlockhash();
+  const message = new TransactionMessage({ payerKey: payer, recentBlockhash: blockhash, instructions: [ix] });
+  const { value } = await connection.simulateTransaction(new VersionedTransaction(message.compileToV0Message()), {
+    sigVerify: false,
+    replaceRecentBlockhash: true,
+  });
+  if (value.err) throw new Error(`view failed: ${JSON.stringify(value.err)}\n${(value.logs ?? []).join("\n")}`);
+  if (!value.returnData) throw new Error("the view returned no data");
+
+  const data = Buffer.from(value.returnData.data[0], "base64");
+  return data.length >= minLen ? data : Buffer.concat([data, Buffer.alloc(minLen - data.length)]);
+}
+
+// Read the cached score of `query_account` with the derived values computed
+// by the same on-chain logic used for gating, through a simulated
+// `get_cached_score`.
+export async function getCachedScoreView(
+  connection: Connection,
+  payer: PublicKey,
+  query_account: PublicKey,
+  freshnessSla: boolean = false,
+): Promise<CachedScoreView> {
+  const data = await simulateView(connection, payer, buildGetCachedScoreIx(query_account, freshnessSla), 106);
+  return {
+    address: new PublicKey(data.subarray(0, 32)),
+    score: data[32],
+    networks: data[33],
+    momentum: MOMENTUM[data[34]],
+    quoteSlot: data.readBigUInt64LE(35),
+    updatedSlot: data.readBigUInt64LE(43),
+    ageSlots: data.readBigUInt64LE(51),
+    passesThreshold: data[59] === 1,
+    provenance: {
+      provider: data[60],
+      template: data[61],
+      schemaVersion: data[62],
+      quoteSlot: data.readBigUInt64LE(63),
+      oracleSamples: data[71],
+    },
+    updatedBy: new PublicKey(data.subarray(72, 104)),
+    withinSla: data[104] === 1 ? data[105] === 1 : undefined,
+  };
+}
+
+// Read the config and the active profile's limits through a simulated
+// `get_config`.
+export async function getConfigView(connection: Connection, payer: PublicKey): Promise<ConfigView> {
+  const data = await simulateView(connection, payer, buildGetConfigIx(), 100);
+  return {
+    authority: new PublicKey(data.subarray(0, 32)),
+    treasury: new PublicKey(data.subarray(32, 64)),
+    flags: data.readBigUInt64LE(64),
+    riskThreshold: data[72],
+    feeLamports: data.readBigUInt64LE(73),
+    maxQuoteAgeSlots: data.readBigUInt64LE(81),
+    minOracleSamples: data[89],
+    emergencyThreshold: data[90],
+    emergencyExpirySlot: data.readBigUInt64LE(91),
+    effectiveThreshold: data[99],
+  };
+}
+
+// Value of a `describe` entry, by kind (mirrors `risk_oracle_shared::describe`).
+// Kinds this SDK doesn't know are kept as raw bytes.
+export type DescribedValue = bigint | boolean | PublicKey | string | { kind: number; bytes: Buffer };
+
+// Decode `describe` entries (without the return data's length prefix), in the
+// program's order.
+export function decodeDescription(data: Buffer): Map<string, DescribedValue> {
+  if (data[0] !== 1) throw new Error(`unsupported description version ${data[0]}`);
+  const entries = new Map<string, DescribedValue>();
+  let at = 1;
+  const take = (len: number): Buffer => {
+    if (at + len > data.length) throw new Error("truncated description");
+    const bytes = data.subarray(at, at + len);
+    at += len;
+    return bytes;
+  };
+  while (at < data.length) {
+    const name = take(take(1)[0]).toString("utf8");
+    const kind = take(1)[0];
+    const bytes = take(take(1)[0]);
+    switch (kind) {
+      case 0:
+        entries.set(name, Buffer.concat([bytes, Buffer.alloc(8 - bytes.length)]).readBigUInt64LE(0));
+        break;
+      case 1:
+        entries.set(name, bytes[0] === 1);
+        break;
+      case 2:
+        entries.set(name, new PublicKey(bytes));
+        break;
+      case 3:
+        entries.set(name, bytes.toString("utf8"));
+        break;
+      default:
+        entries.set(name, { kind, bytes });
+    }
+  }
+  return entries;
+}
+
+// Read the config, the active profile's limits and every feature flag by name
+// through a simulated `describe`.
+export async function getDescription(
+  connection: Connection,
+  payer: PublicKey,
+): Promise<Map<string, DescribedValue>> {
+  const data = await simulateView(connection, payer, buildDescribeIx(), 4);
+  const len = data.readUInt32LE(0);
+  const padded = data.length >= 4 + len ? data : Buffer.concat([data, Buffer.alloc(4 + len - data.length)]);
+  return decodeDescription(padded.subarray(4, 4 + len));
+}
+
+// Outcome of `preflightRiskGated`: the score the oracle verified, or the
+// Anchor error the transaction would fail with (`RiskThresholdExceeded`,
+// `StaleQuote`, ...), undefined when the logs name none.
+export type Preflight =
+  | { passed: true; score: number; oracleSamples: number }
+  | { passed: false; error?: string; logs: string[] };
+
+// Simulate a transaction whose instruction checks a score through CPI (e.g.
+// a handler under `#[risk_gated]`), quote instruction included, before
+// paying for it. A consumer that sets no return data of its own leaves the
+// oracle's `VerifiedScore` as the transaction's. Send only when
+// `shouldSend(preflight, maxScore)`: a margin under the on-chain threshold
+// leaves room for the score to move before the transaction lands.
+export async function preflightRiskGated(
+  connection: Connection,
+  payer: PublicKey,
+  instructions: TransactionInstruction[],
+  lookupTables: AddressLookupTableAccount[] = [],
+): Promise<Preflight> {
+  const { blockhash } = await connection.getLatestBlockhash();
+  const message = new TransactionMessage({ payerKey: payer, recentBlockhash: blockhash, instructions });
+  const { value } = await connection.simulateTransaction(
+    new VersionedTransaction(message.compileToV0Message(lookupTables)),
+    { sigVerify: false, replaceRecentBlockhash: true },
+  );
+  const logs = value.logs ?? [];
+  if (value.err) {
+    const error = logs.map((line) => /Error Code: (\w+)\./.exec(line)?.[1]).find((name) => name);
+    return { passed: false, error, logs };
+  }
+  if (!value.returnData || value.returnData.programId !== PROGRAM_ID.toBase58()) {
+    throw new Error(`no return data of the oracle program (last set by ${value.returnData?.programId ?? "no program"})`);
+  }
+
+  // Trailing zero bytes are dropped by the runtime.
+  const data = Buffer.concat([Buffer.from(value.returnData.data[0], "base64"), Buffer.alloc(2)]);
+  return { passed: true, score: data[0], oracleSamples: data[1] };
+}
+
+export function shouldSend(preflight: Preflight, maxScore: number): boolean {
+  return preflight.passed && preflight.score <= maxScore;
+}
+
+// Emergency bulk denylist import (at most `MAX_DENYLIST_BATCH` addresses).
+// Already denylisted addresses are skipped.
+export function buildImportDenylistIx(authority: PublicKey, addresses: PublicKey[]): TransactionInstruction {
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(addresses.length);
+
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+      ...addresses.map((address) => ({
+        pubkey: findDenylistEntryAddress(address),
+        isSigner: false,
+        isWritable: true,
+      })), // denylist entries, in order
+    ],
+    data: Buffer.concat([IMPORT_DENYLIST_IX, len, ...addresses.map((address) => address.toBuffer())]),
+  });
+}
+
+// Remove `address` from the denylist, refunding the entry rent to the authority.
+export function buildRemoveFromDenylistIx(authority: PublicKey, address: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findDenylistEntryAddress(address), isSigner: false, isWritable: true }, // denylist_entry
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+    ],
+    data: Buffer.concat([REMOVE_FROM_DENYLIST_IX, address.toBuffer()]),
+  });
+}
+
+// List `add` and unlist `remove` in packed denylist segment `segment`, together
+// at most `MAX_DENYLIST_BATCH` addresses, all of that segment (see
+// `denylistSegmentOf`). Creates the segment on first use and grows it, the
+// authority paying the rent.
+export function buildUpdateDenylistSegmentIx(
+  authority: PublicKey,
+  segment: number,
+  add: PublicKey[],
+  remove: PublicKey[] = [],
+): TransactionInstruction {
+  const index = Buffer.alloc(2);
+  index.writeUInt16LE(segment);
+
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findDenylistSegmentAddress(segment), isSigner: false, isWritable: true }, // denylist_segment
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([
+      UPDATE_DENYLIST_SEGMENT_IX,
+      index,
+      u32Le(add.length),
+      ...add.map((address) => address.toBuffer()),
+      u32Le(remove.length),
+      ...remove.map((address) => address.toBuffer()),
+    ]),
+  });
+}
+
+// A listed address of the merkle denylist with its index and siblings (leaf
+// level first), as produced by `risk_oracle_shared::merkle::Tree`.
+export interface LeafProof {
+  address: PublicKey;
+  index: number;
+  siblings: Buffer[];
+}
+
+// Borsh encoding of the program's `DenylistProof`: `lower`/`upper` are the
+// listed neighbors around an unlisted address (omitted at the list edges).
+export type DenylistProof =
+  | { member: LeafProof }
+  | { nonMember: { lower?: LeafProof; upper?: LeafProof } };
+
+function u32Le(value: number): Buffer {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32LE(value);
+  return buf;
+}
+
+function encodeLeafProof(leaf: LeafProof): Buffer {
+  return Buffer.concat([leaf.address.toBuffer(), u32Le(leaf.index), u32Le(leaf.siblings.length), ...leaf.siblings]);
+}
+
+function encodeOptionalLeafProof(leaf?: LeafProof): Buffer {
+  return leaf ? Buffer.concat([Buffer.from([1]), encodeLeafProof(leaf)]) : Buffer.from([0]);
+}
+
+function encodeDenylistProof(proof: DenylistProof): Buffer {
+  if ("member" in proof) {
+    return Buffer.concat([Buffer.from([0]), encodeLeafProof(proof.member)]);
+  }
+  return Buffer.concat([
+    Buffer.from([1]),
+    encodeOptionalLeafProof(proof.nonMember.lower),
+    encodeOptionalLeafProof(proof.nonMember.upper),
+  ]);
+}
+
+// Commit the merkle root of the sorted, deduplicated denylist.
+export function buildSetDenylistRootIx(authority: PublicKey, root: Buffer, leafCount: number): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findDenylistRootAddress(), isSigner: false, isWritable: true }, // denylist_root
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([SET_DENYLIST_ROOT_IX, root, u32Le(leafCount)]),
+  });
+}
+
+// Verify a merkle (non-)membership proof for the screened wallet of
+// `query_account`; returns whether it is listed as return data.
+export function buildCheckDenylistIx(query_account: PublicKey, proof: DenylistProof): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findDenylistRootAddress(), isSigner: false, isWritable: false }, // denylist_root
+      { pubkey: query_account, isSigner: false, isWritable: false }, // query_account
+    ],
+    data: Buffer.concat([CHECK_DENYLIST_IX, encodeDenylistProof(proof)]),
+  });
+}
+
+export interface TransactionOptions {
+  // "v0" (default), or "legacy" for signers rejecting versioned messages.
+  format?: "v0" | "legacy";
+  // Lookup tables of v0 messages; ignored by legacy ones.
+  lookupTables?: AddressLookupTableAccount[];
+  // For a program built with `quote-discovery`: compute budget instructions
+  // may precede the quote.
+  quoteDiscovery?: boolean;
+}
+
+// Compute budget instructions the program skips before the quote with
+// `quote-discovery` (`quote_limits::MAX_COMPUTE_BUDGET_INSTRUCTIONS`).
+export const MAX_COMPUTE_BUDGET_INSTRUCTIONS = 4;
+
+// Index the program reads the quote of `ixs` at: 0, or after the leading
+// compute budget instructions with `quoteDiscovery`.
+export function quoteIndex(ixs: TransactionInstruction[], quoteDiscovery = false): number {
+  if (!quoteDiscovery) return 0;
+  let index = 0;
+  while (
+    index < Math.min(ixs.length, MAX_COMPUTE_BUDGET_INSTRUCTIONS) &&
+    ixs[index].programId.equals(ComputeBudgetProgram.programId)
+  ) {
+    index++;
+  }
+  return index;
+}
+
+// `ixs` (the quote first) with the compute budget instructions `budget`
+// where the program accepts them: before the quote with `quoteDiscovery`,
+// the quote's offsets re-pointed at its new index, after everything
+// otherwise. The signatures cover the message only and stay valid.
+export function withComputeBudget(
+  ixs: TransactionInstruction[],
+  budget: TransactionInstruction[],
+  quoteDiscovery = false,
+): TransactionInstruction[] {
+  if (!quoteDiscovery) return [...ixs, ...budget];
+  const [quote, ...rest] = ixs;
+  if (!quote || !quote.programId.equals(Ed25519Program.programId)) return [...budget, ...ixs];
+  const data = Buffer.from(quote.data);
+  for (let i = 0; i < data[0]; i++) {
+    for (const at of [2, 6, 12].map((field) => 2 + i * 14 + field)) {
+      if (at + 2 <= data.length && data.readUInt16LE(at) !== 0xffff) {
+        data.writeUInt16LE(budget.length, at);
+      }
+    }
+  }
+  const moved = new TransactionInstruction({ programId: quote.programId, keys: quote.keys, data });
+  return [...budget, moved, ...rest];
+}
+
+// Assemble `ixs` as a v0 or legacy transaction, unsigned. The verify
+// instructions read the Switchboard quote (Ed25519) instruction at index 0,
+// or after the leading compute budget instructions with `quoteDiscovery`.
+export function buildTransaction(
+  payer: PublicKey,
+  ixs: TransactionInstruction[],
+  blockhash: string,
+  opts: TransactionOptions = {},
+): VersionedTransaction | Transaction {
+  const quoteAt = ixs.findIndex((ix) => ix.programId.equals(Ed25519Program.programId));
+  const expected = quoteIndex(ixs, opts.quoteDiscovery);
+  if (quoteAt >= 0 && quoteAt !== expected) {
+    throw new Error(`the quote (Ed25519) instruction is at index ${quoteAt}, expected ${expected}`);
+  }
+  if (opts.format === "legacy") {
+    const tx = new Transaction({ feePayer: payer, recentBlockhash: blockhash });
+    return tx.add(...ixs);
+  }
+  const message = new TransactionMessage({
+    payerKey: payer,
+    recentBlockhash: blockhash,
+    instructions: ixs,
+  }).compileToV0Message(opts.lookupTables ?? []);
+  return new VersionedTransaction(message);
+}
+
+// Register `consumer` (a program id) for integrator accounting at
+// `priceLamports` per verification (0 to only count them), or change its
+// price. Signed by the config authority.
+export function buildRegisterIntegratorIx(
+  authority: PublicKey,
+  consumer: PublicKey,
+  priceLamports: bigint,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findIntegratorAddress(consumer), isSigner: false, isWritable: true }, // integrator
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([REGISTER_INTEGRATOR_IX, consumer.toBuffer(), u64Le(priceLamports)]),
+  });
+}
+
+// Add `lamports` from `payer` to the prepaid balance of `consumer`.
+export function buildTopUpIntegratorIx(
+  consumer: PublicKey,
+  payer: PublicKey,
+  lamports: bigint,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findIntegratorAddress(consumer), isSigner: false, isWritable: true }, // integrator
+      { pubkey: payer, isSigner: true, isWritable: true }, // payer
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([TOP_UP_INTEGRATOR_IX, u64Le(lamports)]),
+  });
+}
+
+// Sweep the charged lamports of `consumer` to the configured treasury.
+export function buildCollectIntegratorFeesIx(
+  authority: PublicKey,
+  consumer: PublicKey,
+  treasury: PublicKey,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findIntegratorAddress(consumer), isSigner: false, isWritable: true }, // integrator
+      { pubkey: treasury, isSigner: false, isWritable: true }, // treasury
+      { pubkey: authority, isSigner: true, isWritable: false }, // authority
+    ],
+    data: COLLECT_INTEGRATOR_FEES_IX,
+  });
+}
+
+// Cap the scores accepted for `consumer` (a program id) at `maxScore` (0–100)
+// and name the risk officer who may move the cap. Signed by the config
+// authority.
+export function buildSetRiskCeilingIx(
+  authority: PublicKey,
+  consumer: PublicKey,
+  officer: PublicKey,
+  maxScore: number,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findRiskCeilingAddress(consumer), isSigner: false, isWritable: true }, // risk_ceiling
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([
+      SET_RISK_CEILING_IX,
+      consumer.toBuffer(),
+      officer.toBuffer(),
+      Buffer.from([maxScore]),
+    ]),
+  });
+}
+
+// Move the risk ceiling of `consumer` to `maxScore`. Signed by its risk officer.
+export function buildUpdateRiskCeilingIx(
+  officer: PublicKey,
+  consumer: PublicKey,
+  maxScore: number,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findRiskCeilingAddress(consumer), isSigner: false, isWritable: true }, // risk_ceiling
+      { pubkey: officer, isSigner: true, isWritable: false }, // officer
+    ],
+    data: Buffer.concat([UPDATE_RISK_CEILING_IX, Buffer.from([maxScore])]),
+  });
+}
+
+// Penalize cached scores in `check_or_verify_score` by `penalty` (0–100) per
+// started `intervalSlots` past `graceSlots`, at most `maxPenalty`. Signed by
+// the config authority; applied once SCORE_DECAY is enabled.
+export function buildSetScoreDecayIx(
+  authority: PublicKey,
+  graceSlots: number | bigint,
+  intervalSlots: number | bigint,
+  penalty: number,
+  maxPenalty: number,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findScoreDecayAddress(), isSigner: false, isWritable: true }, // score_decay
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([
+      SET_SCORE_DECAY_IX,
+      u64Le(BigInt(graceSlots)),
+      u64Le(BigInt(intervalSlots)),
+      Buffer.from([penalty, maxPenalty]),
+    ]),
+  });
+}
+
+// `PolicyVerdict` in the program, by Borsh variant index.
+export const POLICY_VERDICT = ["screen", "allow", "deny"] as const;
+export type PolicyVerdict = (typeof POLICY_VERDICT)[number];
+
+// Set the policy of `address`, replacing any previous one: `threshold` (0–100)
+// replaces the config's for it and `minOracleSamples` raises the samples its
+// quotes need (0 adds nothing). Signed by the config authority.
+export function buildSetAddressPolicyIx(
+  authority: PublicKey,
+  address: PublicKey,
+  verdict: PolicyVerdict,
+  threshold?: number,
+  minOracleSamples = 0,
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findAddressPolicyAddress(address), isSigner: false, isWritable: true }, // address_policy
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([
+      SET_ADDRESS_POLICY_IX,
+      address.toBuffer(),
+      Buffer.from([POLICY_VERDICT.indexOf(verdict)]),
+      threshold === undefined ? Buffer.from([0]) : Buffer.from([1, threshold]),
+      Buffer.from([minOracleSamples]),
+    ]),
+  });
+}
+
+export function buildRemoveAddressPolicyIx(authority: PublicKey, address: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findAddressPolicyAddress(address), isSigner: false, isWritable: true }, // address_policy
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+    ],
+    data: Buffer.concat([REMOVE_ADDRESS_POLICY_IX, address.toBuffer()]),
+  });
+}
+
+// Name the compliance officer whose vouchers `redeem_bypass_voucher` accepts.
+// Signed by the config authority.
+export function buildSetComplianceOfficerIx(authority: PublicKey, officer: PublicKey): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findConfigAddress(), isSigner: false, isWritable: false }, // config
+      { pubkey: findComplianceOfficerAddress(), isSigner: false, isWritable: true }, // compliance_officer
+      { pubkey: authority, isSigner: true, isWritable: true }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
+    ],
+    data: Buffer.concat([SET_COMPLIANCE_OFFICER_IX, officer.toBuffer()]),
+  });
+}
+
+// A one-time exception to the risk check for `address`, valid from
+// `notBeforeSlot` to `notAfterSlot`.
+export interface BypassVoucher {
+  address: PublicKey;
+  notBeforeSlot: bigint;
+  notAfterSlot: bigint;
+  nonce: bigint;
+}
+
+function encodeBypassVoucher(voucher: BypassVoucher): Buffer {
+  return Buffer.concat([
+    voucher.address.toBuffer(),
+    u64Le(voucher.notBeforeSlot),
+    u64Le(voucher.notAfterSlot),
+    u64Le(voucher.nonce),
+  ]);
+}
+
+// Bytes the compliance officer signs to issue `voucher`, mirroring
+// `risk_oracle_shared::voucher::BypassVoucher::message`.
+export function bypassVoucherMessage(voucher: BypassVoucher): Buffer {
+  return Buffer.concat([
+    Buffer.from("risk-oracle-bypass-v1"),
+    PROGRAM_ID.toBuffer(),
+    encodeBypassVoucher(voucher),
+  ]);
+}
+
+// The Ed25519 instruction carrying the officer's `signature` of
+// `bypassVoucherMessage(voucher)` and the redemption right after it, in the
+// order the program requires.
+export function buildRedeemBypassVoucherIxs(
+  payer: PublicKey,
+  officer: PublicKey,
+  signature: Uint8Array,
+  voucher: BypassVoucher,
+): TransactionInstruction[] {
+  const signatureIx = Ed25519Program.createInstructionWithPublicKey({
+    publicKey: officer.toBytes(),
+    message: bypassVoucherMessage(voucher),
+    signature,
+  });
+  const redeemIx = new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: findComplianceOfficerAddress(), isSigner: false, isWritable: false }, // compliance_officer
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions
+      { pubkey: findBypassRecordAddress(voucher.address, voucher.nonce), isSigner: false, isWritable: true }, // bypass_record
+      { pubkey: payer, isSigner: true, isWritable: true }, // payer
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
     ],
-    data, // no data to send just descriminator
+    data: Buffer.concat([REDEEM_BYPASS_VOUCHER_IX, encodeBypassVoucher(voucher)]),
   });
+  return [signatureIx, redeemIx];
 }
@@ -13,7 +13,17 @@ import {
 } from "@solana/web3.js";
 import "mocha";
 import * as path from "path";
-import { buildGetRiskScoreIx, getOracleJobSignature, PROGRAM_ID } from "./sdk.ts";
+import {
+  buildGetRiskScoreIx,
+  buildInitializeConfigIx,
+  findConfigAddress,
+  ACTIVE_PROFILE,
+  buildVerifyTestFeedIx,
+  getOracleJobSignature,
+  getTestFeed,
+  PROGRAM_ID,
+  TEST_SCORE,
+} from "./sdk.ts";
 import "dotenv/config";
 
 // Load a Keypair from a JSON file
@@ -36,6 +46,20 @@ describe("Initialize Oracle Example", function () {
   );
 
 
+  before(async () => {
+    // The program reads its feature flags from the config PDA, create it once
+    // with every flag disabled.
+    if (await connection.getAccountInfo(findConfigAddress())) return;
+
+    const ix = buildInitializeConfigIx(DEV_WALLET.publicKey, {
+      treasury: DEV_WALLET.publicKey,
+      flags: 0n,
+      riskThreshold: { score: 100 },
+      feeLamports: 0n,
+    });
+    await sendAndConfirmTransaction(connection, new Transaction().add(ix), [DEV_WALLET]);
+  });
+
   it("initializes the Oracle and call the Oracle Program", async () => {
 
     /**
@@ -80,7 +104,26 @@ describe("Initialize Oracle Example", function () {
 
     // Some basic assertion to ensure it went through can be added here
   });
-});
 
+  it("verifies the constant test feed without an API key", async function () {
+    if (!ACTIVE_PROFILE.testFeed) this.skip();
 
+    // A quote of the ValueTask-only test feed: deterministic, no HTTP call.
+    const query_account = new PublicKey("5PAhQiYdLBd6SVdjzBQDxUAEFyDdF5ExNPQfcscnPRj5");
+    const { queue_account, sigVerifyIx } = await getOracleJobSignature(
+      DEV_WALLET,
+      query_account,
+      getTestFeed(),
+    );
+
+    const tx = new Transaction().add(sigVerifyIx, buildVerifyTestFeedIx(queue_account, query_account));
+    const signature = await sendAndConfirmTransaction(connection, tx, [DEV_WALLET]);
 
+    const confirmed = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const logs = confirmed?.meta?.logMessages ?? [];
+    assert(logs.some((log) => log.includes(`Verified risk score feed! Value: ${TEST_SCORE}`)), logs.join("\n"));
+  });
+});
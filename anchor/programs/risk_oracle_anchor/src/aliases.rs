@@ -0,0 +1,91 @@
+//! Legacy instruction names and argument layouts, kept working across program
+//! upgrades.
+//!
+//! Anchor routes an instruction by the discriminator of its name, so renaming
+//! an instruction or changing its arguments breaks every integrator still
+//! building the old one. Instead of keeping the old handler, the old name goes
+//! in [`ALIASES`] with the instruction now handling it and a translation of
+//! its arguments: the program's `fallback` rewrites the instruction data and
+//! dispatches it as the replacement, with the same accounts, after emitting
+//! `DeprecatedInstructionUsed` so operators see who still needs to migrate.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::Discriminator;
+
+use crate::events::DeprecatedInstructionUsed;
+use crate::instruction;
+use crate::instructions::config::RiskThreshold;
+
+/// A legacy instruction and the one now handling it.
+pub struct Alias {
+    pub legacy: &'static str,
+    pub replacement: &'static str,
+    pub replacement_discriminator: &'static [u8],
+    /// Arguments of the replacement from those of the legacy instruction.
+    pub translate: fn(&[u8]) -> Result<Vec<u8>>,
+}
+
+pub const ALIASES: &[Alias] = &[
+    // The SDK's `buildGetRiskScoreIx` name: same accounts, no arguments.
+    Alias {
+        legacy: "get_risk_score",
+        replacement: "verify_risk_score_feed",
+        replacement_discriminator: instruction::VerifyRiskScoreFeed::DISCRIMINATOR,
+        translate: same_arguments,
+    },
+    // A bare feed-scale `u8`, now `RiskThreshold::Score`.
+    Alias {
+        legacy: "set_threshold",
+        replacement: "set_risk_threshold",
+        replacement_discriminator: instruction::SetRiskThreshold::DISCRIMINATOR,
+        translate: score_threshold,
+    },
+];
+
+/// Anchor discriminator of instruction `name`.
+pub fn discriminator(name: &str) -> [u8; 8] {
+    let hash = hashv(&[b"global:", name.as_bytes()]).to_bytes();
+    hash[..8].try_into().unwrap()
+}
+
+/// The alias `data` was built for, if any.
+pub fn find(data: &[u8]) -> Option<&'static Alias> {
+    let tag = data.get(..8)?;
+    ALIASES
+        .iter()
+        .find(|alias| discriminator(alias.legacy) == tag)
+}
+
+/// Dispatches a legacy instruction as its replacement, failing as Anchor
+/// does without a fallback when `data` matches no alias.
+pub fn dispatch<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    data: &[u8],
+) -> Result<()> {
+    let alias = find(data).ok_or(anchor_lang::error::ErrorCode::InstructionFallbackNotFound)?;
+    let mut replaced = alias.replacement_discriminator.to_vec();
+    replaced.extend((alias.translate)(&data[8..])?);
+
+    emit!(DeprecatedInstructionUsed {
+        legacy: alias.legacy.to_string(),
+        replacement: alias.replacement.to_string(),
+    });
+    crate::entry(program_id, accounts, &replaced).map_err(Into::into)
+}
+
+fn same_arguments(args: &[u8]) -> Result<Vec<u8>> {
+    Ok(args.to_vec())
+}
+
+fn score_threshold(args: &[u8]) -> Result<Vec<u8>> {
+    let [score] = *args else {
+        return Err(anchor_lang::error::ErrorCode::InstructionDidNotDeserialize.into());
+    };
+    let mut translated = Vec::new();
+    RiskThreshold::Score(score)
+        .serialize(&mut translated)
+        .map_err(|_| anchor_lang::error::ErrorCode::InstructionDidNotSerialize)?;
+    Ok(translated)
+}
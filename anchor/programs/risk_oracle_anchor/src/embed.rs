@@ -0,0 +1,88 @@
+//! In-process verification: the program's check of a risk score quote as a
+//! library function, for integrators running it inside their own
+//! instruction instead of calling this program through CPI (see
+//! [`gate`](crate::gate)).
+//!
+//! The checks are those of `verify_risk_score_feed` with no optional
+//! account: queue, sysvars, screened address, feed id (under the config's
+//! derivation), quote signatures, freshness and oracle samples. What needs this program's accounts
+//! (threshold gating, fees, replay protection, receipts, history, denylist)
+//! is left to the integrator, along with the threshold to apply. Depend on
+//! the crate with `no-entrypoint`.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use risk_oracle_shared::{feed, profiles};
+
+use crate::error::ErrorCode;
+use crate::state::Config;
+use crate::verify::{feed_id_with, resolve_screened_address, score_from_value, verify_quote};
+use crate::VerifiedScore;
+
+/// Accounts of the verification, the integrator's own.
+pub struct EmbedAccounts<'a, 'info> {
+    /// This program's config, for its feed id derivation.
+    pub config: &'a AccountInfo<'info>,
+    /// The profile's Switchboard queue.
+    pub queue: &'a AccountInfo<'info>,
+    pub slothashes: &'a AccountInfo<'info>,
+    pub instructions: &'a AccountInfo<'info>,
+    /// Screened account: a wallet, or a token account screened through its
+    /// owner.
+    pub query_account: &'a AccountInfo<'info>,
+}
+
+/// Verifies the transaction's risk score quote for `accounts.query_account`
+/// and returns the score, its oracle count and the verified feed. Logs and
+/// fails as `verify_risk_score_feed` does.
+pub fn verify_risk_score(accounts: EmbedAccounts<'_, '_>) -> Result<VerifiedScore> {
+    // Checked by the instruction's account types when called through CPI.
+    require_keys_eq!(
+        *accounts.queue.key,
+        Pubkey::new_from_array(profiles::ACTIVE.queue),
+        anchor_lang::error::ErrorCode::ConstraintAddress
+    );
+    require_keys_eq!(
+        *accounts.queue.owner,
+        Pubkey::new_from_array(profiles::ACTIVE.switchboard_program),
+        anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram
+    );
+    require_keys_eq!(
+        *accounts.instructions.key,
+        sysvar::instructions::ID,
+        ErrorCode::InvalidSysvar
+    );
+
+    // Owned by this program: only `initialize_config` creates a `Config`, at
+    // its PDA.
+    require_keys_eq!(
+        *accounts.config.owner,
+        crate::ID,
+        anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram
+    );
+    let config = Config::try_deserialize(&mut &accounts.config.try_borrow_data()?[..])?;
+
+    let screened_address = resolve_screened_address(accounts.query_account)?;
+    let derived_feed_id = feed_id_with(
+        config.feed_id_derivation(),
+        &feed::risk_score_feed(&screened_address.to_bytes()),
+    );
+    let verified = verify_quote(
+        accounts.queue,
+        accounts.slothashes,
+        accounts.instructions,
+        &derived_feed_id,
+    )?;
+    let score = score_from_value(verified.value);
+    msg!("Verified risk score feed! Value: {}", score);
+
+    Ok(VerifiedScore {
+        score,
+        oracle_samples: verified.oracle_samples,
+        feeds: verified
+            .feeds
+            .into_iter()
+            .filter(|feed| feed.feed_id == derived_feed_id)
+            .collect(),
+    })
+}
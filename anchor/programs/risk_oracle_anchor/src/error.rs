@@ -0,0 +1,250 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("No oracle feeds available")]
+    NoOracleFeeds,
+
+    #[msg("Feed hash mismatch - oracle feed does not match expected configuration")]
+    FeedMismatch,
+
+    #[msg("Invalid feed JSON")]
+    InvalidFeedJson,
+
+    #[msg("Failed to create quote verifier")]
+    VerifierError,
+
+    #[msg("Failed to verify Ed25519 instruction")]
+    VerificationFailed,
+
+    #[msg("Stale quote - the quote is too old")]
+    StaleQuote,
+
+    #[msg("Risk threshold must be 0–10 in provider units or 0–100 in score units")]
+    InvalidThreshold,
+
+    #[msg("Unknown feature flag")]
+    UnknownFeatureFlag,
+
+    #[msg("Sysvar account does not match the expected sysvar id")]
+    InvalidSysvar,
+
+    #[msg("Risk score does not pass the configured threshold")]
+    RiskThresholdExceeded,
+
+    #[msg("Anti-replay is enabled but no replay record was provided")]
+    MissingReplayRecord,
+
+    #[msg("Quote is not newer than the last accepted quote for this address")]
+    QuoteReplayed,
+
+    #[msg("Fee collection is enabled but the payer, treasury or system program is missing")]
+    MissingFeeAccounts,
+
+    #[msg("Query account is an executable program, there is no wallet to screen")]
+    QueryAccountIsProgram,
+
+    #[msg("Query account is a program-owned data account (PDA, mint, ...), not a wallet")]
+    QueryAccountIsProgramOwned,
+
+    #[msg("ATA mode requires the query account to be a wallet")]
+    QueryAccountNotWallet,
+
+    #[msg("Token program must be SPL Token or Token-2022")]
+    InvalidTokenProgram,
+
+    #[msg("Network set is empty or contains unknown networks")]
+    InvalidNetworks,
+
+    #[msg("Feed id is already registered")]
+    FeedAlreadyRegistered,
+
+    #[msg("Feed registry is full")]
+    RegistryFull,
+
+    #[msg("Feed id is not registered")]
+    FeedNotRegistered,
+
+    #[msg("Pinned mode requires the registry account")]
+    MissingRegistry,
+
+    #[msg("Registered feed id belongs to another address")]
+    FeedAddressMismatch,
+
+    #[msg("Hybrid mode requires the feed id record, create it with derive_feed_id")]
+    MissingFeedIdRecord,
+
+    #[msg("Feed id record was derived for another wallet, call derive_feed_id again")]
+    StaleFeedIdRecord,
+
+    #[msg("Quote marker was not written for the quote of this transaction")]
+    StaleQuoteMarker,
+
+    #[msg("Verification receipt has not expired yet")]
+    ReceiptNotExpired,
+
+    #[msg("Verification was made for another consuming program")]
+    ConsumerMismatch,
+
+    #[msg("No score recorded in the requested window")]
+    NoHistoryInWindow,
+
+    #[msg("Denylist batch is empty, too large, or its accounts don't match the addresses")]
+    InvalidDenylistBatch,
+
+    #[msg("Denylist entry account is not the PDA of the screened address")]
+    InvalidDenylistEntry,
+
+    #[msg("Denylist checks require the denylist entry account")]
+    MissingDenylistEntry,
+
+    #[msg("Address is denylisted")]
+    AddressDenylisted,
+
+    #[msg("Denylist merkle proof is invalid")]
+    InvalidDenylistProof,
+
+    #[msg("The test feed is disabled in this deployment profile")]
+    TestFeedDisabled,
+
+    #[msg("The provider flags the address as blacklisted")]
+    AddressBlacklisted,
+
+    #[msg("Quote is signed by fewer oracles than the feed or profile requires")]
+    InsufficientOracleSamples,
+
+    #[msg("Quote instruction is larger than the profile allows")]
+    QuoteTooLarge,
+
+    #[msg("Quote carries more feeds than the profile allows")]
+    TooManyQuoteFeeds,
+
+    #[msg("Freshness SLA must allow at least one slot")]
+    InvalidSla,
+
+    #[msg("Consumer access list is full")]
+    AccessListTooLarge,
+
+    #[msg("Consuming program is not in the access list")]
+    ConsumerNotAllowed,
+
+    #[msg("Score and salt don't open the stored commitment")]
+    CommitmentMismatch,
+
+    #[msg("Integrator account is required by integrator accounting")]
+    MissingIntegrator,
+
+    #[msg("Integrator account belongs to another consuming program")]
+    IntegratorMismatch,
+
+    #[msg("Integrator's prepaid balance doesn't cover the verification")]
+    IntegratorQuotaExhausted,
+
+    #[msg("Quote carries feeds other than the expected ones")]
+    UnexpectedQuoteFeed,
+
+    #[msg("Slot is past the verification deadline")]
+    DeadlinePassed,
+
+    #[msg("None of the score caches passed has a feed in the quote")]
+    NoCacheInQuote,
+
+    #[msg("No Ed25519 voucher instruction right before the redemption")]
+    MissingBypassVoucher,
+
+    #[msg("Voucher isn't signed by the compliance officer for this address")]
+    InvalidBypassVoucher,
+
+    #[msg("Slot is outside the voucher's window")]
+    BypassVoucherExpired,
+
+    #[msg("Test feed verified with another value than the test score")]
+    HealthCheckValueMismatch,
+
+    #[msg("Program denylist account required")]
+    MissingProgramDenylist,
+
+    #[msg("An instruction of the transaction targets a denied program")]
+    DeniedProgramInTransaction,
+
+    #[msg("Program denylist has too many entries")]
+    ProgramDenylistTooLarge,
+
+    #[msg("Program is already in the program denylist")]
+    ProgramAlreadyFlagged,
+
+    #[msg("Program isn't in the program denylist")]
+    ProgramNotFlagged,
+
+    #[msg("Batch must hold 1 to MAX_BATCH_ADDRESSES query accounts")]
+    InvalidBatchSize,
+
+    #[msg("As-of feed value isn't a Unix time")]
+    InvalidAsOfTime,
+
+    #[msg("Template variables must be at most MAX_TEMPLATE_VARIABLES valid names")]
+    InvalidTemplateVariables,
+
+    #[msg("Feed uses a variable its template doesn't declare")]
+    UndeclaredFeedVariable,
+
+    #[msg("Encoded feed doesn't start with its length")]
+    MalformedFeedEncoding,
+
+    #[msg("Quote account holds no quote yet")]
+    EmptyQuoteAccount,
+
+    #[msg("Risk ceiling account is not the PDA of the consuming program")]
+    InvalidRiskCeiling,
+
+    #[msg("Risk ceilings require the risk ceiling account")]
+    MissingRiskCeiling,
+
+    #[msg("Risk score exceeds the integrator's risk ceiling")]
+    RiskCeilingExceeded,
+
+    #[msg("Emergency threshold expiry must be after the current slot, at most MAX_EMERGENCY_SLOTS ahead")]
+    InvalidEmergencyExpiry,
+
+    #[msg("Threshold ramp must start before it ends, and end after the current slot")]
+    InvalidThresholdRamp,
+
+    #[msg("Address policy account is not the PDA of the screened wallet")]
+    InvalidAddressPolicy,
+
+    #[msg("Address policies require the address policy account")]
+    MissingAddressPolicy,
+
+    #[msg("Address policy denies the screened wallet")]
+    AddressPolicyDenied,
+
+    #[msg("Denylist segment account is not the PDA of the screened wallet's segment")]
+    InvalidDenylistSegment,
+
+    #[msg("Segmented denylist requires the denylist segment account")]
+    MissingDenylistSegment,
+
+    #[msg("Denylist segment is full")]
+    DenylistSegmentFull,
+
+    #[msg("Score decay needs a nonzero interval and penalties on the feed scale")]
+    InvalidScoreDecay,
+
+    #[msg("Score decay requires the score decay account")]
+    MissingScoreDecay,
+
+    #[msg("Feed expiry must be a future slot")]
+    InvalidFeedExpiry,
+
+    #[msg("Registered feed has expired, verify against its newer template")]
+    FeedExpired,
+
+    #[msg("Only the program's upgrade authority can initialize the config")]
+    NotUpgradeAuthority,
+}
+
+// Off-chain tooling names the codes through `risk_oracle_shared::errors::ANCHOR`:
+// append new variants there too.
+const _: () = assert!(
+    risk_oracle_shared::errors::ANCHOR.len() == ErrorCode::NotUpgradeAuthority as usize + 1
+);
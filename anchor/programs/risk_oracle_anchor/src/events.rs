@@ -0,0 +1,318 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{FeedProvenance, Momentum, PolicyVerdict, Severity};
+use crate::verify::FeedValue;
+
+/// A quote was verified for `address`.
+#[event]
+pub struct RiskScoreVerified {
+    /// Screened wallet.
+    pub address: Pubkey,
+    pub feed_id: [u8; 32],
+    /// Verified score, 0–100.
+    pub score: u8,
+    pub quote_slot: u64,
+    /// Program the verification was made for.
+    pub consumer: Pubkey,
+    /// Approved feeds of the quote with their values, `feed_id` included.
+    pub feeds: Vec<FeedValue>,
+    /// Band of `score` under the deployment profile.
+    pub severity: Severity,
+}
+
+/// `verify_risk_score_batch` checked `count` query accounts against one
+/// quote. Bit `i` of the bitmaps is the `i`-th query account.
+#[event]
+pub struct RiskScoreBatchVerified {
+    pub count: u8,
+    pub matched: u64,
+    pub passed: u64,
+    pub quote_slot: u64,
+    pub partial: bool,
+}
+
+/// A score was verified with the time Range computed it at. `as_of` (Unix
+/// seconds) dates the data, `quote_slot` the oracles' attestation of it.
+#[event]
+pub struct ScoreAsOfVerified {
+    pub address: Pubkey,
+    pub score: u8,
+    pub as_of: i64,
+    pub quote_slot: u64,
+}
+
+/// A verified quote was posted to `quote_account`, replacing the previous
+/// one.
+#[event]
+pub struct QuotePosted {
+    pub quote_account: Pubkey,
+    pub owner: Pubkey,
+    pub quote_slot: u64,
+    pub feed_count: u8,
+}
+
+/// A score was verified against the quote posted to `quote_account`.
+#[event]
+pub struct QuoteAccountVerified {
+    pub address: Pubkey,
+    pub score: u8,
+    pub quote_account: Pubkey,
+    pub quote_slot: u64,
+}
+
+/// A composite score was verified and written to the cache.
+#[event]
+pub struct CompositeScoreCached {
+    pub address: Pubkey,
+    pub score: u8,
+    /// `NetworkSet` bits the score covers.
+    pub networks: u8,
+    pub quote_slot: u64,
+    pub momentum: Momentum,
+    /// Oracle signatures backing the quote.
+    pub oracle_samples: u8,
+    /// Band of `score` under the deployment profile.
+    pub severity: Severity,
+    /// Fee payer that submitted the quote.
+    pub updated_by: Pubkey,
+    pub updated_slot: u64,
+}
+
+/// `reconcile` found a cached score more than its tolerance away from the
+/// fresh quote's.
+#[event]
+pub struct CacheDivergence {
+    pub address: Pubkey,
+    pub cached_score: u8,
+    pub fresh_score: u8,
+    pub cached_quote_slot: u64,
+    pub quote_slot: u64,
+    /// Fee payer that wrote the divergent cache.
+    pub cached_by: Pubkey,
+    /// Fee payer that submitted the fresh quote.
+    pub reconciled_by: Pubkey,
+    /// Whether the cache was overwritten with the fresh score.
+    pub corrected: bool,
+}
+
+/// A legacy instruction was run as its replacement, see `aliases`.
+#[event]
+pub struct DeprecatedInstructionUsed {
+    pub legacy: String,
+    pub replacement: String,
+}
+
+/// `health_check` verified the test feed: the verification pipeline works.
+#[event]
+pub struct Heartbeat {
+    /// Keeper that ran the check.
+    pub checked_by: Pubkey,
+    pub slot: u64,
+    pub quote_slot: u64,
+    /// Oracle signatures backing the quote.
+    pub oracle_samples: u8,
+}
+
+/// A confidential score was verified; only its commitment is public.
+#[event]
+pub struct ConfidentialScoreCommitted {
+    pub address: Pubkey,
+    pub commitment: [u8; 32],
+    pub quote_slot: u64,
+}
+
+/// A threshold proof was recorded; the score itself is not part of it.
+#[event]
+pub struct ThresholdProofRecorded {
+    pub address: Pubkey,
+    pub threshold: u8,
+    pub below_threshold: bool,
+    pub commitment: [u8; 32],
+    pub quote_slot: u64,
+}
+
+/// `address` was let through the risk check on a compliance officer's
+/// voucher instead of a quote.
+#[event]
+pub struct RiskCheckBypassed {
+    pub address: Pubkey,
+    /// Officer who signed the voucher.
+    pub officer: Pubkey,
+    pub nonce: u64,
+    /// Program the bypass was redeemed for.
+    pub consumer: Pubkey,
+}
+
+#[event]
+pub struct ComplianceOfficerUpdated {
+    pub old_officer: Pubkey,
+    pub new_officer: Pubkey,
+}
+
+/// The programs allowed to reveal confidential scores were replaced.
+#[event]
+pub struct AccessListUpdated {
+    pub consumers: Vec<Pubkey>,
+}
+
+/// The programs denied in verified transactions changed; `programs` is the
+/// new list.
+#[event]
+pub struct ProgramDenylistUpdated {
+    pub programs: Vec<Pubkey>,
+}
+
+/// The variable names the feeds of template `schema` may use changed;
+/// `names` is the new list.
+#[event]
+pub struct TemplateVariablesUpdated {
+    pub schema: u8,
+    pub names: Vec<String>,
+}
+
+/// The freshness SLA of `query_account` was set or changed.
+#[event]
+pub struct FreshnessSlaSet {
+    pub query_account: Pubkey,
+    pub max_age_slots: u64,
+}
+
+/// The feed id of `query_account` was derived and stored.
+#[event]
+pub struct FeedIdDerived {
+    pub query_account: Pubkey,
+    pub address: Pubkey,
+    pub feed_id: [u8; 32],
+}
+
+/// `consumer` was registered for integrator accounting, or its price changed.
+#[event]
+pub struct IntegratorRegistered {
+    pub consumer: Pubkey,
+    pub price_lamports: u64,
+}
+
+#[event]
+pub struct IntegratorToppedUp {
+    pub consumer: Pubkey,
+    pub lamports: u64,
+    /// Prepaid balance after the top-up.
+    pub balance_lamports: u64,
+}
+
+/// Charges of `consumer` were swept to the treasury.
+#[event]
+pub struct IntegratorFeesCollected {
+    pub consumer: Pubkey,
+    pub lamports: u64,
+}
+
+/// The risk ceiling of `consumer` was set or moved.
+#[event]
+pub struct RiskCeilingUpdated {
+    pub consumer: Pubkey,
+    pub officer: Pubkey,
+    pub max_score: u8,
+}
+
+/// The penalty of aging cached scores was set or replaced.
+#[event]
+pub struct ScoreDecayUpdated {
+    pub grace_slots: u64,
+    pub interval_slots: u64,
+    pub penalty: u8,
+    pub max_penalty: u8,
+}
+
+/// The policy of `address` was set or replaced.
+#[event]
+pub struct AddressPolicyUpdated {
+    pub address: Pubkey,
+    pub verdict: PolicyVerdict,
+    pub threshold: Option<u8>,
+    pub min_oracle_samples: u8,
+}
+
+#[event]
+pub struct AddressPolicyRemoved {
+    pub address: Pubkey,
+}
+
+#[event]
+pub struct FeatureFlagsUpdated {
+    pub old_flags: u64,
+    pub new_flags: u64,
+}
+
+/// Thresholds on the feed scale (0–100).
+#[event]
+pub struct RiskThresholdUpdated {
+    pub old_threshold: u8,
+    pub new_threshold: u8,
+}
+
+/// Gating is clamped to `emergency_threshold` until `expiry_slot` (thresholds
+/// on the feed scale).
+#[event]
+pub struct EmergencyThresholdSet {
+    pub risk_threshold: u8,
+    pub emergency_threshold: u8,
+    pub expiry_slot: u64,
+}
+
+#[event]
+pub struct EmergencyThresholdCleared {}
+
+/// Gating moves from `from` to `to` between `start_slot` and `end_slot`
+/// (thresholds on the feed scale).
+#[event]
+pub struct ThresholdRampScheduled {
+    pub from: u8,
+    pub to: u8,
+    pub start_slot: u64,
+    pub end_slot: u64,
+}
+
+/// `count` wallets were added to the denylist (already listed ones excluded).
+#[event]
+pub struct DenylistImported {
+    pub count: u16,
+}
+
+#[event]
+pub struct DenylistRemoved {
+    pub address: Pubkey,
+}
+
+/// A packed denylist segment was updated; `len` addresses are listed in it.
+#[event]
+pub struct DenylistSegmentUpdated {
+    pub segment: u16,
+    pub added: u16,
+    pub removed: u16,
+    pub len: u16,
+}
+
+#[event]
+pub struct DenylistRootUpdated {
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+}
+
+#[event]
+pub struct FeedRegistered {
+    pub address: Pubkey,
+    pub feed_id: [u8; 32],
+    pub provenance: Option<FeedProvenance>,
+}
+
+#[event]
+pub struct FeedUnregistered {
+    pub feed_id: [u8; 32],
+}
+
+#[event]
+pub struct FeedExpirySet {
+    pub feed_id: [u8; 32],
+    pub expires_at_slot: Option<u64>,
+}
@@ -0,0 +1,73 @@
+//! Risk check of an integrator's instruction through CPI, what
+//! `#[risk_gated]` (crate `risk-oracle-macros`) expands to. Usable directly by
+//! integrators wiring the accounts themselves.
+//!
+//! The quote stays a top-level instruction of the transaction: the oracle
+//! reads it from the Instructions sysvar, and records the integrator as the
+//! consuming program.
+
+use anchor_lang::prelude::*;
+use risk_oracle_shared::threshold::Boundary;
+
+use crate::error::ErrorCode;
+use crate::VerifiedScore;
+
+/// Accounts of `verify_risk_score_feed_compact`, of the optional ones only the
+/// risk ceiling.
+pub struct GateAccounts<'info> {
+    /// This program.
+    pub program: AccountInfo<'info>,
+    pub config: AccountInfo<'info>,
+    pub queue: AccountInfo<'info>,
+    pub slothashes: AccountInfo<'info>,
+    pub instructions: AccountInfo<'info>,
+    /// Screened account.
+    pub query_account: AccountInfo<'info>,
+    /// Risk ceiling PDA of the calling program, the consumer of the
+    /// verification. Read under risk ceilings; empty unless set.
+    pub risk_ceiling: AccountInfo<'info>,
+}
+
+/// Verifies the transaction's quote for `accounts.query_account` and fails
+/// with `RiskThresholdExceeded` when its score is above `threshold` (0–100),
+/// on top of the oracle's own gating (the risk ceiling of the calling program
+/// included): a score equal to `threshold` passes, whatever the config's
+/// boundary. Returns the verified score.
+pub fn require_score_at_most(accounts: GateAccounts<'_>, threshold: u8) -> Result<VerifiedScore> {
+    require_keys_eq!(
+        *accounts.program.key,
+        crate::ID,
+        anchor_lang::error::ErrorCode::InvalidProgramId
+    );
+    let verified = crate::cpi::verify_risk_score_feed_compact(CpiContext::new(
+        accounts.program,
+        crate::cpi::accounts::VerifyRiskScoreFeed {
+            config: accounts.config,
+            queue: accounts.queue,
+            slothashes: accounts.slothashes,
+            instructions: accounts.instructions,
+            query_account: accounts.query_account,
+            payer: None,
+            replay_record: None,
+            treasury: None,
+            system_program: None,
+            registry: None,
+            feed_id_record: None,
+            quote_marker: None,
+            receipt: None,
+            history: None,
+            denylist_entry: None,
+            integrator: None,
+            program_denylist: None,
+            risk_ceiling: Some(accounts.risk_ceiling),
+            address_policy: None,
+            denylist_segment: None,
+        },
+    ))?
+    .get();
+    require!(
+        Boundary::Inclusive.passes(verified.score, threshold),
+        ErrorCode::RiskThresholdExceeded
+    );
+    Ok(verified)
+}
@@ -0,0 +1,55 @@
+//! Accounts that start small and grow with their entries: the
+//! [`FeedRegistry`](crate::state::FeedRegistry) and the
+//! [`ScoreHistory`](crate::state::ScoreHistory).
+//!
+//! Both are created with room for a few entries. When the next entry doesn't
+//! fit, the account is reallocated to twice its entries, capped at the
+//! type's maximum, and the payer of the instruction tops up its rent. Accounts
+//! created at their maximum size before never grow.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Makes room for one more entry in `account`, holding `len` entries of at
+/// most `max`, `space(entries)` bytes for `entries`. A no-op when the entry
+/// fits or `len` is already `max`.
+pub(crate) fn reserve_entry<'info>(
+    account: &AccountInfo<'info>,
+    len: usize,
+    max: usize,
+    space: fn(usize) -> usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if len >= max || account.data_len() >= space(len + 1) {
+        return Ok(());
+    }
+    let capacity = (len * 2).clamp(len + 1, max);
+    resize(account, space(capacity), payer, system_program)
+}
+
+/// Grows `account` to `len` bytes, `payer` topping up its rent exemption.
+pub(crate) fn resize<'info>(
+    account: &AccountInfo<'info>,
+    len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let top_up = Rent::get()?
+        .minimum_balance(len)
+        .saturating_sub(account.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    account.realloc(len, false)?;
+    Ok(())
+}
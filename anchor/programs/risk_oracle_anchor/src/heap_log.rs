@@ -0,0 +1,47 @@
+//! The default bump allocator, counting the bytes it hands out, for the
+//! `heap-log` feature (`risk_oracle_shared::heap`).
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
+
+use anchor_lang::solana_program::entrypoint::{BumpAllocator, HEAP_LENGTH, HEAP_START_ADDRESS};
+
+pub struct HeapTracker {
+    inner: BumpAllocator,
+    used: Cell<usize>,
+}
+
+// SAFETY: programs run on a single thread.
+unsafe impl Sync for HeapTracker {}
+
+impl HeapTracker {
+    /// Bytes allocated so far; the bump allocator never reuses them.
+    pub fn used(&self) -> usize {
+        self.used.get()
+    }
+}
+
+unsafe impl GlobalAlloc for HeapTracker {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            self.used.set(self.used.get() + layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout)
+    }
+}
+
+// Replaces the allocator `entrypoint!` installs without `custom-heap`, at the
+// address and length the runtime maps the heap.
+#[cfg_attr(target_os = "solana", global_allocator)]
+pub static HEAP: HeapTracker = HeapTracker {
+    inner: BumpAllocator {
+        start: HEAP_START_ADDRESS as usize,
+        len: HEAP_LENGTH,
+    },
+    used: Cell::new(0),
+};
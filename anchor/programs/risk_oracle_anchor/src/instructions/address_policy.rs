@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::{diagnostics, feed, seeds};
+
+use crate::error::ErrorCode;
+use crate::events::{AddressPolicyRemoved, AddressPolicyUpdated};
+use crate::state::{AddressPolicy, Config, PolicyVerdict, ADDRESS_POLICY_SEED, CONFIG_SEED};
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct SetAddressPolicy<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AddressPolicy::INIT_SPACE,
+        seeds = [ADDRESS_POLICY_SEED, address.as_ref()],
+        bump
+    )]
+    pub address_policy: Account<'info, AddressPolicy>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct RemoveAddressPolicy<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [ADDRESS_POLICY_SEED, address.as_ref()],
+        bump = address_policy.bump
+    )]
+    pub address_policy: Account<'info, AddressPolicy>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Sets the policy of `address`, or replaces it. `threshold` (0–100) replaces
+/// the config's for the address; `min_oracle_samples` raises the samples its
+/// quotes need, 0 adding nothing.
+pub fn set_address_policy(
+    ctx: Context<SetAddressPolicy>,
+    address: Pubkey,
+    verdict: PolicyVerdict,
+    threshold: Option<u8>,
+    min_oracle_samples: u8,
+) -> Result<()> {
+    require!(
+        matches!(threshold, None | Some(0..=feed::MAX_SCORE)),
+        ErrorCode::InvalidThreshold
+    );
+    let address_policy = &mut ctx.accounts.address_policy;
+    address_policy.address = address;
+    address_policy.verdict = verdict;
+    address_policy.threshold = threshold;
+    address_policy.min_oracle_samples = min_oracle_samples;
+    address_policy.updated_slot = Clock::get()?.slot;
+    address_policy.bump = ctx.bumps.address_policy;
+
+    emit!(AddressPolicyUpdated {
+        address,
+        verdict,
+        threshold,
+        min_oracle_samples,
+    });
+    Ok(())
+}
+
+/// Removes the policy of `address`, which is screened like any other again.
+pub fn remove_address_policy(_ctx: Context<RemoveAddressPolicy>, address: Pubkey) -> Result<()> {
+    emit!(AddressPolicyRemoved { address });
+    Ok(())
+}
+
+/// Reads the policy of `address`. `address_policy` must be the address's
+/// policy PDA; left empty, the address has no policy.
+pub(crate) fn load_address_policy(
+    address_policy: &AccountInfo,
+    address: &Pubkey,
+) -> Result<Option<AddressPolicy>> {
+    let (expected, _) = seeds::find_address_policy_address(
+        Pubkey::find_program_address,
+        &crate::ID,
+        &address.to_bytes(),
+    );
+    require_keys_eq!(
+        address_policy.key(),
+        expected,
+        ErrorCode::InvalidAddressPolicy
+    );
+    if address_policy.owner != &crate::ID {
+        return Ok(None);
+    }
+    let policy = AddressPolicy::try_deserialize(&mut &address_policy.try_borrow_data()?[..])?;
+    Ok(Some(policy))
+}
+
+impl AddressPolicy {
+    /// Fails unless the quote has the oracle samples the policy requires.
+    pub fn require_samples(&self, oracle_samples: u8) -> Result<()> {
+        if oracle_samples < self.min_oracle_samples {
+            msg!(
+                "{}",
+                diagnostics::insufficient_samples(oracle_samples, self.min_oracle_samples)
+            );
+            return Err(ErrorCode::InsufficientOracleSamples.into());
+        }
+        Ok(())
+    }
+
+    /// Whether `score` passes the policy: always when allowed, against the
+    /// policy's threshold when it has one, and the config's gating otherwise.
+    pub fn passes(&self, config: &Config, score: u8) -> bool {
+        match (self.verdict, self.threshold) {
+            (PolicyVerdict::Allow, _) => true,
+            (_, Some(threshold)) => config.within_policy_threshold(score, threshold),
+            (_, None) => config.passes_threshold(score),
+        }
+    }
+}
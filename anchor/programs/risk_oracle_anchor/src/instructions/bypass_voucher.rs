@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use risk_oracle_shared::{constant_time, voucher};
+use switchboard_on_demand::Instructions;
+
+use crate::error::ErrorCode;
+use crate::events::{ComplianceOfficerUpdated, RiskCheckBypassed};
+use crate::state::{
+    BypassRecord, ComplianceOfficer, Config, BYPASS_SEED, COMPLIANCE_OFFICER_SEED, CONFIG_SEED,
+};
+use crate::verify::consumer;
+
+/// A one-time exception to the risk check signed off-chain by the compliance
+/// officer, see `risk_oracle_shared::voucher`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct BypassVoucher {
+    pub address: Pubkey,
+    pub not_before_slot: u64,
+    pub not_after_slot: u64,
+    /// Tells apart the vouchers of one address; each is redeemable once.
+    pub nonce: u64,
+}
+
+impl From<BypassVoucher> for voucher::BypassVoucher {
+    fn from(v: BypassVoucher) -> Self {
+        Self {
+            address: v.address.to_bytes(),
+            not_before_slot: v.not_before_slot,
+            not_after_slot: v.not_after_slot,
+            nonce: v.nonce,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetComplianceOfficer<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ComplianceOfficer::INIT_SPACE,
+        seeds = [COMPLIANCE_OFFICER_SEED],
+        bump
+    )]
+    pub compliance_officer: Account<'info, ComplianceOfficer>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(voucher: BypassVoucher)]
+pub struct RedeemBypassVoucher<'info> {
+    #[account(seeds = [COMPLIANCE_OFFICER_SEED], bump = compliance_officer.bump)]
+    pub compliance_officer: Account<'info, ComplianceOfficer>,
+    pub instructions: Sysvar<'info, Instructions>,
+    // `init` fails on a voucher already redeemed.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + BypassRecord::INIT_SPACE,
+        seeds = [BYPASS_SEED, voucher.address.as_ref(), &voucher.nonce.to_le_bytes()],
+        bump
+    )]
+    pub bypass_record: Account<'info, BypassRecord>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets the key whose vouchers `redeem_bypass_voucher` accepts. Vouchers
+/// signed by the previous officer and not redeemed yet stop working.
+pub fn set_compliance_officer(ctx: Context<SetComplianceOfficer>, officer: Pubkey) -> Result<()> {
+    let compliance_officer = &mut ctx.accounts.compliance_officer;
+    emit!(ComplianceOfficerUpdated {
+        old_officer: compliance_officer.officer,
+        new_officer: officer,
+    });
+    compliance_officer.officer = officer;
+    compliance_officer.updated_slot = Clock::get()?.slot;
+    compliance_officer.bump = ctx.bumps.compliance_officer;
+    Ok(())
+}
+
+/// Manual exception handling: lets `voucher.address` through the risk check
+/// once, on the word of the compliance officer instead of an oracle quote.
+/// The instruction right before this one (the top-level one, when called
+/// through CPI) must be an Ed25519 instruction in which the officer signs
+/// the voucher's message; the runtime has verified that signature by the
+/// time this runs. Succeeds within the voucher's slot window and records the
+/// redemption in a [`BypassRecord`], which also makes it one-time. A consumer
+/// gating on the risk check treats success as a pass.
+pub fn redeem_bypass_voucher(
+    ctx: Context<RedeemBypassVoucher>,
+    voucher: BypassVoucher,
+) -> Result<()> {
+    let instructions = ctx.accounts.instructions.as_ref();
+    let current = load_current_index_checked(instructions)?;
+    let signature_ix = match current.checked_sub(1) {
+        Some(index) => Some(load_instruction_at_checked(index as usize, instructions)?),
+        None => None,
+    }
+    .filter(|ix| ix.program_id == ed25519_program::ID)
+    .ok_or(ErrorCode::MissingBypassVoucher)?;
+
+    let (signer, message) =
+        voucher::signed_message(&signature_ix.data).map_err(|_| ErrorCode::InvalidBypassVoucher)?;
+    let signed = voucher::BypassVoucher::from(voucher);
+    let officer = ctx.accounts.compliance_officer.officer;
+    require!(
+        constant_time::eq_32(signer, &officer.to_bytes())
+            && message == signed.message(&ctx.program_id.to_bytes()),
+        ErrorCode::InvalidBypassVoucher
+    );
+
+    let slot = Clock::get()?.slot;
+    require!(signed.is_valid_at(slot), ErrorCode::BypassVoucherExpired);
+
+    let record = &mut ctx.accounts.bypass_record;
+    record.address = voucher.address;
+    record.officer = officer;
+    record.nonce = voucher.nonce;
+    record.not_before_slot = voucher.not_before_slot;
+    record.not_after_slot = voucher.not_after_slot;
+    record.redeemed_slot = slot;
+    record.consumer = consumer(instructions)?;
+    record.bump = ctx.bumps.bypass_record;
+
+    emit!(RiskCheckBypassed {
+        address: record.address,
+        officer,
+        nonce: record.nonce,
+        consumer: record.consumer,
+    });
+    Ok(())
+}
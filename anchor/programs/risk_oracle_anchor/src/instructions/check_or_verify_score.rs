@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::feed::NetworkSet;
+use risk_oracle_shared::profiles;
+use switchboard_on_demand::{Instructions, QueueAccountData, SlotHashes};
+
+use crate::error::ErrorCode;
+use crate::instructions::risk_ceiling::require_within_consumer_ceiling;
+use crate::instructions::verify_composite_risk_score::{refresh_cache, CacheAccounts};
+use crate::state::{
+    feature_flags, Config, FreshnessSla, Momentum, ScoreCache, ScoreDecay, ScoreHistory,
+    CACHE_SEED, CONFIG_SEED, HISTORY_SEED, INITIAL_HISTORY_ENTRIES, SCORE_DECAY_SEED, SLA_SEED,
+};
+use crate::verify::resolve_screened_address;
+
+#[derive(Accounts)]
+pub struct CheckOrVerifyScore<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(address = Pubkey::new_from_array(profiles::ACTIVE.queue))]
+    pub queue: AccountLoader<'info, QueueAccountData>,
+    pub slothashes: Sysvar<'info, SlotHashes>,
+    pub instructions: Sysvar<'info, Instructions>,
+    /// CHECK: Classified in `resolve_screened_address`
+    pub query_account: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ScoreCache::INIT_SPACE,
+        seeds = [CACHE_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub cache: Account<'info, ScoreCache>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// When passed, a refreshed score is also appended to the history.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ScoreHistory::space(INITIAL_HISTORY_ENTRIES),
+        seeds = [HISTORY_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub history: Option<Account<'info, ScoreHistory>>,
+    /// When passed, a refresh is recorded against the freshness SLA.
+    #[account(mut, seeds = [SLA_SEED, query_account.key().as_ref()], bump = freshness_sla.bump)]
+    pub freshness_sla: Option<Account<'info, FreshnessSla>>,
+    /// Required by score decay.
+    #[account(seeds = [SCORE_DECAY_SEED], bump = score_decay.bump)]
+    pub score_decay: Option<Account<'info, ScoreDecay>>,
+    /// CHECK: Risk ceiling PDA of the consuming program, checked in
+    /// `require_within_ceiling`. Required by risk ceilings; empty unless set.
+    pub risk_ceiling: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> CheckOrVerifyScore<'info> {
+    fn cache_accounts(&mut self, bumps: &CheckOrVerifyScoreBumps) -> CacheAccounts<'_, 'info> {
+        CacheAccounts {
+            derivation: self.config.feed_id_derivation(),
+            queue: &self.queue,
+            slothashes: &self.slothashes,
+            instructions: &self.instructions,
+            cache: (&mut self.cache, bumps.cache),
+            history: self.history.as_mut().zip(bumps.history),
+            freshness_sla: self.freshness_sla.as_mut(),
+            payer: &self.payer,
+            system_program: &self.system_program,
+        }
+    }
+}
+
+/// Score checked by `check_or_verify_score`, returned as return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ScoreCheck {
+    /// Score gated, the decay penalty included.
+    pub score: u8,
+    pub momentum: Momentum,
+    pub quote_slot: u64,
+    /// Whether the score came from a fresh cache, without verifying a quote.
+    pub from_cache: bool,
+    /// Added to the cached score for its age, 0 for a score just verified.
+    pub decay_penalty: u8,
+}
+
+/// One instruction whatever the cache state. When the cache of the query
+/// account holds the composite score of `networks` for the screened wallet,
+/// from a quote at most `max_age_slots` old, it is used as is and no quote is
+/// needed. `max_age_slots` only tightens the profile's `max_cache_age_slots`
+/// and, when passed, the freshness SLA of the query account. Otherwise the attached quote of the composite feed is verified and
+/// the cache refreshed, as `verify_composite_risk_score`. Either way the
+/// score then goes through threshold gating and the consumer's risk ceiling;
+/// with score decay enabled, a cached score gates with the penalty of its age
+/// added.
+pub fn check_or_verify_score(
+    ctx: Context<CheckOrVerifyScore>,
+    networks: u8,
+    max_age_slots: u64,
+) -> Result<ScoreCheck> {
+    let networks = NetworkSet::from_bits(networks).ok_or(ErrorCode::InvalidNetworks)?;
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+
+    let decay = if ctx.accounts.config.is_enabled(feature_flags::SCORE_DECAY) {
+        let score_decay = ctx
+            .accounts
+            .score_decay
+            .as_ref()
+            .ok_or(ErrorCode::MissingScoreDecay)?;
+        Some(score_decay.model())
+    } else {
+        None
+    };
+
+    let mut max_age_slots = max_age_slots.min(profiles::ACTIVE.max_cache_age_slots);
+    if let Some(freshness_sla) = &ctx.accounts.freshness_sla {
+        max_age_slots = max_age_slots.min(freshness_sla.max_age_slots);
+    }
+
+    let slot = Clock::get()?.slot;
+    let cache = &ctx.accounts.cache;
+    let age_slots = slot.saturating_sub(cache.quote_slot);
+    let from_cache = cache.address == screened_address
+        && cache.networks == networks.bits()
+        && cache.quote_slot != 0
+        && age_slots <= max_age_slots;
+
+    let decay_penalty = if from_cache {
+        msg!(
+            "Cached risk score {} is {} slots old",
+            cache.score,
+            age_slots
+        );
+        decay.map_or(0, |decay| decay.apply(cache.score, age_slots) - cache.score)
+    } else {
+        refresh_cache(ctx.accounts.cache_accounts(&ctx.bumps), screened_address, networks)?;
+        0
+    };
+
+    let cache = &ctx.accounts.cache;
+    let score = cache.score + decay_penalty;
+    if decay_penalty != 0 {
+        msg!("Decayed to {} (+{})", score, decay_penalty);
+    }
+    require!(
+        ctx.accounts.config.passes_threshold(score),
+        ErrorCode::RiskThresholdExceeded
+    );
+    require_within_consumer_ceiling(
+        &ctx.accounts.config,
+        Some(ctx.accounts.instructions.as_ref()),
+        ctx.accounts.risk_ceiling.as_deref(),
+        score,
+    )?;
+    Ok(ScoreCheck {
+        score,
+        momentum: cache.momentum,
+        quote_slot: cache.quote_slot,
+        from_cache,
+        decay_penalty,
+    })
+}
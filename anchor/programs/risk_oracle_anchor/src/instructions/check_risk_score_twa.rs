@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::Instructions;
+
+use crate::error::ErrorCode;
+use crate::instructions::risk_ceiling::require_within_consumer_ceiling;
+use crate::state::{Config, ScoreHistory, CONFIG_SEED, HISTORY_SEED};
+
+#[derive(Accounts)]
+pub struct CheckRiskScoreTwa<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: Only used as the history seed
+    pub query_account: UncheckedAccount<'info>,
+    #[account(seeds = [HISTORY_SEED, query_account.key().as_ref()], bump = history.bump)]
+    pub history: Account<'info, ScoreHistory>,
+    /// Required by risk ceilings, to tell the consuming program.
+    pub instructions: Option<Sysvar<'info, Instructions>>,
+    /// CHECK: Risk ceiling PDA of the consuming program, checked in
+    /// `require_within_ceiling`. Required by risk ceilings; empty unless set.
+    pub risk_ceiling: Option<UncheckedAccount<'info>>,
+}
+
+/// Gates on the time-weighted average of the recorded scores over the last
+/// `window_slots` slots instead of the instantaneous score, smoothing out
+/// transient provider noise. Fails unless the average passes
+/// `Config::risk_threshold` and the consumer's risk ceiling, returns it
+/// otherwise.
+pub fn check_risk_score_twa(ctx: Context<CheckRiskScoreTwa>, window_slots: u64) -> Result<u8> {
+    let twa = ctx
+        .accounts
+        .history
+        .time_weighted_average(Clock::get()?.slot, window_slots)
+        .ok_or(ErrorCode::NoHistoryInWindow)?;
+
+    msg!("Risk score TWA over {} slots: {}", window_slots, twa);
+    require!(
+        ctx.accounts.config.within_threshold(twa),
+        ErrorCode::RiskThresholdExceeded
+    );
+    require_within_consumer_ceiling(
+        &ctx.accounts.config,
+        ctx.accounts
+            .instructions
+            .as_ref()
+            .map(|instructions| instructions.as_ref()),
+        ctx.accounts.risk_ceiling.as_deref(),
+        twa,
+    )?;
+    Ok(twa)
+}
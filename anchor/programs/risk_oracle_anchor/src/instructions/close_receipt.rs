@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::state::{VerificationReceipt, RECEIPT_SEED};
+
+#[derive(Accounts)]
+pub struct CloseReceipt<'info> {
+    /// CHECK: Only used as the receipt seed
+    pub query_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [RECEIPT_SEED, query_account.key().as_ref()],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, VerificationReceipt>,
+    /// CHECK: Only receives the rent, pinned to the receipt's payer
+    #[account(mut, address = receipt.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+}
+
+/// Closes an expired receipt. Permissionless: the rent always goes back to
+/// whoever paid for it.
+pub fn close_receipt(ctx: Context<CloseReceipt>) -> Result<()> {
+    require!(
+        !ctx.accounts.receipt.is_valid_at(Clock::get()?.slot),
+        ErrorCode::ReceiptNotExpired
+    );
+    Ok(())
+}
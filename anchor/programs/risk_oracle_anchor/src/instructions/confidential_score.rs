@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::constant_time;
+use risk_oracle_shared::feed::{self, NetworkSet};
+use risk_oracle_shared::profiles;
+use switchboard_on_demand::{Instructions, QueueAccountData, SlotHashes};
+
+use crate::error::ErrorCode;
+use crate::events::{AccessListUpdated, ConfidentialScoreCommitted};
+use crate::instructions::risk_ceiling::require_within_consumer_ceiling;
+use crate::state::{
+    Config, ConfidentialScore, ConsumerAccessList, ACCESS_LIST_SEED, CONFIDENTIAL_SEED,
+    CONFIG_SEED, MAX_ACCESS_LIST_ENTRIES,
+};
+use crate::verify::{
+    consumer, feed_id_with, require_expected_feeds, resolve_screened_address, score_commitment,
+    score_from_value, verify_quote,
+};
+
+#[derive(Accounts)]
+pub struct SetAccessList<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ConsumerAccessList::INIT_SPACE,
+        seeds = [ACCESS_LIST_SEED],
+        bump
+    )]
+    pub access_list: Account<'info, ConsumerAccessList>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyConfidentialRiskScore<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(address = Pubkey::new_from_array(profiles::ACTIVE.queue))]
+    pub queue: AccountLoader<'info, QueueAccountData>,
+    pub slothashes: Sysvar<'info, SlotHashes>,
+    pub instructions: Sysvar<'info, Instructions>,
+    /// CHECK: Classified in `resolve_screened_address`
+    pub query_account: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ConfidentialScore::INIT_SPACE,
+        seeds = [CONFIDENTIAL_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub confidential_score: Account<'info, ConfidentialScore>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Risk ceiling PDA of the consuming program, checked in
+    /// `require_within_ceiling`. Required by risk ceilings; empty unless set.
+    pub risk_ceiling: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct RevealScore<'info> {
+    #[account(seeds = [ACCESS_LIST_SEED], bump = access_list.bump)]
+    pub access_list: Account<'info, ConsumerAccessList>,
+    pub instructions: Sysvar<'info, Instructions>,
+    /// CHECK: Only used as the confidential score seed
+    pub query_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [CONFIDENTIAL_SEED, query_account.key().as_ref()],
+        bump = confidential_score.bump
+    )]
+    pub confidential_score: Account<'info, ConfidentialScore>,
+}
+
+/// Replaces the programs allowed to reveal confidential scores.
+pub fn set_access_list(ctx: Context<SetAccessList>, consumers: Vec<Pubkey>) -> Result<()> {
+    require!(
+        consumers.len() <= MAX_ACCESS_LIST_ENTRIES,
+        ErrorCode::AccessListTooLarge
+    );
+
+    let access_list = &mut ctx.accounts.access_list;
+    access_list.consumers = consumers;
+    access_list.bump = ctx.bumps.access_list;
+    emit!(AccessListUpdated {
+        consumers: access_list.consumers.clone(),
+    });
+    Ok(())
+}
+
+/// Confidential mode: verifies the composite feed like
+/// `verify_composite_risk_score`, but stores and emits only a salted
+/// commitment to the score. Threshold gating and the consumer's risk ceiling
+/// still apply, on the score itself, so gating works without the score ever
+/// being written in clear.
+pub fn verify_confidential_risk_score(
+    ctx: Context<VerifyConfidentialRiskScore>,
+    networks: u8,
+    salt: [u8; 32],
+) -> Result<()> {
+    let networks = NetworkSet::from_bits(networks).ok_or(ErrorCode::InvalidNetworks)?;
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+
+    let derived_feed_id = feed_id_with(
+        ctx.accounts.config.feed_id_derivation(),
+        &feed::composite_risk_score_feed(&screened_address.to_bytes(), networks),
+    );
+    let verified = verify_quote(
+        ctx.accounts.queue.as_ref(),
+        ctx.accounts.slothashes.as_ref(),
+        ctx.accounts.instructions.as_ref(),
+        &derived_feed_id,
+    )?;
+
+    let config = &ctx.accounts.config;
+    require_expected_feeds(config, &verified.feeds, &[derived_feed_id])?;
+    let score = score_from_value(verified.value);
+    require!(config.passes_threshold(score), ErrorCode::RiskThresholdExceeded);
+    require_within_consumer_ceiling(
+        config,
+        Some(ctx.accounts.instructions.as_ref()),
+        ctx.accounts.risk_ceiling.as_deref(),
+        score,
+    )?;
+
+    let record = &mut ctx.accounts.confidential_score;
+    record.address = screened_address;
+    record.commitment = score_commitment(&screened_address, score, &salt);
+    record.quote_slot = verified.quote_slot;
+    record.updated_slot = Clock::get()?.slot;
+    record.bump = ctx.bumps.confidential_score;
+
+    emit!(ConfidentialScoreCommitted {
+        address: screened_address,
+        commitment: record.commitment,
+        quote_slot: record.quote_slot,
+    });
+    Ok(())
+}
+
+/// Returns `score` when it opens the confidential score commitment of
+/// `query_account` with `salt`, for a consuming program (see
+/// `verify::consumer`) in the access list. The consumer gets the score and
+/// salt off-chain from the operator; this proves they are the verified ones.
+pub fn reveal_score(ctx: Context<RevealScore>, score: u8, salt: [u8; 32]) -> Result<u8> {
+    let consumer = consumer(ctx.accounts.instructions.as_ref())?;
+    require!(
+        ctx.accounts.access_list.allows(&consumer),
+        ErrorCode::ConsumerNotAllowed
+    );
+
+    let record = &ctx.accounts.confidential_score;
+    require!(
+        constant_time::eq_32(
+            &score_commitment(&record.address, score, &salt),
+            &record.commitment
+        ),
+        ErrorCode::CommitmentMismatch
+    );
+    Ok(score)
+}
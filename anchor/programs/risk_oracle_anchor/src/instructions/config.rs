@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::feed;
+
+use crate::error::ErrorCode;
+use crate::events::{
+    EmergencyThresholdCleared, EmergencyThresholdSet, FeatureFlagsUpdated, RiskThresholdUpdated,
+    ThresholdRampScheduled,
+};
+use crate::growth;
+use crate::program::AnchorOracleExample;
+use crate::state::{feature_flags, Config, CONFIG_SEED, CONFIG_V1_LEN, MAX_EMERGENCY_SLOTS};
+
+/// A risk threshold tagged with its unit, converted to the feed scale
+/// on-chain with the factor of the feed template. Comparing a provider-native
+/// 7 against a 0–100 score is not expressible.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub enum RiskThreshold {
+    /// Provider-native scale, 0–10.
+    Native(u8),
+    /// Feed scale, 0–100.
+    Score(u8),
+}
+
+impl RiskThreshold {
+    /// The threshold on the feed scale.
+    pub fn to_score(self) -> Result<u8> {
+        match self {
+            Self::Native(native) => feed::native_to_score(native),
+            Self::Score(score) => (score <= feed::MAX_SCORE).then_some(score),
+        }
+        .ok_or_else(|| ErrorCode::InvalidThreshold.into())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    pub treasury: Pubkey,
+    pub flags: u64,
+    pub risk_threshold: RiskThreshold,
+    pub fee_lamports: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    /// The program's upgrade authority: whoever deploys the program creates
+    /// its config, nobody can front-run the deployment with their own.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, AnchorOracleExample>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ ErrorCode::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    pub authority: Signer<'info>,
+}
+
+/// Creates the config, signed by the program's upgrade authority, which
+/// becomes its `authority`.
+pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+    let risk_threshold = params.risk_threshold.to_score()?;
+    require!(
+        params.flags & !feature_flags::ALL == 0,
+        ErrorCode::UnknownFeatureFlag
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.authority = ctx.accounts.authority.key();
+    config.treasury = params.treasury;
+    config.flags = params.flags;
+    config.risk_threshold = risk_threshold;
+    config.fee_lamports = params.fee_lamports;
+    config.bump = ctx.bumps.config;
+    Ok(())
+}
+
+/// Replaces the enabled feature flags. Takes effect on the next verification.
+pub fn set_feature_flags(ctx: Context<UpdateConfig>, flags: u64) -> Result<()> {
+    require!(flags & !feature_flags::ALL == 0, ErrorCode::UnknownFeatureFlag);
+
+    let config = &mut ctx.accounts.config;
+    emit!(FeatureFlagsUpdated {
+        old_flags: config.flags,
+        new_flags: flags,
+    });
+    config.flags = flags;
+    Ok(())
+}
+
+/// Replaces the threshold used by threshold gating, cancelling any scheduled
+/// ramp.
+pub fn set_risk_threshold(ctx: Context<UpdateConfig>, threshold: RiskThreshold) -> Result<()> {
+    let risk_threshold = threshold.to_score()?;
+
+    let config = &mut ctx.accounts.config;
+    emit!(RiskThresholdUpdated {
+        old_threshold: config.risk_threshold,
+        new_threshold: risk_threshold,
+    });
+    config.risk_threshold = risk_threshold;
+    config.ramp_end_slot = 0;
+    Ok(())
+}
+
+/// Phases `threshold` in linearly between `start_slot` and `end_slot`,
+/// starting from the threshold scheduled at the current slot, so a tighter
+/// policy doesn't block every user between the two thresholds at once.
+/// Replaces any scheduled ramp; the emergency threshold still applies on
+/// top.
+pub fn schedule_threshold_ramp(
+    ctx: Context<UpdateConfig>,
+    threshold: RiskThreshold,
+    start_slot: u64,
+    end_slot: u64,
+) -> Result<()> {
+    let risk_threshold = threshold.to_score()?;
+    let slot = Clock::get()?.slot;
+    require!(
+        start_slot < end_slot && end_slot > slot,
+        ErrorCode::InvalidThresholdRamp
+    );
+
+    let config = &mut ctx.accounts.config;
+    let from = config.scheduled_threshold_at(slot);
+    config.ramp_from = from;
+    config.ramp_start_slot = start_slot;
+    config.ramp_end_slot = end_slot;
+    config.risk_threshold = risk_threshold;
+    emit!(ThresholdRampScheduled {
+        from,
+        to: risk_threshold,
+        start_slot,
+        end_slot,
+    });
+    Ok(())
+}
+
+/// Clamps gating down to `threshold` until `expiry_slot`, for every consumer
+/// and whatever the feature flags, without touching `risk_threshold`: meant
+/// for active exploits. Only lowers the threshold in force; the expiry must be
+/// after the current slot and at most `MAX_EMERGENCY_SLOTS` ahead. Setting it
+/// again replaces it.
+pub fn set_emergency_threshold(
+    ctx: Context<UpdateConfig>,
+    threshold: RiskThreshold,
+    expiry_slot: u64,
+) -> Result<()> {
+    let emergency_threshold = threshold.to_score()?;
+    let slot = Clock::get()?.slot;
+    require!(
+        expiry_slot > slot && expiry_slot - slot <= MAX_EMERGENCY_SLOTS,
+        ErrorCode::InvalidEmergencyExpiry
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.emergency_threshold = emergency_threshold;
+    config.emergency_expiry_slot = expiry_slot;
+    msg!(
+        "Emergency threshold {} until slot {}",
+        emergency_threshold,
+        expiry_slot
+    );
+    emit!(EmergencyThresholdSet {
+        risk_threshold: config.risk_threshold,
+        emergency_threshold,
+        expiry_slot,
+    });
+    Ok(())
+}
+
+/// Lifts the emergency threshold before it expires.
+pub fn clear_emergency_threshold(ctx: Context<UpdateConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.emergency_threshold = 0;
+    config.emergency_expiry_slot = 0;
+    emit!(EmergencyThresholdCleared {});
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExtendConfig<'info> {
+    /// CHECK: Config of the previous layout, which `Account` can't read. Owner
+    /// and authority are checked in `extend_config`.
+    #[account(mut, seeds = [CONFIG_SEED], bump)]
+    pub config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a config created before the emergency threshold to the current
+/// layout, the authority topping up its rent. The new fields read as no
+/// emergency threshold. A no-op on a current config.
+pub fn extend_config(ctx: Context<ExtendConfig>) -> Result<()> {
+    let config = ctx.accounts.config.to_account_info();
+    require_keys_eq!(
+        *config.owner,
+        crate::ID,
+        anchor_lang::error::ErrorCode::ConstraintOwner
+    );
+    let len = config.data_len();
+    require!(
+        len >= CONFIG_V1_LEN,
+        anchor_lang::error::ErrorCode::AccountDidNotDeserialize
+    );
+    {
+        let data = config.try_borrow_data()?;
+        require!(
+            data[..8] == *Config::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        // The authority follows the discriminator in both layouts.
+        require!(
+            data[8..40] == ctx.accounts.authority.key().to_bytes(),
+            anchor_lang::error::ErrorCode::ConstraintHasOne
+        );
+    }
+    if len >= 8 + Config::INIT_SPACE {
+        return Ok(());
+    }
+
+    growth::resize(
+        &config,
+        8 + Config::INIT_SPACE,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    msg!("Config extended from {} bytes", len);
+    Ok(())
+}
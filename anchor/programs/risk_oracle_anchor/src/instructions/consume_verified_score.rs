@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use risk_oracle_shared::constant_time;
+use switchboard_on_demand::Instructions;
+
+use crate::error::ErrorCode;
+use crate::state::{QuoteMarker, MARKER_SEED};
+use crate::verify::{consumer, quote_hash};
+
+#[derive(Accounts)]
+pub struct ConsumeVerifiedScore<'info> {
+    pub instructions: Sysvar<'info, Instructions>,
+    /// CHECK: Only used as the marker seed
+    pub query_account: UncheckedAccount<'info>,
+    #[account(seeds = [MARKER_SEED, query_account.key().as_ref()], bump = quote_marker.bump)]
+    pub quote_marker: Account<'info, QuoteMarker>,
+}
+
+/// Returns the score recorded by an earlier verify instruction of the same
+/// transaction, without running the quote verifier again.
+///
+/// The marker is only accepted when it was written in the current slot for the
+/// quote instruction of this transaction, so the quote it vouches for is part
+/// of the transaction; by an earlier instruction of this transaction (see
+/// [`written_earlier`]), so a marker left by another transaction of the slot
+/// carrying the same quote isn't picked up; and for the same consuming
+/// program, so a verification made through program A can't be picked up by
+/// program B.
+pub fn consume_verified_score(ctx: Context<ConsumeVerifiedScore>) -> Result<u8> {
+    let marker = &ctx.accounts.quote_marker;
+    let instructions = ctx.accounts.instructions.as_ref();
+    require!(
+        marker.slot == Clock::get()?.slot
+            && constant_time::eq_32(&marker.quote_hash, &quote_hash(instructions)?)
+            && written_earlier(instructions, &ctx.accounts.quote_marker)?,
+        ErrorCode::StaleQuoteMarker
+    );
+    require_keys_eq!(
+        marker.consumer,
+        consumer(instructions)?,
+        ErrorCode::ConsumerMismatch
+    );
+
+    msg!("Reusing verified risk score {} of {}", marker.score, marker.address);
+    Ok(marker.score)
+}
+
+/// Whether a top-level instruction before the current one, of the program the
+/// marker was written for, takes the marker as writable: the verify
+/// instruction that wrote it in this transaction, called directly or through
+/// that program. Instructions of this program only accept the marker
+/// writable in the verify instructions, which always write it.
+fn written_earlier(instructions: &AccountInfo, marker: &Account<QuoteMarker>) -> Result<bool> {
+    let current = load_current_index_checked(instructions)?;
+    for index in 0..current {
+        let instruction = load_instruction_at_checked(index as usize, instructions)?;
+        if instruction.program_id == marker.consumer
+            && instruction
+                .accounts
+                .iter()
+                .any(|meta| meta.pubkey == marker.key() && meta.is_writable)
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::error::ErrorCode;
+use crate::events::{DenylistImported, DenylistRemoved};
+use crate::state::{Config, DenylistEntry, CONFIG_SEED, DENYLIST_SEED, MAX_DENYLIST_BATCH};
+
+#[derive(Accounts)]
+pub struct ImportDenylist<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: the denylist entry PDA of each address, in order.
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct RemoveFromDenylist<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [DENYLIST_SEED, address.as_ref()],
+        bump = denylist_entry.bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Emergency bulk import: denylists `addresses` directly, without oracle
+/// quotes, for sanctions updates that can't wait for per-address cranking.
+/// Addresses already denylisted are skipped.
+pub fn import_denylist<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ImportDenylist<'info>>,
+    addresses: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        !addresses.is_empty()
+            && addresses.len() <= MAX_DENYLIST_BATCH
+            && addresses.len() == ctx.remaining_accounts.len(),
+        ErrorCode::InvalidDenylistBatch
+    );
+
+    let space = 8 + DenylistEntry::INIT_SPACE;
+    let lamports = Rent::get()?.minimum_balance(space);
+    let slot = Clock::get()?.slot;
+    let mut count: u16 = 0;
+
+    for (address, entry_info) in addresses.iter().zip(ctx.remaining_accounts) {
+        let (expected, bump) =
+            Pubkey::find_program_address(&[DENYLIST_SEED, address.as_ref()], ctx.program_id);
+        require_keys_eq!(entry_info.key(), expected, ErrorCode::InvalidDenylistEntry);
+        if entry_info.owner == ctx.program_id {
+            continue;
+        }
+
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: entry_info.clone(),
+                },
+                &[&[DENYLIST_SEED, address.as_ref(), &[bump]]],
+            ),
+            lamports,
+            space as u64,
+            ctx.program_id,
+        )?;
+
+        let entry = DenylistEntry {
+            address: *address,
+            added_slot: slot,
+            bump,
+        };
+        entry.try_serialize(&mut &mut entry_info.try_borrow_mut_data()?[..])?;
+        count += 1;
+    }
+
+    emit!(DenylistImported { count });
+    Ok(())
+}
+
+pub fn remove_from_denylist(_ctx: Context<RemoveFromDenylist>, address: Pubkey) -> Result<()> {
+    emit!(DenylistRemoved { address });
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use risk_oracle_shared::denylist_segment::{self, MAX_SEGMENT_ENTRIES, SEGMENT_COUNT};
+use risk_oracle_shared::seeds;
+
+use crate::error::ErrorCode;
+use crate::events::DenylistSegmentUpdated;
+use crate::growth;
+use crate::state::{
+    Config, DenylistSegment, CONFIG_SEED, DENYLIST_SEGMENT_SEED, MAX_DENYLIST_BATCH,
+};
+
+#[derive(Accounts)]
+#[instruction(segment: u16)]
+pub struct UpdateDenylistSegment<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = DenylistSegment::space(0),
+        seeds = [DENYLIST_SEGMENT_SEED, &segment.to_be_bytes()],
+        bump
+    )]
+    pub denylist_segment: Account<'info, DenylistSegment>,
+    /// Pays for the segment's growth.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Segment of the packed denylist `address` is listed in.
+pub fn segment_of(address: &Pubkey) -> u16 {
+    denylist_segment::segment_of(|data| hashv(data).to_bytes(), &address.to_bytes())
+}
+
+/// Lists `add` and unlists `remove` in one segment of the packed denylist,
+/// creating it on first use. Every address must belong to the segment.
+/// Addresses already listed (or not listed, for `remove`) are skipped. The
+/// segment only grows: unlisting leaves its size, and rent, as it was.
+pub fn update_denylist_segment(
+    ctx: Context<UpdateDenylistSegment>,
+    segment: u16,
+    add: Vec<Pubkey>,
+    remove: Vec<Pubkey>,
+) -> Result<()> {
+    let batch = add.len() + remove.len();
+    let in_segment = add
+        .iter()
+        .chain(&remove)
+        .all(|address| segment_of(address) == segment);
+    require!(
+        segment < SEGMENT_COUNT && batch > 0 && batch <= MAX_DENYLIST_BATCH && in_segment,
+        ErrorCode::InvalidDenylistBatch
+    );
+
+    let account = &mut ctx.accounts.denylist_segment;
+    account.segment = segment;
+    account.bump = ctx.bumps.denylist_segment;
+
+    // Sorted in place: a full segment and a copy would not fit in the heap.
+    let addresses = &mut account.addresses;
+    addresses.reserve_exact(add.len());
+    let mut added: u16 = 0;
+    for address in add {
+        if denylist_segment::insert(addresses, address) {
+            added += 1;
+        }
+    }
+    let mut removed: u16 = 0;
+    for address in &remove {
+        if denylist_segment::remove(addresses, address) {
+            removed += 1;
+        }
+    }
+    let len = addresses.len();
+    require!(len <= MAX_SEGMENT_ENTRIES, ErrorCode::DenylistSegmentFull);
+
+    let space = DenylistSegment::space(len);
+    if account.to_account_info().data_len() < space {
+        growth::resize(
+            &account.to_account_info(),
+            space,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
+
+    emit!(DenylistSegmentUpdated {
+        segment,
+        added,
+        removed,
+        len: len as u16,
+    });
+    Ok(())
+}
+
+/// Fails when `address` is listed in its segment. `denylist_segment` must be
+/// the PDA of the address's segment; left empty, the segment lists nothing.
+pub(crate) fn require_not_in_segment(
+    denylist_segment: &AccountInfo,
+    address: &Pubkey,
+) -> Result<()> {
+    let (expected, _) = seeds::find_denylist_segment_address(
+        Pubkey::find_program_address,
+        &crate::ID,
+        segment_of(address),
+    );
+    require_keys_eq!(
+        denylist_segment.key(),
+        expected,
+        ErrorCode::InvalidDenylistSegment
+    );
+    if denylist_segment.owner != &crate::ID {
+        return Ok(());
+    }
+    let data = denylist_segment.try_borrow_data()?;
+    require!(
+        !DenylistSegment::data_contains(&data, address),
+        ErrorCode::AddressDenylisted
+    );
+    Ok(())
+}
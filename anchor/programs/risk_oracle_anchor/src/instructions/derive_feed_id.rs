@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::feed;
+
+use crate::events::FeedIdDerived;
+use crate::state::{Config, FeedIdRecord, CONFIG_SEED, FEED_ID_SEED};
+use crate::verify::{feed_id_with, resolve_screened_address};
+
+#[derive(Accounts)]
+pub struct DeriveFeedId<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: Classified in `resolve_screened_address` before its pubkey is used to build the feed id
+    pub query_account: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + FeedIdRecord::INIT_SPACE,
+        seeds = [FEED_ID_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub feed_id_record: Account<'info, FeedIdRecord>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Derives the risk score feed id of `query_account` on-chain and stores it.
+/// Permissionless: the id is computed by the program, not supplied. Calling it
+/// again refreshes the record (e.g. after a token account changed owner, or
+/// `FEED_REQUEST_V2_IDS` changed the config's derivation).
+pub fn derive_feed_id(ctx: Context<DeriveFeedId>) -> Result<()> {
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+    let derived_feed_id = feed_id_with(
+        ctx.accounts.config.feed_id_derivation(),
+        &feed::risk_score_feed(&screened_address.to_bytes()),
+    );
+
+    let record = &mut ctx.accounts.feed_id_record;
+    record.feed_id = derived_feed_id;
+    record.address = screened_address;
+    record.derived_slot = Clock::get()?.slot;
+    record.bump = ctx.bumps.feed_id_record;
+
+    emit!(FeedIdDerived {
+        query_account: ctx.accounts.query_account.key(),
+        address: screened_address,
+        feed_id: record.feed_id,
+    });
+    Ok(())
+}
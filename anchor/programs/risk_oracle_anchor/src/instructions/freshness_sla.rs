@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::events::FreshnessSlaSet;
+use crate::state::{Config, FreshnessSla, CONFIG_SEED, SLA_SEED};
+
+#[derive(Accounts)]
+pub struct SetFreshnessSla<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    /// CHECK: Only its key seeds the SLA PDA
+    pub query_account: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FreshnessSla::INIT_SPACE,
+        seeds = [SLA_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub freshness_sla: Account<'info, FreshnessSla>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct IsWithinSla<'info> {
+    /// CHECK: Only its key seeds the SLA PDA
+    pub query_account: UncheckedAccount<'info>,
+    #[account(seeds = [SLA_SEED, query_account.key().as_ref()], bump = freshness_sla.bump)]
+    pub freshness_sla: Account<'info, FreshnessSla>,
+}
+
+/// Sets the freshness SLA of `query_account`, creating its account on first
+/// use. The recorded refresh slot is kept across updates.
+pub fn set_freshness_sla(ctx: Context<SetFreshnessSla>, max_age_slots: u64) -> Result<()> {
+    require!(max_age_slots > 0, ErrorCode::InvalidSla);
+
+    let sla = &mut ctx.accounts.freshness_sla;
+    sla.query_account = ctx.accounts.query_account.key();
+    sla.max_age_slots = max_age_slots;
+    sla.bump = ctx.bumps.freshness_sla;
+
+    emit!(FreshnessSlaSet {
+        query_account: sla.query_account,
+        max_age_slots,
+    });
+    Ok(())
+}
+
+/// Permissionless: returns, as return data, whether the score cache of
+/// `query_account` was refreshed within its SLA. Writes nothing, so it can be
+/// simulated.
+pub fn is_within_sla(ctx: Context<IsWithinSla>) -> Result<bool> {
+    Ok(ctx.accounts.freshness_sla.is_within_at(Clock::get()?.slot))
+}
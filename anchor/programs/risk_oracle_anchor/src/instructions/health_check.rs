@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use risk_oracle_shared::{feed, profiles};
+use switchboard_on_demand::{Instructions, QueueAccountData, SlotHashes};
+
+use crate::error::ErrorCode;
+use crate::events::Heartbeat;
+use crate::state::{Config, CONFIG_SEED};
+use crate::verify::{feed_id_with, score_from_value, verify_quote};
+
+#[derive(Accounts)]
+pub struct HealthCheck<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(address = Pubkey::new_from_array(profiles::ACTIVE.queue))]
+    pub queue: AccountLoader<'info, QueueAccountData>,
+    pub slothashes: Sysvar<'info, SlotHashes>,
+    pub instructions: Sysvar<'info, Instructions>,
+    /// Keeper running the check, recorded in the heartbeat.
+    pub payer: Signer<'info>,
+}
+
+/// Verifies a quote of the constant `feed::test_feed` end to end (queue,
+/// sysvars, signatures, freshness, oracle samples) and emits a [`Heartbeat`].
+/// The test feed needs no API key and always reports `feed::TEST_SCORE`, so a
+/// failure points at the pipeline itself rather than at an address or the
+/// Range API. Writes nothing; rejected unless the profile enables the test
+/// feed (devnet).
+pub fn health_check(ctx: Context<HealthCheck>) -> Result<()> {
+    require!(profiles::ACTIVE.test_feed, ErrorCode::TestFeedDisabled);
+    // Always strict: a swapped sysvar is what the check is there to catch.
+    require_keys_eq!(
+        ctx.accounts.slothashes.key(),
+        sysvar::slot_hashes::ID,
+        ErrorCode::InvalidSysvar
+    );
+    require_keys_eq!(
+        ctx.accounts.instructions.key(),
+        sysvar::instructions::ID,
+        ErrorCode::InvalidSysvar
+    );
+
+    let verified = verify_quote(
+        ctx.accounts.queue.as_ref(),
+        ctx.accounts.slothashes.as_ref(),
+        ctx.accounts.instructions.as_ref(),
+        &feed_id_with(ctx.accounts.config.feed_id_derivation(), &feed::test_feed()),
+    )?;
+    let score = score_from_value(verified.value);
+    require!(
+        score == feed::TEST_SCORE,
+        ErrorCode::HealthCheckValueMismatch
+    );
+
+    emit!(Heartbeat {
+        checked_by: ctx.accounts.payer.key(),
+        slot: Clock::get()?.slot,
+        quote_slot: verified.quote_slot,
+        oracle_samples: verified.oracle_samples,
+    });
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::events::{IntegratorFeesCollected, IntegratorRegistered, IntegratorToppedUp};
+use crate::state::{Config, Integrator, CONFIG_SEED, INTEGRATOR_SEED};
+
+#[derive(Accounts)]
+#[instruction(consumer: Pubkey)]
+pub struct RegisterIntegrator<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Integrator::INIT_SPACE,
+        seeds = [INTEGRATOR_SEED, consumer.as_ref()],
+        bump
+    )]
+    pub integrator: Account<'info, Integrator>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TopUpIntegrator<'info> {
+    #[account(
+        mut,
+        seeds = [INTEGRATOR_SEED, integrator.consumer.as_ref()],
+        bump = integrator.bump
+    )]
+    pub integrator: Account<'info, Integrator>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CollectIntegratorFees<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority, has_one = treasury)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [INTEGRATOR_SEED, integrator.consumer.as_ref()],
+        bump = integrator.bump
+    )]
+    pub integrator: Account<'info, Integrator>,
+    /// CHECK: Only receives lamports, pinned to the configured treasury
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
+
+/// Registers `consumer` for integrator accounting at `price_lamports` per
+/// verification (0 to only count them), or changes its price. Counters and
+/// balances are kept across updates.
+pub fn register_integrator(
+    ctx: Context<RegisterIntegrator>,
+    consumer: Pubkey,
+    price_lamports: u64,
+) -> Result<()> {
+    let integrator = &mut ctx.accounts.integrator;
+    integrator.consumer = consumer;
+    integrator.price_lamports = price_lamports;
+    integrator.bump = ctx.bumps.integrator;
+
+    emit!(IntegratorRegistered {
+        consumer,
+        price_lamports,
+    });
+    Ok(())
+}
+
+/// Permissionless: adds `lamports` from the payer to the prepaid balance.
+pub fn top_up_integrator(ctx: Context<TopUpIntegrator>, lamports: u64) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.integrator.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+
+    let integrator = &mut ctx.accounts.integrator;
+    integrator.balance_lamports += lamports;
+
+    emit!(IntegratorToppedUp {
+        consumer: integrator.consumer,
+        lamports,
+        balance_lamports: integrator.balance_lamports,
+    });
+    Ok(())
+}
+
+/// Moves the lamports charged so far to the treasury. The prepaid balance
+/// stays with the integrator.
+pub fn collect_integrator_fees(ctx: Context<CollectIntegratorFees>) -> Result<()> {
+    let lamports = ctx.accounts.integrator.charged_lamports;
+    ctx.accounts.integrator.charged_lamports = 0;
+
+    ctx.accounts.integrator.sub_lamports(lamports)?;
+    ctx.accounts.treasury.add_lamports(lamports)?;
+
+    emit!(IntegratorFeesCollected {
+        consumer: ctx.accounts.integrator.consumer,
+        lamports,
+    });
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use risk_oracle_shared::merkle;
+
+use crate::error::ErrorCode;
+use crate::events::DenylistRootUpdated;
+use crate::state::{Config, DenylistRoot, CONFIG_SEED, DENYLIST_ROOT_SEED};
+use crate::verify::resolve_screened_address;
+
+#[derive(Accounts)]
+pub struct SetDenylistRoot<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + DenylistRoot::INIT_SPACE,
+        seeds = [DENYLIST_ROOT_SEED],
+        bump
+    )]
+    pub denylist_root: Account<'info, DenylistRoot>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CheckDenylist<'info> {
+    #[account(seeds = [DENYLIST_ROOT_SEED], bump = denylist_root.bump)]
+    pub denylist_root: Account<'info, DenylistRoot>,
+    /// CHECK: Classified in `resolve_screened_address`
+    pub query_account: UncheckedAccount<'info>,
+}
+
+/// A listed address with its position and merkle siblings.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LeafProof {
+    pub address: Pubkey,
+    pub index: u32,
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl LeafProof {
+    fn as_leaf(&self) -> merkle::Leaf<'_> {
+        merkle::Leaf {
+            key: self.address.to_bytes(),
+            index: self.index,
+            siblings: &self.siblings,
+        }
+    }
+}
+
+/// Proof that the screened wallet is, or is not, in the merkle denylist.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub enum DenylistProof {
+    Member(LeafProof),
+    /// The listed neighbors around the address: `lower` is omitted when the
+    /// address sorts before every leaf, `upper` when it sorts after every
+    /// leaf, both for an empty list.
+    NonMember {
+        lower: Option<LeafProof>,
+        upper: Option<LeafProof>,
+    },
+}
+
+/// Replaces the committed denylist. `root` must be built from the sorted,
+/// deduplicated list (`risk_oracle_shared::merkle::Tree`) or non-membership
+/// proofs are meaningless.
+pub fn set_denylist_root(
+    ctx: Context<SetDenylistRoot>,
+    root: [u8; 32],
+    leaf_count: u32,
+) -> Result<()> {
+    let denylist_root = &mut ctx.accounts.denylist_root;
+    denylist_root.root = root;
+    denylist_root.leaf_count = leaf_count;
+    denylist_root.updated_slot = Clock::get()?.slot;
+    denylist_root.bump = ctx.bumps.denylist_root;
+
+    emit!(DenylistRootUpdated { root, leaf_count });
+    Ok(())
+}
+
+/// Verifies `proof` for the screened wallet of the query account against the
+/// committed root and returns whether the wallet is listed. Fails on any
+/// invalid proof, so a caller can't claim either answer without one.
+pub fn check_denylist(ctx: Context<CheckDenylist>, proof: DenylistProof) -> Result<bool> {
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+    let denylist_root = &ctx.accounts.denylist_root;
+    let sha256: merkle::Hashv = |data| hashv(data).to_bytes();
+
+    let listed = match &proof {
+        DenylistProof::Member(leaf) => {
+            require_keys_eq!(leaf.address, screened_address, ErrorCode::InvalidDenylistProof);
+            require!(
+                leaf.as_leaf()
+                    .verify(sha256, &denylist_root.root, denylist_root.leaf_count),
+                ErrorCode::InvalidDenylistProof
+            );
+            true
+        }
+        DenylistProof::NonMember { lower, upper } => {
+            require!(
+                merkle::verify_non_member(
+                    sha256,
+                    &denylist_root.root,
+                    denylist_root.leaf_count,
+                    &screened_address.to_bytes(),
+                    lower.as_ref().map(LeafProof::as_leaf),
+                    upper.as_ref().map(LeafProof::as_leaf),
+                ),
+                ErrorCode::InvalidDenylistProof
+            );
+            false
+        }
+    };
+
+    msg!("Denylist check for {}: listed = {}", screened_address, listed);
+    Ok(listed)
+}
@@ -0,0 +1,57 @@
+pub mod address_policy;
+pub mod bypass_voucher;
+pub mod check_or_verify_score;
+pub mod check_risk_score_twa;
+pub mod close_receipt;
+pub mod confidential_score;
+pub mod config;
+pub mod consume_verified_score;
+pub mod denylist;
+pub mod denylist_segment;
+pub mod derive_feed_id;
+pub mod freshness_sla;
+pub mod health_check;
+pub mod integrator;
+pub mod merkle_denylist;
+pub mod program_screening;
+pub mod quote_account;
+pub mod reconcile;
+pub mod refresh_score_caches;
+pub mod registry;
+pub mod risk_ceiling;
+pub mod score_decay;
+pub mod template_variables;
+pub mod threshold_proof;
+pub mod verify_composite_risk_score;
+pub mod verify_risk_score_batch;
+pub mod verify_risk_score_feed;
+pub mod views;
+
+pub use address_policy::*;
+pub use bypass_voucher::*;
+pub use check_or_verify_score::*;
+pub use check_risk_score_twa::*;
+pub use close_receipt::*;
+pub use confidential_score::*;
+pub use config::*;
+pub use consume_verified_score::*;
+pub use denylist::*;
+pub use denylist_segment::*;
+pub use derive_feed_id::*;
+pub use freshness_sla::*;
+pub use health_check::*;
+pub use integrator::*;
+pub use merkle_denylist::*;
+pub use program_screening::*;
+pub use quote_account::*;
+pub use reconcile::*;
+pub use refresh_score_caches::*;
+pub use registry::*;
+pub use risk_ceiling::*;
+pub use score_decay::*;
+pub use template_variables::*;
+pub use threshold_proof::*;
+pub use verify_composite_risk_score::*;
+pub use verify_risk_score_batch::*;
+pub use verify_risk_score_feed::*;
+pub use views::*;
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use risk_oracle_shared::diagnostics;
+use risk_oracle_shared::program_screen::{self, ProgramScreenError};
+use switchboard_on_demand::Instructions;
+
+use crate::error::ErrorCode;
+use crate::events::ProgramDenylistUpdated;
+use crate::state::{
+    Config, ProgramDenylist, CONFIG_SEED, MAX_DENIED_PROGRAMS, PROGRAM_DENYLIST_SEED,
+};
+
+#[derive(Accounts)]
+pub struct SetProgramDenylist<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ProgramDenylist::INIT_SPACE,
+        seeds = [PROGRAM_DENYLIST_SEED],
+        bump
+    )]
+    pub program_denylist: Account<'info, ProgramDenylist>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProgramDenylist<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [PROGRAM_DENYLIST_SEED], bump = program_denylist.bump)]
+    pub program_denylist: Account<'info, ProgramDenylist>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ScreenPrograms<'info> {
+    #[account(seeds = [PROGRAM_DENYLIST_SEED], bump = program_denylist.bump)]
+    pub program_denylist: Account<'info, ProgramDenylist>,
+    pub instructions: Sysvar<'info, Instructions>,
+}
+
+/// Replaces the programs no instruction of a verified transaction may target
+/// once `PROGRAM_DENYLIST` is enabled.
+pub fn set_program_denylist(ctx: Context<SetProgramDenylist>, programs: Vec<Pubkey>) -> Result<()> {
+    require!(
+        programs.len() <= MAX_DENIED_PROGRAMS,
+        ErrorCode::ProgramDenylistTooLarge
+    );
+
+    let program_denylist = &mut ctx.accounts.program_denylist;
+    program_denylist.programs = programs;
+    program_denylist.bump = ctx.bumps.program_denylist;
+    emit!(ProgramDenylistUpdated {
+        programs: program_denylist.programs.clone(),
+    });
+    Ok(())
+}
+
+/// Adds `program` to the program denylist, creating it on first use.
+pub fn flag_program(ctx: Context<SetProgramDenylist>, program: Pubkey) -> Result<()> {
+    let program_denylist = &mut ctx.accounts.program_denylist;
+    require!(
+        !program_denylist.denies(&program.to_bytes()),
+        ErrorCode::ProgramAlreadyFlagged
+    );
+    require!(
+        program_denylist.programs.len() < MAX_DENIED_PROGRAMS,
+        ErrorCode::ProgramDenylistTooLarge
+    );
+
+    program_denylist.programs.push(program);
+    program_denylist.bump = ctx.bumps.program_denylist;
+    emit!(ProgramDenylistUpdated {
+        programs: program_denylist.programs.clone(),
+    });
+    Ok(())
+}
+
+pub fn unflag_program(ctx: Context<UpdateProgramDenylist>, program: Pubkey) -> Result<()> {
+    let program_denylist = &mut ctx.accounts.program_denylist;
+    let index = program_denylist
+        .programs
+        .iter()
+        .position(|flagged| *flagged == program)
+        .ok_or(ErrorCode::ProgramNotFlagged)?;
+    program_denylist.programs.swap_remove(index);
+    emit!(ProgramDenylistUpdated {
+        programs: program_denylist.programs.clone(),
+    });
+    Ok(())
+}
+
+/// Fails unless no instruction of the transaction targets a program of the
+/// program denylist, whatever the feature flags. For transactions that
+/// screen their shape without verifying a quote, or integrators checking it
+/// through CPI.
+pub fn screen_programs(ctx: Context<ScreenPrograms>) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.instructions.key(),
+        sysvar::instructions::ID,
+        ErrorCode::InvalidSysvar
+    );
+    require_no_denied_program(
+        ctx.accounts.instructions.as_ref(),
+        &ctx.accounts.program_denylist,
+    )
+}
+
+/// Reads the program id of every instruction of the transaction from the
+/// Instructions sysvar and fails on the first one `program_denylist` denies,
+/// logging its index and id.
+pub(crate) fn require_no_denied_program(
+    instructions: &AccountInfo,
+    program_denylist: &ProgramDenylist,
+) -> Result<()> {
+    let screened = program_screen::screen_programs(&instructions.try_borrow_data()?, |program| {
+        program_denylist.denies(program)
+    });
+    match screened {
+        Ok(()) => Ok(()),
+        Err(ProgramScreenError::Denied { index, program_id }) => {
+            msg!("{}", diagnostics::denied_program(index, &program_id));
+            Err(ErrorCode::DeniedProgramInTransaction.into())
+        }
+        Err(ProgramScreenError::Malformed) => Err(ErrorCode::InvalidSysvar.into()),
+    }
+}
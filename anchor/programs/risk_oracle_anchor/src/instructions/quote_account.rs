@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::{diagnostics, feed, profiles};
+use rust_decimal::Decimal;
+use switchboard_on_demand::{Instructions, QueueAccountData, SlotHashes};
+
+use crate::error::ErrorCode;
+use crate::events::{QuoteAccountVerified, QuotePosted};
+use crate::instructions::risk_ceiling::require_within_consumer_ceiling;
+use crate::state::{Config, PostedFeed, QuoteAccount, CONFIG_SEED, QUOTE_ACCOUNT_SEED};
+use crate::verify::{
+    feed_id_with, resolve_screened_address, score_from_value, verify_quote_feeds,
+    FEED_VALUE_DECIMALS,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateQuoteAccount<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + QuoteAccount::INIT_SPACE,
+        seeds = [QUOTE_ACCOUNT_SEED, owner.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub quote_account: Account<'info, QuoteAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WriteQuoteAccount<'info> {
+    #[account(address = Pubkey::new_from_array(profiles::ACTIVE.queue))]
+    pub queue: AccountLoader<'info, QueueAccountData>,
+    pub slothashes: Sysvar<'info, SlotHashes>,
+    pub instructions: Sysvar<'info, Instructions>,
+    #[account(
+        mut,
+        seeds = [QUOTE_ACCOUNT_SEED, owner.key().as_ref(), &quote_account.id.to_le_bytes()],
+        bump = quote_account.bump,
+        has_one = owner
+    )]
+    pub quote_account: Account<'info, QuoteAccount>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyRiskScoreAccount<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// Any owner's: only verified quotes are written to it.
+    pub quote_account: Account<'info, QuoteAccount>,
+    /// CHECK: Classified in `resolve_screened_address`
+    pub query_account: UncheckedAccount<'info>,
+    /// Required by risk ceilings, to tell the consuming program.
+    pub instructions: Option<Sysvar<'info, Instructions>>,
+    /// CHECK: Risk ceiling PDA of the consuming program, checked in
+    /// `require_within_ceiling`. Required by risk ceilings; empty unless set.
+    pub risk_ceiling: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseQuoteAccount<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [QUOTE_ACCOUNT_SEED, owner.key().as_ref(), &quote_account.id.to_le_bytes()],
+        bump = quote_account.bump,
+        has_one = owner
+    )]
+    pub quote_account: Account<'info, QuoteAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Score verified from a quote account, returned as return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct AccountScore {
+    pub score: u8,
+    pub quote_slot: u64,
+    pub oracle_samples: u8,
+}
+
+/// Creates an empty quote account of the signer, `id` telling several apart.
+pub fn create_quote_account(ctx: Context<CreateQuoteAccount>, id: u64) -> Result<()> {
+    let quote_account = &mut ctx.accounts.quote_account;
+    quote_account.owner = ctx.accounts.owner.key();
+    quote_account.id = id;
+    quote_account.bump = ctx.bumps.quote_account;
+    Ok(())
+}
+
+/// Verifies the quote of the transaction (signatures, queue, freshness and
+/// the oracle samples of every feed) and posts it to the quote account,
+/// replacing the previous one. The quote must be newer than the posted one
+/// (`QuoteReplayed`).
+pub fn write_quote_account(ctx: Context<WriteQuoteAccount>) -> Result<()> {
+    let quote = verify_quote_feeds(
+        ctx.accounts.queue.as_ref(),
+        ctx.accounts.slothashes.as_ref(),
+        ctx.accounts.instructions.as_ref(),
+    )?;
+    for quote_feed in &quote.feeds {
+        quote.require_samples(quote_feed)?;
+    }
+
+    let quote_account = &mut ctx.accounts.quote_account;
+    require!(
+        quote.quote_slot > quote_account.quote_slot,
+        ErrorCode::QuoteReplayed
+    );
+    quote_account.quote_slot = quote.quote_slot;
+    quote_account.written_slot = Clock::get()?.slot;
+    quote_account.oracle_samples = quote.oracle_samples;
+    quote_account.feeds = quote
+        .feeds
+        .iter()
+        .map(|quote_feed| PostedFeed {
+            feed_id: quote_feed.feed_id,
+            value: quote_feed.feed_value,
+        })
+        .collect();
+
+    emit!(QuotePosted {
+        quote_account: quote_account.key(),
+        owner: quote_account.owner,
+        quote_slot: quote.quote_slot,
+        feed_count: quote_account.feeds.len() as u8,
+    });
+    Ok(())
+}
+
+/// Verifies the risk score of `query_account` against the quote posted to
+/// `quote_account` instead of a quote instruction of the transaction. The
+/// quote must be at most `max_age_slots` old: the caller's own freshness
+/// policy, the quote having passed the profile's when it was written. Then
+/// threshold gating and the consumer's risk ceiling apply as for
+/// `verify_risk_score_feed`. Writes nothing.
+pub fn verify_risk_score_account(
+    ctx: Context<VerifyRiskScoreAccount>,
+    max_age_slots: u64,
+) -> Result<AccountScore> {
+    let quote_account = &ctx.accounts.quote_account;
+    require!(quote_account.quote_slot != 0, ErrorCode::EmptyQuoteAccount);
+    let slot = Clock::get()?.slot;
+    if slot.saturating_sub(quote_account.quote_slot) > max_age_slots {
+        msg!(
+            "{}",
+            diagnostics::stale_quote(slot, quote_account.quote_slot, max_age_slots)
+        );
+        return Err(ErrorCode::StaleQuote.into());
+    }
+
+    let config = &ctx.accounts.config;
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+    let derived_feed_id = feed_id_with(
+        config.feed_id_derivation(),
+        &feed::risk_score_feed(&screened_address.to_bytes()),
+    );
+    let Some(posted_feed) = quote_account.feed(&derived_feed_id) else {
+        msg!("{}", diagnostics::feed_mismatch(&derived_feed_id, None));
+        return Err(ErrorCode::FeedMismatch.into());
+    };
+
+    let score = score_from_value(Decimal::from_i128_with_scale(
+        posted_feed.value,
+        FEED_VALUE_DECIMALS,
+    ));
+    require!(
+        config.passes_threshold(score),
+        ErrorCode::RiskThresholdExceeded
+    );
+    require_within_consumer_ceiling(
+        config,
+        ctx.accounts
+            .instructions
+            .as_ref()
+            .map(|instructions| instructions.as_ref()),
+        ctx.accounts.risk_ceiling.as_deref(),
+        score,
+    )?;
+
+    msg!(
+        "Risk score {} from quote account {}",
+        score,
+        quote_account.key()
+    );
+    emit!(QuoteAccountVerified {
+        address: screened_address,
+        score,
+        quote_account: quote_account.key(),
+        quote_slot: quote_account.quote_slot,
+    });
+    Ok(AccountScore {
+        score,
+        quote_slot: quote_account.quote_slot,
+        oracle_samples: quote_account.oracle_samples,
+    })
+}
+
+/// Closes the quote account, the rent going back to its owner.
+pub fn close_quote_account(_ctx: Context<CloseQuoteAccount>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::constant_time;
+use risk_oracle_shared::feed;
+use risk_oracle_shared::profiles;
+use switchboard_on_demand::{Instructions, QueueAccountData, SlotHashes};
+
+use crate::error::ErrorCode;
+use crate::events::{CacheDivergence, CompositeScoreCached};
+use crate::state::{Momentum, ScoreCache, ScoreProvenance, Severity};
+use crate::verify::{score_from_value, verify_quote_feeds};
+
+#[derive(Accounts)]
+pub struct Reconcile<'info> {
+    #[account(address = Pubkey::new_from_array(profiles::ACTIVE.queue))]
+    pub queue: AccountLoader<'info, QueueAccountData>,
+    pub slothashes: Sysvar<'info, SlotHashes>,
+    pub instructions: Sysvar<'info, Instructions>,
+    #[account(mut)]
+    pub cache: Account<'info, ScoreCache>,
+    /// Submitter of the quote, recorded as the cache's `updated_by` when it
+    /// is corrected.
+    pub payer: Signer<'info>,
+}
+
+/// Cross-checks a score cache against a fresh quote of the feed it was last
+/// verified against, as a safety net against a keeper writing bad caches.
+/// The quote must be at least as recent as the cached one (`QuoteReplayed`).
+/// When the scores differ by more than `tolerance`, emits `CacheDivergence`
+/// and, with `correct`, writes the fresh score as `refresh_score_caches`
+/// would. Returns whether the cache diverged.
+pub fn reconcile(ctx: Context<Reconcile>, tolerance: u8, correct: bool) -> Result<bool> {
+    let quote = verify_quote_feeds(
+        ctx.accounts.queue.as_ref(),
+        ctx.accounts.slothashes.as_ref(),
+        ctx.accounts.instructions.as_ref(),
+    )?;
+    let cache = &mut ctx.accounts.cache;
+    let quote_feed = quote
+        .feeds
+        .iter()
+        .find(|quote_feed| constant_time::eq_32(&quote_feed.feed_id, &cache.feed_id))
+        .ok_or(ErrorCode::NoCacheInQuote)?;
+    quote.require_samples(quote_feed)?;
+    require!(
+        quote.quote_slot >= cache.quote_slot,
+        ErrorCode::QuoteReplayed
+    );
+
+    let fresh_score = score_from_value(quote_feed.value);
+    if fresh_score.abs_diff(cache.score) <= tolerance {
+        return Ok(false);
+    }
+
+    let slot = Clock::get()?.slot;
+    let reconciled_by = ctx.accounts.payer.key();
+    emit!(CacheDivergence {
+        address: cache.address,
+        cached_score: cache.score,
+        fresh_score,
+        cached_quote_slot: cache.quote_slot,
+        quote_slot: quote.quote_slot,
+        cached_by: cache.updated_by,
+        reconciled_by,
+        corrected: correct,
+    });
+    if !correct {
+        return Ok(true);
+    }
+
+    cache.score = fresh_score;
+    cache.quote_slot = quote.quote_slot;
+    cache.updated_slot = slot;
+    cache.momentum = Momentum::Unknown;
+    cache.provenance = ScoreProvenance::new(
+        feed::schema::COMPOSITE_RISK_SCORE_V1,
+        quote.quote_slot,
+        quote.oracle_samples,
+    );
+    cache.updated_by = reconciled_by;
+
+    emit!(CompositeScoreCached {
+        address: cache.address,
+        score: cache.score,
+        networks: cache.networks,
+        quote_slot: cache.quote_slot,
+        momentum: cache.momentum,
+        oracle_samples: quote.oracle_samples,
+        severity: Severity::of(cache.score),
+        updated_by: reconciled_by,
+        updated_slot: slot,
+    });
+    Ok(true)
+}
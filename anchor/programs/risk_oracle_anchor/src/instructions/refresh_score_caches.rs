@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::constant_time;
+use risk_oracle_shared::feed;
+use risk_oracle_shared::profiles;
+use switchboard_on_demand::{Instructions, QueueAccountData, SlotHashes};
+
+use crate::error::ErrorCode;
+use crate::events::CompositeScoreCached;
+use crate::state::{Momentum, ScoreCache, ScoreProvenance, Severity};
+use crate::verify::{score_from_value, verify_quote_feeds};
+
+#[derive(Accounts)]
+pub struct RefreshScoreCaches<'info> {
+    #[account(address = Pubkey::new_from_array(profiles::ACTIVE.queue))]
+    pub queue: AccountLoader<'info, QueueAccountData>,
+    pub slothashes: Sysvar<'info, SlotHashes>,
+    pub instructions: Sysvar<'info, Instructions>,
+    /// Submitter of the quote, recorded as the caches' `updated_by`.
+    pub payer: Signer<'info>,
+    // remaining_accounts: existing score caches (writable), any order.
+}
+
+/// Crank for quotes covering many watchlisted addresses: verifies the quote
+/// once and writes every cache of the remaining accounts whose feed the quote
+/// carries, as `verify_composite_risk_score` would. A cache is matched on the
+/// feed id it was last verified against, so no feed is derived again. Caches
+/// without a feed in the quote are left as they are, but at least one must be
+/// written. Histories and SLAs aren't touched. Returns the number of caches
+/// written.
+pub fn refresh_score_caches<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefreshScoreCaches<'info>>,
+) -> Result<u16> {
+    let quote = verify_quote_feeds(
+        ctx.accounts.queue.as_ref(),
+        ctx.accounts.slothashes.as_ref(),
+        ctx.accounts.instructions.as_ref(),
+    )?;
+    let slot = Clock::get()?.slot;
+    let updated_by = ctx.accounts.payer.key();
+    let mut count: u16 = 0;
+
+    for cache_info in ctx.remaining_accounts {
+        let mut cache: Account<ScoreCache> = Account::try_from(cache_info)?;
+        let Some(quote_feed) = quote
+            .feeds
+            .iter()
+            .find(|quote_feed| constant_time::eq_32(&quote_feed.feed_id, &cache.feed_id))
+        else {
+            continue;
+        };
+        quote.require_samples(quote_feed)?;
+
+        cache.score = score_from_value(quote_feed.value);
+        cache.quote_slot = quote.quote_slot;
+        cache.updated_slot = slot;
+        cache.momentum = Momentum::Unknown;
+        cache.provenance = ScoreProvenance::new(
+            feed::schema::COMPOSITE_RISK_SCORE_V1,
+            quote.quote_slot,
+            quote.oracle_samples,
+        );
+        cache.updated_by = updated_by;
+        cache.exit(ctx.program_id)?;
+
+        emit!(CompositeScoreCached {
+            address: cache.address,
+            score: cache.score,
+            networks: cache.networks,
+            quote_slot: cache.quote_slot,
+            momentum: cache.momentum,
+            oracle_samples: quote.oracle_samples,
+            severity: Severity::of(cache.score),
+            updated_by,
+            updated_slot: slot,
+        });
+        count += 1;
+    }
+
+    msg!("Refreshed {} of {} score caches", count, ctx.remaining_accounts.len());
+    require!(count > 0, ErrorCode::NoCacheInQuote);
+    Ok(count)
+}
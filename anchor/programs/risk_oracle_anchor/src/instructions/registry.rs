@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::constant_time;
+
+use crate::error::ErrorCode;
+use crate::events::{FeedExpirySet, FeedRegistered, FeedUnregistered};
+use crate::growth;
+use crate::state::{
+    Config, FeedProvenance, FeedRegistry, RegistryEntry, CONFIG_SEED, INITIAL_REGISTRY_ENTRIES,
+    MAX_REGISTRY_ENTRIES, REGISTRY_SEED,
+};
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = authority,
+        space = FeedRegistry::space(INITIAL_REGISTRY_ENTRIES),
+        seeds = [REGISTRY_SEED],
+        bump
+    )]
+    pub registry: Account<'info, FeedRegistry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterFeed<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [REGISTRY_SEED], bump = registry.bump)]
+    pub registry: Account<'info, FeedRegistry>,
+    /// Pays for the registry's growth.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRegistry<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [REGISTRY_SEED], bump = registry.bump)]
+    pub registry: Account<'info, FeedRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+    ctx.accounts.registry.bump = ctx.bumps.registry;
+    Ok(())
+}
+
+/// Pins `feed_id` as the risk score feed of `address`. The authority computes
+/// the id off-chain with the same template the program derives, and may record
+/// which template that was in `provenance`. Grows the registry when full.
+pub fn register_feed(
+    ctx: Context<RegisterFeed>,
+    address: Pubkey,
+    feed_id: [u8; 32],
+    provenance: Option<FeedProvenance>,
+) -> Result<()> {
+    pin_feed(
+        &mut ctx.accounts.registry,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        address,
+        feed_id,
+        provenance,
+    )
+}
+
+/// Appends the entry of `feed_id` to `registry`, `authority` paying for its
+/// growth.
+pub(crate) fn pin_feed<'info>(
+    registry: &mut Account<'info, FeedRegistry>,
+    authority: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    address: Pubkey,
+    feed_id: [u8; 32],
+    provenance: Option<FeedProvenance>,
+) -> Result<()> {
+    require!(registry.find(&feed_id).is_none(), ErrorCode::FeedAlreadyRegistered);
+    require!(
+        registry.entries.len() < MAX_REGISTRY_ENTRIES,
+        ErrorCode::RegistryFull
+    );
+    growth::reserve_entry(
+        &registry.to_account_info(),
+        registry.entries.len(),
+        MAX_REGISTRY_ENTRIES,
+        FeedRegistry::space,
+        &authority.to_account_info(),
+        &system_program.to_account_info(),
+    )?;
+
+    registry.entries.push(RegistryEntry {
+        feed_id,
+        address,
+        registered_slot: Clock::get()?.slot,
+        provenance,
+        expires_at_slot: None,
+    });
+    emit!(FeedRegistered {
+        address,
+        feed_id,
+        provenance,
+    });
+    Ok(())
+}
+
+pub fn unregister_feed(ctx: Context<UpdateRegistry>, feed_id: [u8; 32]) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    let index = registry
+        .entries
+        .iter()
+        .position(|entry| constant_time::eq_32(&entry.feed_id, &feed_id))
+        .ok_or(ErrorCode::FeedNotRegistered)?;
+    registry.entries.swap_remove(index);
+    emit!(FeedUnregistered { feed_id });
+    Ok(())
+}
+
+/// Schedules the end of life of `feed_id`: from `expires_at_slot` on,
+/// verifications against it fail with `FeedExpired`, so integrators move to
+/// the feed of a newer template by a known slot. `None` lifts the expiry.
+pub fn set_feed_expiry(
+    ctx: Context<UpdateRegistry>,
+    feed_id: [u8; 32],
+    expires_at_slot: Option<u64>,
+) -> Result<()> {
+    if let Some(expires_at) = expires_at_slot {
+        require!(
+            expires_at > Clock::get()?.slot,
+            ErrorCode::InvalidFeedExpiry
+        );
+    }
+    let entry = ctx
+        .accounts
+        .registry
+        .entries
+        .iter_mut()
+        .find(|entry| constant_time::eq_32(&entry.feed_id, &feed_id))
+        .ok_or(ErrorCode::FeedNotRegistered)?;
+    entry.expires_at_slot = expires_at_slot;
+    emit!(FeedExpirySet {
+        feed_id,
+        expires_at_slot,
+    });
+    Ok(())
+}
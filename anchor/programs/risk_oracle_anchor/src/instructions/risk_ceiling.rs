@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::{feed, seeds};
+
+use crate::error::ErrorCode;
+use crate::events::RiskCeilingUpdated;
+use crate::state::{feature_flags, Config, RiskCeiling, CONFIG_SEED, RISK_CEILING_SEED};
+use crate::verify::consumer;
+
+#[derive(Accounts)]
+#[instruction(consumer: Pubkey)]
+pub struct SetRiskCeiling<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RiskCeiling::INIT_SPACE,
+        seeds = [RISK_CEILING_SEED, consumer.as_ref()],
+        bump
+    )]
+    pub risk_ceiling: Account<'info, RiskCeiling>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRiskCeiling<'info> {
+    #[account(
+        mut,
+        seeds = [RISK_CEILING_SEED, risk_ceiling.consumer.as_ref()],
+        bump = risk_ceiling.bump,
+        has_one = officer
+    )]
+    pub risk_ceiling: Account<'info, RiskCeiling>,
+    pub officer: Signer<'info>,
+}
+
+/// Caps the scores accepted for `consumer` at `max_score` (0–100) and names
+/// the risk officer who may move the cap, or replaces both.
+pub fn set_risk_ceiling(
+    ctx: Context<SetRiskCeiling>,
+    consumer: Pubkey,
+    officer: Pubkey,
+    max_score: u8,
+) -> Result<()> {
+    require!(max_score <= feed::MAX_SCORE, ErrorCode::InvalidThreshold);
+    let risk_ceiling = &mut ctx.accounts.risk_ceiling;
+    risk_ceiling.consumer = consumer;
+    risk_ceiling.officer = officer;
+    risk_ceiling.max_score = max_score;
+    risk_ceiling.updated_slot = Clock::get()?.slot;
+    risk_ceiling.bump = ctx.bumps.risk_ceiling;
+
+    emit!(RiskCeilingUpdated {
+        consumer,
+        officer,
+        max_score,
+    });
+    Ok(())
+}
+
+/// Moves the ceiling, signed by its risk officer.
+pub fn update_risk_ceiling(ctx: Context<UpdateRiskCeiling>, max_score: u8) -> Result<()> {
+    require!(max_score <= feed::MAX_SCORE, ErrorCode::InvalidThreshold);
+    let risk_ceiling = &mut ctx.accounts.risk_ceiling;
+    risk_ceiling.max_score = max_score;
+    risk_ceiling.updated_slot = Clock::get()?.slot;
+
+    emit!(RiskCeilingUpdated {
+        consumer: risk_ceiling.consumer,
+        officer: risk_ceiling.officer,
+        max_score,
+    });
+    Ok(())
+}
+
+/// Highest score the ceiling of `consumer` accepts, `feed::MAX_SCORE` when
+/// it has none. `risk_ceiling` must be the consumer's ceiling PDA; left empty,
+/// the consumer has no ceiling.
+pub(crate) fn max_score_of(risk_ceiling: &AccountInfo, consumer: &Pubkey) -> Result<u8> {
+    let (expected, _) = seeds::find_risk_ceiling_address(
+        Pubkey::find_program_address,
+        &crate::ID,
+        &consumer.to_bytes(),
+    );
+    require_keys_eq!(risk_ceiling.key(), expected, ErrorCode::InvalidRiskCeiling);
+    if risk_ceiling.owner != &crate::ID {
+        return Ok(feed::MAX_SCORE);
+    }
+
+    let ceiling = RiskCeiling::try_deserialize(&mut &risk_ceiling.try_borrow_data()?[..])?;
+    Ok(ceiling.max_score)
+}
+
+/// Fails when `score` is above the ceiling of `consumer`, see
+/// [`max_score_of`].
+pub(crate) fn require_within_ceiling(
+    risk_ceiling: &AccountInfo,
+    consumer: &Pubkey,
+    score: u8,
+) -> Result<()> {
+    let max_score = max_score_of(risk_ceiling, consumer)?;
+    if score > max_score {
+        msg!(
+            "Risk score {} above the ceiling {} of {}",
+            score,
+            max_score,
+            consumer
+        );
+        return Err(ErrorCode::RiskCeilingExceeded.into());
+    }
+    Ok(())
+}
+
+/// Under risk ceilings, fails when `score` is above the ceiling of the program
+/// consuming the current instruction (see `verify::consumer`), read from
+/// `instructions`. Both accounts are then required.
+pub(crate) fn require_within_consumer_ceiling(
+    config: &Config,
+    instructions: Option<&AccountInfo>,
+    risk_ceiling: Option<&AccountInfo>,
+    score: u8,
+) -> Result<()> {
+    if !config.is_enabled(feature_flags::RISK_CEILINGS) {
+        return Ok(());
+    }
+    let (Some(instructions), Some(risk_ceiling)) = (instructions, risk_ceiling) else {
+        return Err(ErrorCode::MissingRiskCeiling.into());
+    };
+    require_within_ceiling(risk_ceiling, &consumer(instructions)?, score)
+}
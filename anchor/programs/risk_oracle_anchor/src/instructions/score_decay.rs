@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::decay::Decay;
+
+use crate::error::ErrorCode;
+use crate::events::ScoreDecayUpdated;
+use crate::state::{Config, ScoreDecay, CONFIG_SEED, SCORE_DECAY_SEED};
+
+#[derive(Accounts)]
+pub struct SetScoreDecay<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ScoreDecay::INIT_SPACE,
+        seeds = [SCORE_DECAY_SEED],
+        bump
+    )]
+    pub score_decay: Account<'info, ScoreDecay>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets the penalty cached scores take as they age: `penalty` (0–100) per
+/// started `interval_slots` past `grace_slots`, at most `max_penalty`. Applied
+/// once [`feature_flags::SCORE_DECAY`](crate::state::feature_flags::SCORE_DECAY)
+/// is enabled.
+pub fn set_score_decay(
+    ctx: Context<SetScoreDecay>,
+    grace_slots: u64,
+    interval_slots: u64,
+    penalty: u8,
+    max_penalty: u8,
+) -> Result<()> {
+    let decay = Decay {
+        grace_slots,
+        interval_slots,
+        penalty,
+        max_penalty,
+    };
+    require!(decay.is_valid(), ErrorCode::InvalidScoreDecay);
+
+    let score_decay = &mut ctx.accounts.score_decay;
+    score_decay.grace_slots = grace_slots;
+    score_decay.interval_slots = interval_slots;
+    score_decay.penalty = penalty;
+    score_decay.max_penalty = max_penalty;
+    score_decay.updated_slot = Clock::get()?.slot;
+    score_decay.bump = ctx.bumps.score_decay;
+
+    emit!(ScoreDecayUpdated {
+        grace_slots,
+        interval_slots,
+        penalty,
+        max_penalty,
+    });
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use risk_oracle_shared::feed_variables;
+
+use crate::error::ErrorCode;
+use crate::events::TemplateVariablesUpdated;
+use crate::instructions::registry::pin_feed;
+use crate::state::{
+    Config, FeedProvenance, FeedRegistry, TemplateVariables, CONFIG_SEED, MAX_TEMPLATE_VARIABLES,
+    REGISTRY_SEED, TEMPLATE_VARIABLES_SEED,
+};
+
+#[derive(Accounts)]
+#[instruction(schema: u8)]
+pub struct SetTemplateVariables<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TemplateVariables::INIT_SPACE,
+        seeds = [TEMPLATE_VARIABLES_SEED, &[schema]],
+        bump
+    )]
+    pub template_variables: Account<'info, TemplateVariables>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey, schema: u8)]
+pub struct RegisterFeedTemplate<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [REGISTRY_SEED], bump = registry.bump)]
+    pub registry: Account<'info, FeedRegistry>,
+    #[account(
+        seeds = [TEMPLATE_VARIABLES_SEED, &[schema]],
+        bump = template_variables.bump
+    )]
+    pub template_variables: Account<'info, TemplateVariables>,
+    /// Pays for the registry's growth.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Replaces the variable names the feeds of template `schema` (a
+/// `feed::schema` tag) may use, e.g. `feed_variables::template_variables`.
+pub fn set_template_variables(
+    ctx: Context<SetTemplateVariables>,
+    schema: u8,
+    names: Vec<String>,
+) -> Result<()> {
+    require!(
+        names.len() <= MAX_TEMPLATE_VARIABLES
+            && names
+                .iter()
+                .all(|name| feed_variables::is_variable_name(name.as_bytes())),
+        ErrorCode::InvalidTemplateVariables
+    );
+
+    let template_variables = &mut ctx.accounts.template_variables;
+    template_variables.schema = schema;
+    template_variables.names = names;
+    template_variables.bump = ctx.bumps.template_variables;
+    emit!(TemplateVariablesUpdated {
+        schema,
+        names: template_variables.names.clone(),
+    });
+    Ok(())
+}
+
+/// [`register_feed`](crate::instructions::registry::register_feed) from the
+/// feed itself, `encoded` with `feed::encode`, rather than its id. Fails when
+/// a `${NAME}` placeholder of the feed isn't a variable of template `schema`,
+/// the misspelled override that would otherwise only show up as a
+/// `FeedMismatch`. The id is derived under the config's feed id derivation
+/// and the provenance recorded from `schema` and `encoded`.
+pub fn register_feed_template(
+    ctx: Context<RegisterFeedTemplate>,
+    address: Pubkey,
+    schema: u8,
+    encoded: Vec<u8>,
+) -> Result<()> {
+    let template_variables = &ctx.accounts.template_variables;
+    if let Some(name) =
+        feed_variables::first_undeclared(&encoded, |name| template_variables.declares(name))
+    {
+        msg!(
+            "Feed uses ${{{}}}, not a variable of template {}",
+            String::from_utf8_lossy(name),
+            schema
+        );
+        return Err(ErrorCode::UndeclaredFeedVariable.into());
+    }
+    let preimage = ctx
+        .accounts
+        .config
+        .feed_id_derivation()
+        .preimage_of_encoded(&encoded)
+        .ok_or(ErrorCode::MalformedFeedEncoding)?;
+    let feed_id = hash(preimage).to_bytes();
+
+    pin_feed(
+        &mut ctx.accounts.registry,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        address,
+        feed_id,
+        Some(FeedProvenance::new(schema, &encoded)),
+    )
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::{feed, profiles};
+use switchboard_on_demand::{Instructions, QueueAccountData, SlotHashes};
+
+use crate::events::ThresholdProofRecorded;
+use crate::state::{Config, ThresholdProof, CONFIG_SEED, THRESHOLD_PROOF_SEED};
+use crate::verify::{
+    feed_id_with, require_expected_feeds, resolve_screened_address, score_commitment,
+    score_from_value, verify_quote,
+};
+
+#[derive(Accounts)]
+pub struct VerifyThresholdProof<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(address = Pubkey::new_from_array(profiles::ACTIVE.queue))]
+    pub queue: AccountLoader<'info, QueueAccountData>,
+    pub slothashes: Sysvar<'info, SlotHashes>,
+    pub instructions: Sysvar<'info, Instructions>,
+    /// CHECK: Classified in `resolve_screened_address`
+    pub query_account: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ThresholdProof::INIT_SPACE,
+        seeds = [THRESHOLD_PROOF_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub threshold_proof: Account<'info, ThresholdProof>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Threshold proof mode, for integrators not allowed to redistribute provider
+/// scores on-chain: verifies the risk score feed, then stores, emits and
+/// returns only whether the score passes `Config::effective_threshold`, with
+/// a salted commitment to the score (see `verify::score_commitment`) that the
+/// operator can open off-chain for audits. Unlike threshold gating, a score
+/// above the threshold doesn't fail, it is recorded as such.
+pub fn verify_threshold_proof(
+    ctx: Context<VerifyThresholdProof>,
+    salt: [u8; 32],
+) -> Result<bool> {
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+
+    let derived_feed_id = feed_id_with(
+        ctx.accounts.config.feed_id_derivation(),
+        &feed::risk_score_feed(&screened_address.to_bytes()),
+    );
+    let verified = verify_quote(
+        ctx.accounts.queue.as_ref(),
+        ctx.accounts.slothashes.as_ref(),
+        ctx.accounts.instructions.as_ref(),
+        &derived_feed_id,
+    )?;
+    require_expected_feeds(&ctx.accounts.config, &verified.feeds, &[derived_feed_id])?;
+
+    let config = &ctx.accounts.config;
+    let threshold = config.effective_threshold();
+    let score = score_from_value(verified.value);
+    let proof = &mut ctx.accounts.threshold_proof;
+    proof.address = screened_address;
+    proof.threshold = threshold;
+    proof.below_threshold = config.within_threshold(score);
+    proof.commitment = score_commitment(&screened_address, score, &salt);
+    proof.quote_slot = verified.quote_slot;
+    proof.updated_slot = Clock::get()?.slot;
+    proof.bump = ctx.bumps.threshold_proof;
+
+    emit!(ThresholdProofRecorded {
+        address: screened_address,
+        threshold,
+        below_threshold: proof.below_threshold,
+        commitment: proof.commitment,
+        quote_slot: proof.quote_slot,
+    });
+    Ok(proof.below_threshold)
+}
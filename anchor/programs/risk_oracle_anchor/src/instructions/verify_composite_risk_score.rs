@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::feed::{self, NetworkSet};
+use risk_oracle_shared::feed_id::FeedIdDerivation;
+use risk_oracle_shared::profiles;
+use switchboard_on_demand::{Instructions, QueueAccountData, SlotHashes};
+
+use crate::error::ErrorCode;
+use crate::events::CompositeScoreCached;
+use crate::growth;
+use crate::state::{
+    Config, FreshnessSla, HistoryEntry, Momentum, ScoreCache, ScoreHistory, ScoreProvenance,
+    Severity, CACHE_SEED, CONFIG_SEED, HISTORY_CAPACITY, HISTORY_SEED, INITIAL_HISTORY_ENTRIES,
+    SLA_SEED,
+};
+use crate::verify::{feed_id_with, resolve_screened_address, score_from_value, verify_quote};
+
+#[derive(Accounts)]
+pub struct VerifyCompositeRiskScore<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(address = Pubkey::new_from_array(profiles::ACTIVE.queue))]
+    pub queue: AccountLoader<'info, QueueAccountData>,
+    pub slothashes: Sysvar<'info, SlotHashes>,
+    pub instructions: Sysvar<'info, Instructions>,
+    /// CHECK: Classified in `resolve_screened_address` before its pubkey is used to build the feed id
+    pub query_account: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ScoreCache::INIT_SPACE,
+        seeds = [CACHE_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub cache: Account<'info, ScoreCache>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// When passed, the score is also appended to the query account's history.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ScoreHistory::space(INITIAL_HISTORY_ENTRIES),
+        seeds = [HISTORY_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub history: Option<Account<'info, ScoreHistory>>,
+    /// When passed, the refresh is recorded against the query account's
+    /// freshness SLA (see `set_freshness_sla`).
+    #[account(mut, seeds = [SLA_SEED, query_account.key().as_ref()], bump = freshness_sla.bump)]
+    pub freshness_sla: Option<Account<'info, FreshnessSla>>,
+}
+
+/// Score written to the cache, returned as return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct CompositeScore {
+    pub score: u8,
+    pub momentum: Momentum,
+    /// Oracle signatures backing the quote.
+    pub oracle_samples: u8,
+}
+
+/// Accounts used by [`refresh_cache`], borrowed from the composite layout or
+/// from `CheckOrVerifyScore`.
+pub(crate) struct CacheAccounts<'a, 'info> {
+    /// The config's, so the cached feed id matches the verifications'.
+    pub derivation: FeedIdDerivation,
+    pub queue: &'a AccountLoader<'info, QueueAccountData>,
+    pub slothashes: &'a Sysvar<'info, SlotHashes>,
+    pub instructions: &'a Sysvar<'info, Instructions>,
+    pub cache: (&'a mut Account<'info, ScoreCache>, u8),
+    pub history: Option<(&'a mut Account<'info, ScoreHistory>, u8)>,
+    pub freshness_sla: Option<&'a mut Account<'info, FreshnessSla>>,
+    /// Fee payer submitting the quote, paying for the history's growth.
+    pub payer: &'a Signer<'info>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+impl<'info> VerifyCompositeRiskScore<'info> {
+    fn cache_accounts(
+        &mut self,
+        bumps: &VerifyCompositeRiskScoreBumps,
+    ) -> CacheAccounts<'_, 'info> {
+        CacheAccounts {
+            derivation: self.config.feed_id_derivation(),
+            queue: &self.queue,
+            slothashes: &self.slothashes,
+            instructions: &self.instructions,
+            cache: (&mut self.cache, bumps.cache),
+            history: self.history.as_mut().zip(bumps.history),
+            freshness_sla: self.freshness_sla.as_mut(),
+            payer: &self.payer,
+            system_program: &self.system_program,
+        }
+    }
+}
+
+/// Verifies the composite feed querying the screened wallet on every network
+/// of `networks` (max score across networks) and stores the score, along
+/// with the networks it covers, in the query account's cache. With the
+/// history account, the score's momentum is computed against the history
+/// before the score is appended to it.
+pub fn verify_composite_risk_score(
+    ctx: Context<VerifyCompositeRiskScore>,
+    networks: u8,
+) -> Result<CompositeScore> {
+    let networks = NetworkSet::from_bits(networks).ok_or(ErrorCode::InvalidNetworks)?;
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+    refresh_cache(ctx.accounts.cache_accounts(&ctx.bumps), screened_address, networks)
+}
+
+/// Verifies the composite feed of `screened_address` and writes the score to
+/// the cache, the history and the SLA, as `verify_composite_risk_score`.
+pub(crate) fn refresh_cache(
+    accounts: CacheAccounts,
+    screened_address: Pubkey,
+    networks: NetworkSet,
+) -> Result<CompositeScore> {
+    let derived_feed_id = feed_id_with(
+        accounts.derivation,
+        &feed::composite_risk_score_feed(&screened_address.to_bytes(), networks),
+    );
+    let verified = verify_quote(
+        accounts.queue.as_ref(),
+        accounts.slothashes.as_ref(),
+        accounts.instructions.as_ref(),
+        &derived_feed_id,
+    )?;
+
+    let score = score_from_value(verified.value);
+    msg!(
+        "Verified composite risk score feed! Value: {}, networks: {:#b}",
+        score,
+        networks.bits()
+    );
+
+    let (cache, bump) = accounts.cache;
+    cache.address = screened_address;
+    cache.score = score;
+    cache.quote_slot = verified.quote_slot;
+    cache.updated_slot = Clock::get()?.slot;
+    cache.feed_id = derived_feed_id;
+    cache.networks = networks.bits();
+    cache.bump = bump;
+    cache.momentum = Momentum::Unknown;
+    cache.provenance = ScoreProvenance::new(
+        feed::schema::COMPOSITE_RISK_SCORE_V1,
+        verified.quote_slot,
+        verified.oracle_samples,
+    );
+    cache.updated_by = accounts.payer.key();
+
+    if let Some((history, bump)) = accounts.history {
+        cache.momentum = history.momentum(cache.score, cache.updated_slot);
+        growth::reserve_entry(
+            &history.to_account_info(),
+            history.entries.len(),
+            HISTORY_CAPACITY,
+            ScoreHistory::space,
+            &accounts.payer.to_account_info(),
+            &accounts.system_program.to_account_info(),
+        )?;
+        history.address = screened_address;
+        history.bump = bump;
+        history.push(HistoryEntry {
+            score: cache.score,
+            quote_slot: cache.quote_slot,
+        });
+    }
+
+    if let Some(sla) = accounts.freshness_sla {
+        sla.last_refresh_slot = cache.updated_slot;
+    }
+
+    emit!(CompositeScoreCached {
+        address: screened_address,
+        score: cache.score,
+        networks: cache.networks,
+        quote_slot: cache.quote_slot,
+        momentum: cache.momentum,
+        oracle_samples: verified.oracle_samples,
+        severity: Severity::of(cache.score),
+        updated_by: cache.updated_by,
+        updated_slot: cache.updated_slot,
+    });
+    Ok(CompositeScore {
+        score: cache.score,
+        momentum: cache.momentum,
+        oracle_samples: verified.oracle_samples,
+    })
+}
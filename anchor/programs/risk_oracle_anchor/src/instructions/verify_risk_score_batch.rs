@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::{constant_time, feed, profiles};
+use switchboard_on_demand::{Instructions, QueueAccountData, SlotHashes};
+
+use crate::error::ErrorCode;
+use crate::events::RiskScoreBatchVerified;
+use crate::instructions::risk_ceiling::max_score_of;
+use crate::state::{feature_flags, Config, CONFIG_SEED, MAX_BATCH_ADDRESSES};
+use crate::verify::{
+    consumer, feed_id_with, resolve_screened_address, score_from_value, verify_quote_feeds,
+};
+
+#[derive(Accounts)]
+pub struct VerifyRiskScoreBatch<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(address = Pubkey::new_from_array(profiles::ACTIVE.queue))]
+    pub queue: AccountLoader<'info, QueueAccountData>,
+    pub slothashes: Sysvar<'info, SlotHashes>,
+    pub instructions: Sysvar<'info, Instructions>,
+    /// CHECK: Risk ceiling PDA of the consuming program, checked in
+    /// `max_score_of`. Required by risk ceilings; empty unless set.
+    pub risk_ceiling: Option<UncheckedAccount<'info>>,
+    // remaining_accounts: query accounts, 1 to MAX_BATCH_ADDRESSES, bit i of
+    // the result being the i-th.
+}
+
+/// Result of a batch verification, returned as return data. Bit `i` of each
+/// bitmap is the `i`-th query account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct BatchVerification {
+    /// Query accounts whose risk score feed the quote carries, with the
+    /// oracle samples it requires.
+    pub matched: u64,
+    /// Matched query accounts whose score is within `Config::risk_threshold`,
+    /// whether threshold gating is enabled or not, and the consumer's risk
+    /// ceiling under risk ceilings.
+    pub passed: u64,
+    pub quote_slot: u64,
+}
+
+/// Verifies the quote once and checks the risk score feed of every query
+/// account of the remaining accounts against it.
+///
+/// Without `partial`, fails on the first query account the quote doesn't
+/// carry (`FeedMismatch`), whose score is above the consumer's risk ceiling
+/// (`RiskCeilingExceeded`) or, under threshold gating, above the threshold
+/// (`RiskThresholdExceeded`), like one verification per address. With `partial`, none of them fails the instruction: the bitmaps
+/// tell the caller which addresses matched and passed, to settle mixed
+/// results in its own logic within the same transaction. Query accounts that
+/// can't be screened (programs, program-owned accounts) fail in both modes.
+/// Writes nothing: no replay record, fee, history or receipt.
+pub fn verify_risk_score_batch(
+    ctx: Context<VerifyRiskScoreBatch>,
+    partial: bool,
+) -> Result<BatchVerification> {
+    let query_accounts = ctx.remaining_accounts;
+    require!(
+        (1..=MAX_BATCH_ADDRESSES).contains(&query_accounts.len()),
+        ErrorCode::InvalidBatchSize
+    );
+
+    let quote = verify_quote_feeds(
+        ctx.accounts.queue.as_ref(),
+        ctx.accounts.slothashes.as_ref(),
+        ctx.accounts.instructions.as_ref(),
+    )?;
+    let config = &ctx.accounts.config;
+    let derivation = config.feed_id_derivation();
+    let max_score = if config.is_enabled(feature_flags::RISK_CEILINGS) {
+        let risk_ceiling = ctx
+            .accounts
+            .risk_ceiling
+            .as_ref()
+            .ok_or(ErrorCode::MissingRiskCeiling)?;
+        max_score_of(risk_ceiling, &consumer(ctx.accounts.instructions.as_ref())?)?
+    } else {
+        feed::MAX_SCORE
+    };
+    let mut matched: u64 = 0;
+    let mut passed: u64 = 0;
+
+    for (index, query_account) in query_accounts.iter().enumerate() {
+        let screened_address = resolve_screened_address(query_account)?;
+        let derived_feed_id = feed_id_with(
+            derivation,
+            &feed::risk_score_feed(&screened_address.to_bytes()),
+        );
+        let quote_feed = quote
+            .feeds
+            .iter()
+            .find(|quote_feed| constant_time::eq_32(&quote_feed.feed_id, &derived_feed_id));
+        let Some(quote_feed) = quote_feed else {
+            require!(partial, ErrorCode::FeedMismatch);
+            continue;
+        };
+        if let Err(error) = quote.require_samples(quote_feed) {
+            if !partial {
+                return Err(error);
+            }
+            continue;
+        }
+
+        let score = score_from_value(quote_feed.value);
+        require!(partial || score <= max_score, ErrorCode::RiskCeilingExceeded);
+        require!(
+            partial || config.passes_threshold(score),
+            ErrorCode::RiskThresholdExceeded
+        );
+        matched |= 1 << index;
+        if config.within_threshold(score) && score <= max_score {
+            passed |= 1 << index;
+        }
+    }
+
+    msg!(
+        "Batch of {}: matched {:#x}, passed {:#x}",
+        query_accounts.len(),
+        matched,
+        passed
+    );
+    emit!(RiskScoreBatchVerified {
+        count: query_accounts.len() as u8,
+        matched,
+        passed,
+        quote_slot: quote.quote_slot,
+        partial,
+    });
+    Ok(BatchVerification {
+        matched,
+        passed,
+        quote_slot: quote.quote_slot,
+    })
+}
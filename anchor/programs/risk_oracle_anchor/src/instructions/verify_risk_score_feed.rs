@@ -0,0 +1,723 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use risk_oracle_shared::query_account::{self, QueryAccountKind};
+use risk_oracle_shared::{constant_time, feed, profiles, seeds};
+use rust_decimal::prelude::ToPrimitive;
+use switchboard_on_demand::{Instructions, QueueAccountData, SlotHashes};
+
+use crate::error::ErrorCode;
+use crate::events::{RiskScoreVerified, ScoreAsOfVerified};
+use crate::growth;
+use crate::instructions::address_policy::load_address_policy;
+use crate::instructions::denylist_segment::require_not_in_segment;
+use crate::instructions::program_screening::require_no_denied_program;
+use crate::instructions::risk_ceiling::require_within_ceiling;
+use crate::state::{
+    feature_flags, Config, FeedIdRecord, FeedRegistry, HistoryEntry, Integrator, PolicyVerdict,
+    ProgramDenylist, QuoteMarker, ReplayRecord, ScoreHistory, Severity, VerificationReceipt,
+    CONFIG_SEED, FEED_ID_SEED, HISTORY_CAPACITY, HISTORY_SEED, INITIAL_HISTORY_ENTRIES,
+    INTEGRATOR_SEED, MARKER_SEED, PROGRAM_DENYLIST_SEED, RECEIPT_SEED, RECEIPT_TTL_SLOTS,
+    REGISTRY_SEED, REPLAY_SEED,
+};
+use crate::verify::{
+    consumer, feed_id_with, quote_hash, require_before_deadline, require_expected_feeds,
+    resolve_screened_address, score_from_value, verify_quote_feeds, FeedValue, VerifiedQuote,
+};
+
+/// Account layout of the verify instructions. The slot comes from
+/// `Clock::get()`, so unlike [`VerifyRiskScoreFeedLegacy`] no clock account is
+/// passed. SlotHashes stays an account: the quote verifier reads it directly.
+#[derive(Accounts)]
+pub struct VerifyRiskScoreFeed<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(address = Pubkey::new_from_array(profiles::ACTIVE.queue))]
+    pub queue: AccountLoader<'info, QueueAccountData>,
+    pub slothashes: Sysvar<'info, SlotHashes>,
+    pub instructions: Sysvar<'info, Instructions>,
+    /// CHECK: Classified in `resolve_screened_address` before its pubkey is used to build the feed id
+    pub query_account: UncheckedAccount<'info>,
+    // Optional accounts, only required by the matching feature flags.
+    #[account(mut)]
+    pub payer: Option<Signer<'info>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayRecord::INIT_SPACE,
+        seeds = [REPLAY_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub replay_record: Option<Account<'info, ReplayRecord>>,
+    /// CHECK: Only receives lamports, pinned to the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    pub system_program: Option<Program<'info, System>>,
+    /// Only required by `verify_risk_score_feed_pinned`.
+    #[account(seeds = [REGISTRY_SEED], bump = registry.bump)]
+    pub registry: Option<Account<'info, FeedRegistry>>,
+    /// Only required by `verify_risk_score_feed_cached`.
+    #[account(seeds = [FEED_ID_SEED, query_account.key().as_ref()], bump = feed_id_record.bump)]
+    pub feed_id_record: Option<Account<'info, FeedIdRecord>>,
+    /// When passed, the result is recorded for `consume_verified_score`
+    /// (requires `payer` and `system_program`).
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + QuoteMarker::INIT_SPACE,
+        seeds = [MARKER_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub quote_marker: Option<Account<'info, QuoteMarker>>,
+    /// When passed, a receipt valid for `RECEIPT_TTL_SLOTS` is written for
+    /// programs invoked through CPI (requires `payer` and `system_program`).
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VerificationReceipt::INIT_SPACE,
+        seeds = [RECEIPT_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub receipt: Option<Account<'info, VerificationReceipt>>,
+    /// When passed, the score is appended to the query account's history
+    /// (requires `payer` and `system_program`).
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ScoreHistory::space(INITIAL_HISTORY_ENTRIES),
+        seeds = [HISTORY_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub history: Option<Account<'info, ScoreHistory>>,
+    /// CHECK: Denylist entry PDA of the screened wallet, checked in
+    /// `process_verify`. Required by the denylist flag; empty unless listed.
+    pub denylist_entry: Option<UncheckedAccount<'info>>,
+    /// Usage account of the consuming program, required by integrator
+    /// accounting. When passed, the verification is metered.
+    #[account(
+        mut,
+        seeds = [INTEGRATOR_SEED, integrator.consumer.as_ref()],
+        bump = integrator.bump
+    )]
+    pub integrator: Option<Account<'info, Integrator>>,
+    /// Required by the program denylist flag.
+    #[account(seeds = [PROGRAM_DENYLIST_SEED], bump = program_denylist.bump)]
+    pub program_denylist: Option<Account<'info, ProgramDenylist>>,
+    /// CHECK: Risk ceiling PDA of the consuming program, checked in
+    /// `require_within_ceiling`. Required by risk ceilings; empty unless set.
+    pub risk_ceiling: Option<UncheckedAccount<'info>>,
+    /// CHECK: Address policy PDA of the screened wallet, checked in
+    /// `load_address_policy`. Required by address policies; empty unless set.
+    pub address_policy: Option<UncheckedAccount<'info>>,
+    /// CHECK: Packed denylist segment of the screened wallet, checked in
+    /// `require_not_in_segment`. Required by the segmented denylist; empty
+    /// until the segment lists an address.
+    pub denylist_segment: Option<UncheckedAccount<'info>>,
+}
+
+/// Original layout of `verify_risk_score_feed`: its five accounts (queue,
+/// clock, slothashes, instructions, query_account) in their original
+/// positions, then the config and the optional accounts. Integrations built
+/// on the original layout append the config; new ones should use
+/// `verify_risk_score_feed_compact`.
+#[derive(Accounts)]
+pub struct VerifyRiskScoreFeedLegacy<'info> {
+    #[account(address = Pubkey::new_from_array(profiles::ACTIVE.queue))]
+    pub queue: AccountLoader<'info, QueueAccountData>,
+    pub clock: Sysvar<'info, Clock>,
+    pub slothashes: Sysvar<'info, SlotHashes>,
+    pub instructions: Sysvar<'info, Instructions>,
+    /// CHECK: Classified in `resolve_screened_address` before its pubkey is used to build the feed id
+    pub query_account: UncheckedAccount<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub payer: Option<Signer<'info>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayRecord::INIT_SPACE,
+        seeds = [REPLAY_SEED, query_account.key().as_ref()],
+        bump
+    )]
+    pub replay_record: Option<Account<'info, ReplayRecord>>,
+    /// CHECK: Only receives lamports, pinned to the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    pub system_program: Option<Program<'info, System>>,
+}
+
+/// Score of a successful verification, returned as return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VerifiedScore {
+    pub score: u8,
+    /// Oracle signatures backing the quote, so integrators can tell a
+    /// single-oracle quote from a consensus of several.
+    pub oracle_samples: u8,
+    /// Approved feeds of the quote with their values, in quote order: the
+    /// verified feed and, when the registry is passed, the other unexpired
+    /// feeds registered for the screened wallet. Callers pick or aggregate.
+    pub feeds: Vec<FeedValue>,
+}
+
+/// Accounts used by [`process_verify`], borrowed from either layout.
+struct VerifyAccounts<'a, 'info> {
+    config: &'a Account<'info, Config>,
+    queue: &'a AccountLoader<'info, QueueAccountData>,
+    slothashes: &'a Sysvar<'info, SlotHashes>,
+    instructions: &'a Sysvar<'info, Instructions>,
+    payer: Option<&'a Signer<'info>>,
+    replay_record: Option<&'a mut Account<'info, ReplayRecord>>,
+    treasury: Option<&'a UncheckedAccount<'info>>,
+    system_program: Option<&'a Program<'info, System>>,
+    registry: Option<&'a Account<'info, FeedRegistry>>,
+    quote_marker: Option<(&'a mut Account<'info, QuoteMarker>, u8)>,
+    receipt: Option<(&'a mut Account<'info, VerificationReceipt>, u8)>,
+    history: Option<(&'a mut Account<'info, ScoreHistory>, u8)>,
+    denylist_entry: Option<&'a UncheckedAccount<'info>>,
+    integrator: Option<&'a mut Account<'info, Integrator>>,
+    program_denylist: Option<&'a Account<'info, ProgramDenylist>>,
+    risk_ceiling: Option<&'a UncheckedAccount<'info>>,
+    address_policy: Option<&'a UncheckedAccount<'info>>,
+    denylist_segment: Option<&'a UncheckedAccount<'info>>,
+}
+
+impl<'info> VerifyRiskScoreFeed<'info> {
+    fn verify_accounts(&mut self, bumps: &VerifyRiskScoreFeedBumps) -> VerifyAccounts<'_, 'info> {
+        VerifyAccounts {
+            config: &self.config,
+            queue: &self.queue,
+            slothashes: &self.slothashes,
+            instructions: &self.instructions,
+            payer: self.payer.as_ref(),
+            replay_record: self.replay_record.as_mut(),
+            treasury: self.treasury.as_ref(),
+            system_program: self.system_program.as_ref(),
+            registry: self.registry.as_ref(),
+            quote_marker: self.quote_marker.as_mut().zip(bumps.quote_marker),
+            receipt: self.receipt.as_mut().zip(bumps.receipt),
+            history: self.history.as_mut().zip(bumps.history),
+            denylist_entry: self.denylist_entry.as_ref(),
+            integrator: self.integrator.as_mut(),
+            program_denylist: self.program_denylist.as_ref(),
+            risk_ceiling: self.risk_ceiling.as_ref(),
+            address_policy: self.address_policy.as_ref(),
+            denylist_segment: self.denylist_segment.as_ref(),
+        }
+    }
+}
+
+impl<'info> VerifyRiskScoreFeedLegacy<'info> {
+    fn verify_accounts(&mut self) -> VerifyAccounts<'_, 'info> {
+        VerifyAccounts {
+            config: &self.config,
+            queue: &self.queue,
+            slothashes: &self.slothashes,
+            instructions: &self.instructions,
+            payer: self.payer.as_ref(),
+            replay_record: self.replay_record.as_mut(),
+            treasury: self.treasury.as_ref(),
+            system_program: self.system_program.as_ref(),
+            registry: None,
+            quote_marker: None,
+            receipt: None,
+            history: None,
+            denylist_entry: None,
+            integrator: None,
+            program_denylist: None,
+            risk_ceiling: None,
+            address_policy: None,
+            denylist_segment: None,
+        }
+    }
+}
+
+pub fn verify_risk_score_feed(ctx: Context<VerifyRiskScoreFeedLegacy>) -> Result<VerifiedScore> {
+    if ctx.accounts.config.is_enabled(feature_flags::STRICT_SYSVAR_CHECKS) {
+        require_keys_eq!(
+            ctx.accounts.clock.key(),
+            sysvar::clock::ID,
+            ErrorCode::InvalidSysvar
+        );
+    }
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+    let derived_feed_id = feed_id_with(
+        ctx.accounts.config.feed_id_derivation(),
+        &feed::risk_score_feed(&screened_address.to_bytes()),
+    );
+    process_verify(
+        ctx.accounts.verify_accounts(),
+        screened_address,
+        derived_feed_id,
+        &[],
+        None,
+    )
+}
+
+/// `verify_risk_score_feed` with the compact account layout.
+pub fn verify_risk_score_feed_compact(ctx: Context<VerifyRiskScoreFeed>) -> Result<VerifiedScore> {
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+    let derived_feed_id = feed_id_with(
+        ctx.accounts.config.feed_id_derivation(),
+        &feed::risk_score_feed(&screened_address.to_bytes()),
+    );
+    process_verify(
+        ctx.accounts.verify_accounts(&ctx.bumps),
+        screened_address,
+        derived_feed_id,
+        &[],
+        None,
+    )
+}
+
+/// `verify_risk_score_feed_compact` bound to a deadline: fails once the slot
+/// is past `not_after_slot`, even with a fresh quote, so time-sensitive flows
+/// (auctions, liquidations) bound when the screening result can be acted on.
+pub fn verify_risk_score_feed_with_deadline(
+    ctx: Context<VerifyRiskScoreFeed>,
+    not_after_slot: u64,
+) -> Result<VerifiedScore> {
+    require_before_deadline(not_after_slot)?;
+    verify_risk_score_feed_compact(ctx)
+}
+
+/// Verifies a quote of the constant `feed::test_feed` for `query_account`, so
+/// integration tests run the whole flow without an API key. Rejected unless
+/// the profile enables the test feed (devnet).
+pub fn verify_test_feed(ctx: Context<VerifyRiskScoreFeed>) -> Result<VerifiedScore> {
+    require!(profiles::ACTIVE.test_feed, ErrorCode::TestFeedDisabled);
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+    let test_feed_id = feed_id_with(ctx.accounts.config.feed_id_derivation(), &feed::test_feed());
+    process_verify(
+        ctx.accounts.verify_accounts(&ctx.bumps),
+        screened_address,
+        test_feed_id,
+        &[],
+        None,
+    )
+}
+
+/// Verifies the `feed::score_and_flag_feeds` pair of `query_account`, score
+/// first and blacklist flag second in the quote. Fails when the provider flags
+/// the address, otherwise proceeds as `verify_risk_score_feed_compact` with the
+/// score feed.
+pub fn verify_risk_score_with_flag(ctx: Context<VerifyRiskScoreFeed>) -> Result<VerifiedScore> {
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+    let [score_feed, flag_feed] = feed::score_and_flag_feeds(&screened_address.to_bytes());
+    let derivation = ctx.accounts.config.feed_id_derivation();
+
+    // One verification for both feeds: the flag from it here, the score in
+    // `process_verify`.
+    let quote = verify_quote_feeds(
+        ctx.accounts.queue.as_ref(),
+        ctx.accounts.slothashes.as_ref(),
+        ctx.accounts.instructions.as_ref(),
+    )?;
+    let flag_feed_id = feed_id_with(derivation, &flag_feed);
+    let flag = quote.feed(1, &flag_feed_id)?;
+    require!(flag.value.is_zero(), ErrorCode::AddressBlacklisted);
+
+    process_verify(
+        ctx.accounts.verify_accounts(&ctx.bumps),
+        screened_address,
+        feed_id_with(derivation, &score_feed),
+        &[flag_feed_id],
+        Some(quote),
+    )
+}
+
+/// Score of `verify_risk_score_with_as_of`, returned as return data: the
+/// [`VerifiedScore`] with the two times compliance reports tell apart.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VerifiedScoreAsOf {
+    /// Unix time Range computed the score at: how old the data is.
+    pub as_of: i64,
+    /// Slot the oracles signed the quote at: how old the attestation is.
+    pub quote_slot: u64,
+    pub verified: VerifiedScore,
+}
+
+/// Verifies the `feed::score_and_as_of_feeds` pair of `query_account`, score
+/// first and as-of time second in the quote, then proceeds as
+/// `verify_risk_score_feed_compact` with the score feed. Returns and emits
+/// when Range computed the score next to when the quote was signed.
+pub fn verify_risk_score_with_as_of(
+    ctx: Context<VerifyRiskScoreFeed>,
+) -> Result<VerifiedScoreAsOf> {
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+    let [score_feed, as_of_feed] = feed::score_and_as_of_feeds(&screened_address.to_bytes());
+    let derivation = ctx.accounts.config.feed_id_derivation();
+
+    let quote = verify_quote_feeds(
+        ctx.accounts.queue.as_ref(),
+        ctx.accounts.slothashes.as_ref(),
+        ctx.accounts.instructions.as_ref(),
+    )?;
+    let quote_slot = quote.quote_slot;
+    let as_of_feed_id = feed_id_with(derivation, &as_of_feed);
+    let as_of = quote
+        .feed(1, &as_of_feed_id)?
+        .value
+        .trunc()
+        .to_i64()
+        .filter(|as_of| *as_of >= 0)
+        .ok_or(ErrorCode::InvalidAsOfTime)?;
+
+    let verified = process_verify(
+        ctx.accounts.verify_accounts(&ctx.bumps),
+        screened_address,
+        feed_id_with(derivation, &score_feed),
+        &[as_of_feed_id],
+        Some(quote),
+    )?;
+    emit!(ScoreAsOfVerified {
+        address: screened_address,
+        score: verified.score,
+        as_of,
+        quote_slot,
+    });
+    Ok(VerifiedScoreAsOf {
+        as_of,
+        quote_slot,
+        verified,
+    })
+}
+
+/// Pinned mode: skips the on-chain feed derivation. `feed_id` must be pinned
+/// in the registry for the screened wallet, so trust rests on the config
+/// authority that registered it instead of on the caller, and not expired.
+pub fn verify_risk_score_feed_pinned(
+    ctx: Context<VerifyRiskScoreFeed>,
+    feed_id: [u8; 32],
+) -> Result<VerifiedScore> {
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+    let registry = ctx
+        .accounts
+        .registry
+        .as_ref()
+        .ok_or(ErrorCode::MissingRegistry)?;
+    let entry = registry.find(&feed_id).ok_or(ErrorCode::FeedNotRegistered)?;
+    require_keys_eq!(
+        entry.address,
+        screened_address,
+        ErrorCode::FeedAddressMismatch
+    );
+    require!(
+        !entry.is_expired_at(Clock::get()?.slot),
+        ErrorCode::FeedExpired
+    );
+
+    process_verify(
+        ctx.accounts.verify_accounts(&ctx.bumps),
+        screened_address,
+        feed_id,
+        &[],
+        None,
+    )
+}
+
+/// Hybrid mode: loads the feed id stored by `derive_feed_id` instead of
+/// re-deriving it. The query account is still classified (cheap) so a token
+/// account whose owner changed since the derivation is caught.
+pub fn verify_risk_score_feed_cached(ctx: Context<VerifyRiskScoreFeed>) -> Result<VerifiedScore> {
+    let screened_address = resolve_screened_address(&ctx.accounts.query_account)?;
+    let record = ctx
+        .accounts
+        .feed_id_record
+        .as_ref()
+        .ok_or(ErrorCode::MissingFeedIdRecord)?;
+    require_keys_eq!(
+        record.address,
+        screened_address,
+        ErrorCode::StaleFeedIdRecord
+    );
+
+    let cached_feed_id = record.feed_id;
+    process_verify(
+        ctx.accounts.verify_accounts(&ctx.bumps),
+        screened_address,
+        cached_feed_id,
+        &[],
+        None,
+    )
+}
+
+/// ATA mode: `query_account` must be the wallet holding an associated token
+/// account for `mint`. The program screens the **wallet** pubkey, so
+/// integrators never put the ATA in the Range URL.
+pub fn verify_risk_score_feed_for_ata(
+    ctx: Context<VerifyRiskScoreFeed>,
+    mint: Pubkey,
+    token_program: Pubkey,
+) -> Result<VerifiedScore> {
+    let wallet = &ctx.accounts.query_account;
+    let kind = query_account::classify(
+        &wallet.owner.to_bytes(),
+        wallet.executable,
+        &wallet.try_borrow_data()?,
+    );
+    require!(kind == QueryAccountKind::Wallet, ErrorCode::QueryAccountNotWallet);
+    require!(
+        token_program.to_bytes() == query_account::TOKEN_PROGRAM_ID
+            || token_program.to_bytes() == query_account::TOKEN_2022_PROGRAM_ID,
+        ErrorCode::InvalidTokenProgram
+    );
+
+    // ATA mode screens the wallet itself, never the ATA. Deriving the ATA
+    // costs compute on every verification, so only `logging` builds name it.
+    #[cfg(feature = "logging")]
+    {
+        let (ata, _) = Pubkey::find_program_address(
+            &[wallet.key.as_ref(), token_program.as_ref(), mint.as_ref()],
+            &Pubkey::new_from_array(query_account::ASSOCIATED_TOKEN_PROGRAM_ID),
+        );
+        msg!(
+            "Screening wallet {} (associated token account {} for mint {})",
+            wallet.key,
+            ata,
+            mint
+        );
+    }
+    #[cfg(not(feature = "logging"))]
+    let _ = mint;
+
+    let wallet = wallet.key();
+    let derived_feed_id = feed_id_with(
+        ctx.accounts.config.feed_id_derivation(),
+        &feed::risk_score_feed(&wallet.to_bytes()),
+    );
+    process_verify(
+        ctx.accounts.verify_accounts(&ctx.bumps),
+        wallet,
+        derived_feed_id,
+        &[],
+        None,
+    )
+}
+
+/// Verifies the quote at instruction 0 against `expected_feed_id`, the feed of
+/// `screened_address`, and applies the config's optional behaviors.
+/// `companion_feed_ids` are the other feeds the quote is expected to carry,
+/// only used by strict quote feeds. `quote` is the quote when the caller
+/// already verified it to read those feeds, so it is not verified twice.
+fn process_verify(
+    accounts: VerifyAccounts,
+    screened_address: Pubkey,
+    expected_feed_id: [u8; 32],
+    companion_feed_ids: &[[u8; 32]],
+    quote: Option<VerifiedQuote>,
+) -> Result<VerifiedScore> {
+    let config = accounts.config;
+
+    if config.is_enabled(feature_flags::STRICT_SYSVAR_CHECKS) {
+        require_keys_eq!(
+            accounts.slothashes.key(),
+            sysvar::slot_hashes::ID,
+            ErrorCode::InvalidSysvar
+        );
+        require_keys_eq!(
+            accounts.instructions.key(),
+            sysvar::instructions::ID,
+            ErrorCode::InvalidSysvar
+        );
+    }
+
+    if config.is_enabled(feature_flags::DENYLIST) {
+        let entry = accounts
+            .denylist_entry
+            .ok_or(ErrorCode::MissingDenylistEntry)?;
+        let (expected, _) = seeds::find_denylist_entry_address(
+            Pubkey::find_program_address,
+            &crate::ID,
+            &screened_address.to_bytes(),
+        );
+        require_keys_eq!(entry.key(), expected, ErrorCode::InvalidDenylistEntry);
+        require!(entry.owner != &crate::ID, ErrorCode::AddressDenylisted);
+    }
+
+    if config.is_enabled(feature_flags::SEGMENTED_DENYLIST) {
+        let denylist_segment = accounts
+            .denylist_segment
+            .ok_or(ErrorCode::MissingDenylistSegment)?;
+        require_not_in_segment(denylist_segment, &screened_address)?;
+    }
+
+    let address_policy = if config.is_enabled(feature_flags::ADDRESS_POLICIES) {
+        let address_policy = accounts
+            .address_policy
+            .ok_or(ErrorCode::MissingAddressPolicy)?;
+        load_address_policy(address_policy, &screened_address)?
+    } else {
+        None
+    };
+    if let Some(policy) = &address_policy {
+        require!(
+            policy.verdict != PolicyVerdict::Deny,
+            ErrorCode::AddressPolicyDenied
+        );
+    }
+
+    if config.is_enabled(feature_flags::PROGRAM_DENYLIST) {
+        let program_denylist = accounts
+            .program_denylist
+            .ok_or(ErrorCode::MissingProgramDenylist)?;
+        require_no_denied_program(accounts.instructions.as_ref(), program_denylist)?;
+    }
+
+    let quote = match quote {
+        Some(quote) => quote,
+        None => verify_quote_feeds(
+            accounts.queue.as_ref(),
+            accounts.slothashes.as_ref(),
+            accounts.instructions.as_ref(),
+        )?,
+    };
+    let verified = quote.verified_feed(0, &expected_feed_id)?;
+
+    let mut expected_feed_ids = vec![expected_feed_id];
+    expected_feed_ids.extend_from_slice(companion_feed_ids);
+    require_expected_feeds(config, &verified.feeds, &expected_feed_ids)?;
+
+    // Logs the score rather than formatting the `Decimal` value on every
+    // verification; audit builds log the exact value.
+    let score = score_from_value(verified.value);
+    msg!("Verified risk score feed! Value: {}", score);
+
+    match &address_policy {
+        Some(policy) => {
+            policy.require_samples(verified.oracle_samples)?;
+            require!(
+                policy.passes(config, score),
+                ErrorCode::RiskThresholdExceeded
+            );
+        }
+        None => require!(
+            config.passes_threshold(score),
+            ErrorCode::RiskThresholdExceeded
+        ),
+    }
+
+    if config.is_enabled(feature_flags::ANTI_REPLAY) {
+        let record = accounts
+            .replay_record
+            .ok_or(ErrorCode::MissingReplayRecord)?;
+        require!(
+            verified.quote_slot > record.last_quote_slot,
+            ErrorCode::QuoteReplayed
+        );
+        record.last_quote_slot = verified.quote_slot;
+    }
+
+    if config.is_enabled(feature_flags::FEE_COLLECTION) && config.fee_lamports > 0 {
+        let (Some(payer), Some(treasury), Some(system_program)) =
+            (accounts.payer, accounts.treasury, accounts.system_program)
+        else {
+            return Err(ErrorCode::MissingFeeAccounts.into());
+        };
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: treasury.to_account_info(),
+                },
+            ),
+            config.fee_lamports,
+        )?;
+    }
+
+    let slot = Clock::get()?.slot;
+    let approved = |feed: &&FeedValue| {
+        constant_time::eq_32(&feed.feed_id, &expected_feed_id)
+            || accounts.registry.is_some_and(|registry| {
+                registry.find(&feed.feed_id).is_some_and(|entry| {
+                    entry.address == screened_address && !entry.is_expired_at(slot)
+                })
+            })
+    };
+    let feeds: Vec<FeedValue> = verified.feeds.iter().filter(approved).copied().collect();
+
+    let consumer = consumer(accounts.instructions.as_ref())?;
+
+    if config.is_enabled(feature_flags::RISK_CEILINGS) {
+        let risk_ceiling = accounts
+            .risk_ceiling
+            .ok_or(ErrorCode::MissingRiskCeiling)?;
+        require_within_ceiling(risk_ceiling, &consumer, score)?;
+    }
+
+    require!(
+        accounts.integrator.is_some()
+            || !config.is_enabled(feature_flags::INTEGRATOR_ACCOUNTING),
+        ErrorCode::MissingIntegrator
+    );
+    if let Some(integrator) = accounts.integrator {
+        require_keys_eq!(integrator.consumer, consumer, ErrorCode::IntegratorMismatch);
+        require!(
+            integrator.record_verification(),
+            ErrorCode::IntegratorQuotaExhausted
+        );
+    }
+
+    if let Some((marker, bump)) = accounts.quote_marker {
+        marker.address = screened_address;
+        marker.score = score;
+        marker.feed_id = expected_feed_id;
+        marker.slot = slot;
+        marker.quote_hash = quote_hash(accounts.instructions.as_ref())?;
+        marker.consumer = consumer;
+        marker.bump = bump;
+    }
+
+    if let (Some((receipt, bump)), Some(payer)) = (accounts.receipt, accounts.payer) {
+        receipt.address = screened_address;
+        receipt.score = score;
+        receipt.feed_id = expected_feed_id;
+        receipt.quote_slot = verified.quote_slot;
+        receipt.issued_slot = slot;
+        receipt.expires_slot = slot + RECEIPT_TTL_SLOTS;
+        receipt.rent_payer = payer.key();
+        receipt.consumer = consumer;
+        receipt.bump = bump;
+    }
+
+    if let Some((history, bump)) = accounts.history {
+        // Both are required to create the history.
+        let (Some(payer), Some(system_program)) = (accounts.payer, accounts.system_program) else {
+            return Err(anchor_lang::error::ErrorCode::ConstraintAccountIsNone.into());
+        };
+        growth::reserve_entry(
+            &history.to_account_info(),
+            history.entries.len(),
+            HISTORY_CAPACITY,
+            ScoreHistory::space,
+            &payer.to_account_info(),
+            &system_program.to_account_info(),
+        )?;
+        history.address = screened_address;
+        history.bump = bump;
+        history.push(HistoryEntry {
+            score,
+            quote_slot: verified.quote_slot,
+        });
+    }
+
+    emit!(RiskScoreVerified {
+        address: screened_address,
+        feed_id: expected_feed_id,
+        score,
+        quote_slot: verified.quote_slot,
+        consumer,
+        feeds: feeds.clone(),
+        severity: Severity::of(score),
+    });
+
+    #[cfg(feature = "heap-log")]
+    msg!("{}", risk_oracle_shared::heap::usage(crate::heap_log::HEAP.used()));
+
+    Ok(VerifiedScore {
+        score,
+        oracle_samples: verified.oracle_samples,
+        feeds,
+    })
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::describe::Writer;
+use risk_oracle_shared::profiles;
+
+use crate::state::{
+    feature_flags, Config, FreshnessSla, Momentum, ScoreCache, ScoreProvenance, CACHE_SEED,
+    CONFIG_SEED, SLA_SEED,
+};
+
+#[derive(Accounts)]
+pub struct GetCachedScore<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: Only its key seeds the cache PDA
+    pub query_account: UncheckedAccount<'info>,
+    #[account(seeds = [CACHE_SEED, query_account.key().as_ref()], bump = cache.bump)]
+    pub cache: Account<'info, ScoreCache>,
+    /// When passed, the view says whether the cache is within its SLA.
+    #[account(seeds = [SLA_SEED, query_account.key().as_ref()], bump = freshness_sla.bump)]
+    pub freshness_sla: Option<Account<'info, FreshnessSla>>,
+}
+
+#[derive(Accounts)]
+pub struct GetConfig<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct Describe<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+/// Cached score with the values derived from it on-chain, returned by
+/// `get_cached_score`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct CachedScoreView {
+    pub address: Pubkey,
+    pub score: u8,
+    pub networks: u8,
+    pub momentum: Momentum,
+    pub quote_slot: u64,
+    pub updated_slot: u64,
+    /// Slots since the cache was written.
+    pub age_slots: u64,
+    /// Whether threshold gating, as configured now, lets the score through.
+    pub passes_threshold: bool,
+    pub provenance: ScoreProvenance,
+    /// Fee payer of the last update.
+    pub updated_by: Pubkey,
+    /// Whether the cache is within its freshness SLA, when the SLA account
+    /// was passed.
+    pub within_sla: Option<bool>,
+}
+
+/// Config with the limits of the profile the program was built for, returned
+/// by `get_config`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ConfigView {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub flags: u64,
+    pub risk_threshold: u8,
+    pub fee_lamports: u64,
+    pub max_quote_age_slots: u64,
+    pub min_oracle_samples: u8,
+    pub emergency_threshold: u8,
+    pub emergency_expiry_slot: u64,
+    /// Threshold the gates compare against at the simulated slot.
+    pub effective_threshold: u8,
+}
+
+/// View: returns the cached score of `query_account` and what the program
+/// derives from it (age, threshold outcome, SLA), so frontends show what
+/// gating would decide. Writes nothing, meant to be simulated.
+pub fn get_cached_score(ctx: Context<GetCachedScore>) -> Result<CachedScoreView> {
+    let slot = Clock::get()?.slot;
+    let cache = &ctx.accounts.cache;
+    Ok(CachedScoreView {
+        address: cache.address,
+        score: cache.score,
+        networks: cache.networks,
+        momentum: cache.momentum,
+        quote_slot: cache.quote_slot,
+        updated_slot: cache.updated_slot,
+        age_slots: slot.saturating_sub(cache.updated_slot),
+        passes_threshold: ctx.accounts.config.passes_threshold(cache.score),
+        provenance: cache.provenance,
+        updated_by: cache.updated_by,
+        within_sla: ctx.accounts.freshness_sla.as_ref().map(|sla| sla.is_within_at(slot)),
+    })
+}
+
+/// View: returns the config. Writes nothing, meant to be simulated.
+pub fn get_config(ctx: Context<GetConfig>) -> Result<ConfigView> {
+    let config = &ctx.accounts.config;
+    Ok(ConfigView {
+        authority: config.authority,
+        treasury: config.treasury,
+        flags: config.flags,
+        risk_threshold: config.risk_threshold,
+        fee_lamports: config.fee_lamports,
+        max_quote_age_slots: profiles::ACTIVE.max_quote_age_slots,
+        min_oracle_samples: profiles::ACTIVE.min_oracle_samples,
+        emergency_threshold: config.emergency_threshold,
+        emergency_expiry_slot: config.emergency_expiry_slot,
+        effective_threshold: config.effective_threshold(),
+    })
+}
+
+/// View: returns the config, the profile's limits and every feature flag by
+/// name, encoded as `risk_oracle_shared::describe` entries, so explorers
+/// render the deployment without its layouts. Writes nothing, meant to be
+/// simulated.
+pub fn describe(ctx: Context<Describe>) -> Result<Vec<u8>> {
+    let config = &ctx.accounts.config;
+    let mut writer = Writer::new();
+    writer
+        .text("profile", profiles::ACTIVE.name)
+        .pubkey("authority", &config.authority.to_bytes())
+        .pubkey("treasury", &config.treasury.to_bytes())
+        .unsigned("flags", config.flags)
+        .unsigned("risk_threshold", config.risk_threshold.into())
+        .unsigned("effective_threshold", config.effective_threshold().into())
+        .text("threshold_boundary", config.boundary().as_str())
+        .unsigned("fee_lamports", config.fee_lamports)
+        .unsigned("emergency_threshold", config.emergency_threshold.into())
+        .unsigned("emergency_expiry_slot", config.emergency_expiry_slot)
+        .unsigned("ramp_from", config.ramp_from.into())
+        .unsigned("ramp_start_slot", config.ramp_start_slot)
+        .unsigned("ramp_end_slot", config.ramp_end_slot)
+        .unsigned("max_quote_age_slots", profiles::ACTIVE.max_quote_age_slots)
+        .unsigned(
+            "min_oracle_samples",
+            profiles::ACTIVE.min_oracle_samples.into(),
+        );
+    for (flag, name) in feature_flags::NAMES {
+        writer.bool(name, config.is_enabled(flag));
+    }
+    Ok(writer.finish())
+}
@@ -2,161 +2,493 @@
 #![allow(unexpected_cfgs)]
 
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::hash::hash;
-use prost::Message;
-use switchboard_on_demand::{default_queue, QueueAccountData};
-use switchboard_on_demand::{Instructions, QuoteVerifier, SlotHashes};
-use switchboard_protos::oracle_job::oracle_job::http_task::Header;
-use switchboard_protos::oracle_job::oracle_job::multiply_task;
-use switchboard_protos::oracle_job::oracle_job::task;
-use switchboard_protos::oracle_job::oracle_job::BoundTask;
-use switchboard_protos::oracle_job::oracle_job::HttpTask;
-use switchboard_protos::oracle_job::oracle_job::MultiplyTask;
-use switchboard_protos::oracle_job::oracle_job::{JsonParseTask, Task};
-use switchboard_protos::OracleFeed;
-use switchboard_protos::OracleJob;
+
+pub mod aliases;
+pub mod embed;
+pub mod error;
+pub mod events;
+#[cfg(feature = "cpi")]
+pub mod gate;
+mod growth;
+#[cfg(feature = "heap-log")]
+pub mod heap_log;
+pub mod instructions;
+pub mod state;
+pub mod verify;
+
+// The handlers share the instructions' names, which `#[program]` re-exports too.
+#[allow(ambiguous_glob_reexports)]
+pub use instructions::*;
+use state::{FeedProvenance, PolicyVerdict};
 
 declare_id!("Hiy3MrT746mmcEGDRyomPFCG1quUgLRYvUTxijWPshJH");
 #[program]
 pub mod anchor_oracle_example {
     use super::*;
 
-    pub fn verify_risk_score_feed<'a>(ctx: Context<VerifyRiskScoreFeed>) -> Result<()> {
-        let mut verifier = QuoteVerifier::new();
-        let slot = Clock::get()?.slot;
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        instructions::config::initialize_config(ctx, params)
+    }
 
-        verifier
-            .queue(ctx.accounts.queue.as_ref())
-            .slothash_sysvar(ctx.accounts.slothashes.as_ref())
-            .ix_sysvar(ctx.accounts.instructions.as_ref())
-            .clock_slot(slot);
+    /// Replaces the enabled feature flags. Takes effect on the next verification.
+    pub fn set_feature_flags(ctx: Context<UpdateConfig>, flags: u64) -> Result<()> {
+        instructions::config::set_feature_flags(ctx, flags)
+    }
 
-        // Verify the Ed25519 instruction at index 0
-        let quote = verifier.verify_instruction_at(0).unwrap();
-        let quote_slot = quote.slot();
+    /// Replaces the gating threshold, given in provider-native or feed units.
+    pub fn set_risk_threshold(ctx: Context<UpdateConfig>, threshold: RiskThreshold) -> Result<()> {
+        instructions::config::set_risk_threshold(ctx, threshold)
+    }
 
-        // Ensure the quote is recent enough (within 50 slots).
-        //
-        if slot.saturating_sub(quote_slot) > 50 {
-            // Extra check: ensure the quote is fresh enough (within 30 slots).
-            msg!(
-                "Quote too old. Current slot: {}, quote slot: {}",
-                slot,
-                quote_slot
-            );
-            return Err(ErrorCode::StaleQuote.into());
-        }
+    /// Phases a new threshold in linearly between two slots.
+    pub fn schedule_threshold_ramp(
+        ctx: Context<UpdateConfig>,
+        threshold: RiskThreshold,
+        start_slot: u64,
+        end_slot: u64,
+    ) -> Result<()> {
+        instructions::config::schedule_threshold_ramp(ctx, threshold, start_slot, end_slot)
+    }
 
-        let feeds = quote.feeds();
-        require!(!feeds.is_empty(), ErrorCode::NoOracleFeeds);
+    /// Clamps gating down to an emergency threshold until `expiry_slot`.
+    pub fn set_emergency_threshold(
+        ctx: Context<UpdateConfig>,
+        threshold: RiskThreshold,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        instructions::config::set_emergency_threshold(ctx, threshold, expiry_slot)
+    }
 
-        let feed = &feeds[0];
-        let actual_feed_id = feed.feed_id();
+    /// Lifts the emergency threshold before it expires.
+    pub fn clear_emergency_threshold(ctx: Context<UpdateConfig>) -> Result<()> {
+        instructions::config::clear_emergency_threshold(ctx)
+    }
 
-        let derived_feed_id = create_risk_score_feed_id(&ctx.accounts.query_account.key())?;
+    /// Grows a config of the previous layout to the current one.
+    pub fn extend_config(ctx: Context<ExtendConfig>) -> Result<()> {
+        instructions::config::extend_config(ctx)
+    }
 
-        require!(*actual_feed_id == derived_feed_id, ErrorCode::FeedMismatch);
+    /// Emergency bulk denylist import, entry PDAs passed as remaining accounts.
+    pub fn import_denylist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ImportDenylist<'info>>,
+        addresses: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::denylist::import_denylist(ctx, addresses)
+    }
 
-        msg!(
-            "Verified risk score feed! Value: {}",
-            feed.value().to_string().as_str()
-        );
-        Ok(())
+    pub fn remove_from_denylist(ctx: Context<RemoveFromDenylist>, address: Pubkey) -> Result<()> {
+        instructions::denylist::remove_from_denylist(ctx, address)
     }
-}
 
-fn create_risk_score_feed_id(query_pubkey: &Pubkey) -> Result<[u8; 32]> {
-    let addr_b58 = bs58::encode(query_pubkey).into_string();
-    let url = format!(
-        "https://api.range.org/v1/risk/address?address={}&network=solana",
-        addr_b58
-    );
-
-    let feed = OracleFeed {
-        name: Some("Risk Score".to_string()),
-        jobs: vec![OracleJob {
-            tasks: vec![
-                Task {
-                    task: Some(task::Task::HttpTask(HttpTask {
-                        url: Some(url),
-                        headers: [
-                            Header {
-                                key: Some("accept".to_string()),
-                                value: Some("application/json".to_string()),
-                            },
-                            Header {
-                                key: Some("X-API-KEY".to_string()),
-                                value: Some("${RANGE_API_KEY}".to_string()),
-                            },
-                        ]
-                        .into(),
-                        ..Default::default()
-                    })),
-                },
-                Task {
-                    task: Some(task::Task::JsonParseTask(JsonParseTask {
-                        path: Some("$.riskScore".to_string()),
-                        // aggregation_method: Some(1), // optional; not needed for single value
-                        ..Default::default()
-                    })),
-                },
-                Task {
-                    task: Some(task::Task::MultiplyTask(MultiplyTask {
-                        multiple: Some(multiply_task::Multiple::Scalar(10.0)), // 0–10 => 0–100
-                    })),
-                },
-                Task {
-                    task: Some(task::Task::BoundTask(BoundTask {
-                        lower_bound_value: Some("0".into()),
-                        upper_bound_value: Some("100".into()),
-                        on_exceeds_lower_bound_value: Some("0".into()),
-                        on_exceeds_upper_bound_value: Some("100".into()),
-                        ..Default::default()
-                    })),
-                },
-            ],
-            weight: None,
-        }],
-        min_job_responses: Some(1),
-        min_oracle_samples: Some(1),
-        max_job_range_pct: Some(100),
-    };
-
-    // Encode as protobuf length-delimited bytes using prost::Message trait
-    let bytes = OracleFeed::encode_length_delimited_to_vec(&feed);
-
-    // Hash the protobuf bytes
-    Ok(hash(&bytes).to_bytes())
-}
+    /// Lists and unlists addresses in one segment of the packed denylist.
+    pub fn update_denylist_segment(
+        ctx: Context<UpdateDenylistSegment>,
+        segment: u16,
+        add: Vec<Pubkey>,
+        remove: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::denylist_segment::update_denylist_segment(ctx, segment, add, remove)
+    }
 
-#[derive(Accounts)]
-pub struct VerifyRiskScoreFeed<'info> {
-    #[account(address = default_queue())]
-    pub queue: AccountLoader<'info, QueueAccountData>,
-    pub clock: Sysvar<'info, Clock>, // This is actually not used as anchor uses
-    pub slothashes: Sysvar<'info, SlotHashes>,
-    pub instructions: Sysvar<'info, Instructions>,
-    /// CHECK: This doesnt need to be checked we just need the pubkey to build the feed id
-    pub query_account: UncheckedAccount<'info>,
-}
+    /// Replaces the programs denied in verified transactions.
+    pub fn set_program_denylist(
+        ctx: Context<SetProgramDenylist>,
+        programs: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::program_screening::set_program_denylist(ctx, programs)
+    }
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("No oracle feeds available")]
-    NoOracleFeeds,
+    /// Adds a program to the program denylist.
+    pub fn flag_program(ctx: Context<SetProgramDenylist>, program: Pubkey) -> Result<()> {
+        instructions::program_screening::flag_program(ctx, program)
+    }
+
+    pub fn unflag_program(ctx: Context<UpdateProgramDenylist>, program: Pubkey) -> Result<()> {
+        instructions::program_screening::unflag_program(ctx, program)
+    }
+
+    /// Fails when an instruction of the transaction targets a denied program.
+    pub fn screen_programs(ctx: Context<ScreenPrograms>) -> Result<()> {
+        instructions::program_screening::screen_programs(ctx)
+    }
 
-    #[msg("Feed hash mismatch - oracle feed does not match expected configuration")]
-    FeedMismatch,
+    /// Commits the merkle root of a sorted denylist.
+    pub fn set_denylist_root(
+        ctx: Context<SetDenylistRoot>,
+        root: [u8; 32],
+        leaf_count: u32,
+    ) -> Result<()> {
+        instructions::merkle_denylist::set_denylist_root(ctx, root, leaf_count)
+    }
 
-    #[msg("Invalid feed JSON")]
-    InvalidFeedJson,
+    /// Verifies a merkle (non-)membership proof, returns whether the screened
+    /// wallet is listed.
+    pub fn check_denylist(ctx: Context<CheckDenylist>, proof: DenylistProof) -> Result<bool> {
+        instructions::merkle_denylist::check_denylist(ctx, proof)
+    }
 
-    #[msg("Failed to create quote verifier")]
-    VerifierError,
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+        instructions::registry::initialize_registry(ctx)
+    }
 
-    #[msg("Failed to verify Ed25519 instruction")]
-    VerificationFailed,
+    pub fn register_feed(
+        ctx: Context<RegisterFeed>,
+        address: Pubkey,
+        feed_id: [u8; 32],
+        provenance: Option<FeedProvenance>,
+    ) -> Result<()> {
+        instructions::registry::register_feed(ctx, address, feed_id, provenance)
+    }
+
+    pub fn unregister_feed(ctx: Context<UpdateRegistry>, feed_id: [u8; 32]) -> Result<()> {
+        instructions::registry::unregister_feed(ctx, feed_id)
+    }
+
+    /// Rejects verifications against `feed_id` from `expires_at_slot` on.
+    pub fn set_feed_expiry(
+        ctx: Context<UpdateRegistry>,
+        feed_id: [u8; 32],
+        expires_at_slot: Option<u64>,
+    ) -> Result<()> {
+        instructions::registry::set_feed_expiry(ctx, feed_id, expires_at_slot)
+    }
+
+    /// Sets the variable names the feeds of template `schema` may use.
+    pub fn set_template_variables(
+        ctx: Context<SetTemplateVariables>,
+        schema: u8,
+        names: Vec<String>,
+    ) -> Result<()> {
+        instructions::template_variables::set_template_variables(ctx, schema, names)
+    }
 
-    #[msg("Stale quote - the quote is too old")]
-    StaleQuote,
+    /// Pins the feed encoded in `encoded` for `address`, once its variable
+    /// placeholders are checked against those of template `schema`.
+    pub fn register_feed_template(
+        ctx: Context<RegisterFeedTemplate>,
+        address: Pubkey,
+        schema: u8,
+        encoded: Vec<u8>,
+    ) -> Result<()> {
+        instructions::template_variables::register_feed_template(ctx, address, schema, encoded)
+    }
+
+    /// Original account layout, with the clock account, the config following
+    /// the query account.
+    pub fn verify_risk_score_feed(
+        ctx: Context<VerifyRiskScoreFeedLegacy>,
+    ) -> Result<VerifiedScore> {
+        instructions::verify_risk_score_feed::verify_risk_score_feed(ctx)
+    }
+
+    /// Same as `verify_risk_score_feed` without the clock account.
+    pub fn verify_risk_score_feed_compact(
+        ctx: Context<VerifyRiskScoreFeed>,
+    ) -> Result<VerifiedScore> {
+        instructions::verify_risk_score_feed::verify_risk_score_feed_compact(ctx)
+    }
+
+    /// `verify_risk_score_feed_compact`, failing past `not_after_slot`.
+    pub fn verify_risk_score_feed_with_deadline(
+        ctx: Context<VerifyRiskScoreFeed>,
+        not_after_slot: u64,
+    ) -> Result<VerifiedScore> {
+        instructions::verify_risk_score_feed::verify_risk_score_feed_with_deadline(
+            ctx,
+            not_after_slot,
+        )
+    }
+
+    /// ATA mode: screens the wallet passed as `query_account`, see
+    /// `instructions::verify_risk_score_feed_for_ata`.
+    pub fn verify_risk_score_feed_for_ata(
+        ctx: Context<VerifyRiskScoreFeed>,
+        mint: Pubkey,
+        token_program: Pubkey,
+    ) -> Result<VerifiedScore> {
+        instructions::verify_risk_score_feed::verify_risk_score_feed_for_ata(
+            ctx,
+            mint,
+            token_program,
+        )
+    }
+
+    /// Pinned mode: verifies against a registered feed id instead of deriving it.
+    pub fn verify_risk_score_feed_pinned(
+        ctx: Context<VerifyRiskScoreFeed>,
+        feed_id: [u8; 32],
+    ) -> Result<VerifiedScore> {
+        instructions::verify_risk_score_feed::verify_risk_score_feed_pinned(ctx, feed_id)
+    }
+
+    /// Verifies the score and blacklist flag feed pair, failing on a flag.
+    pub fn verify_risk_score_with_flag(ctx: Context<VerifyRiskScoreFeed>) -> Result<VerifiedScore> {
+        instructions::verify_risk_score_feed::verify_risk_score_with_flag(ctx)
+    }
+
+    /// Verifies the score and as-of time feed pair, returning when Range
+    /// computed the score next to when the quote was signed.
+    pub fn verify_risk_score_with_as_of(
+        ctx: Context<VerifyRiskScoreFeed>,
+    ) -> Result<VerifiedScoreAsOf> {
+        instructions::verify_risk_score_feed::verify_risk_score_with_as_of(ctx)
+    }
+
+    /// Verifies the constant test feed (devnet profile only).
+    pub fn verify_test_feed(ctx: Context<VerifyRiskScoreFeed>) -> Result<VerifiedScore> {
+        instructions::verify_risk_score_feed::verify_test_feed(ctx)
+    }
+
+    /// Verifies the test feed end to end and emits a heartbeat (devnet
+    /// profile only).
+    pub fn health_check(ctx: Context<HealthCheck>) -> Result<()> {
+        instructions::health_check::health_check(ctx)
+    }
+
+    /// Derives the feed id of `query_account` once and stores it in its PDA.
+    pub fn derive_feed_id(ctx: Context<DeriveFeedId>) -> Result<()> {
+        instructions::derive_feed_id::derive_feed_id(ctx)
+    }
+
+    /// Hybrid mode: verifies against the feed id stored by `derive_feed_id`.
+    pub fn verify_risk_score_feed_cached(
+        ctx: Context<VerifyRiskScoreFeed>,
+    ) -> Result<VerifiedScore> {
+        instructions::verify_risk_score_feed::verify_risk_score_feed_cached(ctx)
+    }
+
+    /// Verifies the multi-network composite feed and caches the score.
+    pub fn verify_composite_risk_score(
+        ctx: Context<VerifyCompositeRiskScore>,
+        networks: u8,
+    ) -> Result<CompositeScore> {
+        instructions::verify_composite_risk_score::verify_composite_risk_score(ctx, networks)
+    }
+
+    /// Crank: writes every score cache of the remaining accounts whose feed
+    /// the quote carries, verifying the quote once.
+    pub fn refresh_score_caches<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefreshScoreCaches<'info>>,
+    ) -> Result<u16> {
+        instructions::refresh_score_caches::refresh_score_caches(ctx)
+    }
+
+    /// Cross-checks a score cache against a fresh quote, emitting
+    /// `CacheDivergence` (and correcting it with `correct`) beyond `tolerance`.
+    pub fn reconcile(ctx: Context<Reconcile>, tolerance: u8, correct: bool) -> Result<bool> {
+        instructions::reconcile::reconcile(ctx, tolerance, correct)
+    }
+
+    /// Verifies the risk score feeds of the remaining accounts against one
+    /// quote. With `partial`, returns which matched and passed instead of
+    /// failing on the first that doesn't.
+    pub fn verify_risk_score_batch(
+        ctx: Context<VerifyRiskScoreBatch>,
+        partial: bool,
+    ) -> Result<BatchVerification> {
+        instructions::verify_risk_score_batch::verify_risk_score_batch(ctx, partial)
+    }
+
+    /// Uses the cached composite score when fresh, verifies the attached
+    /// quote and refreshes the cache otherwise.
+    pub fn check_or_verify_score(
+        ctx: Context<CheckOrVerifyScore>,
+        networks: u8,
+        max_age_slots: u64,
+    ) -> Result<ScoreCheck> {
+        instructions::check_or_verify_score::check_or_verify_score(ctx, networks, max_age_slots)
+    }
+
+    /// Sets the freshness SLA of `query_account`, in slots.
+    pub fn set_freshness_sla(ctx: Context<SetFreshnessSla>, max_age_slots: u64) -> Result<()> {
+        instructions::freshness_sla::set_freshness_sla(ctx, max_age_slots)
+    }
+
+    /// Returns whether the cache of `query_account` is within its SLA.
+    pub fn is_within_sla(ctx: Context<IsWithinSla>) -> Result<bool> {
+        instructions::freshness_sla::is_within_sla(ctx)
+    }
+
+    /// Replaces the programs allowed to reveal confidential scores.
+    pub fn set_access_list(ctx: Context<SetAccessList>, consumers: Vec<Pubkey>) -> Result<()> {
+        instructions::confidential_score::set_access_list(ctx, consumers)
+    }
+
+    /// Confidential mode: verifies the composite feed and stores a salted
+    /// commitment to the score instead of the score.
+    pub fn verify_confidential_risk_score(
+        ctx: Context<VerifyConfidentialRiskScore>,
+        networks: u8,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::confidential_score::verify_confidential_risk_score(ctx, networks, salt)
+    }
+
+    /// Returns a confidential score to an access-listed consumer.
+    pub fn reveal_score(ctx: Context<RevealScore>, score: u8, salt: [u8; 32]) -> Result<u8> {
+        instructions::confidential_score::reveal_score(ctx, score, salt)
+    }
+
+    /// View: the cached score of a query account with its derived values.
+    pub fn get_cached_score(ctx: Context<GetCachedScore>) -> Result<CachedScoreView> {
+        instructions::views::get_cached_score(ctx)
+    }
+
+    /// View: the config and the active profile's limits.
+    pub fn get_config(ctx: Context<GetConfig>) -> Result<ConfigView> {
+        instructions::views::get_config(ctx)
+    }
+
+    /// View: the config and feature flags as self-describing entries.
+    pub fn describe(ctx: Context<Describe>) -> Result<Vec<u8>> {
+        instructions::views::describe(ctx)
+    }
+
+    /// Threshold proof mode: records whether the score is below the threshold
+    /// and a commitment to it, never the score.
+    pub fn verify_threshold_proof(
+        ctx: Context<VerifyThresholdProof>,
+        salt: [u8; 32],
+    ) -> Result<bool> {
+        instructions::threshold_proof::verify_threshold_proof(ctx, salt)
+    }
+
+    /// Returns the score verified earlier in the same transaction (see
+    /// `instructions::consume_verified_score`).
+    pub fn consume_verified_score(ctx: Context<ConsumeVerifiedScore>) -> Result<u8> {
+        instructions::consume_verified_score::consume_verified_score(ctx)
+    }
+
+    /// Closes an expired verification receipt, refunding its payer.
+    pub fn close_receipt(ctx: Context<CloseReceipt>) -> Result<()> {
+        instructions::close_receipt::close_receipt(ctx)
+    }
+
+    /// Creates an empty quote account of the signer.
+    pub fn create_quote_account(ctx: Context<CreateQuoteAccount>, id: u64) -> Result<()> {
+        instructions::quote_account::create_quote_account(ctx, id)
+    }
+
+    /// Verifies the attached quote and posts it to a quote account.
+    pub fn write_quote_account(ctx: Context<WriteQuoteAccount>) -> Result<()> {
+        instructions::quote_account::write_quote_account(ctx)
+    }
+
+    /// Verifies a risk score against the quote posted to a quote account.
+    pub fn verify_risk_score_account(
+        ctx: Context<VerifyRiskScoreAccount>,
+        max_age_slots: u64,
+    ) -> Result<AccountScore> {
+        instructions::quote_account::verify_risk_score_account(ctx, max_age_slots)
+    }
+
+    /// Closes a quote account, refunding its owner.
+    pub fn close_quote_account(ctx: Context<CloseQuoteAccount>) -> Result<()> {
+        instructions::quote_account::close_quote_account(ctx)
+    }
+
+    /// Gates on the time-weighted average of the recorded scores.
+    pub fn check_risk_score_twa(ctx: Context<CheckRiskScoreTwa>, window_slots: u64) -> Result<u8> {
+        instructions::check_risk_score_twa::check_risk_score_twa(ctx, window_slots)
+    }
+
+    /// Registers a consuming program for integrator accounting, or reprices it.
+    pub fn register_integrator(
+        ctx: Context<RegisterIntegrator>,
+        consumer: Pubkey,
+        price_lamports: u64,
+    ) -> Result<()> {
+        instructions::integrator::register_integrator(ctx, consumer, price_lamports)
+    }
+
+    /// Adds prepaid lamports to an integrator's balance.
+    pub fn top_up_integrator(ctx: Context<TopUpIntegrator>, lamports: u64) -> Result<()> {
+        instructions::integrator::top_up_integrator(ctx, lamports)
+    }
+
+    /// Sweeps an integrator's charged lamports to the treasury.
+    pub fn collect_integrator_fees(ctx: Context<CollectIntegratorFees>) -> Result<()> {
+        instructions::integrator::collect_integrator_fees(ctx)
+    }
+
+    /// Caps the scores a consuming program accepts and names its risk officer.
+    pub fn set_risk_ceiling(
+        ctx: Context<SetRiskCeiling>,
+        consumer: Pubkey,
+        officer: Pubkey,
+        max_score: u8,
+    ) -> Result<()> {
+        instructions::risk_ceiling::set_risk_ceiling(ctx, consumer, officer, max_score)
+    }
+
+    /// Moves a risk ceiling, signed by its risk officer.
+    pub fn update_risk_ceiling(ctx: Context<UpdateRiskCeiling>, max_score: u8) -> Result<()> {
+        instructions::risk_ceiling::update_risk_ceiling(ctx, max_score)
+    }
+
+    /// Sets the penalty cached scores take as their quote ages.
+    pub fn set_score_decay(
+        ctx: Context<SetScoreDecay>,
+        grace_slots: u64,
+        interval_slots: u64,
+        penalty: u8,
+        max_penalty: u8,
+    ) -> Result<()> {
+        instructions::score_decay::set_score_decay(
+            ctx,
+            grace_slots,
+            interval_slots,
+            penalty,
+            max_penalty,
+        )
+    }
+
+    /// Sets the policy of a screened wallet, overriding the config's threshold.
+    pub fn set_address_policy(
+        ctx: Context<SetAddressPolicy>,
+        address: Pubkey,
+        verdict: PolicyVerdict,
+        threshold: Option<u8>,
+        min_oracle_samples: u8,
+    ) -> Result<()> {
+        instructions::address_policy::set_address_policy(
+            ctx,
+            address,
+            verdict,
+            threshold,
+            min_oracle_samples,
+        )
+    }
+
+    /// Removes the policy of a screened wallet.
+    pub fn remove_address_policy(ctx: Context<RemoveAddressPolicy>, address: Pubkey) -> Result<()> {
+        instructions::address_policy::remove_address_policy(ctx, address)
+    }
+
+    /// Sets the compliance officer whose vouchers can bypass the risk check.
+    pub fn set_compliance_officer(
+        ctx: Context<SetComplianceOfficer>,
+        officer: Pubkey,
+    ) -> Result<()> {
+        instructions::bypass_voucher::set_compliance_officer(ctx, officer)
+    }
+
+    /// Redeems a compliance officer's one-time bypass voucher, see
+    /// `instructions::bypass_voucher`.
+    pub fn redeem_bypass_voucher(
+        ctx: Context<RedeemBypassVoucher>,
+        voucher: BypassVoucher,
+    ) -> Result<()> {
+        instructions::bypass_voucher::redeem_bypass_voucher(ctx, voucher)
+    }
+
+    /// Runs legacy instructions as their replacements, see `aliases`.
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        aliases::dispatch(program_id, accounts, data)
+    }
 }
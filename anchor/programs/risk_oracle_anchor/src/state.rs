@@ -0,0 +1,1047 @@
+use anchor_lang::prelude::*;
+use risk_oracle_shared::decay::Decay;
+use risk_oracle_shared::denylist_segment::MAX_SEGMENT_ENTRIES;
+use risk_oracle_shared::feed_id::FeedIdDerivation;
+use risk_oracle_shared::feed_variables::MAX_VARIABLE_LEN;
+use risk_oracle_shared::threshold::{Boundary, Ramp};
+use risk_oracle_shared::{constant_time, denylist_segment, feed, profiles, severity};
+#[cfg(feature = "serde")]
+use risk_oracle_shared::serialize::{base58, base58_vec, hex};
+
+// PDA seeds live in the shared crate, so clients derive the same addresses.
+pub use risk_oracle_shared::seeds::{
+    ACCESS_LIST_SEED, ADDRESS_POLICY_SEED, BYPASS_SEED, CACHE_SEED, COMPLIANCE_OFFICER_SEED,
+    CONFIDENTIAL_SEED, CONFIG_SEED, DENYLIST_ROOT_SEED, DENYLIST_SEED, DENYLIST_SEGMENT_SEED,
+    FEED_ID_SEED, HISTORY_SEED, INTEGRATOR_SEED, MARKER_SEED, PROGRAM_DENYLIST_SEED,
+    QUOTE_ACCOUNT_SEED, RECEIPT_SEED, REGISTRY_SEED, REPLAY_SEED, RISK_CEILING_SEED,
+    SCORE_DECAY_SEED, SLA_SEED, TEMPLATE_VARIABLES_SEED, THRESHOLD_PROOF_SEED,
+};
+
+/// Maximum number of programs in the [`ConsumerAccessList`].
+pub const MAX_ACCESS_LIST_ENTRIES: usize = 16;
+
+/// Maximum number of programs in the [`ProgramDenylist`].
+pub const MAX_DENIED_PROGRAMS: usize = 16;
+
+/// Maximum number of variable names of a [`TemplateVariables`].
+pub const MAX_TEMPLATE_VARIABLES: usize = 4;
+
+/// Maximum number of addresses per `import_denylist` call (transaction size).
+pub const MAX_DENYLIST_BATCH: usize = 16;
+
+/// Maximum number of query accounts per `verify_risk_score_batch` call, one
+/// bit each of its result bitmaps.
+pub const MAX_BATCH_ADDRESSES: usize = 64;
+
+/// Maximum number of feeds a [`QuoteAccount`] holds: every feed a quote of the
+/// active profile may carry.
+pub const MAX_QUOTE_ACCOUNT_FEEDS: usize = 8;
+const _: () = assert!(profiles::ACTIVE.max_quote_feeds <= MAX_QUOTE_ACCOUNT_FEEDS);
+
+/// Slots a [`VerificationReceipt`] stays valid after it is written (~1 minute).
+pub const RECEIPT_TTL_SLOTS: u64 = 150;
+
+/// Longest an emergency threshold lasts from when it is set (~1 day); the
+/// authority sets it again to extend it.
+pub const MAX_EMERGENCY_SLOTS: u64 = 216_000;
+
+/// Bits of [`Config::flags`].
+///
+/// Each flag turns on an optional behavior of `verify_risk_score_feed`, so an
+/// operator can roll features out gradually without redeploying.
+pub mod feature_flags {
+    /// Compare the sysvar accounts against the canonical sysvar ids instead of
+    /// relying on the wrapper types alone.
+    pub const STRICT_SYSVAR_CHECKS: u64 = 1 << 0;
+    /// Reject quotes that are not newer than the last one accepted for the
+    /// same query account (requires the `replay_record` account).
+    pub const ANTI_REPLAY: u64 = 1 << 1;
+    /// Fail when the verified score doesn't pass `Config::risk_threshold`
+    /// (an emergency threshold gates even with the flag off).
+    pub const THRESHOLD_GATING: u64 = 1 << 2;
+    /// Charge `Config::fee_lamports` to the payer (requires the `payer`,
+    /// `treasury` and `system_program` accounts).
+    pub const FEE_COLLECTION: u64 = 1 << 3;
+    /// Reject denylisted wallets before verifying the quote (requires the
+    /// `denylist_entry` account).
+    pub const DENYLIST: u64 = 1 << 4;
+    /// Meter every verification against the consuming program's
+    /// [`Integrator`](super::Integrator) account (requires the `integrator`
+    /// account).
+    pub const INTEGRATOR_ACCOUNTING: u64 = 1 << 5;
+    /// Reject quotes carrying any feed besides the expected ones, so no
+    /// unrelated feed rides along for downstream code to read by mistake.
+    pub const STRICT_QUOTE_FEEDS: u64 = 1 << 6;
+    /// Fail scores equal to `Config::risk_threshold` too: gates pass scores
+    /// strictly below it instead of at most it (see
+    /// `risk_oracle_shared::threshold::Boundary`).
+    pub const EXCLUSIVE_THRESHOLD: u64 = 1 << 7;
+    /// Reject transactions with an instruction targeting a program of the
+    /// [`ProgramDenylist`](super::ProgramDenylist) (requires the
+    /// `program_denylist` account).
+    pub const PROGRAM_DENYLIST: u64 = 1 << 8;
+    /// Derive feed ids as the gateway's `FeedRequestV2::feed_id()` does, from
+    /// the bare feed encoding instead of the length-delimited one (see
+    /// `risk_oracle_shared::feed_id`).
+    pub const FEED_REQUEST_V2_IDS: u64 = 1 << 9;
+    /// Fail scores above the consuming program's
+    /// [`RiskCeiling`](super::RiskCeiling), whatever the threshold (requires
+    /// the `risk_ceiling` account).
+    pub const RISK_CEILINGS: u64 = 1 << 10;
+    /// Apply the screened wallet's [`AddressPolicy`](super::AddressPolicy)
+    /// over the config's threshold (requires the `address_policy` account).
+    pub const ADDRESS_POLICIES: u64 = 1 << 11;
+    /// Reject wallets listed in their
+    /// [`DenylistSegment`](super::DenylistSegment) before verifying the quote
+    /// (requires the `denylist_segment` account).
+    pub const SEGMENTED_DENYLIST: u64 = 1 << 12;
+    /// Gate cached scores in `check_or_verify_score` with the
+    /// [`ScoreDecay`](super::ScoreDecay) penalty of their age added
+    /// (requires the `score_decay` account).
+    pub const SCORE_DECAY: u64 = 1 << 13;
+
+    /// Every flag known to this program version.
+    pub const ALL: u64 = STRICT_SYSVAR_CHECKS
+        | ANTI_REPLAY
+        | THRESHOLD_GATING
+        | FEE_COLLECTION
+        | DENYLIST
+        | INTEGRATOR_ACCOUNTING
+        | STRICT_QUOTE_FEEDS
+        | EXCLUSIVE_THRESHOLD
+        | PROGRAM_DENYLIST
+        | FEED_REQUEST_V2_IDS
+        | RISK_CEILINGS
+        | ADDRESS_POLICIES
+        | SEGMENTED_DENYLIST
+        | SCORE_DECAY;
+
+    /// Name of every flag, as `describe` reports them.
+    pub const NAMES: [(u64, &str); 14] = [
+        (STRICT_SYSVAR_CHECKS, "strict_sysvar_checks"),
+        (ANTI_REPLAY, "anti_replay"),
+        (THRESHOLD_GATING, "threshold_gating"),
+        (FEE_COLLECTION, "fee_collection"),
+        (DENYLIST, "denylist"),
+        (INTEGRATOR_ACCOUNTING, "integrator_accounting"),
+        (STRICT_QUOTE_FEEDS, "strict_quote_feeds"),
+        (EXCLUSIVE_THRESHOLD, "exclusive_threshold"),
+        (PROGRAM_DENYLIST, "program_denylist"),
+        (FEED_REQUEST_V2_IDS, "feed_request_v2_ids"),
+        (RISK_CEILINGS, "risk_ceilings"),
+        (ADDRESS_POLICIES, "address_policies"),
+        (SEGMENTED_DENYLIST, "segmented_denylist"),
+        (SCORE_DECAY, "score_decay"),
+    ];
+
+    // A flag added to `ALL` needs a name too.
+    const _: () = {
+        let mut named = 0;
+        let mut i = 0;
+        while i < NAMES.len() {
+            named |= NAMES[i].0;
+            i += 1;
+        }
+        assert!(named == ALL);
+    };
+}
+
+/// Program-wide configuration, owned by `authority`.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Config {
+    /// Key allowed to update this account.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub authority: Pubkey,
+    /// Account receiving verification fees.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub treasury: Pubkey,
+    /// Enabled [`feature_flags`].
+    pub flags: u64,
+    /// Highest accepted score when threshold gating is enabled, always on the
+    /// feed scale (0–100), see `RiskThreshold`. The target of the threshold
+    /// ramp while one is scheduled.
+    pub risk_threshold: u8,
+    /// Fee charged per verification when fee collection is enabled.
+    pub fee_lamports: u64,
+    pub bump: u8,
+    /// Threshold set by `set_emergency_threshold`, in force instead of
+    /// `risk_threshold` when lower, until `emergency_expiry_slot`.
+    pub emergency_threshold: u8,
+    /// First slot the emergency threshold is no longer in force, 0 when none
+    /// was set.
+    pub emergency_expiry_slot: u64,
+    /// Threshold the ramp scheduled by `schedule_threshold_ramp` starts from,
+    /// moving to `risk_threshold` between the two ramp slots.
+    pub ramp_from: u8,
+    pub ramp_start_slot: u64,
+    /// First slot `risk_threshold` applies again, 0 when no ramp was
+    /// scheduled.
+    pub ramp_end_slot: u64,
+}
+
+/// Size of a [`Config`] created before the emergency threshold and ramp
+/// fields, which `extend_config` grows to the current one.
+pub const CONFIG_V1_LEN: usize = 8 + 32 + 32 + 8 + 1 + 8 + 1;
+const _: () = assert!(CONFIG_V1_LEN + 1 + 8 + 1 + 8 + 8 == 8 + Config::INIT_SPACE);
+
+impl Config {
+    pub fn is_enabled(&self, flag: u64) -> bool {
+        self.flags & flag == flag
+    }
+
+    /// Feed id derivation of the verifications reading the config,
+    /// length-delimited unless [`feature_flags::FEED_REQUEST_V2_IDS`] is
+    /// enabled.
+    pub fn feed_id_derivation(&self) -> FeedIdDerivation {
+        if self.is_enabled(feature_flags::FEED_REQUEST_V2_IDS) {
+            FeedIdDerivation::FeedRequestV2
+        } else {
+            FeedIdDerivation::LengthDelimited
+        }
+    }
+
+    /// Comparison at `risk_threshold`, inclusive unless
+    /// [`feature_flags::EXCLUSIVE_THRESHOLD`] is enabled.
+    pub fn boundary(&self) -> Boundary {
+        if self.is_enabled(feature_flags::EXCLUSIVE_THRESHOLD) {
+            Boundary::Exclusive
+        } else {
+            Boundary::Inclusive
+        }
+    }
+
+    /// The scheduled threshold ramp, `None` when none was. Past its end it
+    /// reads `risk_threshold`.
+    pub fn ramp(&self) -> Option<Ramp> {
+        (self.ramp_end_slot != 0).then_some(Ramp {
+            from: self.ramp_from,
+            to: self.risk_threshold,
+            start_slot: self.ramp_start_slot,
+            end_slot: self.ramp_end_slot,
+        })
+    }
+
+    /// Whether the emergency threshold is in force at `slot`.
+    pub fn emergency_active_at(&self, slot: u64) -> bool {
+        slot < self.emergency_expiry_slot
+    }
+
+    /// Threshold at `slot` before any emergency: `risk_threshold`, or the
+    /// ramp's threshold while one is scheduled.
+    pub fn scheduled_threshold_at(&self, slot: u64) -> u8 {
+        self.ramp()
+            .map_or(self.risk_threshold, |ramp| ramp.at(slot))
+    }
+
+    /// Threshold the gates compare against at `slot`: the scheduled one,
+    /// lowered to the emergency threshold while it is in force.
+    pub fn threshold_at(&self, slot: u64) -> u8 {
+        self.under_emergency_at(slot, self.scheduled_threshold_at(slot))
+    }
+
+    /// `threshold`, lowered to the emergency threshold while it is in force
+    /// at `slot`.
+    pub fn under_emergency_at(&self, slot: u64, threshold: u8) -> u8 {
+        if self.emergency_active_at(slot) {
+            threshold.min(self.emergency_threshold)
+        } else {
+            threshold
+        }
+    }
+
+    /// [`Config::emergency_active_at`] the current slot. Never off-chain,
+    /// where there is no clock.
+    pub fn emergency_active(&self) -> bool {
+        Clock::get().is_ok_and(|clock| self.emergency_active_at(clock.slot))
+    }
+
+    /// [`Config::threshold_at`] the current slot; `risk_threshold`
+    /// off-chain, where there is no clock.
+    pub fn effective_threshold(&self) -> u8 {
+        Clock::get().map_or(self.risk_threshold, |clock| self.threshold_at(clock.slot))
+    }
+
+    /// Whether `score` passes the effective threshold with the configured
+    /// boundary, whether or not threshold gating is enabled.
+    pub fn within_threshold(&self, score: u8) -> bool {
+        self.boundary().passes(score, self.effective_threshold())
+    }
+
+    /// Whether threshold gating would let `score` through: always with the
+    /// flag off, unless an emergency threshold is in force, which gates
+    /// whatever the flags.
+    pub fn passes_threshold(&self, score: u8) -> bool {
+        let gating = self.is_enabled(feature_flags::THRESHOLD_GATING) || self.emergency_active();
+        !gating || self.within_threshold(score)
+    }
+
+    /// Whether `score` passes the threshold of an address policy in place of
+    /// the scheduled one, with the configured boundary. An emergency
+    /// threshold still lowers it; the gating flag doesn't apply.
+    pub fn within_policy_threshold(&self, score: u8, threshold: u8) -> bool {
+        let threshold = Clock::get().map_or(threshold, |clock| {
+            self.under_emergency_at(clock.slot, threshold)
+        });
+        self.boundary().passes(score, threshold)
+    }
+}
+
+/// Last quote slot accepted for a query account, used for anti-replay.
+///
+/// The record is shared by every consuming program: once a quote is accepted
+/// through one of them it is rejected through all the others.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ReplayRecord {
+    pub last_quote_slot: u64,
+}
+
+/// Last verified score of a query account.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ScoreCache {
+    /// Wallet the score was computed for (the token account owner when the
+    /// query account is a token account).
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub address: Pubkey,
+    /// Verified score, 0–100.
+    pub score: u8,
+    /// Slot the quote was signed at.
+    pub quote_slot: u64,
+    /// Slot the cache was written at.
+    pub updated_slot: u64,
+    /// Feed id the score was verified against.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub feed_id: [u8; 32],
+    /// Provenance: networks aggregated into the score (`NetworkSet` bits).
+    pub networks: u8,
+    pub bump: u8,
+    /// Trend of the score against its recent history.
+    pub momentum: Momentum,
+    /// What the score means, for readers long after it was written.
+    pub provenance: ScoreProvenance,
+    /// Fee payer of the transaction that wrote the cache at `updated_slot`:
+    /// the keeper or relayer that submitted the quote.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub updated_by: Pubkey,
+}
+
+/// Source and meaning of a stored score: which provider scored it, through
+/// which template and on which scale, from which quote. Lets consumers and
+/// auditors read a stored 72 correctly after templates, scales or providers
+/// changed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ScoreProvenance {
+    /// `risk_oracle_shared::feed::provider` id.
+    pub provider: u8,
+    /// `feed::schema` tag of the template the feed was built from.
+    pub template: u8,
+    /// `feed::SCORE_SCHEMA_VERSION` the score is expressed in.
+    pub schema_version: u8,
+    /// Slot the quote was signed at.
+    pub quote_slot: u64,
+    /// Oracle signatures backing the quote.
+    pub oracle_samples: u8,
+}
+
+impl ScoreProvenance {
+    /// Provenance of a Range score verified by this program version against
+    /// a feed of `template`.
+    pub fn new(template: u8, quote_slot: u64, oracle_samples: u8) -> Self {
+        Self {
+            provider: feed::provider::RANGE,
+            template,
+            schema_version: feed::SCORE_SCHEMA_VERSION,
+            quote_slot,
+            oracle_samples,
+        }
+    }
+}
+
+/// Slots of history the new score is compared against (~1 hour).
+pub const MOMENTUM_WINDOW_SLOTS: u64 = 9_000;
+
+/// Distance from the window average, in score points, within which a score
+/// is considered stable.
+pub const MOMENTUM_BAND: u8 = 5;
+
+/// Trend of a score against the time-weighted average of its history, so a
+/// rapidly deteriorating 40 can be told apart from a stable 40.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Momentum {
+    /// No history to compare against.
+    Unknown,
+    Stable,
+    /// Lower risk than the recent average.
+    Improving,
+    /// Higher risk than the recent average.
+    Deteriorating,
+}
+
+/// Severity band of a score under the deployment profile's bands, see
+/// `risk_oracle_shared::severity`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Severity {
+    Info,
+    Warn,
+    Critical,
+}
+
+impl Severity {
+    /// Band of `score` under the active profile.
+    pub fn of(score: u8) -> Self {
+        match profiles::ACTIVE.severity_bands.classify(score) {
+            severity::Severity::Info => Self::Info,
+            severity::Severity::Warn => Self::Warn,
+            severity::Severity::Critical => Self::Critical,
+        }
+    }
+}
+
+/// Maximum number of entries in the [`FeedRegistry`].
+pub const MAX_REGISTRY_ENTRIES: usize = 32;
+
+/// Entries a new [`FeedRegistry`] has room for; it grows as feeds are
+/// registered, up to [`MAX_REGISTRY_ENTRIES`].
+pub const INITIAL_REGISTRY_ENTRIES: usize = 4;
+
+/// Feed ids pinned by the config authority, each bound to the wallet its feed
+/// was built for. Lets verifications skip the on-chain feed derivation.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FeedRegistry {
+    #[max_len(MAX_REGISTRY_ENTRIES)]
+    pub entries: Vec<RegistryEntry>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RegistryEntry {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub feed_id: [u8; 32],
+    /// Wallet whose risk score feed hashes to `feed_id`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub address: Pubkey,
+    pub registered_slot: u64,
+    /// Template that produced `feed_id`, as recorded by the authority.
+    pub provenance: Option<FeedProvenance>,
+    /// First slot verifications against the entry are rejected, set with
+    /// `set_feed_expiry` to retire a template version.
+    pub expires_at_slot: Option<u64>,
+}
+
+impl RegistryEntry {
+    pub fn is_expired_at(&self, slot: u64) -> bool {
+        self.expires_at_slot
+            .is_some_and(|expires_at| slot >= expires_at)
+    }
+}
+
+/// Which template version produced a feed id, readable without the feed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FeedProvenance {
+    /// `risk_oracle_shared::feed::schema` tag.
+    pub schema: u8,
+    /// `feed::encoded_prefix` of the encoded feed (length varint first).
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub encoded_prefix: [u8; 8],
+}
+
+impl FeedProvenance {
+    /// Provenance of a feed encoded with `feed::encode` from template `schema`.
+    pub fn new(schema: u8, encoded: &[u8]) -> Self {
+        Self {
+            schema,
+            encoded_prefix: risk_oracle_shared::feed::encoded_prefix(encoded),
+        }
+    }
+}
+
+impl FeedRegistry {
+    /// Account size with room for `entries` entries.
+    pub fn space(entries: usize) -> usize {
+        8 + 4 + entries * RegistryEntry::INIT_SPACE + 1
+    }
+
+    pub fn find(&self, feed_id: &[u8; 32]) -> Option<&RegistryEntry> {
+        self.entries.iter().find(|entry| constant_time::eq_32(&entry.feed_id, feed_id))
+    }
+}
+
+/// Feed id derived on-chain for a query account, so later verifications can
+/// load it instead of rebuilding and hashing the feed.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FeedIdRecord {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub feed_id: [u8; 32],
+    /// Wallet the feed was derived for (the owner when the query account is a
+    /// token account).
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub address: Pubkey,
+    pub derived_slot: u64,
+    pub bump: u8,
+}
+
+/// Result of the last verification of a query account, written so later
+/// instructions of the same transaction can reuse it without re-running the
+/// quote verifier (see `consume_verified_score`).
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct QuoteMarker {
+    /// Screened wallet.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub address: Pubkey,
+    pub score: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub feed_id: [u8; 32],
+    /// Slot the marker was written at.
+    pub slot: u64,
+    /// Hash of the quote instruction data (instruction 0) that was verified.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub quote_hash: [u8; 32],
+    /// Program the verification was made for (see `verify::consumer`).
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub consumer: Pubkey,
+    pub bump: u8,
+}
+
+/// Proof that a verification happened, for programs invoked through CPI: they
+/// cannot see the top-level quote instruction, but can require this account.
+///
+/// A consumer must check that the account is owned by this program, sits at
+/// the `[RECEIPT_SEED, query_account]` PDA, [`is_valid_at`] the current slot
+/// and was issued for it (`consumer` is its program id), so a verification
+/// made for another program can't be reused.
+/// Once expired, anyone can close it (`close_receipt`), the rent going back to
+/// `rent_payer`.
+///
+/// [`is_valid_at`]: VerificationReceipt::is_valid_at
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VerificationReceipt {
+    /// Screened wallet.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub address: Pubkey,
+    pub score: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub feed_id: [u8; 32],
+    pub quote_slot: u64,
+    /// Slot the receipt was written at.
+    pub issued_slot: u64,
+    /// Last slot the receipt is valid at.
+    pub expires_slot: u64,
+    /// Refunded when the receipt is closed.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub rent_payer: Pubkey,
+    /// Program the verification was made for (see `verify::consumer`).
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub consumer: Pubkey,
+    pub bump: u8,
+}
+
+impl VerificationReceipt {
+    pub fn is_valid_at(&self, slot: u64) -> bool {
+        slot <= self.expires_slot
+    }
+}
+
+/// Number of scores kept by a [`ScoreHistory`].
+pub const HISTORY_CAPACITY: usize = 32;
+
+/// Scores a new [`ScoreHistory`] has room for; it grows with each score
+/// appended, up to [`HISTORY_CAPACITY`].
+pub const INITIAL_HISTORY_ENTRIES: usize = 4;
+
+/// Ring buffer of the last verified scores of a query account, oldest entries
+/// overwritten first.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ScoreHistory {
+    /// Screened wallet.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub address: Pubkey,
+    /// Index of the oldest entry once the buffer is full.
+    pub head: u8,
+    #[max_len(HISTORY_CAPACITY)]
+    pub entries: Vec<HistoryEntry>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HistoryEntry {
+    pub score: u8,
+    pub quote_slot: u64,
+}
+
+impl ScoreHistory {
+    /// Account size with room for `entries` entries.
+    pub fn space(entries: usize) -> usize {
+        8 + 32 + 1 + 4 + entries * HistoryEntry::INIT_SPACE + 1
+    }
+
+    pub fn push(&mut self, entry: HistoryEntry) {
+        if self.entries.len() < HISTORY_CAPACITY {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.head as usize] = entry;
+            self.head = ((self.head as usize + 1) % HISTORY_CAPACITY) as u8;
+        }
+    }
+
+    /// Entries, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &HistoryEntry> {
+        let (newer, older) = self.entries.split_at(self.head as usize);
+        older.iter().chain(newer)
+    }
+
+    /// Trend of `score` against the average of the last
+    /// [`MOMENTUM_WINDOW_SLOTS`], computed before `score` is pushed.
+    pub fn momentum(&self, score: u8, now: u64) -> Momentum {
+        match self.time_weighted_average(now, MOMENTUM_WINDOW_SLOTS) {
+            None => Momentum::Unknown,
+            Some(average) if score > average.saturating_add(MOMENTUM_BAND) => {
+                Momentum::Deteriorating
+            }
+            Some(average) if score < average.saturating_sub(MOMENTUM_BAND) => Momentum::Improving,
+            Some(_) => Momentum::Stable,
+        }
+    }
+
+    /// Time-weighted average score over the last `window` slots before `now`,
+    /// rounded up like the scores themselves. Each score holds from its quote
+    /// slot until the next entry (or `now`); the entry in force at the window
+    /// start counts from the window start. Returns `None` when no score falls
+    /// in the window.
+    pub fn time_weighted_average(&self, now: u64, window: u64) -> Option<u8> {
+        let start = now.saturating_sub(window);
+        let mut weighted: u128 = 0;
+        let mut total: u64 = 0;
+
+        let mut entries = self.iter().peekable();
+        while let Some(entry) = entries.next() {
+            let end = entries.peek().map_or(now, |next| next.quote_slot).min(now);
+            let span = end.saturating_sub(entry.quote_slot.max(start));
+            weighted += entry.score as u128 * span as u128;
+            total += span;
+        }
+
+        match total {
+            // Only scores quoted at `now`: nothing to weight, use the latest.
+            0 => self
+                .iter()
+                .last()
+                .filter(|entry| entry.quote_slot >= start)
+                .map(|entry| entry.score),
+            total => Some(weighted.div_ceil(total as u128) as u8),
+        }
+    }
+}
+
+/// A wallet barred from verification regardless of its score. Its existence
+/// at `[DENYLIST_SEED, address]` is the denylist membership.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DenylistEntry {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub address: Pubkey,
+    pub added_slot: u64,
+    pub bump: u8,
+}
+
+/// Merkle root of a sorted denylist (see `risk_oracle_shared::merkle`), for
+/// lists too large for one [`DenylistEntry`] PDA per address. Membership is
+/// checked with client-provided proofs (`check_denylist`).
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DenylistRoot {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub root: [u8; 32],
+    /// Number of addresses in the list, fixes the proof depth.
+    pub leaf_count: u32,
+    pub updated_slot: u64,
+    pub bump: u8,
+}
+
+/// One segment of a packed denylist (see `risk_oracle_shared::denylist_segment`),
+/// for lists too large for one [`DenylistEntry`] PDA per address but checked
+/// on-chain without proofs. Holds the listed addresses of one hash prefix,
+/// sorted, at about a fifth of the rent of their entries.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DenylistSegment {
+    pub segment: u16,
+    pub bump: u8,
+    /// Sorted ascending. Last, so lookups read it in place.
+    #[max_len(MAX_SEGMENT_ENTRIES)]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58_vec"))]
+    pub addresses: Vec<Pubkey>,
+}
+
+impl DenylistSegment {
+    /// Offset of the packed addresses in the account data, after the
+    /// discriminator, the segment, the bump and the vector length.
+    pub const ADDRESSES_OFFSET: usize = 8 + 2 + 1 + 4;
+
+    /// Account size with room for `addresses` addresses.
+    pub fn space(addresses: usize) -> usize {
+        Self::ADDRESSES_OFFSET + addresses * 32
+    }
+
+    /// Whether `address` is listed in the segment account `data`, read
+    /// without deserializing it.
+    pub fn data_contains(data: &[u8], address: &Pubkey) -> bool {
+        let Some(len) = data.get(Self::ADDRESSES_OFFSET - 4..Self::ADDRESSES_OFFSET) else {
+            return false;
+        };
+        let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+        data.get(Self::ADDRESSES_OFFSET..Self::ADDRESSES_OFFSET + len * 32)
+            .is_some_and(|entries| denylist_segment::contains(entries, &address.to_bytes()))
+    }
+}
+
+/// Freshness SLA of a watched query account: its score cache must be
+/// refreshed at least every `max_age_slots`. Set by the config authority;
+/// `verify_composite_risk_score` records the refresh when it is passed this
+/// account. Anyone can check it with `is_within_sla`.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FreshnessSla {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub query_account: Pubkey,
+    pub max_age_slots: u64,
+    /// Slot of the last recorded refresh, 0 until the first one.
+    pub last_refresh_slot: u64,
+    pub bump: u8,
+}
+
+impl FreshnessSla {
+    /// Whether a refresh was recorded within `max_age_slots` of `slot`.
+    pub fn is_within_at(&self, slot: u64) -> bool {
+        self.last_refresh_slot != 0
+            && slot.saturating_sub(self.last_refresh_slot) <= self.max_age_slots
+    }
+}
+
+/// Score of a query account kept as a salted commitment instead of in clear,
+/// for deployments whose scores must not be publicly readable. Only programs
+/// in the [`ConsumerAccessList`] can have it revealed (`reveal_score`).
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConfidentialScore {
+    /// Screened wallet.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub address: Pubkey,
+    /// `verify::score_commitment` of the score, the address and the salt.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub commitment: [u8; 32],
+    pub quote_slot: u64,
+    pub updated_slot: u64,
+    pub bump: u8,
+}
+
+/// Programs allowed to have confidential scores revealed, managed by the
+/// config authority.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConsumerAccessList {
+    #[max_len(MAX_ACCESS_LIST_ENTRIES)]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58_vec"))]
+    pub consumers: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl ConsumerAccessList {
+    pub fn allows(&self, consumer: &Pubkey) -> bool {
+        self.consumers.contains(consumer)
+    }
+}
+
+/// Programs no instruction of a verified transaction may target when
+/// [`feature_flags::PROGRAM_DENYLIST`] is enabled (mixer routers and the
+/// like), managed by the config authority.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProgramDenylist {
+    #[max_len(MAX_DENIED_PROGRAMS)]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58_vec"))]
+    pub programs: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl ProgramDenylist {
+    pub fn denies(&self, program: &[u8; 32]) -> bool {
+        self.programs.iter().any(|denied| denied.as_ref() == program)
+    }
+}
+
+/// Outcome of the last threshold proof of a query account: whether the score
+/// passed the configured threshold, plus a salted commitment to the
+/// score, which is itself never stored.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ThresholdProof {
+    /// Screened wallet.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub address: Pubkey,
+    /// `Config::risk_threshold` the score was compared against.
+    pub threshold: u8,
+    /// Whether the score passed `threshold`, with the config's boundary.
+    pub below_threshold: bool,
+    /// `verify::score_commitment` of the score, the address and the salt.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub commitment: [u8; 32],
+    pub quote_slot: u64,
+    pub updated_slot: u64,
+    pub bump: u8,
+}
+
+/// Usage account of a consuming program (the integrator namespace), so a
+/// shared deployment can bill oracle costs back to each integrator.
+/// Registered by the config authority; anyone can top it up.
+///
+/// The lamports of `balance_lamports` and `charged_lamports` are held by this
+/// account on top of its rent. A verification moves `price_lamports` from the
+/// balance to the charges, which `collect_integrator_fees` sweeps to the
+/// treasury.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Integrator {
+    /// Program the verifications are made for, see `verify::consumer`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub consumer: Pubkey,
+    /// Verifications metered so far.
+    pub verifications: u64,
+    /// Charged per verification, 0 to only count them.
+    pub price_lamports: u64,
+    /// Prepaid lamports not charged yet.
+    pub balance_lamports: u64,
+    /// Charged lamports not collected yet.
+    pub charged_lamports: u64,
+    pub bump: u8,
+}
+
+impl Integrator {
+    /// Counts a verification and charges its price, unless the prepaid
+    /// balance doesn't cover it.
+    pub fn record_verification(&mut self) -> bool {
+        let Some(balance) = self.balance_lamports.checked_sub(self.price_lamports) else {
+            return false;
+        };
+        self.balance_lamports = balance;
+        self.charged_lamports += self.price_lamports;
+        self.verifications += 1;
+        true
+    }
+
+    /// Verifications the prepaid balance still covers, `None` when they are
+    /// free.
+    pub fn remaining_verifications(&self) -> Option<u64> {
+        (self.price_lamports > 0).then(|| self.balance_lamports / self.price_lamports)
+    }
+}
+
+/// Highest score a consuming program (the integrator namespace) accepts: its
+/// risk appetite, enforced on every verification made for it on top of the
+/// threshold the call gates on. The config authority creates it and names the
+/// risk officer, who can then move the ceiling without touching the
+/// integration. Without one, nothing is capped.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RiskCeiling {
+    /// Program the verifications are made for, see `verify::consumer`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub consumer: Pubkey,
+    /// Key allowed to change `max_score`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub officer: Pubkey,
+    /// Scores above it fail, on the feed scale (0–100).
+    pub max_score: u8,
+    pub updated_slot: u64,
+    pub bump: u8,
+}
+
+/// What an [`AddressPolicy`] does with the score of its address.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PolicyVerdict {
+    /// Gate the score on the policy's threshold, or the config's without one.
+    Screen,
+    /// Pass whatever the score, still from a verified quote.
+    Allow,
+    /// Fail before the quote is verified.
+    Deny,
+}
+
+/// Address-specific exception to the config's threshold, set by the config
+/// authority. Applied by `verify_risk_score_feed` under the address policies
+/// flag; without one, the address is screened like any other.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AddressPolicy {
+    /// Screened wallet the policy applies to.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub address: Pubkey,
+    pub verdict: PolicyVerdict,
+    /// Replaces the config's scheduled threshold for this address, on the
+    /// feed scale (0–100). Gates even with threshold gating off.
+    pub threshold: Option<u8>,
+    /// Oracle signatures the quote needs for this address, over what the
+    /// feed and the profile already require.
+    pub min_oracle_samples: u8,
+    pub updated_slot: u64,
+    pub bump: u8,
+}
+
+/// Penalty added to cached scores as their quote ages, see
+/// `risk_oracle_shared::decay`. Set by the config authority, applied by
+/// `check_or_verify_score` under [`feature_flags::SCORE_DECAY`].
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ScoreDecay {
+    /// Age up to which a cached score gates as is.
+    pub grace_slots: u64,
+    pub interval_slots: u64,
+    /// Added per started interval past the grace period, on the feed scale.
+    pub penalty: u8,
+    pub max_penalty: u8,
+    pub updated_slot: u64,
+    pub bump: u8,
+}
+
+impl ScoreDecay {
+    pub fn model(&self) -> Decay {
+        Decay {
+            grace_slots: self.grace_slots,
+            interval_slots: self.interval_slots,
+            penalty: self.penalty,
+            max_penalty: self.max_penalty,
+        }
+    }
+}
+
+/// Key of the compliance officer whose signed vouchers let an address through
+/// the risk check once (`redeem_bypass_voucher`). Set by the config authority.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ComplianceOfficer {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub officer: Pubkey,
+    pub updated_slot: u64,
+    pub bump: u8,
+}
+
+/// Audit record of a redeemed bypass voucher (see
+/// `risk_oracle_shared::voucher`). Its existence at
+/// `[BYPASS_SEED, address, nonce]` marks the voucher as spent.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BypassRecord {
+    /// Wallet let through.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub address: Pubkey,
+    /// Compliance officer who signed the voucher.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub officer: Pubkey,
+    pub nonce: u64,
+    pub not_before_slot: u64,
+    pub not_after_slot: u64,
+    /// Slot the voucher was redeemed at.
+    pub redeemed_slot: u64,
+    /// Program the bypass was redeemed for (see `verify::consumer`).
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub consumer: Pubkey,
+    pub bump: u8,
+}
+
+/// Variable override names (`${NAME}` placeholders) the feeds of one
+/// template may use, checked by `register_feed_template` so a misspelled
+/// placeholder is caught at registration instead of as a `FeedMismatch`.
+/// Set by the config authority, see `risk_oracle_shared::feed_variables`.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TemplateVariables {
+    /// `risk_oracle_shared::feed::schema` tag.
+    pub schema: u8,
+    #[max_len(MAX_TEMPLATE_VARIABLES, MAX_VARIABLE_LEN)]
+    pub names: Vec<String>,
+    pub bump: u8,
+}
+
+impl TemplateVariables {
+    pub fn declares(&self, name: &[u8]) -> bool {
+        self.names.iter().any(|declared| declared.as_bytes() == name)
+    }
+}
+
+/// A quote verified once and posted for many transactions to verify scores
+/// against (`verify_risk_score_account`) without carrying the quote
+/// instruction themselves. Created and written by its owner, who closes it to
+/// reclaim the rent. Only verified quotes are ever written, so readers need
+/// not trust the owner, only check the age of `quote_slot`.
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct QuoteAccount {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "base58"))]
+    pub owner: Pubkey,
+    /// Seed telling the owner's quote accounts apart.
+    pub id: u64,
+    /// Slot the posted quote was signed at, 0 before the first write.
+    pub quote_slot: u64,
+    /// Slot the quote was written at.
+    pub written_slot: u64,
+    /// Number of oracle signatures backing the quote.
+    pub oracle_samples: u8,
+    /// Every feed of the quote, in quote order.
+    #[max_len(MAX_QUOTE_ACCOUNT_FEEDS)]
+    pub feeds: Vec<PostedFeed>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PostedFeed {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub feed_id: [u8; 32],
+    /// Fixed-point value with `verify::FEED_VALUE_DECIMALS` decimals.
+    pub value: i128,
+}
+
+impl QuoteAccount {
+    pub fn feed(&self, feed_id: &[u8; 32]) -> Option<&PostedFeed> {
+        self.feeds
+            .iter()
+            .find(|feed| constant_time::eq_32(&feed.feed_id, feed_id))
+    }
+}
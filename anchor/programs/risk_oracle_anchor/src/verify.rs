@@ -0,0 +1,414 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use risk_oracle_shared::feed_id::FeedIdDerivation;
+use risk_oracle_shared::feed_match::{self, MatchResult};
+use risk_oracle_shared::proto::OracleFeed;
+use risk_oracle_shared::query_account::{self, QueryAccountKind};
+use risk_oracle_shared::{diagnostics, profiles, quote_limits, verifier_inputs};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use switchboard_on_demand::QuoteVerifier;
+
+use crate::error::ErrorCode;
+use crate::state::{feature_flags, Config};
+
+/// Decimals of [`FeedValue::value`] (Switchboard's `PRECISION`).
+pub const FEED_VALUE_DECIMALS: u32 = 18;
+
+/// A feed of a verified quote with its raw value, as returned and emitted for
+/// every approved feed of the quote.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct FeedValue {
+    pub feed_id: [u8; 32],
+    /// Fixed-point value with [`FEED_VALUE_DECIMALS`] decimals.
+    pub value: i128,
+}
+
+/// A feed value taken from a verified quote.
+pub struct VerifiedFeed {
+    pub value: Decimal,
+    pub quote_slot: u64,
+    /// Number of oracle signatures backing the quote.
+    pub oracle_samples: u8,
+    /// Every feed of the quote, in quote order, the verified one included.
+    /// Callers keep the ones they approve of.
+    pub feeds: Vec<FeedValue>,
+}
+
+/// Verifies the Ed25519 quote instruction at [`quote_index`] (signatures,
+/// queue, freshness, oracle samples) and returns the value of its first feed
+/// once its id matches `derived_feed_id`.
+pub fn verify_quote<'info>(
+    queue: &AccountInfo<'info>,
+    slothashes: &AccountInfo<'info>,
+    instructions: &AccountInfo<'info>,
+    derived_feed_id: &[u8; 32],
+) -> Result<VerifiedFeed> {
+    verify_quote_feed(queue, slothashes, instructions, 0, derived_feed_id)
+}
+
+/// [`verify_quote`] for the feed at `index`, for quotes carrying several
+/// feeds (e.g. `feed::score_and_flag_feeds`). Callers reading more than one
+/// feed verify the quote once with [`verify_quote_feeds`] and take each feed
+/// with [`VerifiedQuote::feed`].
+pub fn verify_quote_feed<'info>(
+    queue: &AccountInfo<'info>,
+    slothashes: &AccountInfo<'info>,
+    instructions: &AccountInfo<'info>,
+    index: usize,
+    derived_feed_id: &[u8; 32],
+) -> Result<VerifiedFeed> {
+    verify_quote_feeds(queue, slothashes, instructions)?.verified_feed(index, derived_feed_id)
+}
+
+/// A feed of a verified quote.
+pub struct QuoteFeed {
+    pub feed_id: [u8; 32],
+    pub value: Decimal,
+    /// `value` in fixed point, with [`FEED_VALUE_DECIMALS`] decimals.
+    pub feed_value: i128,
+    pub min_oracle_samples: u8,
+}
+
+/// A verified quote with every feed it carries, none of them checked against
+/// a derived id yet.
+pub struct VerifiedQuote {
+    pub quote_slot: u64,
+    /// Number of oracle signatures backing the quote.
+    pub oracle_samples: u8,
+    pub feeds: Vec<QuoteFeed>,
+}
+
+impl VerifiedQuote {
+    /// The feed at `index` once its id matches `derived_feed_id` and the quote
+    /// has the oracle samples it requires.
+    pub fn feed(&self, index: usize, derived_feed_id: &[u8; 32]) -> Result<&QuoteFeed> {
+        #[cfg(feature = "audit")]
+        msg!("{}", risk_oracle_shared::audit::feed_id(derived_feed_id));
+
+        let feeds = &self.feeds;
+
+        #[cfg(feature = "audit")]
+        for (position, feed) in feeds.iter().enumerate() {
+            let matched = position == index
+                && risk_oracle_shared::constant_time::eq_32(&feed.feed_id, derived_feed_id);
+            msg!(
+                "{}",
+                risk_oracle_shared::audit::quote_feed(position, &feed.feed_id, matched)
+            );
+        }
+        let ids = feeds.iter().map(|feed| &feed.feed_id);
+        let position = match feed_match::match_feeds(ids, &[*derived_feed_id]) {
+            MatchResult::Matched {
+                positions: [position],
+            } => Some(position),
+            MatchResult::NoFeeds => {
+                msg!("{}", diagnostics::no_oracle_feeds(derived_feed_id));
+                return Err(ErrorCode::NoOracleFeeds.into());
+            }
+            MatchResult::Mismatch { .. } => None,
+        };
+        // The feed must also sit at `index`, where the caller expects it.
+        let feed = match position {
+            Some(position) if position == index => &feeds[position],
+            _ => {
+                let actual_feed_id = feeds.get(index).map(|feed| &feed.feed_id);
+                msg!(
+                    "{}",
+                    diagnostics::feed_mismatch(derived_feed_id, actual_feed_id)
+                );
+                return Err(ErrorCode::FeedMismatch.into());
+            }
+        };
+        self.require_samples(feed)?;
+
+        #[cfg(feature = "audit")]
+        msg!("{}", risk_oracle_shared::audit::value(feed.feed_value));
+
+        Ok(feed)
+    }
+
+    /// [`feed`](Self::feed) with every feed of the quote, as the verify
+    /// instructions return them.
+    pub fn verified_feed(&self, index: usize, derived_feed_id: &[u8; 32]) -> Result<VerifiedFeed> {
+        let feed = self.feed(index, derived_feed_id)?;
+
+        #[cfg(feature = "compute-log")]
+        log_compute(risk_oracle_shared::compute::VERIFIED);
+
+        Ok(VerifiedFeed {
+            value: feed.value,
+            quote_slot: self.quote_slot,
+            oracle_samples: self.oracle_samples,
+            feeds: self
+                .feeds
+                .iter()
+                .map(|feed| FeedValue {
+                    feed_id: feed.feed_id,
+                    value: feed.feed_value,
+                })
+                .collect(),
+        })
+    }
+
+    /// Fails unless the quote has the oracle samples `feed` requires. The
+    /// feed's `min_oracle_samples` is part of its id, so the requirement is
+    /// the one the feed was built with; the profile can only raise it.
+    pub fn require_samples(&self, feed: &QuoteFeed) -> Result<()> {
+        let required = feed
+            .min_oracle_samples
+            .max(profiles::ACTIVE.min_oracle_samples);
+        if self.oracle_samples < required {
+            msg!(
+                "{}",
+                diagnostics::insufficient_samples(self.oracle_samples, required)
+            );
+            return Err(ErrorCode::InsufficientOracleSamples.into());
+        }
+        Ok(())
+    }
+}
+
+/// Verifies the Ed25519 quote instruction at [`quote_index`] (size,
+/// signatures, queue, freshness, feed count) once, leaving the callers to
+/// pick their feeds out of it.
+pub fn verify_quote_feeds<'info>(
+    queue: &AccountInfo<'info>,
+    slothashes: &AccountInfo<'info>,
+    instructions: &AccountInfo<'info>,
+) -> Result<VerifiedQuote> {
+    let mut verifier = QuoteVerifier::new();
+    let slot = Clock::get()?.slot;
+
+    // Bound the work before the verifier parses the quote. A missing quote
+    // instruction is reported below.
+    let quote_index = quote_index(instructions)?;
+    let quote_len =
+        quote_limits::instruction_data_len(&instructions.try_borrow_data()?, quote_index);
+    if let Some(len) = quote_len.filter(|&len| len > profiles::ACTIVE.max_quote_data_len) {
+        msg!(
+            "{}",
+            diagnostics::quote_too_large(len, profiles::ACTIVE.max_quote_data_len)
+        );
+        return Err(ErrorCode::QuoteTooLarge.into());
+    }
+
+    // The verifier panics on inputs it can't read or that don't match its
+    // accounts; fail with an error code instead.
+    require_keys_eq!(
+        slothashes.key(),
+        sysvar::slot_hashes::ID,
+        ErrorCode::InvalidSysvar
+    );
+    let queue_len = queue.data_len();
+    if queue_len != verifier_inputs::QUEUE_ACCOUNT_LEN {
+        msg!(
+            "{}",
+            diagnostics::verifier_error(queue_len, verifier_inputs::QUEUE_ACCOUNT_LEN)
+        );
+        return Err(ErrorCode::VerifierError.into());
+    }
+    if let Err(error) = verifier_inputs::check_quote(
+        &instructions.try_borrow_data()?,
+        quote_index,
+        &queue.try_borrow_data()?,
+        &slothashes.try_borrow_data()?,
+    ) {
+        msg!("{}", diagnostics::verification_failed(error.reason()));
+        return Err(ErrorCode::VerificationFailed.into());
+    }
+
+    verifier
+        .queue(queue)
+        .slothash_sysvar(slothashes)
+        .ix_sysvar(instructions)
+        .clock_slot(slot);
+
+    // Verify the Ed25519 quote instruction
+    let quote_index = i64::try_from(quote_index).map_err(|_| ErrorCode::VerificationFailed)?;
+    let quote = verifier.verify_instruction_at(quote_index).map_err(|error| {
+        msg!("Quote verification failed: {}", error);
+        msg!("{}", diagnostics::verification_failed("rejected"));
+        ErrorCode::VerificationFailed
+    })?;
+    let quote_slot = quote.slot();
+
+    #[cfg(feature = "audit")]
+    msg!(
+        "{}",
+        risk_oracle_shared::audit::quote(
+            &queue.key.to_bytes(),
+            slot,
+            quote_slot,
+            profiles::ACTIVE.max_quote_age_slots
+        )
+    );
+
+    // Ensure the quote is recent enough (within the profile's slot limit).
+    if slot.saturating_sub(quote_slot) > profiles::ACTIVE.max_quote_age_slots {
+        msg!(
+            "{}",
+            diagnostics::stale_quote(slot, quote_slot, profiles::ACTIVE.max_quote_age_slots)
+        );
+        return Err(ErrorCode::StaleQuote.into());
+    }
+
+    let feeds = quote.feeds();
+    if feeds.len() > profiles::ACTIVE.max_quote_feeds {
+        msg!(
+            "{}",
+            diagnostics::too_many_quote_feeds(feeds.len(), profiles::ACTIVE.max_quote_feeds)
+        );
+        return Err(ErrorCode::TooManyQuoteFeeds.into());
+    }
+
+    Ok(VerifiedQuote {
+        quote_slot,
+        oracle_samples: quote.oracle_count,
+        feeds: feeds
+            .iter()
+            .map(|feed| QuoteFeed {
+                feed_id: *feed.feed_id(),
+                value: feed.value(),
+                feed_value: feed.feed_value(),
+                min_oracle_samples: feed.min_oracle_samples(),
+            })
+            .collect(),
+    })
+}
+
+/// Fails once the current slot is past `not_after_slot`, the deadline the
+/// client bound the verification to.
+pub fn require_before_deadline(not_after_slot: u64) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    if slot > not_after_slot {
+        msg!("{}", diagnostics::deadline_passed(slot, not_after_slot));
+        return Err(ErrorCode::DeadlinePassed.into());
+    }
+    Ok(())
+}
+
+/// Strict quote feeds: with the flag enabled, fails unless the quote's feeds
+/// are exactly `expected`, each once, in any order.
+pub fn require_expected_feeds(
+    config: &Config,
+    feeds: &[FeedValue],
+    expected: &[[u8; 32]],
+) -> Result<()> {
+    if !config.is_enabled(feature_flags::STRICT_QUOTE_FEEDS) {
+        return Ok(());
+    }
+    let is_expected = |feed: &&FeedValue| {
+        expected
+            .iter()
+            .any(|id| risk_oracle_shared::constant_time::eq_32(id, &feed.feed_id))
+    };
+    let unexpected = feeds.iter().find(|feed| !is_expected(feed));
+    if unexpected.is_some() || feeds.len() != expected.len() {
+        msg!(
+            "{}",
+            diagnostics::unexpected_quote_feed(
+                feeds.len(),
+                expected.len(),
+                unexpected.map(|feed| &feed.feed_id)
+            )
+        );
+        return Err(ErrorCode::UnexpectedQuoteFeed.into());
+    }
+    Ok(())
+}
+
+/// Classifies `query_account` and returns the wallet whose pubkey goes into the
+/// Range URL: the account itself for wallets, the owner for token accounts.
+/// Programs and other program-owned accounts are rejected.
+pub fn resolve_screened_address(query_account: &AccountInfo) -> Result<Pubkey> {
+    #[cfg(feature = "compute-log")]
+    log_compute(risk_oracle_shared::compute::ACCOUNTS);
+
+    let kind = query_account::classify(
+        &query_account.owner.to_bytes(),
+        query_account.executable,
+        &query_account.try_borrow_data()?,
+    );
+
+    match kind {
+        QueryAccountKind::Wallet => Ok(query_account.key()),
+        QueryAccountKind::TokenAccount { owner } => {
+            let owner = Pubkey::new_from_array(owner);
+            msg!("Query account is a token account, screening owner {}", owner);
+            Ok(owner)
+        }
+        QueryAccountKind::ProgramOwned => err!(ErrorCode::QueryAccountIsProgramOwned),
+        QueryAccountKind::Program => err!(ErrorCode::QueryAccountIsProgram),
+    }
+}
+
+/// Feed id of a template: hash of its length-delimited protobuf encoding.
+pub fn feed_id(feed: &OracleFeed) -> [u8; 32] {
+    feed_id_with(FeedIdDerivation::LengthDelimited, feed)
+}
+
+/// Feed id of a template under `derivation`, the config's
+/// [`Config::feed_id_derivation`] where the instruction reads it.
+pub fn feed_id_with(derivation: FeedIdDerivation, feed: &OracleFeed) -> [u8; 32] {
+    let id = hash(&derivation.preimage(feed)).to_bytes();
+    #[cfg(feature = "compute-log")]
+    log_compute(risk_oracle_shared::compute::FEED);
+    id
+}
+
+/// Logs the compute units left at `checkpoint` (`risk_oracle_shared::compute`).
+#[cfg(feature = "compute-log")]
+fn log_compute(checkpoint: &str) {
+    use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+    msg!(
+        "{}",
+        risk_oracle_shared::compute::checkpoint(checkpoint, sol_remaining_compute_units())
+    );
+}
+
+/// Index of the quote instruction: 0, or with the `quote-discovery` feature
+/// the first instruction after the leading ComputeBudget ones, so priority
+/// fee instructions may come first.
+pub fn quote_index(instructions: &AccountInfo) -> Result<usize> {
+    if cfg!(feature = "quote-discovery") {
+        Ok(quote_limits::discover_quote_index(&instructions.try_borrow_data()?))
+    } else {
+        Ok(0)
+    }
+}
+
+/// Hash of the quote instruction data ([`quote_index`]) of the current
+/// transaction. Identifies the quote without verifying it again.
+pub fn quote_hash(instructions: &AccountInfo) -> Result<[u8; 32]> {
+    let quote_ix = load_instruction_at_checked(quote_index(instructions)?, instructions)?;
+    Ok(hash(&quote_ix.data).to_bytes())
+}
+
+/// Program consuming the current instruction: the program of the top-level
+/// instruction being executed, i.e. the integrator that reached this program
+/// through CPI, or this program itself when called directly.
+pub fn consumer(instructions: &AccountInfo) -> Result<Pubkey> {
+    let index = load_current_index_checked(instructions)?;
+    Ok(load_instruction_at_checked(index as usize, instructions)?.program_id)
+}
+
+/// Domain separator of [`score_commitment`].
+const SCORE_COMMITMENT_DOMAIN: &[u8] = b"risk-oracle-score";
+
+/// Salted commitment to the `score` of `address`, as stored by the
+/// confidential mode. The salt keeps account readers from trying the 101
+/// possible scores.
+pub fn score_commitment(address: &Pubkey, score: u8, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[SCORE_COMMITMENT_DOMAIN, address.as_ref(), &[score], salt]).to_bytes()
+}
+
+/// Converts a verified feed value to a 0–100 score, any fraction rounded up
+/// as by `feed::score_from_feed_value` (see `risk_oracle_shared::threshold`).
+pub fn score_from_value(value: Decimal) -> u8 {
+    value.ceil().to_u8().unwrap_or(u8::MAX).min(100)
+}
@@ -0,0 +1,208 @@
+//! `risk_oracle_shared::layout` against the program's own encoding: each
+//! described account is serialized with distinct field values, which must sit
+//! at the offsets, with the discriminator and the size of the `#[account]`.
+//!
+//! Needs no built program, runs under plain `cargo test`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountSerialize, Discriminator, Space};
+use anchor_oracle_example::state::{
+    Config, FeedIdRecord, Momentum, QuoteMarker, ReplayRecord, ScoreCache, ScoreProvenance,
+    VerificationReceipt,
+};
+use risk_oracle_shared::layout::{self, AccountLayout};
+
+fn key(byte: u8) -> Pubkey {
+    Pubkey::new_from_array([byte; 32])
+}
+
+/// Serializes `account` and checks the discriminator and size of `layout`.
+fn encode<T: AccountSerialize + Discriminator + Space>(
+    layout: &AccountLayout,
+    account: &T,
+) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    assert_eq!(layout.discriminator, T::DISCRIMINATOR, "{}", layout.name);
+    assert_eq!(layout.size(), 8 + T::INIT_SPACE, "{}", layout.name);
+    assert_eq!(data.len(), layout.size(), "{}", layout.name);
+    assert_eq!(layout::identify(&data), Some(layout));
+    data
+}
+
+fn assert_field(layout: &AccountLayout, data: &[u8], name: &str, expected: &[u8]) {
+    let field = layout
+        .field(name)
+        .unwrap_or_else(|| panic!("{}.{name} is not described", layout.name));
+    assert_eq!(field.read(data), Some(expected), "{}.{name}", layout.name);
+}
+
+#[test]
+fn config() {
+    let layout = &layout::CONFIG;
+    let data = encode(
+        layout,
+        &Config {
+            authority: key(1),
+            treasury: key(2),
+            flags: 3,
+            risk_threshold: 4,
+            fee_lamports: 5,
+            bump: 6,
+            emergency_threshold: 7,
+            emergency_expiry_slot: 8,
+            ramp_from: 9,
+            ramp_start_slot: 10,
+            ramp_end_slot: 11,
+        },
+    );
+    assert_field(layout, &data, "authority", &[1; 32]);
+    assert_field(layout, &data, "treasury", &[2; 32]);
+    assert_field(layout, &data, "flags", &3u64.to_le_bytes());
+    assert_field(layout, &data, "risk_threshold", &[4]);
+    assert_field(layout, &data, "fee_lamports", &5u64.to_le_bytes());
+    assert_field(layout, &data, "bump", &[6]);
+    assert_field(layout, &data, "emergency_threshold", &[7]);
+    assert_field(layout, &data, "emergency_expiry_slot", &8u64.to_le_bytes());
+    assert_field(layout, &data, "ramp_from", &[9]);
+    assert_field(layout, &data, "ramp_start_slot", &10u64.to_le_bytes());
+    assert_field(layout, &data, "ramp_end_slot", &11u64.to_le_bytes());
+}
+
+#[test]
+fn replay_record() {
+    let layout = &layout::REPLAY_RECORD;
+    let data = encode(layout, &ReplayRecord { last_quote_slot: 1 });
+    assert_field(layout, &data, "last_quote_slot", &1u64.to_le_bytes());
+}
+
+#[test]
+fn score_cache() {
+    let layout = &layout::SCORE_CACHE;
+    let data = encode(
+        layout,
+        &ScoreCache {
+            address: key(1),
+            score: 2,
+            quote_slot: 3,
+            updated_slot: 4,
+            feed_id: [5; 32],
+            networks: 6,
+            bump: 7,
+            momentum: Momentum::Deteriorating,
+            provenance: ScoreProvenance {
+                provider: 8,
+                template: 9,
+                schema_version: 10,
+                quote_slot: 11,
+                oracle_samples: 12,
+            },
+            updated_by: key(13),
+        },
+    );
+    assert_field(layout, &data, "address", &[1; 32]);
+    assert_field(layout, &data, "score", &[2]);
+    assert_field(layout, &data, "quote_slot", &3u64.to_le_bytes());
+    assert_field(layout, &data, "updated_slot", &4u64.to_le_bytes());
+    assert_field(layout, &data, "feed_id", &[5; 32]);
+    assert_field(layout, &data, "networks", &[6]);
+    assert_field(layout, &data, "bump", &[7]);
+    assert_field(layout, &data, "momentum", &[Momentum::Deteriorating as u8]);
+    assert_eq!(layout::MOMENTUM, layout.field("momentum").unwrap().ty);
+    assert_field(layout, &data, "provenance.provider", &[8]);
+    assert_field(layout, &data, "provenance.template", &[9]);
+    assert_field(layout, &data, "provenance.schema_version", &[10]);
+    assert_field(layout, &data, "provenance.quote_slot", &11u64.to_le_bytes());
+    assert_field(layout, &data, "provenance.oracle_samples", &[12]);
+    assert_field(layout, &data, "updated_by", &[13; 32]);
+}
+
+#[test]
+fn momentum_variants() {
+    let layout::FieldType::Enum(variants) = layout::MOMENTUM else {
+        panic!("momentum is an enum");
+    };
+    for (index, momentum) in [
+        Momentum::Unknown,
+        Momentum::Stable,
+        Momentum::Improving,
+        Momentum::Deteriorating,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        assert_eq!(momentum as usize, index);
+        assert_eq!(variants[index], format!("{momentum:?}"));
+    }
+    assert_eq!(variants.len(), 4);
+}
+
+#[test]
+fn feed_id_record() {
+    let layout = &layout::FEED_ID_RECORD;
+    let data = encode(
+        layout,
+        &FeedIdRecord {
+            feed_id: [1; 32],
+            address: key(2),
+            derived_slot: 3,
+            bump: 4,
+        },
+    );
+    assert_field(layout, &data, "feed_id", &[1; 32]);
+    assert_field(layout, &data, "address", &[2; 32]);
+    assert_field(layout, &data, "derived_slot", &3u64.to_le_bytes());
+    assert_field(layout, &data, "bump", &[4]);
+}
+
+#[test]
+fn quote_marker() {
+    let layout = &layout::QUOTE_MARKER;
+    let data = encode(
+        layout,
+        &QuoteMarker {
+            address: key(1),
+            score: 2,
+            feed_id: [3; 32],
+            slot: 4,
+            quote_hash: [5; 32],
+            consumer: key(6),
+            bump: 7,
+        },
+    );
+    assert_field(layout, &data, "address", &[1; 32]);
+    assert_field(layout, &data, "score", &[2]);
+    assert_field(layout, &data, "feed_id", &[3; 32]);
+    assert_field(layout, &data, "slot", &4u64.to_le_bytes());
+    assert_field(layout, &data, "quote_hash", &[5; 32]);
+    assert_field(layout, &data, "consumer", &[6; 32]);
+    assert_field(layout, &data, "bump", &[7]);
+}
+
+#[test]
+fn verification_receipt() {
+    let layout = &layout::VERIFICATION_RECEIPT;
+    let data = encode(
+        layout,
+        &VerificationReceipt {
+            address: key(1),
+            score: 2,
+            feed_id: [3; 32],
+            quote_slot: 4,
+            issued_slot: 5,
+            expires_slot: 6,
+            rent_payer: key(7),
+            consumer: key(8),
+            bump: 9,
+        },
+    );
+    assert_field(layout, &data, "address", &[1; 32]);
+    assert_field(layout, &data, "score", &[2]);
+    assert_field(layout, &data, "feed_id", &[3; 32]);
+    assert_field(layout, &data, "quote_slot", &4u64.to_le_bytes());
+    assert_field(layout, &data, "issued_slot", &5u64.to_le_bytes());
+    assert_field(layout, &data, "expires_slot", &6u64.to_le_bytes());
+    assert_field(layout, &data, "rent_payer", &[7; 32]);
+    assert_field(layout, &data, "consumer", &[8; 32]);
+    assert_field(layout, &data, "bump", &[9]);
+}
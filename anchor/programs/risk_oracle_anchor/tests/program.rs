@@ -0,0 +1,2533 @@
+//! Runs the built program in Mollusk: the account constraints of the verify
+//! instructions (queue, sysvars, query account), the verifier failures mapped
+//! to error codes, adversarial quotes (truncated, padded, signed for another
+//! queue) that must fail with a code rather than panic, the caches
+//! `refresh_score_caches` writes from one quote and `reconcile` corrects, the
+//! bitmaps of `verify_risk_score_batch`, the quotes posted to quote accounts
+//! and verified from them, the threshold boundary of cached scores, the decay
+//! of stale cached scores, `describe`, `health_check` on the test feed, the
+//! feed id derivation flag, legacy instructions run through their aliases, the
+//! risk ceilings, the address policies, the emergency threshold and threshold
+//! ramps, the bypass vouchers, the expiry of registered feeds, the quote
+//! markers consumed later in their transaction and the paths
+//! that need no signed quote (configuration, integrators, registry growth,
+//! template variables, denylist, segmented denylist, program denylist,
+//! deadline).
+//!
+//! Needs the program built for the default profile, which `cargo test-sbf`
+//! does before running the tests:
+//!
+//! ```text
+//! cargo test-sbf --manifest-path anchor/programs/risk_oracle_anchor/Cargo.toml
+//! ```
+//!
+//! Under plain `cargo test` (no `SBF_OUT_DIR`) the tests are skipped.
+
+use std::path::Path;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{
+    AccountMeta, BorrowedAccountMeta, BorrowedInstruction, Instruction,
+};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{
+    AccountDeserialize, AccountSerialize, AnchorDeserialize, InstructionData, Space, ToAccountMetas,
+};
+use anchor_oracle_example::aliases;
+use anchor_oracle_example::error::ErrorCode;
+use anchor_oracle_example::instructions::denylist_segment;
+use anchor_oracle_example::state::{
+    feature_flags, AddressPolicy, BypassRecord, ComplianceOfficer, Config, DenylistSegment,
+    FeedRegistry, FreshnessSla, Integrator, Momentum, PolicyVerdict, PostedFeed, ProgramDenylist,
+    QuoteAccount, QuoteMarker, RegistryEntry, RiskCeiling, ScoreCache, ScoreDecay,
+    ScoreProvenance, TemplateVariables, ADDRESS_POLICY_SEED, BYPASS_SEED, CACHE_SEED,
+    COMPLIANCE_OFFICER_SEED, CONFIG_SEED, CONFIG_V1_LEN, DENYLIST_SEED, DENYLIST_SEGMENT_SEED,
+    INTEGRATOR_SEED, MARKER_SEED, MAX_REGISTRY_ENTRIES, PROGRAM_DENYLIST_SEED, QUOTE_ACCOUNT_SEED,
+    REGISTRY_SEED, RISK_CEILING_SEED, SCORE_DECAY_SEED, SLA_SEED, TEMPLATE_VARIABLES_SEED,
+};
+use anchor_oracle_example::verify::{feed_id, feed_id_with};
+use anchor_oracle_example::{
+    accounts, instruction, AccountScore, BatchVerification, BypassVoucher, CachedScoreView,
+    ConfigParams, RiskThreshold, ScoreCheck, ID,
+};
+use mollusk_svm::program::{create_program_account_loader_v3, keyed_account_for_system_program};
+use mollusk_svm::result::Check;
+use mollusk_svm::Mollusk;
+use risk_oracle_client::fixtures::{self, QUEUE_ACCOUNT_LEN};
+use risk_oracle_client::quote::QuoteFeed;
+use risk_oracle_shared::describe::{self, Value};
+use risk_oracle_shared::feed_id::FeedIdDerivation;
+use risk_oracle_shared::{feed, feed_variables, profiles, voucher};
+use solana_sdk::account::Account;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{keypair_from_seed, Keypair, Signer};
+use solana_sdk::system_program;
+
+const AUTHORITY: Pubkey = Pubkey::new_from_array([0xa1; 32]);
+const TREASURY: Pubkey = Pubkey::new_from_array([0xa2; 32]);
+const WALLET: Pubkey = Pubkey::new_from_array([0xa3; 32]);
+/// Slot of the signed quotes, and of the only SlotHashes entry.
+const QUOTE_SLOT: u64 = 1_000;
+const SLOT_HASH: [u8; 32] = [0x5a; 32];
+/// Feed id of the signed quotes, no template's.
+const QUOTE_FEED_ID: [u8; 32] = [0x77; 32];
+
+fn mollusk() -> Option<Mollusk> {
+    let built = std::env::var_os("SBF_OUT_DIR")
+        .map(|dir| Path::new(&dir).join("anchor_oracle_example.so"))
+        .is_some_and(|path| path.exists());
+    if !built {
+        eprintln!("anchor_oracle_example.so is not built, skipped (run `cargo test-sbf`)");
+        return None;
+    }
+    Some(Mollusk::new(&ID, "anchor_oracle_example"))
+}
+
+fn program_error(code: ErrorCode) -> Check<'static> {
+    Check::err(ProgramError::Custom(code.into()))
+}
+
+fn anchor_error(code: AnchorErrorCode) -> Check<'static> {
+    Check::err(ProgramError::Custom(code as u32))
+}
+
+fn rent_exempt(owner: Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn wallet() -> Account {
+    Account::new(10_000_000_000, 0, &system_program::ID)
+}
+
+fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_SEED], &ID).0
+}
+
+fn config_account(flags: u64) -> Account {
+    config_with_threshold(flags, 100)
+}
+
+fn config_with_threshold(flags: u64, risk_threshold: u8) -> Account {
+    config_data(config_state(flags, risk_threshold))
+}
+
+/// Config with no emergency threshold nor ramp.
+fn config_state(flags: u64, risk_threshold: u8) -> Config {
+    let (_, bump) = Pubkey::find_program_address(&[CONFIG_SEED], &ID);
+    Config {
+        authority: AUTHORITY,
+        treasury: TREASURY,
+        flags,
+        risk_threshold,
+        fee_lamports: 0,
+        bump,
+        emergency_threshold: 0,
+        emergency_expiry_slot: 0,
+        ramp_from: 0,
+        ramp_start_slot: 0,
+        ramp_end_slot: 0,
+    }
+}
+
+fn config_data(config: Config) -> Account {
+    let mut data = Vec::with_capacity(8 + Config::INIT_SPACE);
+    config.try_serialize(&mut data).unwrap();
+    rent_exempt(ID, data)
+}
+
+fn queue_address() -> Pubkey {
+    Pubkey::new_from_array(profiles::ACTIVE.queue)
+}
+
+/// Queue image with no oracles, enough for the account constraints.
+fn queue_account() -> Account {
+    fixtures::queue_account(&[]).unwrap()
+}
+
+/// SlotHashes holding only [`SLOT_HASH`] at [`QUOTE_SLOT`].
+fn slot_hashes_sysvar() -> (Pubkey, Account) {
+    let mut data = 1u64.to_le_bytes().to_vec();
+    data.extend_from_slice(&QUOTE_SLOT.to_le_bytes());
+    data.extend_from_slice(&SLOT_HASH);
+    (sysvar::slot_hashes::ID, rent_exempt(sysvar::ID, data))
+}
+
+/// Quote of one feed ([`QUOTE_FEED_ID`], value 1) signed by `oracle` as the
+/// queue's oracle 0 for [`SLOT_HASH`] at [`QUOTE_SLOT`].
+fn signed_quote(oracle: &Keypair) -> Vec<u8> {
+    signed_quote_of(oracle, QUOTE_FEED_ID, 1)
+}
+
+/// [`signed_quote`] of `feed_id` reporting `value`.
+fn signed_quote_of(oracle: &Keypair, feed_id: [u8; 32], value: i128) -> Vec<u8> {
+    signed_quote_scaled(oracle, feed_id, value * 10i128.pow(18))
+}
+
+/// [`signed_quote_of`] with the raw value, scaled by 10^18.
+fn signed_quote_scaled(oracle: &Keypair, feed_id: [u8; 32], scaled: i128) -> Vec<u8> {
+    let feed = QuoteFeed {
+        feed_id,
+        value: scaled,
+        min_oracle_samples: 1,
+    };
+    fixtures::quote_data(&[oracle.insecure_clone()], SLOT_HASH, QUOTE_SLOT, &[feed])
+}
+
+/// [`verify_accounts`] at [`QUOTE_SLOT`] with `quote` at instruction 0, the
+/// SlotHashes it was signed for and a queue whose oracle 0 is `signing_key`.
+fn quote_accounts(
+    mollusk: &mut Mollusk,
+    quote: Vec<u8>,
+    signing_key: &Pubkey,
+) -> Vec<(Pubkey, Account)> {
+    mollusk.warp_to_slot(QUOTE_SLOT);
+    let mut accounts = verify_accounts(mollusk, 0);
+    let queue = fixtures::queue_account(&[*signing_key]).unwrap();
+    replace(&mut accounts, queue_address(), queue);
+    let (key, slot_hashes) = slot_hashes_sysvar();
+    replace(&mut accounts, key, slot_hashes);
+    let (key, instructions) = instructions_sysvar(&[(ed25519_program::ID, quote)]);
+    replace(&mut accounts, key, instructions);
+    accounts
+}
+
+fn instructions_sysvar(instructions: &[(Pubkey, Vec<u8>)]) -> (Pubkey, Account) {
+    let borrowed: Vec<BorrowedInstruction> = instructions
+        .iter()
+        .map(|(program_id, data)| BorrowedInstruction {
+            program_id,
+            accounts: Vec::new(),
+            data,
+        })
+        .collect();
+    let data = sysvar::instructions::construct_instructions_data(&borrowed);
+    (sysvar::instructions::ID, rent_exempt(sysvar::ID, data))
+}
+
+/// Ed25519 header with no signature and first offsets pointing at
+/// instruction `index`.
+fn quote_data(index: u16) -> Vec<u8> {
+    let mut data = vec![0u8; 16];
+    for at in [4, 8, 14] {
+        data[at..at + 2].copy_from_slice(&index.to_le_bytes());
+    }
+    data
+}
+
+/// Keys and accounts of a compact verify layout that passes the account
+/// constraints, optional accounts left out.
+fn verify_keys() -> accounts::VerifyRiskScoreFeed {
+    accounts::VerifyRiskScoreFeed {
+        config: config_address(),
+        queue: queue_address(),
+        slothashes: sysvar::slot_hashes::ID,
+        instructions: sysvar::instructions::ID,
+        query_account: WALLET,
+        payer: None,
+        replay_record: None,
+        treasury: None,
+        system_program: None,
+        registry: None,
+        feed_id_record: None,
+        quote_marker: None,
+        receipt: None,
+        history: None,
+        denylist_entry: None,
+        integrator: None,
+        program_denylist: None,
+        risk_ceiling: None,
+        address_policy: None,
+        denylist_segment: None,
+    }
+}
+
+fn verify_accounts(mollusk: &Mollusk, flags: u64) -> Vec<(Pubkey, Account)> {
+    vec![
+        (config_address(), config_account(flags)),
+        (queue_address(), queue_account()),
+        mollusk.sysvars.keyed_account_for_slot_hashes_sysvar(),
+        instructions_sysvar(&[]),
+        (WALLET, wallet()),
+        // Absent optional accounts are passed as the program id.
+        (ID, create_program_account_loader_v3(&ID)),
+    ]
+}
+
+fn verify_ix(keys: accounts::VerifyRiskScoreFeed) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: keys.to_account_metas(None),
+        data: instruction::VerifyRiskScoreFeedCompact {}.data(),
+    }
+}
+
+fn program_denylist_account(programs: Vec<Pubkey>) -> (Pubkey, Account) {
+    let (key, bump) = Pubkey::find_program_address(&[PROGRAM_DENYLIST_SEED], &ID);
+    let program_denylist = ProgramDenylist { programs, bump };
+    let mut data = Vec::with_capacity(8 + ProgramDenylist::INIT_SPACE);
+    program_denylist.try_serialize(&mut data).unwrap();
+    (key, rent_exempt(ID, data))
+}
+
+/// The program's `ProgramData`, upgradeable by `upgrade_authority`: the
+/// bincode `UpgradeableLoaderState::ProgramData` header, no ELF behind it.
+fn program_data_account(upgrade_authority: Option<Pubkey>) -> (Pubkey, Account) {
+    let (key, _) = Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID);
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    match upgrade_authority {
+        Some(authority) => {
+            data.push(1);
+            data.extend_from_slice(authority.as_ref());
+        }
+        None => data.push(0),
+    }
+    (key, rent_exempt(bpf_loader_upgradeable::ID, data))
+}
+
+fn initialize_config_ix(params: ConfigParams) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::InitializeConfig {
+            config: config_address(),
+            authority: AUTHORITY,
+            program: ID,
+            program_data: program_data_account(None).0,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::InitializeConfig { params }.data(),
+    }
+}
+
+fn initialize_config_accounts(upgrade_authority: Option<Pubkey>) -> Vec<(Pubkey, Account)> {
+    vec![
+        (config_address(), Account::default()),
+        (AUTHORITY, wallet()),
+        (ID, create_program_account_loader_v3(&ID)),
+        program_data_account(upgrade_authority),
+        keyed_account_for_system_program(),
+    ]
+}
+
+fn replace(accounts: &mut Vec<(Pubkey, Account)>, key: Pubkey, account: Account) {
+    accounts.retain(|(k, _)| *k != key);
+    accounts.push((key, account));
+}
+
+#[test]
+fn initialize_config_records_params() {
+    let Some(mollusk) = mollusk() else { return };
+    let ix = initialize_config_ix(ConfigParams {
+        treasury: TREASURY,
+        flags: feature_flags::THRESHOLD_GATING,
+        risk_threshold: RiskThreshold::Native(5),
+        fee_lamports: 1_000,
+    });
+    let result = mollusk.process_and_validate_instruction(
+        &ix,
+        &initialize_config_accounts(Some(AUTHORITY)),
+        &[Check::success()],
+    );
+
+    let account = result.get_account(&config_address()).unwrap();
+    let config = Config::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(config.authority, AUTHORITY);
+    assert_eq!(config.treasury, TREASURY);
+    assert_eq!(config.flags, feature_flags::THRESHOLD_GATING);
+    assert_eq!(config.risk_threshold, 50);
+    assert_eq!(config.fee_lamports, 1_000);
+}
+
+#[test]
+fn initialize_config_rejects_unknown_flags() {
+    let Some(mollusk) = mollusk() else { return };
+    let ix = initialize_config_ix(ConfigParams {
+        treasury: TREASURY,
+        flags: 1 << 63,
+        risk_threshold: RiskThreshold::Score(50),
+        fee_lamports: 0,
+    });
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &initialize_config_accounts(Some(AUTHORITY)),
+        &[program_error(ErrorCode::UnknownFeatureFlag)],
+    );
+}
+
+#[test]
+fn initialize_config_requires_the_upgrade_authority() {
+    let Some(mollusk) = mollusk() else { return };
+    let ix = initialize_config_ix(ConfigParams {
+        treasury: TREASURY,
+        flags: 0,
+        risk_threshold: RiskThreshold::Score(50),
+        fee_lamports: 0,
+    });
+    // Someone else racing the deployment.
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &initialize_config_accounts(Some(WALLET)),
+        &[program_error(ErrorCode::NotUpgradeAuthority)],
+    );
+    // An immutable program has no authority to create its config.
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &initialize_config_accounts(None),
+        &[program_error(ErrorCode::NotUpgradeAuthority)],
+    );
+}
+
+#[test]
+fn set_feature_flags_requires_the_authority() {
+    let Some(mollusk) = mollusk() else { return };
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateConfig {
+            config: config_address(),
+            authority: WALLET,
+        }
+        .to_account_metas(None),
+        data: instruction::SetFeatureFlags {
+            flags: feature_flags::DENYLIST,
+        }
+        .data(),
+    };
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &[(config_address(), config_account(0)), (WALLET, wallet())],
+        &[anchor_error(AnchorErrorCode::ConstraintHasOne)],
+    );
+}
+
+#[test]
+fn extend_config_grows_a_config_of_the_previous_layout() {
+    let Some(mollusk) = mollusk() else { return };
+    let mut v1 = config_account(0);
+    v1.data.truncate(CONFIG_V1_LEN);
+    v1.lamports = Rent::default().minimum_balance(CONFIG_V1_LEN);
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::ExtendConfig {
+            config: config_address(),
+            authority: AUTHORITY,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::ExtendConfig {}.data(),
+    };
+    let result = mollusk.process_and_validate_instruction(
+        &ix,
+        &[
+            (config_address(), v1),
+            (AUTHORITY, wallet()),
+            keyed_account_for_system_program(),
+        ],
+        &[Check::success()],
+    );
+
+    let account = result.get_account(&config_address()).unwrap();
+    assert_eq!(account.data.len(), 8 + Config::INIT_SPACE);
+    let config = Config::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(config.authority, AUTHORITY);
+    assert_eq!(config.emergency_expiry_slot, 0);
+}
+
+fn legacy_ix(name: &str, args: &[u8]) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateConfig {
+            config: config_address(),
+            authority: AUTHORITY,
+        }
+        .to_account_metas(None),
+        data: [&aliases::discriminator(name)[..], args].concat(),
+    }
+}
+
+#[test]
+fn a_legacy_instruction_runs_as_its_replacement() {
+    let Some(mollusk) = mollusk() else { return };
+    let result = mollusk.process_and_validate_instruction(
+        &legacy_ix("set_threshold", &[42]),
+        &[(config_address(), config_account(0)), (AUTHORITY, wallet())],
+        &[Check::success()],
+    );
+
+    let account = result.get_account(&config_address()).unwrap();
+    let config = Config::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(config.risk_threshold, 42);
+}
+
+#[test]
+fn an_instruction_matching_no_alias_is_rejected() {
+    let Some(mollusk) = mollusk() else { return };
+    mollusk.process_and_validate_instruction(
+        &legacy_ix("set_threshold_v0", &[42]),
+        &[(config_address(), config_account(0)), (AUTHORITY, wallet())],
+        &[anchor_error(AnchorErrorCode::InstructionFallbackNotFound)],
+    );
+}
+
+#[test]
+fn emergency_threshold_gates_with_the_flag_off() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, mut accounts) = verify_test_feed(&mut mollusk, 0, feed_id(&feed::test_feed()));
+    let config = Config {
+        emergency_threshold: feed::TEST_SCORE - 1,
+        emergency_expiry_slot: QUOTE_SLOT + 1,
+        ..config_state(0, 100)
+    };
+    replace(&mut accounts, config_address(), config_data(config));
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::RiskThresholdExceeded)],
+    );
+}
+
+#[test]
+fn an_expired_emergency_threshold_no_longer_gates() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, mut accounts) = verify_test_feed(&mut mollusk, 0, feed_id(&feed::test_feed()));
+    let config = Config {
+        emergency_threshold: feed::TEST_SCORE - 1,
+        emergency_expiry_slot: QUOTE_SLOT,
+        ..config_state(0, 100)
+    };
+    replace(&mut accounts, config_address(), config_data(config));
+    mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+}
+
+/// `verify_test_feed` under threshold gating, the threshold ramping from 100
+/// to 0 from slot 0 to `end_slot`.
+fn verify_on_ramp(mollusk: &mut Mollusk, end_slot: u64) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let (ix, mut accounts) = verify_test_feed(
+        mollusk,
+        feature_flags::THRESHOLD_GATING,
+        feed_id(&feed::test_feed()),
+    );
+    let config = Config {
+        ramp_from: 100,
+        ramp_start_slot: 0,
+        ramp_end_slot: end_slot,
+        ..config_state(feature_flags::THRESHOLD_GATING, 0)
+    };
+    replace(&mut accounts, config_address(), config_data(config));
+    (ix, accounts)
+}
+
+#[test]
+fn threshold_ramp_gates_on_the_interpolated_threshold() {
+    let Some(mut mollusk) = mollusk() else { return };
+    // Threshold 50 at the quote slot.
+    let (ix, accounts) = verify_on_ramp(&mut mollusk, 2 * QUOTE_SLOT);
+    mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    // Threshold 37.5, rounded down.
+    let (ix, accounts) = verify_on_ramp(&mut mollusk, 1_600);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::RiskThresholdExceeded)],
+    );
+}
+
+#[test]
+fn register_integrator_creates_the_usage_account() {
+    let Some(mollusk) = mollusk() else { return };
+    let consumer = Pubkey::new_from_array([0xc1; 32]);
+    let integrator = Pubkey::find_program_address(&[INTEGRATOR_SEED, consumer.as_ref()], &ID).0;
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::RegisterIntegrator {
+            config: config_address(),
+            integrator,
+            authority: AUTHORITY,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::RegisterIntegrator {
+            consumer,
+            price_lamports: 5_000,
+        }
+        .data(),
+    };
+    let result = mollusk.process_and_validate_instruction(
+        &ix,
+        &[
+            (config_address(), config_account(0)),
+            (integrator, Account::default()),
+            (AUTHORITY, wallet()),
+            keyed_account_for_system_program(),
+        ],
+        &[Check::success()],
+    );
+
+    let account = result.get_account(&integrator).unwrap();
+    let integrator = Integrator::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(integrator.consumer, consumer);
+    assert_eq!(integrator.price_lamports, 5_000);
+    assert_eq!(integrator.verifications, 0);
+}
+
+#[test]
+fn verify_rejects_a_queue_at_another_address() {
+    let Some(mollusk) = mollusk() else { return };
+    let other = Pubkey::new_from_array([0xb1; 32]);
+    let mut accounts = verify_accounts(&mollusk, 0);
+    accounts.push((other, queue_account()));
+    let ix = verify_ix(accounts::VerifyRiskScoreFeed {
+        queue: other,
+        ..verify_keys()
+    });
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[anchor_error(AnchorErrorCode::ConstraintAddress)],
+    );
+}
+
+#[test]
+fn verify_rejects_a_queue_not_owned_by_switchboard() {
+    let Some(mollusk) = mollusk() else { return };
+    let mut accounts = verify_accounts(&mollusk, 0);
+    let mut queue = queue_account();
+    queue.owner = system_program::ID;
+    replace(&mut accounts, queue_address(), queue);
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &accounts,
+        &[anchor_error(AnchorErrorCode::AccountOwnedByWrongProgram)],
+    );
+}
+
+#[test]
+fn verify_requires_the_sysvars() {
+    let Some(mollusk) = mollusk() else { return };
+    let mut ix = verify_ix(verify_keys());
+    // Config and queue only.
+    ix.accounts.truncate(2);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &verify_accounts(&mollusk, 0),
+        &[anchor_error(AnchorErrorCode::AccountNotEnoughKeys)],
+    );
+}
+
+#[test]
+fn verify_rejects_another_instructions_account() {
+    let Some(mollusk) = mollusk() else { return };
+    let ix = verify_ix(accounts::VerifyRiskScoreFeed {
+        instructions: WALLET,
+        ..verify_keys()
+    });
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &verify_accounts(&mollusk, 0),
+        &[anchor_error(AnchorErrorCode::AccountSysvarMismatch)],
+    );
+}
+
+#[test]
+fn strict_sysvar_checks_reject_another_slot_hashes_account() {
+    let Some(mollusk) = mollusk() else { return };
+    // The Switchboard SlotHashes type accepts any account; the flag pins it.
+    let ix = verify_ix(accounts::VerifyRiskScoreFeed {
+        slothashes: WALLET,
+        ..verify_keys()
+    });
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &verify_accounts(&mollusk, feature_flags::STRICT_SYSVAR_CHECKS),
+        &[program_error(ErrorCode::InvalidSysvar)],
+    );
+}
+
+#[test]
+fn verify_rejects_a_program_as_query_account() {
+    let Some(mollusk) = mollusk() else { return };
+    let mut accounts = verify_accounts(&mollusk, 0);
+    accounts.push(keyed_account_for_system_program());
+    let ix = verify_ix(accounts::VerifyRiskScoreFeed {
+        query_account: system_program::ID,
+        ..verify_keys()
+    });
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::QueryAccountIsProgram)],
+    );
+}
+
+#[test]
+fn denylist_flag_requires_the_entry() {
+    let Some(mollusk) = mollusk() else { return };
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &verify_accounts(&mollusk, feature_flags::DENYLIST),
+        &[program_error(ErrorCode::MissingDenylistEntry)],
+    );
+}
+
+#[test]
+fn denylist_flag_rejects_a_listed_wallet() {
+    let Some(mollusk) = mollusk() else { return };
+    let entry = Pubkey::find_program_address(&[DENYLIST_SEED, WALLET.as_ref()], &ID).0;
+    let mut accounts = verify_accounts(&mollusk, feature_flags::DENYLIST);
+    accounts.push((entry, rent_exempt(ID, vec![0; 8])));
+    let ix = verify_ix(accounts::VerifyRiskScoreFeed {
+        denylist_entry: Some(entry),
+        ..verify_keys()
+    });
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::AddressDenylisted)],
+    );
+}
+
+/// Packed denylist segment `segment` listing `addresses`.
+fn denylist_segment_account(segment: u16, mut addresses: Vec<Pubkey>) -> (Pubkey, Account) {
+    let (key, bump) =
+        Pubkey::find_program_address(&[DENYLIST_SEGMENT_SEED, &segment.to_be_bytes()], &ID);
+    addresses.sort();
+    let account = DenylistSegment {
+        segment,
+        bump,
+        addresses,
+    };
+    let mut data = Vec::with_capacity(DenylistSegment::space(account.addresses.len()));
+    account.try_serialize(&mut data).unwrap();
+    (key, rent_exempt(ID, data))
+}
+
+fn verify_with_segment(
+    mollusk: &Mollusk,
+    (key, segment): (Pubkey, Account),
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let mut accounts = verify_accounts(mollusk, feature_flags::SEGMENTED_DENYLIST);
+    accounts.push((key, segment));
+    let ix = verify_ix(accounts::VerifyRiskScoreFeed {
+        denylist_segment: Some(key),
+        ..verify_keys()
+    });
+    (ix, accounts)
+}
+
+#[test]
+fn segmented_denylist_rejects_a_listed_wallet() {
+    let Some(mollusk) = mollusk() else { return };
+    let segment = denylist_segment::segment_of(&WALLET);
+    let other = Pubkey::new_from_array([0x5e; 32]);
+    let listed = denylist_segment_account(segment, vec![other, WALLET]);
+    let (ix, accounts) = verify_with_segment(&mollusk, listed);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::AddressDenylisted)],
+    );
+}
+
+#[test]
+fn segmented_denylist_rejects_the_segment_of_another_address() {
+    let Some(mollusk) = mollusk() else { return };
+    let segment = denylist_segment::segment_of(&WALLET) ^ 1;
+    let (ix, accounts) = verify_with_segment(&mollusk, denylist_segment_account(segment, vec![]));
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::InvalidDenylistSegment)],
+    );
+}
+
+#[test]
+fn update_denylist_segment_lists_addresses_sorted() {
+    let Some(mollusk) = mollusk() else { return };
+    let segment = denylist_segment::segment_of(&WALLET);
+    let key = Pubkey::find_program_address(&[DENYLIST_SEGMENT_SEED, &segment.to_be_bytes()], &ID).0;
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateDenylistSegment {
+            config: config_address(),
+            denylist_segment: key,
+            authority: AUTHORITY,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::UpdateDenylistSegment {
+            segment,
+            add: vec![WALLET, WALLET],
+            remove: vec![],
+        }
+        .data(),
+    };
+    let result = mollusk.process_and_validate_instruction(
+        &ix,
+        &[
+            (config_address(), config_account(0)),
+            (key, Account::default()),
+            (AUTHORITY, wallet()),
+            keyed_account_for_system_program(),
+        ],
+        &[Check::success()],
+    );
+    let account = result.get_account(&key).unwrap();
+    let listed = DenylistSegment::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(listed.addresses, vec![WALLET]);
+    assert!(DenylistSegment::data_contains(&account.data, &WALLET));
+}
+
+#[test]
+fn program_denylist_flag_requires_the_account() {
+    let Some(mollusk) = mollusk() else { return };
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &verify_accounts(&mollusk, feature_flags::PROGRAM_DENYLIST),
+        &[program_error(ErrorCode::MissingProgramDenylist)],
+    );
+}
+
+#[test]
+fn program_denylist_flag_rejects_a_transaction_through_a_denied_program() {
+    let Some(mollusk) = mollusk() else { return };
+    let mixer = Pubkey::new_from_array([0x6d; 32]);
+    let (key, program_denylist) = program_denylist_account(vec![mixer]);
+
+    let mut accounts = verify_accounts(&mollusk, feature_flags::PROGRAM_DENYLIST);
+    let (sysvar_key, instructions) =
+        instructions_sysvar(&[(ed25519_program::ID, quote_data(0)), (mixer, Vec::new())]);
+    replace(&mut accounts, sysvar_key, instructions);
+    accounts.push((key, program_denylist));
+    let ix = verify_ix(accounts::VerifyRiskScoreFeed {
+        program_denylist: Some(key),
+        ..verify_keys()
+    });
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::DeniedProgramInTransaction)],
+    );
+}
+
+fn screen_programs_ix() -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ScreenPrograms {
+            program_denylist: program_denylist_account(Vec::new()).0,
+            instructions: sysvar::instructions::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::ScreenPrograms {}.data(),
+    }
+}
+
+#[test]
+fn screen_programs_rejects_a_denied_program_without_the_flag() {
+    let Some(mollusk) = mollusk() else { return };
+    let mixer = Pubkey::new_from_array([0x6d; 32]);
+    mollusk.process_and_validate_instruction(
+        &screen_programs_ix(),
+        &[
+            program_denylist_account(vec![mixer]),
+            instructions_sysvar(&[(ID, Vec::new()), (mixer, Vec::new())]),
+        ],
+        &[program_error(ErrorCode::DeniedProgramInTransaction)],
+    );
+}
+
+#[test]
+fn screen_programs_passes_a_transaction_through_allowed_programs() {
+    let Some(mollusk) = mollusk() else { return };
+    mollusk.process_and_validate_instruction(
+        &screen_programs_ix(),
+        &[
+            program_denylist_account(vec![Pubkey::new_from_array([0x6d; 32])]),
+            instructions_sysvar(&[(ID, Vec::new()), (system_program::ID, Vec::new())]),
+        ],
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn flag_program_rejects_a_listed_program() {
+    let Some(mollusk) = mollusk() else { return };
+    let mixer = Pubkey::new_from_array([0x6d; 32]);
+    let (key, program_denylist) = program_denylist_account(vec![mixer]);
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::SetProgramDenylist {
+            config: config_address(),
+            program_denylist: key,
+            authority: AUTHORITY,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::FlagProgram { program: mixer }.data(),
+    };
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &[
+            (config_address(), config_account(0)),
+            (key, program_denylist),
+            (AUTHORITY, wallet()),
+            keyed_account_for_system_program(),
+        ],
+        &[program_error(ErrorCode::ProgramAlreadyFlagged)],
+    );
+}
+
+#[test]
+fn unflag_program_removes_the_program() {
+    let Some(mollusk) = mollusk() else { return };
+    let mixer = Pubkey::new_from_array([0x6d; 32]);
+    let router = Pubkey::new_from_array([0x72; 32]);
+    let (key, program_denylist) = program_denylist_account(vec![mixer, router]);
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateProgramDenylist {
+            config: config_address(),
+            program_denylist: key,
+            authority: AUTHORITY,
+        }
+        .to_account_metas(None),
+        data: instruction::UnflagProgram { program: mixer }.data(),
+    };
+    let result = mollusk.process_and_validate_instruction(
+        &ix,
+        &[
+            (config_address(), config_account(0)),
+            (key, program_denylist),
+            (AUTHORITY, wallet()),
+        ],
+        &[Check::success()],
+    );
+    let account = result.get_account(&key).unwrap();
+    let program_denylist = ProgramDenylist::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(program_denylist.programs, vec![router]);
+}
+
+/// Registry holding `entries` entries, sized for `capacity`.
+fn registry_account(entries: usize, capacity: usize) -> (Pubkey, Account) {
+    let entries = (0..entries)
+        .map(|index| registry_entry([index as u8; 32], None))
+        .collect();
+    registry_of(entries, capacity)
+}
+
+/// Entry of `feed_id` for [`WALLET`].
+fn registry_entry(feed_id: [u8; 32], expires_at_slot: Option<u64>) -> RegistryEntry {
+    RegistryEntry {
+        feed_id,
+        address: WALLET,
+        registered_slot: 0,
+        provenance: None,
+        expires_at_slot,
+    }
+}
+
+fn registry_of(entries: Vec<RegistryEntry>, capacity: usize) -> (Pubkey, Account) {
+    let (key, bump) = Pubkey::find_program_address(&[REGISTRY_SEED], &ID);
+    let registry = FeedRegistry { entries, bump };
+    let mut data = Vec::with_capacity(FeedRegistry::space(capacity));
+    registry.try_serialize(&mut data).unwrap();
+    data.resize(FeedRegistry::space(capacity), 0);
+    (key, rent_exempt(ID, data))
+}
+
+fn register_feed(mollusk: &Mollusk, registry: (Pubkey, Account)) -> Account {
+    let key = registry.0;
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::RegisterFeed {
+            config: config_address(),
+            registry: key,
+            authority: AUTHORITY,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::RegisterFeed {
+            address: WALLET,
+            feed_id: [0xfe; 32],
+            provenance: None,
+        }
+        .data(),
+    };
+    let result = mollusk.process_and_validate_instruction(
+        &ix,
+        &[
+            (config_address(), config_account(0)),
+            registry,
+            (AUTHORITY, wallet()),
+            keyed_account_for_system_program(),
+        ],
+        &[Check::success()],
+    );
+    result.get_account(&key).unwrap().clone()
+}
+
+#[test]
+fn register_feed_grows_a_full_registry() {
+    let Some(mollusk) = mollusk() else { return };
+    let account = register_feed(&mollusk, registry_account(4, 4));
+    assert_eq!(account.data.len(), FeedRegistry::space(8));
+    assert!(account.lamports >= Rent::default().minimum_balance(account.data.len()));
+    let registry = FeedRegistry::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(registry.entries.len(), 5);
+}
+
+#[test]
+fn register_feed_leaves_a_registry_with_room_as_is() {
+    let Some(mollusk) = mollusk() else { return };
+    let account = register_feed(&mollusk, registry_account(2, 4));
+    assert_eq!(account.data.len(), FeedRegistry::space(4));
+}
+
+#[test]
+fn register_feed_caps_the_growth_at_the_maximum() {
+    let Some(mollusk) = mollusk() else { return };
+    let account = register_feed(&mollusk, registry_account(20, 20));
+    assert_eq!(account.data.len(), FeedRegistry::space(MAX_REGISTRY_ENTRIES));
+}
+
+/// Pinned verification of the signed quote, its feed registered with
+/// `expires_at_slot`.
+fn verify_pinned(
+    mollusk: &mut Mollusk,
+    expires_at_slot: Option<u64>,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let oracle = fixtures::oracle_keypair(1);
+    let mut accounts = quote_accounts(mollusk, signed_quote(&oracle), &oracle.pubkey());
+    let registry = registry_of(vec![registry_entry(QUOTE_FEED_ID, expires_at_slot)], 4);
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::VerifyRiskScoreFeed {
+            registry: Some(registry.0),
+            ..verify_keys()
+        }
+        .to_account_metas(None),
+        data: instruction::VerifyRiskScoreFeedPinned {
+            feed_id: QUOTE_FEED_ID,
+        }
+        .data(),
+    };
+    accounts.push(registry);
+    (ix, accounts)
+}
+
+#[test]
+fn pinned_verification_rejects_an_expired_feed() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = verify_pinned(&mut mollusk, Some(QUOTE_SLOT + 1));
+    mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let (ix, accounts) = verify_pinned(&mut mollusk, Some(QUOTE_SLOT));
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::FeedExpired)],
+    );
+}
+
+fn set_feed_expiry_ix(expires_at_slot: Option<u64>) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateRegistry {
+            config: config_address(),
+            registry: registry_account(1, 4).0,
+            authority: AUTHORITY,
+        }
+        .to_account_metas(None),
+        data: instruction::SetFeedExpiry {
+            feed_id: [0; 32],
+            expires_at_slot,
+        }
+        .data(),
+    }
+}
+
+#[test]
+fn set_feed_expiry_schedules_a_future_slot() {
+    let Some(mut mollusk) = mollusk() else { return };
+    mollusk.warp_to_slot(QUOTE_SLOT);
+    let accounts = [
+        (config_address(), config_account(0)),
+        registry_account(1, 4),
+        (AUTHORITY, wallet()),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(
+        &set_feed_expiry_ix(Some(QUOTE_SLOT + 100)),
+        &accounts,
+        &[Check::success()],
+    );
+    let account = result.get_account(&accounts[1].0).unwrap();
+    let registry = FeedRegistry::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(registry.entries[0].expires_at_slot, Some(QUOTE_SLOT + 100));
+
+    mollusk.process_and_validate_instruction(
+        &set_feed_expiry_ix(Some(QUOTE_SLOT)),
+        &accounts,
+        &[program_error(ErrorCode::InvalidFeedExpiry)],
+    );
+}
+
+/// `register_feed_template` of `encoded` as a [`feed::schema::RISK_SCORE_V1`]
+/// feed, its template declaring the variables of the shared crate.
+fn register_feed_template(encoded: Vec<u8>) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let schema = feed::schema::RISK_SCORE_V1;
+    let (template_key, bump) =
+        Pubkey::find_program_address(&[TEMPLATE_VARIABLES_SEED, &[schema]], &ID);
+    let template_variables = TemplateVariables {
+        schema,
+        names: feed_variables::template_variables(schema)
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        bump,
+    };
+    let mut data = Vec::with_capacity(8 + TemplateVariables::INIT_SPACE);
+    template_variables.try_serialize(&mut data).unwrap();
+
+    let registry = registry_account(0, 4);
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::RegisterFeedTemplate {
+            config: config_address(),
+            registry: registry.0,
+            template_variables: template_key,
+            authority: AUTHORITY,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::RegisterFeedTemplate {
+            address: WALLET,
+            schema,
+            encoded,
+        }
+        .data(),
+    };
+    let accounts = vec![
+        (config_address(), config_account(0)),
+        registry,
+        (template_key, rent_exempt(ID, data)),
+        (AUTHORITY, wallet()),
+        keyed_account_for_system_program(),
+    ];
+    (ix, accounts)
+}
+
+#[test]
+fn register_feed_template_pins_the_derived_feed_id() {
+    let Some(mollusk) = mollusk() else { return };
+    let template = feed::risk_score_feed(&WALLET.to_bytes());
+    let (ix, accounts) = register_feed_template(feed::encode(&template));
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let data = result.get_account(&accounts[1].0).unwrap().data.clone();
+    let registry = FeedRegistry::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(registry.entries[0].feed_id, feed_id(&template));
+    assert_eq!(registry.entries[0].address, WALLET);
+}
+
+#[test]
+fn register_feed_template_rejects_a_misspelled_variable() {
+    let Some(mollusk) = mollusk() else { return };
+    let mut encoded = feed::encode(&feed::risk_score_feed(&WALLET.to_bytes()));
+    let at = encoded
+        .windows(13)
+        .position(|window| window == b"RANGE_API_KEY")
+        .unwrap();
+    encoded[at..at + 13].copy_from_slice(b"RANGE_API_KYE");
+    let (ix, accounts) = register_feed_template(encoded);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::UndeclaredFeedVariable)],
+    );
+}
+
+#[test]
+fn verify_with_deadline_rejects_a_passed_deadline() {
+    let Some(mut mollusk) = mollusk() else { return };
+    mollusk.warp_to_slot(1_000);
+    let ix = Instruction {
+        program_id: ID,
+        accounts: verify_keys().to_account_metas(None),
+        data: instruction::VerifyRiskScoreFeedWithDeadline {
+            not_after_slot: 999,
+        }
+        .data(),
+    };
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &verify_accounts(&mollusk, 0),
+        &[program_error(ErrorCode::DeadlinePassed)],
+    );
+}
+
+#[test]
+fn verify_fails_without_a_quote_instruction() {
+    let Some(mollusk) = mollusk() else { return };
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &verify_accounts(&mollusk, 0),
+        &[program_error(ErrorCode::VerificationFailed)],
+    );
+}
+
+#[test]
+fn verify_fails_on_a_quote_instruction_of_another_program() {
+    let Some(mollusk) = mollusk() else { return };
+    let mut accounts = verify_accounts(&mollusk, 0);
+    let (key, sysvar) = instructions_sysvar(&[(system_program::ID, quote_data(0))]);
+    replace(&mut accounts, key, sysvar);
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &accounts,
+        &[program_error(ErrorCode::VerificationFailed)],
+    );
+}
+
+#[test]
+fn verify_fails_on_a_quote_reading_another_instruction() {
+    let Some(mollusk) = mollusk() else { return };
+    let mut accounts = verify_accounts(&mollusk, 0);
+    let (key, sysvar) = instructions_sysvar(&[(ed25519_program::ID, quote_data(1))]);
+    replace(&mut accounts, key, sysvar);
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &accounts,
+        &[program_error(ErrorCode::VerificationFailed)],
+    );
+}
+
+#[test]
+fn verify_fails_on_a_quote_without_signatures() {
+    let Some(mollusk) = mollusk() else { return };
+    // Well formed offsets but no signature.
+    let mut accounts = verify_accounts(&mollusk, 0);
+    let (key, sysvar) = instructions_sysvar(&[(ed25519_program::ID, quote_data(0))]);
+    replace(&mut accounts, key, sysvar);
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &accounts,
+        &[program_error(ErrorCode::VerificationFailed)],
+    );
+}
+
+#[test]
+fn verify_fails_on_a_queue_the_verifier_cannot_read() {
+    let Some(mollusk) = mollusk() else { return };
+    let mut accounts = verify_accounts(&mollusk, 0);
+    let mut queue = queue_account();
+    queue.data.truncate(QUEUE_ACCOUNT_LEN / 2);
+    replace(&mut accounts, queue_address(), queue);
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &accounts,
+        &[program_error(ErrorCode::VerifierError)],
+    );
+}
+
+#[test]
+fn verify_rejects_another_slot_hashes_account_without_strict_checks() {
+    let Some(mollusk) = mollusk() else { return };
+    // The verifier would assert on it.
+    let ix = verify_ix(accounts::VerifyRiskScoreFeed {
+        slothashes: WALLET,
+        ..verify_keys()
+    });
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &verify_accounts(&mollusk, 0),
+        &[program_error(ErrorCode::InvalidSysvar)],
+    );
+}
+
+#[test]
+fn verify_rejects_swapped_sysvar_accounts() {
+    let Some(mollusk) = mollusk() else { return };
+    let ix = verify_ix(accounts::VerifyRiskScoreFeed {
+        slothashes: sysvar::instructions::ID,
+        instructions: sysvar::slot_hashes::ID,
+        ..verify_keys()
+    });
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &verify_accounts(&mollusk, 0),
+        &[anchor_error(AnchorErrorCode::AccountSysvarMismatch)],
+    );
+}
+
+#[test]
+fn verify_accepts_the_signed_quote_up_to_the_feed_check() {
+    let Some(mut mollusk) = mollusk() else { return };
+    // The quote the adversarial tests below alter gets through the verifier:
+    // only its feed id, no template's, fails.
+    let oracle = fixtures::oracle_keypair(1);
+    let accounts = quote_accounts(&mut mollusk, signed_quote(&oracle), &oracle.pubkey());
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &accounts,
+        &[program_error(ErrorCode::FeedMismatch)],
+    );
+}
+
+#[test]
+fn verify_fails_on_a_truncated_quote() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let oracle = fixtures::oracle_keypair(1);
+    let mut quote = signed_quote(&oracle);
+    quote.truncate(quote.len() / 2);
+    let accounts = quote_accounts(&mut mollusk, quote, &oracle.pubkey());
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &accounts,
+        &[program_error(ErrorCode::VerificationFailed)],
+    );
+}
+
+#[test]
+fn verify_fails_on_a_quote_with_extra_data() {
+    let Some(mut mollusk) = mollusk() else { return };
+    // The verifier reads the slot and oracle indexes from the end of the
+    // data, so padding would make it read them from the padding.
+    let oracle = fixtures::oracle_keypair(1);
+    let mut quote = signed_quote(&oracle);
+    quote.extend_from_slice(&[0xff; 32]);
+    let accounts = quote_accounts(&mut mollusk, quote, &oracle.pubkey());
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &accounts,
+        &[program_error(ErrorCode::VerificationFailed)],
+    );
+}
+
+#[test]
+fn verify_fails_on_a_quote_pointing_past_its_data() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let oracle = fixtures::oracle_keypair(1);
+    let mut quote = signed_quote(&oracle);
+    // Signature offset of the first signature.
+    quote[2..4].copy_from_slice(&u16::MAX.to_le_bytes());
+    let accounts = quote_accounts(&mut mollusk, quote, &oracle.pubkey());
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &accounts,
+        &[program_error(ErrorCode::VerificationFailed)],
+    );
+}
+
+#[test]
+fn verify_fails_on_a_quote_for_another_queue() {
+    let Some(mut mollusk) = mollusk() else { return };
+    // Signed by an oracle the profile's queue doesn't list.
+    let quote = signed_quote(&fixtures::oracle_keypair(1));
+    let accounts = quote_accounts(&mut mollusk, quote, &fixtures::oracle_keypair(2).pubkey());
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &accounts,
+        &[program_error(ErrorCode::VerificationFailed)],
+    );
+}
+
+#[test]
+fn verify_fails_on_a_quote_for_another_slot_hash() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let oracle = fixtures::oracle_keypair(1);
+    let mut quote = signed_quote(&oracle);
+    // First byte of the signed slot hash, right after the offsets.
+    quote[2 + 14] ^= 0xff;
+    let accounts = quote_accounts(&mut mollusk, quote, &oracle.pubkey());
+    mollusk.process_and_validate_instruction(
+        &verify_ix(verify_keys()),
+        &accounts,
+        &[program_error(ErrorCode::VerificationFailed)],
+    );
+}
+
+/// Score cache of `address`, verified at slot 1 against `feed_id`.
+fn score_cache(address: Pubkey, feed_id: [u8; 32]) -> (Pubkey, Account) {
+    let (key, bump) = Pubkey::find_program_address(&[CACHE_SEED, address.as_ref()], &ID);
+    let cache = ScoreCache {
+        address,
+        score: 50,
+        quote_slot: 1,
+        updated_slot: 1,
+        feed_id,
+        networks: 1,
+        bump,
+        momentum: Momentum::Unknown,
+        provenance: ScoreProvenance::new(0, 1, 1),
+        updated_by: Pubkey::default(),
+    };
+    let mut data = Vec::with_capacity(8 + ScoreCache::INIT_SPACE);
+    cache.try_serialize(&mut data).unwrap();
+    (key, rent_exempt(ID, data))
+}
+
+/// `refresh_score_caches` over `caches`, with [`quote_accounts`]'s quote.
+fn refresh_caches(
+    mollusk: &mut Mollusk,
+    caches: &[(Pubkey, Account)],
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    refresh_caches_at(mollusk, 10i128.pow(18), caches)
+}
+
+/// [`refresh_caches`] with a quote of [`QUOTE_FEED_ID`] reporting `scaled`
+/// (10^18 per score).
+fn refresh_caches_at(
+    mollusk: &mut Mollusk,
+    scaled: i128,
+    caches: &[(Pubkey, Account)],
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let oracle = fixtures::oracle_keypair(1);
+    let quote = signed_quote_scaled(&oracle, QUOTE_FEED_ID, scaled);
+    let mut accounts = quote_accounts(mollusk, quote, &oracle.pubkey());
+    let mut ix = Instruction {
+        program_id: ID,
+        accounts: accounts::RefreshScoreCaches {
+            queue: queue_address(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            payer: AUTHORITY,
+        }
+        .to_account_metas(None),
+        data: instruction::RefreshScoreCaches {}.data(),
+    };
+    accounts.push((AUTHORITY, wallet()));
+    for (key, cache) in caches {
+        ix.accounts.push(AccountMeta::new(*key, false));
+        accounts.push((*key, cache.clone()));
+    }
+    (ix, accounts)
+}
+
+#[test]
+fn refresh_score_caches_writes_the_caches_the_quote_covers() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let covered = score_cache(WALLET, QUOTE_FEED_ID);
+    let other = score_cache(TREASURY, [0x78; 32]);
+    let (ix, accounts) = refresh_caches(&mut mollusk, &[covered.clone(), other.clone()]);
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let data = result.get_account(&covered.0).unwrap().data.clone();
+    let cache = ScoreCache::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(cache.score, 1);
+    assert_eq!(cache.quote_slot, QUOTE_SLOT);
+    assert_eq!(cache.updated_slot, QUOTE_SLOT);
+    assert_eq!(cache.updated_by, AUTHORITY);
+    assert_eq!(result.get_account(&other.0).unwrap().data, other.1.data);
+}
+
+#[test]
+fn refresh_score_caches_rounds_values_between_scores_up() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let covered = score_cache(WALLET, QUOTE_FEED_ID);
+    let (ix, accounts) = refresh_caches_at(&mut mollusk, 495 * 10i128.pow(17), &[covered.clone()]);
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let data = result.get_account(&covered.0).unwrap().data.clone();
+    let cache = ScoreCache::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(cache.score, 50);
+}
+
+/// `reconcile` of `cache` with [`quote_accounts`]'s quote, which reports 1.
+fn reconcile(
+    mollusk: &mut Mollusk,
+    cache: &(Pubkey, Account),
+    tolerance: u8,
+    correct: bool,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let oracle = fixtures::oracle_keypair(1);
+    let quote = signed_quote_scaled(&oracle, QUOTE_FEED_ID, 10i128.pow(18));
+    let mut accounts = quote_accounts(mollusk, quote, &oracle.pubkey());
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::Reconcile {
+            queue: queue_address(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            cache: cache.0,
+            payer: AUTHORITY,
+        }
+        .to_account_metas(None),
+        data: instruction::Reconcile { tolerance, correct }.data(),
+    };
+    accounts.push((AUTHORITY, wallet()));
+    accounts.push(cache.clone());
+    (ix, accounts)
+}
+
+#[test]
+fn reconcile_corrects_a_divergent_cache() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let cache = score_cache(WALLET, QUOTE_FEED_ID);
+    let (ix, accounts) = reconcile(&mut mollusk, &cache, 10, true);
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    assert_eq!(result.return_data, [1]);
+    let data = result.get_account(&cache.0).unwrap().data.clone();
+    let corrected = ScoreCache::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(corrected.score, 1);
+    assert_eq!(corrected.quote_slot, QUOTE_SLOT);
+    assert_eq!(corrected.updated_by, AUTHORITY);
+}
+
+#[test]
+fn reconcile_leaves_a_cache_within_tolerance() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let cache = score_cache(WALLET, QUOTE_FEED_ID);
+    let (ix, accounts) = reconcile(&mut mollusk, &cache, 49, true);
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    assert_eq!(result.return_data, [0]);
+    assert_eq!(result.get_account(&cache.0).unwrap().data, cache.1.data);
+}
+
+/// `get_cached_score` of a cache holding 50, gated at 50 with `flags`.
+fn cached_score_at_threshold(mollusk: &Mollusk, flags: u64) -> CachedScoreView {
+    let cache = score_cache(WALLET, QUOTE_FEED_ID);
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::GetCachedScore {
+            config: config_address(),
+            query_account: WALLET,
+            cache: cache.0,
+            freshness_sla: None,
+        }
+        .to_account_metas(None),
+        data: instruction::GetCachedScore {}.data(),
+    };
+    let accounts = vec![
+        (config_address(), config_with_threshold(flags, 50)),
+        (WALLET, wallet()),
+        cache,
+    ];
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    CachedScoreView::try_from_slice(&result.return_data).unwrap()
+}
+
+#[test]
+fn a_score_equal_to_the_threshold_passes_by_default() {
+    let Some(mollusk) = mollusk() else { return };
+    let view = cached_score_at_threshold(&mollusk, feature_flags::THRESHOLD_GATING);
+    assert_eq!(view.score, 50);
+    assert!(view.passes_threshold);
+}
+
+#[test]
+fn exclusive_threshold_fails_a_score_equal_to_the_threshold() {
+    let Some(mollusk) = mollusk() else { return };
+    let flags = feature_flags::THRESHOLD_GATING | feature_flags::EXCLUSIVE_THRESHOLD;
+    assert!(!cached_score_at_threshold(&mollusk, flags).passes_threshold);
+}
+
+#[test]
+fn exclusive_threshold_alone_does_not_gate() {
+    let Some(mollusk) = mollusk() else { return };
+    let view = cached_score_at_threshold(&mollusk, feature_flags::EXCLUSIVE_THRESHOLD);
+    assert!(view.passes_threshold);
+}
+
+/// Penalty of 5 per started 50 slots past 100, at most 20.
+fn score_decay_account() -> (Pubkey, Account) {
+    let (key, bump) = Pubkey::find_program_address(&[SCORE_DECAY_SEED], &ID);
+    let score_decay = ScoreDecay {
+        grace_slots: 100,
+        interval_slots: 50,
+        penalty: 5,
+        max_penalty: 20,
+        updated_slot: 0,
+        bump,
+    };
+    let mut data = Vec::with_capacity(8 + ScoreDecay::INIT_SPACE);
+    score_decay.try_serialize(&mut data).unwrap();
+    (key, rent_exempt(ID, data))
+}
+
+#[test]
+fn legacy_layout_keeps_the_original_accounts_in_place() {
+    let keys: Vec<Pubkey> = accounts::VerifyRiskScoreFeedLegacy {
+        queue: queue_address(),
+        clock: sysvar::clock::ID,
+        slothashes: sysvar::slot_hashes::ID,
+        instructions: sysvar::instructions::ID,
+        query_account: WALLET,
+        config: config_address(),
+        payer: None,
+        replay_record: None,
+        treasury: None,
+        system_program: None,
+    }
+    .to_account_metas(None)
+    .iter()
+    .map(|meta| meta.pubkey)
+    .collect();
+    assert_eq!(
+        keys[..6],
+        [
+            queue_address(),
+            sysvar::clock::ID,
+            sysvar::slot_hashes::ID,
+            sysvar::instructions::ID,
+            WALLET,
+            config_address(),
+        ]
+    );
+}
+
+/// [`WALLET`]'s freshness SLA of `max_age_slots`.
+fn freshness_sla(max_age_slots: u64) -> (Pubkey, Account) {
+    let (key, bump) = Pubkey::find_program_address(&[SLA_SEED, WALLET.as_ref()], &ID);
+    let sla = FreshnessSla {
+        query_account: WALLET,
+        max_age_slots,
+        last_refresh_slot: 1,
+        bump,
+    };
+    let mut data = Vec::with_capacity(8 + FreshnessSla::INIT_SPACE);
+    sla.try_serialize(&mut data).unwrap();
+    (key, rent_exempt(ID, data))
+}
+
+/// `check_or_verify_score` of a cache holding 50 from a quote 300 slots old,
+/// accepted up to 1,000 slots, gated at 60 with `flags`, with or without the
+/// decay model and the wallet's freshness SLA of `sla_max_age_slots`.
+fn check_stale_cache(
+    mollusk: &mut Mollusk,
+    flags: u64,
+    score_decay: bool,
+    sla_max_age_slots: Option<u64>,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    mollusk.warp_to_slot(301);
+    let cache = score_cache(WALLET, QUOTE_FEED_ID);
+    let decay = score_decay_account();
+    let sla = sla_max_age_slots.map(freshness_sla);
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::CheckOrVerifyScore {
+            config: config_address(),
+            queue: queue_address(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            query_account: WALLET,
+            cache: cache.0,
+            payer: AUTHORITY,
+            system_program: system_program::ID,
+            history: None,
+            freshness_sla: sla.as_ref().map(|(key, _)| *key),
+            score_decay: score_decay.then_some(decay.0),
+            risk_ceiling: None,
+        }
+        .to_account_metas(None),
+        data: instruction::CheckOrVerifyScore {
+            networks: 1,
+            max_age_slots: 1_000,
+        }
+        .data(),
+    };
+    let mut accounts = vec![
+        (config_address(), config_with_threshold(flags, 60)),
+        (queue_address(), queue_account()),
+        mollusk.sysvars.keyed_account_for_slot_hashes_sysvar(),
+        instructions_sysvar(&[]),
+        (WALLET, wallet()),
+        cache,
+        (AUTHORITY, wallet()),
+        keyed_account_for_system_program(),
+        (ID, create_program_account_loader_v3(&ID)),
+    ];
+    if score_decay {
+        accounts.push(decay);
+    }
+    accounts.extend(sla);
+    (ix, accounts)
+}
+
+#[test]
+fn score_decay_gates_a_stale_cached_score_with_its_penalty() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) =
+        check_stale_cache(&mut mollusk, feature_flags::THRESHOLD_GATING, false, None);
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    let check = ScoreCheck::try_from_slice(&result.return_data).unwrap();
+    assert!(check.from_cache);
+    assert_eq!((check.score, check.decay_penalty), (50, 0));
+
+    let flags = feature_flags::THRESHOLD_GATING | feature_flags::SCORE_DECAY;
+    let (ix, accounts) = check_stale_cache(&mut mollusk, flags, true, None);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::RiskThresholdExceeded)],
+    );
+}
+
+#[test]
+fn freshness_sla_caps_the_callers_cache_age() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = check_stale_cache(&mut mollusk, 0, false, Some(400));
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    assert!(ScoreCheck::try_from_slice(&result.return_data).unwrap().from_cache);
+
+    // Past the SLA, the cache needs a quote, which the transaction lacks.
+    let (ix, accounts) = check_stale_cache(&mut mollusk, 0, false, Some(200));
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::VerificationFailed)],
+    );
+}
+
+#[test]
+fn score_decay_flag_requires_the_model() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = check_stale_cache(&mut mollusk, feature_flags::SCORE_DECAY, false, None);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::MissingScoreDecay)],
+    );
+}
+
+#[test]
+fn describe_reports_the_config_and_flags_by_name() {
+    let Some(mollusk) = mollusk() else { return };
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::Describe {
+            config: config_address(),
+        }
+        .to_account_metas(None),
+        data: instruction::Describe {}.data(),
+    };
+    let config = config_with_threshold(feature_flags::DENYLIST, 70);
+    let accounts = vec![(config_address(), config)];
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let data = Vec::<u8>::try_from_slice(&result.return_data).unwrap();
+    let entries = describe::entries(&data).unwrap();
+    let value = |name| {
+        entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .unwrap()
+            .value
+    };
+    assert_eq!(value("profile"), Value::Text(profiles::ACTIVE.name));
+    assert_eq!(value("risk_threshold"), Value::Unsigned(70));
+    assert_eq!(value("denylist"), Value::Bool(true));
+    assert_eq!(value("threshold_gating"), Value::Bool(false));
+}
+
+#[test]
+fn refresh_score_caches_fails_when_no_cache_is_covered() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = refresh_caches(&mut mollusk, &[score_cache(WALLET, [0x78; 32])]);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::NoCacheInQuote)],
+    );
+}
+
+/// `verify_risk_score_batch` of [`TREASURY`] then [`WALLET`], gated at 50,
+/// with a quote of [`WALLET`]'s feed only reporting `score`. With
+/// `max_score`, under risk ceilings capping the consumer at it.
+fn verify_batch(
+    mollusk: &mut Mollusk,
+    score: i128,
+    partial: bool,
+    max_score: Option<u8>,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let oracle = fixtures::oracle_keypair(1);
+    let wallet_feed_id = feed_id(&feed::risk_score_feed(&WALLET.to_bytes()));
+    let quote = signed_quote_of(&oracle, wallet_feed_id, score);
+    let mut accounts = quote_accounts(mollusk, quote, &oracle.pubkey());
+    let mut flags = feature_flags::THRESHOLD_GATING;
+    let mut risk_ceiling = None;
+    if let Some(max_score) = max_score {
+        flags |= feature_flags::RISK_CEILINGS;
+        // The Instructions sysvar holds the quote alone, at the current
+        // index: the consumer is the Ed25519 program.
+        let (key, ceiling) = risk_ceiling_account(ed25519_program::ID, Some(max_score));
+        accounts.push((key, ceiling));
+        risk_ceiling = Some(key);
+    }
+    replace(&mut accounts, config_address(), config_with_threshold(flags, 50));
+    accounts.push((TREASURY, wallet()));
+    let mut ix = Instruction {
+        program_id: ID,
+        accounts: accounts::VerifyRiskScoreBatch {
+            config: config_address(),
+            queue: queue_address(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            risk_ceiling,
+        }
+        .to_account_metas(None),
+        data: instruction::VerifyRiskScoreBatch { partial }.data(),
+    };
+    ix.accounts.push(AccountMeta::new_readonly(TREASURY, false));
+    ix.accounts.push(AccountMeta::new_readonly(WALLET, false));
+    (ix, accounts)
+}
+
+#[test]
+fn partial_batch_returns_the_addresses_the_quote_covers() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = verify_batch(&mut mollusk, 10, true, None);
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let batch = BatchVerification::try_from_slice(&result.return_data).unwrap();
+    assert_eq!(batch.matched, 0b10);
+    assert_eq!(batch.passed, 0b10);
+    assert_eq!(batch.quote_slot, QUOTE_SLOT);
+}
+
+#[test]
+fn partial_batch_reports_a_score_above_the_threshold() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = verify_batch(&mut mollusk, 60, true, None);
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let batch = BatchVerification::try_from_slice(&result.return_data).unwrap();
+    assert_eq!(batch.matched, 0b10);
+    assert_eq!(batch.passed, 0);
+}
+
+#[test]
+fn partial_batch_reports_a_score_above_the_risk_ceiling() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = verify_batch(&mut mollusk, 10, true, Some(5));
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let batch = BatchVerification::try_from_slice(&result.return_data).unwrap();
+    assert_eq!(batch.matched, 0b10);
+    assert_eq!(batch.passed, 0);
+}
+
+#[test]
+fn strict_batch_fails_on_an_address_the_quote_does_not_cover() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = verify_batch(&mut mollusk, 10, false, None);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::FeedMismatch)],
+    );
+}
+
+/// [`AUTHORITY`]'s quote account 0, holding `feeds` quoted at `quote_slot`.
+fn quote_account(quote_slot: u64, feeds: Vec<PostedFeed>) -> (Pubkey, Account) {
+    let seeds: &[&[u8]] = &[QUOTE_ACCOUNT_SEED, AUTHORITY.as_ref(), &0u64.to_le_bytes()];
+    let (key, bump) = Pubkey::find_program_address(seeds, &ID);
+    let quote_account = QuoteAccount {
+        owner: AUTHORITY,
+        id: 0,
+        quote_slot,
+        written_slot: quote_slot,
+        oracle_samples: 1,
+        feeds,
+        bump,
+    };
+    let mut data = Vec::with_capacity(8 + QuoteAccount::INIT_SPACE);
+    quote_account.try_serialize(&mut data).unwrap();
+    data.resize(8 + QuoteAccount::INIT_SPACE, 0);
+    (key, rent_exempt(ID, data))
+}
+
+#[test]
+fn write_quote_account_posts_the_verified_quote() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let oracle = fixtures::oracle_keypair(1);
+    let quote = signed_quote_of(&oracle, QUOTE_FEED_ID, 10);
+    let mut accounts = quote_accounts(&mut mollusk, quote, &oracle.pubkey());
+    let (key, empty) = quote_account(0, Vec::new());
+    accounts.push((key, empty));
+    accounts.push((AUTHORITY, wallet()));
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::WriteQuoteAccount {
+            queue: queue_address(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            quote_account: key,
+            owner: AUTHORITY,
+        }
+        .to_account_metas(None),
+        data: instruction::WriteQuoteAccount {}.data(),
+    };
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let account = result.get_account(&key).unwrap();
+    let posted = QuoteAccount::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(posted.quote_slot, QUOTE_SLOT);
+    assert_eq!(posted.feeds.len(), 1);
+    assert_eq!(posted.feeds[0].feed_id, QUOTE_FEED_ID);
+    assert_eq!(posted.feeds[0].value, 10 * 10i128.pow(18));
+}
+
+/// `verify_risk_score_account` of [`WALLET`] at `slot`, against a quote
+/// account posted at [`QUOTE_SLOT`] with its feed reporting 10.
+fn verify_from_quote_account(
+    mollusk: &mut Mollusk,
+    slot: u64,
+    max_age_slots: u64,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    mollusk.warp_to_slot(slot);
+    let posted_feed = PostedFeed {
+        feed_id: feed_id(&feed::risk_score_feed(&WALLET.to_bytes())),
+        value: 10 * 10i128.pow(18),
+    };
+    let (key, posted) = quote_account(QUOTE_SLOT, vec![posted_feed]);
+    let accounts = vec![
+        (config_address(), config_account(0)),
+        (key, posted),
+        (WALLET, wallet()),
+        (ID, create_program_account_loader_v3(&ID)),
+    ];
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::VerifyRiskScoreAccount {
+            config: config_address(),
+            quote_account: key,
+            query_account: WALLET,
+            instructions: None,
+            risk_ceiling: None,
+        }
+        .to_account_metas(None),
+        data: instruction::VerifyRiskScoreAccount { max_age_slots }.data(),
+    };
+    (ix, accounts)
+}
+
+#[test]
+fn verify_risk_score_account_reads_the_posted_score() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = verify_from_quote_account(&mut mollusk, QUOTE_SLOT + 100, 150);
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let score = AccountScore::try_from_slice(&result.return_data).unwrap();
+    assert_eq!(score.score, 10);
+    assert_eq!(score.quote_slot, QUOTE_SLOT);
+}
+
+#[test]
+fn verify_risk_score_account_rejects_a_quote_older_than_the_callers_limit() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = verify_from_quote_account(&mut mollusk, QUOTE_SLOT + 100, 50);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::StaleQuote)],
+    );
+}
+
+#[test]
+fn verify_risk_score_account_applies_the_risk_ceiling() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (mut ix, mut accounts) = verify_from_quote_account(&mut mollusk, QUOTE_SLOT + 100, 150);
+    let config = config_account(feature_flags::RISK_CEILINGS);
+    replace(&mut accounts, config_address(), config);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::MissingRiskCeiling)],
+    );
+
+    // Called directly, the program is its own consumer.
+    let (key, ceiling) = risk_ceiling_account(ID, Some(5));
+    ix.accounts = accounts::VerifyRiskScoreAccount {
+        config: config_address(),
+        quote_account: ix.accounts[1].pubkey,
+        query_account: WALLET,
+        instructions: Some(sysvar::instructions::ID),
+        risk_ceiling: Some(key),
+    }
+    .to_account_metas(None);
+    accounts.push(instructions_sysvar(&[(ID, Vec::new())]));
+    accounts.push((key, ceiling));
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::RiskCeilingExceeded)],
+    );
+}
+
+/// `health_check` with a quote of the test feed reporting `value`.
+fn health_check(mollusk: &mut Mollusk, value: i128) -> (Instruction, Vec<(Pubkey, Account)>) {
+    health_check_with(mollusk, 0, feed_id(&feed::test_feed()), value)
+}
+
+/// `health_check` with `flags`, the quote signed for `feed_id`.
+fn health_check_with(
+    mollusk: &mut Mollusk,
+    flags: u64,
+    feed_id: [u8; 32],
+    value: i128,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let oracle = fixtures::oracle_keypair(1);
+    let quote = signed_quote_of(&oracle, feed_id, value);
+    let mut accounts = quote_accounts(mollusk, quote, &oracle.pubkey());
+    replace(&mut accounts, config_address(), config_account(flags));
+    accounts.push((AUTHORITY, wallet()));
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::HealthCheck {
+            config: config_address(),
+            queue: queue_address(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            payer: AUTHORITY,
+        }
+        .to_account_metas(None),
+        data: instruction::HealthCheck {}.data(),
+    };
+    (ix, accounts)
+}
+
+#[test]
+fn health_check_verifies_the_test_feed() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = health_check(&mut mollusk, feed::TEST_SCORE.into());
+    mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+}
+
+#[test]
+fn health_check_rejects_another_value() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = health_check(&mut mollusk, 41);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::HealthCheckValueMismatch)],
+    );
+}
+
+/// `verify_test_feed` with `flags`, the quote signed for `feed_id`.
+fn verify_test_feed(
+    mollusk: &mut Mollusk,
+    flags: u64,
+    feed_id: [u8; 32],
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let oracle = fixtures::oracle_keypair(1);
+    let quote = signed_quote_of(&oracle, feed_id, feed::TEST_SCORE.into());
+    let mut accounts = quote_accounts(mollusk, quote, &oracle.pubkey());
+    replace(&mut accounts, config_address(), config_account(flags));
+    let ix = Instruction {
+        program_id: ID,
+        accounts: verify_keys().to_account_metas(None),
+        data: instruction::VerifyTestFeed {}.data(),
+    };
+    (ix, accounts)
+}
+
+#[test]
+fn feed_request_v2_ids_verify_a_quote_of_the_v2_id() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let v2_id = feed_id_with(FeedIdDerivation::FeedRequestV2, &feed::test_feed());
+    let (ix, accounts) =
+        verify_test_feed(&mut mollusk, feature_flags::FEED_REQUEST_V2_IDS, v2_id);
+    mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+}
+
+#[test]
+fn feed_request_v2_ids_reject_a_quote_of_the_length_delimited_id() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = verify_test_feed(
+        &mut mollusk,
+        feature_flags::FEED_REQUEST_V2_IDS,
+        feed_id(&feed::test_feed()),
+    );
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::FeedMismatch)],
+    );
+}
+
+#[test]
+fn feed_request_v2_ids_apply_to_health_checks() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let v2_id = feed_id_with(FeedIdDerivation::FeedRequestV2, &feed::test_feed());
+    let (ix, accounts) = health_check_with(
+        &mut mollusk,
+        feature_flags::FEED_REQUEST_V2_IDS,
+        v2_id,
+        feed::TEST_SCORE.into(),
+    );
+    mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+}
+
+#[test]
+fn length_delimited_ids_reject_a_quote_of_the_v2_id() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let v2_id = feed_id_with(FeedIdDerivation::FeedRequestV2, &feed::test_feed());
+    let (ix, accounts) = verify_test_feed(&mut mollusk, 0, v2_id);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::FeedMismatch)],
+    );
+}
+
+/// Risk ceiling PDA of `consumer`, at `max_score` or unset.
+fn risk_ceiling_account(consumer: Pubkey, max_score: Option<u8>) -> (Pubkey, Account) {
+    let (key, bump) = Pubkey::find_program_address(&[RISK_CEILING_SEED, consumer.as_ref()], &ID);
+    let account = match max_score {
+        Some(max_score) => {
+            let ceiling = RiskCeiling {
+                consumer,
+                officer: AUTHORITY,
+                max_score,
+                updated_slot: 1,
+                bump,
+            };
+            let mut data = Vec::with_capacity(8 + RiskCeiling::INIT_SPACE);
+            ceiling.try_serialize(&mut data).unwrap();
+            rent_exempt(ID, data)
+        }
+        None => Account::default(),
+    };
+    (key, account)
+}
+
+/// `verify_test_feed` under risk ceilings, the consumer's ceiling at
+/// `max_score` or unset. The Instructions sysvar of these tests holds the
+/// quote alone, at the current index, so the consumer read from it is the
+/// Ed25519 program.
+fn verify_under_ceiling(
+    mollusk: &mut Mollusk,
+    max_score: Option<u8>,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let (key, account) = risk_ceiling_account(ed25519_program::ID, max_score);
+    let (mut ix, mut accounts) = verify_test_feed(
+        mollusk,
+        feature_flags::RISK_CEILINGS,
+        feed_id(&feed::test_feed()),
+    );
+    ix.accounts = accounts::VerifyRiskScoreFeed {
+        risk_ceiling: Some(key),
+        ..verify_keys()
+    }
+    .to_account_metas(None);
+    accounts.push((key, account));
+    (ix, accounts)
+}
+
+#[test]
+fn risk_ceilings_reject_a_score_above_the_ceiling() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = verify_under_ceiling(&mut mollusk, Some(feed::TEST_SCORE - 1));
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::RiskCeilingExceeded)],
+    );
+}
+
+#[test]
+fn risk_ceilings_pass_a_score_at_the_ceiling_or_without_one() {
+    let Some(mut mollusk) = mollusk() else { return };
+    for max_score in [Some(feed::TEST_SCORE), None] {
+        let (ix, accounts) = verify_under_ceiling(&mut mollusk, max_score);
+        mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    }
+}
+
+/// `verify_test_feed` under address policies with the config threshold at
+/// `risk_threshold`, the wallet's policy being `policy` or unset.
+fn verify_under_policy(
+    mollusk: &mut Mollusk,
+    flags: u64,
+    risk_threshold: u8,
+    policy: Option<(PolicyVerdict, Option<u8>, u8)>,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let (key, bump) = Pubkey::find_program_address(&[ADDRESS_POLICY_SEED, WALLET.as_ref()], &ID);
+    let account = match policy {
+        Some((verdict, threshold, min_oracle_samples)) => {
+            let policy = AddressPolicy {
+                address: WALLET,
+                verdict,
+                threshold,
+                min_oracle_samples,
+                updated_slot: 1,
+                bump,
+            };
+            let mut data = Vec::with_capacity(8 + AddressPolicy::INIT_SPACE);
+            policy.try_serialize(&mut data).unwrap();
+            rent_exempt(ID, data)
+        }
+        None => Account::default(),
+    };
+
+    let flags = flags | feature_flags::ADDRESS_POLICIES;
+    let (mut ix, mut accounts) = verify_test_feed(mollusk, flags, feed_id(&feed::test_feed()));
+    let config = config_state(flags, risk_threshold);
+    replace(&mut accounts, config_address(), config_data(config));
+    ix.accounts = accounts::VerifyRiskScoreFeed {
+        address_policy: Some(key),
+        ..verify_keys()
+    }
+    .to_account_metas(None);
+    accounts.push((key, account));
+    (ix, accounts)
+}
+
+#[test]
+fn address_policies_deny_before_the_score() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let policy = (PolicyVerdict::Deny, None, 0);
+    let (ix, accounts) = verify_under_policy(&mut mollusk, 0, 100, Some(policy));
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::AddressPolicyDenied)],
+    );
+}
+
+#[test]
+fn address_policies_allow_a_score_over_the_config_threshold() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let gating = feature_flags::THRESHOLD_GATING;
+    let (ix, accounts) = verify_under_policy(&mut mollusk, gating, 0, None);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::RiskThresholdExceeded)],
+    );
+    let policy = (PolicyVerdict::Allow, None, 0);
+    let (ix, accounts) = verify_under_policy(&mut mollusk, gating, 0, Some(policy));
+    mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+}
+
+#[test]
+fn address_policy_threshold_gates_with_gating_off() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let policy = (PolicyVerdict::Screen, Some(feed::TEST_SCORE - 1), 0);
+    let (ix, accounts) = verify_under_policy(&mut mollusk, 0, 100, Some(policy));
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::RiskThresholdExceeded)],
+    );
+}
+
+#[test]
+fn address_policy_requires_its_oracle_samples() {
+    let Some(mut mollusk) = mollusk() else { return };
+    // The test quote is signed by one oracle.
+    let policy = (PolicyVerdict::Screen, None, 2);
+    let (ix, accounts) = verify_under_policy(&mut mollusk, 0, 100, Some(policy));
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::InsufficientOracleSamples)],
+    );
+}
+
+fn officer(seed: u8) -> Keypair {
+    keypair_from_seed(&[seed; 32]).unwrap()
+}
+
+/// Compliance officer PDA naming `officer`.
+fn compliance_officer(officer: &Keypair) -> (Pubkey, Account) {
+    let (key, bump) = Pubkey::find_program_address(&[COMPLIANCE_OFFICER_SEED], &ID);
+    let account = ComplianceOfficer {
+        officer: officer.pubkey(),
+        updated_slot: 1,
+        bump,
+    };
+    let mut data = Vec::with_capacity(8 + ComplianceOfficer::INIT_SPACE);
+    account.try_serialize(&mut data).unwrap();
+    (key, rent_exempt(ID, data))
+}
+
+fn bypass_voucher() -> BypassVoucher {
+    BypassVoucher {
+        address: WALLET,
+        not_before_slot: 900,
+        not_after_slot: 1_100,
+        nonce: 1,
+    }
+}
+
+fn bypass_record_address(voucher: &BypassVoucher) -> Pubkey {
+    let seeds: &[&[u8]] = &[
+        BYPASS_SEED,
+        voucher.address.as_ref(),
+        &voucher.nonce.to_le_bytes(),
+    ];
+    Pubkey::find_program_address(seeds, &ID).0
+}
+
+/// `redeem_bypass_voucher` at [`QUOTE_SLOT`], right after an Ed25519
+/// instruction in which `signer` signs `voucher` for the officer `officer`.
+fn redeem_voucher(
+    mollusk: &mut Mollusk,
+    officer: &Keypair,
+    signer: &Keypair,
+    voucher: BypassVoucher,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    mollusk.warp_to_slot(QUOTE_SLOT);
+    let message = voucher::BypassVoucher::from(voucher).message(&ID.to_bytes());
+    let signature: [u8; 64] = signer.sign_message(&message).into();
+    let signature_data =
+        voucher::instruction_data(&signer.pubkey().to_bytes(), &signature, &message);
+
+    let (officer_key, officer_account) = compliance_officer(officer);
+    let record = bypass_record_address(&voucher);
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::RedeemBypassVoucher {
+            compliance_officer: officer_key,
+            instructions: sysvar::instructions::ID,
+            bypass_record: record,
+            payer: AUTHORITY,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::RedeemBypassVoucher { voucher }.data(),
+    };
+    let (key, mut instructions) =
+        instructions_sysvar(&[(ed25519_program::ID, signature_data), (ID, ix.data.clone())]);
+    // The redemption is the instruction being executed.
+    let current_at = instructions.data.len() - 2;
+    instructions.data[current_at..].copy_from_slice(&1u16.to_le_bytes());
+
+    let accounts = vec![
+        (officer_key, officer_account),
+        (key, instructions),
+        (record, Account::default()),
+        (AUTHORITY, wallet()),
+        keyed_account_for_system_program(),
+    ];
+    (ix, accounts)
+}
+
+#[test]
+fn redeem_bypass_voucher_records_the_redemption() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let officer = officer(0xc0);
+    let voucher = bypass_voucher();
+    let (ix, accounts) = redeem_voucher(&mut mollusk, &officer, &officer, voucher);
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let account = result
+        .get_account(&bypass_record_address(&voucher))
+        .unwrap();
+    let record = BypassRecord::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(record.address, WALLET);
+    assert_eq!(record.officer, officer.pubkey());
+    assert_eq!(record.nonce, 1);
+    assert_eq!(record.redeemed_slot, QUOTE_SLOT);
+}
+
+#[test]
+fn redeem_bypass_voucher_rejects_another_signer() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) =
+        redeem_voucher(&mut mollusk, &officer(0xc0), &officer(0xc1), bypass_voucher());
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::InvalidBypassVoucher)],
+    );
+}
+
+#[test]
+fn redeem_bypass_voucher_rejects_a_slot_outside_the_window() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let officer = officer(0xc0);
+    let voucher = BypassVoucher {
+        not_after_slot: QUOTE_SLOT - 1,
+        ..bypass_voucher()
+    };
+    let (ix, accounts) = redeem_voucher(&mut mollusk, &officer, &officer, voucher);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::BypassVoucherExpired)],
+    );
+}
+
+#[test]
+fn redeem_bypass_voucher_is_one_time() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let officer = officer(0xc0);
+    let voucher = bypass_voucher();
+    let (ix, mut accounts) = redeem_voucher(&mut mollusk, &officer, &officer, voucher);
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let record = bypass_record_address(&voucher);
+    replace(
+        &mut accounts,
+        record,
+        result.get_account(&record).unwrap().clone(),
+    );
+    // The system program refuses to allocate the record again.
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::Custom(0))],
+    );
+}
+
+#[test]
+fn redeem_bypass_voucher_requires_the_signature_instruction() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let officer = officer(0xc0);
+    let (ix, mut accounts) = redeem_voucher(&mut mollusk, &officer, &officer, bypass_voucher());
+    let (key, instructions) = instructions_sysvar(&[(ID, ix.data.clone())]);
+    replace(&mut accounts, key, instructions);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::MissingBypassVoucher)],
+    );
+}
+
+/// Quote instruction data of the marker tests; only its hash is checked.
+const MARKER_QUOTE: [u8; 4] = [0x51, 0x0a, 0x7e, 0x01];
+
+fn marker_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MARKER_SEED, WALLET.as_ref()], &ID)
+}
+
+/// `consume_verified_score` as the last instruction of a transaction: the
+/// quote at index 0, then `earlier` (program, marker writable), with the
+/// marker a verification of this program wrote at `QUOTE_SLOT`.
+fn consume_marker(
+    mollusk: &mut Mollusk,
+    earlier: &[(Pubkey, bool)],
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    mollusk.warp_to_slot(QUOTE_SLOT);
+    let (marker_key, bump) = marker_address();
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::ConsumeVerifiedScore {
+            instructions: sysvar::instructions::ID,
+            query_account: WALLET,
+            quote_marker: marker_key,
+        }
+        .to_account_metas(None),
+        data: instruction::ConsumeVerifiedScore {}.data(),
+    };
+
+    let writer_accounts: Vec<Vec<BorrowedAccountMeta>> = earlier
+        .iter()
+        .map(|(_, writable)| {
+            vec![BorrowedAccountMeta {
+                pubkey: &marker_key,
+                is_signer: false,
+                is_writable: *writable,
+            }]
+        })
+        .collect();
+    let mut borrowed = vec![BorrowedInstruction {
+        program_id: &ed25519_program::ID,
+        accounts: Vec::new(),
+        data: &MARKER_QUOTE,
+    }];
+    for ((program_id, _), accounts) in earlier.iter().zip(writer_accounts) {
+        borrowed.push(BorrowedInstruction {
+            program_id,
+            accounts,
+            data: &[],
+        });
+    }
+    borrowed.push(BorrowedInstruction {
+        program_id: &ID,
+        accounts: Vec::new(),
+        data: &ix.data,
+    });
+    let mut instructions = sysvar::instructions::construct_instructions_data(&borrowed);
+    // The consumption is the instruction being executed.
+    let current_at = instructions.len() - 2;
+    instructions[current_at..].copy_from_slice(&(borrowed.len() as u16 - 1).to_le_bytes());
+
+    let marker = QuoteMarker {
+        address: WALLET,
+        score: 12,
+        feed_id: QUOTE_FEED_ID,
+        slot: QUOTE_SLOT,
+        quote_hash: hash(&MARKER_QUOTE).to_bytes(),
+        consumer: ID,
+        bump,
+    };
+    let mut data = Vec::with_capacity(8 + QuoteMarker::INIT_SPACE);
+    marker.try_serialize(&mut data).unwrap();
+
+    let accounts = vec![
+        (
+            sysvar::instructions::ID,
+            rent_exempt(sysvar::ID, instructions),
+        ),
+        (WALLET, wallet()),
+        (marker_key, rent_exempt(ID, data)),
+    ];
+    (ix, accounts)
+}
+
+#[test]
+fn consume_verified_score_reads_a_marker_of_the_transaction() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = consume_marker(&mut mollusk, &[(ID, true)]);
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    assert_eq!(result.return_data, vec![12]);
+}
+
+#[test]
+fn consume_verified_score_rejects_a_marker_of_another_transaction() {
+    let Some(mut mollusk) = mollusk() else { return };
+    // Same slot, same quote, but no verification wrote the marker here.
+    let (ix, accounts) = consume_marker(&mut mollusk, &[]);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::StaleQuoteMarker)],
+    );
+}
+
+#[test]
+fn consume_verified_score_requires_the_writer_to_take_the_marker_writable() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = consume_marker(&mut mollusk, &[(ID, false)]);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::StaleQuoteMarker)],
+    );
+    // Another program's instruction can't have written it for this one.
+    let (ix, accounts) = consume_marker(&mut mollusk, &[(WALLET, true)]);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::StaleQuoteMarker)],
+    );
+}
+
+#[test]
+fn consume_verified_score_rejects_a_marker_of_another_slot() {
+    let Some(mut mollusk) = mollusk() else { return };
+    let (ix, accounts) = consume_marker(&mut mollusk, &[(ID, true)]);
+    mollusk.warp_to_slot(QUOTE_SLOT + 1);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[program_error(ErrorCode::StaleQuoteMarker)],
+    );
+}
@@ -0,0 +1,107 @@
+//! Sizes read from a program's ELF: its sections, and the largest stack frame
+//! its code addresses.
+//!
+//! SBF programs get a fixed 4 KiB frame per call; locals live at negative
+//! offsets of the frame pointer `r10`. The deepest such offset in `.text` is
+//! the largest frame any function needs, the figure `cargo build-sbf` warns
+//! about once it passes the frame size.
+
+use anyhow::{bail, Context, Result};
+
+/// Stack frame of an SBF function.
+pub const STACK_FRAME: u64 = 4096;
+
+/// Frame pointer register.
+const FRAME_POINTER: u8 = 10;
+
+/// Instruction classes addressing memory (`opcode & 0x07`).
+const CLASS_LDX: u8 = 0x01;
+const CLASS_ST: u8 = 0x02;
+const CLASS_STX: u8 = 0x03;
+
+#[derive(Clone, Debug)]
+pub struct Section {
+    pub name: String,
+    pub offset: u64,
+    pub size: u64,
+}
+
+/// Sections of a 64-bit little-endian ELF, in header order.
+pub fn sections(elf: &[u8]) -> Result<Vec<Section>> {
+    if elf.get(..4) != Some(b"\x7fELF".as_slice()) || elf.get(4) != Some(&2) {
+        bail!("not a 64-bit ELF");
+    }
+    let header_offset = read_u64(elf, 0x28)? as usize;
+    let header_len = read_u16(elf, 0x3a)? as usize;
+    let count = read_u16(elf, 0x3c)? as usize;
+    let names_index = read_u16(elf, 0x3e)? as usize;
+
+    let header = |index: usize| -> Result<(u32, u64, u64)> {
+        let at = header_offset + index * header_len;
+        Ok((
+            read_u32(elf, at)?,
+            read_u64(elf, at + 24)?,
+            read_u64(elf, at + 32)?,
+        ))
+    };
+    let (_, names_offset, names_len) = header(names_index)?;
+    let names = elf
+        .get(names_offset as usize..(names_offset + names_len) as usize)
+        .context("section names past the end of the file")?;
+
+    (0..count)
+        .map(|index| {
+            let (name_at, offset, size) = header(index)?;
+            let name = names
+                .get(name_at as usize..)
+                .and_then(|rest| rest.split(|&b| b == 0).next())
+                .context("section name past the section names")?;
+            Ok(Section {
+                name: String::from_utf8_lossy(name).into_owned(),
+                offset,
+                size,
+            })
+        })
+        .collect()
+}
+
+/// Bytes of section `name`.
+pub fn section_data<'a>(elf: &'a [u8], sections: &[Section], name: &str) -> Option<&'a [u8]> {
+    let section = sections.iter().find(|section| section.name == name)?;
+    elf.get(section.offset as usize..(section.offset + section.size) as usize)
+}
+
+/// Largest offset below `r10` loaded from or stored to by `text`.
+pub fn largest_stack_frame(text: &[u8]) -> u64 {
+    text.chunks_exact(8)
+        .filter_map(|insn| {
+            let (dst, src) = (insn[1] & 0x0f, insn[1] >> 4);
+            let base = match insn[0] & 0x07 {
+                CLASS_LDX => src,
+                CLASS_ST | CLASS_STX => dst,
+                _ => return None,
+            };
+            let offset = i16::from_le_bytes([insn[2], insn[3]]);
+            (base == FRAME_POINTER && offset < 0).then(|| u64::from(offset.unsigned_abs()))
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+fn read_u16(elf: &[u8], at: usize) -> Result<u16> {
+    Ok(u16::from_le_bytes(read(elf, at)?))
+}
+
+fn read_u32(elf: &[u8], at: usize) -> Result<u32> {
+    Ok(u32::from_le_bytes(read(elf, at)?))
+}
+
+fn read_u64(elf: &[u8], at: usize) -> Result<u64> {
+    Ok(u64::from_le_bytes(read(elf, at)?))
+}
+
+fn read<const N: usize>(elf: &[u8], at: usize) -> Result<[u8; N]> {
+    elf.get(at..at + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .context("ELF header past the end of the file")
+}
@@ -0,0 +1,4 @@
+//! ELF measurements shared by the `risk-oracle-bench` report and the
+//! `cargo xtask size` budget check.
+
+pub mod elf;
@@ -0,0 +1,128 @@
+//! `risk-oracle-bench`: runs the verifications both programs implement in
+//! LiteSVM and compares compute units, heap, binary and section sizes and the
+//! largest stack frame, side by side.
+//!
+//! Build both programs first, with `heap-log` for the heap figures:
+//!
+//! ```text
+//! cargo build-sbf --manifest-path anchor/programs/risk_oracle_anchor/Cargo.toml --features heap-log
+//! cargo build-sbf --manifest-path pinocchio/programs/risk_oracle_pinocchio/Cargo.toml --features bpf-entrypoint,heap-log
+//! cargo run -p risk-oracle-bench -- --out bench.md
+//! ```
+//!
+//! `--sha2-baseline` adds a column for a Pinocchio build hashing with the
+//! `sha2` crate instead of the `sol_sha256` syscall, the saving of
+//! `risk_oracle_shared::hash`. Copy that build aside, as it shares the
+//! program's file name:
+//!
+//! ```text
+//! cargo build-sbf --manifest-path pinocchio/programs/risk_oracle_pinocchio/Cargo.toml --features bpf-entrypoint,heap-log,sha2-hash
+//! cp target/deploy/risk_oracle.so target/risk_oracle_sha2.so
+//! ```
+
+mod report;
+mod scenario;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use risk_oracle_bench::elf;
+use risk_oracle_shared::profiles;
+
+use report::{Program, Report, ScenarioResult, SectionSize, SECTIONS};
+use scenario::{Implementation, Scenario};
+
+#[derive(Parser)]
+#[command(name = "risk-oracle-bench", about)]
+struct Cli {
+    /// Directory holding `anchor_oracle_example.so` and `risk_oracle.so`.
+    #[arg(long, env = "SBF_OUT_DIR", default_value = "target/deploy")]
+    sbf_out_dir: PathBuf,
+    #[arg(long, value_enum, default_value_t = Format::Markdown)]
+    format: Format,
+    /// Writes the report to this file instead of stdout.
+    #[arg(long)]
+    out: Option<PathBuf>,
+    /// Pinocchio program built with `sha2-hash`, measured as a third column.
+    #[arg(long)]
+    sha2_baseline: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Markdown,
+    Json,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut programs = Implementation::ALL
+        .iter()
+        .map(|&implementation| {
+            let path = cli.sbf_out_dir.join(implementation.file());
+            measure(&path, implementation, implementation.name())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if let Some(path) = &cli.sha2_baseline {
+        programs.push(measure(
+            path,
+            Implementation::Pinocchio,
+            "Pinocchio (sha2)",
+        )?);
+    }
+    let report = Report {
+        profile: profiles::ACTIVE.name,
+        programs,
+    };
+
+    let text = match cli.format {
+        Format::Markdown => report.markdown(),
+        Format::Json => report.json(),
+    };
+    match cli.out {
+        Some(path) => {
+            fs::write(&path, text).with_context(|| format!("failed to write {}", path.display()))
+        }
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+/// Measures the program at `path`, an `implementation` build, as `name`.
+fn measure(path: &Path, implementation: Implementation, name: &'static str) -> Result<Program> {
+    let elf = fs::read(path).with_context(|| {
+        format!(
+            "failed to read {}, build it with `cargo build-sbf`",
+            path.display()
+        )
+    })?;
+    let sections = elf::sections(&elf).with_context(|| format!("{}", path.display()))?;
+    let largest_stack_frame = elf::section_data(&elf, &sections, ".text")
+        .map(elf::largest_stack_frame)
+        .unwrap_or(0);
+
+    Ok(Program {
+        name,
+        binary_bytes: elf.len(),
+        sections: SECTIONS
+            .iter()
+            .filter_map(|name| sections.iter().find(|section| section.name == *name))
+            .map(|section| SectionSize {
+                name: section.name.clone(),
+                bytes: section.size,
+            })
+            .collect(),
+        largest_stack_frame,
+        scenarios: Scenario::all()
+            .into_iter()
+            .map(|scenario| {
+                let outcome = scenario::run(&elf, implementation, scenario);
+                ScenarioResult::new(scenario.name(), outcome)
+            })
+            .collect(),
+    })
+}
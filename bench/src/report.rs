@@ -0,0 +1,146 @@
+//! The comparison, as a Markdown table per measure or as JSON.
+
+use std::fmt::Write;
+
+use risk_oracle_bench::elf::STACK_FRAME;
+use serde::Serialize;
+
+use crate::scenario::Outcome;
+
+/// Sections whose sizes are reported, when present.
+pub const SECTIONS: &[&str] = &[".text", ".rodata", ".data.rel.ro", ".dynsym", ".dynstr"];
+
+#[derive(Serialize)]
+pub struct Report {
+    pub profile: &'static str,
+    pub programs: Vec<Program>,
+}
+
+#[derive(Serialize)]
+pub struct Program {
+    pub name: &'static str,
+    pub binary_bytes: usize,
+    pub sections: Vec<SectionSize>,
+    pub largest_stack_frame: u64,
+    pub scenarios: Vec<ScenarioResult>,
+}
+
+#[derive(Serialize)]
+pub struct SectionSize {
+    pub name: String,
+    pub bytes: u64,
+}
+
+#[derive(Serialize)]
+pub struct ScenarioResult {
+    pub name: &'static str,
+    pub compute_units: u64,
+    pub heap_bytes: Option<usize>,
+    pub error: Option<String>,
+}
+
+impl ScenarioResult {
+    pub fn new(name: &'static str, outcome: Outcome) -> Self {
+        Self {
+            name,
+            compute_units: outcome.compute_units,
+            heap_bytes: outcome.heap_bytes,
+            error: outcome.error,
+        }
+    }
+}
+
+impl Report {
+    pub fn json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("report serializes")
+    }
+
+    /// Side by side, one column per program.
+    pub fn markdown(&self) -> String {
+        let mut out = format!("# Anchor vs Pinocchio ({} profile)\n\n", self.profile);
+
+        self.table(&mut out, "Binary", |program| {
+            let mut rows = vec![("size".to_string(), program.binary_bytes.to_string())];
+            rows.extend(
+                program
+                    .sections
+                    .iter()
+                    .map(|section| (format!("`{}`", section.name), section.bytes.to_string())),
+            );
+            rows.push((
+                "largest stack frame".to_string(),
+                format!("{} / {}", program.largest_stack_frame, STACK_FRAME),
+            ));
+            rows
+        });
+        self.table(&mut out, "Compute units", |program| {
+            program
+                .scenarios
+                .iter()
+                .map(|scenario| {
+                    let value = match &scenario.error {
+                        Some(error) => format!("{} (failed: {})", scenario.compute_units, error),
+                        None => scenario.compute_units.to_string(),
+                    };
+                    (scenario.name.to_string(), value)
+                })
+                .collect()
+        });
+        self.table(&mut out, "Heap bytes", |program| {
+            program
+                .scenarios
+                .iter()
+                .map(|scenario| {
+                    let value = scenario
+                        .heap_bytes
+                        .map_or_else(|| "-".to_string(), |bytes| bytes.to_string());
+                    (scenario.name.to_string(), value)
+                })
+                .collect()
+        });
+        out.push_str(
+            "Heap figures need builds with the `heap-log` feature; \
+             `-` marks a build without it or a failed verification.\n",
+        );
+        out
+    }
+
+    /// Table `title` of the `rows` of each program, joined on the row name.
+    fn table(
+        &self,
+        out: &mut String,
+        title: &str,
+        rows: impl Fn(&Program) -> Vec<(String, String)>,
+    ) {
+        let columns: Vec<_> = self.programs.iter().map(&rows).collect();
+        let mut names: Vec<String> = Vec::new();
+        for (name, _) in columns.iter().flatten() {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+
+        let _ = writeln!(out, "## {}\n", title);
+        out.push('|');
+        for program in &self.programs {
+            let _ = write!(out, " | {}", program.name);
+        }
+        out.push_str(" |\n|---");
+        for _ in &self.programs {
+            out.push_str("|---:");
+        }
+        out.push_str("|\n");
+        for name in names {
+            let _ = write!(out, "| {}", name);
+            for column in &columns {
+                let value = column
+                    .iter()
+                    .find(|(row, _)| *row == name)
+                    .map_or("-", |(_, value)| value.as_str());
+                let _ = write!(out, " | {}", value);
+            }
+            out.push_str(" |\n");
+        }
+        out.push('\n');
+    }
+}
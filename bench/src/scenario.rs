@@ -0,0 +1,294 @@
+//! The verifications both programs implement, run in LiteSVM: a wallet
+//! screened with a quote signed by a local oracle for the feed the program
+//! derives, through the same modes on both sides.
+
+use anchor_lang::{AccountSerialize, InstructionData, Space, ToAccountMetas};
+use anchor_oracle_example::state::{Config, CONFIG_SEED};
+use anchor_oracle_example::{accounts, instruction};
+use litesvm::LiteSVM;
+use risk_oracle_client::fixtures;
+use risk_oracle_client::quote::QuoteFeed;
+use risk_oracle_shared::proto::OracleFeed;
+use risk_oracle_shared::{errors, feed, heap, profiles, query_account};
+use sha2::{Digest, Sha256};
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::slot_hashes::SlotHashes;
+use solana_sdk::transaction::Transaction;
+use solana_sdk::{pubkey, system_program, sysvar};
+
+/// Program id of the Pinocchio program (`pinocchio/.../lib.rs`).
+pub const PINOCCHIO_ID: Pubkey = pubkey!("CR8mpiY9eEbNkU8w4VJkGB4gzEnozp739jwvTiXRmACc");
+
+const WALLET: Pubkey = Pubkey::new_from_array([0xa3; 32]);
+const MINT: Pubkey = Pubkey::new_from_array([0xa4; 32]);
+/// Score of the signed quotes, under the Anchor config's threshold.
+const SCORE: i128 = 25;
+/// Slot of the signed quotes and of the verifications.
+const QUOTE_SLOT: u64 = 1_000;
+const SLOT_HASH: [u8; 32] = [0x5a; 32];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Implementation {
+    Anchor,
+    Pinocchio,
+}
+
+impl Implementation {
+    pub const ALL: [Implementation; 2] = [Implementation::Anchor, Implementation::Pinocchio];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Anchor => "Anchor",
+            Self::Pinocchio => "Pinocchio",
+        }
+    }
+
+    /// File `cargo build-sbf` writes the program to.
+    pub fn file(self) -> &'static str {
+        match self {
+            Self::Anchor => "anchor_oracle_example.so",
+            Self::Pinocchio => "risk_oracle.so",
+        }
+    }
+
+    pub fn id(self) -> Pubkey {
+        match self {
+            Self::Anchor => anchor_oracle_example::ID,
+            Self::Pinocchio => PINOCCHIO_ID,
+        }
+    }
+}
+
+/// A verification mode of both programs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scenario {
+    /// A wallet, with the Range score feed.
+    Wallet,
+    /// ATA mode: the wallet owning a token account.
+    AssociatedTokenAccount,
+    /// A wallet, with a deadline slot.
+    Deadline,
+    /// The constant test feed.
+    TestFeed,
+}
+
+impl Scenario {
+    /// Scenarios of the active profile; the test feed only where it is enabled.
+    pub fn all() -> Vec<Scenario> {
+        let mut scenarios = vec![Self::Wallet, Self::AssociatedTokenAccount, Self::Deadline];
+        if profiles::ACTIVE.test_feed {
+            scenarios.push(Self::TestFeed);
+        }
+        scenarios
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Wallet => "verify",
+            Self::AssociatedTokenAccount => "verify (ATA mode)",
+            Self::Deadline => "verify with deadline",
+            Self::TestFeed => "verify test feed",
+        }
+    }
+
+    fn feed(self) -> OracleFeed {
+        match self {
+            Self::TestFeed => feed::test_feed(),
+            _ => feed::risk_score_feed(&WALLET.to_bytes()),
+        }
+    }
+
+    /// Instruction data of the mode in `implementation`.
+    fn data(self, implementation: Implementation) -> Vec<u8> {
+        let token_program = Pubkey::new_from_array(query_account::TOKEN_PROGRAM_ID);
+        match (implementation, self) {
+            (Implementation::Anchor, Self::Wallet) => {
+                instruction::VerifyRiskScoreFeedCompact {}.data()
+            }
+            (Implementation::Anchor, Self::AssociatedTokenAccount) => {
+                instruction::VerifyRiskScoreFeedForAta {
+                    mint: MINT,
+                    token_program,
+                }
+                .data()
+            }
+            (Implementation::Anchor, Self::Deadline) => {
+                instruction::VerifyRiskScoreFeedWithDeadline {
+                    not_after_slot: QUOTE_SLOT,
+                }
+                .data()
+            }
+            (Implementation::Anchor, Self::TestFeed) => instruction::VerifyTestFeed {}.data(),
+            (Implementation::Pinocchio, Self::Wallet) => Vec::new(),
+            (Implementation::Pinocchio, Self::AssociatedTokenAccount) => {
+                let mut data = vec![1];
+                data.extend_from_slice(MINT.as_ref());
+                data.extend_from_slice(token_program.as_ref());
+                data
+            }
+            (Implementation::Pinocchio, Self::Deadline) => {
+                let mut data = vec![4];
+                data.extend_from_slice(&QUOTE_SLOT.to_le_bytes());
+                data
+            }
+            (Implementation::Pinocchio, Self::TestFeed) => vec![2],
+        }
+    }
+}
+
+/// What one verification cost.
+#[derive(Clone, Debug)]
+pub struct Outcome {
+    pub compute_units: u64,
+    /// From the `risk-oracle-heap` line of `heap-log` builds.
+    pub heap_bytes: Option<usize>,
+    /// The transaction error, with the program's error name.
+    pub error: Option<String>,
+}
+
+/// Runs `scenario` through the program `elf` of `implementation`.
+pub fn run(elf: &[u8], implementation: Implementation, scenario: Scenario) -> Outcome {
+    let program_id = implementation.id();
+    let mut svm = LiteSVM::new().with_blockhash_check(false);
+    svm.add_program(program_id, elf);
+
+    let mut clock: Clock = svm.get_sysvar();
+    clock.slot = QUOTE_SLOT;
+    svm.set_sysvar(&clock);
+    svm.set_sysvar(&SlotHashes::new(&[(
+        QUOTE_SLOT,
+        Hash::new_from_array(SLOT_HASH),
+    )]));
+
+    let oracle = fixtures::oracle_keypair(0);
+    let payer = Keypair::new();
+    let queue = Pubkey::new_from_array(profiles::ACTIVE.queue);
+    let mut accounts = vec![
+        (queue, fixtures::queue_account(&[oracle.pubkey()]).unwrap()),
+        (WALLET, Account::new(1_000_000_000, 0, &system_program::ID)),
+    ];
+    if implementation == Implementation::Anchor {
+        accounts.push(config_account());
+    }
+    for (key, account) in accounts {
+        svm.set_account(key, account).unwrap();
+    }
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+
+    let feed_id: [u8; 32] = Sha256::digest(feed::encode(&scenario.feed())).into();
+    let quote = fixtures::quote_instruction(
+        &[oracle],
+        SLOT_HASH,
+        QUOTE_SLOT,
+        &[QuoteFeed {
+            feed_id,
+            value: SCORE * 10i128.pow(feed::VALUE_DECIMALS),
+            min_oracle_samples: 1,
+        }],
+    );
+    let verify = Instruction {
+        program_id,
+        accounts: verify_accounts(implementation, queue),
+        data: scenario.data(implementation),
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &[quote, verify],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    );
+
+    let (compute_units, logs, error) = match svm.send_transaction(transaction) {
+        Ok(result) => (result.compute_units_consumed, result.logs, None),
+        Err(failed) => (
+            failed.meta.compute_units_consumed,
+            failed.meta.logs,
+            Some(errors::describe(&failed.err.to_string())),
+        ),
+    };
+    Outcome {
+        compute_units,
+        heap_bytes: logs.iter().find_map(|log| heap_used(log)),
+        error,
+    }
+}
+
+fn verify_accounts(implementation: Implementation, queue: Pubkey) -> Vec<AccountMeta> {
+    match implementation {
+        Implementation::Anchor => accounts::VerifyRiskScoreFeed {
+            config: Pubkey::find_program_address(&[CONFIG_SEED], &anchor_oracle_example::ID).0,
+            queue,
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            query_account: WALLET,
+            payer: None,
+            replay_record: None,
+            treasury: None,
+            system_program: None,
+            registry: None,
+            feed_id_record: None,
+            quote_marker: None,
+            receipt: None,
+            history: None,
+            denylist_entry: None,
+            integrator: None,
+            program_denylist: None,
+            risk_ceiling: None,
+            address_policy: None,
+            denylist_segment: None,
+        }
+        .to_account_metas(None),
+        // The clock is read through the syscall.
+        Implementation::Pinocchio => vec![
+            AccountMeta::new_readonly(queue, false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+            AccountMeta::new_readonly(WALLET, false),
+        ],
+    }
+}
+
+/// Anchor config with no feature flag and a threshold every score passes.
+fn config_account() -> (Pubkey, Account) {
+    let (address, bump) = Pubkey::find_program_address(&[CONFIG_SEED], &anchor_oracle_example::ID);
+    let config = Config {
+        authority: Pubkey::new_unique(),
+        treasury: Pubkey::new_unique(),
+        flags: 0,
+        risk_threshold: 100,
+        fee_lamports: 0,
+        bump,
+        emergency_threshold: 0,
+        emergency_expiry_slot: 0,
+        ramp_from: 0,
+        ramp_start_slot: 0,
+        ramp_end_slot: 0,
+    };
+    let mut data = Vec::with_capacity(8 + Config::INIT_SPACE);
+    config.try_serialize(&mut data).unwrap();
+    (address, rent_exempt(anchor_oracle_example::ID, data))
+}
+
+fn rent_exempt(owner: Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// `used` of a `risk-oracle-heap` log line.
+fn heap_used(log: &str) -> Option<usize> {
+    let (_, line) = log.split_once(heap::PREFIX)?;
+    line.split_whitespace()
+        .find_map(|field| field.strip_prefix("used="))
+        .and_then(|used| used.parse().ok())
+}
@@ -0,0 +1,84 @@
+//! `dlq`: inspects and replays the keeper's dead-letter queue.
+
+use std::str::FromStr;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use risk_oracle_config::KeeperConfig;
+use risk_oracle_keeper::store::Store;
+use serde_json::json;
+
+#[derive(Subcommand)]
+pub enum DlqCommand {
+    /// List the refreshes the keeper gave up on.
+    List {
+        #[arg(long)]
+        json: bool,
+    },
+    /// Put addresses back in the keeper's rotation, once the root cause of
+    /// their failures is fixed.
+    Replay {
+        /// Query account to replay.
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        query_account: Option<String>,
+        /// Replay the whole queue.
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+pub fn dlq(keeper: &KeeperConfig, command: DlqCommand) -> Result<()> {
+    let store = Store::open(&keeper.state_db)
+        .with_context(|| format!("failed to open {}", keeper.state_db.display()))?;
+
+    match command {
+        DlqCommand::List { json: true } => {
+            let letters: Vec<_> = store
+                .dead_letters()?
+                .into_iter()
+                .map(|letter| {
+                    json!({
+                        "query_account": letter.target.query_account.to_string(),
+                        "screened_address": letter.target.screened_address.to_string(),
+                        "networks": letter.target.networks.bits(),
+                        "record_history": letter.target.record_history,
+                        "freshness_sla": letter.target.freshness_sla,
+                        "failures": letter.failures,
+                        "last_error": letter.last_error,
+                        "added_at": letter.added_at,
+                    })
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&letters)?);
+        }
+        DlqCommand::List { json: false } => {
+            for letter in store.dead_letters()? {
+                println!(
+                    "{} (screens {}) failed {} times, since {}: {}",
+                    letter.target.query_account,
+                    letter.target.screened_address,
+                    letter.failures,
+                    letter.added_at,
+                    letter.last_error
+                );
+            }
+        }
+        DlqCommand::Replay { all: true, .. } => {
+            println!("replayed {} addresses", store.replay_all()?);
+        }
+        DlqCommand::Replay {
+            query_account: Some(query_account),
+            ..
+        } => {
+            let query_account =
+                Pubkey::from_str(&query_account).context("invalid query account")?;
+            if !store.replay(&query_account)? {
+                bail!("{} is not in the dead-letter queue", query_account);
+            }
+            println!("replayed {}", query_account);
+        }
+        DlqCommand::Replay { .. } => unreachable!("clap requires an address or --all"),
+    }
+    Ok(())
+}
@@ -0,0 +1,143 @@
+//! `dump`: prints program accounts as JSON, through the account types' serde
+//! implementations.
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::Keypair;
+use anchor_client::{Client, Cluster};
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use risk_oracle_client::describe::{self, Value};
+use risk_oracle_client::{Page, RiskOracleClient};
+use risk_oracle_config::RpcConfig;
+use risk_oracle_shared::diagnostics::hex;
+use serde::Serialize;
+
+#[derive(Subcommand)]
+pub enum Account {
+    Config,
+    /// Upgrade authority of the program, which must sign `initialize_config`.
+    UpgradeAuthority,
+    /// Config and feature flags as the program describes them (`describe`).
+    Description,
+    Registry,
+    DenylistRoot,
+    /// Programs allowed to reveal confidential scores.
+    AccessList,
+    /// Programs denied in verified transactions.
+    ProgramDenylist,
+    /// Penalty cached scores take as they age.
+    ScoreDecay,
+    /// Score cache of a query account.
+    Cache { query_account: String },
+    /// Score history of a query account.
+    History { query_account: String },
+    /// Freshness SLA of a query account.
+    Sla { query_account: String },
+    /// Confidential score commitment of a query account.
+    Confidential { query_account: String },
+    /// Threshold proof of a query account.
+    ThresholdProof { query_account: String },
+    /// Usage account of a consuming program.
+    Integrator { consumer: String },
+    /// Risk ceiling of a consuming program.
+    RiskCeiling { consumer: String },
+    /// Packed denylist segment a wallet would be listed in.
+    DenylistSegment { address: String },
+    /// Address policy of a screened wallet.
+    AddressPolicy { address: String },
+    /// Every score cache.
+    Watchlist,
+    /// Every imported denylist entry.
+    Denylist,
+    /// Every freshness SLA.
+    Slas,
+    /// Every integrator registered for accounting.
+    Integrators,
+}
+
+pub fn dump(rpc: &RpcConfig, account: Account) -> Result<()> {
+    // Reads only: the payer never signs anything.
+    let cluster = Cluster::Custom(rpc.url.clone(), rpc.ws_url());
+    let client = Client::new(cluster, Rc::new(Keypair::new()));
+    let oracle = RiskOracleClient::new(&client)?;
+
+    match account {
+        Account::Config => print(&oracle.get_config()?),
+        Account::UpgradeAuthority => {
+            print(&oracle.get_upgrade_authority()?.map(|key| key.to_string()))
+        }
+        Account::Description => print(&description(&oracle.view_description()?)),
+        Account::Registry => print(&oracle.get_registry()?),
+        Account::DenylistRoot => print(&oracle.get_denylist_root()?),
+        Account::AccessList => print(&oracle.get_access_list()?),
+        Account::ProgramDenylist => print(&oracle.get_program_denylist()?),
+        Account::ScoreDecay => print(&oracle.get_score_decay()?),
+        Account::Cache { query_account } => {
+            print(&oracle.get_cached_score(&parse(&query_account)?)?)
+        }
+        Account::History { query_account } => print(&oracle.get_history(&parse(&query_account)?)?),
+        Account::Sla { query_account } => {
+            print(&oracle.get_freshness_sla(&parse(&query_account)?)?)
+        }
+        Account::Confidential { query_account } => {
+            print(&oracle.get_confidential_score(&parse(&query_account)?)?)
+        }
+        Account::ThresholdProof { query_account } => {
+            print(&oracle.get_threshold_proof(&parse(&query_account)?)?)
+        }
+        Account::Integrator { consumer } => {
+            print(&oracle.get_integrator(&parse(&consumer)?)?)
+        }
+        Account::RiskCeiling { consumer } => {
+            print(&oracle.get_risk_ceiling(&parse(&consumer)?)?)
+        }
+        Account::DenylistSegment { address } => {
+            print(&oracle.get_denylist_segment(&parse(&address)?)?)
+        }
+        Account::AddressPolicy { address } => {
+            print(&oracle.get_address_policy(&parse(&address)?)?)
+        }
+        Account::Watchlist => print(&keyed(oracle.list_watchlist(Page::ALL)?)),
+        Account::Denylist => print(&keyed(oracle.list_denylist(Page::ALL)?)),
+        Account::Slas => print(&keyed(oracle.list_freshness_slas(Page::ALL)?)),
+        Account::Integrators => print(&keyed(oracle.list_integrators(Page::ALL)?)),
+    }
+}
+
+/// `describe` entries as a JSON object, in the program's order.
+fn description(data: &[u8]) -> serde_json::Map<String, serde_json::Value> {
+    let entries = describe::entries(data).unwrap_or_default();
+    entries
+        .into_iter()
+        .map(|entry| {
+            let value = match entry.value {
+                Value::Unsigned(value) => value.into(),
+                Value::Bool(value) => value.into(),
+                Value::Pubkey(key) => Pubkey::new_from_array(*key).to_string().into(),
+                Value::Text(text) => text.into(),
+                Value::Unknown { kind, bytes } => format!("{:#04x}:{}", kind, hex(bytes)).into(),
+            };
+            (entry.name.to_string(), value)
+        })
+        .collect()
+}
+
+fn parse(address: &str) -> Result<Pubkey> {
+    Pubkey::from_str(address).context("invalid address")
+}
+
+/// Accounts of a listing, keyed by their base58 address.
+pub(crate) fn keyed<T>(accounts: Vec<(Pubkey, T)>) -> Vec<(String, T)> {
+    accounts
+        .into_iter()
+        .map(|(address, account)| (address.to_string(), account))
+        .collect()
+}
+
+fn print(value: &impl Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
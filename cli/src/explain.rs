@@ -0,0 +1,53 @@
+//! `explain`: what an error code means and what to do about it, from the
+//! shared `errors` and `explain` tables.
+
+use anyhow::{anyhow, Result};
+use risk_oracle_shared::errors::{self, ErrorInfo, ANCHOR_ERROR_OFFSET};
+use risk_oracle_shared::explain;
+
+/// Explains `error`: a code in hex (`0x1775`) or decimal (`6005`), or an
+/// error name, which can name an error of both programs.
+pub fn explain(error: &str) -> Result<()> {
+    let code = match error.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => error.parse().ok(),
+    };
+    let matches: Vec<&ErrorInfo> = match code {
+        Some(code) => errors::lookup(code).into_iter().collect(),
+        None => errors::ANCHOR
+            .iter()
+            .chain(errors::PINOCCHIO)
+            .filter(|info| info.name.eq_ignore_ascii_case(error))
+            .collect(),
+    };
+    if matches.is_empty() {
+        return Err(anyhow!("`{}` is not an error of either program", error));
+    }
+
+    for (i, info) in matches.into_iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_explanation(info);
+    }
+    Ok(())
+}
+
+fn print_explanation(info: &ErrorInfo) {
+    let program = if info.code >= ANCHOR_ERROR_OFFSET {
+        "Anchor"
+    } else {
+        "Pinocchio"
+    };
+    println!("{} ({:#x}, {} program)", info.name, info.code, program);
+    println!("  {}", info.message);
+    let Some(explanation) = explain::explain(info.code) else {
+        return;
+    };
+    println!("  raised by: {}", explanation.module);
+    println!("  likely causes:");
+    for cause in explanation.causes {
+        println!("    - {}", cause);
+    }
+    println!("  next: {}", explanation.next);
+}
@@ -0,0 +1,77 @@
+//! `fixture`: writes account images for local testing in the
+//! `solana-test-validator --account` JSON format.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anchor_client::solana_sdk::account::Account;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::Signer;
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use clap::Subcommand;
+use risk_oracle_client::fixtures;
+use serde_json::json;
+
+#[derive(Subcommand)]
+pub enum Fixture {
+    /// The profile's Switchboard queue, with test-controlled oracle keys.
+    Queue {
+        /// Use the deterministic `fixtures::oracle_keypair` keys 0..N.
+        #[arg(long, default_value_t = 1, conflicts_with = "oracle")]
+        oracles: u8,
+        /// Oracle signing key (base58); repeat for several oracles.
+        #[arg(long)]
+        oracle: Vec<String>,
+        #[arg(long)]
+        out: PathBuf,
+    },
+}
+
+pub fn fixture(fixture: Fixture) -> Result<()> {
+    match fixture {
+        Fixture::Queue {
+            oracles,
+            oracle,
+            out,
+        } => {
+            let keys = if oracle.is_empty() {
+                (0..oracles)
+                    .map(|index| fixtures::oracle_keypair(index).pubkey())
+                    .collect()
+            } else {
+                oracle
+                    .iter()
+                    .map(|key| Pubkey::from_str(key).context("invalid oracle key"))
+                    .collect::<Result<Vec<_>>>()?
+            };
+            let account = fixtures::queue_account(&keys)?;
+            let queue = risk_oracle_client::queue();
+            write(&out, &queue, &account)?;
+
+            eprintln!("queue {} written to {}", queue, out.display());
+            for (index, key) in keys.iter().enumerate() {
+                eprintln!("  oracle {} {}", index, key);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write(out: &Path, address: &Pubkey, account: &Account) -> Result<()> {
+    let json = json!({
+        "pubkey": address.to_string(),
+        "account": {
+            "lamports": account.lamports,
+            "data": [STANDARD.encode(&account.data), "base64"],
+            "owner": account.owner.to_string(),
+            "executable": account.executable,
+            "rentEpoch": account.rent_epoch,
+            "space": account.data.len(),
+        },
+    });
+    fs::write(out, serde_json::to_string_pretty(&json)?)
+        .with_context(|| format!("failed to write {}", out.display()))
+}
@@ -0,0 +1,161 @@
+//! `import-list`: seeds a list from a CSV file, batching the admin
+//! instructions into as many transactions as needed.
+//!
+//! Progress is checkpointed to `<csv>.progress` after every confirmed
+//! transaction, so a failed run resumes where it stopped when started again
+//! with the same file. The program skips entries that already exist, so
+//! re-sending a batch is harmless either way.
+//!
+//! `--segmented` writes the denylist to the packed segments instead of one
+//! PDA per address, one `update_denylist_segment` per segment batch.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{Keypair, Signer};
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use risk_oracle_client::{denylist, instructions, transaction};
+use risk_oracle_config::Config;
+use risk_oracle_keeper::rpc::RpcPool;
+use sha2::{Digest, Sha256};
+
+/// Addresses per transaction. A full `import_denylist` batch (16) doesn't fit
+/// a 1232-byte transaction: each address costs an account key and 32 bytes
+/// of instruction data.
+const ADDRESSES_PER_TRANSACTION: usize = 14;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ListKind {
+    Allow,
+    Deny,
+}
+
+pub fn import_list(
+    config: &Config,
+    authority: &Keypair,
+    kind: ListKind,
+    csv: &Path,
+    segmented: bool,
+) -> Result<()> {
+    if let ListKind::Allow = kind {
+        bail!("the program has no allowlist, only `--kind deny` can be imported");
+    }
+
+    let contents =
+        fs::read_to_string(csv).with_context(|| format!("failed to read {}", csv.display()))?;
+    let addresses = parse_csv(&contents)?;
+    let checksum = checksum(&addresses);
+
+    // Batches differ between the two layouts, and so does their progress.
+    let extension = match segmented {
+        true => "segments.progress",
+        false => "progress",
+    };
+    let progress_path = progress_path(csv, extension);
+    let mut done = read_progress(&progress_path, &checksum)?;
+    if done > 0 {
+        eprintln!("resuming after {} of {} addresses", done, addresses.len());
+    }
+
+    // Segment batches come in segment order, the same for the same file.
+    let batches: Vec<(Option<u16>, Vec<Pubkey>)> = match segmented {
+        true => denylist::segment_batches(addresses.iter().copied())
+            .into_iter()
+            .map(|(segment, batch)| (Some(segment), batch))
+            .collect(),
+        false => addresses
+            .chunks(ADDRESSES_PER_TRANSACTION)
+            .map(|batch| (None, batch.to_vec()))
+            .collect(),
+    };
+
+    let mut pool = RpcPool::from_config(config);
+    let options = pool.transaction_options(&config.transactions)?;
+    let mut skipped = 0;
+    for (segment, batch) in batches {
+        let len = batch.len();
+        // Progress always ends on a batch boundary.
+        if skipped < done {
+            skipped += len;
+            continue;
+        }
+        let instruction = match segment {
+            Some(segment) => instructions::update_denylist_segment(
+                authority.pubkey(),
+                segment,
+                batch,
+                Vec::new(),
+            ),
+            None => instructions::import_denylist(authority.pubkey(), batch),
+        };
+        let signature = pool.send_and_confirm(|blockhash| {
+            Ok(transaction::transaction(&[authority], &[instruction.clone()], blockhash, &options)?)
+        })?;
+
+        done += len;
+        fs::write(&progress_path, format!("{} {}\n", done, checksum))
+            .with_context(|| format!("failed to write {}", progress_path.display()))?;
+        eprintln!("{}/{} imported ({})", done, addresses.len(), signature);
+    }
+
+    fs::remove_file(&progress_path).ok();
+    eprintln!("imported {} addresses from {}", addresses.len(), csv.display());
+    Ok(())
+}
+
+/// Addresses of the first column, in order and deduplicated. Blank lines,
+/// `#` comments and a header line are skipped.
+pub(crate) fn parse_csv(contents: &str) -> Result<Vec<Pubkey>> {
+    let mut seen = HashSet::new();
+    let mut addresses = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let field = line.split(',').next().unwrap_or_default().trim().trim_matches('"');
+        if field.is_empty() || field.starts_with('#') {
+            continue;
+        }
+        let address = match Pubkey::from_str(field) {
+            Ok(address) => address,
+            Err(_) if index == 0 => continue,
+            Err(_) => bail!("line {}: `{}` is not a valid address", index + 1, field),
+        };
+        if seen.insert(address) {
+            addresses.push(address);
+        }
+    }
+    Ok(addresses)
+}
+
+/// Identifies the address list, so progress of another file isn't reused.
+pub(crate) fn checksum(addresses: &[Pubkey]) -> String {
+    let mut hasher = Sha256::new();
+    for address in addresses {
+        hasher.update(address.as_ref());
+    }
+    hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// `<csv>.<extension>`.
+pub(crate) fn progress_path(csv: &Path, extension: &str) -> PathBuf {
+    let mut path = csv.as_os_str().to_owned();
+    path.push(".");
+    path.push(extension);
+    PathBuf::from(path)
+}
+
+/// Addresses already done from this list, 0 without a matching progress
+/// file.
+pub(crate) fn read_progress(path: &Path, checksum: &str) -> Result<usize> {
+    let Ok(progress) = fs::read_to_string(path) else {
+        return Ok(0);
+    };
+    match progress.trim().split_once(' ') {
+        Some((done, sum)) if sum == checksum => {
+            done.parse().with_context(|| format!("corrupt {}", path.display()))
+        }
+        _ => Ok(0),
+    }
+}
@@ -0,0 +1,253 @@
+//! `inspect-tx`: fetches a transaction and prints its quote, events and
+//! errors, so production issues can be debugged without hand-decoding base64.
+
+use std::str::FromStr;
+
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::RpcTransactionConfig;
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::ed25519_program;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::Signature;
+use anyhow::{Context, Result};
+use risk_oracle_client::events::{self, OracleEvent};
+use risk_oracle_client::{errors, quote};
+use risk_oracle_shared::compute;
+use risk_oracle_shared::diagnostics::{self, hex};
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionEncoding;
+
+pub fn inspect_tx(url: &str, signature: &str, json: bool) -> Result<()> {
+    let signature = Signature::from_str(signature).context("invalid signature")?;
+    let client = RpcClient::new(url.to_string());
+    let confirmed = client
+        .get_transaction_with_config(
+            &signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .context("failed to fetch the transaction")?;
+    let transaction = confirmed
+        .transaction
+        .transaction
+        .decode()
+        .context("failed to decode the transaction")?;
+
+    let message = &transaction.message;
+    let keys = message.static_account_keys();
+    let quote_ixs = message
+        .instructions()
+        .iter()
+        .enumerate()
+        .filter(|(_, ix)| keys.get(ix.program_id_index as usize) == Some(&ed25519_program::ID));
+
+    if json {
+        let quotes = quote_ixs
+            .map(|(_, ix)| quote::decode(&ix.data))
+            .collect::<Result<Vec<_>, _>>()?;
+        println!("{}", serde_json::to_string_pretty(&quotes)?);
+        return Ok(());
+    }
+
+    println!("Transaction {} (slot {})", signature, confirmed.slot);
+
+    let mut found = false;
+    for (index, ix) in quote_ixs {
+        found = true;
+        println!("\nQuote (instruction {})", index);
+        match quote::decode(&ix.data) {
+            Ok(quote) => print_quote(&quote),
+            Err(err) => println!("  undecodable: {:#}\n  raw: {}", err, hex(&ix.data)),
+        }
+    }
+    if !found {
+        println!("\nNo Ed25519 quote instruction in this transaction");
+    }
+
+    let logs = match confirmed.transaction.meta.map(|meta| meta.log_messages) {
+        Some(OptionSerializer::Some(logs)) => logs,
+        _ => Vec::new(),
+    };
+    print_logs(&logs);
+    Ok(())
+}
+
+fn print_quote(quote: &quote::Quote) {
+    match quote.slot {
+        Some(slot) => println!("  slot:      {}", slot),
+        None => println!("  slot:      unknown"),
+    }
+    println!("  slot hash: {}", hex(&quote.slot_hash));
+    println!("  signers:");
+    for signature in &quote.signatures {
+        println!("    {}", Pubkey::new_from_array(signature.signer));
+    }
+    println!("  feeds:");
+    for feed in &quote.feeds {
+        println!(
+            "    {}  value {}  min samples {}",
+            hex(&feed.feed_id),
+            quote::format_value(feed.value),
+            feed.min_oracle_samples
+        );
+    }
+}
+
+fn print_logs(logs: &[String]) {
+    let events = events::from_logs(logs);
+    if !events.is_empty() {
+        println!("\nEvents");
+        for event in &events {
+            print_event(event);
+        }
+    }
+
+    let context: Vec<_> = logs.iter().filter_map(|line| diagnostics::parse(line)).collect();
+    if let Some(error) = errors::from_logs(logs) {
+        println!("\nError {} ({}): {}", error.name, error.number, error.message);
+    }
+    for fields in context {
+        let fields: Vec<String> = fields.map(|(k, v)| format!("{}={}", k, v)).collect();
+        println!("  {}", fields.join(" "));
+    }
+
+    print_compute(logs);
+}
+
+/// Checkpoints of `compute-log` builds, with the units spent since the
+/// previous one.
+fn print_compute(logs: &[String]) {
+    let checkpoints: Vec<(&str, u64)> = logs
+        .iter()
+        .filter_map(|line| diagnostics::parse_with(compute::PREFIX, line))
+        .filter_map(|mut fields| {
+            let name = fields.find(|(k, _)| *k == "checkpoint")?.1;
+            let remaining = fields.find(|(k, _)| *k == "remaining")?.1.parse().ok()?;
+            Some((name, remaining))
+        })
+        .collect();
+    if checkpoints.is_empty() {
+        return;
+    }
+
+    println!("\nCompute");
+    let mut previous = None;
+    for (name, remaining) in checkpoints {
+        match previous {
+            Some(previous) => println!(
+                "  {:<10} remaining {:>7}  spent {:>7}",
+                name,
+                remaining,
+                u64::saturating_sub(previous, remaining)
+            ),
+            None => println!("  {:<10} remaining {:>7}", name, remaining),
+        }
+        previous = Some(remaining);
+    }
+}
+
+pub(crate) fn print_event(event: &OracleEvent) {
+    match event {
+        OracleEvent::RiskScoreVerified(e) => {
+            println!(
+                "  RiskScoreVerified address {} score {} ({:?}) quote slot {} feed {} consumer {}",
+                e.address,
+                e.score,
+                e.severity,
+                e.quote_slot,
+                hex(&e.feed_id),
+                e.consumer
+            );
+            for feed in &e.feeds {
+                println!(
+                    "    {}  value {}",
+                    hex(&feed.feed_id),
+                    quote::format_value(feed.value)
+                );
+            }
+        }
+        OracleEvent::CompositeScoreCached(e) => println!(
+            "  CompositeScoreCached address {} score {} ({:?}) networks {:#b} quote slot {} momentum {:?} by {} at slot {}",
+            e.address,
+            e.score,
+            e.severity,
+            e.networks,
+            e.quote_slot,
+            e.momentum,
+            e.updated_by,
+            e.updated_slot
+        ),
+        OracleEvent::CacheDivergence(e) => println!(
+            "  CacheDivergence address {} cached {} (quote slot {}, by {}) fresh {} (quote slot {}, by {}) corrected {}",
+            e.address,
+            e.cached_score,
+            e.cached_quote_slot,
+            e.cached_by,
+            e.fresh_score,
+            e.quote_slot,
+            e.reconciled_by,
+            e.corrected
+        ),
+        OracleEvent::FeedIdDerived(e) => println!(
+            "  FeedIdDerived query account {} address {} feed {}",
+            e.query_account,
+            e.address,
+            hex(&e.feed_id)
+        ),
+        OracleEvent::FeatureFlagsUpdated(e) => println!(
+            "  FeatureFlagsUpdated {:#x} -> {:#x}",
+            e.old_flags, e.new_flags
+        ),
+        OracleEvent::RiskThresholdUpdated(e) => println!(
+            "  RiskThresholdUpdated {} -> {}",
+            e.old_threshold, e.new_threshold
+        ),
+        OracleEvent::FeedRegistered(e) => match e.provenance {
+            Some(provenance) => println!(
+                "  FeedRegistered address {} feed {} schema {} prefix {}",
+                e.address,
+                hex(&e.feed_id),
+                provenance.schema,
+                hex(&provenance.encoded_prefix)
+            ),
+            None => println!(
+                "  FeedRegistered address {} feed {}",
+                e.address,
+                hex(&e.feed_id)
+            ),
+        },
+        OracleEvent::FeedUnregistered(e) => {
+            println!("  FeedUnregistered feed {}", hex(&e.feed_id))
+        }
+        OracleEvent::FeedExpirySet(e) => match e.expires_at_slot {
+            Some(slot) => println!("  FeedExpirySet feed {} slot {}", hex(&e.feed_id), slot),
+            None => println!("  FeedExpirySet feed {} lifted", hex(&e.feed_id)),
+        },
+        OracleEvent::DenylistImported(e) => println!("  DenylistImported {} addresses", e.count),
+        OracleEvent::DenylistRemoved(e) => println!("  DenylistRemoved address {}", e.address),
+        OracleEvent::DenylistRootUpdated(e) => println!(
+            "  DenylistRootUpdated root {} ({} addresses)",
+            hex(&e.root),
+            e.leaf_count
+        ),
+        OracleEvent::ComplianceOfficerUpdated(e) => println!(
+            "  ComplianceOfficerUpdated {} -> {}",
+            e.old_officer, e.new_officer
+        ),
+        OracleEvent::RiskCheckBypassed(e) => println!(
+            "  RiskCheckBypassed address {} officer {} nonce {} consumer {}",
+            e.address, e.officer, e.nonce, e.consumer
+        ),
+        OracleEvent::Heartbeat(e) => println!(
+            "  Heartbeat by {} at slot {} quote slot {} samples {}",
+            e.checked_by, e.slot, e.quote_slot, e.oracle_samples
+        ),
+        OracleEvent::DeprecatedInstructionUsed(e) => println!(
+            "  DeprecatedInstructionUsed {} (now {})",
+            e.legacy, e.replacement
+        ),
+    }
+}
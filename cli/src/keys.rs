@@ -0,0 +1,40 @@
+//! `keys`: requests and rate limits per Range API key, as totalled by the
+//! keeper.
+
+use anyhow::{Context, Result};
+use risk_oracle_config::KeeperConfig;
+use risk_oracle_keeper::store::Store;
+use serde_json::json;
+
+pub fn keys(keeper: &KeeperConfig, json: bool) -> Result<()> {
+    let store = Store::open(&keeper.state_db)
+        .with_context(|| format!("failed to open {}", keeper.state_db.display()))?;
+    let usage = store.key_usage()?;
+
+    if json {
+        let keys: Vec<_> = usage
+            .iter()
+            .map(|key| {
+                json!({
+                    "name": key.name,
+                    "requests": key.requests,
+                    "rate_limited": key.rate_limited,
+                    "last_rate_limited": key.last_rate_limited,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&keys)?);
+        return Ok(());
+    }
+    println!("{:<20} {:>9} {:>12}  last rate limited", "key", "requests", "rate limited");
+    for key in usage {
+        println!(
+            "{:<20} {:>9} {:>12}  {}",
+            key.name,
+            key.requests,
+            key.rate_limited,
+            key.last_rate_limited.map_or("-".to_string(), |at| at.to_string())
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,316 @@
+//! `risk-oracle-cli`: command line tools for the Range Risk API oracle.
+
+mod dlq;
+mod dump;
+mod explain;
+mod fixture;
+mod import;
+mod inspect;
+mod keys;
+mod migrate;
+mod pin;
+mod replay;
+mod report;
+mod watch;
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{read_keypair_file, Keypair};
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand};
+use risk_oracle_client::cluster::{self, ProgramIds};
+use risk_oracle_config::{Config, KeeperConfig, ProgramsConfig};
+use risk_oracle_shared::errors;
+
+#[derive(Parser)]
+#[command(name = "risk-oracle-cli", version, about)]
+struct Cli {
+    /// Config file, `risk-oracle.toml` by default when it exists.
+    #[arg(long, global = true, env = "RISK_ORACLE_CONFIG")]
+    config: Option<PathBuf>,
+    /// RPC endpoint, overrides `rpc.url`.
+    #[arg(long, global = true, env = "RPC_URL")]
+    url: Option<String>,
+    /// Websocket endpoint, overrides `rpc.ws_url`.
+    #[arg(long, global = true)]
+    ws_url: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Decode the oracle quote of a transaction, with the program's events
+    /// and error context.
+    InspectTx {
+        /// Transaction signature (base58).
+        signature: String,
+        /// Print the decoded quotes as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Stream the program's verifications live.
+    Watch {
+        /// Program to watch, the Anchor program by default.
+        #[arg(long)]
+        program: Option<String>,
+    },
+    /// Print a program account (or every account of a type) as JSON.
+    Dump {
+        #[command(subcommand)]
+        account: dump::Account,
+    },
+    /// Export every cache, history, denylist entry and attestation as a signed
+    /// compliance report.
+    Report {
+        /// Report file; the signature goes to `<out>.sig`.
+        #[arg(long)]
+        out: PathBuf,
+        #[arg(long, value_enum, default_value = "json")]
+        format: report::ReportFormat,
+        /// Keypair signing the report, `keeper.payer` by default.
+        #[arg(long)]
+        keypair: Option<PathBuf>,
+    },
+    /// Populate a list from a CSV file (addresses in the first column), in as
+    /// many transactions as needed; a failed run resumes when started again.
+    ImportList {
+        #[arg(long, value_enum)]
+        kind: import::ListKind,
+        #[arg(long)]
+        csv: PathBuf,
+        /// Config authority keypair, `keeper.payer` by default.
+        #[arg(long)]
+        keypair: Option<PathBuf>,
+        /// Write the denylist to the packed segments instead of one account
+        /// per address.
+        #[arg(long)]
+        segmented: bool,
+    },
+    /// Pin the composite feed of every address of a CSV file (addresses in
+    /// the first column) with the gateway, ahead of the keeper refreshing
+    /// them; a failed run resumes when started again.
+    PinFeeds {
+        /// Gateway base URL, e.g. `http://127.0.0.1:8787`.
+        #[arg(long)]
+        gateway_url: String,
+        #[arg(long)]
+        csv: PathBuf,
+        /// Networks of the feeds, as `NetworkSet` bits (1: Solana).
+        #[arg(long, default_value_t = 1)]
+        networks: u8,
+        /// Feeds pinned at once.
+        #[arg(long, default_value_t = 8)]
+        concurrency: usize,
+    },
+    /// Check that the feeds of a CSV file quote from another Switchboard
+    /// queue and verify with it, before migrating the programs to it.
+    MigrateQueue {
+        /// The new queue.
+        #[arg(long)]
+        queue: String,
+        /// Base URL of the new queue's gateway.
+        #[arg(long)]
+        gateway_url: String,
+        /// Addresses whose feeds are pinned, as given to `pin-feeds`.
+        #[arg(long)]
+        csv: PathBuf,
+        /// Networks of the feeds, as `NetworkSet` bits (1: Solana).
+        #[arg(long, default_value_t = 1)]
+        networks: u8,
+        /// Print the report as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Re-execute historical transactions against a local build of the
+    /// program and compare their outcomes with the recorded ones.
+    Replay {
+        /// Transaction signatures (base58).
+        signatures: Vec<String>,
+        /// File with more signatures, one per line.
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Program build to replay against (`.so`).
+        #[arg(long)]
+        program: PathBuf,
+        /// Program id the build is loaded at, the Anchor program by default.
+        #[arg(long)]
+        program_id: Option<String>,
+    },
+    /// Write an account image for local testing (`solana-test-validator
+    /// --account` JSON).
+    Fixture {
+        #[command(subcommand)]
+        fixture: fixture::Fixture,
+    },
+    /// Inspect or replay the keeper's dead-letter queue.
+    Dlq {
+        /// Queue of this tenant (`[tenants.<name>]`) instead of the shared
+        /// keeper's.
+        #[arg(long, global = true)]
+        tenant: Option<String>,
+        #[command(subcommand)]
+        command: dlq::DlqCommand,
+    },
+    /// Requests and rate limits per Range API key (`[gateway] api_keys`).
+    Keys {
+        /// Usage of this tenant (`[tenants.<name>]`) instead of the shared
+        /// keeper's.
+        #[arg(long)]
+        tenant: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Explain an error code (`0x1775`, `6005`) or name: the module raising
+    /// it, likely causes and the command to run next.
+    Explain {
+        error: String,
+    },
+    /// Configuration tools.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Validate the effective configuration (file, environment and flags)
+    /// and print it.
+    Check,
+}
+
+fn main() {
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("Error: {}", errors::describe(&format!("{:#}", error)));
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let mut config = Config::load(cli.config.as_deref())?;
+    if let Some(url) = cli.url {
+        config.rpc.url = url;
+    }
+    if let Some(ws_url) = cli.ws_url {
+        config.rpc.ws_url = Some(ws_url);
+    }
+
+    match cli.command {
+        Command::InspectTx { signature, json } => {
+            inspect::inspect_tx(&config.rpc.url, &signature, json)
+        }
+        Command::Watch { program } => watch::watch(&config.rpc.ws_url(), program.as_deref()),
+        Command::Dump { account } => {
+            select_program(&config)?;
+            dump::dump(&config.rpc, account)
+        }
+        Command::Report {
+            out,
+            format,
+            keypair,
+        } => {
+            select_program(&config)?;
+            let signer = read_keypair(keypair.as_deref().or(config.keeper.payer.as_deref()))?;
+            report::report(&config.rpc, signer, format, &out)
+        }
+        Command::ImportList {
+            kind,
+            csv,
+            keypair,
+            segmented,
+        } => {
+            select_program(&config)?;
+            let authority = read_keypair(keypair.as_deref().or(config.keeper.payer.as_deref()))?;
+            import::import_list(&config, &authority, kind, &csv, segmented)
+        }
+        Command::PinFeeds {
+            gateway_url,
+            csv,
+            networks,
+            concurrency,
+        } => pin::pin_feeds(&config, &gateway_url, &csv, networks, concurrency),
+        Command::MigrateQueue {
+            queue,
+            gateway_url,
+            csv,
+            networks,
+            json,
+        } => {
+            let queue = Pubkey::from_str(&queue).context("invalid queue")?;
+            migrate::migrate_queue(&config, &queue, &gateway_url, &csv, networks, json)
+        }
+        Command::Replay {
+            signatures,
+            file,
+            program,
+            program_id,
+        } => {
+            let program_id = match program_id {
+                Some(id) => Pubkey::from_str(&id).context("invalid program id")?,
+                None => risk_oracle_client::ID,
+            };
+            replay::replay(&config.rpc.url, &program, program_id, &signatures, file.as_deref())
+        }
+        Command::Fixture { fixture } => fixture::fixture(fixture),
+        Command::Dlq { tenant, command } => {
+            dlq::dlq(keeper_config(&config, tenant.as_deref())?, command)
+        }
+        Command::Keys { tenant, json } => {
+            keys::keys(keeper_config(&config, tenant.as_deref())?, json)
+        }
+        Command::Explain { error } => explain::explain(&error),
+        Command::Config {
+            command: ConfigCommand::Check,
+        } => {
+            print!("{}", config.to_toml());
+            config.validate()?;
+            eprintln!("config ok");
+            Ok(())
+        }
+    }
+}
+
+fn read_keypair(path: Option<&Path>) -> Result<Keypair> {
+    let path = path.ok_or_else(|| anyhow!("no keypair, pass --keypair or set keeper.payer"))?;
+    read_keypair_file(path).map_err(|error| anyhow!("failed to read {}: {}", path.display(), error))
+}
+
+/// `[keeper]` of `tenant` (`[tenants.<name>]`), the shared one by default.
+fn keeper_config<'a>(config: &'a Config, tenant: Option<&str>) -> Result<&'a KeeperConfig> {
+    match tenant {
+        Some(name) => config
+            .tenants
+            .get(name)
+            .map(|tenant| &tenant.keeper)
+            .ok_or_else(|| anyhow!("no tenant `{name}` in the config")),
+        None => Ok(&config.keeper),
+    }
+}
+
+/// Refuses endpoints serving a cluster this build has no deployment on, see
+/// `cluster::select`.
+fn select_program(config: &Config) -> Result<()> {
+    let ids = program_ids(&config.programs)?;
+    let (cluster, program_id) = cluster::select(&RpcClient::new(config.rpc.url.clone()), &ids)?;
+    eprintln!("{} program {}", cluster, program_id);
+    Ok(())
+}
+
+fn program_ids(config: &ProgramsConfig) -> Result<ProgramIds> {
+    let parse = |id: &Option<String>, default: Option<Pubkey>| {
+        id.as_deref()
+            .map(|id| Pubkey::from_str(id).context("invalid program id"))
+            .transpose()
+            .map(|id| id.or(default))
+    };
+    let defaults = ProgramIds::default();
+    Ok(ProgramIds {
+        mainnet: parse(&config.mainnet, defaults.mainnet)?,
+        devnet: parse(&config.devnet, defaults.devnet)?,
+        localnet: parse(&config.localnet, defaults.localnet)?,
+    })
+}
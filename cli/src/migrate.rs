@@ -0,0 +1,192 @@
+//! `migrate-queue`: checks, before moving the programs to another Switchboard
+//! queue, that the watchlist's feeds quote from it.
+//!
+//! The programs accept quotes from the queue of their build profile only
+//! (`profiles::ACTIVE.queue`), so a queue migration is a profile change and a
+//! program upgrade. Upgrading before the new queue's oracles serve every
+//! pinned feed would fail every verification at once. This command requests a
+//! quote of the composite feed of every address of a CSV file (the one given
+//! to `pin-feeds`) from the new queue's gateway, and checks each as the
+//! verifier would with the new queue: layout, feed id, oracle samples and
+//! signers. Run it against the devnet build first.
+//!
+//! It fails unless every feed verifies, and ends with the steps left to the
+//! operator.
+
+use std::fs;
+use std::path::Path;
+
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_sdk::hash::hash;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, bail, Context, Result};
+use risk_oracle_client::quote;
+use risk_oracle_config::Config;
+use risk_oracle_keeper::gateway::GatewayQuoteSource;
+use risk_oracle_keeper::refresh::QuoteSource;
+use risk_oracle_shared::diagnostics::hex;
+use risk_oracle_shared::feed::{self, NetworkSet};
+use risk_oracle_shared::profiles;
+use risk_oracle_shared::verifier_inputs::{self, QUEUE_ACCOUNT_LEN};
+use serde_json::json;
+
+use crate::import::parse_csv;
+
+/// Outcome of one address.
+struct FeedCheck {
+    address: Pubkey,
+    feed_id: [u8; 32],
+    /// Why the quote wouldn't verify with the new queue, `None` when it would.
+    failure: Option<String>,
+}
+
+pub fn migrate_queue(
+    config: &Config,
+    queue: &Pubkey,
+    gateway_url: &str,
+    csv: &Path,
+    networks: u8,
+    json: bool,
+) -> Result<()> {
+    let networks = NetworkSet::from_bits(networks)
+        .ok_or_else(|| anyhow!("invalid network set {:#b}", networks))?;
+    let current = Pubkey::new_from_array(profiles::ACTIVE.queue);
+    if *queue == current {
+        bail!(
+            "{} is already the queue of the {} profile",
+            queue,
+            profiles::ACTIVE.name
+        );
+    }
+
+    let rpc = RpcClient::new(config.rpc.url.clone());
+    let account = rpc
+        .get_account(queue)
+        .with_context(|| format!("failed to fetch the queue {}", queue))?;
+    let switchboard = Pubkey::new_from_array(profiles::ACTIVE.switchboard_program);
+    if account.owner != switchboard {
+        bail!(
+            "{} is owned by {}, not the Switchboard program {}",
+            queue,
+            account.owner,
+            switchboard
+        );
+    }
+    if account.data.len() != QUEUE_ACCOUNT_LEN {
+        bail!(
+            "{} is {} bytes, the verifier reads {}-byte queues",
+            queue,
+            account.data.len(),
+            QUEUE_ACCOUNT_LEN
+        );
+    }
+
+    let contents =
+        fs::read_to_string(csv).with_context(|| format!("failed to read {}", csv.display()))?;
+    let addresses = parse_csv(&contents)?;
+    let gateway = GatewayQuoteSource::new(gateway_url, config.rpc.url.clone());
+    let checks: Vec<_> = addresses
+        .iter()
+        .map(|address| {
+            let check = check_feed(&gateway, &account.data, address, networks);
+            if !json {
+                match &check.failure {
+                    None => eprintln!("{} {} ok", check.address, hex(&check.feed_id)),
+                    Some(failure) => {
+                        eprintln!("{} {} {}", check.address, hex(&check.feed_id), failure)
+                    }
+                }
+            }
+            check
+        })
+        .collect();
+    let failed = checks
+        .iter()
+        .filter(|check| check.failure.is_some())
+        .count();
+
+    if json {
+        let feeds: Vec<_> = checks
+            .iter()
+            .map(|check| {
+                json!({
+                    "address": check.address.to_string(),
+                    "feed_id": hex(&check.feed_id),
+                    "ok": check.failure.is_none(),
+                    "failure": check.failure,
+                })
+            })
+            .collect();
+        let report = json!({
+            "profile": profiles::ACTIVE.name,
+            "current_queue": current.to_string(),
+            "queue": queue.to_string(),
+            "networks": networks.bits(),
+            "feeds": feeds,
+            "failed": failed,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    }
+
+    if failed > 0 {
+        bail!(
+            "{} of {} feeds don't verify with the queue {}",
+            failed,
+            checks.len(),
+            queue
+        );
+    }
+    eprintln!("all {} feeds verify with the queue {}", checks.len(), queue);
+    eprintln!("next steps:");
+    eprintln!(
+        "  1. set `queue` of the {} profile (shared/src/profiles.rs) to {}",
+        profiles::ACTIVE.name,
+        queue
+    );
+    eprintln!("  2. rebuild the programs and upgrade them");
+    eprintln!("  3. point the keepers at the new queue's gateway");
+    Ok(())
+}
+
+/// Quotes the composite feed of `address` from `gateway` and checks it
+/// against `queue_data`.
+fn check_feed(
+    gateway: &GatewayQuoteSource,
+    queue_data: &[u8],
+    address: &Pubkey,
+    networks: NetworkSet,
+) -> FeedCheck {
+    let feed = feed::composite_risk_score_feed(&address.to_bytes(), networks);
+    let feed_id = hash(&feed::encode(&feed)).to_bytes();
+    let failure = gateway
+        .quote_instruction(&feed, address)
+        .map_err(|err| format!("no quote: {}", err))
+        .and_then(|instruction| {
+            verifier_inputs::check_quote_signers(&instruction.data, 0, queue_data)
+                .map_err(|err| err.reason().to_string())?;
+            let quote = quote::decode(&instruction.data).map_err(|err| err.to_string())?;
+            let quoted = quote
+                .feeds
+                .iter()
+                .find(|quoted| quoted.feed_id == feed_id)
+                .ok_or_else(|| "feed_id_mismatch".to_string())?;
+            // As the programs require it: the feed's, at least the profile's.
+            let required = quoted
+                .min_oracle_samples
+                .max(profiles::ACTIVE.min_oracle_samples);
+            if quote.signatures.len() < usize::from(required) {
+                return Err(format!(
+                    "{} signatures, the programs require {}",
+                    quote.signatures.len(),
+                    required
+                ));
+            }
+            Ok(())
+        })
+        .err();
+    FeedCheck {
+        address: *address,
+        feed_id,
+        failure,
+    }
+}
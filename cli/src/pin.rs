@@ -0,0 +1,78 @@
+//! `pin-feeds`: derives the composite feed of every address of a CSV file and
+//! pins it with the gateway, so the keeper's first quotes for a large
+//! watchlist don't wait on the gateway resolving each feed.
+//!
+//! Addresses are pinned `concurrency` at a time. Progress is checkpointed to
+//! `<csv>.pinned` after every round, so a failed run resumes where it stopped
+//! when started again with the same file. Pinning a feed again is a no-op on
+//! the gateway, so re-sending a round is harmless either way.
+
+use std::fs;
+use std::path::Path;
+use std::thread;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, bail, Context, Result};
+use risk_oracle_config::Config;
+use risk_oracle_keeper::gateway::GatewayQuoteSource;
+use risk_oracle_shared::diagnostics::hex;
+use risk_oracle_shared::feed::{self, NetworkSet};
+
+use crate::import::{checksum, parse_csv, progress_path, read_progress};
+
+pub fn pin_feeds(
+    config: &Config,
+    gateway_url: &str,
+    csv: &Path,
+    networks: u8,
+    concurrency: usize,
+) -> Result<()> {
+    let networks = NetworkSet::from_bits(networks)
+        .ok_or_else(|| anyhow!("invalid network set {:#b}", networks))?;
+    if concurrency == 0 {
+        bail!("--concurrency must be at least 1");
+    }
+
+    let contents =
+        fs::read_to_string(csv).with_context(|| format!("failed to read {}", csv.display()))?;
+    let addresses = parse_csv(&contents)?;
+    // The networks are part of the feed, so they are part of the list too.
+    let checksum = format!("{}-{:x}", checksum(&addresses), networks.bits());
+
+    let progress_path = progress_path(csv, "pinned");
+    let mut done = read_progress(&progress_path, &checksum)?;
+    if done > 0 {
+        eprintln!("resuming after {} of {} addresses", done, addresses.len());
+    }
+
+    let gateway = GatewayQuoteSource::new(gateway_url, config.rpc.url.clone());
+    for round in addresses[done..].chunks(concurrency) {
+        let results: Vec<_> = thread::scope(|scope| {
+            let pins: Vec<_> = round
+                .iter()
+                .map(|address| scope.spawn(|| pin(&gateway, address, networks)))
+                .collect();
+            pins.into_iter()
+                .map(|pin| pin.join().unwrap_or_else(|_| Err(anyhow!("pinning thread panicked"))))
+                .collect()
+        });
+        for (address, result) in round.iter().zip(results) {
+            let feed_id = result.with_context(|| format!("failed to pin the feed of {}", address))?;
+            eprintln!("{} pinned as {}", address, hex(&feed_id));
+        }
+
+        done += round.len();
+        fs::write(&progress_path, format!("{} {}\n", done, checksum))
+            .with_context(|| format!("failed to write {}", progress_path.display()))?;
+        eprintln!("{}/{} pinned", done, addresses.len());
+    }
+
+    fs::remove_file(&progress_path).ok();
+    eprintln!("pinned {} feeds from {}", addresses.len(), csv.display());
+    Ok(())
+}
+
+fn pin(gateway: &GatewayQuoteSource, address: &Pubkey, networks: NetworkSet) -> Result<[u8; 32]> {
+    let feed = feed::composite_risk_score_feed(&address.to_bytes(), networks);
+    Ok(gateway.pin_feed(&feed)?)
+}
@@ -0,0 +1,230 @@
+//! `replay`: re-executes historical transactions against a local build of the
+//! program in LiteSVM and compares the outcome with the recorded one, to audit
+//! past verifications or to check that a refactor did not change behavior.
+//!
+//! RPC nodes only serve current account state, so the state at the
+//! transaction's slot is reconstructed: Clock from the slot and block time,
+//! SlotHashes from the quote's slot and slot hash, lamports from the recorded
+//! pre-balances, and account data as it is now. Accounts the program wrote
+//! since (caches, history, integrator counters) can make a replay differ from
+//! its original outcome; replay in slot order against a fresh deployment, or
+//! read a mismatch together with `inspect-tx`.
+
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::RpcTransactionConfig;
+use anchor_client::solana_sdk::account::Account;
+use anchor_client::solana_sdk::clock::{Clock, DEFAULT_SLOTS_PER_EPOCH};
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::hash::Hash;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::Signature;
+use anchor_client::solana_sdk::slot_hashes::SlotHashes;
+use anchor_client::solana_sdk::transaction::TransactionError;
+use anchor_client::solana_sdk::{ed25519_program, native_loader, sysvar};
+use anyhow::{anyhow, bail, Context, Result};
+use litesvm::LiteSVM;
+use risk_oracle_client::{errors, quote};
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionEncoding;
+
+/// `getMultipleAccounts` limit.
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+pub fn replay(
+    url: &str,
+    program: &Path,
+    program_id: Pubkey,
+    signatures: &[String],
+    file: Option<&Path>,
+) -> Result<()> {
+    let mut signatures = signatures.to_vec();
+    if let Some(file) = file {
+        let text = fs::read_to_string(file)
+            .with_context(|| format!("failed to read {}", file.display()))?;
+        signatures.extend(
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(String::from),
+        );
+    }
+    if signatures.is_empty() {
+        bail!("no signatures, pass them as arguments or with --file");
+    }
+    let elf =
+        fs::read(program).with_context(|| format!("failed to read {}", program.display()))?;
+
+    let client = RpcClient::new(url.to_string());
+    let mut mismatches = 0;
+    for signature in &signatures {
+        match replay_one(&client, &elf, program_id, signature) {
+            Ok(true) => {}
+            Ok(false) => mismatches += 1,
+            Err(error) => {
+                println!("{} ERROR {:#}", signature, error);
+                mismatches += 1;
+            }
+        }
+    }
+
+    println!(
+        "\n{} replayed, {} matched",
+        signatures.len(),
+        signatures.len() - mismatches
+    );
+    if mismatches > 0 {
+        bail!("{} replays did not reproduce the recorded outcome", mismatches);
+    }
+    Ok(())
+}
+
+/// Replays one transaction, returning whether its outcome matched.
+fn replay_one(
+    client: &RpcClient,
+    elf: &[u8],
+    program_id: Pubkey,
+    signature: &str,
+) -> Result<bool> {
+    let signature = Signature::from_str(signature).context("invalid signature")?;
+    let confirmed = client
+        .get_transaction_with_config(
+            &signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .context("failed to fetch the transaction")?;
+    let transaction = confirmed
+        .transaction
+        .transaction
+        .decode()
+        .context("failed to decode the transaction")?;
+    let meta = confirmed
+        .transaction
+        .meta
+        .context("the transaction has no status")?;
+
+    let message = &transaction.message;
+    let static_keys = message.static_account_keys();
+    let quote = message
+        .instructions()
+        .iter()
+        .find(|ix| static_keys.get(ix.program_id_index as usize) == Some(&ed25519_program::ID))
+        .map(|ix| quote::decode(&ix.data))
+        .transpose()?
+        .context("no Ed25519 quote instruction in this transaction")?;
+    let quote_slot = quote.slot.context("the quote carries no slot")?;
+
+    // Same order as the pre-balances: static keys, then the writable and the
+    // readonly addresses loaded from lookup tables.
+    let mut keys = static_keys.to_vec();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        for address in loaded.writable.iter().chain(&loaded.readonly) {
+            keys.push(Pubkey::from_str(address).context("invalid loaded address")?);
+        }
+    }
+    let tables: Vec<Pubkey> = message
+        .address_table_lookups()
+        .into_iter()
+        .flatten()
+        .map(|lookup| lookup.account_key)
+        .collect();
+
+    let mut svm = LiteSVM::new().with_sigverify(false).with_blockhash_check(false);
+    svm.add_program(program_id, elf);
+
+    let mut clock: Clock = svm.get_sysvar();
+    clock.slot = confirmed.slot;
+    clock.epoch = confirmed.slot / DEFAULT_SLOTS_PER_EPOCH;
+    if let Some(block_time) = confirmed.block_time {
+        clock.unix_timestamp = block_time;
+    }
+    svm.set_sysvar(&clock);
+    svm.set_sysvar(&SlotHashes::new(&[(
+        quote_slot,
+        Hash::new_from_array(quote.slot_hash),
+    )]));
+
+    let fetch: Vec<Pubkey> = keys
+        .iter()
+        .chain(&tables)
+        .copied()
+        .filter(|key| *key != program_id)
+        .collect();
+    let mut accounts = Vec::with_capacity(fetch.len());
+    for chunk in fetch.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+        accounts.extend(chunk.iter().copied().zip(client.get_multiple_accounts(chunk)?));
+    }
+
+    let mut warnings = Vec::new();
+    let mut executables = Vec::new();
+    for (key, account) in accounts {
+        let index = keys.iter().position(|k| *k == key);
+        let pre_balance = index.and_then(|index| meta.pre_balances.get(index)).copied();
+        let Some(mut account) = account else {
+            if pre_balance.is_some_and(|lamports| lamports > 0) {
+                warnings.push(format!("{} was closed since, replayed without it", key));
+            }
+            continue;
+        };
+        // Builtins and sysvars are provided by LiteSVM.
+        if account.owner == native_loader::ID || account.owner == sysvar::ID {
+            continue;
+        }
+        if let Some(lamports) = pre_balance {
+            account.lamports = lamports;
+        }
+        // Upgradeable programs are loaded from their program data, which has
+        // to be in place first.
+        if account.executable {
+            executables.push((key, account));
+        } else {
+            set_account(&mut svm, key, account)?;
+        }
+    }
+    for (key, account) in executables {
+        set_account(&mut svm, key, account)?;
+    }
+
+    let (replayed, logs) = match svm.send_transaction(transaction) {
+        Ok(result) => (None, result.logs),
+        Err(failed) => (Some(failed.err), failed.meta.logs),
+    };
+    let matched = replayed == meta.err;
+
+    println!(
+        "{} slot {} {} recorded {}, replayed {}",
+        signature,
+        confirmed.slot,
+        if matched { "MATCH" } else { "MISMATCH" },
+        outcome(meta.err.as_ref()),
+        outcome(replayed.as_ref())
+    );
+    for warning in &warnings {
+        println!("  warning: {}", warning);
+    }
+    if !matched {
+        if let Some(error) = errors::from_logs(&logs) {
+            println!("  replayed error {} ({}): {}", error.name, error.number, error.message);
+        }
+    }
+    Ok(matched)
+}
+
+fn set_account(svm: &mut LiteSVM, key: Pubkey, account: Account) -> Result<()> {
+    svm.set_account(key, account)
+        .map_err(|error| anyhow!("failed to load {}: {:?}", key, error))
+}
+
+fn outcome(error: Option<&TransactionError>) -> String {
+    match error {
+        None => "ok".to_string(),
+        Some(error) => format!("failed ({})", error),
+    }
+}
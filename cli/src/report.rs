@@ -0,0 +1,195 @@
+//! `report`: exports the program's screening state as a signed compliance
+//! report, evidence of ongoing screening for auditors and regulators.
+//!
+//! The report holds every score cache, score history, denylist entry and
+//! attestation (verification receipts, threshold proofs, confidential score
+//! commitments), the config and the denylist root, stamped with the slot it
+//! was taken at and the deployed program's version. The exact bytes written
+//! are signed with an Ed25519 keypair into a detached `<out>.sig` file.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_client::solana_sdk::bpf_loader_upgradeable;
+use anchor_client::solana_sdk::signature::{Keypair, Signer};
+use anchor_client::{Client, Cluster};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use risk_oracle_client::{
+    profiles, ConfidentialScore, Config, DenylistEntry, DenylistRoot, Page, RiskOracleClient,
+    ScoreCache, ScoreHistory, ThresholdProof, VerificationReceipt, ID,
+};
+use risk_oracle_config::RpcConfig;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::dump::keyed;
+
+/// Size of the `ProgramData` header before the executable: enum tag (u32),
+/// last deployment slot (u64) and optional upgrade authority (1 + 32 bytes).
+const PROGRAM_DATA_METADATA_LEN: usize = 45;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    Json,
+    /// One `kind,address,json` row per account.
+    Csv,
+}
+
+#[derive(Serialize)]
+struct Metadata {
+    /// Unix seconds.
+    generated_at: u64,
+    /// Slot the accounts were read at (or shortly after).
+    slot: u64,
+    profile: &'static str,
+    cli_version: &'static str,
+    program: ProgramVersion,
+}
+
+/// Identifies the deployed program binary.
+#[derive(Serialize)]
+struct ProgramVersion {
+    program_id: String,
+    /// Unset for programs not owned by the upgradeable loader.
+    last_deployed_slot: Option<u64>,
+    /// SHA-256 of the executable, hex.
+    executable_sha256: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Report {
+    metadata: Metadata,
+    config: Config,
+    denylist_root: Option<DenylistRoot>,
+    caches: Vec<(String, ScoreCache)>,
+    histories: Vec<(String, ScoreHistory)>,
+    denylist: Vec<(String, DenylistEntry)>,
+    receipts: Vec<(String, VerificationReceipt)>,
+    threshold_proofs: Vec<(String, ThresholdProof)>,
+    confidential_scores: Vec<(String, ConfidentialScore)>,
+}
+
+/// Detached signature of a report file.
+#[derive(Serialize)]
+struct ReportSignature {
+    signer: String,
+    /// Ed25519 signature of the report bytes, base58.
+    signature: String,
+    /// SHA-256 of the report bytes, hex.
+    sha256: String,
+}
+
+pub fn report(rpc: &RpcConfig, signer: Keypair, format: ReportFormat, out: &Path) -> Result<()> {
+    let signer = Rc::new(signer);
+
+    let cluster = Cluster::Custom(rpc.url.clone(), rpc.ws_url());
+    let client = Client::new(cluster, signer.clone());
+    let oracle = RiskOracleClient::new(&client)?;
+
+    let report = Report {
+        metadata: Metadata {
+            generated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            slot: oracle.program().rpc().get_slot()?,
+            profile: profiles::ACTIVE.name,
+            cli_version: env!("CARGO_PKG_VERSION"),
+            program: program_version(&oracle)?,
+        },
+        config: oracle.get_config()?,
+        denylist_root: oracle.get_denylist_root().ok(),
+        caches: keyed(oracle.list_watchlist(Page::ALL)?),
+        histories: keyed(oracle.list_histories(Page::ALL)?),
+        denylist: keyed(oracle.list_denylist(Page::ALL)?),
+        receipts: keyed(oracle.list_receipts(Page::ALL)?),
+        threshold_proofs: keyed(oracle.list_threshold_proofs(Page::ALL)?),
+        confidential_scores: keyed(oracle.list_confidential_scores(Page::ALL)?),
+    };
+
+    let bytes = match format {
+        ReportFormat::Json => serde_json::to_vec_pretty(&report)?,
+        ReportFormat::Csv => csv(&report)?.into_bytes(),
+    };
+    fs::write(out, &bytes).with_context(|| format!("failed to write {}", out.display()))?;
+
+    let signature = ReportSignature {
+        signer: signer.pubkey().to_string(),
+        signature: signer.sign_message(&bytes).to_string(),
+        sha256: hex(&Sha256::digest(&bytes)),
+    };
+    let signature_path = signature_path(out);
+    fs::write(&signature_path, serde_json::to_vec_pretty(&signature)?)
+        .with_context(|| format!("failed to write {}", signature_path.display()))?;
+
+    eprintln!(
+        "wrote {} ({} caches, {} denylist entries) signed by {} in {}",
+        out.display(),
+        report.caches.len(),
+        report.denylist.len(),
+        signature.signer,
+        signature_path.display()
+    );
+    Ok(())
+}
+
+fn program_version(oracle: &RiskOracleClient<Rc<Keypair>>) -> Result<ProgramVersion> {
+    let program_data = bpf_loader_upgradeable::get_program_data_address(&ID);
+    let rpc = oracle.program().rpc();
+    let account = rpc
+        .get_account_with_commitment(&program_data, rpc.commitment())?
+        .value;
+    let metadata = account
+        .as_ref()
+        .filter(|account| account.data.len() >= PROGRAM_DATA_METADATA_LEN);
+
+    Ok(ProgramVersion {
+        program_id: ID.to_string(),
+        last_deployed_slot: metadata
+            .map(|account| u64::from_le_bytes(account.data[4..12].try_into().unwrap())),
+        executable_sha256: metadata
+            .map(|account| hex(&Sha256::digest(&account.data[PROGRAM_DATA_METADATA_LEN..]))),
+    })
+}
+
+fn csv(report: &Report) -> Result<String> {
+    let mut out = String::from("kind,address,json\n");
+    row(&mut out, "metadata", "", &report.metadata)?;
+    row(&mut out, "config", "", &report.config)?;
+    if let Some(root) = &report.denylist_root {
+        row(&mut out, "denylist_root", "", root)?;
+    }
+    rows(&mut out, "cache", &report.caches)?;
+    rows(&mut out, "history", &report.histories)?;
+    rows(&mut out, "denylist", &report.denylist)?;
+    rows(&mut out, "receipt", &report.receipts)?;
+    rows(&mut out, "threshold_proof", &report.threshold_proofs)?;
+    rows(&mut out, "confidential_score", &report.confidential_scores)?;
+    Ok(out)
+}
+
+fn rows<T: Serialize>(out: &mut String, kind: &str, accounts: &[(String, T)]) -> Result<()> {
+    for (address, account) in accounts {
+        row(out, kind, address, account)?;
+    }
+    Ok(())
+}
+
+/// The JSON column is quoted, its quotes doubled.
+fn row(out: &mut String, kind: &str, address: &str, value: &impl Serialize) -> Result<()> {
+    let json = serde_json::to_string(value)?;
+    out.push_str(&format!("{},{},\"{}\"\n", kind, address, json.replace('"', "\"\"")));
+    Ok(())
+}
+
+fn signature_path(out: &Path) -> PathBuf {
+    let mut path = out.as_os_str().to_owned();
+    path.push(".sig");
+    PathBuf::from(path)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
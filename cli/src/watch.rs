@@ -0,0 +1,67 @@
+//! `watch`: streams the program's transactions over websocket and prints each
+//! verification (address, score, outcome) as it lands.
+
+use std::str::FromStr;
+
+use anchor_client::solana_client::pubsub_client::PubsubClient;
+use anchor_client::solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{Context, Result};
+use risk_oracle_client::events::{self, OracleEvent};
+use risk_oracle_client::errors;
+use risk_oracle_shared::errors as codes;
+
+use crate::inspect::print_event;
+
+pub fn watch(ws_url: &str, program: Option<&str>) -> Result<()> {
+    let program = match program {
+        Some(program) => Pubkey::from_str(program).context("invalid program id")?,
+        None => risk_oracle_client::ID,
+    };
+
+    let (_subscription, receiver) = PubsubClient::logs_subscribe(
+        ws_url,
+        RpcTransactionLogsFilter::Mentions(vec![program.to_string()]),
+        RpcTransactionLogsConfig {
+            commitment: Some(CommitmentConfig::confirmed()),
+        },
+    )
+    .context("failed to subscribe to the program logs")?;
+    eprintln!("Watching {} on {} (Ctrl-C to stop)", program, ws_url);
+
+    for response in receiver {
+        let logs = response.value;
+        let signature = &logs.signature;
+
+        if let Some(err) = &logs.err {
+            match errors::from_logs(&logs.logs) {
+                Some(error) => println!("{} FAILED {}: {}", signature, error.name, error.message),
+                None => println!("{} FAILED {}", signature, codes::describe(&err.to_string())),
+            }
+            continue;
+        }
+
+        let events = events::from_logs(&logs.logs);
+        if events.is_empty() {
+            println!("{} ok", signature);
+        }
+        for event in &events {
+            match event {
+                OracleEvent::RiskScoreVerified(e) => println!(
+                    "{} VERIFIED address {} score {} ({:?}) quote slot {}",
+                    signature, e.address, e.score, e.severity, e.quote_slot
+                ),
+                OracleEvent::CompositeScoreCached(e) => println!(
+                    "{} VERIFIED address {} score {} ({:?}) networks {:#b} momentum {:?} by {}",
+                    signature, e.address, e.score, e.severity, e.networks, e.momentum, e.updated_by
+                ),
+                other => {
+                    println!("{}", signature);
+                    print_event(other);
+                }
+            }
+        }
+    }
+    Ok(())
+}
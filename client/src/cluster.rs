@@ -0,0 +1,131 @@
+//! Cluster detection and the per-cluster program id registry.
+//!
+//! The cluster an RPC endpoint serves is identified by its genesis hash, so a
+//! client pointed at the wrong endpoint notices before sending anything:
+//! [`select`] refuses a cluster the registry has no deployment on, a cluster
+//! the compiled profile wasn't built for (a devnet build against mainnet,
+//! or the reverse), and a registered program id other than [`ID`], the one
+//! the instruction builders target.
+
+use std::fmt;
+
+use anchor_client::solana_client::client_error::ClientError;
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_sdk::hash::Hash;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+use crate::{profiles, ID};
+
+const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+const DEVNET_GENESIS_HASH: &str = "EtWTRABZaYq6iMfeYKouRuZWdNs7nLQbRHwXBuajRjMp";
+const TESTNET_GENESIS_HASH: &str = "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClusterKind {
+    Mainnet,
+    Devnet,
+    Testnet,
+    /// Any other genesis, e.g. a local test validator.
+    Localnet,
+}
+
+impl ClusterKind {
+    pub fn from_genesis_hash(hash: &Hash) -> Self {
+        match hash.to_string().as_str() {
+            MAINNET_GENESIS_HASH => Self::Mainnet,
+            DEVNET_GENESIS_HASH => Self::Devnet,
+            TESTNET_GENESIS_HASH => Self::Testnet,
+            _ => Self::Localnet,
+        }
+    }
+
+    /// Whether the active profile's queue lives on this cluster: the mainnet
+    /// profile only on mainnet, the devnet-based ones anywhere else.
+    pub fn matches_profile(self) -> bool {
+        (self == Self::Mainnet) == (profiles::ACTIVE.name == profiles::MAINNET.name)
+    }
+}
+
+impl fmt::Display for ClusterKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Mainnet => "mainnet",
+            Self::Devnet => "devnet",
+            Self::Testnet => "testnet",
+            Self::Localnet => "localnet",
+        })
+    }
+}
+
+/// Program id deployed on each cluster, `None` where there is no deployment.
+#[derive(Clone, Debug)]
+pub struct ProgramIds {
+    pub mainnet: Option<Pubkey>,
+    pub devnet: Option<Pubkey>,
+    pub localnet: Option<Pubkey>,
+}
+
+impl Default for ProgramIds {
+    /// [`ID`] everywhere but testnet.
+    fn default() -> Self {
+        Self {
+            mainnet: Some(ID),
+            devnet: Some(ID),
+            localnet: Some(ID),
+        }
+    }
+}
+
+impl ProgramIds {
+    pub fn get(&self, cluster: ClusterKind) -> Option<Pubkey> {
+        match cluster {
+            ClusterKind::Mainnet => self.mainnet,
+            ClusterKind::Devnet => self.devnet,
+            ClusterKind::Testnet => None,
+            ClusterKind::Localnet => self.localnet,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClusterError {
+    #[error("failed to fetch the genesis hash: {0}")]
+    Rpc(#[from] ClientError),
+    #[error("no program id registered for {0}")]
+    NotDeployed(ClusterKind),
+    #[error("the RPC endpoint serves {cluster}, this build targets the {profile} profile")]
+    ProfileMismatch {
+        cluster: ClusterKind,
+        profile: &'static str,
+    },
+    #[error("the program on {cluster} is {registered}, this build targets {}", ID)]
+    ProgramMismatch {
+        cluster: ClusterKind,
+        registered: Pubkey,
+    },
+}
+
+/// Cluster served by `rpc`.
+pub fn detect(rpc: &RpcClient) -> Result<ClusterKind, ClientError> {
+    Ok(ClusterKind::from_genesis_hash(&rpc.get_genesis_hash()?))
+}
+
+/// Detects the cluster served by `rpc` and returns it with its program id,
+/// once checked against this build (see the module docs).
+pub fn select(rpc: &RpcClient, ids: &ProgramIds) -> Result<(ClusterKind, Pubkey), ClusterError> {
+    let cluster = detect(rpc)?;
+    let program_id = ids.get(cluster).ok_or(ClusterError::NotDeployed(cluster))?;
+    if !cluster.matches_profile() {
+        return Err(ClusterError::ProfileMismatch {
+            cluster,
+            profile: profiles::ACTIVE.name,
+        });
+    }
+    if program_id != ID {
+        return Err(ClusterError::ProgramMismatch {
+            cluster,
+            registered: program_id,
+        });
+    }
+    Ok((cluster, program_id))
+}
@@ -0,0 +1,82 @@
+//! Large denylists: the merkle tree committed with `set_denylist_root` and
+//! the proofs `check_denylist` expects, and the batches of the packed
+//! segments `update_denylist_segment` writes.
+
+use std::collections::BTreeMap;
+
+use anchor_client::solana_sdk::hash::hashv;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_oracle_example::state::MAX_DENYLIST_BATCH;
+use anchor_oracle_example::{DenylistProof, LeafProof};
+use risk_oracle_shared::denylist_segment;
+use risk_oracle_shared::merkle::Tree;
+
+/// The denylist as a sorted merkle tree.
+pub struct DenylistTree {
+    tree: Tree,
+}
+
+impl DenylistTree {
+    pub fn new(addresses: impl IntoIterator<Item = Pubkey>) -> Self {
+        let keys = addresses.into_iter().map(|address| address.to_bytes()).collect();
+        Self {
+            tree: Tree::new(|data| hashv(data).to_bytes(), keys),
+        }
+    }
+
+    /// Arguments of `set_denylist_root`.
+    pub fn root(&self) -> [u8; 32] {
+        self.tree.root()
+    }
+
+    pub fn leaf_count(&self) -> u32 {
+        self.tree.leaf_count()
+    }
+
+    /// Membership proof when `address` is listed, the neighbors around it
+    /// otherwise.
+    pub fn proof(&self, address: &Pubkey) -> DenylistProof {
+        match self.tree.search(&address.to_bytes()) {
+            Ok(index) => DenylistProof::Member(self.leaf(index)),
+            Err(upper) => DenylistProof::NonMember {
+                lower: upper.checked_sub(1).map(|index| self.leaf(index)),
+                upper: (upper < self.leaf_count()).then(|| self.leaf(upper)),
+            },
+        }
+    }
+
+    fn leaf(&self, index: u32) -> LeafProof {
+        LeafProof {
+            address: Pubkey::new_from_array(self.tree.keys()[index as usize]),
+            index,
+            siblings: self.tree.proof(index),
+        }
+    }
+}
+
+/// Packed denylist segment `address` is listed in.
+pub fn segment_of(address: &Pubkey) -> u16 {
+    denylist_segment::segment_of(|data| hashv(data).to_bytes(), &address.to_bytes())
+}
+
+/// `addresses` grouped by segment and split into batches of at most
+/// `MAX_DENYLIST_BATCH`, in segment order: the `segment` and `add` arguments
+/// of one `update_denylist_segment` each.
+pub fn segment_batches(addresses: impl IntoIterator<Item = Pubkey>) -> Vec<(u16, Vec<Pubkey>)> {
+    let mut segments: BTreeMap<u16, Vec<Pubkey>> = BTreeMap::new();
+    for address in addresses {
+        segments
+            .entry(segment_of(&address))
+            .or_default()
+            .push(address);
+    }
+    segments
+        .into_iter()
+        .flat_map(|(segment, addresses)| {
+            addresses
+                .chunks(MAX_DENYLIST_BATCH)
+                .map(|batch| (segment, batch.to_vec()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
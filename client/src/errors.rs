@@ -0,0 +1,57 @@
+//! Program error mapping.
+//!
+//! Anchor logs every failure as
+//! `Program log: AnchorError ... Error Code: <Name>. Error Number: <n>. Error Message: <msg>.`
+//! so decoding the log keeps the mapping in sync with `ErrorCode` without a
+//! hand-maintained table. Failures logged without it (the Pinocchio program,
+//! `custom program error: 0x<code>` only) are named through
+//! [`risk_oracle_shared::errors`].
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use risk_oracle_shared::errors as codes;
+
+/// A program error taken from a transaction's logs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramError {
+    /// `ErrorCode` variant (or Anchor framework error) name.
+    pub name: String,
+    pub number: u32,
+    pub message: String,
+}
+
+impl ProgramError {
+    /// Whether the error is one of the program's own `ErrorCode`s rather than
+    /// an Anchor framework error.
+    pub fn is_custom(&self) -> bool {
+        self.number >= ERROR_CODE_OFFSET
+    }
+}
+
+/// Returns the first Anchor error found in `logs`, else the first known
+/// custom program error.
+pub fn from_logs<S: AsRef<str>>(logs: &[S]) -> Option<ProgramError> {
+    logs.iter()
+        .find_map(|line| parse(line.as_ref()))
+        .or_else(|| logs.iter().find_map(|line| custom(line.as_ref())))
+}
+
+fn parse(line: &str) -> Option<ProgramError> {
+    let (_, rest) = line.split_once("AnchorError")?;
+    let (_, rest) = rest.split_once("Error Code: ")?;
+    let (name, rest) = rest.split_once(". Error Number: ")?;
+    let (number, message) = rest.split_once(". Error Message: ")?;
+    Some(ProgramError {
+        name: name.to_string(),
+        number: number.parse().ok()?,
+        message: message.trim_end_matches('.').to_string(),
+    })
+}
+
+fn custom(line: &str) -> Option<ProgramError> {
+    let error = codes::lookup(codes::custom_code(line)?)?;
+    Some(ProgramError {
+        name: error.name.to_string(),
+        number: error.code,
+        message: error.message.to_string(),
+    })
+}
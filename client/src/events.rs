@@ -0,0 +1,72 @@
+//! Event decoding.
+//!
+//! `emit!` logs events as `Program data: <base64>`, the payload being the
+//! event discriminator followed by its Borsh encoding.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::Engine;
+
+pub use anchor_oracle_example::events::{
+    CacheDivergence, ComplianceOfficerUpdated, CompositeScoreCached, DenylistImported,
+    DenylistRemoved, DenylistRootUpdated, DeprecatedInstructionUsed, FeatureFlagsUpdated,
+    FeedExpirySet, FeedIdDerived, FeedRegistered, FeedUnregistered, Heartbeat, RiskCheckBypassed,
+    RiskScoreVerified, RiskThresholdUpdated,
+};
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Any event emitted by the program.
+pub enum OracleEvent {
+    RiskScoreVerified(RiskScoreVerified),
+    CompositeScoreCached(CompositeScoreCached),
+    CacheDivergence(CacheDivergence),
+    FeedIdDerived(FeedIdDerived),
+    FeatureFlagsUpdated(FeatureFlagsUpdated),
+    RiskThresholdUpdated(RiskThresholdUpdated),
+    FeedRegistered(FeedRegistered),
+    FeedUnregistered(FeedUnregistered),
+    FeedExpirySet(FeedExpirySet),
+    DenylistImported(DenylistImported),
+    DenylistRemoved(DenylistRemoved),
+    DenylistRootUpdated(DenylistRootUpdated),
+    ComplianceOfficerUpdated(ComplianceOfficerUpdated),
+    RiskCheckBypassed(RiskCheckBypassed),
+    Heartbeat(Heartbeat),
+    DeprecatedInstructionUsed(DeprecatedInstructionUsed),
+}
+
+/// Decodes every program event found in a transaction's logs, in order.
+/// Lines that aren't events of this program are skipped.
+pub fn from_logs<S: AsRef<str>>(logs: &[S]) -> Vec<OracleEvent> {
+    logs.iter()
+        .filter_map(|line| line.as_ref().strip_prefix(PROGRAM_DATA))
+        .filter_map(|data| base64::engine::general_purpose::STANDARD.decode(data).ok())
+        .filter_map(|data| decode(&data))
+        .collect()
+}
+
+/// Decodes a single event payload (discriminator included).
+pub fn decode(data: &[u8]) -> Option<OracleEvent> {
+    fn try_decode<E: Discriminator + AnchorDeserialize>(data: &[u8]) -> Option<E> {
+        let mut payload = data.strip_prefix(E::DISCRIMINATOR)?;
+        E::deserialize(&mut payload).ok()
+    }
+
+    try_decode(data)
+        .map(OracleEvent::RiskScoreVerified)
+        .or_else(|| try_decode(data).map(OracleEvent::CompositeScoreCached))
+        .or_else(|| try_decode(data).map(OracleEvent::CacheDivergence))
+        .or_else(|| try_decode(data).map(OracleEvent::FeedIdDerived))
+        .or_else(|| try_decode(data).map(OracleEvent::FeatureFlagsUpdated))
+        .or_else(|| try_decode(data).map(OracleEvent::RiskThresholdUpdated))
+        .or_else(|| try_decode(data).map(OracleEvent::FeedRegistered))
+        .or_else(|| try_decode(data).map(OracleEvent::FeedUnregistered))
+        .or_else(|| try_decode(data).map(OracleEvent::FeedExpirySet))
+        .or_else(|| try_decode(data).map(OracleEvent::DenylistImported))
+        .or_else(|| try_decode(data).map(OracleEvent::DenylistRemoved))
+        .or_else(|| try_decode(data).map(OracleEvent::DenylistRootUpdated))
+        .or_else(|| try_decode(data).map(OracleEvent::ComplianceOfficerUpdated))
+        .or_else(|| try_decode(data).map(OracleEvent::RiskCheckBypassed))
+        .or_else(|| try_decode(data).map(OracleEvent::Heartbeat))
+        .or_else(|| try_decode(data).map(OracleEvent::DeprecatedInstructionUsed))
+}
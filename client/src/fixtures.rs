@@ -0,0 +1,154 @@
+//! Account images for local testing.
+//!
+//! The verifier reads the oracles' Ed25519 signing keys from the profile's
+//! Switchboard queue, so a localnet or LiteSVM test needs that account. Instead
+//! of cloning it from mainnet, [`queue_account`] builds one holding signing
+//! keys the test controls, with the owner and discriminator the programs
+//! check. Load it at [`crate::queue`]:
+//!
+//! ```ignore
+//! let oracle = fixtures::oracle_keypair(0);
+//! svm.set_account(risk_oracle_client::queue(), fixtures::queue_account(&[oracle.pubkey()])?)?;
+//! ```
+//!
+//! The image is deterministic: only the oracle keys and their count are set,
+//! the authority, every timestamp and every counter are zero.
+//!
+//! [`quote_instruction`] then produces quotes those oracles signed, in the
+//! layout [`crate::quote::decode`] reads, for any feed value.
+
+use anchor_client::solana_sdk::account::Account;
+use anchor_client::solana_sdk::ed25519_program;
+use anchor_client::solana_sdk::instruction::Instruction;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::rent::Rent;
+use anchor_client::solana_sdk::signature::{keypair_from_seed, Keypair, Signer};
+use risk_oracle_shared::profiles;
+
+use crate::quote::{
+    QuoteFeed, FEED_LEN, HEADER_LEN, OFFSETS_LEN, QUOTE_IX, QUOTE_TAIL, QUOTE_VERSION,
+};
+
+/// Size of the queue account, discriminator included. The verifier rejects
+/// any other length.
+pub const QUEUE_ACCOUNT_LEN: usize = 6280;
+/// Anchor discriminator of Switchboard's `QueueAccountData`.
+pub const QUEUE_ACCOUNT_DISCRIMINATOR: [u8; 8] = [217, 194, 55, 127, 184, 83, 138, 1];
+/// Ed25519 signing key slots of a queue.
+pub const MAX_QUEUE_ORACLES: usize = 30;
+
+// Offsets in the account data of the `QueueAccountData` fields that are set
+// (`#[repr(C)]`, after the discriminator).
+const ORACLE_KEYS_OFFSET: usize = 8 + 1056;
+const ED25519_SIGNING_KEYS_OFFSET: usize = 8 + 4192;
+const ORACLE_KEYS_LEN_OFFSET: usize = 8 + 5196;
+
+/// Prefix of the seeds of [`oracle_keypair`].
+const ORACLE_SEED_PREFIX: &[u8] = b"risk-oracle fixture oracle";
+
+#[derive(Debug, thiserror::Error)]
+pub enum FixtureError {
+    #[error("a queue holds at most {MAX_QUEUE_ORACLES} oracles, got {0}")]
+    TooManyOracles(usize),
+}
+
+/// Queue account image whose oracles sign with `oracle_signing_keys`, in
+/// order: the quote's oracle index `i` maps to `oracle_signing_keys[i]`. The
+/// same keys are listed as the queue's oracle accounts. Owned by the active
+/// profile's Switchboard program and rent exempt.
+pub fn queue_account(oracle_signing_keys: &[Pubkey]) -> Result<Account, FixtureError> {
+    if oracle_signing_keys.len() > MAX_QUEUE_ORACLES {
+        return Err(FixtureError::TooManyOracles(oracle_signing_keys.len()));
+    }
+
+    let mut data = vec![0u8; QUEUE_ACCOUNT_LEN];
+    data[..8].copy_from_slice(&QUEUE_ACCOUNT_DISCRIMINATOR);
+    for (i, key) in oracle_signing_keys.iter().enumerate() {
+        let at = ORACLE_KEYS_OFFSET + i * 32;
+        data[at..at + 32].copy_from_slice(key.as_ref());
+        let at = ED25519_SIGNING_KEYS_OFFSET + i * 32;
+        data[at..at + 32].copy_from_slice(key.as_ref());
+    }
+    data[ORACLE_KEYS_LEN_OFFSET..ORACLE_KEYS_LEN_OFFSET + 4]
+        .copy_from_slice(&(oracle_signing_keys.len() as u32).to_le_bytes());
+
+    Ok(Account {
+        lamports: Rent::default().minimum_balance(QUEUE_ACCOUNT_LEN),
+        data,
+        owner: Pubkey::new_from_array(profiles::ACTIVE.switchboard_program),
+        executable: false,
+        rent_epoch: 0,
+    })
+}
+
+/// Deterministic oracle signing keypair `index`, for quotes signed in tests.
+/// Never use these keys outside a test: the seeds are public.
+pub fn oracle_keypair(index: u8) -> Keypair {
+    let mut seed = [0u8; 32];
+    seed[..ORACLE_SEED_PREFIX.len()].copy_from_slice(ORACLE_SEED_PREFIX);
+    seed[31] = index;
+    keypair_from_seed(&seed).expect("32 byte seed")
+}
+
+/// Data of a quote instruction: `oracles` each sign the quote message for
+/// `slot_hash` and `feeds`, and the suffix the verifier reads ends it: the
+/// oracles' queue indexes (`oracles[i]` signs as the queue's key `i`), `slot`,
+/// the version and the tail discriminator. Every offset points into the
+/// instruction itself, at index 0 where the programs read it.
+pub fn quote_data(
+    oracles: &[Keypair],
+    slot_hash: [u8; 32],
+    slot: u64,
+    feeds: &[QuoteFeed],
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + feeds.len() * FEED_LEN);
+    message.extend_from_slice(&slot_hash);
+    for feed in feeds {
+        message.extend_from_slice(&feed.feed_id);
+        message.extend_from_slice(&feed.value.to_le_bytes());
+        message.push(feed.min_oracle_samples);
+    }
+
+    // Header and offsets, the message, then each oracle's key and signature.
+    let message_offset = HEADER_LEN + oracles.len() * OFFSETS_LEN;
+    let mut data = vec![oracles.len() as u8, 0];
+    for i in 0..oracles.len() {
+        let public_key_offset = message_offset + message.len() + i * 96;
+        let signature_offset = public_key_offset + 32;
+        for field in [
+            signature_offset as u16,
+            QUOTE_IX,
+            public_key_offset as u16,
+            QUOTE_IX,
+            message_offset as u16,
+            message.len() as u16,
+            QUOTE_IX,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+    data.extend_from_slice(&message);
+    for oracle in oracles {
+        data.extend_from_slice(oracle.pubkey().as_ref());
+        data.extend_from_slice(oracle.sign_message(&message).as_ref());
+    }
+    data.extend(0..oracles.len() as u8);
+    data.extend_from_slice(&slot.to_le_bytes());
+    data.push(QUOTE_VERSION);
+    data.extend_from_slice(&QUOTE_TAIL);
+    data
+}
+
+/// Ed25519 program instruction carrying [`quote_data`], to place at index 0.
+pub fn quote_instruction(
+    oracles: &[Keypair],
+    slot_hash: [u8; 32],
+    slot: u64,
+    feeds: &[QuoteFeed],
+) -> Instruction {
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: Vec::new(),
+        data: quote_data(oracles, slot_hash, slot, feeds),
+    }
+}
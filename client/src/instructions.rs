@@ -0,0 +1,1127 @@
+//! Instruction builders.
+//!
+//! Account metas and instruction data are the program's generated `accounts`
+//! and `instruction` types; these functions only fill in the PDAs, sysvars and
+//! the profile queue. Remember the verify instructions expect the Switchboard
+//! quote instruction at index 0 of the transaction.
+
+use anchor_client::solana_sdk::instruction::{AccountMeta, Instruction};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::{ed25519_program, system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_oracle_example::state::{FeedProvenance, PolicyVerdict};
+use anchor_oracle_example::{
+    accounts, instruction, BypassVoucher, ConfigParams, DenylistProof, RiskThreshold, ID,
+};
+use risk_oracle_shared::proto::OracleFeed;
+use risk_oracle_shared::{feed, voucher};
+
+use crate::{denylist, pda, queue};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// `authority` must be the program's upgrade authority.
+pub fn initialize_config(authority: Pubkey, params: ConfigParams) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            config: pda::config().0,
+            authority,
+            program: ID,
+            program_data: pda::program_data().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig { params },
+    )
+}
+
+pub fn set_feature_flags(authority: Pubkey, flags: u64) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            authority,
+        },
+        instruction::SetFeatureFlags { flags },
+    )
+}
+
+pub fn set_risk_threshold(authority: Pubkey, threshold: RiskThreshold) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            authority,
+        },
+        instruction::SetRiskThreshold { threshold },
+    )
+}
+
+/// Phases `threshold` in linearly between `start_slot` and `end_slot`.
+pub fn schedule_threshold_ramp(
+    authority: Pubkey,
+    threshold: RiskThreshold,
+    start_slot: u64,
+    end_slot: u64,
+) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            authority,
+        },
+        instruction::ScheduleThresholdRamp {
+            threshold,
+            start_slot,
+            end_slot,
+        },
+    )
+}
+
+/// Clamps gating down to `threshold` until `expiry_slot`, at most
+/// `MAX_EMERGENCY_SLOTS` ahead.
+pub fn set_emergency_threshold(
+    authority: Pubkey,
+    threshold: RiskThreshold,
+    expiry_slot: u64,
+) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            authority,
+        },
+        instruction::SetEmergencyThreshold {
+            threshold,
+            expiry_slot,
+        },
+    )
+}
+
+pub fn clear_emergency_threshold(authority: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            authority,
+        },
+        instruction::ClearEmergencyThreshold {},
+    )
+}
+
+/// Grows a config created before the emergency threshold to the current
+/// layout; the authority pays the extra rent.
+pub fn extend_config(authority: Pubkey) -> Instruction {
+    build(
+        accounts::ExtendConfig {
+            config: pda::config().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::ExtendConfig {},
+    )
+}
+
+pub fn initialize_registry(authority: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeRegistry {
+            config: pda::config().0,
+            registry: pda::registry().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeRegistry {},
+    )
+}
+
+/// `provenance` records the template of `feed_id`, see [`FeedProvenance::new`].
+/// The authority pays for the registry's growth.
+pub fn register_feed(
+    authority: Pubkey,
+    address: Pubkey,
+    feed_id: [u8; 32],
+    provenance: Option<FeedProvenance>,
+) -> Instruction {
+    build(
+        accounts::RegisterFeed {
+            config: pda::config().0,
+            registry: pda::registry().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterFeed {
+            address,
+            feed_id,
+            provenance,
+        },
+    )
+}
+
+/// Pins `feed` for `address` from its encoding, its `${NAME}` placeholders
+/// checked against the variables of template `schema`.
+pub fn register_feed_template(
+    authority: Pubkey,
+    address: Pubkey,
+    schema: u8,
+    feed: &OracleFeed,
+) -> Instruction {
+    build(
+        accounts::RegisterFeedTemplate {
+            config: pda::config().0,
+            registry: pda::registry().0,
+            template_variables: pda::template_variables(schema).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterFeedTemplate {
+            address,
+            schema,
+            encoded: feed::encode(feed),
+        },
+    )
+}
+
+/// Sets the variables of template `schema`, by default
+/// `feed_variables::template_variables(schema)`.
+pub fn set_template_variables(authority: Pubkey, schema: u8, names: Vec<String>) -> Instruction {
+    build(
+        accounts::SetTemplateVariables {
+            config: pda::config().0,
+            template_variables: pda::template_variables(schema).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetTemplateVariables { schema, names },
+    )
+}
+
+pub fn unregister_feed(authority: Pubkey, feed_id: [u8; 32]) -> Instruction {
+    build(
+        accounts::UpdateRegistry {
+            config: pda::config().0,
+            registry: pda::registry().0,
+            authority,
+        },
+        instruction::UnregisterFeed { feed_id },
+    )
+}
+
+/// Rejects verifications against `feed_id` from `expires_at_slot` on, or
+/// lifts its expiry with `None`.
+pub fn set_feed_expiry(
+    authority: Pubkey,
+    feed_id: [u8; 32],
+    expires_at_slot: Option<u64>,
+) -> Instruction {
+    build(
+        accounts::UpdateRegistry {
+            config: pda::config().0,
+            registry: pda::registry().0,
+            authority,
+        },
+        instruction::SetFeedExpiry {
+            feed_id,
+            expires_at_slot,
+        },
+    )
+}
+
+/// Emergency bulk denylist import (at most `MAX_DENYLIST_BATCH` addresses),
+/// the entry PDAs passed as remaining accounts.
+pub fn import_denylist(authority: Pubkey, addresses: Vec<Pubkey>) -> Instruction {
+    let entries: Vec<AccountMeta> = addresses
+        .iter()
+        .map(|address| AccountMeta::new(pda::denylist_entry(address).0, false))
+        .collect();
+    let mut ix = build(
+        accounts::ImportDenylist {
+            config: pda::config().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::ImportDenylist { addresses },
+    );
+    ix.accounts.extend(entries);
+    ix
+}
+
+pub fn remove_from_denylist(authority: Pubkey, address: Pubkey) -> Instruction {
+    build(
+        accounts::RemoveFromDenylist {
+            config: pda::config().0,
+            denylist_entry: pda::denylist_entry(&address).0,
+            authority,
+        },
+        instruction::RemoveFromDenylist { address },
+    )
+}
+
+/// Lists `add` and unlists `remove` in packed denylist segment `segment`
+/// (together at most `MAX_DENYLIST_BATCH` addresses, all of the segment), see
+/// [`crate::denylist::segment_batches`].
+pub fn update_denylist_segment(
+    authority: Pubkey,
+    segment: u16,
+    add: Vec<Pubkey>,
+    remove: Vec<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::UpdateDenylistSegment {
+            config: pda::config().0,
+            denylist_segment: pda::denylist_segment(segment).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateDenylistSegment {
+            segment,
+            add,
+            remove,
+        },
+    )
+}
+
+/// Replaces the programs no instruction of a verified transaction may target
+/// (program denylist checks).
+pub fn set_program_denylist(authority: Pubkey, programs: Vec<Pubkey>) -> Instruction {
+    build(
+        accounts::SetProgramDenylist {
+            config: pda::config().0,
+            program_denylist: pda::program_denylist().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetProgramDenylist { programs },
+    )
+}
+
+/// Adds `program` to the program denylist, creating the account on first use.
+pub fn flag_program(authority: Pubkey, program: Pubkey) -> Instruction {
+    build(
+        accounts::SetProgramDenylist {
+            config: pda::config().0,
+            program_denylist: pda::program_denylist().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::FlagProgram { program },
+    )
+}
+
+pub fn unflag_program(authority: Pubkey, program: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateProgramDenylist {
+            config: pda::config().0,
+            program_denylist: pda::program_denylist().0,
+            authority,
+        },
+        instruction::UnflagProgram { program },
+    )
+}
+
+/// `screen_programs`: fails when an instruction of the transaction targets a
+/// program of the program denylist, whatever the feature flags.
+pub fn screen_programs() -> Instruction {
+    build(
+        accounts::ScreenPrograms {
+            program_denylist: pda::program_denylist().0,
+            instructions: sysvar::instructions::ID,
+        },
+        instruction::ScreenPrograms {},
+    )
+}
+
+/// Commits a denylist built with [`DenylistTree`](crate::denylist::DenylistTree).
+pub fn set_denylist_root(authority: Pubkey, root: [u8; 32], leaf_count: u32) -> Instruction {
+    build(
+        accounts::SetDenylistRoot {
+            config: pda::config().0,
+            denylist_root: pda::denylist_root().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetDenylistRoot { root, leaf_count },
+    )
+}
+
+/// `proof` is for the screened wallet of `query_account` (the owner for
+/// token accounts). Returns whether it is listed as return data.
+pub fn check_denylist(query_account: Pubkey, proof: DenylistProof) -> Instruction {
+    build(
+        accounts::CheckDenylist {
+            denylist_root: pda::denylist_root().0,
+            query_account,
+        },
+        instruction::CheckDenylist { proof },
+    )
+}
+
+/// Optional accounts of the verify instructions. Which ones are needed depends
+/// on the config's feature flags and on the verification mode.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VerifyOptions {
+    /// Required by anti-replay (record rent) and fee collection.
+    pub payer: Option<Pubkey>,
+    /// Required by fee collection, must be the configured treasury.
+    pub treasury: Option<Pubkey>,
+    /// Pass the replay record PDA (anti-replay).
+    pub anti_replay: bool,
+    /// Pass the registry PDA (pinned mode).
+    pub registry: bool,
+    /// Pass the feed id record PDA (hybrid mode).
+    pub feed_id_record: bool,
+    /// Record the result for [`consume_verified_score`] (requires `payer`).
+    pub quote_marker: bool,
+    /// Write a verification receipt for CPI consumers (requires `payer`).
+    pub receipt: bool,
+    /// Append the score to the query account's history (requires `payer`).
+    pub history: bool,
+    /// Screened wallet whose denylist entry to pass (denylist checks). For
+    /// token accounts, this is the owner.
+    pub denylist_address: Option<Pubkey>,
+    /// Consuming program whose integrator account to meter (integrator
+    /// accounting): this program when called directly, the caller through CPI.
+    pub integrator: Option<Pubkey>,
+    /// Pass the program denylist PDA (program denylist checks).
+    pub program_denylist: bool,
+    /// Consuming program whose risk ceiling to pass (risk ceilings), as for
+    /// `integrator`.
+    pub risk_ceiling: Option<Pubkey>,
+    /// Screened wallet whose address policy to pass (address policies), as
+    /// for `denylist_address`.
+    pub address_policy: Option<Pubkey>,
+    /// Screened wallet whose packed denylist segment to pass (segmented
+    /// denylist), as for `denylist_address`.
+    pub denylist_segment: Option<Pubkey>,
+}
+
+fn verify_accounts(query_account: Pubkey, opts: &VerifyOptions) -> accounts::VerifyRiskScoreFeed {
+    let needs_system_program = opts.payer.is_some();
+    accounts::VerifyRiskScoreFeed {
+        config: pda::config().0,
+        queue: queue(),
+        slothashes: sysvar::slot_hashes::ID,
+        instructions: sysvar::instructions::ID,
+        query_account,
+        payer: opts.payer,
+        replay_record: opts
+            .anti_replay
+            .then(|| pda::replay_record(&query_account).0),
+        treasury: opts.treasury,
+        system_program: needs_system_program.then_some(system_program::ID),
+        registry: opts.registry.then(|| pda::registry().0),
+        feed_id_record: opts
+            .feed_id_record
+            .then(|| pda::feed_id_record(&query_account).0),
+        quote_marker: opts
+            .quote_marker
+            .then(|| pda::quote_marker(&query_account).0),
+        receipt: opts.receipt.then(|| pda::receipt(&query_account).0),
+        history: opts.history.then(|| pda::score_history(&query_account).0),
+        denylist_entry: opts
+            .denylist_address
+            .map(|address| pda::denylist_entry(&address).0),
+        integrator: opts
+            .integrator
+            .map(|consumer| pda::integrator(&consumer).0),
+        program_denylist: opts.program_denylist.then(|| pda::program_denylist().0),
+        risk_ceiling: opts
+            .risk_ceiling
+            .map(|consumer| pda::risk_ceiling(&consumer).0),
+        address_policy: opts
+            .address_policy
+            .map(|address| pda::address_policy(&address).0),
+        denylist_segment: opts
+            .denylist_segment
+            .map(|address| pda::denylist_segment(denylist::segment_of(&address)).0),
+    }
+}
+
+/// Original account layout, with the clock account and the config after the
+/// query account. `registry`,
+/// `feed_id_record`, `quote_marker`, `receipt`, `history`, `denylist_address`,
+/// `integrator`, `program_denylist`, `risk_ceiling`, `address_policy` and
+/// `denylist_segment` are ignored.
+pub fn verify_risk_score_feed(query_account: Pubkey, opts: &VerifyOptions) -> Instruction {
+    let compact = verify_accounts(query_account, opts);
+    build(
+        accounts::VerifyRiskScoreFeedLegacy {
+            config: compact.config,
+            queue: compact.queue,
+            clock: sysvar::clock::ID,
+            slothashes: compact.slothashes,
+            instructions: compact.instructions,
+            query_account,
+            payer: compact.payer,
+            replay_record: compact.replay_record,
+            treasury: compact.treasury,
+            system_program: compact.system_program,
+        },
+        instruction::VerifyRiskScoreFeed {},
+    )
+}
+
+pub fn verify_risk_score_feed_compact(query_account: Pubkey, opts: &VerifyOptions) -> Instruction {
+    build(
+        verify_accounts(query_account, opts),
+        instruction::VerifyRiskScoreFeedCompact {},
+    )
+}
+
+/// Compact layout, failing once the slot is past `not_after_slot`.
+pub fn verify_risk_score_feed_with_deadline(
+    query_account: Pubkey,
+    not_after_slot: u64,
+    opts: &VerifyOptions,
+) -> Instruction {
+    build(
+        verify_accounts(query_account, opts),
+        instruction::VerifyRiskScoreFeedWithDeadline { not_after_slot },
+    )
+}
+
+/// ATA mode: `wallet` is screened, its associated token account for `mint` is
+/// derived on-chain.
+pub fn verify_risk_score_feed_for_ata(
+    wallet: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    opts: &VerifyOptions,
+) -> Instruction {
+    build(
+        verify_accounts(wallet, opts),
+        instruction::VerifyRiskScoreFeedForAta {
+            mint,
+            token_program,
+        },
+    )
+}
+
+/// Pinned mode, the registry account is always passed.
+pub fn verify_risk_score_feed_pinned(
+    query_account: Pubkey,
+    feed_id: [u8; 32],
+    opts: &VerifyOptions,
+) -> Instruction {
+    let opts = VerifyOptions {
+        registry: true,
+        ..*opts
+    };
+    build(
+        verify_accounts(query_account, &opts),
+        instruction::VerifyRiskScoreFeedPinned { feed_id },
+    )
+}
+
+/// Hybrid mode, the feed id record is always passed.
+pub fn verify_risk_score_feed_cached(query_account: Pubkey, opts: &VerifyOptions) -> Instruction {
+    let opts = VerifyOptions {
+        feed_id_record: true,
+        ..*opts
+    };
+    build(
+        verify_accounts(query_account, &opts),
+        instruction::VerifyRiskScoreFeedCached {},
+    )
+}
+
+pub fn derive_feed_id(query_account: Pubkey, payer: Pubkey) -> Instruction {
+    build(
+        accounts::DeriveFeedId {
+            config: pda::config().0,
+            query_account,
+            feed_id_record: pda::feed_id_record(&query_account).0,
+            payer,
+            system_program: system_program::ID,
+        },
+        instruction::DeriveFeedId {},
+    )
+}
+
+/// `health_check`: expects a quote of `feed::test_feed` at index 0.
+pub fn health_check(payer: Pubkey) -> Instruction {
+    build(
+        accounts::HealthCheck {
+            config: pda::config().0,
+            queue: queue(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            payer,
+        },
+        instruction::HealthCheck {},
+    )
+}
+
+/// `networks` are `NetworkSet` bits. With `history`, the score is also
+/// appended to the query account's history; with `freshness_sla`, the refresh
+/// is recorded against its SLA (which must exist).
+pub fn verify_composite_risk_score(
+    query_account: Pubkey,
+    payer: Pubkey,
+    networks: u8,
+    history: bool,
+    freshness_sla: bool,
+) -> Instruction {
+    build(
+        accounts::VerifyCompositeRiskScore {
+            config: pda::config().0,
+            queue: queue(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            query_account,
+            cache: pda::score_cache(&query_account).0,
+            payer,
+            system_program: system_program::ID,
+            history: history.then(|| pda::score_history(&query_account).0),
+            freshness_sla: freshness_sla.then(|| pda::freshness_sla(&query_account).0),
+        },
+        instruction::VerifyCompositeRiskScore { networks },
+    )
+}
+
+/// `refresh_score_caches`: refreshes the caches of `query_accounts` whose
+/// feed the quote carries, verifying the quote once.
+pub fn refresh_score_caches(payer: Pubkey, query_accounts: &[Pubkey]) -> Instruction {
+    let caches: Vec<AccountMeta> = query_accounts
+        .iter()
+        .map(|query_account| AccountMeta::new(pda::score_cache(query_account).0, false))
+        .collect();
+    let mut ix = build(
+        accounts::RefreshScoreCaches {
+            queue: queue(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            payer,
+        },
+        instruction::RefreshScoreCaches {},
+    );
+    ix.accounts.extend(caches);
+    ix
+}
+
+/// `reconcile`: cross-checks the cache of `query_account` against the quote,
+/// correcting it with `correct` when it is more than `tolerance` away.
+pub fn reconcile(
+    payer: Pubkey,
+    query_account: &Pubkey,
+    tolerance: u8,
+    correct: bool,
+) -> Instruction {
+    build(
+        accounts::Reconcile {
+            queue: queue(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            cache: pda::score_cache(query_account).0,
+            payer,
+        },
+        instruction::Reconcile { tolerance, correct },
+    )
+}
+
+/// `verify_risk_score_batch`: checks the risk score feeds of `query_accounts`
+/// against one quote; with `partial`, returns which matched and passed
+/// instead of failing on the first that doesn't. `risk_ceiling` is the
+/// consuming program whose risk ceiling to pass (risk ceilings), as for
+/// [`VerifyOptions::risk_ceiling`].
+pub fn verify_risk_score_batch(
+    query_accounts: &[Pubkey],
+    partial: bool,
+    risk_ceiling: Option<Pubkey>,
+) -> Instruction {
+    let mut ix = build(
+        accounts::VerifyRiskScoreBatch {
+            config: pda::config().0,
+            queue: queue(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            risk_ceiling: risk_ceiling.map(|consumer| pda::risk_ceiling(&consumer).0),
+        },
+        instruction::VerifyRiskScoreBatch { partial },
+    );
+    ix.accounts.extend(
+        query_accounts
+            .iter()
+            .map(|query_account| AccountMeta::new_readonly(*query_account, false)),
+    );
+    ix
+}
+
+/// Optional accounts of [`check_or_verify_score`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CheckOptions {
+    /// Append a refreshed score to the query account's history.
+    pub history: bool,
+    /// Pass the freshness SLA PDA, which a refresh is recorded against.
+    pub freshness_sla: bool,
+    /// Pass the decay model, required once the program enables `SCORE_DECAY`.
+    pub score_decay: bool,
+    /// Consuming program whose risk ceiling to pass (risk ceilings), as for
+    /// [`VerifyOptions::risk_ceiling`].
+    pub risk_ceiling: Option<Pubkey>,
+}
+
+/// `check_or_verify_score`: the quote instruction is only needed when the
+/// cache is older than `max_age_slots`.
+pub fn check_or_verify_score(
+    query_account: Pubkey,
+    payer: Pubkey,
+    networks: u8,
+    max_age_slots: u64,
+    opts: &CheckOptions,
+) -> Instruction {
+    build(
+        accounts::CheckOrVerifyScore {
+            config: pda::config().0,
+            queue: queue(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            query_account,
+            cache: pda::score_cache(&query_account).0,
+            payer,
+            system_program: system_program::ID,
+            history: opts
+                .history
+                .then(|| pda::score_history(&query_account).0),
+            freshness_sla: opts
+                .freshness_sla
+                .then(|| pda::freshness_sla(&query_account).0),
+            score_decay: opts.score_decay.then(|| pda::score_decay().0),
+            risk_ceiling: opts
+                .risk_ceiling
+                .map(|consumer| pda::risk_ceiling(&consumer).0),
+        },
+        instruction::CheckOrVerifyScore {
+            networks,
+            max_age_slots,
+        },
+    )
+}
+
+pub fn set_freshness_sla(
+    authority: Pubkey,
+    query_account: Pubkey,
+    max_age_slots: u64,
+) -> Instruction {
+    build(
+        accounts::SetFreshnessSla {
+            config: pda::config().0,
+            query_account,
+            freshness_sla: pda::freshness_sla(&query_account).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetFreshnessSla { max_age_slots },
+    )
+}
+
+/// Returns whether the cache of `query_account` is within its freshness SLA
+/// as return data. Writes nothing, meant to be simulated.
+pub fn is_within_sla(query_account: Pubkey) -> Instruction {
+    build(
+        accounts::IsWithinSla {
+            query_account,
+            freshness_sla: pda::freshness_sla(&query_account).0,
+        },
+        instruction::IsWithinSla {},
+    )
+}
+
+pub fn set_access_list(authority: Pubkey, consumers: Vec<Pubkey>) -> Instruction {
+    build(
+        accounts::SetAccessList {
+            config: pda::config().0,
+            access_list: pda::access_list().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetAccessList { consumers },
+    )
+}
+
+/// `networks` are `NetworkSet` bits. Only the commitment to the score under
+/// `salt` is stored; keep the salt to hand to access-listed consumers.
+/// `risk_ceiling` is as for [`verify_risk_score_batch`].
+pub fn verify_confidential_risk_score(
+    query_account: Pubkey,
+    payer: Pubkey,
+    networks: u8,
+    salt: [u8; 32],
+    risk_ceiling: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::VerifyConfidentialRiskScore {
+            config: pda::config().0,
+            queue: queue(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            query_account,
+            confidential_score: pda::confidential_score(&query_account).0,
+            payer,
+            system_program: system_program::ID,
+            risk_ceiling: risk_ceiling.map(|consumer| pda::risk_ceiling(&consumer).0),
+        },
+        instruction::VerifyConfidentialRiskScore { networks, salt },
+    )
+}
+
+/// Returns `score` as return data when it opens the confidential score
+/// commitment of `query_account` with `salt`. The calling program must be in
+/// the access list.
+pub fn reveal_score(query_account: Pubkey, score: u8, salt: [u8; 32]) -> Instruction {
+    build(
+        accounts::RevealScore {
+            access_list: pda::access_list().0,
+            instructions: sysvar::instructions::ID,
+            query_account,
+            confidential_score: pda::confidential_score(&query_account).0,
+        },
+        instruction::RevealScore { score, salt },
+    )
+}
+
+/// Records whether the score is at most the configured threshold, with a
+/// commitment to the score under `salt`, and returns the outcome as return
+/// data. The score itself is never stored.
+pub fn verify_threshold_proof(query_account: Pubkey, payer: Pubkey, salt: [u8; 32]) -> Instruction {
+    build(
+        accounts::VerifyThresholdProof {
+            config: pda::config().0,
+            queue: queue(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            query_account,
+            threshold_proof: pda::threshold_proof(&query_account).0,
+            payer,
+            system_program: system_program::ID,
+        },
+        instruction::VerifyThresholdProof { salt },
+    )
+}
+
+/// View returning the cached score of `query_account` with its derived
+/// values, see [`crate::RiskOracleClient::view_cached_score`]. With
+/// `freshness_sla`, the view also checks the SLA (which must exist).
+pub fn get_cached_score(query_account: Pubkey, freshness_sla: bool) -> Instruction {
+    build(
+        accounts::GetCachedScore {
+            config: pda::config().0,
+            query_account,
+            cache: pda::score_cache(&query_account).0,
+            freshness_sla: freshness_sla.then(|| pda::freshness_sla(&query_account).0),
+        },
+        instruction::GetCachedScore {},
+    )
+}
+
+/// View returning the config, see [`crate::RiskOracleClient::view_config`].
+pub fn get_config() -> Instruction {
+    build(
+        accounts::GetConfig {
+            config: pda::config().0,
+        },
+        instruction::GetConfig {},
+    )
+}
+
+/// View returning the config as self-describing entries, see
+/// [`crate::RiskOracleClient::view_description`].
+pub fn describe() -> Instruction {
+    build(
+        accounts::Describe {
+            config: pda::config().0,
+        },
+        instruction::Describe {},
+    )
+}
+
+/// Reads the score verified earlier in the same transaction by a verify
+/// instruction built with `quote_marker`.
+pub fn consume_verified_score(query_account: Pubkey) -> Instruction {
+    build(
+        accounts::ConsumeVerifiedScore {
+            instructions: sysvar::instructions::ID,
+            query_account,
+            quote_marker: pda::quote_marker(&query_account).0,
+        },
+        instruction::ConsumeVerifiedScore {},
+    )
+}
+
+/// Closes the expired receipt of `query_account`, refunding `rent_payer`.
+pub fn close_receipt(query_account: Pubkey, rent_payer: Pubkey) -> Instruction {
+    build(
+        accounts::CloseReceipt {
+            query_account,
+            receipt: pda::receipt(&query_account).0,
+            rent_payer,
+        },
+        instruction::CloseReceipt {},
+    )
+}
+
+pub fn create_quote_account(owner: Pubkey, id: u64) -> Instruction {
+    build(
+        accounts::CreateQuoteAccount {
+            quote_account: pda::quote_account(&owner, id).0,
+            owner,
+            system_program: system_program::ID,
+        },
+        instruction::CreateQuoteAccount { id },
+    )
+}
+
+/// Posts the quote of the transaction to the owner's quote account `id`.
+pub fn write_quote_account(owner: Pubkey, id: u64) -> Instruction {
+    build(
+        accounts::WriteQuoteAccount {
+            queue: queue(),
+            slothashes: sysvar::slot_hashes::ID,
+            instructions: sysvar::instructions::ID,
+            quote_account: pda::quote_account(&owner, id).0,
+            owner,
+        },
+        instruction::WriteQuoteAccount {},
+    )
+}
+
+/// Verifies against the quote posted to `quote_account`: no quote
+/// instruction needed. `risk_ceiling` is as for [`verify_risk_score_batch`],
+/// the Instructions sysvar being passed along with it.
+pub fn verify_risk_score_account(
+    quote_account: Pubkey,
+    query_account: Pubkey,
+    max_age_slots: u64,
+    risk_ceiling: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::VerifyRiskScoreAccount {
+            config: pda::config().0,
+            quote_account,
+            query_account,
+            instructions: risk_ceiling.map(|_| sysvar::instructions::ID),
+            risk_ceiling: risk_ceiling.map(|consumer| pda::risk_ceiling(&consumer).0),
+        },
+        instruction::VerifyRiskScoreAccount { max_age_slots },
+    )
+}
+
+pub fn close_quote_account(owner: Pubkey, id: u64) -> Instruction {
+    build(
+        accounts::CloseQuoteAccount {
+            quote_account: pda::quote_account(&owner, id).0,
+            owner,
+        },
+        instruction::CloseQuoteAccount {},
+    )
+}
+
+/// Gates on the time-weighted average score over the last `window_slots`.
+/// `risk_ceiling` is as for [`verify_risk_score_account`].
+pub fn check_risk_score_twa(
+    query_account: Pubkey,
+    window_slots: u64,
+    risk_ceiling: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::CheckRiskScoreTwa {
+            config: pda::config().0,
+            query_account,
+            history: pda::score_history(&query_account).0,
+            instructions: risk_ceiling.map(|_| sysvar::instructions::ID),
+            risk_ceiling: risk_ceiling.map(|consumer| pda::risk_ceiling(&consumer).0),
+        },
+        instruction::CheckRiskScoreTwa { window_slots },
+    )
+}
+
+/// Registers `consumer` for integrator accounting, or changes its price.
+pub fn register_integrator(
+    authority: Pubkey,
+    consumer: Pubkey,
+    price_lamports: u64,
+) -> Instruction {
+    build(
+        accounts::RegisterIntegrator {
+            config: pda::config().0,
+            integrator: pda::integrator(&consumer).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterIntegrator {
+            consumer,
+            price_lamports,
+        },
+    )
+}
+
+pub fn top_up_integrator(consumer: Pubkey, payer: Pubkey, lamports: u64) -> Instruction {
+    build(
+        accounts::TopUpIntegrator {
+            integrator: pda::integrator(&consumer).0,
+            payer,
+            system_program: system_program::ID,
+        },
+        instruction::TopUpIntegrator { lamports },
+    )
+}
+
+/// `treasury` must be the configured one.
+pub fn collect_integrator_fees(
+    authority: Pubkey,
+    consumer: Pubkey,
+    treasury: Pubkey,
+) -> Instruction {
+    build(
+        accounts::CollectIntegratorFees {
+            config: pda::config().0,
+            integrator: pda::integrator(&consumer).0,
+            treasury,
+            authority,
+        },
+        instruction::CollectIntegratorFees {},
+    )
+}
+
+/// Caps the scores accepted for `consumer` at `max_score` and names the risk
+/// officer who may move the cap.
+pub fn set_risk_ceiling(
+    authority: Pubkey,
+    consumer: Pubkey,
+    officer: Pubkey,
+    max_score: u8,
+) -> Instruction {
+    build(
+        accounts::SetRiskCeiling {
+            config: pda::config().0,
+            risk_ceiling: pda::risk_ceiling(&consumer).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetRiskCeiling {
+            consumer,
+            officer,
+            max_score,
+        },
+    )
+}
+
+/// Signed by the ceiling's risk officer.
+pub fn update_risk_ceiling(officer: Pubkey, consumer: Pubkey, max_score: u8) -> Instruction {
+    build(
+        accounts::UpdateRiskCeiling {
+            risk_ceiling: pda::risk_ceiling(&consumer).0,
+            officer,
+        },
+        instruction::UpdateRiskCeiling { max_score },
+    )
+}
+
+/// Sets the penalty cached scores take in `check_or_verify_score` as they
+/// age past `grace_slots`.
+pub fn set_score_decay(
+    authority: Pubkey,
+    grace_slots: u64,
+    interval_slots: u64,
+    penalty: u8,
+    max_penalty: u8,
+) -> Instruction {
+    build(
+        accounts::SetScoreDecay {
+            config: pda::config().0,
+            score_decay: pda::score_decay().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetScoreDecay {
+            grace_slots,
+            interval_slots,
+            penalty,
+            max_penalty,
+        },
+    )
+}
+
+/// Sets the policy of `address`, `threshold` replacing the config's for it
+/// and `min_oracle_samples` raising the samples its quotes need.
+pub fn set_address_policy(
+    authority: Pubkey,
+    address: Pubkey,
+    verdict: PolicyVerdict,
+    threshold: Option<u8>,
+    min_oracle_samples: u8,
+) -> Instruction {
+    build(
+        accounts::SetAddressPolicy {
+            config: pda::config().0,
+            address_policy: pda::address_policy(&address).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetAddressPolicy {
+            address,
+            verdict,
+            threshold,
+            min_oracle_samples,
+        },
+    )
+}
+
+pub fn remove_address_policy(authority: Pubkey, address: Pubkey) -> Instruction {
+    build(
+        accounts::RemoveAddressPolicy {
+            config: pda::config().0,
+            address_policy: pda::address_policy(&address).0,
+            authority,
+        },
+        instruction::RemoveAddressPolicy { address },
+    )
+}
+
+pub fn set_compliance_officer(authority: Pubkey, officer: Pubkey) -> Instruction {
+    build(
+        accounts::SetComplianceOfficer {
+            config: pda::config().0,
+            compliance_officer: pda::compliance_officer().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetComplianceOfficer { officer },
+    )
+}
+
+/// Bytes the compliance officer signs (off-chain, with the officer key) to
+/// issue `voucher`.
+pub fn bypass_voucher_message(voucher: BypassVoucher) -> [u8; voucher::MESSAGE_LEN] {
+    voucher::BypassVoucher::from(voucher).message(&ID.to_bytes())
+}
+
+/// The Ed25519 instruction carrying the officer's `signature` of
+/// [`bypass_voucher_message`] and the redemption right after it, in the order
+/// the program requires.
+pub fn redeem_bypass_voucher(
+    payer: Pubkey,
+    officer: Pubkey,
+    signature: [u8; 64],
+    voucher: BypassVoucher,
+) -> [Instruction; 2] {
+    let signature_ix = Instruction {
+        program_id: ed25519_program::ID,
+        accounts: Vec::new(),
+        data: voucher::instruction_data(
+            &officer.to_bytes(),
+            &signature,
+            &bypass_voucher_message(voucher),
+        ),
+    };
+    let redeem_ix = build(
+        accounts::RedeemBypassVoucher {
+            compliance_officer: pda::compliance_officer().0,
+            instructions: sysvar::instructions::ID,
+            bypass_record: pda::bypass_record(&voucher.address, voucher.nonce).0,
+            payer,
+            system_program: system_program::ID,
+        },
+        instruction::RedeemBypassVoucher { voucher },
+    );
+    [signature_ix, redeem_ix]
+}
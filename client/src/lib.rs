@@ -0,0 +1,243 @@
+//! Typed Rust client for the Anchor risk oracle program.
+//!
+//! Everything here is generated from the program crate itself (built with
+//! `no-entrypoint`): instruction data and account metas come from the
+//! `instruction` and `accounts` modules Anchor emits, account types from
+//! `state`, events from `events` and errors from `error::ErrorCode`. Adding an
+//! instruction to the program makes it available here on the next build;
+//! only the convenience builders in [`instructions`] are written by hand.
+
+pub mod cluster;
+pub mod denylist;
+pub mod errors;
+pub mod events;
+pub mod fixtures;
+pub mod instructions;
+mod list;
+pub mod pda;
+pub mod preflight;
+pub mod quote;
+pub mod range;
+pub mod subscribe;
+pub mod transaction;
+pub mod validate;
+pub mod view;
+
+use std::ops::Deref;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::Signer;
+use anchor_client::{Client, ClientError, Program};
+use anchor_lang::prelude::ProgramData;
+
+pub use anchor_oracle_example::error::ErrorCode;
+pub use anchor_oracle_example::state::{
+    AddressPolicy, ConfidentialScore, Config, ConsumerAccessList, DenylistEntry, DenylistRoot,
+    DenylistSegment, FeedIdRecord, FeedProvenance, FeedRegistry, FreshnessSla, Integrator,
+    PolicyVerdict, PostedFeed, ProgramDenylist, QuoteAccount, QuoteMarker, RegistryEntry,
+    RiskCeiling, ScoreCache, ScoreDecay, HistoryEntry, Momentum, ScoreHistory, ScoreProvenance,
+    Severity, ThresholdProof, VerificationReceipt,
+};
+pub use anchor_oracle_example::{
+    accounts, instruction, CachedScoreView, ConfigParams, ConfigView, DenylistProof, LeafProof,
+    RiskThreshold, ID,
+};
+pub use list::Page;
+pub use view::ViewError;
+pub use risk_oracle_shared::{describe, profiles};
+
+/// Switchboard queue of the active deployment profile.
+pub fn queue() -> Pubkey {
+    Pubkey::new_from_array(profiles::ACTIVE.queue)
+}
+
+/// Reads the program accounts through an `anchor_client` connection.
+pub struct RiskOracleClient<C> {
+    program: Program<C>,
+}
+
+impl<C: Deref<Target = impl Signer> + Clone> RiskOracleClient<C> {
+    pub fn new(client: &Client<C>) -> Result<Self, ClientError> {
+        Ok(Self {
+            program: client.program(ID)?,
+        })
+    }
+
+    /// Underlying program handle, to send transactions built with
+    /// [`instructions`].
+    pub fn program(&self) -> &Program<C> {
+        &self.program
+    }
+
+    pub fn get_config(&self) -> Result<Config, ClientError> {
+        self.program.account(pda::config().0)
+    }
+
+    /// The only signer `initialize_config` accepts; `None` once the program
+    /// is immutable.
+    pub fn get_upgrade_authority(&self) -> Result<Option<Pubkey>, ClientError> {
+        let program_data: ProgramData = self.program.account(pda::program_data().0)?;
+        Ok(program_data.upgrade_authority_address)
+    }
+
+    pub fn get_registry(&self) -> Result<FeedRegistry, ClientError> {
+        self.program.account(pda::registry().0)
+    }
+
+    pub fn get_cached_score(&self, query_account: &Pubkey) -> Result<ScoreCache, ClientError> {
+        self.program.account(pda::score_cache(query_account).0)
+    }
+
+    pub fn get_feed_id_record(&self, query_account: &Pubkey) -> Result<FeedIdRecord, ClientError> {
+        self.program.account(pda::feed_id_record(query_account).0)
+    }
+
+    pub fn get_receipt(&self, query_account: &Pubkey) -> Result<VerificationReceipt, ClientError> {
+        self.program.account(pda::receipt(query_account).0)
+    }
+
+    pub fn get_history(&self, query_account: &Pubkey) -> Result<ScoreHistory, ClientError> {
+        self.program.account(pda::score_history(query_account).0)
+    }
+
+    pub fn get_freshness_sla(&self, query_account: &Pubkey) -> Result<FreshnessSla, ClientError> {
+        self.program.account(pda::freshness_sla(query_account).0)
+    }
+
+    /// Only the commitment, the score itself is never stored.
+    pub fn get_confidential_score(
+        &self,
+        query_account: &Pubkey,
+    ) -> Result<ConfidentialScore, ClientError> {
+        self.program.account(pda::confidential_score(query_account).0)
+    }
+
+    pub fn get_threshold_proof(
+        &self,
+        query_account: &Pubkey,
+    ) -> Result<ThresholdProof, ClientError> {
+        self.program.account(pda::threshold_proof(query_account).0)
+    }
+
+    pub fn get_access_list(&self) -> Result<ConsumerAccessList, ClientError> {
+        self.program.account(pda::access_list().0)
+    }
+
+    pub fn get_program_denylist(&self) -> Result<ProgramDenylist, ClientError> {
+        self.program.account(pda::program_denylist().0)
+    }
+
+    /// Usage account of the consuming program `consumer`.
+    pub fn get_integrator(&self, consumer: &Pubkey) -> Result<Integrator, ClientError> {
+        self.program.account(pda::integrator(consumer).0)
+    }
+
+    pub fn get_score_decay(&self) -> Result<ScoreDecay, ClientError> {
+        self.program.account(pda::score_decay().0)
+    }
+
+    /// Risk ceiling of the consuming program `consumer`.
+    pub fn get_risk_ceiling(&self, consumer: &Pubkey) -> Result<RiskCeiling, ClientError> {
+        self.program.account(pda::risk_ceiling(consumer).0)
+    }
+
+    /// Policy of the screened wallet `address`.
+    pub fn get_address_policy(&self, address: &Pubkey) -> Result<AddressPolicy, ClientError> {
+        self.program.account(pda::address_policy(address).0)
+    }
+
+    pub fn get_denylist_root(&self) -> Result<DenylistRoot, ClientError> {
+        self.program.account(pda::denylist_root().0)
+    }
+
+    /// Packed denylist segment `address` would be listed in.
+    pub fn get_denylist_segment(&self, address: &Pubkey) -> Result<DenylistSegment, ClientError> {
+        let segment = denylist::segment_of(address);
+        self.program.account(pda::denylist_segment(segment).0)
+    }
+
+    /// The cached score of `query_account` with the values the program derives
+    /// from it, through a simulated `get_cached_score`. With `freshness_sla`,
+    /// the SLA is checked too (it must exist).
+    pub fn view_cached_score(
+        &self,
+        query_account: &Pubkey,
+        freshness_sla: bool,
+    ) -> Result<CachedScoreView, ViewError> {
+        view::simulate(
+            &self.program,
+            instructions::get_cached_score(*query_account, freshness_sla),
+        )
+    }
+
+    /// The config and the active profile's limits, through a simulated
+    /// `get_config`.
+    pub fn view_config(&self) -> Result<ConfigView, ViewError> {
+        view::simulate(&self.program, instructions::get_config())
+    }
+
+    /// The config, the active profile's limits and every feature flag by
+    /// name, through a simulated `describe`. Read the entries with
+    /// [`describe::entries`]; they are checked to decode.
+    pub fn view_description(&self) -> Result<Vec<u8>, ViewError> {
+        let data: Vec<u8> = view::simulate(&self.program, instructions::describe())?;
+        describe::entries(&data).map_err(|error| ViewError::Decode(format!("{:?}", error)))?;
+        Ok(data)
+    }
+
+    /// Score caches kept by the program, i.e. the addresses being watched,
+    /// keyed by cache address.
+    pub fn list_watchlist(&self, page: Page) -> Result<Vec<(Pubkey, ScoreCache)>, ClientError> {
+        list::accounts(&self.program, page)
+    }
+
+    /// Freshness SLAs set with `set_freshness_sla`, keyed by SLA address.
+    pub fn list_freshness_slas(
+        &self,
+        page: Page,
+    ) -> Result<Vec<(Pubkey, FreshnessSla)>, ClientError> {
+        list::accounts(&self.program, page)
+    }
+
+    /// Integrators registered for accounting, keyed by integrator address.
+    pub fn list_integrators(&self, page: Page) -> Result<Vec<(Pubkey, Integrator)>, ClientError> {
+        list::accounts(&self.program, page)
+    }
+
+    /// Score histories, keyed by history address.
+    pub fn list_histories(&self, page: Page) -> Result<Vec<(Pubkey, ScoreHistory)>, ClientError> {
+        list::accounts(&self.program, page)
+    }
+
+    /// Verification receipts, expired ones included, keyed by receipt address.
+    pub fn list_receipts(
+        &self,
+        page: Page,
+    ) -> Result<Vec<(Pubkey, VerificationReceipt)>, ClientError> {
+        list::accounts(&self.program, page)
+    }
+
+    /// Threshold proofs, keyed by proof address.
+    pub fn list_threshold_proofs(
+        &self,
+        page: Page,
+    ) -> Result<Vec<(Pubkey, ThresholdProof)>, ClientError> {
+        list::accounts(&self.program, page)
+    }
+
+    /// Confidential score commitments, keyed by commitment address.
+    pub fn list_confidential_scores(
+        &self,
+        page: Page,
+    ) -> Result<Vec<(Pubkey, ConfidentialScore)>, ClientError> {
+        list::accounts(&self.program, page)
+    }
+
+    /// Addresses imported with `import_denylist`, keyed by entry address. The
+    /// merkle denylist is off-chain, see [`get_denylist_root`].
+    ///
+    /// [`get_denylist_root`]: Self::get_denylist_root
+    pub fn list_denylist(&self, page: Page) -> Result<Vec<(Pubkey, DenylistEntry)>, ClientError> {
+        list::accounts(&self.program, page)
+    }
+}
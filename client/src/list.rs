@@ -0,0 +1,93 @@
+//! Paginated listing of program accounts of one type.
+//!
+//! `getProgramAccounts` has no pagination, so listings fetch the matching
+//! addresses only (discriminator filter, empty data slice), sort them, and
+//! load the requested page with `getMultipleAccounts`.
+
+use std::ops::Deref;
+
+use anchor_client::solana_account_decoder::UiDataSliceConfig;
+use anchor_client::solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use anchor_client::solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::Signer;
+use anchor_client::{ClientError, Program};
+use anchor_lang::{AccountDeserialize, Discriminator};
+
+/// `getMultipleAccounts` limit.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Window over the accounts of a listing, sorted by address.
+#[derive(Clone, Copy, Debug)]
+pub struct Page {
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl Default for Page {
+    /// The first [`MAX_MULTIPLE_ACCOUNTS`] accounts.
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            limit: MAX_MULTIPLE_ACCOUNTS,
+        }
+    }
+}
+
+impl Page {
+    /// Every account, however many.
+    pub const ALL: Page = Page {
+        offset: 0,
+        limit: usize::MAX,
+    };
+}
+
+pub(crate) fn accounts<C, S, T>(
+    program: &Program<C>,
+    page: Page,
+) -> Result<Vec<(Pubkey, T)>, ClientError>
+where
+    C: Deref<Target = S> + Clone,
+    S: Signer,
+    T: AccountDeserialize + Discriminator,
+{
+    let rpc = program.rpc();
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            T::DISCRIMINATOR.to_vec(),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            data_slice: Some(UiDataSliceConfig {
+                offset: 0,
+                length: 0,
+            }),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let mut addresses: Vec<Pubkey> = rpc
+        .get_program_accounts_with_config(&program.id(), config)?
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+    addresses.sort_unstable();
+
+    let mut accounts = Vec::new();
+    for chunk in addresses
+        .iter()
+        .skip(page.offset)
+        .take(page.limit)
+        .collect::<Vec<_>>()
+        .chunks(MAX_MULTIPLE_ACCOUNTS)
+    {
+        let keys: Vec<Pubkey> = chunk.iter().map(|address| **address).collect();
+        for (address, account) in keys.iter().zip(rpc.get_multiple_accounts(&keys)?) {
+            // Closed since the address listing.
+            let Some(account) = account else { continue };
+            let state = T::try_deserialize(&mut account.data.as_slice())?;
+            accounts.push((*address, state));
+        }
+    }
+    Ok(accounts)
+}
@@ -0,0 +1,118 @@
+//! Program derived addresses, from the seeds and derivations of
+//! `risk_oracle_shared::seeds`.
+
+use anchor_client::solana_sdk::bpf_loader_upgradeable;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_oracle_example::ID;
+use risk_oracle_shared::seeds::{self, FindProgramAddress};
+
+const FIND: FindProgramAddress<Pubkey> = Pubkey::find_program_address;
+
+pub fn config() -> (Pubkey, u8) {
+    seeds::find_config_address(FIND, &ID)
+}
+
+/// The program's `ProgramData`, holding its upgrade authority.
+pub fn program_data() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+pub fn registry() -> (Pubkey, u8) {
+    seeds::find_registry_address(FIND, &ID)
+}
+
+pub fn denylist_root() -> (Pubkey, u8) {
+    seeds::find_denylist_root_address(FIND, &ID)
+}
+
+pub fn access_list() -> (Pubkey, u8) {
+    seeds::find_access_list_address(FIND, &ID)
+}
+
+pub fn program_denylist() -> (Pubkey, u8) {
+    seeds::find_program_denylist_address(FIND, &ID)
+}
+
+pub fn score_decay() -> (Pubkey, u8) {
+    seeds::find_score_decay_address(FIND, &ID)
+}
+
+pub fn compliance_officer() -> (Pubkey, u8) {
+    seeds::find_compliance_officer_address(FIND, &ID)
+}
+
+/// Keyed by the `feed::schema` tag of the template.
+pub fn template_variables(schema: u8) -> (Pubkey, u8) {
+    seeds::find_template_variables_address(FIND, &ID, schema)
+}
+
+pub fn replay_record(query_account: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_replay_record_address(FIND, &ID, &query_account.to_bytes())
+}
+
+pub fn score_cache(query_account: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_score_cache_address(FIND, &ID, &query_account.to_bytes())
+}
+
+pub fn feed_id_record(query_account: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_feed_id_record_address(FIND, &ID, &query_account.to_bytes())
+}
+
+pub fn quote_marker(query_account: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_quote_marker_address(FIND, &ID, &query_account.to_bytes())
+}
+
+pub fn receipt(query_account: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_receipt_address(FIND, &ID, &query_account.to_bytes())
+}
+
+pub fn score_history(query_account: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_score_history_address(FIND, &ID, &query_account.to_bytes())
+}
+
+pub fn freshness_sla(query_account: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_freshness_sla_address(FIND, &ID, &query_account.to_bytes())
+}
+
+pub fn confidential_score(query_account: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_confidential_score_address(FIND, &ID, &query_account.to_bytes())
+}
+
+pub fn threshold_proof(query_account: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_threshold_proof_address(FIND, &ID, &query_account.to_bytes())
+}
+
+/// Keyed by the screened wallet, not the query account.
+pub fn denylist_entry(address: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_denylist_entry_address(FIND, &ID, &address.to_bytes())
+}
+
+/// Keyed by the consuming program, not the query account.
+pub fn integrator(consumer: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_integrator_address(FIND, &ID, &consumer.to_bytes())
+}
+
+/// Keyed by the consuming program, not the query account.
+pub fn risk_ceiling(consumer: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_risk_ceiling_address(FIND, &ID, &consumer.to_bytes())
+}
+
+/// Keyed by the segment index, see [`crate::denylist::segment_of`].
+pub fn denylist_segment(segment: u16) -> (Pubkey, u8) {
+    seeds::find_denylist_segment_address(FIND, &ID, segment)
+}
+
+/// Keyed by the screened wallet. For token accounts, this is the owner.
+pub fn address_policy(address: &Pubkey) -> (Pubkey, u8) {
+    seeds::find_address_policy_address(FIND, &ID, &address.to_bytes())
+}
+
+/// Keyed by the screened wallet and the voucher nonce.
+pub fn bypass_record(address: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    seeds::find_bypass_record_address(FIND, &ID, &address.to_bytes(), nonce)
+}
+
+/// Keyed by the owner and its own id for the account.
+pub fn quote_account(owner: &Pubkey, id: u64) -> (Pubkey, u8) {
+    seeds::find_quote_account_address(FIND, &ID, &owner.to_bytes(), id)
+}
@@ -0,0 +1,80 @@
+//! Preflight of a transaction whose instruction checks a score through CPI,
+//! e.g. an integrator's handler under `#[risk_gated]`.
+//!
+//! The transaction is simulated as it will be sent, quote included, and
+//! nothing is paid for. A consumer that sets no return data of its own
+//! leaves the oracle's, the [`VerifiedScore`] of the check, as the
+//! transaction's return data: the caller learns the score, or the error the
+//! transaction would fail with, and decides whether to send it at all.
+
+use anchor_client::solana_client::client_error::ClientError as RpcError;
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::RpcSimulateTransactionConfig;
+use anchor_client::solana_sdk::transaction::VersionedTransaction;
+use anchor_oracle_example::{VerifiedScore, ID};
+
+use crate::errors::{self, ProgramError};
+use crate::view;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PreflightError {
+    #[error("simulation request failed: {0}")]
+    Rpc(#[from] RpcError),
+    /// The transaction set no return data, or the consumer replaced the
+    /// oracle's with its own.
+    #[error("no return data of the oracle program (last set by {0})")]
+    NoScore(String),
+    #[error("failed to decode the verified score: {0}")]
+    Decode(String),
+}
+
+/// Outcome of the simulation.
+#[derive(Clone, Debug)]
+pub enum Preflight {
+    /// The transaction would succeed with this score.
+    Passed(VerifiedScore),
+    /// The transaction would fail, with the program error when the logs name
+    /// one (`RiskThresholdExceeded`, `StaleQuote`, ...).
+    Failed(Option<ProgramError>),
+}
+
+impl Preflight {
+    /// Whether the transaction is worth sending: its simulation succeeded
+    /// with a score of at most `max_score`. A margin under the on-chain
+    /// threshold leaves room for the score to move before the transaction
+    /// lands with a new quote.
+    pub fn should_send(&self, max_score: u8) -> bool {
+        matches!(self, Preflight::Passed(verified) if verified.score <= max_score)
+    }
+}
+
+/// Simulates `transaction` (its signatures and blockhash aren't checked)
+/// and reads the oracle's score from its return data.
+pub fn simulate(
+    rpc: &RpcClient,
+    transaction: &VersionedTransaction,
+) -> Result<Preflight, PreflightError> {
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = rpc
+        .simulate_transaction_with_config(transaction, config)?
+        .value;
+    if result.err.is_some() {
+        return Ok(Preflight::Failed(
+            result.logs.as_deref().and_then(errors::from_logs),
+        ));
+    }
+
+    let return_data = result
+        .return_data
+        .ok_or_else(|| PreflightError::NoScore("no program".to_string()))?;
+    if return_data.program_id != ID.to_string() {
+        return Err(PreflightError::NoScore(return_data.program_id));
+    }
+    view::decode(&return_data.data.0)
+        .map(Preflight::Passed)
+        .map_err(PreflightError::Decode)
+}
@@ -0,0 +1,280 @@
+//! Switchboard quote payload, decoded off-chain.
+//!
+//! Lets indexers and tooling read a quote without the on-chain verifier. The
+//! quote is an Ed25519 program instruction. It starts with the standard
+//! Ed25519 header:
+//!
+//! ```text
+//! [num_signatures: u8][padding: u8]
+//! num_signatures x [signature_offset: u16][signature_ix: u16]
+//!                  [public_key_offset: u16][public_key_ix: u16]
+//!                  [message_offset: u16][message_size: u16][message_ix: u16]
+//! ```
+//!
+//! Every oracle signs the same Switchboard quote message:
+//!
+//! ```text
+//! [slot_hash: 32] then per feed [feed_id: 32][value: i128 LE, 18 decimals][min_oracle_samples: u8]
+//! ```
+//!
+//! The data ends with a suffix: the signers' queue indexes (one byte per
+//! signature), the quote slot (u64 LE), a version byte and the `SBOD` tail.
+//! Quotes without the tail carry the slot right after the signed parts.
+//!
+//! The types serialize to JSON with byte arrays as lowercase hex and values
+//! as decimal strings, since an `i128` doesn't fit a JSON number.
+
+use risk_oracle_shared::quote_limits;
+use serde::{Deserialize, Serialize};
+
+pub(crate) const HEADER_LEN: usize = 2;
+pub(crate) const OFFSETS_LEN: usize = 14;
+const SLOT_HASH_LEN: usize = 32;
+pub(crate) const FEED_LEN: usize = 32 + 16 + 1;
+/// Instruction index meaning "this instruction" in the offsets.
+const CURRENT_IX: u16 = u16::MAX;
+/// Index of the quote instruction in the transaction. The verifier requires
+/// the offsets to name it explicitly; under `quote-discovery` the quote may
+/// follow up to `quote_limits::MAX_COMPUTE_BUDGET_INSTRUCTIONS` others (see
+/// [`set_quote_index`]).
+pub(crate) const QUOTE_IX: u16 = 0;
+/// Version byte of the quote suffix.
+pub(crate) const QUOTE_VERSION: u8 = 1;
+/// Last bytes of a quote instruction.
+pub(crate) const QUOTE_TAIL: [u8; 4] = *b"SBOD";
+/// Slot, version and tail of the suffix.
+const SUFFIX_LEN: usize = 8 + 1 + 4;
+/// Scale of the feed values.
+pub const VALUE_DECIMALS: u32 = 18;
+
+#[derive(Debug, thiserror::Error)]
+pub enum QuoteDecodeError {
+    #[error("quote instruction is truncated")]
+    Truncated,
+    #[error("quote carries no signature")]
+    NoSignature,
+    #[error("signature {0} references another instruction")]
+    ExternalReference(usize),
+    #[error("oracles signed different messages")]
+    MessageMismatch,
+    #[error("unexpected quote message length {0}")]
+    MessageLength(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OracleSignature {
+    #[serde(with = "hex_bytes")]
+    pub signer: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub signature: [u8; 64],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteFeed {
+    #[serde(with = "hex_bytes")]
+    pub feed_id: [u8; 32],
+    /// Feed value scaled by 10^[`VALUE_DECIMALS`].
+    #[serde(with = "i128_string")]
+    pub value: i128,
+    pub min_oracle_samples: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Quote {
+    pub signatures: Vec<OracleSignature>,
+    #[serde(with = "hex_bytes")]
+    pub slot_hash: [u8; 32],
+    pub feeds: Vec<QuoteFeed>,
+    pub slot: Option<u64>,
+}
+
+fn read_u16(data: &[u8], at: usize) -> Result<u16, QuoteDecodeError> {
+    let bytes = data.get(at..at + 2).ok_or(QuoteDecodeError::Truncated)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], QuoteDecodeError> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(QuoteDecodeError::Truncated)
+}
+
+/// Highest index the program reads the quote instruction at.
+fn max_quote_ix() -> u16 {
+    if cfg!(feature = "quote-discovery") {
+        QUOTE_IX + quote_limits::MAX_COMPUTE_BUDGET_INSTRUCTIONS as u16
+    } else {
+        QUOTE_IX
+    }
+}
+
+/// Points the offsets of the quote instruction `data` at instruction `index`,
+/// for a quote moved behind compute budget instructions. The signatures cover
+/// the message only, so they stay valid.
+pub fn set_quote_index(data: &mut [u8], index: u16) {
+    let count = data.first().copied().unwrap_or(0) as usize;
+    for i in 0..count {
+        let at = HEADER_LEN + i * OFFSETS_LEN;
+        for ix_index in [at + 2, at + 6, at + 12] {
+            if let Some(field) = data.get_mut(ix_index..ix_index + 2) {
+                if u16::from_le_bytes([field[0], field[1]]) != CURRENT_IX {
+                    field.copy_from_slice(&index.to_le_bytes());
+                }
+            }
+        }
+    }
+}
+
+/// Decodes the data of a quote instruction.
+pub fn decode(data: &[u8]) -> Result<Quote, QuoteDecodeError> {
+    if data.len() < HEADER_LEN {
+        return Err(QuoteDecodeError::Truncated);
+    }
+    let count = data[0] as usize;
+    if count == 0 {
+        return Err(QuoteDecodeError::NoSignature);
+    }
+
+    let mut signatures = Vec::with_capacity(count);
+    let mut message: Option<&[u8]> = None;
+    let mut end = HEADER_LEN + count * OFFSETS_LEN;
+    for i in 0..count {
+        let at = HEADER_LEN + i * OFFSETS_LEN;
+        let signature_offset = read_u16(data, at)? as usize;
+        let public_key_offset = read_u16(data, at + 4)? as usize;
+        let message_offset = read_u16(data, at + 8)? as usize;
+        let message_size = read_u16(data, at + 10)? as usize;
+        for ix_index in [at + 2, at + 6, at + 12] {
+            let ix = read_u16(data, ix_index)?;
+            if ix != CURRENT_IX && ix > max_quote_ix() {
+                return Err(QuoteDecodeError::ExternalReference(i));
+            }
+        }
+
+        let signed = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(QuoteDecodeError::Truncated)?;
+        if message.is_some_and(|message| message != signed) {
+            return Err(QuoteDecodeError::MessageMismatch);
+        }
+        message = Some(signed);
+
+        signatures.push(OracleSignature {
+            signer: read(data, public_key_offset)?,
+            signature: read(data, signature_offset)?,
+        });
+        end = end
+            .max(message_offset + message_size)
+            .max(public_key_offset + 32)
+            .max(signature_offset + 64);
+    }
+
+    let message = message.unwrap_or_default();
+    if message.len() < SLOT_HASH_LEN || (message.len() - SLOT_HASH_LEN) % FEED_LEN != 0 {
+        return Err(QuoteDecodeError::MessageLength(message.len()));
+    }
+    let (slot_hash, feeds) = message.split_at(SLOT_HASH_LEN);
+    let feeds = feeds
+        .chunks_exact(FEED_LEN)
+        .map(|feed| QuoteFeed {
+            feed_id: feed[..32].try_into().unwrap(),
+            value: i128::from_le_bytes(feed[32..48].try_into().unwrap()),
+            min_oracle_samples: feed[48],
+        })
+        .collect();
+
+    Ok(Quote {
+        signatures,
+        slot_hash: read(slot_hash, 0)?,
+        feeds,
+        slot: read(data, slot_offset(data, count, end)).ok().map(u64::from_le_bytes),
+    })
+}
+
+/// Offset of the quote slot: in the suffix when the data ends with the tail,
+/// right after the signed parts otherwise.
+fn slot_offset(data: &[u8], count: usize, end: usize) -> usize {
+    if data.ends_with(&QUOTE_TAIL) && data.len() >= end + count + SUFFIX_LEN {
+        data.len() - SUFFIX_LEN
+    } else {
+        end
+    }
+}
+
+/// Formats a feed value with its 18 decimals, trailing zeros dropped.
+pub fn format_value(value: i128) -> String {
+    let scale = 10i128.pow(VALUE_DECIMALS);
+    let sign = if value < 0 { "-" } else { "" };
+    let integer = (value / scale).unsigned_abs();
+    let fraction = (value % scale).unsigned_abs();
+    if fraction == 0 {
+        return format!("{}{}", sign, integer);
+    }
+    let fraction = format!("{:018}", fraction);
+    format!("{}{}.{}", sign, integer, fraction.trim_end_matches('0'))
+}
+
+/// Parses a decimal feed value (`"2"`, `"-0.5"`) scaled by 10^18, the inverse
+/// of [`format_value`]. `None` for more than 18 decimals or out of range.
+pub fn parse_value(text: &str) -> Option<i128> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if integer.is_empty() || fraction.len() > VALUE_DECIMALS as usize {
+        return None;
+    }
+    if !integer.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let fraction = format!("{:0<18}", fraction);
+    let value = integer
+        .parse::<i128>()
+        .ok()?
+        .checked_mul(10i128.pow(VALUE_DECIMALS))?
+        .checked_add(fraction.parse::<i128>().ok()?)?;
+    Some(if negative { -value } else { value })
+}
+
+mod hex_bytes {
+    use risk_oracle_shared::diagnostics::hex;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        bytes: &[u8; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[u8; N], D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        if hex.len() != N * 2 || !hex.is_ascii() {
+            return Err(D::Error::custom(format!("expected {} hex bytes", N)));
+        }
+        let mut bytes = [0u8; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(D::Error::custom)?;
+        }
+        Ok(bytes)
+    }
+}
+
+mod i128_string {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &i128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i128, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
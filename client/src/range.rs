@@ -0,0 +1,136 @@
+//! Typed Range risk API responses, and a client fetching them directly, off
+//! the oracle path.
+//!
+//! Keepers use it to pre-filter: read the score Range would answer the oracle
+//! now and only pay for a quote (and a transaction) when it differs from the
+//! cached one. The request is the one the feed's HTTP task sends
+//! ([`feed::risk_score_url`], same headers), and [`RiskResponse::score`] maps
+//! the answer to the feed scale the way the job and the program do, so an
+//! off-chain score compares directly with a verified one. It is unsigned:
+//! never a substitute for verifying a quote.
+
+use risk_oracle_shared::feed;
+#[cfg(feature = "range")]
+use risk_oracle_shared::profiles;
+use serde::{Deserialize, Serialize};
+
+/// Answer of the risk endpoint (`profiles::ACTIVE.range_risk_url`). Fields
+/// the oracle doesn't read are optional, Range adds to the response over time.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskResponse {
+    /// Provider-native score, 0–10; the job's `$.riskScore`.
+    pub risk_score: f64,
+    /// Range's wording of the score, e.g. `"Low risk"`.
+    #[serde(default)]
+    pub risk_level: Option<String>,
+    /// The blacklist flag, `$.isBlacklisted` of the score-and-flag feeds.
+    #[serde(default)]
+    pub is_blacklisted: Option<bool>,
+    /// Labels Range attaches to the address (exchange, mixer, ...).
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub network: Option<String>,
+}
+
+/// A label of the address, fields as Range sends them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Label {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub entity: Option<String>,
+}
+
+impl RiskResponse {
+    /// Score on the feed scale (0–100), as the program would read it from a
+    /// quote of this answer: ×10, bounded, fraction rounded up.
+    pub fn score(&self) -> u8 {
+        // In millionths, so that float noise (0.3 × 10) isn't rounded up.
+        let millionths = (self.risk_score * f64::from(feed::SCORE_SCALE) * 1e6).round();
+        let millionths = millionths.clamp(0.0, f64::from(feed::MAX_SCORE) * 1e6) as u64;
+        millionths.div_ceil(1_000_000) as u8
+    }
+
+    /// Whether Range flags the address, `false` when it doesn't say.
+    pub fn blacklisted(&self) -> bool {
+        self.is_blacklisted.unwrap_or(false)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RangeError {
+    #[error("Range request failed: {0}")]
+    Request(String),
+    #[error("Range answered {status}: {body}")]
+    Status { status: u16, body: String },
+    #[error("unreadable Range response: {0}")]
+    Decode(String),
+}
+
+/// Range risk API client, sending the feed's request with `api_key`.
+#[cfg(feature = "range")]
+#[derive(Clone, Debug)]
+pub struct RangeApi {
+    http: reqwest::blocking::Client,
+    api_key: String,
+    /// Replaces the profile's `range_risk_url`, e.g. for the Range mock.
+    risk_url: String,
+}
+
+#[cfg(feature = "range")]
+impl RangeApi {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self::with_client(reqwest::blocking::Client::new(), api_key)
+    }
+
+    /// Client sending requests through `http` (timeouts, proxies, ...).
+    pub fn with_client(http: reqwest::blocking::Client, api_key: impl Into<String>) -> Self {
+        Self {
+            http,
+            api_key: api_key.into(),
+            risk_url: profiles::ACTIVE.range_risk_url.to_string(),
+        }
+    }
+
+    /// Sends the requests to `risk_url` instead of the profile's endpoint.
+    pub fn with_risk_url(mut self, risk_url: impl Into<String>) -> Self {
+        self.risk_url = risk_url.into();
+        self
+    }
+
+    /// Current Range answer for `address` on `network` (e.g.
+    /// `profiles::ACTIVE.range_network`).
+    pub fn fetch_offchain_score(
+        &self,
+        address: &[u8; 32],
+        network: &str,
+    ) -> Result<RiskResponse, RangeError> {
+        let url = feed::risk_score_url(address, network).replacen(
+            profiles::ACTIVE.range_risk_url,
+            &self.risk_url,
+            1,
+        );
+        let response = self
+            .http
+            .get(url)
+            .header("accept", profiles::ACTIVE.response.accept)
+            .header("X-API-KEY", &self.api_key)
+            .send()
+            .map_err(|err| RangeError::Request(err.to_string()))?;
+        let status = response.status().as_u16();
+        let body = response
+            .text()
+            .map_err(|err| RangeError::Request(err.to_string()))?;
+        if !(200..300).contains(&status) {
+            return Err(RangeError::Status { status, body });
+        }
+        serde_json::from_str(&body).map_err(|err| RangeError::Decode(err.to_string()))
+    }
+}
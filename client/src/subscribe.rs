@@ -0,0 +1,103 @@
+//! Live score updates: websocket subscriptions to score cache PDAs.
+//!
+//! Each watched query account gets an `accountSubscribe` on its cache PDA;
+//! the notifications are decoded as [`ScoreCache`] and merged into a single
+//! blocking iterator, so a consumer reacts to on-chain risk changes as they
+//! land instead of polling.
+
+use std::sync::mpsc;
+use std::thread;
+
+use anchor_client::solana_account_decoder::UiAccountEncoding;
+use anchor_client::solana_client::pubsub_client::{
+    PubsubAccountClientSubscription, PubsubClient, PubsubClientError,
+};
+use anchor_client::solana_client::rpc_config::RpcAccountInfoConfig;
+use anchor_client::solana_sdk::account::Account;
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_lang::AccountDeserialize;
+
+use crate::{pda, ScoreCache};
+
+/// New content of the score cache of `query_account`.
+#[derive(Clone)]
+pub struct ScoreUpdate {
+    pub query_account: Pubkey,
+    /// Slot of the notification.
+    pub slot: u64,
+    pub cache: ScoreCache,
+}
+
+/// Merged stream of score updates; the subscriptions end when it is dropped.
+pub struct ScoreSubscription {
+    subscriptions: Vec<PubsubAccountClientSubscription>,
+    receiver: mpsc::Receiver<ScoreUpdate>,
+}
+
+/// Subscribes to the score caches of `query_accounts` on `ws_url`. Caches
+/// don't need to exist yet: the first update comes when they are written.
+/// Notifications that don't decode as a cache (e.g. the account was closed)
+/// are skipped.
+pub fn scores(
+    ws_url: &str,
+    query_accounts: &[Pubkey],
+    commitment: CommitmentConfig,
+) -> Result<ScoreSubscription, PubsubClientError> {
+    let (sender, receiver) = mpsc::channel();
+    let mut subscriptions = Vec::with_capacity(query_accounts.len());
+
+    for &query_account in query_accounts {
+        let (subscription, notifications) = PubsubClient::account_subscribe(
+            ws_url,
+            &pda::score_cache(&query_account).0,
+            Some(RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(commitment),
+                ..RpcAccountInfoConfig::default()
+            }),
+        )?;
+        subscriptions.push(subscription);
+
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for notification in notifications {
+                let Some(cache) = notification.value.decode::<Account>().and_then(|account| {
+                    ScoreCache::try_deserialize(&mut account.data.as_slice()).ok()
+                }) else {
+                    continue;
+                };
+                let update = ScoreUpdate {
+                    query_account,
+                    slot: notification.context.slot,
+                    cache,
+                };
+                if sender.send(update).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    Ok(ScoreSubscription {
+        subscriptions,
+        receiver,
+    })
+}
+
+impl Iterator for ScoreSubscription {
+    type Item = ScoreUpdate;
+
+    /// Blocks until the next update, `None` once every subscription ended.
+    fn next(&mut self) -> Option<ScoreUpdate> {
+        self.receiver.recv().ok()
+    }
+}
+
+impl Drop for ScoreSubscription {
+    fn drop(&mut self) {
+        for subscription in &mut self.subscriptions {
+            let _ = subscription.shutdown();
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Transaction assembly, v0 with address lookup tables or legacy.
+//!
+//! Some custodial signers still reject v0 messages, so the format is a
+//! choice. Both formats keep the instruction order, and [`message`] checks
+//! the one thing the program relies on: the Switchboard quote (Ed25519)
+//! instruction comes first, as the verify instructions read it at index 0.
+//! With the `quote-discovery` feature (for a program built with it) compute
+//! budget instructions may precede it; [`with_compute_budget`] puts them
+//! where the program accepts them.
+//! It also refuses a transaction whose fee would exceed
+//! [`TransactionOptions::max_fee_lamports`].
+
+use anchor_client::solana_client::client_error::ClientError;
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_sdk::address_lookup_table::state::AddressLookupTable;
+use anchor_client::solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use anchor_client::solana_sdk::{compute_budget, ed25519_program};
+use anchor_client::solana_sdk::hash::Hash;
+use anchor_client::solana_sdk::instruction::Instruction;
+use anchor_client::solana_sdk::message::{v0, CompileError, Message, VersionedMessage};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signer::{Signer, SignerError};
+use anchor_client::solana_sdk::transaction::VersionedTransaction;
+use risk_oracle_shared::quote_limits;
+use serde::{Deserialize, Serialize};
+
+use crate::quote;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionFormat {
+    /// Legacy message; lookup tables are ignored.
+    Legacy,
+    /// Versioned message, accounts found in the lookup tables are loaded
+    /// from them.
+    #[default]
+    V0,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionBuildError {
+    #[error(
+        "the quote (Ed25519) instruction is at index {0}, the program reads it first (after the \
+         compute budget instructions with `quote-discovery`)"
+    )]
+    QuoteNotFirst(usize),
+    #[error("failed to compile the v0 message: {0}")]
+    Compile(#[from] CompileError),
+    #[error("failed to sign: {0}")]
+    Sign(#[from] SignerError),
+    #[error("failed to fetch a lookup table: {0}")]
+    Rpc(#[from] ClientError),
+    #[error("{0} is not an address lookup table")]
+    InvalidLookupTable(Pubkey),
+    #[error("the transaction would cost {fee} lamports, above the {cap} lamports cap")]
+    OverBudget { fee: u64, cap: u64 },
+}
+
+/// Signature fee, per transaction signature and per signature checked by the
+/// Ed25519 precompile (one per oracle in a quote).
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Compute unit limit of an instruction when no limit is requested.
+const DEFAULT_INSTRUCTION_UNITS: u64 = 200_000;
+const MAX_COMPUTE_UNIT_LIMIT: u64 = 1_400_000;
+
+/// Format and lookup tables used to assemble transactions.
+#[derive(Clone, Debug, Default)]
+pub struct TransactionOptions {
+    pub format: TransactionFormat,
+    pub lookup_tables: Vec<AddressLookupTableAccount>,
+    /// Highest fee ([`fee_lamports`]) a transaction may cost.
+    pub max_fee_lamports: Option<u64>,
+}
+
+/// Compiles `instructions` in the chosen format.
+pub fn message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    blockhash: Hash,
+    options: &TransactionOptions,
+) -> Result<VersionedMessage, TransactionBuildError> {
+    if let Some(index) = instructions
+        .iter()
+        .position(|ix| ix.program_id == ed25519_program::ID)
+        .filter(|&index| index != quote_index(instructions))
+    {
+        return Err(TransactionBuildError::QuoteNotFirst(index));
+    }
+
+    let message = match options.format {
+        TransactionFormat::Legacy => VersionedMessage::Legacy(Message::new_with_blockhash(
+            instructions,
+            Some(payer),
+            &blockhash,
+        )),
+        TransactionFormat::V0 => VersionedMessage::V0(v0::Message::try_compile(
+            payer,
+            instructions,
+            &options.lookup_tables,
+            blockhash,
+        )?),
+    };
+    if let Some(cap) = options.max_fee_lamports {
+        let signers = message.header().num_required_signatures;
+        let fee = fee_lamports(instructions, signers as usize);
+        if fee > cap {
+            return Err(TransactionBuildError::OverBudget { fee, cap });
+        }
+    }
+    Ok(message)
+}
+
+/// Index the program reads the quote of `instructions` at: 0, or with
+/// `quote-discovery` the first instruction after the leading compute budget
+/// ones.
+pub fn quote_index(instructions: &[Instruction]) -> usize {
+    if !cfg!(feature = "quote-discovery") {
+        return 0;
+    }
+    instructions
+        .iter()
+        .take(quote_limits::MAX_COMPUTE_BUDGET_INSTRUCTIONS)
+        .take_while(|ix| ix.program_id == compute_budget::ID)
+        .count()
+}
+
+/// `instructions` (the quote first) with the compute budget instructions
+/// `budget` where the program accepts them: before the quote with
+/// `quote-discovery`, the quote's offsets re-pointed at its new index, and
+/// after everything otherwise.
+pub fn with_compute_budget(instructions: &[Instruction], budget: &[Instruction]) -> Vec<Instruction> {
+    if !cfg!(feature = "quote-discovery") {
+        return [instructions, budget].concat();
+    }
+    let mut placed = [budget, instructions].concat();
+    if let Some(quote) = placed.get_mut(budget.len()) {
+        if quote.program_id == ed25519_program::ID {
+            quote::set_quote_index(&mut quote.data, budget.len() as u16);
+        }
+    }
+    placed
+}
+
+/// Fee of a transaction made of `instructions` and signed by `signers`:
+/// signature fees (the precompile's included) plus the priority fee set by
+/// its compute budget instructions, charged on the requested unit limit.
+pub fn fee_lamports(instructions: &[Instruction], signers: usize) -> u64 {
+    let mut signatures = signers as u64;
+    let mut limit = None;
+    let mut price = 0u64;
+    let mut other_instructions = 0u64;
+    for ix in instructions {
+        if ix.program_id == ed25519_program::ID {
+            signatures += ix.data.first().copied().unwrap_or(0) as u64;
+        }
+        if ix.program_id != compute_budget::ID {
+            other_instructions += 1;
+            continue;
+        }
+        // `ComputeBudgetInstruction`: SetComputeUnitLimit(u32) is variant 2,
+        // SetComputeUnitPrice(u64) variant 3.
+        match ix.data.split_first() {
+            Some((2, value)) if value.len() >= 4 => {
+                limit = Some(u32::from_le_bytes(value[..4].try_into().unwrap()) as u64);
+            }
+            Some((3, value)) if value.len() >= 8 => {
+                price = u64::from_le_bytes(value[..8].try_into().unwrap());
+            }
+            _ => {}
+        }
+    }
+    let limit = limit
+        .unwrap_or(other_instructions * DEFAULT_INSTRUCTION_UNITS)
+        .min(MAX_COMPUTE_UNIT_LIMIT);
+    let priority = (limit as u128 * price as u128).div_ceil(1_000_000) as u64;
+    signatures * LAMPORTS_PER_SIGNATURE + priority
+}
+
+/// Compiles and signs `instructions`, the first signer paying.
+pub fn transaction(
+    signers: &[&dyn Signer],
+    instructions: &[Instruction],
+    blockhash: Hash,
+    options: &TransactionOptions,
+) -> Result<VersionedTransaction, TransactionBuildError> {
+    let payer = signers.first().map(|signer| signer.pubkey()).unwrap_or_default();
+    let message = message(&payer, instructions, blockhash, options)?;
+    Ok(VersionedTransaction::try_new(message, signers)?)
+}
+
+/// Loads lookup tables by address, for [`TransactionOptions::lookup_tables`].
+pub fn fetch_lookup_tables(
+    rpc: &RpcClient,
+    addresses: &[Pubkey],
+) -> Result<Vec<AddressLookupTableAccount>, TransactionBuildError> {
+    addresses
+        .iter()
+        .map(|key| {
+            let account = rpc.get_account(key)?;
+            let table = AddressLookupTable::deserialize(&account.data)
+                .map_err(|_| TransactionBuildError::InvalidLookupTable(*key))?;
+            Ok(AddressLookupTableAccount {
+                key: *key,
+                addresses: table.addresses.to_vec(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,288 @@
+//! Static checks of a built transaction against the program's requirements.
+//!
+//! [`validate_transaction`] looks at every instruction of the program that
+//! reads a Switchboard quote and reports what would make it fail on-chain:
+//! the quote (Ed25519) instruction missing from index 0 (or from after the
+//! leading compute budget instructions with `quote-discovery`) or malformed, a queue
+//! other than the active profile's, a sysvar in the wrong place, missing
+//! accounts, or instruction data of the wrong length. Nothing is fetched:
+//! this catches integration bugs before they cost a failed transaction, not
+//! stale quotes or a queue rotation.
+
+use anchor_client::solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use anchor_client::solana_sdk::instruction::{AccountMeta, Instruction};
+use anchor_client::solana_sdk::message::VersionedMessage;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::transaction::VersionedTransaction;
+use anchor_client::solana_sdk::{ed25519_program, sysvar};
+use risk_oracle_shared::verifier_inputs::{self, QuoteInstructionError};
+
+use crate::instructions::{self, VerifyOptions};
+use crate::transaction;
+use crate::{queue, ID};
+
+/// Anchor instruction discriminator length.
+const DISCRIMINATOR_LEN: usize = 8;
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Diagnostic {
+    #[error(
+        "instruction {index} ({name}) reads the Switchboard quote first, but there is no \
+         Ed25519 instruction there: put the quote instruction first"
+    )]
+    QuoteMissing { index: usize, name: &'static str },
+    #[error(
+        "the quote (Ed25519) instruction is at index {0}, the program only reads index 0: move \
+         it first and the compute budget instructions after it"
+    )]
+    QuoteNotFirst(usize),
+    #[error("the quote instruction is malformed ({}): use the quote as fetched", .0.reason())]
+    MalformedQuote(QuoteInstructionError),
+    #[error(
+        "instruction {index} ({name}) has {found} accounts, expected {expected}: build it with \
+         `instructions::{name}`"
+    )]
+    MissingAccounts {
+        index: usize,
+        name: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error(
+        "instruction {index} ({name}): account {position} is {found}, expected {account} \
+         {expected}"
+    )]
+    WrongAccount {
+        index: usize,
+        name: &'static str,
+        /// Which account is expected, e.g. `the profile queue`.
+        account: &'static str,
+        position: usize,
+        expected: Pubkey,
+        found: Pubkey,
+    },
+    #[error("instruction {index} ({name}) has {found} bytes of data, expected {expected}")]
+    DataLength {
+        index: usize,
+        name: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error(
+        "the message loads accounts from lookup table {0}, which was not passed or doesn't hold \
+         them: pass its current contents"
+    )]
+    LookupTable(Pubkey),
+}
+
+/// Checks `transaction`, resolving the accounts of a v0 message through
+/// `lookup_tables`. Empty when nothing is wrong.
+pub fn validate_transaction(
+    transaction: &VersionedTransaction,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Vec<Diagnostic> {
+    match decompile(&transaction.message, lookup_tables) {
+        Ok(instructions) => validate_instructions(&instructions),
+        Err(diagnostic) => vec![diagnostic],
+    }
+}
+
+/// [`validate_transaction`] on the instructions of a transaction, in order.
+pub fn validate_instructions(instructions: &[Instruction]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let quote_index = transaction::quote_index(instructions);
+    let quote_first = instructions
+        .get(quote_index)
+        .is_some_and(|ix| ix.program_id == ed25519_program::ID);
+    let templates = templates();
+    let expected_accounts = [
+        ("the profile queue", queue()),
+        ("the SlotHashes sysvar", sysvar::slot_hashes::ID),
+        ("the Instructions sysvar", sysvar::instructions::ID),
+        ("the Clock sysvar", sysvar::clock::ID),
+    ];
+
+    for (index, ix) in instructions.iter().enumerate() {
+        if ix.program_id == ed25519_program::ID {
+            if index != quote_index {
+                diagnostics.push(Diagnostic::QuoteNotFirst(index));
+            } else if let Err(err) = verifier_inputs::check_quote_data(&ix.data, index) {
+                diagnostics.push(Diagnostic::MalformedQuote(err));
+            }
+            continue;
+        }
+        if ix.program_id != ID || ix.data.len() < DISCRIMINATOR_LEN {
+            continue;
+        }
+        let discriminator = &ix.data[..DISCRIMINATOR_LEN];
+        let Some(template) = templates
+            .iter()
+            .find(|template| template.instruction.data[..DISCRIMINATOR_LEN] == *discriminator)
+        else {
+            continue;
+        };
+        let name = template.name;
+
+        if template.quote_required && !quote_first {
+            diagnostics.push(Diagnostic::QuoteMissing { index, name });
+        }
+        if ix.data.len() != template.instruction.data.len() {
+            diagnostics.push(Diagnostic::DataLength {
+                index,
+                name,
+                expected: template.instruction.data.len(),
+                found: ix.data.len(),
+            });
+        }
+        if ix.accounts.len() < template.instruction.accounts.len() {
+            diagnostics.push(Diagnostic::MissingAccounts {
+                index,
+                name,
+                expected: template.instruction.accounts.len(),
+                found: ix.accounts.len(),
+            });
+            continue;
+        }
+        for (account, expected) in expected_accounts {
+            let Some(position) = template
+                .instruction
+                .accounts
+                .iter()
+                .position(|meta| meta.pubkey == expected)
+            else {
+                continue;
+            };
+            let found = ix.accounts[position].pubkey;
+            if found != expected {
+                diagnostics.push(Diagnostic::WrongAccount {
+                    index,
+                    name,
+                    account,
+                    position,
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+    diagnostics
+}
+
+/// A quote-reading instruction as the builders make it.
+struct Template {
+    name: &'static str,
+    instruction: Instruction,
+    /// `check_or_verify_score` only reads the quote when the cache is stale.
+    quote_required: bool,
+}
+
+fn templates() -> Vec<Template> {
+    let key = Pubkey::default();
+    let opts = VerifyOptions::default();
+    let template = |name, instruction| Template {
+        name,
+        instruction,
+        quote_required: true,
+    };
+    vec![
+        template(
+            "verify_risk_score_feed",
+            instructions::verify_risk_score_feed(key, &opts),
+        ),
+        template(
+            "verify_risk_score_feed_compact",
+            instructions::verify_risk_score_feed_compact(key, &opts),
+        ),
+        template(
+            "verify_risk_score_feed_with_deadline",
+            instructions::verify_risk_score_feed_with_deadline(key, 0, &opts),
+        ),
+        template(
+            "verify_risk_score_feed_for_ata",
+            instructions::verify_risk_score_feed_for_ata(key, key, key, &opts),
+        ),
+        template(
+            "verify_risk_score_feed_pinned",
+            instructions::verify_risk_score_feed_pinned(key, [0; 32], &opts),
+        ),
+        template(
+            "verify_risk_score_feed_cached",
+            instructions::verify_risk_score_feed_cached(key, &opts),
+        ),
+        template(
+            "verify_composite_risk_score",
+            instructions::verify_composite_risk_score(key, key, 0, false, false),
+        ),
+        template(
+            "refresh_score_caches",
+            instructions::refresh_score_caches(key, &[]),
+        ),
+        template("reconcile", instructions::reconcile(key, &key, 0, false)),
+        template(
+            "verify_risk_score_batch",
+            instructions::verify_risk_score_batch(&[], false, None),
+        ),
+        template(
+            "write_quote_account",
+            instructions::write_quote_account(key, 0),
+        ),
+        Template {
+            name: "check_or_verify_score",
+            instruction: instructions::check_or_verify_score(key, key, 0, 0, &Default::default()),
+            quote_required: false,
+        },
+        template(
+            "verify_confidential_risk_score",
+            instructions::verify_confidential_risk_score(key, key, 0, [0; 32], None),
+        ),
+        template(
+            "verify_threshold_proof",
+            instructions::verify_threshold_proof(key, key, [0; 32]),
+        ),
+    ]
+}
+
+/// Instructions of `message`, their accounts resolved (signer and writable
+/// flags aren't checked, so they are left unset).
+fn decompile(
+    message: &VersionedMessage,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<Vec<Instruction>, Diagnostic> {
+    let mut keys = message.static_account_keys().to_vec();
+    if let Some(lookups) = message.address_table_lookups() {
+        // Loaded addresses follow the static keys: every table's writable
+        // ones, then every table's read-only ones.
+        let mut readonly = Vec::new();
+        for lookup in lookups {
+            let table = lookup_tables
+                .iter()
+                .find(|table| table.key == lookup.account_key)
+                .ok_or(Diagnostic::LookupTable(lookup.account_key))?;
+            let load = |indexes: &[u8]| {
+                indexes
+                    .iter()
+                    .map(|&i| table.addresses.get(i as usize).copied())
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(Diagnostic::LookupTable(lookup.account_key))
+            };
+            keys.extend(load(&lookup.writable_indexes)?);
+            readonly.extend(load(&lookup.readonly_indexes)?);
+        }
+        keys.extend(readonly);
+    }
+
+    let key = |index: u8| keys.get(index as usize).copied().unwrap_or_default();
+    Ok(message
+        .instructions()
+        .iter()
+        .map(|ix| Instruction {
+            program_id: key(ix.program_id_index),
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|&index| AccountMeta::new_readonly(key(index), false))
+                .collect(),
+            data: ix.data.clone(),
+        })
+        .collect())
+}
@@ -0,0 +1,75 @@
+//! Reads through the program's view instructions (`get_cached_score`,
+//! `get_config`, `describe`), simulated rather than sent.
+//!
+//! The instructions write nothing and return their result as return data, so
+//! the values they derive (threshold outcome, SLA) are the program's own. The
+//! runtime drops trailing zero bytes of return data, they are restored before
+//! decoding.
+
+use std::ops::Deref;
+
+use anchor_client::solana_client::client_error::ClientError as RpcError;
+use anchor_client::solana_client::rpc_config::RpcSimulateTransactionConfig;
+use anchor_client::solana_sdk::instruction::Instruction;
+use anchor_client::solana_sdk::signature::Signer;
+use anchor_client::solana_sdk::transaction::Transaction;
+use anchor_client::Program;
+use anchor_lang::AnchorDeserialize;
+use base64::Engine;
+
+use crate::errors::{self, ProgramError};
+
+/// Largest view result, in bytes.
+const MAX_RETURN_DATA: usize = 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ViewError {
+    #[error("simulation request failed: {0}")]
+    Rpc(#[from] RpcError),
+    #[error("view failed: {}", .0.as_ref().map_or("unknown error", |error| error.name.as_str()))]
+    Failed(Option<ProgramError>),
+    #[error("the view returned no data")]
+    NoReturnData,
+    #[error("failed to decode the view result: {0}")]
+    Decode(String),
+}
+
+/// Simulates the view instruction `instruction` and decodes its return data.
+/// The program's payer is the fee payer, it must exist but signs nothing.
+pub(crate) fn simulate<C, S, T>(
+    program: &Program<C>,
+    instruction: Instruction,
+) -> Result<T, ViewError>
+where
+    C: Deref<Target = S> + Clone,
+    S: Signer,
+    T: AnchorDeserialize,
+{
+    let transaction = Transaction::new_with_payer(&[instruction], Some(&program.payer()));
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = program
+        .rpc()
+        .simulate_transaction_with_config(&transaction, config)?
+        .value;
+    if result.err.is_some() {
+        return Err(ViewError::Failed(
+            result.logs.as_deref().and_then(errors::from_logs),
+        ));
+    }
+
+    let return_data = result.return_data.ok_or(ViewError::NoReturnData)?;
+    decode(&return_data.data.0).map_err(ViewError::Decode)
+}
+
+/// Decodes base64 return data, its trailing zero bytes restored.
+pub(crate) fn decode<T: AnchorDeserialize>(base64_data: &str) -> Result<T, String> {
+    let mut data = base64::engine::general_purpose::STANDARD
+        .decode(base64_data)
+        .map_err(|error| error.to_string())?;
+    data.resize(MAX_RETURN_DATA.max(data.len()), 0);
+    T::deserialize(&mut data.as_slice()).map_err(|error| error.to_string())
+}
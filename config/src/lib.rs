@@ -0,0 +1,800 @@
+//! Configuration of the off-chain binaries.
+//!
+//! Every binary reads the same file, each using its own sections. Values are
+//! layered, later layers winning:
+//!
+//! 1. defaults (below)
+//! 2. the TOML file (`--config`, `RISK_ORACLE_CONFIG`, or `risk-oracle.toml`
+//!    in the working directory when it exists)
+//! 3. environment variables ([`ENV_OVERRIDES`])
+//! 4. command line flags, applied by each binary on the loaded [`Config`]
+//!
+//! [`Config::validate`] reports every invalid value at once.
+//!
+//! One keeper process can serve several tenants: each `[tenants.<name>]`
+//! section holds the sections that differ for that tenant (`keeper`, `rpc`,
+//! `alerts`, ...), layered over the shared ones after the environment
+//! overrides, so a tenant's own values win.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use serde::{Deserialize, Serialize};
+
+/// File read when no path is given and it exists.
+pub const DEFAULT_PATH: &str = "risk-oracle.toml";
+
+/// Environment variable naming the config file.
+pub const CONFIG_ENV: &str = "RISK_ORACLE_CONFIG";
+
+/// Environment variables overriding a file value, with the value's path.
+pub const ENV_OVERRIDES: &[(&str, &[&str])] = &[
+    ("RISK_ORACLE_RPC_URL", &["rpc", "url"]),
+    ("RISK_ORACLE_RPC_WS_URL", &["rpc", "ws_url"]),
+    ("RISK_ORACLE_RPC_COMMITMENT", &["rpc", "commitment"]),
+    ("RISK_ORACLE_KEEPER_PAYER", &["keeper", "payer"]),
+    ("RISK_ORACLE_KEEPER_MAX_ATTEMPTS", &["keeper", "max_attempts"]),
+    ("RISK_ORACLE_KEEPER_MAX_PRIORITY_FEE", &["keeper", "priority_fee", "max_price"]),
+    ("RISK_ORACLE_TRANSACTION_FORMAT", &["transactions", "format"]),
+];
+
+/// Highest compute unit limit a transaction can request.
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid config: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("{var}: {message}")]
+    Env { var: &'static str, message: String },
+    #[error("invalid config:\n  {}", .0.join("\n  "))]
+    Invalid(Vec<String>),
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub rpc: RpcConfig,
+    pub keeper: KeeperConfig,
+    pub transactions: TransactionsConfig,
+    pub alerts: AlertsConfig,
+    pub programs: ProgramsConfig,
+    pub gateway: GatewayConfig,
+    /// Keepers served by the same process, by name, each already layered
+    /// over the shared sections. Their state database defaults to
+    /// `keeper-<name>.db`.
+    pub tenants: BTreeMap<String, Config>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcConfig {
+    /// Primary RPC endpoint.
+    pub url: String,
+    /// Websocket endpoint, derived from `url` when unset.
+    pub ws_url: Option<String>,
+    /// Fallback endpoints of the keeper's RPC pool.
+    pub fallback_urls: Vec<String>,
+    /// `processed`, `confirmed` or `finalized`.
+    pub commitment: String,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            url: "https://api.devnet.solana.com".to_string(),
+            ws_url: None,
+            fallback_urls: Vec::new(),
+            commitment: "confirmed".to_string(),
+        }
+    }
+}
+
+impl RpcConfig {
+    pub fn ws_url(&self) -> String {
+        self.ws_url
+            .clone()
+            .unwrap_or_else(|| self.url.replacen("http", "ws", 1))
+    }
+}
+
+/// Program id deployed on each cluster (base58), the cluster being detected
+/// from the RPC endpoint's genesis hash. Unset ones default to the id the
+/// binaries were built with.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProgramsConfig {
+    pub mainnet: Option<String>,
+    pub devnet: Option<String>,
+    pub localnet: Option<String>,
+}
+
+/// Credentials sent with the quote requests.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GatewayConfig {
+    /// Range API keys, used in turn: a key the gateway answers 429 for rests
+    /// for `rate_limit_cooldown_secs` while the next one takes over.
+    pub api_keys: Vec<ApiKeyConfig>,
+    pub rate_limit_cooldown_secs: u64,
+}
+
+impl Default for GatewayConfig {
+    fn default() -> Self {
+        Self {
+            api_keys: Vec::new(),
+            rate_limit_cooldown_secs: 60,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// Label of the key in the usage report, never the key itself.
+    pub name: String,
+    /// Environment variable holding the key, so it stays out of the file.
+    pub key_env: String,
+}
+
+impl ApiKeyConfig {
+    /// The key, `None` when its variable is unset.
+    pub fn key(&self) -> Option<String> {
+        env::var(&self.key_env).ok().filter(|key| !key.is_empty())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeeperConfig {
+    /// Keypair file paying for the refreshes.
+    pub payer: Option<PathBuf>,
+    /// Send attempts per refresh.
+    pub max_attempts: usize,
+    /// Also append refreshed scores to the address histories.
+    pub record_history: bool,
+    /// SQLite database of the keeper's per-address state.
+    pub state_db: PathBuf,
+    /// Consecutive failures after which an address goes to the dead-letter
+    /// queue.
+    pub dead_letter_after: u32,
+    /// Fees a cycle may spend, in lamports. Once the next refresh would go
+    /// over it, the rest of the cycle is deferred to the next run.
+    pub max_cycle_lamports: Option<u64>,
+    pub priority_fee: PriorityFeeConfig,
+    /// Skip refreshes whose off-chain score hasn't moved, off when unset.
+    pub differential: Option<DifferentialConfig>,
+    /// Spread a cycle's refreshes over a window of slots, all at once when
+    /// unset.
+    pub schedule: Option<ScheduleConfig>,
+    /// CSV of the addresses to keep fresh (address, optional network bits),
+    /// reloaded when it changes.
+    pub watchlist: Option<PathBuf>,
+    /// Run `health_check` between cycles, off when unset.
+    pub health_check: Option<HealthCheckConfig>,
+    /// Watch the payer's and the treasury's balances, off when unset.
+    pub balances: Option<BalancesConfig>,
+}
+
+impl Default for KeeperConfig {
+    fn default() -> Self {
+        Self {
+            payer: None,
+            max_attempts: 3,
+            record_history: false,
+            state_db: PathBuf::from("keeper.db"),
+            dead_letter_after: 5,
+            max_cycle_lamports: None,
+            priority_fee: PriorityFeeConfig::default(),
+            differential: None,
+            schedule: None,
+            watchlist: None,
+            health_check: None,
+            balances: None,
+        }
+    }
+}
+
+/// Slot-scheduled refreshes: each address gets a stable slot of the window,
+/// in one of `buckets` equal slices and jittered within it, and is refreshed
+/// once the cycle reaches it.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScheduleConfig {
+    /// Slots a cycle is spread over, ~1 hour by default.
+    pub window_slots: u64,
+    /// Slices of the window the addresses are hashed into.
+    pub buckets: u64,
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            window_slots: 9_000,
+            buckets: 150,
+        }
+    }
+}
+
+/// Health checks: every `interval_secs`, a cycle first verifies a quote of
+/// the test feed with the program's `health_check` and alerts when it fails.
+/// Only profiles with the test feed (devnet) accept it.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthCheckConfig {
+    /// Seconds between two checks, 10 minutes by default.
+    pub interval_secs: u64,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self { interval_secs: 600 }
+    }
+}
+
+/// Balance monitoring: before a cycle, the payer (and the program's treasury
+/// when `min_treasury_lamports` is set) is checked against its minimum. Below
+/// it, an account is topped up by airdrop off mainnet, and raises a
+/// `low_balance` alert on mainnet or when the airdrop fails.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BalancesConfig {
+    /// Lowest payer balance, 0.5 SOL by default.
+    pub min_payer_lamports: u64,
+    pub min_treasury_lamports: Option<u64>,
+    /// Requested per airdrop, 1 SOL by default. 0 only alerts.
+    pub airdrop_lamports: u64,
+}
+
+impl Default for BalancesConfig {
+    fn default() -> Self {
+        Self {
+            min_payer_lamports: 500_000_000,
+            min_treasury_lamports: None,
+            airdrop_lamports: 1_000_000_000,
+        }
+    }
+}
+
+/// Differential cranking: before quoting, the keeper reads the score from the
+/// Range API directly and skips the refresh when it is within `epsilon` of
+/// the cached on-chain score.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DifferentialConfig {
+    /// Smallest change (0–100 scale) worth a quote. 1 refreshes on any change.
+    pub epsilon: u8,
+    /// Refresh anyway once the cache was written this many slots ago, so
+    /// stable scores still get fresh quotes.
+    pub max_skip_slots: Option<u64>,
+    /// Environment variable holding the Range API key.
+    pub key_env: String,
+    /// Replaces the profile's Range risk endpoint, e.g. for the Range mock.
+    pub range_url: Option<String>,
+}
+
+impl Default for DifferentialConfig {
+    fn default() -> Self {
+        Self {
+            epsilon: 5,
+            max_skip_slots: None,
+            key_env: "RANGE_API_KEY".to_string(),
+            range_url: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PriorityFeeConfig {
+    /// Percentile (0–100) of the recent fees to pay.
+    pub percentile: u8,
+    /// Highest price paid, in micro-lamports per compute unit.
+    pub max_price: u64,
+    pub compute_unit_limit: u32,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            percentile: 75,
+            max_price: 100_000,
+            compute_unit_limit: 300_000,
+        }
+    }
+}
+
+/// Alert conditions, each disabled when unset, and where alerts go.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertsConfig {
+    /// Alert when a refreshed score crosses above this score (0–100).
+    pub high_risk_threshold: Option<u8>,
+    /// Alert when an address has not been refreshed successfully for this
+    /// many seconds.
+    pub staleness_sla_secs: Option<u64>,
+    /// Alert when the on-chain freshness SLA (`set_freshness_sla`) of a target
+    /// is breached at the end of a cycle.
+    pub sla_breaches: bool,
+    /// Alert when more than this share (0–1) of a cycle's refreshes fail.
+    pub max_failure_rate: Option<f64>,
+    /// Refreshes a cycle needs before its failure rate is judged.
+    pub min_attempts: usize,
+    pub webhooks: Vec<WebhookConfig>,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            high_risk_threshold: None,
+            staleness_sla_secs: None,
+            sla_breaches: false,
+            max_failure_rate: None,
+            min_attempts: 10,
+            webhooks: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransactionsConfig {
+    /// `v0`, or `legacy` for signers that reject versioned messages.
+    pub format: String,
+    /// Address lookup tables of v0 transactions (base58 addresses).
+    pub lookup_tables: Vec<String>,
+    /// Refuse to send a transaction whose signature and priority fees exceed
+    /// this many lamports (for the keeper, the cap of each address' refresh).
+    pub max_fee_lamports: Option<u64>,
+}
+
+impl Default for TransactionsConfig {
+    fn default() -> Self {
+        Self {
+            format: "v0".to_string(),
+            lookup_tables: Vec::new(),
+            max_fee_lamports: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub kind: WebhookKind,
+    /// PagerDuty integration key.
+    pub routing_key: Option<String>,
+    /// Payload replacing the kind's default, with `{placeholders}` filled
+    /// from the alert (`{kind}`, `{message}`, `{address}`, ...).
+    pub template: Option<String>,
+    /// Alerts below this severity are not posted to this webhook.
+    pub min_severity: Option<Severity>,
+}
+
+/// Severity of an alert. High-risk alerts take the severity band the program
+/// tagged the score with (`risk_oracle_shared::severity`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warn,
+    Critical,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    Slack,
+    PagerDuty,
+    /// Posts the alert fields as JSON.
+    Generic,
+}
+
+impl Config {
+    /// Loads `path`, or the default file when `path` is `None`, then applies
+    /// the environment overrides.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let path = path
+            .map(Path::to_path_buf)
+            .or_else(|| env::var_os(CONFIG_ENV).map(PathBuf::from))
+            .or_else(|| Some(PathBuf::from(DEFAULT_PATH)).filter(|path| path.exists()));
+
+        let mut table = match &path {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|source| ConfigError::Io {
+                    path: path.clone(),
+                    source,
+                })?
+                .parse::<toml::Table>()?,
+            None => toml::Table::new(),
+        };
+        for (var, key) in ENV_OVERRIDES {
+            if let Ok(value) = env::var(var) {
+                set(&mut table, key, parse_value(&value));
+            }
+        }
+        layer_tenants(&mut table);
+        Ok(table.try_into()?)
+    }
+
+    /// Checks every value, reporting all problems at once.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+
+        for url in std::iter::once(&self.rpc.url).chain(&self.rpc.fallback_urls) {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                problems.push(format!("rpc url `{url}` is not an http(s) url"));
+            }
+        }
+        let ws_url = self.rpc.ws_url();
+        if !ws_url.starts_with("ws://") && !ws_url.starts_with("wss://") {
+            problems.push(format!("rpc.ws_url `{ws_url}` is not a ws(s) url"));
+        }
+        if !matches!(self.rpc.commitment.as_str(), "processed" | "confirmed" | "finalized") {
+            problems.push(format!("rpc.commitment `{}` is unknown", self.rpc.commitment));
+        }
+
+        if let Some(payer) = &self.keeper.payer {
+            if !payer.is_file() {
+                problems.push(format!("keeper.payer `{}` does not exist", payer.display()));
+            }
+        }
+        if let Some(watchlist) = &self.keeper.watchlist {
+            if !watchlist.is_file() {
+                problems.push(format!(
+                    "keeper.watchlist `{}` does not exist",
+                    watchlist.display()
+                ));
+            }
+        }
+        if self.keeper.dead_letter_after == 0 {
+            problems.push("keeper.dead_letter_after must be at least 1".to_string());
+        }
+        if self.keeper.max_attempts == 0 {
+            problems.push("keeper.max_attempts must be at least 1".to_string());
+        }
+        let fee = &self.keeper.priority_fee;
+        if fee.percentile > 100 {
+            problems.push("keeper.priority_fee.percentile must be 0–100".to_string());
+        }
+        if fee.compute_unit_limit == 0 || fee.compute_unit_limit > MAX_COMPUTE_UNIT_LIMIT {
+            problems.push(format!(
+                "keeper.priority_fee.compute_unit_limit must be 1–{MAX_COMPUTE_UNIT_LIMIT}"
+            ));
+        }
+
+        if let Some(differential) = &self.keeper.differential {
+            if differential.epsilon == 0 || differential.epsilon > 100 {
+                problems.push("keeper.differential.epsilon must be 1–100".to_string());
+            }
+        }
+        if let Some(schedule) = &self.keeper.schedule {
+            if schedule.buckets == 0 || schedule.buckets > schedule.window_slots {
+                problems.push(
+                    "keeper.schedule.buckets must be 1 to keeper.schedule.window_slots".to_string(),
+                );
+            }
+        }
+        if self.keeper.health_check.is_some_and(|check| check.interval_secs == 0) {
+            problems.push("keeper.health_check.interval_secs must be at least 1".to_string());
+        }
+
+        let transactions = &self.transactions;
+        if !matches!(transactions.format.as_str(), "v0" | "legacy") {
+            problems.push(format!("transactions.format `{}` is unknown", transactions.format));
+        }
+        for table in &transactions.lookup_tables {
+            if !is_base58_address(table) {
+                problems.push(format!("lookup table `{table}` is not a base58 address"));
+            }
+        }
+        if transactions.format == "legacy" && !transactions.lookup_tables.is_empty() {
+            problems.push("transactions.lookup_tables need the v0 format".to_string());
+        }
+
+        let programs = &self.programs;
+        for (cluster, id) in [
+            ("mainnet", &programs.mainnet),
+            ("devnet", &programs.devnet),
+            ("localnet", &programs.localnet),
+        ] {
+            if id.as_deref().is_some_and(|id| !is_base58_address(id)) {
+                problems.push(format!("programs.{cluster} is not a base58 address"));
+            }
+        }
+
+        let mut key_names = Vec::new();
+        for key in &self.gateway.api_keys {
+            if key_names.contains(&&key.name) {
+                problems.push(format!("gateway api key `{}` is declared twice", key.name));
+            }
+            key_names.push(&key.name);
+            if key.key().is_none() {
+                problems.push(format!(
+                    "gateway api key `{}`: ${} is not set",
+                    key.name, key.key_env
+                ));
+            }
+        }
+        if self.gateway.rate_limit_cooldown_secs == 0 {
+            problems.push("gateway.rate_limit_cooldown_secs must be at least 1".to_string());
+        }
+
+        let alerts = &self.alerts;
+        if alerts.high_risk_threshold.is_some_and(|threshold| threshold > 100) {
+            problems.push("alerts.high_risk_threshold must be 0–100".to_string());
+        }
+        if alerts.max_failure_rate.is_some_and(|rate| !(0.0..=1.0).contains(&rate)) {
+            problems.push("alerts.max_failure_rate must be 0–1".to_string());
+        }
+        for webhook in &alerts.webhooks {
+            if !webhook.url.starts_with("https://") && !webhook.url.starts_with("http://") {
+                problems.push(format!("webhook url `{}` is not an http(s) url", webhook.url));
+            }
+            if webhook.kind == WebhookKind::PagerDuty && webhook.routing_key.is_none() {
+                problems.push(format!("PagerDuty webhook `{}` needs a routing_key", webhook.url));
+            }
+        }
+
+        let mut claimed = BTreeMap::new();
+        for (name, tenant) in &self.tenants {
+            let valid_name = !name.is_empty()
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid_name {
+                problems.push(format!("tenant name `{name}` must be letters, digits, `-` or `_`"));
+            }
+            if !tenant.tenants.is_empty() {
+                problems.push(format!("tenants.{name} cannot declare tenants"));
+            }
+            if tenant.keeper.payer.is_none() {
+                problems.push(format!("tenants.{name}.keeper.payer is not set"));
+            }
+            if let Err(ConfigError::Invalid(invalid)) = tenant.validate() {
+                let prefixed = invalid.into_iter();
+                problems.extend(prefixed.map(|problem| format!("tenants.{name}: {problem}")));
+            }
+            // Signers and state are never shared between tenants.
+            let paths = [
+                ("payer", tenant.keeper.payer.as_ref()),
+                ("state_db", Some(&tenant.keeper.state_db)),
+            ];
+            for (field, path) in paths {
+                let Some(path) = path else { continue };
+                if let Some(other) = claimed.insert((field, path), name) {
+                    problems.push(format!(
+                        "tenants {other} and {name} share keeper.{field} `{}`",
+                        path.display()
+                    ));
+                }
+            }
+        }
+
+        match problems.is_empty() {
+            true => Ok(()),
+            false => Err(ConfigError::Invalid(problems)),
+        }
+    }
+
+    /// Effective configuration, as TOML.
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).expect("config serializes to TOML")
+    }
+}
+
+/// Replaces each tenant table with the rest of the file overlaid by it.
+fn layer_tenants(table: &mut toml::Table) {
+    let tenants = match table.remove("tenants") {
+        Some(toml::Value::Table(tenants)) => tenants,
+        // Left for deserialization to reject.
+        Some(other) => {
+            table.insert("tenants".to_string(), other);
+            return;
+        }
+        None => return,
+    };
+    let layered = tenants
+        .into_iter()
+        .map(|(name, tenant)| {
+            let toml::Value::Table(tenant) = tenant else {
+                return (name, tenant);
+            };
+            let mut base = table.clone();
+            let state_db = toml::Value::String(format!("keeper-{name}.db"));
+            set(&mut base, &["keeper", "state_db"], state_db);
+            overlay(&mut base, tenant);
+            (name, toml::Value::Table(base))
+        })
+        .collect();
+    table.insert("tenants".to_string(), toml::Value::Table(layered));
+}
+
+/// Sets every value of `top` in `base`, merging tables and replacing the
+/// rest (arrays included).
+fn overlay(base: &mut toml::Table, top: toml::Table) {
+    for (key, value) in top {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(top)) => overlay(base, top),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Shape check only, the binaries decode the address.
+fn is_base58_address(value: &str) -> bool {
+    (32..=44).contains(&value.len())
+        && value.chars().all(|c| c.is_ascii_alphanumeric() && !"0OIl".contains(c))
+}
+
+/// Environment values are TOML values (`3`, `true`, ...), bare strings
+/// otherwise.
+fn parse_value(value: &str) -> toml::Value {
+    format!("v = {value}")
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|mut table| table.remove("v"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()))
+}
+
+fn set(table: &mut toml::Table, key: &[&str], value: toml::Value) {
+    let (last, sections) = key.split_last().expect("non-empty key");
+    let mut table = table;
+    for section in sections {
+        let entry = table
+            .entry(section.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        table = entry.as_table_mut().expect("just made a table");
+    }
+    table.insert(last.to_string(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Tests setting variables run one at a time, `Config::load` reads them
+    /// all.
+    static ENV: Mutex<()> = Mutex::new(());
+
+    /// Writes `contents` to a config file of this test run.
+    fn write(name: &str, contents: &str) -> PathBuf {
+        let file = format!("risk-oracle-{}-{name}.toml", std::process::id());
+        let path = env::temp_dir().join(file);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    /// Loads `file` with `vars` set, as a binary would before its flags.
+    fn load(name: &str, file: &str, vars: &[(&str, &str)]) -> Config {
+        let _env = ENV.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let path = write(name, file);
+        for (var, value) in vars {
+            env::set_var(var, value);
+        }
+        let config = Config::load(Some(&path));
+        for (var, _) in vars {
+            env::remove_var(var);
+        }
+        fs::remove_file(&path).unwrap();
+        config.unwrap()
+    }
+
+    const FILE: &str = r#"
+        [rpc]
+        url = "https://file.example"
+        commitment = "finalized"
+
+        [keeper]
+        max_attempts = 2
+    "#;
+
+    #[test]
+    fn file_over_defaults() {
+        let config = load("file", FILE, &[]);
+        assert_eq!(config.rpc.url, "https://file.example");
+        assert_eq!(config.rpc.commitment, "finalized");
+        assert_eq!(config.keeper.max_attempts, 2);
+        // Unset values keep their defaults.
+        assert_eq!(config.keeper.dead_letter_after, 5);
+        assert_eq!(config.rpc.ws_url(), "wss://file.example");
+        assert_eq!(config.transactions.format, "v0");
+    }
+
+    #[test]
+    fn env_over_file_and_flags_over_env() {
+        let mut config = load(
+            "env",
+            FILE,
+            &[
+                ("RISK_ORACLE_RPC_URL", "https://env.example"),
+                ("RISK_ORACLE_KEEPER_MAX_ATTEMPTS", "7"),
+                ("RISK_ORACLE_KEEPER_MAX_PRIORITY_FEE", "5000"),
+            ],
+        );
+        assert_eq!(config.rpc.url, "https://env.example");
+        assert_eq!(config.keeper.max_attempts, 7);
+        // A section the file doesn't have is created.
+        assert_eq!(config.keeper.priority_fee.max_price, 5_000);
+        // Values without a variable keep the file's.
+        assert_eq!(config.rpc.commitment, "finalized");
+
+        // Flags are set by the binaries on the loaded config, last.
+        config.rpc.url = "https://flag.example".to_string();
+        assert_eq!(config.rpc.url, "https://flag.example");
+        assert_eq!(config.rpc.ws_url(), "wss://flag.example");
+    }
+
+    #[test]
+    fn tenants_over_shared_sections() {
+        let file = r#"
+            [rpc]
+            url = "https://file.example"
+            fallback_urls = ["https://a.example", "https://b.example"]
+
+            [keeper]
+            max_attempts = 2
+
+            [tenants.alpha.rpc]
+            url = "https://alpha.example"
+            fallback_urls = []
+
+            [tenants.beta.keeper]
+            state_db = "beta.db"
+        "#;
+        let config = load(
+            "tenants",
+            file,
+            &[("RISK_ORACLE_RPC_COMMITMENT", "processed")],
+        );
+        let alpha = &config.tenants["alpha"];
+        let beta = &config.tenants["beta"];
+        // The tenant's values win, arrays replaced rather than merged.
+        assert_eq!(alpha.rpc.url, "https://alpha.example");
+        assert!(alpha.rpc.fallback_urls.is_empty());
+        assert_eq!(beta.rpc.url, "https://file.example");
+        assert_eq!(beta.rpc.fallback_urls.len(), 2);
+        // Shared sections, environment included, fill in the rest.
+        for tenant in [alpha, beta] {
+            assert_eq!(tenant.rpc.commitment, "processed");
+            assert_eq!(tenant.keeper.max_attempts, 2);
+            assert!(tenant.tenants.is_empty());
+        }
+        assert_eq!(alpha.keeper.state_db, PathBuf::from("keeper-alpha.db"));
+        assert_eq!(beta.keeper.state_db, PathBuf::from("beta.db"));
+        assert_eq!(config.keeper.state_db, PathBuf::from("keeper.db"));
+    }
+
+    #[test]
+    fn env_values_are_toml_or_strings() {
+        assert_eq!(parse_value("3"), toml::Value::Integer(3));
+        assert_eq!(parse_value("true"), toml::Value::Boolean(true));
+        assert_eq!(parse_value("\"7\""), toml::Value::String("7".to_string()));
+        assert_eq!(
+            parse_value("https://env.example"),
+            toml::Value::String("https://env.example".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_keys_and_missing_files_are_errors() {
+        let path = write("unknown", "[rpc]\nurll = \"https://file.example\"\n");
+        let config = Config::load(Some(&path));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(config, Err(ConfigError::Parse(_))));
+        let missing = Config::load(Some(&path));
+        assert!(matches!(missing, Err(ConfigError::Io { .. })));
+    }
+}
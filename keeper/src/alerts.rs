@@ -0,0 +1,320 @@
+//! Webhook alerts (Slack, PagerDuty, or any endpoint taking JSON).
+//!
+//! The conditions come from the `[alerts]` config section; each webhook gets
+//! its kind's default payload, or its `template` with `{placeholders}`
+//! replaced by the alert fields (JSON-escaped, so templates stay valid JSON).
+//! A tenant's alerts carry its name: a `[name]` message prefix and the
+//! `{tenant}` field. Every alert has a [`Severity`], the band the program
+//! tagged the score with for high-risk alerts; a webhook's `min_severity`
+//! drops the alerts below it. Webhooks are posted through an
+//! [`HttpTransport`].
+
+use std::sync::Arc;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use risk_oracle_client::FreshnessSla;
+use risk_oracle_config::{AlertsConfig, Severity, WebhookConfig, WebhookKind};
+use serde_json::{json, Value};
+
+use crate::http::{HttpError, HttpTransport};
+
+#[cfg(feature = "reqwest")]
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[derive(Clone, Debug)]
+pub enum Alert {
+    /// A refreshed score went above the high-risk threshold.
+    HighRisk {
+        address: Pubkey,
+        score: u8,
+        previous: Option<u8>,
+        threshold: u8,
+        /// Band the program tagged `score` with.
+        severity: Severity,
+    },
+    /// No successful refresh within the staleness SLA.
+    StaleCache { address: Pubkey, age_secs: u64 },
+    /// The on-chain freshness SLA of a query account is breached.
+    SlaBreach {
+        query_account: Pubkey,
+        last_refresh_slot: u64,
+        max_age_slots: u64,
+        slot: u64,
+    },
+    /// Share of failed refreshes in a cycle above the configured rate.
+    FailureRate { failed: usize, attempted: usize },
+    /// The program's `health_check` failed: the verification pipeline is
+    /// broken.
+    HealthCheckFailed { error: String },
+    /// A watched account is below its minimum balance and wasn't topped up.
+    LowBalance {
+        /// `payer` or `treasury`.
+        account: &'static str,
+        address: Pubkey,
+        lamports: u64,
+        min_lamports: u64,
+    },
+}
+
+impl Alert {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Alert::HighRisk { .. } => "high_risk",
+            Alert::StaleCache { .. } => "stale_cache",
+            Alert::SlaBreach { .. } => "sla_breach",
+            Alert::FailureRate { .. } => "failure_rate",
+            Alert::HealthCheckFailed { .. } => "health_check_failed",
+            Alert::LowBalance { .. } => "low_balance",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Alert::HighRisk {
+                address,
+                score,
+                previous,
+                threshold,
+                ..
+            } => format!(
+                "{address} crossed the high-risk threshold {threshold}: score {score} (was {})",
+                previous.map_or("unknown".to_string(), |score| score.to_string())
+            ),
+            Alert::StaleCache { address, age_secs } => {
+                format!("{address} has not been refreshed for {age_secs}s")
+            }
+            Alert::SlaBreach {
+                query_account,
+                last_refresh_slot,
+                max_age_slots,
+                slot,
+            } => format!(
+                "{query_account} is outside its freshness SLA of {max_age_slots} slots: \
+                 last refresh at slot {last_refresh_slot}, now {slot}"
+            ),
+            Alert::FailureRate { failed, attempted } => {
+                format!("{failed} of {attempted} refreshes failed this cycle")
+            }
+            Alert::HealthCheckFailed { error } => {
+                format!("the verification pipeline health check failed: {error}")
+            }
+            Alert::LowBalance {
+                account,
+                address,
+                lamports,
+                min_lamports,
+            } => format!(
+                "the {account} {address} holds {lamports} lamports, below its minimum of \
+                 {min_lamports}"
+            ),
+        }
+    }
+
+    /// Template placeholders.
+    pub fn fields(&self) -> Value {
+        let mut fields = match self {
+            Alert::HighRisk {
+                address,
+                score,
+                previous,
+                threshold,
+                ..
+            } => json!({
+                "address": address.to_string(),
+                "score": score,
+                "previous": previous,
+                "threshold": threshold,
+            }),
+            Alert::StaleCache { address, age_secs } => json!({
+                "address": address.to_string(),
+                "age_secs": age_secs,
+            }),
+            Alert::SlaBreach {
+                query_account,
+                last_refresh_slot,
+                max_age_slots,
+                slot,
+            } => json!({
+                "address": query_account.to_string(),
+                "last_refresh_slot": last_refresh_slot,
+                "max_age_slots": max_age_slots,
+                "slot": slot,
+            }),
+            Alert::FailureRate { failed, attempted } => json!({
+                "failed": failed,
+                "attempted": attempted,
+            }),
+            Alert::HealthCheckFailed { error } => json!({ "error": error }),
+            Alert::LowBalance {
+                account,
+                address,
+                lamports,
+                min_lamports,
+            } => json!({
+                "account": account,
+                "address": address.to_string(),
+                "lamports": lamports,
+                "min_lamports": min_lamports,
+            }),
+        };
+        fields["kind"] = self.kind().into();
+        fields["severity"] = json!(self.severity());
+        fields["message"] = self.message().into();
+        fields
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Alert::HighRisk { severity, .. } => *severity,
+            Alert::FailureRate { .. }
+            | Alert::SlaBreach { .. }
+            | Alert::HealthCheckFailed { .. }
+            | Alert::LowBalance { .. } => Severity::Critical,
+            Alert::StaleCache { .. } => Severity::Warn,
+        }
+    }
+}
+
+/// Alert conditions and the webhooks alerts are posted to.
+pub struct Alerter {
+    pub config: AlertsConfig,
+    /// Name of the tenant raising the alerts, if any.
+    pub tenant: Option<String>,
+    http: Arc<dyn HttpTransport>,
+}
+
+impl Alerter {
+    /// Alerter posting with reqwest.
+    #[cfg(feature = "reqwest")]
+    pub fn new(config: AlertsConfig) -> Self {
+        let http = crate::http::ReqwestTransport::with_timeout(TIMEOUT);
+        Self::with_transport(config, Arc::new(http))
+    }
+
+    /// Alerter posting through `http`.
+    pub fn with_transport(config: AlertsConfig, http: Arc<dyn HttpTransport>) -> Self {
+        Self {
+            config,
+            tenant: None,
+            http,
+        }
+    }
+
+    /// Alert for a refreshed score, when it crossed the threshold.
+    pub fn high_risk(
+        &self,
+        address: Pubkey,
+        previous: Option<u8>,
+        score: u8,
+        severity: Severity,
+    ) -> Option<Alert> {
+        let threshold = self.config.high_risk_threshold?;
+        let crossed = score > threshold && previous.map_or(true, |previous| previous <= threshold);
+        crossed.then_some(Alert::HighRisk {
+            address,
+            score,
+            previous,
+            threshold,
+            severity,
+        })
+    }
+
+    /// Alert for an on-chain freshness SLA, when it is breached at `slot`.
+    pub fn sla_breach(&self, sla: &FreshnessSla, slot: u64) -> Option<Alert> {
+        let breached = self.config.sla_breaches && !sla.is_within_at(slot);
+        breached.then_some(Alert::SlaBreach {
+            query_account: sla.query_account,
+            last_refresh_slot: sla.last_refresh_slot,
+            max_age_slots: sla.max_age_slots,
+            slot,
+        })
+    }
+
+    /// Alert for a cycle's outcome, when too many refreshes failed.
+    pub fn failure_rate(&self, failed: usize, attempted: usize) -> Option<Alert> {
+        let max_rate = self.config.max_failure_rate?;
+        let spiking = attempted >= self.config.min_attempts
+            && failed as f64 > max_rate * attempted as f64;
+        spiking.then_some(Alert::FailureRate { failed, attempted })
+    }
+
+    /// Posts `alert` to every webhook taking its severity. Returns how many
+    /// deliveries failed; alerting problems never stop the keeper.
+    pub fn send(&self, alert: &Alert) -> usize {
+        self.config
+            .webhooks
+            .iter()
+            .filter(|webhook| webhook.min_severity.map_or(true, |min| alert.severity() >= min))
+            .filter(|webhook| self.post(webhook, alert).is_err())
+            .count()
+    }
+
+    fn post(&self, webhook: &WebhookConfig, alert: &Alert) -> Result<(), HttpError> {
+        let body = match &webhook.template {
+            Some(template) => render(template, &self.fields(alert)),
+            None => self.payload(webhook, alert).to_string(),
+        };
+        let response = self.http.post_json(&webhook.url, body.into_bytes())?;
+        match response.is_success() {
+            true => Ok(()),
+            false => Err(HttpError(format!("{} answered {}", webhook.url, response.status))),
+        }
+    }
+
+    fn payload(&self, webhook: &WebhookConfig, alert: &Alert) -> Value {
+        match webhook.kind {
+            WebhookKind::Slack => json!({ "text": self.fields(alert)["message"] }),
+            WebhookKind::PagerDuty => json!({
+                "routing_key": webhook.routing_key,
+                "event_action": "trigger",
+                "payload": {
+                    "summary": self.fields(alert)["message"],
+                    "source": self.tenant.as_ref().map_or(
+                        "risk-oracle-keeper".to_string(),
+                        |tenant| format!("risk-oracle-keeper/{tenant}"),
+                    ),
+                    "severity": pagerduty_severity(alert.severity()),
+                    "custom_details": self.fields(alert),
+                },
+            }),
+            WebhookKind::Generic => self.fields(alert),
+        }
+    }
+
+    /// The alert's fields, labeled with the tenant.
+    fn fields(&self, alert: &Alert) -> Value {
+        let mut fields = alert.fields();
+        if let Some(tenant) = &self.tenant {
+            fields["message"] = format!("[{tenant}] {}", alert.message()).into();
+            fields["tenant"] = tenant.as_str().into();
+        }
+        fields
+    }
+}
+
+/// PagerDuty Events v2 severity.
+fn pagerduty_severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "info",
+        Severity::Warn => "warning",
+        Severity::Critical => "critical",
+    }
+}
+
+/// Replaces `{name}` with the JSON-escaped value of field `name`.
+fn render(template: &str, fields: &Value) -> String {
+    let mut out = template.to_string();
+    if let Value::Object(fields) = fields {
+        for (name, value) in fields {
+            let value = match value {
+                Value::String(text) => {
+                    let quoted = Value::String(text.clone()).to_string();
+                    quoted[1..quoted.len() - 1].to_string()
+                }
+                other => other.to_string(),
+            };
+            out = out.replace(&format!("{{{name}}}"), &value);
+        }
+    }
+    out
+}
@@ -0,0 +1,100 @@
+//! Balance monitoring of the fee payer and the treasury.
+//!
+//! Running out of SOL is the keeper's most common failure: refreshes need
+//! fees and new caches need rent. Before a cycle, [`BalanceMonitor::check`]
+//! reads the payer's balance and, when watched, the treasury's named in the
+//! program config. An account below its minimum is topped up by airdrop on
+//! clusters with a faucet (devnet, testnet, a local validator); on mainnet,
+//! or when the faucet refuses, the cycle raises a `low_balance` alert, once
+//! until the balance is back above the minimum.
+
+use anchor_client::anchor_lang::AccountDeserialize;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use risk_oracle_client::cluster::ClusterKind;
+use risk_oracle_client::{pda, Config};
+use risk_oracle_config::BalancesConfig;
+
+use crate::rpc::RpcPool;
+use crate::KeeperError;
+
+#[derive(Clone, Copy, Debug)]
+pub struct BalanceMonitor {
+    pub min_payer_lamports: u64,
+    /// Watches the treasury too, when set.
+    pub min_treasury_lamports: Option<u64>,
+    /// Requested per top-up, none where there is no faucet.
+    pub airdrop_lamports: Option<u64>,
+}
+
+/// Balance of a watched account after its top-up, if any.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Balance {
+    /// `payer` or `treasury`.
+    pub account: &'static str,
+    pub address: Pubkey,
+    pub lamports: u64,
+    pub min_lamports: u64,
+    /// Airdropped by this check.
+    pub topped_up: u64,
+}
+
+impl Balance {
+    pub fn is_low(&self) -> bool {
+        self.lamports < self.min_lamports
+    }
+}
+
+impl BalanceMonitor {
+    /// Monitor of `config` on `cluster`, airdropping everywhere but mainnet.
+    pub fn new(config: &BalancesConfig, cluster: ClusterKind) -> Self {
+        let faucet = cluster != ClusterKind::Mainnet && config.airdrop_lamports > 0;
+        Self {
+            min_payer_lamports: config.min_payer_lamports,
+            min_treasury_lamports: config.min_treasury_lamports,
+            airdrop_lamports: faucet.then_some(config.airdrop_lamports),
+        }
+    }
+
+    /// Airdrop requested for an account holding `lamports` with a minimum of
+    /// `min_lamports`, if any.
+    pub fn top_up(&self, lamports: u64, min_lamports: u64) -> Option<u64> {
+        self.airdrop_lamports.filter(|_| lamports < min_lamports)
+    }
+
+    /// Reads the watched accounts and tops up those below their minimum. A
+    /// failed airdrop leaves the account low rather than failing the check;
+    /// the treasury is skipped while the config can't be read.
+    pub fn check(&self, pool: &mut RpcPool, payer: &Pubkey) -> Result<Vec<Balance>, KeeperError> {
+        let mut watched = vec![("payer", *payer, self.min_payer_lamports)];
+        if let Some(min_lamports) = self.min_treasury_lamports {
+            let config = pool.accounts(&[pda::config().0])?.pop().flatten();
+            let config = config
+                .and_then(|account| Config::try_deserialize(&mut account.data.as_slice()).ok());
+            if let Some(config) = config {
+                watched.push(("treasury", config.treasury, min_lamports));
+            }
+        }
+
+        let addresses: Vec<Pubkey> = watched.iter().map(|(_, address, _)| *address).collect();
+        let accounts = pool.accounts(&addresses)?;
+        let mut balances = Vec::with_capacity(watched.len());
+        for ((account, address, min_lamports), found) in watched.into_iter().zip(accounts) {
+            let mut lamports = found.map_or(0, |found| found.lamports);
+            let mut topped_up = 0;
+            if let Some(airdrop) = self.top_up(lamports, min_lamports) {
+                if pool.airdrop(&address, airdrop).is_ok() {
+                    lamports += airdrop;
+                    topped_up = airdrop;
+                }
+            }
+            balances.push(Balance {
+                account,
+                address,
+                lamports,
+                min_lamports,
+                topped_up,
+            });
+        }
+        Ok(balances)
+    }
+}
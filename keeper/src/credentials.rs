@@ -0,0 +1,204 @@
+//! Range API keys sent with the quote requests, rotated on rate limits.
+//!
+//! Keys are used in turn. When the gateway answers 429 for a key, the key
+//! rests for the configured cooldown and the request is retried at once with
+//! the next one, so one exhausted quota doesn't stall a cycle. Requests and
+//! rate limits are counted per key; the cycle hands the counts to the
+//! [`Store`](crate::store::Store) for `risk-oracle-cli keys`.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use risk_oracle_config::GatewayConfig;
+
+/// Requests made with a key since the counts were last taken.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyUsage {
+    /// The key's configured name.
+    pub name: String,
+    pub requests: u64,
+    /// Requests answered with a 429.
+    pub rate_limited: u64,
+}
+
+struct Key {
+    value: String,
+    usage: KeyUsage,
+    resting_until: Option<Instant>,
+}
+
+pub struct ApiKeys {
+    keys: Mutex<Keys>,
+    cooldown: Duration,
+}
+
+struct Keys {
+    keys: Vec<Key>,
+    /// Key tried first by the next request.
+    next: usize,
+}
+
+/// A key lent to one request.
+#[derive(Clone, Debug)]
+pub struct ApiKey {
+    index: usize,
+    pub value: String,
+}
+
+impl ApiKeys {
+    /// `(name, key)` pairs, rotated after `cooldown`.
+    pub fn new(keys: impl IntoIterator<Item = (String, String)>, cooldown: Duration) -> Self {
+        let keys = keys
+            .into_iter()
+            .map(|(name, value)| Key {
+                value,
+                usage: KeyUsage {
+                    name,
+                    ..KeyUsage::default()
+                },
+                resting_until: None,
+            })
+            .collect();
+        Self {
+            keys: Mutex::new(Keys { keys, next: 0 }),
+            cooldown,
+        }
+    }
+
+    /// The `[gateway]` keys whose variable is set.
+    pub fn from_config(config: &GatewayConfig) -> Self {
+        let keys = config
+            .api_keys
+            .iter()
+            .filter_map(|key| Some((key.name.clone(), key.key()?)));
+        Self::new(keys, Duration::from_secs(config.rate_limit_cooldown_secs))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().keys.is_empty()
+    }
+
+    /// Next key not resting, counted as a request. `None` when every key
+    /// is resting (or there is none).
+    pub fn take(&self) -> Option<ApiKey> {
+        let now = Instant::now();
+        let mut keys = self.lock();
+        let count = keys.keys.len();
+        let index = (0..count)
+            .map(|offset| (keys.next + offset) % count)
+            .find(|&index| keys.keys[index].resting_until.map_or(true, |until| until <= now))?;
+        keys.next = (index + 1) % count;
+        let key = &mut keys.keys[index];
+        key.resting_until = None;
+        key.usage.requests += 1;
+        Some(ApiKey {
+            index,
+            value: key.value.clone(),
+        })
+    }
+
+    /// Records that `key` was rate limited and rests it.
+    pub fn rate_limited(&self, key: &ApiKey) {
+        let mut keys = self.lock();
+        let key = &mut keys.keys[key.index];
+        key.usage.rate_limited += 1;
+        key.resting_until = Some(Instant::now() + self.cooldown);
+    }
+
+    /// Usage of every key since the last call, the counts reset.
+    pub fn take_usage(&self) -> Vec<KeyUsage> {
+        self.lock()
+            .keys
+            .iter_mut()
+            .map(|key| {
+                let name = key.usage.name.clone();
+                std::mem::replace(&mut key.usage, KeyUsage { name, ..KeyUsage::default() })
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Keys> {
+        self.keys.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(cooldown: Duration) -> ApiKeys {
+        let keys = ["a", "b", "c"].map(|name| (name.to_string(), format!("key-{name}")));
+        ApiKeys::new(keys, cooldown)
+    }
+
+    fn take(keys: &ApiKeys) -> Option<String> {
+        keys.take().map(|key| key.value)
+    }
+
+    #[test]
+    fn keys_are_used_in_turn() {
+        let keys = keys(Duration::from_secs(60));
+        let taken: Vec<_> = (0..4).map(|_| take(&keys).unwrap()).collect();
+        assert_eq!(taken, ["key-a", "key-b", "key-c", "key-a"]);
+    }
+
+    #[test]
+    fn rate_limited_keys_fall_back_to_the_next() {
+        let keys = keys(Duration::from_secs(60));
+        let a = keys.take().unwrap();
+        keys.rate_limited(&a);
+        assert_eq!(take(&keys).as_deref(), Some("key-b"));
+        assert_eq!(take(&keys).as_deref(), Some("key-c"));
+        // `a` rests, the rotation skips it.
+        assert_eq!(take(&keys).as_deref(), Some("key-b"));
+
+        for _ in 0..2 {
+            let key = keys.take().unwrap();
+            keys.rate_limited(&key);
+        }
+        // Every key rests.
+        assert!(keys.take().is_none());
+    }
+
+    #[test]
+    fn rested_keys_are_used_again() {
+        let keys = keys(Duration::ZERO);
+        for _ in 0..3 {
+            let key = keys.take().unwrap();
+            keys.rate_limited(&key);
+        }
+        assert_eq!(take(&keys).as_deref(), Some("key-a"));
+    }
+
+    #[test]
+    fn no_keys() {
+        let keys = ApiKeys::new([], Duration::from_secs(60));
+        assert!(keys.is_empty());
+        assert!(keys.take().is_none());
+        assert!(keys.take_usage().is_empty());
+    }
+
+    #[test]
+    fn usage_is_counted_per_key_and_reset() {
+        let keys = keys(Duration::from_secs(60));
+        for _ in 0..4 {
+            let key = keys.take().unwrap();
+            if key.value == "key-a" {
+                keys.rate_limited(&key);
+            }
+        }
+        let usage = |name: &str, requests, rate_limited| KeyUsage {
+            name: name.to_string(),
+            requests,
+            rate_limited,
+        };
+        assert_eq!(
+            keys.take_usage(),
+            [usage("a", 1, 1), usage("b", 2, 0), usage("c", 1, 0)]
+        );
+        assert_eq!(
+            keys.take_usage(),
+            [usage("a", 0, 0), usage("b", 0, 0), usage("c", 0, 0)]
+        );
+    }
+}
@@ -0,0 +1,528 @@
+//! Refresh cycles that survive restarts.
+//!
+//! A cycle walks the targets in address order and records the last finished
+//! one in a checkpoint file after every refresh. On SIGTERM/SIGINT the
+//! in-flight refresh is finished, the checkpoint written, and the cycle
+//! returns; the next run resumes after the checkpointed address instead of
+//! starting over. Resuming by address rather than by index keeps the
+//! position meaningful when the watchlist changed in between.
+//!
+//! Every outcome is recorded in the [`Store`], and addresses still in their
+//! failure backoff are skipped. Alert conditions are checked after each
+//! refresh (high risk) and at the end of the cycle (failure rate, staleness,
+//! on-chain freshness SLAs).
+//!
+//! Fees are capped per refresh (`TransactionOptions::max_fee_lamports`, the
+//! address is skipped this cycle) and per cycle (`Keeper::max_cycle_lamports`):
+//! once the next refresh would go over the cycle's budget, the cycle stops
+//! as if interrupted and the next run resumes there with a fresh budget.
+//!
+//! With [`Keeper::differential`], targets whose off-chain score is within
+//! epsilon of their cache are skipped before any quote is requested.
+//!
+//! With [`Keeper::schedule`], targets are walked in the order of their slot
+//! in the schedule's window instead, each refresh waiting for its slot. The
+//! checkpoint keeps the window's first slot; a run resuming after the window
+//! ended starts a new one where the previous left off.
+//!
+//! With [`Keeper::health_check_secs`], a cycle first runs the program's
+//! `health_check` when the last one is older than that, and alerts when it
+//! fails ([`health`](crate::health)). The targets are refreshed either way.
+//!
+//! With [`Keeper::balances`], a cycle first tops up the payer and the
+//! treasury when they run low, or alerts when it can't
+//! ([`balance`](crate::balance)).
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_client::anchor_lang::AccountDeserialize;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{Keypair, Signer};
+use risk_oracle_client::transaction::{TransactionBuildError, TransactionOptions};
+use risk_oracle_client::{pda, FreshnessSla, ScoreCache};
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::{Alert, Alerter};
+use crate::balance::BalanceMonitor;
+use crate::differential::Differential;
+use crate::fees::PriorityFeeConfig;
+use crate::health;
+use crate::refresh::{refresh_score_cache, refreshed_score, QuoteSource, Target};
+use crate::rpc::RpcPool;
+use crate::schedule::{self, SlotSchedule};
+use crate::store::Store;
+use crate::KeeperError;
+
+/// Flag raised by SIGTERM and SIGINT, checked between refreshes.
+pub fn shutdown_flag() -> io::Result<Arc<AtomicBool>> {
+    let flag = Arc::new(AtomicBool::new(false));
+    for signal in [signal_hook::consts::SIGTERM, signal_hook::consts::SIGINT] {
+        signal_hook::flag::register(signal, Arc::clone(&flag))?;
+    }
+    Ok(flag)
+}
+
+/// Progress of the current cycle.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Checkpoint {
+    /// Query account of the last finished refresh (successful or not).
+    #[serde(with = "optional_pubkey")]
+    pub last_finished: Option<Pubkey>,
+    pub refreshed: usize,
+    pub failed: usize,
+    /// Skipped, still backing off after failures.
+    #[serde(default)]
+    pub backed_off: usize,
+    /// Skipped, in the dead-letter queue.
+    #[serde(default)]
+    pub dead_lettered: usize,
+    /// Skipped, the refresh alone costing more than the per-refresh cap.
+    #[serde(default)]
+    pub over_budget: usize,
+    /// Skipped, the off-chain score within epsilon of the cached one.
+    #[serde(default)]
+    pub unchanged: usize,
+    /// Fees paid since the cycle (or its last deferral) started, in lamports.
+    #[serde(default)]
+    pub spent_lamports: u64,
+    /// First slot of the scheduled window, with a schedule.
+    #[serde(default)]
+    pub window_start: Option<u64>,
+}
+
+/// Checkpoint file, written atomically (temporary file then rename).
+pub struct CheckpointFile {
+    path: PathBuf,
+}
+
+impl CheckpointFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn load(&self) -> Result<Checkpoint, KeeperError> {
+        match fs::read(&self.path) {
+            Ok(data) => serde_json::from_slice(&data).map_err(|err| {
+                KeeperError::Checkpoint(io::Error::new(io::ErrorKind::InvalidData, err))
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Checkpoint::default()),
+            Err(err) => Err(KeeperError::Checkpoint(err)),
+        }
+    }
+
+    pub fn save(&self, checkpoint: &Checkpoint) -> Result<(), KeeperError> {
+        let tmp = self.path.with_extension("tmp");
+        let data = serde_json::to_vec_pretty(checkpoint).expect("checkpoint serializes");
+        fs::write(&tmp, data)
+            .and_then(|()| fs::rename(&tmp, &self.path))
+            .map_err(KeeperError::Checkpoint)
+    }
+
+    /// Removes the checkpoint once a cycle completed.
+    pub fn clear(&self) -> Result<(), KeeperError> {
+        match fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(KeeperError::Checkpoint(err)),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CycleReport {
+    pub refreshed: usize,
+    pub failed: usize,
+    pub backed_off: usize,
+    pub dead_lettered: usize,
+    pub over_budget: usize,
+    pub unchanged: usize,
+    /// Fees paid by the cycle so far, in lamports.
+    pub spent_lamports: u64,
+    /// Stopped at the cycle's fee budget, the rest deferred to the next run.
+    pub deferred: bool,
+    /// Alerts raised during the run.
+    pub alerts: usize,
+    /// Alert deliveries that failed.
+    pub alerts_failed: usize,
+    /// Stopped by a shutdown signal, to be resumed from the checkpoint.
+    pub interrupted: bool,
+    /// Quote requests answered with a 429, retried with another API key.
+    pub rate_limited: u64,
+    /// Outcome of the health check, `None` when none was due.
+    pub health_check: Option<bool>,
+    /// Airdropped to the payer and the treasury before the cycle.
+    pub topped_up_lamports: u64,
+}
+
+/// Everything a refresh cycle needs.
+pub struct Keeper<Q> {
+    pub pool: RpcPool,
+    pub quotes: Q,
+    pub payer: Keypair,
+    pub fees: PriorityFeeConfig,
+    pub transactions: TransactionOptions,
+    pub store: Store,
+    pub alerter: Alerter,
+    /// Fees a cycle may spend, in lamports.
+    pub max_cycle_lamports: Option<u64>,
+    /// Skips refreshes of scores that haven't moved, when set.
+    pub differential: Option<Differential>,
+    /// Spreads the refreshes over a window of slots, when set.
+    pub schedule: Option<SlotSchedule>,
+    /// Seconds between two health checks, none when unset.
+    pub health_check_secs: Option<u64>,
+    /// Watches the payer's and the treasury's balances, when set.
+    pub balances: Option<BalanceMonitor>,
+}
+
+impl<Q: QuoteSource> Keeper<Q> {
+    /// Refreshes every target after the checkpointed one, in address order
+    /// or, with a schedule, in slot order. Failed refreshes are counted and
+    /// skipped; RPC-level failures (every endpoint down) end the cycle with
+    /// an error, the checkpoint kept.
+    pub fn run_cycle(
+        &mut self,
+        targets: &[Target],
+        checkpoint_file: &CheckpointFile,
+        shutdown: &AtomicBool,
+    ) -> Result<CycleReport, KeeperError> {
+        let mut targets: Vec<&Target> = targets.iter().collect();
+        targets.sort_by_key(|target| self.position(&target.query_account));
+
+        let mut checkpoint = checkpoint_file.load()?;
+        let start = checkpoint.last_finished.map_or(0, |last| {
+            let last = self.position(&last);
+            targets.partition_point(|target| self.position(&target.query_account) <= last)
+        });
+        if let Some(schedule) = &self.schedule {
+            let slot = self.pool.slot()?;
+            checkpoint.window_start = match checkpoint.window_start {
+                Some(window_start) if slot < window_start + schedule.window_slots => {
+                    Some(window_start)
+                }
+                // A new window, shifted so the next target is due now.
+                _ => Some(slot.saturating_sub(
+                    targets
+                        .get(start)
+                        .map_or(0, |target| schedule.offset(&target.query_account)),
+                )),
+            };
+        }
+        let mut report = CycleReport::default();
+        self.check_balances(&mut report)?;
+        self.check_health(&mut checkpoint, &mut report)?;
+
+        for target in &targets[start..] {
+            if shutdown.load(Ordering::Relaxed) {
+                report.interrupted = true;
+                self.record_key_usage(&mut report)?;
+                return Ok(report.with(&checkpoint));
+            }
+
+            let mut now = unix_now();
+            if self.store.is_dead_lettered(&target.query_account)? {
+                checkpoint.dead_lettered += 1;
+            } else if self.store.is_backing_off(&target.query_account, now)? {
+                checkpoint.backed_off += 1;
+            } else if !self.wait_for_slot(target, &checkpoint, shutdown)? {
+                report.interrupted = true;
+                self.record_key_usage(&mut report)?;
+                return Ok(report.with(&checkpoint));
+            } else if self.unchanged(target)? {
+                checkpoint.unchanged += 1;
+            } else {
+                now = unix_now();
+                let remaining = self
+                    .max_cycle_lamports
+                    .map(|cap| cap.saturating_sub(checkpoint.spent_lamports));
+                match self.refresh(target, now, remaining) {
+                    Ok((fee, alert)) => {
+                        checkpoint.refreshed += 1;
+                        checkpoint.spent_lamports += fee;
+                        if let Some(alert) = alert {
+                            self.raise(&alert, &mut report);
+                        }
+                    }
+                    Err(KeeperError::Transaction(TransactionBuildError::OverBudget {
+                        fee,
+                        ..
+                    })) => {
+                        // Over its own cap, or a whole cycle's: skipped. Over what
+                        // is left of this cycle's: deferred.
+                        let refresh_cap = self.transactions.max_fee_lamports;
+                        let fresh = checkpoint.spent_lamports == 0;
+                        if fresh || refresh_cap.is_some_and(|cap| fee > cap) {
+                            checkpoint.over_budget += 1;
+                        } else {
+                            report.deferred = true;
+                            self.record_key_usage(&mut report)?;
+                            let report = report.with(&checkpoint);
+                            checkpoint.spent_lamports = 0;
+                            checkpoint.window_start = None;
+                            checkpoint_file.save(&checkpoint)?;
+                            return Ok(report);
+                        }
+                    }
+                    Err(err @ (KeeperError::NoEndpoints | KeeperError::Rpc(_))) => return Err(err),
+                    Err(err) => {
+                        self.store.record_failure(target, &err.to_string(), now)?;
+                        checkpoint.failed += 1;
+                    }
+                }
+            }
+            checkpoint.last_finished = Some(target.query_account);
+            checkpoint_file.save(&checkpoint)?;
+        }
+
+        let attempted = checkpoint.refreshed + checkpoint.failed;
+        if let Some(alert) = self.alerter.failure_rate(checkpoint.failed, attempted) {
+            self.raise(&alert, &mut report);
+        }
+        if let Some(sla_secs) = self.alerter.config.staleness_sla_secs {
+            for (address, age_secs) in self.store.take_stale(unix_now(), sla_secs)? {
+                self.raise(&Alert::StaleCache { address, age_secs }, &mut report);
+            }
+        }
+        if self.alerter.config.sla_breaches {
+            self.check_slas(&targets, &mut report)?;
+        }
+
+        self.record_key_usage(&mut report)?;
+        checkpoint_file.clear()?;
+        Ok(report.with(&checkpoint))
+    }
+
+    /// Persists the quote source's per API key usage since the last call.
+    fn record_key_usage(&mut self, report: &mut CycleReport) -> Result<(), KeeperError> {
+        let usage = self.quotes.take_key_usage();
+        report.rate_limited += usage.iter().map(|key| key.rate_limited).sum::<u64>();
+        self.store.record_key_usage(&usage, unix_now())
+    }
+
+    /// Tops up the watched accounts below their minimum, alerting on those
+    /// still low once per breach.
+    fn check_balances(&mut self, report: &mut CycleReport) -> Result<(), KeeperError> {
+        let Some(monitor) = self.balances else {
+            return Ok(());
+        };
+        for balance in monitor.check(&mut self.pool, &self.payer.pubkey())? {
+            report.topped_up_lamports += balance.topped_up;
+            if self.store.take_low_balance(&balance.address, balance.is_low())? {
+                let alert = Alert::LowBalance {
+                    account: balance.account,
+                    address: balance.address,
+                    lamports: balance.lamports,
+                    min_lamports: balance.min_lamports,
+                };
+                self.raise(&alert, report);
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the health check when one is due, its fee counted in the cycle's
+    /// spending. A failure of any kind is alerted on, not returned.
+    fn check_health(
+        &mut self,
+        checkpoint: &mut Checkpoint,
+        report: &mut CycleReport,
+    ) -> Result<(), KeeperError> {
+        let Some(interval) = self.health_check_secs else {
+            return Ok(());
+        };
+        let now = unix_now();
+        if self
+            .store
+            .last_health_check()?
+            .is_some_and(|last| now < last.saturating_add(interval as i64))
+        {
+            return Ok(());
+        }
+
+        let result = health::health_check(
+            &mut self.pool,
+            &self.quotes,
+            &self.payer,
+            &self.fees,
+            &self.transactions,
+        );
+        match result {
+            Ok((_, fee)) => {
+                checkpoint.spent_lamports += fee;
+                self.store.record_health_check(now, None)?;
+                report.health_check = Some(true);
+            }
+            Err(err) => {
+                let error = err.to_string();
+                self.store.record_health_check(now, Some(&error))?;
+                report.health_check = Some(false);
+                self.raise(&Alert::HealthCheckFailed { error }, report);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sort key of a query account in the cycle: its slot in the window,
+    /// then its address.
+    fn position(&self, query_account: &Pubkey) -> (u64, Pubkey) {
+        let offset = self
+            .schedule
+            .map_or(0, |schedule| schedule.offset(query_account));
+        (offset, *query_account)
+    }
+
+    /// Waits for `target`'s slot in the window, with a schedule. `false`
+    /// when a shutdown came first.
+    fn wait_for_slot(
+        &mut self,
+        target: &Target,
+        checkpoint: &Checkpoint,
+        shutdown: &AtomicBool,
+    ) -> Result<bool, KeeperError> {
+        match (&self.schedule, checkpoint.window_start) {
+            (Some(schedule), Some(window_start)) => {
+                let due = window_start + schedule.offset(&target.query_account);
+                schedule::wait_for_slot(&mut self.pool, due, shutdown)
+            }
+            _ => Ok(true),
+        }
+    }
+
+    /// Whether differential cranking skips `target`: its cache's score is
+    /// within epsilon of the off-chain one.
+    fn unchanged(&mut self, target: &Target) -> Result<bool, KeeperError> {
+        let Some(differential) = &self.differential else {
+            return Ok(false);
+        };
+        let address = pda::score_cache(&target.query_account).0;
+        let account = self.pool.accounts(&[address])?.pop().flatten();
+        let cache = account
+            .and_then(|account| ScoreCache::try_deserialize(&mut account.data.as_slice()).ok());
+        let slot = self.pool.slot()?;
+        Ok(differential.unchanged(target, cache.as_ref(), slot))
+    }
+
+    /// Refreshes `target` within `remaining` lamports (on top of the
+    /// per-refresh cap) and records it, returning the fee paid and the
+    /// high-risk alert it triggers, if any.
+    fn refresh(
+        &mut self,
+        target: &Target,
+        now: i64,
+        remaining: Option<u64>,
+    ) -> Result<(u64, Option<Alert>), KeeperError> {
+        let capped;
+        let options = match remaining {
+            Some(remaining) => {
+                let cap = self.transactions.max_fee_lamports.unwrap_or(u64::MAX);
+                capped = TransactionOptions {
+                    max_fee_lamports: Some(cap.min(remaining)),
+                    ..self.transactions.clone()
+                };
+                &capped
+            }
+            None => &self.transactions,
+        };
+        let (signature, fee) = refresh_score_cache(
+            &mut self.pool,
+            &self.quotes,
+            &self.payer,
+            target,
+            &self.fees,
+            options,
+        )?;
+        let refreshed = refreshed_score(&mut self.pool, &signature);
+        let previous = self.store.last_score(&target.query_account)?;
+        let score = refreshed.map(|(score, _)| score);
+        self.store.record_success(&target.query_account, &signature, score, now)?;
+        let alert = refreshed.and_then(|(score, severity)| {
+            self.alerter.high_risk(target.query_account, previous, score, severity)
+        });
+        Ok((fee, alert))
+    }
+
+    /// Raises an alert for every target with a breached on-chain freshness
+    /// SLA.
+    fn check_slas(
+        &mut self,
+        targets: &[&Target],
+        report: &mut CycleReport,
+    ) -> Result<(), KeeperError> {
+        let addresses: Vec<Pubkey> = targets
+            .iter()
+            .filter(|target| target.freshness_sla)
+            .map(|target| pda::freshness_sla(&target.query_account).0)
+            .collect();
+        if addresses.is_empty() {
+            return Ok(());
+        }
+
+        let slot = self.pool.slot()?;
+        for account in self.pool.accounts(&addresses)?.into_iter().flatten() {
+            let Ok(sla) = FreshnessSla::try_deserialize(&mut account.data.as_slice()) else {
+                continue;
+            };
+            if let Some(alert) = self.alerter.sla_breach(&sla, slot) {
+                self.raise(&alert, report);
+            }
+        }
+        Ok(())
+    }
+
+    fn raise(&self, alert: &Alert, report: &mut CycleReport) {
+        report.alerts += 1;
+        report.alerts_failed += self.alerter.send(alert);
+    }
+}
+
+impl CycleReport {
+    fn with(mut self, checkpoint: &Checkpoint) -> Self {
+        self.refreshed = checkpoint.refreshed;
+        self.failed = checkpoint.failed;
+        self.backed_off = checkpoint.backed_off;
+        self.dead_lettered = checkpoint.dead_lettered;
+        self.over_budget = checkpoint.over_budget;
+        self.unchanged = checkpoint.unchanged;
+        self.spent_lamports = checkpoint.spent_lamports;
+        self
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+mod optional_pubkey {
+    use std::str::FromStr;
+
+    use anchor_client::solana_sdk::pubkey::Pubkey;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        key: &Option<Pubkey>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match key {
+            Some(key) => serializer.serialize_some(&key.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Pubkey>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|key| Pubkey::from_str(&key).map_err(de::Error::custom))
+            .transpose()
+    }
+}
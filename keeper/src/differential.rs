@@ -0,0 +1,105 @@
+//! Differential cranking: refresh a score cache only when the score it would
+//! get differs materially from the one it holds.
+//!
+//! Before quoting a target, the cycle reads its cache and asks an
+//! [`OffchainScores`] source (the Range API itself, [`RangeApi`] with the
+//! `reqwest` feature) for the score the oracle would quote now. Within
+//! `epsilon` of the cached score, the refresh is skipped: no quote, no
+//! transaction. Stable watchlists then cost a Range request per address and
+//! cycle instead of a quote and a transaction.
+//!
+//! A refresh is never skipped on doubt: no cache yet, a cache of other
+//! networks, an off-chain read failing, or a cache older than
+//! `max_skip_slots` all refresh as usual. The off-chain score only decides
+//! whether to pay for a quote; what lands on-chain is always verified.
+//!
+//! [`RangeApi`]: risk_oracle_client::range::RangeApi
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use risk_oracle_client::ScoreCache;
+#[cfg(feature = "reqwest")]
+use risk_oracle_config::DifferentialConfig;
+use risk_oracle_shared::feed::NetworkSet;
+
+use crate::refresh::Target;
+use crate::KeeperError;
+
+#[cfg(feature = "reqwest")]
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Current score of an address, read off the oracle path.
+pub trait OffchainScores {
+    /// Score (0–100) a quote of the composite feed of `networks` for
+    /// `address` would carry now: the riskiest network's.
+    fn offchain_score(&self, address: &Pubkey, networks: NetworkSet) -> Result<u8, KeeperError>;
+}
+
+#[cfg(feature = "reqwest")]
+impl OffchainScores for risk_oracle_client::range::RangeApi {
+    fn offchain_score(&self, address: &Pubkey, networks: NetworkSet) -> Result<u8, KeeperError> {
+        networks.networks().try_fold(0, |score, network| {
+            let response = self
+                .fetch_offchain_score(&address.to_bytes(), network)
+                .map_err(|err| KeeperError::Range(err.to_string()))?;
+            Ok(score.max(response.score()))
+        })
+    }
+}
+
+pub struct Differential {
+    pub scores: Box<dyn OffchainScores>,
+    /// Smallest score change worth a refresh.
+    pub epsilon: u8,
+    /// Age (in slots since the cache was written) from which caches are
+    /// refreshed whatever the score.
+    pub max_skip_slots: Option<u64>,
+}
+
+impl Differential {
+    /// From `[keeper.differential]`, reading Range with the key of its
+    /// `key_env`.
+    #[cfg(feature = "reqwest")]
+    pub fn from_config(config: &DifferentialConfig) -> Result<Self, KeeperError> {
+        use risk_oracle_client::range::RangeApi;
+
+        let key = std::env::var(&config.key_env)
+            .ok()
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| KeeperError::Range(format!("{} is not set", config.key_env)))?;
+        let http = reqwest::blocking::Client::builder()
+            .timeout(TIMEOUT)
+            .build()
+            .map_err(|err| KeeperError::Range(err.to_string()))?;
+        let mut api = RangeApi::with_client(http, key);
+        if let Some(url) = &config.range_url {
+            api = api.with_risk_url(url);
+        }
+        Ok(Self {
+            scores: Box::new(api),
+            epsilon: config.epsilon,
+            max_skip_slots: config.max_skip_slots,
+        })
+    }
+
+    /// Whether `target`'s refresh can be skipped, its cache being `cache`
+    /// at `slot`. Off-chain failures count as a change.
+    pub fn unchanged(&self, target: &Target, cache: Option<&ScoreCache>, slot: u64) -> bool {
+        let Some(cache) = cache else {
+            return false;
+        };
+        if cache.networks != target.networks.bits()
+            || self
+                .max_skip_slots
+                .is_some_and(|max| slot.saturating_sub(cache.updated_slot) >= max)
+        {
+            return false;
+        }
+        match self
+            .scores
+            .offchain_score(&target.screened_address, target.networks)
+        {
+            Ok(score) => score.abs_diff(cache.score) < self.epsilon,
+            Err(_) => false,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Dry run: what a full refresh cycle would cost, without sending anything.
+//!
+//! A sample of the targets is simulated with real quotes to measure compute
+//! units, and the average is extrapolated to the whole cycle. Priority fees
+//! are priced like the refreshes themselves ([`PriorityFeeConfig`]), against
+//! the fees recently paid to write the cache accounts.
+
+use std::fmt;
+
+use anchor_client::solana_sdk::message::Message;
+use anchor_client::solana_sdk::native_token::LAMPORTS_PER_SOL;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::transaction::Transaction;
+use risk_oracle_client::pda;
+
+use crate::fees::PriorityFeeConfig;
+use crate::refresh::{QuoteSource, Target};
+use crate::rpc::RpcPool;
+use crate::KeeperError;
+
+/// Cost of refreshing every target once.
+#[derive(Clone, Debug, Default)]
+pub struct CycleEstimate {
+    /// Caches that would be refreshed.
+    pub targets: usize,
+    /// Watchlist caches that can't be refreshed (see [`Target::from_cache`]).
+    pub skipped: usize,
+    /// Oracle quotes fetched, one per transaction.
+    pub quotes: usize,
+    pub transactions: usize,
+    /// Targets actually simulated.
+    pub sampled: usize,
+    /// Total compute units, extrapolated from the sample.
+    pub compute_units: u64,
+    /// Priority fee price, in micro-lamports per compute unit.
+    pub priority_fee_price: u64,
+    /// Signature fees, in lamports.
+    pub base_fees: u64,
+    /// Priority fees at `priority_fee_price`, charged on the requested
+    /// compute unit limit, in lamports.
+    pub priority_fees: u64,
+}
+
+impl CycleEstimate {
+    pub fn total_lamports(&self) -> u64 {
+        self.base_fees + self.priority_fees
+    }
+}
+
+impl fmt::Display for CycleEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let per_tx = self.compute_units.checked_div(self.transactions as u64).unwrap_or(0);
+        writeln!(f, "Refresh cycle estimate")?;
+        writeln!(f, "  targets        {} ({} skipped)", self.targets, self.skipped)?;
+        writeln!(f, "  quotes         {}", self.quotes)?;
+        writeln!(f, "  transactions   {}", self.transactions)?;
+        writeln!(
+            f,
+            "  compute units  {} (~{} per tx, {} simulated)",
+            self.compute_units, per_tx, self.sampled
+        )?;
+        writeln!(f, "  priority fee   {} micro-lamports/CU", self.priority_fee_price)?;
+        writeln!(f, "  base fees      {} lamports", self.base_fees)?;
+        writeln!(f, "  priority fees  {} lamports", self.priority_fees)?;
+        write!(
+            f,
+            "  total          {} lamports ({:.6} SOL)",
+            self.total_lamports(),
+            self.total_lamports() as f64 / LAMPORTS_PER_SOL as f64
+        )
+    }
+}
+
+/// Estimates a refresh of every target, simulating the first `sample` of
+/// them. `skipped` is carried into the report as is.
+pub fn estimate_refresh_cycle(
+    pool: &mut RpcPool,
+    quotes: &impl QuoteSource,
+    payer: &Pubkey,
+    targets: &[Target],
+    skipped: usize,
+    sample: usize,
+    fees: &PriorityFeeConfig,
+) -> Result<CycleEstimate, KeeperError> {
+    let mut estimate = CycleEstimate {
+        targets: targets.len(),
+        skipped,
+        quotes: targets.len(),
+        transactions: targets.len(),
+        ..CycleEstimate::default()
+    };
+    if targets.is_empty() {
+        return Ok(estimate);
+    }
+
+    let mut sampled_units = 0;
+    let mut base_fee = 0;
+    for target in targets.iter().take(sample.max(1)) {
+        let quote_ix = quotes.quote_instruction(&target.feed(), payer)?;
+        let message = Message::new(&[quote_ix, target.verify_instruction(payer)], Some(payer));
+        base_fee = pool.fee_for_message(&message)?;
+        sampled_units += pool.simulate_units(&Transaction::new_unsigned(message))?;
+        estimate.sampled += 1;
+    }
+    estimate.compute_units = sampled_units / estimate.sampled as u64 * targets.len() as u64;
+    estimate.base_fees = base_fee * targets.len() as u64;
+
+    let caches: Vec<Pubkey> = targets
+        .iter()
+        .map(|target| pda::score_cache(&target.query_account).0)
+        .collect();
+    estimate.priority_fee_price = fees.price(pool, &caches)?;
+    estimate.priority_fees = fees.lamports(estimate.priority_fee_price) * targets.len() as u64;
+    Ok(estimate)
+}
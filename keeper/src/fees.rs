@@ -0,0 +1,69 @@
+//! Dynamic priority fees.
+//!
+//! Before each refresh the keeper prices its transaction at a percentile of
+//! the prioritization fees recently paid to write the same accounts, clamped
+//! to a configured cap, so refreshes land during congestion without paying
+//! congestion prices when the network is quiet.
+
+use anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction;
+use anchor_client::solana_sdk::instruction::Instruction;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+use crate::rpc::RpcPool;
+use crate::KeeperError;
+
+#[derive(Clone, Copy, Debug)]
+pub struct PriorityFeeConfig {
+    /// Percentile (0–100) of the recent fees to pay.
+    pub percentile: u8,
+    /// Highest price paid, in micro-lamports per compute unit.
+    pub max_price: u64,
+    /// Compute unit limit requested per refresh; priority fees are charged on
+    /// the limit, not on the units consumed.
+    pub compute_unit_limit: u32,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        (&risk_oracle_config::PriorityFeeConfig::default()).into()
+    }
+}
+
+impl From<&risk_oracle_config::PriorityFeeConfig> for PriorityFeeConfig {
+    fn from(config: &risk_oracle_config::PriorityFeeConfig) -> Self {
+        Self {
+            percentile: config.percentile,
+            max_price: config.max_price,
+            compute_unit_limit: config.compute_unit_limit,
+        }
+    }
+}
+
+impl PriorityFeeConfig {
+    /// Price (micro-lamports per CU) for a transaction writing `accounts`:
+    /// the configured percentile of the recent fees, capped at `max_price`.
+    pub fn price(&self, pool: &mut RpcPool, accounts: &[Pubkey]) -> Result<u64, KeeperError> {
+        let mut fees = pool.recent_prioritization_fees(accounts)?;
+        if fees.is_empty() {
+            return Ok(0);
+        }
+        fees.sort_unstable();
+        let index = (fees.len() - 1) * self.percentile.min(100) as usize / 100;
+        Ok(fees[index].min(self.max_price))
+    }
+
+    /// Compute budget instructions for `price`, placed with
+    /// `transaction::with_compute_budget`: after the verify instruction, or
+    /// before the quote for a program built with `quote-discovery`.
+    pub fn instructions(&self, price: u64) -> [Instruction; 2] {
+        [
+            ComputeBudgetInstruction::set_compute_unit_limit(self.compute_unit_limit),
+            ComputeBudgetInstruction::set_compute_unit_price(price),
+        ]
+    }
+
+    /// Priority fee of one transaction at `price`, in lamports.
+    pub fn lamports(&self, price: u64) -> u64 {
+        (self.compute_unit_limit as u128 * price as u128).div_ceil(1_000_000) as u64
+    }
+}
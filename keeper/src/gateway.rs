@@ -0,0 +1,179 @@
+//! [`QuoteSource`] backed by `risk-oracle-mock-gateway`, to run the keeper
+//! end to end offline: a local validator loaded with the fixture queue, and
+//! quotes signed by the fixture oracles. Requests go through an
+//! [`HttpTransport`], carrying the next of the configured Range API keys
+//! ([`ApiKeys`]) as the `RANGE_API_KEY` variable override; a 429 moves on to
+//! the next key. [`GatewayQuoteSource::pin_feed`] pins feeds ahead of their
+//! first quote.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_sdk::account::from_account;
+use anchor_client::solana_sdk::ed25519_program;
+use anchor_client::solana_sdk::hash::hash;
+use anchor_client::solana_sdk::instruction::Instruction;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::slot_hashes::SlotHashes;
+use anchor_client::solana_sdk::sysvar;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use risk_oracle_shared::diagnostics::hex;
+use risk_oracle_shared::feed;
+use risk_oracle_shared::proto::OracleFeed;
+use serde_json::{json, Value};
+
+use crate::credentials::{ApiKeys, KeyUsage};
+use crate::http::HttpTransport;
+use crate::refresh::QuoteSource;
+use crate::KeeperError;
+
+#[cfg(feature = "reqwest")]
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// Quote endpoint of the mock gateway.
+const QUOTE_PATH: &str = "/gateway/api/v1/quote";
+/// Endpoint pinning feeds, like Crossbar's `storeOracleFeed`.
+const STORE_PATH: &str = "/gateway/api/v1/store";
+const API_KEY_VARIABLE: &str = "RANGE_API_KEY";
+const TOO_MANY_REQUESTS: u16 = 429;
+
+pub struct GatewayQuoteSource {
+    http: Arc<dyn HttpTransport>,
+    /// Gateway base URL, e.g. `http://127.0.0.1:8787`.
+    url: String,
+    /// Cluster the quotes are for, read for its latest slot hash.
+    rpc: RpcClient,
+    /// Empty: the gateway's own key.
+    api_keys: ApiKeys,
+}
+
+impl GatewayQuoteSource {
+    /// Source requesting quotes with reqwest.
+    #[cfg(feature = "reqwest")]
+    pub fn new(url: impl Into<String>, rpc_url: impl Into<String>) -> Self {
+        let http = crate::http::ReqwestTransport::with_timeout(TIMEOUT);
+        Self::with_transport(url, rpc_url, Arc::new(http))
+    }
+
+    /// Source requesting quotes through `http`.
+    pub fn with_transport(
+        url: impl Into<String>,
+        rpc_url: impl Into<String>,
+        http: Arc<dyn HttpTransport>,
+    ) -> Self {
+        Self {
+            http,
+            url: url.into(),
+            rpc: RpcClient::new(rpc_url.into()),
+            api_keys: ApiKeys::new([], Duration::ZERO),
+        }
+    }
+
+    /// Sends `api_keys` in turn instead of relying on the gateway's key.
+    pub fn with_api_keys(mut self, api_keys: ApiKeys) -> Self {
+        self.api_keys = api_keys;
+        self
+    }
+
+    /// Pins `feed` with the gateway, returning its id. Fails when the gateway
+    /// pins it under another id than the programs derive.
+    pub fn pin_feed(&self, feed: &OracleFeed) -> Result<[u8; 32], KeeperError> {
+        let encoded = feed::encode(feed);
+        let request = json!({ "feed": STANDARD.encode(&encoded) });
+        let response = self
+            .http
+            .post_json(
+                &format!("{}{}", self.url.trim_end_matches('/'), STORE_PATH),
+                request.to_string().into_bytes(),
+            )
+            .map_err(|err| KeeperError::Pin(err.to_string()))?;
+        let body: Value = serde_json::from_slice(&response.body)
+            .map_err(|err| KeeperError::Pin(err.to_string()))?;
+        if !response.is_success() {
+            let error = body["error"].as_str().unwrap_or("no error message");
+            return Err(KeeperError::Pin(format!(
+                "gateway answered {}: {}",
+                response.status, error
+            )));
+        }
+
+        let feed_id = hash(&encoded).to_bytes();
+        let pinned = body["feed_hash"].as_str().unwrap_or_default();
+        if pinned.trim_start_matches("0x") != hex(&feed_id) {
+            return Err(KeeperError::Pin(format!(
+                "gateway pinned the feed as `{pinned}`, the programs derive {}",
+                hex(&feed_id)
+            )));
+        }
+        Ok(feed_id)
+    }
+}
+
+impl QuoteSource for GatewayQuoteSource {
+    fn quote_instruction(
+        &self,
+        feed: &OracleFeed,
+        _payer: &Pubkey,
+    ) -> Result<Instruction, KeeperError> {
+        // The program looks the quote's slot hash up in SlotHashes, quote the
+        // most recent entry.
+        let account = self.rpc.get_account(&sysvar::slot_hashes::ID)?;
+        let slot_hashes: SlotHashes = from_account(&account)
+            .ok_or_else(|| KeeperError::Quote("unreadable SlotHashes sysvar".to_string()))?;
+        let (slot, hash) = slot_hashes
+            .first()
+            .ok_or_else(|| KeeperError::Quote("empty SlotHashes sysvar".to_string()))?;
+
+        let url = format!("{}{}", self.url.trim_end_matches('/'), QUOTE_PATH);
+        let mut request = json!({
+            "feeds": [STANDARD.encode(feed::encode(feed))],
+            "slot": slot,
+            "slot_hash": hex(&hash.to_bytes()),
+        });
+        let response = loop {
+            let key = match self.api_keys.is_empty() {
+                true => None,
+                false => Some(self.api_keys.take().ok_or_else(|| {
+                    KeeperError::Quote("every API key is rate limited".to_string())
+                })?),
+            };
+            if let Some(key) = &key {
+                request["variable_overrides"] = json!({ API_KEY_VARIABLE: key.value });
+            }
+            let response = self
+                .http
+                .post_json(&url, request.to_string().into_bytes())
+                .map_err(|err| KeeperError::Quote(err.to_string()))?;
+            match key {
+                Some(key) if response.status == TOO_MANY_REQUESTS => {
+                    self.api_keys.rate_limited(&key)
+                }
+                _ => break response,
+            }
+        };
+        let body: Value = serde_json::from_slice(&response.body)
+            .map_err(|err| KeeperError::Quote(err.to_string()))?;
+        if !response.is_success() {
+            let error = body["error"].as_str().unwrap_or("no error message");
+            return Err(KeeperError::Quote(format!(
+                "gateway answered {}: {}",
+                response.status, error
+            )));
+        }
+
+        let data = body["instruction_data"]
+            .as_str()
+            .and_then(|data| STANDARD.decode(data).ok())
+            .ok_or_else(|| KeeperError::Quote("gateway answer has no quote".to_string()))?;
+        Ok(Instruction {
+            program_id: ed25519_program::ID,
+            accounts: Vec::new(),
+            data,
+        })
+    }
+
+    fn take_key_usage(&self) -> Vec<KeyUsage> {
+        self.api_keys.take_usage()
+    }
+}
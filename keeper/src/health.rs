@@ -0,0 +1,52 @@
+//! Health checks of the verification pipeline itself.
+//!
+//! The program's `health_check` verifies a quote of the constant test feed
+//! (no API key, always `feed::TEST_SCORE`) end to end and emits a
+//! `Heartbeat`. A refresh failing can be the address, the Range API or the
+//! pipeline; the check failing can only be the pipeline: the queue, the
+//! sysvars, the oracles or the program. Cycles run it at most every
+//! `[keeper.health_check] interval_secs` and alert when it fails, so a broken
+//! pipeline pages once instead of showing up as failures across the
+//! watchlist.
+
+use anchor_client::solana_sdk::signature::{Keypair, Signature, Signer};
+use risk_oracle_client::instructions;
+use risk_oracle_client::transaction::{self, TransactionOptions};
+use risk_oracle_shared::feed;
+
+use crate::fees::PriorityFeeConfig;
+use crate::refresh::QuoteSource;
+use crate::rpc::RpcPool;
+use crate::KeeperError;
+
+/// Sends `[test feed quote, health_check, compute budget]`, priced with
+/// `fees`, in the format of `options`. Returns the signature and the fee
+/// paid, in lamports. The transaction writes nothing but the payer, so it is
+/// priced against the network's recent fees.
+pub fn health_check(
+    pool: &mut RpcPool,
+    quotes: &impl QuoteSource,
+    payer: &Keypair,
+    fees: &PriorityFeeConfig,
+    options: &TransactionOptions,
+) -> Result<(Signature, u64), KeeperError> {
+    let feed = feed::test_feed();
+    let check_ix = instructions::health_check(payer.pubkey());
+    let price = fees.price(pool, &[])?;
+    let budget_ixs = fees.instructions(price);
+
+    let mut fee = 0;
+    let signature = pool.send_and_confirm(|blockhash| {
+        let quote_ix = quotes.quote_instruction(&feed, &payer.pubkey())?;
+        let instructions =
+            transaction::with_compute_budget(&[quote_ix, check_ix.clone()], &budget_ixs);
+        fee = transaction::fee_lamports(&instructions, 1);
+        Ok(transaction::transaction(
+            &[payer],
+            &instructions,
+            blockhash,
+            options,
+        )?)
+    })?;
+    Ok((signature, fee))
+}
@@ -0,0 +1,89 @@
+//! HTTP behind a trait, for the gateway ([`crate::gateway`]) and the alert
+//! webhooks ([`crate::alerts`]).
+//!
+//! Embedders plug in the client they already run (custom TLS, proxies,
+//! instrumentation) by implementing [`HttpTransport`]. The reqwest one,
+//! [`ReqwestTransport`], comes with the `reqwest` feature (on by default);
+//! without it the keeper builds without an HTTP stack. One transport can be
+//! shared (`Arc`) by every gateway and alerter of the process.
+
+/// Failure to get a response at all (connection, TLS, timeout, ...). Error
+/// statuses are responses.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct HttpError(pub String);
+
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+pub trait HttpTransport: Send + Sync {
+    /// POSTs the JSON `body` to `url` (`content-type: application/json`).
+    fn post_json(&self, url: &str, body: Vec<u8>) -> Result<HttpResponse, HttpError>;
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for std::sync::Arc<T> {
+    fn post_json(&self, url: &str, body: Vec<u8>) -> Result<HttpResponse, HttpError> {
+        (**self).post_json(url, body)
+    }
+}
+
+#[cfg(feature = "reqwest")]
+pub use self::reqwest_transport::ReqwestTransport;
+
+#[cfg(feature = "reqwest")]
+mod reqwest_transport {
+    use std::time::Duration;
+
+    use reqwest::blocking::Client;
+
+    use super::{HttpError, HttpResponse, HttpTransport};
+
+    /// [`HttpTransport`] over a blocking reqwest client.
+    #[derive(Clone, Debug)]
+    pub struct ReqwestTransport(pub Client);
+
+    impl ReqwestTransport {
+        /// Client with the default TLS backend and a `timeout` per request.
+        pub fn with_timeout(timeout: Duration) -> Self {
+            Self(
+                Client::builder()
+                    .timeout(timeout)
+                    .build()
+                    .expect("default TLS backend"),
+            )
+        }
+    }
+
+    impl From<Client> for ReqwestTransport {
+        fn from(client: Client) -> Self {
+            Self(client)
+        }
+    }
+
+    impl HttpTransport for ReqwestTransport {
+        fn post_json(&self, url: &str, body: Vec<u8>) -> Result<HttpResponse, HttpError> {
+            let response = self
+                .0
+                .post(url)
+                .header("content-type", "application/json")
+                .body(body)
+                .send()
+                .map_err(|err| HttpError(err.to_string()))?;
+            let status = response.status().as_u16();
+            let body = response.bytes().map_err(|err| HttpError(err.to_string()))?;
+            Ok(HttpResponse {
+                status,
+                body: body.to_vec(),
+            })
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Keeper for the Anchor risk oracle program.
+//!
+//! Refreshes the per-address score caches by sending a Switchboard quote
+//! together with `verify_composite_risk_score`, through a pool of RPC
+//! providers so refreshes keep landing when one of them degrades, paying a
+//! priority fee that follows recent network fees up to a cap ([`fees`]).
+//! [`estimate`] prices a full refresh cycle beforehand, without sending
+//! anything, and [`cycle`] runs cycles that resume after a restart. Per-address
+//! outcomes are kept in a local SQLite database ([`store`]) to back off
+//! failing addresses, and alert conditions are posted to webhooks ([`alerts`]).
+//! Offline, [`gateway`] takes its quotes from the mock gateway. HTTP goes
+//! through a pluggable transport ([`http`]). One process can serve several
+//! configured tenants ([`tenant`]). Range API keys are rotated on rate limits
+//! ([`credentials`]). Refreshes of scores that haven't moved off-chain can be
+//! skipped ([`differential`]), and a cycle's refreshes spread over a window of
+//! slots ([`schedule`]). The config and watchlist reload without a restart
+//! ([`reload`]). The verification pipeline itself is checked with the test
+//! feed ([`health`]). Quotes can also be posted to quote accounts for
+//! integrators to verify against ([`quote_account`]). The payer and treasury
+//! balances are watched, topped up by airdrop off mainnet ([`balance`]).
+
+pub mod alerts;
+pub mod balance;
+pub mod credentials;
+pub mod cycle;
+pub mod differential;
+pub mod estimate;
+pub mod fees;
+pub mod gateway;
+pub mod health;
+pub mod http;
+pub mod quote_account;
+pub mod refresh;
+pub mod reload;
+pub mod rpc;
+pub mod schedule;
+pub mod store;
+pub mod tenant;
+
+use anchor_client::solana_client::client_error::ClientError;
+use anchor_client::solana_sdk::transaction::TransactionError;
+use risk_oracle_client::cluster::ClusterError;
+use risk_oracle_client::transaction::TransactionBuildError;
+use risk_oracle_shared::errors;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeeperError {
+    #[error("no RPC endpoint configured")]
+    NoEndpoints,
+    #[error("every RPC endpoint failed, last error: {}", named(.0))]
+    Rpc(#[from] ClientError),
+    #[error("`{0}` is not a valid address")]
+    InvalidAddress(String),
+    #[error("failed to fetch the quote: {0}")]
+    Quote(String),
+    #[error("failed to pin the feed: {0}")]
+    Pin(String),
+    #[error("failed to build the transaction: {0}")]
+    Transaction(#[from] TransactionBuildError),
+    #[error("transaction failed: {}", named(.0))]
+    TransactionFailed(TransactionError),
+    #[error("transaction not confirmed after {0} attempts")]
+    NotConfirmed(usize),
+    #[error("checkpoint: {0}")]
+    Checkpoint(std::io::Error),
+    #[error("state database: {0}")]
+    Store(#[from] rusqlite::Error),
+    #[error("failed to read the payer keypair: {0}")]
+    Payer(String),
+    #[error("cluster check: {0}")]
+    Cluster(#[from] ClusterError),
+    #[error("Range API: {0}")]
+    Range(String),
+    #[error("watchlist {0}")]
+    Watchlist(String),
+}
+
+/// `err` displayed with its custom program errors named, e.g.
+/// `custom program error: StaleQuote (0x1775)`.
+fn named(err: &impl std::fmt::Display) -> String {
+    errors::describe(&err.to_string())
+}
@@ -0,0 +1,78 @@
+//! Posting quotes to quote accounts.
+//!
+//! Integrators verifying from many transactions can read the score from a
+//! quote account (`verify_risk_score_account`) instead of each attaching a
+//! quote. The keeper keeps such an account current: [`post_quote`] creates it
+//! on first use and sends `[quote, write_quote_account, compute budget]`, the
+//! program verifying the quote before replacing the posted one. The account
+//! belongs to the payer, the only signer that can write or close it.
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{Keypair, Signature, Signer};
+use risk_oracle_client::transaction::{self, TransactionOptions};
+use risk_oracle_client::{instructions, pda};
+use risk_oracle_shared::proto::OracleFeed;
+
+use crate::fees::PriorityFeeConfig;
+use crate::refresh::QuoteSource;
+use crate::rpc::RpcPool;
+use crate::KeeperError;
+
+/// Posts a fresh quote of `feed` to the payer's quote account `id`, creating
+/// the account in the same transaction when it doesn't exist yet. Returns the
+/// quote account, the signature and the fee paid, in lamports.
+pub fn post_quote(
+    pool: &mut RpcPool,
+    quotes: &impl QuoteSource,
+    payer: &Keypair,
+    id: u64,
+    feed: &OracleFeed,
+    fees: &PriorityFeeConfig,
+    options: &TransactionOptions,
+) -> Result<(Pubkey, Signature, u64), KeeperError> {
+    let owner = payer.pubkey();
+    let quote_account = pda::quote_account(&owner, id).0;
+    let exists = pool.accounts(&[quote_account])?[0].is_some();
+
+    let mut program_ixs = Vec::new();
+    if !exists {
+        program_ixs.push(instructions::create_quote_account(owner, id));
+    }
+    program_ixs.push(instructions::write_quote_account(owner, id));
+    let price = fees.price(pool, &[quote_account])?;
+    let budget_ixs = fees.instructions(price);
+
+    let mut fee = 0;
+    let signature = pool.send_and_confirm(|blockhash| {
+        // The quote goes first, where the program reads it.
+        let mut ixs = vec![quotes.quote_instruction(feed, &owner)?];
+        ixs.extend(program_ixs.iter().cloned());
+        let instructions = transaction::with_compute_budget(&ixs, &budget_ixs);
+        fee = transaction::fee_lamports(&instructions, 1);
+        Ok(transaction::transaction(
+            &[payer],
+            &instructions,
+            blockhash,
+            options,
+        )?)
+    })?;
+    Ok((quote_account, signature, fee))
+}
+
+/// Closes the payer's quote account `id`, refunding its rent to the payer.
+pub fn close_quote_account(
+    pool: &mut RpcPool,
+    payer: &Keypair,
+    id: u64,
+    options: &TransactionOptions,
+) -> Result<Signature, KeeperError> {
+    let instruction = instructions::close_quote_account(payer.pubkey(), id);
+    pool.send_and_confirm(|blockhash| {
+        Ok(transaction::transaction(
+            &[payer],
+            &[instruction.clone()],
+            blockhash,
+            options,
+        )?)
+    })
+}
@@ -0,0 +1,138 @@
+//! Score cache refreshes.
+
+use anchor_client::solana_sdk::instruction::Instruction;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{Keypair, Signature, Signer};
+use risk_oracle_client::events::{self, OracleEvent};
+use risk_oracle_client::transaction::{self, TransactionOptions};
+use risk_oracle_client::{instructions, pda, ScoreCache};
+use risk_oracle_config::Severity;
+use risk_oracle_shared::feed::{self, NetworkSet};
+use risk_oracle_shared::proto::OracleFeed;
+
+use crate::credentials::KeyUsage;
+use crate::fees::PriorityFeeConfig;
+use crate::rpc::RpcPool;
+use crate::KeeperError;
+
+/// Produces the Switchboard quote instruction (Ed25519 signatures over the
+/// feed result) for a feed, e.g. through Crossbar.
+///
+/// The feed is the vendored [`risk_oracle_shared::proto`] type; a source built
+/// on another copy of the Switchboard protos converts it by re-encoding.
+pub trait QuoteSource {
+    fn quote_instruction(
+        &self,
+        feed: &OracleFeed,
+        payer: &Pubkey,
+    ) -> Result<Instruction, KeeperError>;
+
+    /// Per API key usage since the last call, for sources rotating keys.
+    fn take_key_usage(&self) -> Vec<KeyUsage> {
+        Vec::new()
+    }
+}
+
+/// One score cache to keep fresh.
+#[derive(Clone, Copy, Debug)]
+pub struct Target {
+    /// Account passed to the program, its cache PDA is refreshed.
+    pub query_account: Pubkey,
+    /// Wallet the feed is built for: the query account itself, or the owner
+    /// when it is a token account.
+    pub screened_address: Pubkey,
+    pub networks: NetworkSet,
+    /// Also append the score to the query account's history.
+    pub record_history: bool,
+    /// Record the refresh against the query account's on-chain freshness SLA
+    /// (the SLA account must exist).
+    pub freshness_sla: bool,
+}
+
+impl Target {
+    /// Target refreshing an existing cache, when it can be refreshed: the
+    /// cache only records the screened wallet, so caches of token accounts
+    /// (keyed by an unknown query account) are `None`. `freshness_sla` is
+    /// whether the query account has an SLA (see
+    /// `RiskOracleClient::list_freshness_slas`).
+    pub fn from_cache(
+        cache_address: &Pubkey,
+        cache: &ScoreCache,
+        record_history: bool,
+        freshness_sla: bool,
+    ) -> Option<Self> {
+        if pda::score_cache(&cache.address).0 != *cache_address {
+            return None;
+        }
+        Some(Self {
+            query_account: cache.address,
+            screened_address: cache.address,
+            networks: NetworkSet::from_bits(cache.networks)?,
+            record_history,
+            freshness_sla,
+        })
+    }
+
+    pub(crate) fn feed(&self) -> OracleFeed {
+        feed::composite_risk_score_feed(&self.screened_address.to_bytes(), self.networks)
+    }
+
+    pub(crate) fn verify_instruction(&self, payer: &Pubkey) -> Instruction {
+        instructions::verify_composite_risk_score(
+            self.query_account,
+            *payer,
+            self.networks.bits(),
+            self.record_history,
+            self.freshness_sla,
+        )
+    }
+}
+
+/// Sends `[quote, verify_composite_risk_score, compute budget]` for
+/// `target`, priced with `fees` against the fees recently paid to write its
+/// cache, in the format of `options`. A new quote is fetched for every
+/// attempt so a retry never carries a stale one. Returns the signature and
+/// the fee paid, in lamports; a fee above `options.max_fee_lamports` fails
+/// with `TransactionBuildError::OverBudget` before anything is sent.
+pub fn refresh_score_cache(
+    pool: &mut RpcPool,
+    quotes: &impl QuoteSource,
+    payer: &Keypair,
+    target: &Target,
+    fees: &PriorityFeeConfig,
+    options: &TransactionOptions,
+) -> Result<(Signature, u64), KeeperError> {
+    let feed = target.feed();
+    let verify_ix = target.verify_instruction(&payer.pubkey());
+    let price = fees.price(pool, &[pda::score_cache(&target.query_account).0])?;
+    let budget_ixs = fees.instructions(price);
+
+    let mut fee = 0;
+    let signature = pool.send_and_confirm(|blockhash| {
+        let quote_ix = quotes.quote_instruction(&feed, &payer.pubkey())?;
+        let instructions =
+            transaction::with_compute_budget(&[quote_ix, verify_ix.clone()], &budget_ixs);
+        fee = transaction::fee_lamports(&instructions, 1);
+        Ok(transaction::transaction(&[payer], &instructions, blockhash, options)?)
+    })?;
+    Ok((signature, fee))
+}
+
+/// Score written by a confirmed refresh and the severity band the program
+/// tagged it with, read from its `CompositeScoreCached` event. `None` when
+/// the logs can't be fetched.
+pub fn refreshed_score(pool: &mut RpcPool, signature: &Signature) -> Option<(u8, Severity)> {
+    let logs = pool.transaction_logs(signature).ok()?;
+    events::from_logs(&logs).into_iter().find_map(|event| match event {
+        OracleEvent::CompositeScoreCached(e) => Some((e.score, severity(e.severity))),
+        _ => None,
+    })
+}
+
+fn severity(severity: risk_oracle_client::Severity) -> Severity {
+    match severity {
+        risk_oracle_client::Severity::Info => Severity::Info,
+        risk_oracle_client::Severity::Warn => Severity::Warn,
+        risk_oracle_client::Severity::Critical => Severity::Critical,
+    }
+}
@@ -0,0 +1,141 @@
+//! Reloading the config and watchlist without restarting.
+//!
+//! [`Reload::watch`] raises a reload request on SIGHUP or when one of the
+//! watched files (the config, the `keeper.watchlist` files) is modified,
+//! polled every [`POLL_INTERVAL`]. Either way, and on SIGTERM/SIGINT, it also
+//! raises [`Reload::interrupt`], the flag cycles run with: the in-flight
+//! refresh is finished and checkpointed, then the cycle returns. The caller
+//! reloads and starts the next cycle, which resumes from the checkpoint with
+//! the new targets. The checkpoint records an address, not an index, so the
+//! position stays meaningful when the watchlist changed. A config that fails
+//! to load or validate should leave the running keepers in place.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use risk_oracle_shared::feed::NetworkSet;
+
+use crate::refresh::Target;
+use crate::KeeperError;
+
+/// How often the watched files' modification times are checked.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Reload and shutdown requests.
+pub struct Reload {
+    interrupt: Arc<AtomicBool>,
+    requested: Arc<AtomicBool>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl Reload {
+    /// Watches SIGHUP, SIGTERM/SIGINT and `files` from a background thread.
+    pub fn watch(files: Vec<PathBuf>) -> io::Result<Self> {
+        let reload = Self {
+            interrupt: Arc::new(AtomicBool::new(false)),
+            requested: Arc::new(AtomicBool::new(false)),
+            shutdown: Arc::new(AtomicBool::new(false)),
+        };
+        for flag in [&reload.requested, &reload.interrupt] {
+            signal_hook::flag::register(signal_hook::consts::SIGHUP, Arc::clone(flag))?;
+        }
+        for signal in [signal_hook::consts::SIGTERM, signal_hook::consts::SIGINT] {
+            for flag in [&reload.shutdown, &reload.interrupt] {
+                signal_hook::flag::register(signal, Arc::clone(flag))?;
+            }
+        }
+
+        let requested = Arc::clone(&reload.requested);
+        let interrupt = Arc::clone(&reload.interrupt);
+        let mut times: Vec<_> = files.iter().map(|path| modified(path)).collect();
+        thread::spawn(move || loop {
+            thread::sleep(POLL_INTERVAL);
+            for (path, last) in files.iter().zip(&mut times) {
+                let now = modified(path);
+                if now != *last {
+                    *last = now;
+                    requested.store(true, Ordering::Relaxed);
+                    interrupt.store(true, Ordering::Relaxed);
+                }
+            }
+        });
+        Ok(reload)
+    }
+
+    /// Flag to run cycles with, raised by reload and shutdown requests.
+    pub fn interrupt(&self) -> &AtomicBool {
+        &self.interrupt
+    }
+
+    pub fn shutdown_requested(&self) -> bool {
+        self.shutdown.load(Ordering::Relaxed)
+    }
+
+    /// Whether a reload was requested since the last call. Lowers the
+    /// interrupt unless a shutdown is pending.
+    pub fn take_request(&self) -> bool {
+        if !self.shutdown_requested() {
+            self.interrupt.store(false, Ordering::Relaxed);
+        }
+        self.requested.swap(false, Ordering::Relaxed)
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Targets of a watchlist file: CSV of the query account and, optionally, the
+/// network bits of its feed (`1` for Solana by default). Blank lines, `#`
+/// comments and a header line are skipped; later duplicates are dropped.
+pub fn load_watchlist(path: &Path, record_history: bool) -> Result<Vec<Target>, KeeperError> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| KeeperError::Watchlist(format!("{}: {}", path.display(), err)))?;
+    let invalid = |line: usize, message: String| {
+        KeeperError::Watchlist(format!("{}:{}: {}", path.display(), line + 1, message))
+    };
+
+    let mut seen = HashSet::new();
+    let mut targets = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let mut fields = line.split(',').map(|field| field.trim().trim_matches('"'));
+        let address = fields.next().unwrap_or_default();
+        if address.is_empty() || address.starts_with('#') {
+            continue;
+        }
+        let query_account = match Pubkey::from_str(address) {
+            Ok(address) => address,
+            Err(_) if index == 0 => continue,
+            Err(_) => return Err(invalid(index, format!("`{}` is not an address", address))),
+        };
+        let networks = match fields.next().filter(|field| !field.is_empty()) {
+            Some(bits) => bits
+                .parse()
+                .ok()
+                .and_then(NetworkSet::from_bits)
+                .ok_or_else(|| invalid(index, format!("invalid network set `{}`", bits)))?,
+            None => NetworkSet::SOLANA,
+        };
+        if !seen.insert(query_account) {
+            continue;
+        }
+        targets.push(Target {
+            query_account,
+            screened_address: query_account,
+            networks,
+            record_history,
+            freshness_sla: false,
+        });
+    }
+    Ok(targets)
+}
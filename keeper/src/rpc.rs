@@ -0,0 +1,302 @@
+//! Transaction submission across several RPC providers.
+//!
+//! The first URL is the primary, the others are fallbacks. Each endpoint keeps
+//! a success score (exponential moving average of its recent calls) and calls
+//! go to the best scored endpoint first, so a degraded provider is skipped
+//! until it recovers. Every send attempt uses a fresh blockhash and is
+//! tracked until it is confirmed, fails, or its blockhash expires.
+
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anchor_client::solana_client::client_error::ClientError;
+use anchor_client::solana_sdk::account::Account;
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::{RpcSimulateTransactionConfig, RpcTransactionConfig};
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::hash::Hash;
+use anchor_client::solana_sdk::message::Message;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::Signature;
+use anchor_client::solana_sdk::transaction::{Transaction, VersionedTransaction};
+use risk_oracle_client::transaction::{
+    self, TransactionBuildError, TransactionFormat, TransactionOptions,
+};
+use risk_oracle_config::{Config, TransactionsConfig};
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::KeeperError;
+
+/// `getMultipleAccounts` limit.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Weight of the latest call in an endpoint's success score.
+const SCORE_WEIGHT: f64 = 0.2;
+/// Initial score of fallbacks, below the primary's so it is preferred until it
+/// starts failing.
+const FALLBACK_SCORE: f64 = 0.9;
+
+struct Endpoint {
+    url: String,
+    client: RpcClient,
+    score: f64,
+}
+
+pub struct RpcPool {
+    endpoints: Vec<Endpoint>,
+    commitment: CommitmentConfig,
+    /// Send attempts (each with a fresh blockhash) per transaction.
+    pub max_attempts: usize,
+    /// Delay between two confirmation polls.
+    pub poll_interval: Duration,
+    /// Give up on an attempt after this long even if its blockhash is valid.
+    pub confirm_timeout: Duration,
+}
+
+impl RpcPool {
+    pub fn new<I, S>(urls: I, commitment: CommitmentConfig) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let endpoints = urls
+            .into_iter()
+            .enumerate()
+            .map(|(i, url)| {
+                let url = url.into();
+                Endpoint {
+                    client: RpcClient::new_with_commitment(url.clone(), commitment),
+                    url,
+                    score: if i == 0 { 1.0 } else { FALLBACK_SCORE },
+                }
+            })
+            .collect();
+        Self {
+            endpoints,
+            commitment,
+            max_attempts: 3,
+            poll_interval: Duration::from_millis(500),
+            confirm_timeout: Duration::from_secs(60),
+        }
+    }
+
+    /// Pool of the configured primary and fallback endpoints.
+    pub fn from_config(config: &Config) -> Self {
+        let commitment = CommitmentConfig::from_str(&config.rpc.commitment)
+            .unwrap_or_else(|_| CommitmentConfig::confirmed());
+        let urls = std::iter::once(&config.rpc.url).chain(&config.rpc.fallback_urls).cloned();
+        let mut pool = Self::new(urls, commitment);
+        pool.max_attempts = config.keeper.max_attempts;
+        pool
+    }
+
+    /// Transaction options of the `[transactions]` section, its lookup tables
+    /// fetched from the best endpoint.
+    pub fn transaction_options(
+        &mut self,
+        config: &TransactionsConfig,
+    ) -> Result<TransactionOptions, KeeperError> {
+        let format = match config.format.as_str() {
+            "legacy" => TransactionFormat::Legacy,
+            _ => TransactionFormat::V0,
+        };
+        let addresses = config
+            .lookup_tables
+            .iter()
+            .map(|table| {
+                Pubkey::from_str(table).map_err(|_| KeeperError::InvalidAddress(table.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut last_error = None;
+        for index in self.ranked() {
+            match transaction::fetch_lookup_tables(&self.endpoints[index].client, &addresses) {
+                Ok(lookup_tables) => {
+                    self.record(index, true);
+                    return Ok(TransactionOptions {
+                        format,
+                        lookup_tables,
+                        max_fee_lamports: config.max_fee_lamports,
+                    });
+                }
+                Err(TransactionBuildError::Rpc(err)) => {
+                    self.record(index, false);
+                    last_error = Some(err);
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Err(last_error.map_or(KeeperError::NoEndpoints, KeeperError::Rpc))
+    }
+
+    /// `(url, score)` of every endpoint, in configuration order.
+    pub fn scores(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.endpoints.iter().map(|e| (e.url.as_str(), e.score))
+    }
+
+    /// Endpoint indexes, best scored first (configuration order on ties).
+    fn ranked(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.endpoints.len()).collect();
+        order.sort_by(|&a, &b| self.endpoints[b].score.total_cmp(&self.endpoints[a].score));
+        order
+    }
+
+    fn record(&mut self, index: usize, success: bool) {
+        let endpoint = &mut self.endpoints[index];
+        let outcome = if success { 1.0 } else { 0.0 };
+        endpoint.score = (1.0 - SCORE_WEIGHT) * endpoint.score + SCORE_WEIGHT * outcome;
+    }
+
+    /// Runs `call` on the endpoints in ranked order until one succeeds.
+    /// Returns the index of the endpoint that answered with the result.
+    fn call<T>(
+        &mut self,
+        mut call: impl FnMut(&RpcClient) -> Result<T, ClientError>,
+    ) -> Result<(usize, T), KeeperError> {
+        let mut last_error = None;
+        for index in self.ranked() {
+            match call(&self.endpoints[index].client) {
+                Ok(value) => {
+                    self.record(index, true);
+                    return Ok((index, value));
+                }
+                Err(err) => {
+                    self.record(index, false);
+                    last_error = Some(err);
+                }
+            }
+        }
+        Err(last_error.map_or(KeeperError::NoEndpoints, KeeperError::Rpc))
+    }
+
+    pub fn latest_blockhash(&mut self) -> Result<Hash, KeeperError> {
+        self.call(|client| client.get_latest_blockhash())
+            .map(|(_, hash)| hash)
+    }
+
+    /// Compute units consumed by `transaction` in a simulation. The
+    /// transaction doesn't need to be signed nor to carry a valid blockhash.
+    pub fn simulate_units(&mut self, transaction: &Transaction) -> Result<u64, KeeperError> {
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(self.commitment),
+            ..RpcSimulateTransactionConfig::default()
+        };
+        let (_, response) = self.call(|client| {
+            client.simulate_transaction_with_config(transaction, config.clone())
+        })?;
+        match response.value.err {
+            Some(err) => Err(KeeperError::TransactionFailed(err)),
+            None => Ok(response.value.units_consumed.unwrap_or_default()),
+        }
+    }
+
+    /// Base fee of `message` (signature fees), in lamports.
+    pub fn fee_for_message(&mut self, message: &Message) -> Result<u64, KeeperError> {
+        self.call(|client| client.get_fee_for_message(message))
+            .map(|(_, fee)| fee)
+    }
+
+    /// Prioritization fees (micro-lamports per CU) paid in recent slots by
+    /// transactions writing to any of `accounts`, one value per slot.
+    pub fn recent_prioritization_fees(
+        &mut self,
+        accounts: &[Pubkey],
+    ) -> Result<Vec<u64>, KeeperError> {
+        self.call(|client| client.get_recent_prioritization_fees(accounts))
+            .map(|(_, fees)| fees.into_iter().map(|fee| fee.prioritization_fee).collect())
+    }
+
+    /// Current slot at the pool's commitment.
+    pub fn slot(&mut self) -> Result<u64, KeeperError> {
+        let commitment = self.commitment;
+        self.call(|client| client.get_slot_with_commitment(commitment))
+            .map(|(_, slot)| slot)
+    }
+
+    /// Accounts at `addresses`, `None` for those that don't exist.
+    pub fn accounts(&mut self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>, KeeperError> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let (_, found) = self.call(|client| client.get_multiple_accounts(chunk))?;
+            accounts.extend(found);
+        }
+        Ok(accounts)
+    }
+
+    /// Requests `lamports` for `to` from the cluster's faucet and waits for
+    /// the airdrop to confirm. Mainnet has no faucet.
+    pub fn airdrop(&mut self, to: &Pubkey, lamports: u64) -> Result<Signature, KeeperError> {
+        let blockhash = self.latest_blockhash()?;
+        let (_, signature) =
+            self.call(|client| client.request_airdrop_with_blockhash(to, lamports, &blockhash))?;
+        match self.confirm(&signature, &blockhash)? {
+            true => Ok(signature),
+            false => Err(KeeperError::NotConfirmed(1)),
+        }
+    }
+
+    /// Logs of a confirmed transaction.
+    pub fn transaction_logs(&mut self, signature: &Signature) -> Result<Vec<String>, KeeperError> {
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(self.commitment),
+            max_supported_transaction_version: Some(0),
+        };
+        let (_, transaction) =
+            self.call(|client| client.get_transaction_with_config(signature, config))?;
+        Ok(transaction
+            .transaction
+            .meta
+            .and_then(|meta| Option::from(meta.log_messages))
+            .unwrap_or_default())
+    }
+
+    /// Builds, sends and confirms a transaction. `build` is called once per
+    /// attempt with a fresh blockhash, so it can also refresh anything else
+    /// that expires (e.g. the oracle quote).
+    pub fn send_and_confirm(
+        &mut self,
+        mut build: impl FnMut(Hash) -> Result<VersionedTransaction, KeeperError>,
+    ) -> Result<Signature, KeeperError> {
+        for _ in 0..self.max_attempts {
+            let blockhash = self.latest_blockhash()?;
+            let transaction = build(blockhash)?;
+            let (_, signature) = self.call(|client| client.send_transaction(&transaction))?;
+
+            if self.confirm(&signature, &blockhash)? {
+                return Ok(signature);
+            }
+        }
+        Err(KeeperError::NotConfirmed(self.max_attempts))
+    }
+
+    /// Polls the signature status until it reaches the pool's commitment.
+    /// Returns `false` when the blockhash expired (or the timeout elapsed)
+    /// first, meaning the transaction can be rebuilt and sent again.
+    fn confirm(&mut self, signature: &Signature, blockhash: &Hash) -> Result<bool, KeeperError> {
+        let commitment = self.commitment;
+        let started = Instant::now();
+        while started.elapsed() < self.confirm_timeout {
+            let (_, statuses) =
+                self.call(|client| client.get_signature_statuses(&[*signature]))?;
+            if let Some(Some(status)) = statuses.value.first() {
+                if let Some(err) = &status.err {
+                    return Err(KeeperError::TransactionFailed(err.clone()));
+                }
+                if status.satisfies_commitment(commitment) {
+                    return Ok(true);
+                }
+            }
+
+            let (_, valid) =
+                self.call(|client| client.is_blockhash_valid(blockhash, commitment))?;
+            if !valid {
+                return Ok(false);
+            }
+            thread::sleep(self.poll_interval);
+        }
+        Ok(false)
+    }
+}
@@ -0,0 +1,153 @@
+//! Slot-scheduled refreshes.
+//!
+//! Without a schedule a cycle sends its refreshes back to back, so a
+//! watchlist of thousands bursts every run: priority fees spike on the
+//! caches' write locks and the RPC endpoints throttle. With one, the cycle
+//! is spread over a window of `window_slots`. The window is cut in `buckets`
+//! equal slices, each address is hashed into one and jittered within it, and
+//! the cycle waits for an address's slot before refreshing it.
+//!
+//! An address keeps its slot from cycle to cycle, so it is refreshed once
+//! per window. The hash is seeded (with the payer's key for tenants), so
+//! keepers sharing a watchlist don't line up on the same slots.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use risk_oracle_config::ScheduleConfig;
+
+use crate::rpc::RpcPool;
+use crate::KeeperError;
+
+/// Target slot time.
+const SLOT_DURATION: Duration = Duration::from_millis(400);
+/// Slots slept at most between two checks of the slot and shutdown flag.
+const MAX_WAIT_SLOTS: u64 = 25;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SlotSchedule {
+    /// Slots a cycle is spread over.
+    pub window_slots: u64,
+    /// Slices of the window the addresses are hashed into.
+    pub buckets: u64,
+    pub seed: u64,
+}
+
+impl SlotSchedule {
+    /// From `[keeper.schedule]`, hashing addresses with `seed`.
+    pub fn new(config: &ScheduleConfig, seed: &Pubkey) -> Self {
+        Self {
+            window_slots: config.window_slots,
+            buckets: config.buckets,
+            seed: prefix(seed),
+        }
+    }
+
+    /// Slot of `address` from the start of the window: its bucket's first
+    /// slot plus its jitter.
+    pub fn offset(&self, address: &Pubkey) -> u64 {
+        let buckets = self.buckets.max(1);
+        let width = (self.window_slots / buckets).max(1);
+        let hash = mix(prefix(address) ^ self.seed);
+        (hash % buckets) * width + mix(hash) % width
+    }
+}
+
+/// Waits until `pool` reaches `slot`, sleeping the expected time in steps.
+/// Returns `false` when a shutdown was requested first.
+pub fn wait_for_slot(
+    pool: &mut RpcPool,
+    slot: u64,
+    shutdown: &AtomicBool,
+) -> Result<bool, KeeperError> {
+    loop {
+        if shutdown.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+        let current = pool.slot()?;
+        if current >= slot {
+            return Ok(true);
+        }
+        thread::sleep(SLOT_DURATION * (slot - current).min(MAX_WAIT_SLOTS) as u32);
+    }
+}
+
+fn prefix(key: &Pubkey) -> u64 {
+    let bytes = key.to_bytes();
+    u64::from_le_bytes(bytes[..8].try_into().expect("8 bytes"))
+}
+
+/// splitmix64's finalizer: spreads close inputs over the whole range.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(index: u64) -> Pubkey {
+        let mut bytes = [7; 32];
+        bytes[..8].copy_from_slice(&index.to_le_bytes());
+        Pubkey::new_from_array(bytes)
+    }
+
+    fn schedule(window_slots: u64, buckets: u64) -> SlotSchedule {
+        SlotSchedule {
+            window_slots,
+            buckets,
+            seed: 0,
+        }
+    }
+
+    #[test]
+    fn offsets_stay_in_the_window() {
+        for (window_slots, buckets) in [(9_000, 150), (1_000, 7), (10, 10), (10, 1), (1, 1)] {
+            let schedule = schedule(window_slots, buckets);
+            for index in 0..2_000 {
+                assert!(schedule.offset(&address(index)) < window_slots);
+            }
+        }
+    }
+
+    #[test]
+    fn jitter_stays_in_the_bucket() {
+        let schedule = schedule(9_000, 150);
+        let width = 9_000 / 150;
+        let mut used = [false; 150];
+        let mut jitters = Vec::new();
+        for index in 0..3_000 {
+            let offset = schedule.offset(&address(index));
+            used[(offset / width) as usize] = true;
+            jitters.push(offset % width);
+        }
+        // Consecutive keys are spread over every bucket and the whole width
+        // of the buckets, not bunched at their start.
+        assert!(used.iter().all(|&used| used));
+        assert_eq!(jitters.iter().min(), Some(&0));
+        assert_eq!(jitters.iter().max(), Some(&(width - 1)));
+    }
+
+    #[test]
+    fn one_slot_per_address_and_seed() {
+        let schedule = schedule(9_000, 150);
+        let reseeded = SlotSchedule {
+            seed: 1,
+            ..schedule
+        };
+        let mut moved = 0;
+        for address in (0..100).map(address) {
+            let offset = schedule.offset(&address);
+            assert_eq!(schedule.offset(&address), offset);
+            if reseeded.offset(&address) != offset {
+                moved += 1;
+            }
+        }
+        // Another seed gives the addresses other slots.
+        assert!(moved > 90, "{moved} of 100 addresses moved");
+    }
+}
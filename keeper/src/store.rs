@@ -0,0 +1,544 @@
+//! Local keeper state in an embedded SQLite database.
+//!
+//! One row per query account records its last refresh, last score and
+//! failures, so consistently failing addresses are backed off exponentially
+//! instead of burning a quote every cycle, and operators get a report that
+//! survives restarts.
+//!
+//! After `dead_letter_after` consecutive failures an address is moved to the
+//! dead-letter queue: the keeper stops refreshing it until an operator
+//! replays it (`risk-oracle-cli dlq replay`) once the root cause is fixed.
+//!
+//! Requests and rate limits per Range API key are totalled too, for
+//! `risk-oracle-cli keys`, and the last health check is recorded so its
+//! interval holds across restarts. Accounts below their minimum balance are
+//! remembered so each breach is alerted once.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::Signature;
+use risk_oracle_shared::feed::NetworkSet;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::credentials::KeyUsage;
+use crate::refresh::Target;
+use crate::KeeperError;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS addresses (
+    query_account        TEXT PRIMARY KEY,
+    last_attempt         INTEGER NOT NULL,
+    last_success         INTEGER,
+    last_signature       TEXT,
+    last_score           INTEGER,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    total_refreshes      INTEGER NOT NULL DEFAULT 0,
+    total_failures       INTEGER NOT NULL DEFAULT 0,
+    last_error           TEXT,
+    stale_alerted        INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS dead_letters (
+    query_account    TEXT PRIMARY KEY,
+    screened_address TEXT NOT NULL,
+    networks         INTEGER NOT NULL,
+    record_history   INTEGER NOT NULL,
+    freshness_sla    INTEGER NOT NULL DEFAULT 0,
+    failures         INTEGER NOT NULL,
+    last_error       TEXT NOT NULL,
+    added_at         INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS low_balances (
+    address TEXT PRIMARY KEY
+);
+CREATE TABLE IF NOT EXISTS api_key_usage (
+    name              TEXT PRIMARY KEY,
+    requests          INTEGER NOT NULL DEFAULT 0,
+    rate_limited      INTEGER NOT NULL DEFAULT 0,
+    last_rate_limited INTEGER
+);
+CREATE TABLE IF NOT EXISTS health_check (
+    id           INTEGER PRIMARY KEY CHECK (id = 0),
+    last_check   INTEGER NOT NULL,
+    last_success INTEGER,
+    last_error   TEXT
+);";
+
+/// Delay before retrying an address after `n` consecutive failures:
+/// `base * 2^(n - 1)`, capped at `max`.
+#[derive(Clone, Copy, Debug)]
+pub struct Backoff {
+    pub base: Duration,
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            base: Duration::from_secs(60),
+            max: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+impl Backoff {
+    pub fn delay(&self, consecutive_failures: u32) -> Duration {
+        match consecutive_failures {
+            0 => Duration::ZERO,
+            n => self
+                .base
+                .saturating_mul(1 << (n - 1).min(31))
+                .min(self.max),
+        }
+    }
+}
+
+/// Row of the operator report. Times are unix seconds.
+#[derive(Clone, Debug)]
+pub struct AddressState {
+    pub query_account: Pubkey,
+    pub last_attempt: i64,
+    pub last_success: Option<i64>,
+    pub last_signature: Option<Signature>,
+    pub last_score: Option<u8>,
+    pub consecutive_failures: u32,
+    pub total_refreshes: u64,
+    pub total_failures: u64,
+    pub last_error: Option<String>,
+}
+
+/// A refresh given up on, with what is needed to inspect it.
+#[derive(Clone, Debug)]
+pub struct DeadLetter {
+    pub target: Target,
+    pub failures: u32,
+    pub last_error: String,
+    pub added_at: i64,
+}
+
+/// Totals of an API key. Times are unix seconds.
+#[derive(Clone, Debug)]
+pub struct KeyTotals {
+    pub name: String,
+    pub requests: u64,
+    pub rate_limited: u64,
+    pub last_rate_limited: Option<i64>,
+}
+
+pub struct Store {
+    connection: Connection,
+    pub backoff: Backoff,
+    /// Consecutive failures sending an address to the dead-letter queue.
+    pub dead_letter_after: u32,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, KeeperError> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection,
+            backoff: Backoff::default(),
+            dead_letter_after: 5,
+        })
+    }
+
+    pub fn record_success(
+        &self,
+        query_account: &Pubkey,
+        signature: &Signature,
+        score: Option<u8>,
+        now: i64,
+    ) -> Result<(), KeeperError> {
+        self.connection.execute(
+            "INSERT INTO addresses (query_account, last_attempt, last_success, last_signature,
+                                    last_score, total_refreshes)
+             VALUES (?1, ?2, ?2, ?3, ?4, 1)
+             ON CONFLICT (query_account) DO UPDATE SET
+                last_attempt = ?2,
+                last_success = ?2,
+                last_signature = ?3,
+                last_score = COALESCE(?4, last_score),
+                consecutive_failures = 0,
+                total_refreshes = total_refreshes + 1,
+                stale_alerted = 0",
+            params![query_account.to_string(), now, signature.to_string(), score],
+        )?;
+        Ok(())
+    }
+
+    /// Records a failed refresh of `target`, moving it to the dead-letter
+    /// queue once it failed `dead_letter_after` times in a row. Returns
+    /// whether it was dead-lettered.
+    pub fn record_failure(
+        &self,
+        target: &Target,
+        error: &str,
+        now: i64,
+    ) -> Result<bool, KeeperError> {
+        let query_account = &target.query_account;
+        let failures: u32 = self.connection.query_row(
+            "INSERT INTO addresses (query_account, last_attempt, consecutive_failures,
+                                    total_failures, last_error)
+             VALUES (?1, ?2, 1, 1, ?3)
+             ON CONFLICT (query_account) DO UPDATE SET
+                last_attempt = ?2,
+                consecutive_failures = consecutive_failures + 1,
+                total_failures = total_failures + 1,
+                last_error = ?3
+             RETURNING consecutive_failures",
+            params![query_account.to_string(), now, error],
+            |row| row.get(0),
+        )?;
+        if failures < self.dead_letter_after {
+            return Ok(false);
+        }
+
+        self.connection.execute(
+            "INSERT OR REPLACE INTO dead_letters (query_account, screened_address, networks,
+                                                  record_history, freshness_sla, failures,
+                                                  last_error, added_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                query_account.to_string(),
+                target.screened_address.to_string(),
+                target.networks.bits(),
+                target.record_history,
+                target.freshness_sla,
+                failures,
+                error,
+                now
+            ],
+        )?;
+        Ok(true)
+    }
+
+    pub fn is_dead_lettered(&self, query_account: &Pubkey) -> Result<bool, KeeperError> {
+        let found = self
+            .connection
+            .query_row(
+                "SELECT 1 FROM dead_letters WHERE query_account = ?1",
+                params![query_account.to_string()],
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(found.is_some())
+    }
+
+    /// The dead-letter queue, oldest first.
+    pub fn dead_letters(&self) -> Result<Vec<DeadLetter>, KeeperError> {
+        let mut statement = self.connection.prepare(
+            "SELECT query_account, screened_address, networks, record_history, freshness_sla,
+                    failures, last_error, added_at
+             FROM dead_letters
+             ORDER BY added_at, query_account",
+        )?;
+        let rows = statement.query_map([], |row| {
+            Ok(DeadLetter {
+                target: Target {
+                    query_account: parse(row.get::<_, String>(0)?),
+                    screened_address: parse(row.get::<_, String>(1)?),
+                    networks: NetworkSet::from_bits(row.get(2)?).unwrap_or(NetworkSet::SOLANA),
+                    record_history: row.get(3)?,
+                    freshness_sla: row.get(4)?,
+                },
+                failures: row.get(5)?,
+                last_error: row.get(6)?,
+                added_at: row.get(7)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Takes `query_account` out of the dead-letter queue with a clean
+    /// failure count, so the next cycle refreshes it. Returns whether it was
+    /// queued.
+    pub fn replay(&self, query_account: &Pubkey) -> Result<bool, KeeperError> {
+        let key = query_account.to_string();
+        let removed = self
+            .connection
+            .execute("DELETE FROM dead_letters WHERE query_account = ?1", params![key])?;
+        self.connection.execute(
+            "UPDATE addresses SET consecutive_failures = 0 WHERE query_account = ?1",
+            params![key],
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Replays the whole queue, returns how many addresses were replayed.
+    pub fn replay_all(&self) -> Result<usize, KeeperError> {
+        let addresses: Vec<Pubkey> = self
+            .dead_letters()?
+            .into_iter()
+            .map(|letter| letter.target.query_account)
+            .collect();
+        for address in &addresses {
+            self.replay(address)?;
+        }
+        Ok(addresses.len())
+    }
+
+    /// Whether `query_account` is still in its backoff delay at `now`.
+    pub fn is_backing_off(&self, query_account: &Pubkey, now: i64) -> Result<bool, KeeperError> {
+        let row: Option<(i64, u32)> = self
+            .connection
+            .query_row(
+                "SELECT last_attempt, consecutive_failures FROM addresses WHERE query_account = ?1",
+                params![query_account.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        Ok(row.is_some_and(|(last_attempt, failures)| {
+            now < last_attempt.saturating_add(self.backoff.delay(failures).as_secs() as i64)
+        }))
+    }
+
+    pub fn last_score(&self, query_account: &Pubkey) -> Result<Option<u8>, KeeperError> {
+        let score = self
+            .connection
+            .query_row(
+                "SELECT last_score FROM addresses WHERE query_account = ?1",
+                params![query_account.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(score.flatten())
+    }
+
+    /// Time of the last health check, passed or failed.
+    pub fn last_health_check(&self) -> Result<Option<i64>, KeeperError> {
+        Ok(self
+            .connection
+            .query_row("SELECT last_check FROM health_check", [], |row| row.get(0))
+            .optional()?)
+    }
+
+    /// Records a health check run at `now`, failed with `error` if any.
+    pub fn record_health_check(&self, now: i64, error: Option<&str>) -> Result<(), KeeperError> {
+        self.connection.execute(
+            "INSERT INTO health_check (id, last_check, last_success, last_error)
+             VALUES (0, ?1, CASE WHEN ?2 IS NULL THEN ?1 END, ?2)
+             ON CONFLICT(id) DO UPDATE SET
+                 last_check = excluded.last_check,
+                 last_success = coalesce(excluded.last_success, last_success),
+                 last_error = excluded.last_error",
+            params![now, error],
+        )?;
+        Ok(())
+    }
+
+    /// Records whether `address` is below its minimum balance. Returns `true`
+    /// when it just went below, the breach to alert on.
+    pub fn take_low_balance(&self, address: &Pubkey, low: bool) -> Result<bool, KeeperError> {
+        let key = address.to_string();
+        if !low {
+            self.connection
+                .execute("DELETE FROM low_balances WHERE address = ?1", params![key])?;
+            return Ok(false);
+        }
+        let inserted = self.connection.execute(
+            "INSERT OR IGNORE INTO low_balances (address) VALUES (?1)",
+            params![key],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Addresses whose last successful refresh is older than `sla_secs`, with
+    /// its age. Each breach is returned once, until the next success.
+    pub fn take_stale(&self, now: i64, sla_secs: u64) -> Result<Vec<(Pubkey, u64)>, KeeperError> {
+        let deadline = now.saturating_sub(sla_secs as i64);
+        let mut statement = self.connection.prepare(
+            "UPDATE addresses SET stale_alerted = 1
+             WHERE stale_alerted = 0 AND last_success < ?1
+             RETURNING query_account, last_success",
+        )?;
+        let rows = statement.query_map(params![deadline], |row| {
+            let last_success: i64 = row.get(1)?;
+            Ok((
+                parse(row.get::<_, String>(0)?),
+                now.saturating_sub(last_success) as u64,
+            ))
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Adds `usage`, counted up to `now`, to the per key totals.
+    pub fn record_key_usage(&self, usage: &[KeyUsage], now: i64) -> Result<(), KeeperError> {
+        for key in usage {
+            self.connection.execute(
+                "INSERT INTO api_key_usage (name, requests, rate_limited, last_rate_limited)
+                 VALUES (?1, ?2, ?3, CASE WHEN ?3 > 0 THEN ?4 END)
+                 ON CONFLICT(name) DO UPDATE SET
+                     requests = requests + excluded.requests,
+                     rate_limited = rate_limited + excluded.rate_limited,
+                     last_rate_limited = coalesce(excluded.last_rate_limited, last_rate_limited)",
+                params![key.name, key.requests, key.rate_limited, now],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Totals of every API key used so far, by name.
+    pub fn key_usage(&self) -> Result<Vec<KeyTotals>, KeeperError> {
+        let mut statement = self.connection.prepare(
+            "SELECT name, requests, rate_limited, last_rate_limited
+             FROM api_key_usage
+             ORDER BY name",
+        )?;
+        let rows = statement.query_map([], |row| {
+            Ok(KeyTotals {
+                name: row.get(0)?,
+                requests: row.get(1)?,
+                rate_limited: row.get(2)?,
+                last_rate_limited: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Every known address, most failing first.
+    pub fn report(&self) -> Result<Report, KeeperError> {
+        let mut statement = self.connection.prepare(
+            "SELECT query_account, last_attempt, last_success, last_signature, last_score,
+                    consecutive_failures, total_refreshes, total_failures, last_error
+             FROM addresses
+             ORDER BY consecutive_failures DESC, query_account",
+        )?;
+        let rows = statement.query_map([], |row| {
+            Ok(AddressState {
+                query_account: parse(row.get::<_, String>(0)?),
+                last_attempt: row.get(1)?,
+                last_success: row.get(2)?,
+                last_signature: row.get::<_, Option<String>>(3)?.map(parse),
+                last_score: row.get(4)?,
+                consecutive_failures: row.get(5)?,
+                total_refreshes: row.get(6)?,
+                total_failures: row.get(7)?,
+                last_error: row.get(8)?,
+            })
+        })?;
+        Ok(Report(rows.collect::<Result<_, _>>()?))
+    }
+}
+
+/// Keys are only written by this module.
+fn parse<T: FromStr>(value: String) -> T
+where
+    T::Err: fmt::Debug,
+{
+    value.parse().expect("stored keys are valid")
+}
+
+/// Operator report, one line per address.
+pub struct Report(pub Vec<AddressState>);
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<44} {:>5} {:>8} {:>9} {:>8}  last error",
+            "query account", "score", "failing", "refreshes", "failures"
+        )?;
+        for state in &self.0 {
+            writeln!(
+                f,
+                "{:<44} {:>5} {:>8} {:>9} {:>8}  {}",
+                state.query_account.to_string(),
+                state.last_score.map_or("-".to_string(), |score| score.to_string()),
+                state.consecutive_failures,
+                state.total_refreshes,
+                state.total_failures,
+                state.last_error.as_deref().unwrap_or("")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(byte: u8) -> Target {
+        let key = Pubkey::new_from_array([byte; 32]);
+        Target {
+            query_account: key,
+            screened_address: key,
+            networks: NetworkSet::SOLANA,
+            record_history: false,
+            freshness_sla: false,
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_from_the_base() {
+        let backoff = Backoff::default();
+        assert_eq!(backoff.delay(0), Duration::ZERO);
+        assert_eq!(backoff.delay(1), Duration::from_secs(60));
+        assert_eq!(backoff.delay(2), Duration::from_secs(120));
+        assert_eq!(backoff.delay(3), Duration::from_secs(240));
+        assert_eq!(backoff.delay(11), Duration::from_secs(60 << 10));
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let backoff = Backoff::default();
+        // 60 s * 2^11 is past a day.
+        assert_eq!(backoff.delay(12), backoff.max);
+        // The shift stops at 31 and the product saturates.
+        assert_eq!(backoff.delay(32), backoff.max);
+        assert_eq!(backoff.delay(u32::MAX), backoff.max);
+        let unbounded = Backoff {
+            base: Duration::from_secs(u64::MAX / 2),
+            max: Duration::MAX,
+        };
+        assert_eq!(unbounded.delay(u32::MAX), Duration::MAX);
+    }
+
+    #[test]
+    fn failing_addresses_back_off_until_the_delay_passed() {
+        let store = Store::open(":memory:").unwrap();
+        let failing = target(1);
+        let key = &failing.query_account;
+        assert!(!store.is_backing_off(key, 1_000).unwrap());
+
+        store.record_failure(&failing, "timeout", 1_000).unwrap();
+        assert!(store.is_backing_off(key, 1_059).unwrap());
+        assert!(!store.is_backing_off(key, 1_060).unwrap());
+
+        store.record_failure(&failing, "timeout", 1_060).unwrap();
+        assert!(store.is_backing_off(key, 1_179).unwrap());
+        assert!(!store.is_backing_off(key, 1_180).unwrap());
+
+        // Other addresses are not held back.
+        let other = target(2).query_account;
+        assert!(!store.is_backing_off(&other, 1_060).unwrap());
+
+        store
+            .record_success(key, &Signature::default(), Some(40), 1_180)
+            .unwrap();
+        assert!(!store.is_backing_off(key, 1_180).unwrap());
+        assert_eq!(store.last_score(key).unwrap(), Some(40));
+    }
+
+    #[test]
+    fn dead_lettered_after_consecutive_failures() {
+        let store = Store::open(":memory:").unwrap();
+        let target = target(1);
+        let key = &target.query_account;
+        for now in 1..store.dead_letter_after as i64 {
+            assert!(!store.record_failure(&target, "timeout", now).unwrap());
+        }
+        assert!(store.record_failure(&target, "gone", 10).unwrap());
+        assert!(store.is_dead_lettered(key).unwrap());
+        assert_eq!(store.dead_letters().unwrap()[0].failures, 5);
+
+        assert!(store.replay(key).unwrap());
+        assert!(!store.is_dead_lettered(key).unwrap());
+        assert!(!store.is_backing_off(key, 10).unwrap());
+        assert!(!store.replay(key).unwrap());
+    }
+}
@@ -0,0 +1,170 @@
+//! Several keepers in one process.
+//!
+//! Each `[tenants.<name>]` config section is a keeper of its own, layered over
+//! the shared sections: its payer, RPC pool, state database, checkpoint (next
+//! to the database) and alerts, labeled with its name. Tenants can point at
+//! different clusters; each endpoint is checked with `cluster::select`
+//! against the tenant's `[programs]` when the tenant is built, so every
+//! deployment must carry the program id this build targets.
+//!
+//! [`Tenants::run_cycles`] runs one cycle per tenant, one after the other. A
+//! tenant whose cycle fails (e.g. every endpoint of its cluster down) is
+//! reported without holding the others back.
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{read_keypair_file, Signer};
+use risk_oracle_client::cluster::{self, ClusterKind, ProgramIds};
+use risk_oracle_config::{Config, ProgramsConfig};
+
+use crate::alerts::Alerter;
+use crate::balance::BalanceMonitor;
+use crate::cycle::{CheckpointFile, CycleReport, Keeper};
+#[cfg(feature = "reqwest")]
+use crate::differential::Differential;
+use crate::http::HttpTransport;
+use crate::refresh::{QuoteSource, Target};
+use crate::rpc::RpcPool;
+use crate::schedule::SlotSchedule;
+use crate::store::Store;
+use crate::KeeperError;
+
+/// A named keeper.
+pub struct Tenant<Q> {
+    pub name: String,
+    /// Cluster its endpoint serves.
+    pub cluster: ClusterKind,
+    pub keeper: Keeper<Q>,
+    pub checkpoint: CheckpointFile,
+}
+
+impl<Q: QuoteSource> Tenant<Q> {
+    /// Tenant `name` built from its layered `config` (an entry of
+    /// `Config::tenants`), taking its quotes from `quotes` and posting its
+    /// alerts through `http`.
+    pub fn from_config(
+        name: &str,
+        config: &Config,
+        quotes: Q,
+        http: Arc<dyn HttpTransport>,
+    ) -> Result<Self, KeeperError> {
+        let path = config
+            .keeper
+            .payer
+            .as_ref()
+            .ok_or_else(|| KeeperError::Payer(format!("tenant {name} has no keeper.payer")))?;
+        let payer = read_keypair_file(path)
+            .map_err(|err| KeeperError::Payer(format!("{}: {err}", path.display())))?;
+
+        let rpc = RpcClient::new(config.rpc.url.clone());
+        let (cluster, _) = cluster::select(&rpc, &program_ids(&config.programs)?)?;
+
+        let mut pool = RpcPool::from_config(config);
+        let transactions = pool.transaction_options(&config.transactions)?;
+        let mut store = Store::open(&config.keeper.state_db)?;
+        store.dead_letter_after = config.keeper.dead_letter_after;
+        let mut alerter = Alerter::with_transport(config.alerts.clone(), http);
+        alerter.tenant = Some(name.to_string());
+        #[cfg(feature = "reqwest")]
+        let differential = config
+            .keeper
+            .differential
+            .as_ref()
+            .map(Differential::from_config)
+            .transpose()?;
+        #[cfg(not(feature = "reqwest"))]
+        let differential = None;
+        let schedule = config
+            .keeper
+            .schedule
+            .as_ref()
+            .map(|schedule| SlotSchedule::new(schedule, &payer.pubkey()));
+
+        Ok(Self {
+            name: name.to_string(),
+            cluster,
+            keeper: Keeper {
+                pool,
+                quotes,
+                payer,
+                fees: (&config.keeper.priority_fee).into(),
+                transactions,
+                store,
+                alerter,
+                max_cycle_lamports: config.keeper.max_cycle_lamports,
+                differential,
+                schedule,
+                health_check_secs: config.keeper.health_check.map(|check| check.interval_secs),
+                balances: config
+                    .keeper
+                    .balances
+                    .map(|balances| BalanceMonitor::new(&balances, cluster)),
+            },
+            checkpoint: CheckpointFile::new(
+                config.keeper.state_db.with_extension("checkpoint.json"),
+            ),
+        })
+    }
+}
+
+/// The tenants of one process, in name order.
+pub struct Tenants<Q>(pub Vec<Tenant<Q>>);
+
+impl<Q: QuoteSource> Tenants<Q> {
+    /// Every tenant of `config`, each taking its quotes from `quotes(name,
+    /// tenant config)`, all posting alerts through `http`.
+    pub fn from_config(
+        config: &Config,
+        mut quotes: impl FnMut(&str, &Config) -> Q,
+        http: Arc<dyn HttpTransport>,
+    ) -> Result<Self, KeeperError> {
+        config
+            .tenants
+            .iter()
+            .map(|(name, tenant)| {
+                Tenant::from_config(name, tenant, quotes(name, tenant), Arc::clone(&http))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    /// Runs a cycle for each tenant over its own targets
+    /// (`targets(name)`), stopping before the next tenant on shutdown.
+    /// Returns each tenant's name with its cycle outcome.
+    pub fn run_cycles(
+        &mut self,
+        mut targets: impl FnMut(&str) -> Vec<Target>,
+        shutdown: &AtomicBool,
+    ) -> Vec<(String, Result<CycleReport, KeeperError>)> {
+        let mut reports = Vec::new();
+        for tenant in &mut self.0 {
+            if shutdown.load(Ordering::Relaxed) {
+                break;
+            }
+            let targets = targets(&tenant.name);
+            let report = tenant.keeper.run_cycle(&targets, &tenant.checkpoint, shutdown);
+            reports.push((tenant.name.clone(), report));
+        }
+        reports
+    }
+}
+
+/// The `[programs]` ids, [`ProgramIds::default`] where unset.
+fn program_ids(config: &ProgramsConfig) -> Result<ProgramIds, KeeperError> {
+    let parse = |id: &Option<String>, default: Option<Pubkey>| match id {
+        Some(id) => Pubkey::from_str(id)
+            .map(Some)
+            .map_err(|_| KeeperError::InvalidAddress(id.clone())),
+        None => Ok(default),
+    };
+    let defaults = ProgramIds::default();
+    Ok(ProgramIds {
+        mainnet: parse(&config.mainnet, defaults.mainnet)?,
+        devnet: parse(&config.devnet, defaults.devnet)?,
+        localnet: parse(&config.localnet, defaults.localnet)?,
+    })
+}
@@ -0,0 +1,95 @@
+//! Pins every feed template on the real Switchboard gateway (Crossbar) and
+//! checks it reports the feed id the shared crate derives off-chain, the id
+//! both programs derive on-chain. Catches encoding differences between
+//! `feed::encode` and Switchboard's own tooling before a deployment does.
+//!
+//! Needs network access, so only built with the `network` feature:
+//!
+//! ```text
+//! cargo test -p risk-oracle-keeper --features network --test gateway_feed_id
+//! ```
+//!
+//! `CROSSBAR_URL` points it at another gateway than Switchboard's.
+#![cfg(feature = "network")]
+
+use std::time::Duration;
+
+use anchor_client::solana_sdk::hash::hash;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use risk_oracle_shared::diagnostics::hex;
+use risk_oracle_shared::feed::{self, NetworkSet};
+use risk_oracle_shared::proto::OracleFeed;
+use serde_json::{json, Value};
+
+const DEFAULT_CROSSBAR_URL: &str = "https://crossbar.switchboard.xyz";
+/// Endpoint of Crossbar's `storeOracleFeed`.
+const STORE_PATH: &str = "/v2/store";
+/// Screened wallet of the templates; pinning doesn't run the feed, any
+/// address does.
+const ADDRESS: Pubkey = Pubkey::new_from_array([0x2b; 32]);
+
+/// Feed hash the gateway pins `feed` under, hex without `0x`.
+fn pinned_feed_hash(feed: &OracleFeed) -> String {
+    let url = std::env::var("CROSSBAR_URL").unwrap_or_else(|_| DEFAULT_CROSSBAR_URL.into());
+    let response: Value = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .unwrap()
+        .post(format!("{}{}", url.trim_end_matches('/'), STORE_PATH))
+        .json(&json!({ "feed": STANDARD.encode(feed::encode(feed)) }))
+        .send()
+        .unwrap()
+        .error_for_status()
+        .unwrap()
+        .json()
+        .unwrap();
+    let feed_hash = response["feedHash"]
+        .as_str()
+        .or_else(|| response["feed_hash"].as_str())
+        .unwrap_or_else(|| panic!("no feed hash in {}", response));
+    feed_hash.trim_start_matches("0x").to_lowercase()
+}
+
+fn assert_same_feed_id(template: &str, feed: &OracleFeed) {
+    let derived = hex(&hash(&feed::encode(feed)).to_bytes());
+    assert_eq!(
+        pinned_feed_hash(feed),
+        derived,
+        "the gateway pinned the {} feed under another id than the programs derive",
+        template
+    );
+}
+
+#[test]
+fn risk_score_feed() {
+    assert_same_feed_id("risk score", &feed::risk_score_feed(&ADDRESS.to_bytes()));
+}
+
+#[test]
+fn composite_risk_score_feed() {
+    let networks =
+        NetworkSet::from_bits(NetworkSet::SOLANA.bits() | NetworkSet::ECLIPSE.bits()).unwrap();
+    assert_same_feed_id(
+        "composite risk score",
+        &feed::composite_risk_score_feed(&ADDRESS.to_bytes(), networks),
+    );
+}
+
+#[test]
+fn score_and_flag_feeds() {
+    for feed in feed::score_and_flag_feeds(&ADDRESS.to_bytes()) {
+        assert_same_feed_id("score and flag", &feed);
+    }
+}
+
+#[test]
+fn pass_fail_feed() {
+    assert_same_feed_id("pass/fail", &feed::pass_fail_feed(&ADDRESS.to_bytes(), 50));
+}
+
+#[test]
+fn test_feed() {
+    assert_same_feed_id("test", &feed::test_feed());
+}
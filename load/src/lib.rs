@@ -0,0 +1,12 @@
+//! Sustained verification load against a local validator.
+//!
+//! `risk-oracle-load` sends verifications at a fixed rate, each with a quote
+//! signed by the fixture oracles ([`risk_oracle_client::fixtures`]) for the
+//! validator's latest slot hash, and reports how many landed, what they cost
+//! and how they spread over slots. Which addresses are screened follows a
+//! [`plan::Plan`]: a share of the load can be concentrated on a few hot
+//! addresses, so the landed rate of transactions writing the same per-address
+//! PDA compares with that of transactions that don't ([`report`]).
+
+pub mod plan;
+pub mod report;
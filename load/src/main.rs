@@ -0,0 +1,292 @@
+//! `risk-oracle-load`: sends verifications at a fixed rate to a local
+//! validator and reports the landed rate, compute units and how the
+//! transactions spread over slots and addresses.
+//!
+//! The validator needs the program deployed with its config initialized (a
+//! threshold passing `--score`), the fixture queue for the same `--oracles`
+//! in its genesis, and a funded payer:
+//!
+//! ```text
+//! cargo run -p risk-oracle-cli -- fixture queue --oracles 1 --out queue.json
+//! solana-test-validator --account <QUEUE> queue.json --bpf-program <PROGRAM_ID> target/deploy/anchor_oracle_example.so
+//! cargo run -p risk-oracle-load -- --tps 200 --duration 30 --addresses 1000 --hot 4 --hot-percent 20 --anti-replay
+//! ```
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::{RpcSendTransactionConfig, RpcTransactionConfig};
+use anchor_client::solana_sdk::account::from_account;
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::hash::Hash;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use anchor_client::solana_sdk::slot_hashes::SlotHashes;
+use anchor_client::solana_sdk::sysvar;
+use anchor_client::solana_sdk::transaction::Transaction;
+use anyhow::{anyhow, ensure, Context, Result};
+use clap::{Parser, ValueEnum};
+use risk_oracle_client::fixtures;
+use risk_oracle_client::instructions::{self, VerifyOptions};
+use risk_oracle_client::quote::QuoteFeed;
+use risk_oracle_load::plan::{self, Plan};
+use risk_oracle_load::report::{Outcome, Report, Sample};
+use risk_oracle_shared::{errors, feed, profiles};
+use sha2::{Digest, Sha256};
+use solana_transaction_status::UiTransactionEncoding;
+
+/// `getSignatureStatuses` limit.
+const MAX_SIGNATURE_STATUSES: usize = 256;
+/// Slot hashes are re-read this often, about once a slot.
+const SLOT_HASH_REFRESH: Duration = Duration::from_millis(400);
+/// Blockhashes are re-read this often, well within their validity.
+const BLOCKHASH_REFRESH: Duration = Duration::from_secs(5);
+
+#[derive(Parser)]
+#[command(name = "risk-oracle-load", about)]
+struct Cli {
+    #[arg(long, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Fee payer keypair.
+    #[arg(long, default_value = "~/.config/solana/id.json")]
+    keypair: PathBuf,
+    /// Transactions sent per second.
+    #[arg(long, default_value_t = 100.0)]
+    tps: f64,
+    /// Seconds of load.
+    #[arg(long, default_value_t = 30)]
+    duration: u64,
+    /// Addresses screened, the hot ones included.
+    #[arg(long, default_value_t = 1000)]
+    addresses: usize,
+    /// Addresses taking `--hot-percent` of the transactions.
+    #[arg(long, default_value_t = 0)]
+    hot: usize,
+    #[arg(long, default_value_t = 0)]
+    hot_percent: u8,
+    /// Pass the payer and the replay record, written by every verification.
+    #[arg(long)]
+    anti_replay: bool,
+    /// Fixture oracles signing the quotes, as loaded in the queue.
+    #[arg(long, default_value_t = 1)]
+    oracles: u8,
+    /// Score of the quotes (1–100).
+    #[arg(long, default_value_t = 25)]
+    score: u8,
+    /// Seconds to wait for the last transactions before reading statuses.
+    #[arg(long, default_value_t = 10)]
+    settle: u64,
+    /// Landed transactions whose compute units are fetched.
+    #[arg(long, default_value_t = 200)]
+    compute_unit_samples: usize,
+    #[arg(long, value_enum, default_value_t = Format::Markdown)]
+    format: Format,
+    /// Writes the report to this file instead of stdout.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Markdown,
+    Json,
+}
+
+/// A transaction sent, or refused by the RPC node.
+struct Sent {
+    address: usize,
+    signature: Option<Signature>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    ensure!(cli.tps > 0.0, "--tps must be positive");
+    ensure!(
+        (1..=feed::MAX_SCORE).contains(&cli.score),
+        "--score must be 1..={}",
+        feed::MAX_SCORE
+    );
+    let plan = Plan::new(cli.addresses, cli.hot, cli.hot_percent)
+        .ok_or_else(|| anyhow!("--hot must be under --addresses, --hot-percent at most 100"))?;
+    let keypair = expand_home(cli.keypair);
+    let payer = read_keypair_file(&keypair)
+        .map_err(|err| anyhow!("failed to read {}: {}", keypair.display(), err))?;
+    let oracles: Vec<Keypair> = (0..cli.oracles).map(fixtures::oracle_keypair).collect();
+    let rpc = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+
+    let addresses: Vec<Pubkey> = (0..plan.addresses)
+        .map(|index| Pubkey::new_from_array(plan::address(index)))
+        .collect();
+    let feeds: Vec<QuoteFeed> = addresses
+        .iter()
+        .map(|address| {
+            let feed = feed::risk_score_feed(&address.to_bytes());
+            QuoteFeed {
+                feed_id: Sha256::digest(feed::encode(&feed)).into(),
+                value: 0,
+                min_oracle_samples: feed.min_oracle_samples.unwrap_or(1) as u8,
+            }
+        })
+        .collect();
+    let opts = VerifyOptions {
+        payer: cli.anti_replay.then(|| payer.pubkey()),
+        anti_replay: cli.anti_replay,
+        ..VerifyOptions::default()
+    };
+
+    let interval = Duration::from_secs_f64(1.0 / cli.tps);
+    let total = (cli.tps * cli.duration as f64) as u64;
+    let send_config = RpcSendTransactionConfig {
+        skip_preflight: true,
+        max_retries: Some(0),
+        ..RpcSendTransactionConfig::default()
+    };
+    let mut sent = Vec::with_capacity(total as usize);
+    let mut slot_hash = latest_slot_hash(&rpc)?;
+    let mut blockhash = rpc.get_latest_blockhash()?;
+    let (mut slot_hash_read, mut blockhash_read) = (Instant::now(), Instant::now());
+    let start = Instant::now();
+    for n in 0..total {
+        if slot_hash_read.elapsed() >= SLOT_HASH_REFRESH {
+            slot_hash = latest_slot_hash(&rpc)?;
+            slot_hash_read = Instant::now();
+        }
+        if blockhash_read.elapsed() >= BLOCKHASH_REFRESH {
+            blockhash = rpc.get_latest_blockhash()?;
+            blockhash_read = Instant::now();
+        }
+
+        let address = plan.address(n);
+        let quote_feed = QuoteFeed {
+            value: plan::quote_value(cli.score, n),
+            ..feeds[address]
+        };
+        let (slot, hash) = slot_hash;
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                fixtures::quote_instruction(&oracles, hash.to_bytes(), slot, &[quote_feed]),
+                instructions::verify_risk_score_feed_compact(addresses[address], &opts),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            blockhash,
+        );
+        let signature = rpc
+            .send_transaction_with_config(&transaction, send_config)
+            .ok();
+        sent.push(Sent { address, signature });
+
+        if let Some(wait) = (interval * (n + 1) as u32).checked_sub(start.elapsed()) {
+            thread::sleep(wait);
+        }
+    }
+    let elapsed = start.elapsed();
+
+    thread::sleep(Duration::from_secs(cli.settle));
+    let mut samples = outcomes(&rpc, &plan, &sent)?;
+    compute_units(&rpc, &sent, &mut samples, cli.compute_unit_samples);
+
+    let report = Report::new(
+        profiles::ACTIVE.name,
+        cli.anti_replay,
+        cli.tps,
+        elapsed,
+        &samples,
+    );
+    let text = match cli.format {
+        Format::Markdown => report.markdown(),
+        Format::Json => report.json(),
+    };
+    match cli.out {
+        Some(path) => {
+            fs::write(&path, text).with_context(|| format!("failed to write {}", path.display()))
+        }
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+/// Newest entry of the cluster's SlotHashes, the one quotes are signed for.
+fn latest_slot_hash(rpc: &RpcClient) -> Result<(u64, Hash)> {
+    let account = rpc.get_account(&sysvar::slot_hashes::ID)?;
+    let slot_hashes: SlotHashes =
+        from_account(&account).ok_or_else(|| anyhow!("unreadable SlotHashes sysvar"))?;
+    slot_hashes
+        .first()
+        .copied()
+        .ok_or_else(|| anyhow!("empty SlotHashes sysvar"))
+}
+
+/// Where each transaction ended up. Transactions the RPC node refused count
+/// as dropped.
+fn outcomes(rpc: &RpcClient, plan: &Plan, sent: &[Sent]) -> Result<Vec<Sample>> {
+    let signatures: Vec<Signature> = sent.iter().filter_map(|sent| sent.signature).collect();
+    let mut statuses = HashMap::new();
+    for chunk in signatures.chunks(MAX_SIGNATURE_STATUSES) {
+        let response = rpc.get_signature_statuses(chunk)?;
+        statuses.extend(chunk.iter().zip(response.value));
+    }
+
+    Ok(sent
+        .iter()
+        .map(|sent| {
+            let status = sent
+                .signature
+                .and_then(|signature| statuses.get(&signature).cloned().flatten());
+            Sample {
+                address: sent.address,
+                hot: plan.is_hot(sent.address),
+                outcome: match status {
+                    Some(status) => match status.err {
+                        None => Outcome::Landed { slot: status.slot },
+                        Some(err) => Outcome::Failed {
+                            slot: status.slot,
+                            error: errors::describe(&err.to_string()),
+                        },
+                    },
+                    None => Outcome::Dropped,
+                },
+                compute_units: None,
+            }
+        })
+        .collect())
+}
+
+/// Fetches the compute units of the first `limit` landed transactions. A
+/// transaction the node can't return is left without.
+fn compute_units(rpc: &RpcClient, sent: &[Sent], samples: &mut [Sample], limit: usize) {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let landed = sent
+        .iter()
+        .zip(samples.iter_mut())
+        .filter(|(_, sample)| matches!(sample.outcome, Outcome::Landed { .. }))
+        .take(limit);
+    for (sent, sample) in landed {
+        let Some(signature) = sent.signature else {
+            continue;
+        };
+        sample.compute_units = rpc
+            .get_transaction_with_config(&signature, config)
+            .ok()
+            .and_then(|transaction| transaction.transaction.meta)
+            .and_then(|meta| Option::from(meta.compute_units_consumed));
+    }
+}
+
+/// Resolves a leading `~/` against `$HOME`, as the shell would have.
+fn expand_home(path: PathBuf) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path,
+    }
+}
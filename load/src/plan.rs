@@ -0,0 +1,74 @@
+//! Which address each transaction screens, and the quote it carries.
+//!
+//! Addresses are deterministic, so two runs screen the same wallets, and
+//! never exist on-chain: a missing account screens as a wallet. Transactions
+//! screening the same address in the same slot would be byte-identical and
+//! deduplicated by the validator, so every quote carries a nonce in the
+//! fraction of its value, below what changes the score.
+
+use risk_oracle_shared::feed;
+use sha2::{Digest, Sha256};
+
+/// Domain of the screened addresses.
+const ADDRESS_DOMAIN: &[u8] = b"risk-oracle-load";
+
+/// How the load spreads over the screened addresses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Plan {
+    /// Addresses screened, the hot ones included.
+    pub addresses: usize,
+    /// Addresses `0..hot` take `hot_percent` of the transactions.
+    pub hot: usize,
+    pub hot_percent: u8,
+}
+
+impl Plan {
+    /// `None` unless there are addresses, `hot_percent` is a percentage and
+    /// hot and cold transactions both have addresses to go to.
+    pub fn new(addresses: usize, hot: usize, hot_percent: u8) -> Option<Self> {
+        let hot_percent = if hot == 0 { 0 } else { hot_percent };
+        let valid = addresses > 0
+            && hot <= addresses
+            && hot_percent <= 100
+            && (hot < addresses || hot_percent == 100);
+        valid.then_some(Self {
+            addresses,
+            hot,
+            hot_percent,
+        })
+    }
+
+    /// Index of the address transaction `n` screens. Hot transactions are
+    /// interleaved evenly with the others and go round the hot addresses;
+    /// the others go round the rest.
+    pub fn address(&self, n: u64) -> usize {
+        let percent = u64::from(self.hot_percent);
+        let hot_before = n * percent / 100;
+        if (n + 1) * percent / 100 > hot_before {
+            (hot_before % self.hot as u64) as usize
+        } else {
+            let cold = (self.addresses - self.hot) as u64;
+            self.hot + ((n - hot_before) % cold) as usize
+        }
+    }
+
+    pub fn is_hot(&self, address: usize) -> bool {
+        address < self.hot
+    }
+}
+
+/// Screened wallet `index`.
+pub fn address(index: usize) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(ADDRESS_DOMAIN)
+        .chain_update((index as u64).to_le_bytes())
+        .finalize()
+        .into()
+}
+
+/// Feed value of a quote for `score` (1–100), unique per `nonce`: the nonce
+/// is taken off the fraction, which the program rounds back up to `score`.
+pub fn quote_value(score: u8, nonce: u64) -> i128 {
+    let scale = 10i128.pow(feed::VALUE_DECIMALS);
+    i128::from(score) * scale - i128::from(nonce) % scale
+}
@@ -0,0 +1,259 @@
+//! What a load run measured, as Markdown or as JSON.
+//!
+//! Landed transactions are counted per slot: with verifications that write
+//! no shared account, the validator can land many of them in one slot.
+//! Hot transactions are compared with the others: under `--anti-replay`
+//! each one writes the replay record PDA of its address, so hot addresses
+//! serialize while the rest stay parallel.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// One transaction sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sample {
+    /// Index of the screened address in the plan.
+    pub address: usize,
+    pub hot: bool,
+    pub outcome: Outcome,
+    /// From the transaction's metadata, for the landed transactions fetched.
+    pub compute_units: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Landed {
+        slot: u64,
+    },
+    /// Processed with an error, named after the program's error code.
+    Failed {
+        slot: u64,
+        error: String,
+    },
+    /// Never processed: dropped by the validator or expired.
+    Dropped,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Report {
+    pub profile: &'static str,
+    pub anti_replay: bool,
+    pub target_tps: f64,
+    /// Rate the transactions were actually sent at.
+    pub sent_tps: f64,
+    pub sent: usize,
+    pub landed: usize,
+    pub failed: usize,
+    pub dropped: usize,
+    pub landed_percent: f64,
+    /// Failed transactions by error.
+    pub errors: BTreeMap<String, usize>,
+    pub compute_units: Option<ComputeUnits>,
+    pub slots: Slots,
+    pub hot: Class,
+    pub cold: Class,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ComputeUnits {
+    pub samples: usize,
+    pub min: u64,
+    pub p50: u64,
+    pub p95: u64,
+    pub max: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Slots {
+    /// Slots with at least one landed transaction.
+    pub slots: usize,
+    pub mean_landed: f64,
+    pub max_landed: usize,
+    /// Most transactions screening one address landed in one slot.
+    pub max_landed_same_address: usize,
+}
+
+/// Transactions to hot or to cold addresses.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Class {
+    pub sent: usize,
+    pub landed: usize,
+    pub landed_percent: f64,
+}
+
+impl Report {
+    pub fn new(
+        profile: &'static str,
+        anti_replay: bool,
+        target_tps: f64,
+        elapsed: Duration,
+        samples: &[Sample],
+    ) -> Self {
+        let mut errors = BTreeMap::new();
+        let mut per_slot: BTreeMap<u64, usize> = BTreeMap::new();
+        let mut per_address_slot: HashMap<(usize, u64), usize> = HashMap::new();
+        let (mut landed, mut failed) = (0, 0);
+        for sample in samples {
+            match &sample.outcome {
+                Outcome::Landed { slot } => {
+                    landed += 1;
+                    *per_slot.entry(*slot).or_default() += 1;
+                    *per_address_slot.entry((sample.address, *slot)).or_default() += 1;
+                }
+                Outcome::Failed { error, .. } => {
+                    failed += 1;
+                    *errors.entry(error.clone()).or_default() += 1;
+                }
+                Outcome::Dropped => {}
+            }
+        }
+
+        let seconds = elapsed.as_secs_f64();
+        Self {
+            profile,
+            anti_replay,
+            target_tps,
+            sent_tps: if seconds > 0.0 {
+                samples.len() as f64 / seconds
+            } else {
+                0.0
+            },
+            sent: samples.len(),
+            landed,
+            failed,
+            dropped: samples.len() - landed - failed,
+            landed_percent: percent(landed, samples.len()),
+            errors,
+            compute_units: compute_units(samples),
+            slots: Slots {
+                slots: per_slot.len(),
+                mean_landed: if per_slot.is_empty() {
+                    0.0
+                } else {
+                    landed as f64 / per_slot.len() as f64
+                },
+                max_landed: per_slot.values().copied().max().unwrap_or(0),
+                max_landed_same_address: per_address_slot.values().copied().max().unwrap_or(0),
+            },
+            hot: Class::of(samples.iter().filter(|sample| sample.hot)),
+            cold: Class::of(samples.iter().filter(|sample| !sample.hot)),
+        }
+    }
+
+    pub fn json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("report serializes")
+    }
+
+    pub fn markdown(&self) -> String {
+        let mut out = format!(
+            "# Verification load ({} profile{})\n\n",
+            self.profile,
+            if self.anti_replay {
+                ", anti-replay"
+            } else {
+                ""
+            }
+        );
+        out.push_str("| | |\n|---|---|\n");
+        let mut row = |name: &str, value: String| {
+            writeln!(out, "| {} | {} |", name, value).unwrap();
+        };
+        row(
+            "sent",
+            format!(
+                "{} ({:.1} TPS, target {:.1})",
+                self.sent, self.sent_tps, self.target_tps
+            ),
+        );
+        row(
+            "landed",
+            format!("{} ({:.1}%)", self.landed, self.landed_percent),
+        );
+        row("failed", self.failed.to_string());
+        row("dropped", self.dropped.to_string());
+        if let Some(cu) = &self.compute_units {
+            row(
+                "compute units",
+                format!(
+                    "min {} p50 {} p95 {} max {} ({} transactions)",
+                    cu.min, cu.p50, cu.p95, cu.max, cu.samples
+                ),
+            );
+        }
+        row(
+            "landed per slot",
+            format!(
+                "mean {:.1} max {} over {} slots",
+                self.slots.mean_landed, self.slots.max_landed, self.slots.slots
+            ),
+        );
+        row(
+            "same address per slot",
+            format!("max {}", self.slots.max_landed_same_address),
+        );
+        for (name, class) in [("hot addresses", &self.hot), ("cold addresses", &self.cold)] {
+            if class.sent > 0 {
+                row(
+                    name,
+                    format!(
+                        "{} / {} landed ({:.1}%)",
+                        class.landed, class.sent, class.landed_percent
+                    ),
+                );
+            }
+        }
+
+        if !self.errors.is_empty() {
+            out.push_str("\n## Errors\n\n| error | transactions |\n|---|---|\n");
+            for (error, count) in &self.errors {
+                writeln!(out, "| {} | {} |", error, count).unwrap();
+            }
+        }
+        out
+    }
+}
+
+impl Class {
+    fn of<'a>(samples: impl Iterator<Item = &'a Sample>) -> Self {
+        let (mut sent, mut landed) = (0, 0);
+        for sample in samples {
+            sent += 1;
+            landed += matches!(sample.outcome, Outcome::Landed { .. }) as usize;
+        }
+        Self {
+            sent,
+            landed,
+            landed_percent: percent(landed, sent),
+        }
+    }
+}
+
+fn compute_units(samples: &[Sample]) -> Option<ComputeUnits> {
+    let mut units: Vec<u64> = samples
+        .iter()
+        .filter_map(|sample| sample.compute_units)
+        .collect();
+    if units.is_empty() {
+        return None;
+    }
+    units.sort_unstable();
+    let at = |percentile: usize| units[(units.len() - 1) * percentile / 100];
+    Some(ComputeUnits {
+        samples: units.len(),
+        min: at(0),
+        p50: at(50),
+        p95: at(95),
+        max: at(100),
+    })
+}
+
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
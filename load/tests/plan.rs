@@ -0,0 +1,47 @@
+//! Load plan: hot transactions take their share, spread evenly, and every
+//! quote stays unique without changing its score.
+
+use std::collections::HashSet;
+
+use risk_oracle_load::plan::{self, Plan};
+use risk_oracle_shared::feed;
+
+#[test]
+fn hot_addresses_take_their_share() {
+    let plan = Plan::new(8, 2, 25).unwrap();
+    let mut counts = [0; 8];
+    for n in 0..400 {
+        counts[plan.address(n)] += 1;
+    }
+    assert_eq!(counts, [50; 8]);
+    assert!(plan.is_hot(1) && !plan.is_hot(2));
+}
+
+#[test]
+fn plans_without_addresses_for_their_load_are_rejected() {
+    assert_eq!(Plan::new(0, 0, 0), None);
+    assert_eq!(Plan::new(4, 5, 50), None);
+    assert_eq!(Plan::new(4, 4, 50), None);
+    assert_eq!(Plan::new(4, 1, 101), None);
+    assert!(Plan::new(4, 4, 100).is_some());
+    assert_eq!(Plan::new(4, 0, 50).unwrap().hot_percent, 0);
+}
+
+#[test]
+fn addresses_are_distinct() {
+    let addresses: HashSet<_> = (0..1000).map(plan::address).collect();
+    assert_eq!(addresses.len(), 1000);
+}
+
+#[test]
+fn quote_values_are_unique_and_keep_the_score() {
+    let scale = 10u64.pow(feed::VALUE_DECIMALS);
+    for score in [1, 25, feed::MAX_SCORE] {
+        for nonce in [0, 1, scale - 1, scale, u64::MAX] {
+            let value = plan::quote_value(score, nonce);
+            assert_eq!(feed::score_from_feed_value(value), score);
+        }
+    }
+    let values: HashSet<_> = (0..100).map(|nonce| plan::quote_value(25, nonce)).collect();
+    assert_eq!(values.len(), 100);
+}
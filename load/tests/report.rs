@@ -0,0 +1,65 @@
+//! Load report: outcomes counted, landed transactions per slot and per
+//! address, hot and cold classes, compute unit percentiles.
+
+use std::time::Duration;
+
+use risk_oracle_load::report::{Outcome, Report, Sample};
+
+fn sample(address: usize, hot: bool, outcome: Outcome, compute_units: Option<u64>) -> Sample {
+    Sample {
+        address,
+        hot,
+        outcome,
+        compute_units,
+    }
+}
+
+#[test]
+fn report_counts_outcomes_per_slot_and_class() {
+    let failed = Outcome::Failed {
+        slot: 11,
+        error: "QuoteReplayed".to_string(),
+    };
+    let samples = [
+        sample(0, true, Outcome::Landed { slot: 10 }, Some(30_000)),
+        sample(0, true, failed.clone(), None),
+        sample(0, true, failed, None),
+        sample(1, false, Outcome::Landed { slot: 10 }, Some(31_000)),
+        sample(2, false, Outcome::Landed { slot: 10 }, None),
+        sample(3, false, Outcome::Landed { slot: 11 }, Some(29_000)),
+        sample(3, false, Outcome::Landed { slot: 11 }, Some(29_500)),
+        sample(4, false, Outcome::Dropped, None),
+    ];
+    let report = Report::new("devnet", true, 8.0, Duration::from_secs(2), &samples);
+
+    assert_eq!(report.sent, 8);
+    assert_eq!(report.sent_tps, 4.0);
+    assert_eq!((report.landed, report.failed, report.dropped), (5, 2, 1));
+    assert_eq!(report.landed_percent, 62.5);
+    assert_eq!(report.errors.get("QuoteReplayed"), Some(&2));
+
+    assert_eq!(report.slots.slots, 2);
+    assert_eq!(report.slots.mean_landed, 2.5);
+    assert_eq!(report.slots.max_landed, 3);
+    assert_eq!(report.slots.max_landed_same_address, 2);
+
+    assert_eq!((report.hot.sent, report.hot.landed), (3, 1));
+    assert_eq!((report.cold.sent, report.cold.landed), (5, 4));
+
+    let compute_units = report.compute_units.unwrap();
+    assert_eq!(compute_units.samples, 4);
+    assert_eq!(
+        (compute_units.min, compute_units.p50, compute_units.max),
+        (29_000, 29_500, 31_000)
+    );
+    assert!(report.markdown().contains("| QuoteReplayed | 2 |"));
+}
+
+#[test]
+fn report_of_nothing_landed_has_no_compute_units() {
+    let samples = [sample(0, false, Outcome::Dropped, None)];
+    let report = Report::new("devnet", false, 1.0, Duration::from_secs(1), &samples);
+    assert_eq!(report.compute_units, None);
+    assert_eq!(report.slots.mean_landed, 0.0);
+    assert_eq!(report.landed_percent, 0.0);
+}
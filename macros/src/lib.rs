@@ -0,0 +1,253 @@
+//! Attribute macros gating an integrator's Anchor instructions on the risk
+//! oracle, so a handler takes the risk check with one line:
+//!
+//! ```ignore
+//! use risk_oracle_macros::{risk_gate_accounts, risk_gated};
+//!
+//! #[risk_gate_accounts]
+//! #[derive(Accounts)]
+//! pub struct Deposit<'info> {
+//!     #[account(mut)]
+//!     pub user: Signer<'info>,
+//! }
+//!
+//! #[risk_gated(threshold = 50)]
+//! pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+//!     // Only reached when the screened account scores 50 or less.
+//!     Ok(())
+//! }
+//! ```
+//!
+//! `#[risk_gate_accounts]` adds the oracle's accounts to the struct, and
+//! `#[risk_gated]` verifies the transaction's quote through CPI
+//! (`anchor_oracle_example::gate`) before the handler body runs. Both expand
+//! to paths of `anchor_lang` and `anchor_oracle_example`, which the integrator
+//! depends on, the latter with its `cpi` feature. The screened account is the
+//! injected `risk_query_account`, or an account of the struct named by
+//! `account = <field>` on both attributes.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, parse_quote, Error, Expr, ExprLit, Fields, FnArg, Ident, ItemFn, ItemStruct,
+    Lit, Meta, Pat, Result, Token,
+};
+
+/// Highest score of the feed scale.
+const MAX_SCORE: u8 = 100;
+
+/// Field of the injected screened account.
+const QUERY_ACCOUNT: &str = "risk_query_account";
+
+/// Arguments of the attributes.
+struct Args {
+    threshold: Option<u8>,
+    account: Option<Ident>,
+}
+
+impl Args {
+    fn parse(attr: TokenStream) -> Result<Self> {
+        let metas = Punctuated::<Meta, Token![,]>::parse_terminated.parse(attr)?;
+        let mut args = Args {
+            threshold: None,
+            account: None,
+        };
+        for meta in metas {
+            let Meta::NameValue(pair) = &meta else {
+                return Err(Error::new_spanned(meta, "expected `name = value`"));
+            };
+            if pair.path.is_ident("threshold") {
+                let threshold = match &pair.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Int(int), ..
+                    }) => int.base10_parse::<u8>().ok(),
+                    _ => None,
+                };
+                match threshold {
+                    Some(threshold) if threshold <= MAX_SCORE => args.threshold = Some(threshold),
+                    _ => {
+                        return Err(Error::new_spanned(
+                            &pair.value,
+                            format!("`threshold` is a score from 0 to {}", MAX_SCORE),
+                        ))
+                    }
+                }
+            } else if pair.path.is_ident("account") {
+                match &pair.value {
+                    Expr::Path(path) if path.path.get_ident().is_some() => {
+                        args.account = path.path.get_ident().cloned();
+                    }
+                    value => {
+                        return Err(Error::new_spanned(value, "`account` is a field name"));
+                    }
+                }
+            } else {
+                return Err(Error::new_spanned(
+                    &pair.path,
+                    "expected `threshold` or `account`",
+                ));
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// Verifies the transaction's risk score quote before the handler runs, and
+/// fails with the oracle's `RiskThresholdExceeded` above `threshold` (0–100).
+///
+/// The handler's `Context` must hold the accounts of `#[risk_gate_accounts]`.
+/// `account = <field>` screens that account instead of `risk_query_account`.
+#[proc_macro_attribute]
+pub fn risk_gated(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut handler = parse_macro_input!(item as ItemFn);
+    match Args::parse(attr).and_then(|args| gate_handler(&mut handler, args)) {
+        Ok(()) => quote!(#handler).into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn gate_handler(handler: &mut ItemFn, args: Args) -> Result<()> {
+    let threshold = args
+        .threshold
+        .ok_or_else(|| Error::new(Span::call_site(), "missing `threshold = <score>`"))?;
+    let account = args
+        .account
+        .unwrap_or_else(|| format_ident!("{}", QUERY_ACCOUNT));
+    let ctx = match handler.sig.inputs.first() {
+        Some(FnArg::Typed(arg)) => match &*arg.pat {
+            Pat::Ident(ident) => ident.ident.clone(),
+            pat => return Err(Error::new_spanned(pat, "expected the `Context` argument")),
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &handler.sig,
+                "expected a handler taking a `Context` first",
+            ))
+        }
+    };
+
+    let check = parse_quote! {
+        ::anchor_oracle_example::gate::require_score_at_most(
+            ::anchor_oracle_example::gate::GateAccounts {
+                program: ::anchor_lang::ToAccountInfo::to_account_info(
+                    &#ctx.accounts.risk_oracle_program,
+                ),
+                config: ::anchor_lang::ToAccountInfo::to_account_info(&#ctx.accounts.risk_config),
+                queue: ::anchor_lang::ToAccountInfo::to_account_info(&#ctx.accounts.risk_queue),
+                slothashes: ::anchor_lang::ToAccountInfo::to_account_info(
+                    &#ctx.accounts.risk_slothashes,
+                ),
+                instructions: ::anchor_lang::ToAccountInfo::to_account_info(
+                    &#ctx.accounts.risk_instructions,
+                ),
+                query_account: ::anchor_lang::ToAccountInfo::to_account_info(
+                    &#ctx.accounts.#account,
+                ),
+                risk_ceiling: ::anchor_lang::ToAccountInfo::to_account_info(
+                    &#ctx.accounts.risk_ceiling,
+                ),
+            },
+            #threshold,
+        )?;
+    };
+    handler.block.stmts.insert(0, check);
+    Ok(())
+}
+
+/// Adds the accounts `#[risk_gated]` passes to the oracle to an Anchor
+/// accounts struct: `risk_oracle_program`, `risk_config`, `risk_queue`,
+/// `risk_slothashes`, `risk_instructions`, `risk_ceiling` and, unless
+/// `account = <field>` names an account the struct already has,
+/// `risk_query_account`. Goes above `#[derive(Accounts)]`.
+///
+/// The oracle checks every one of them; the program is checked by type.
+/// `risk_ceiling` is the integrator's `["risk_ceiling", crate::ID]` PDA of the
+/// oracle, derived by its seeds constraint.
+#[proc_macro_attribute]
+pub fn risk_gate_accounts(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut accounts = parse_macro_input!(item as ItemStruct);
+    match Args::parse(attr).and_then(|args| inject_accounts(&mut accounts, args)) {
+        Ok(()) => quote!(#accounts).into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn inject_accounts(accounts: &mut ItemStruct, args: Args) -> Result<()> {
+    if args.threshold.is_some() {
+        return Err(Error::new(
+            Span::call_site(),
+            "`threshold` goes on the `#[risk_gated]` handler",
+        ));
+    }
+    let lifetime = accounts
+        .generics
+        .lifetimes()
+        .next()
+        .map(|param| param.lifetime.clone())
+        .ok_or_else(|| Error::new_spanned(&accounts.ident, "expected an `'info` lifetime"))?;
+    let Fields::Named(fields) = &mut accounts.fields else {
+        return Err(Error::new_spanned(
+            &accounts.ident,
+            "expected a struct with named fields",
+        ));
+    };
+    if let Some(account) = &args.account {
+        if !fields
+            .named
+            .iter()
+            .any(|field| field.ident.as_ref() == Some(account))
+        {
+            return Err(Error::new_spanned(account, "no such account in the struct"));
+        }
+    }
+
+    let unchecked = quote!(::anchor_lang::prelude::UncheckedAccount<#lifetime>);
+    let mut injected = vec![
+        quote! {
+            pub risk_oracle_program: ::anchor_lang::prelude::Program<
+                #lifetime,
+                ::anchor_oracle_example::program::AnchorOracleExample,
+            >
+        },
+        quote! {
+            /// CHECK: checked by the risk oracle.
+            pub risk_config: #unchecked
+        },
+        quote! {
+            /// CHECK: checked by the risk oracle.
+            pub risk_queue: #unchecked
+        },
+        quote! {
+            /// CHECK: checked by the risk oracle.
+            pub risk_slothashes: #unchecked
+        },
+        quote! {
+            /// CHECK: checked by the risk oracle.
+            pub risk_instructions: #unchecked
+        },
+        quote! {
+            /// CHECK: checked by the risk oracle, which reads it under risk
+            /// ceilings.
+            #[account(
+                seeds = [::anchor_oracle_example::state::RISK_CEILING_SEED, crate::ID.as_ref()],
+                bump,
+                seeds::program = ::anchor_oracle_example::ID
+            )]
+            pub risk_ceiling: #unchecked
+        },
+    ];
+    if args.account.is_none() {
+        let query_account = format_ident!("{}", QUERY_ACCOUNT);
+        injected.push(quote! {
+            /// CHECK: screened by the risk oracle.
+            pub #query_account: #unchecked
+        });
+    }
+    for field in injected {
+        fields.named.push(syn::Field::parse_named.parse2(field)?);
+    }
+    Ok(())
+}
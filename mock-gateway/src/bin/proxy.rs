@@ -0,0 +1,66 @@
+//! `risk-oracle-gateway-proxy`: caches and deduplicates quote requests of
+//! several services in front of one gateway, see
+//! `risk_oracle_mock_gateway::proxy`.
+
+use std::fs;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use risk_oracle_mock_gateway::proxy::{self, Proxy, METRICS_PATH};
+use risk_oracle_mock_gateway::QUOTE_PATH;
+
+#[derive(Parser)]
+#[command(name = "risk-oracle-gateway-proxy", version, about)]
+struct Args {
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8789")]
+    bind: String,
+    /// Gateway base URL the requests are forwarded to.
+    #[arg(long)]
+    upstream: String,
+    /// Teams (JSON) with their tokens and quotas; open to anyone without.
+    #[arg(long)]
+    teams: Option<PathBuf>,
+    /// How long an answer is served from the cache, in milliseconds.
+    #[arg(long, default_value_t = 5_000)]
+    ttl_ms: u64,
+    /// `RANGE_API_KEY` set on every forwarded quote request.
+    #[arg(long, env = "RANGE_API_KEY", hide_env_values = true)]
+    range_api_key: Option<String>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let teams = match &args.teams {
+        Some(path) => {
+            let text = fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            proxy::parse_teams(&text).with_context(|| format!("{}", path.display()))?
+        }
+        None => Default::default(),
+    };
+    let listener = TcpListener::bind(&args.bind)
+        .with_context(|| format!("failed to listen on {}", args.bind))?;
+
+    let address = listener.local_addr()?;
+    eprintln!(
+        "quotes on http://{}{} -> {}",
+        address, QUOTE_PATH, args.upstream
+    );
+    eprintln!("metrics on http://{}{}", address, METRICS_PATH);
+    for (name, team) in &teams {
+        match team.quotes_per_minute {
+            Some(quota) => eprintln!("  team {} ({} quotes/min)", name, quota),
+            None => eprintln!("  team {}", name),
+        }
+    }
+    let mut proxy = Proxy::new(&args.upstream, Duration::from_millis(args.ttl_ms), teams);
+    if let Some(api_key) = &args.range_api_key {
+        proxy = proxy.with_range_api_key(api_key);
+    }
+    proxy.serve(listener)?;
+    Ok(())
+}
@@ -0,0 +1,40 @@
+//! `risk-oracle-mock-range`: serves the Range risk API from a scenario file,
+//! see `risk_oracle_mock_gateway::range`.
+
+use std::fs;
+use std::net::TcpListener;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use risk_oracle_mock_gateway::range::{RangeMock, Scenario, RANGE_PATH};
+
+#[derive(Parser)]
+#[command(name = "risk-oracle-mock-range", version, about)]
+struct Args {
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8788")]
+    bind: String,
+    /// Scenario (JSON); every address scores 0 without one.
+    #[arg(long)]
+    scenario: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let scenario = match &args.scenario {
+        Some(path) => {
+            let text = fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            serde_json::from_str::<Scenario>(&text)
+                .with_context(|| format!("invalid scenario {}", path.display()))?
+        }
+        None => Scenario::default(),
+    };
+    let listener = TcpListener::bind(&args.bind)
+        .with_context(|| format!("failed to listen on {}", args.bind))?;
+
+    eprintln!("Range API on http://{}{}", listener.local_addr()?, RANGE_PATH);
+    RangeMock::new(scenario).serve(listener)?;
+    Ok(())
+}
@@ -0,0 +1,269 @@
+//! Offline stand-in for the Switchboard gateway.
+//!
+//! Serves quotes signed by the deterministic test oracles of
+//! `risk_oracle_client::fixtures`, so the SDK, the CLI and the keeper can run
+//! end to end against a local validator or LiteSVM loaded with
+//! `fixtures::queue_account` for the same oracles. Feeds are answered with a
+//! constant value, or run against the Range mock ([`range`]) the way an
+//! oracle would ([`oracle`]), so the score follows the mock's scenario.
+//!
+//! Two endpoints, JSON in and out:
+//!
+//! ```text
+//! POST /gateway/api/v1/quote
+//! {"feeds": ["<base64 feed::encode bytes>", ...], "slot": <u64>,
+//!  "slot_hash": "<hex>", "num_signatures": <u8, optional>,
+//!  "variable_overrides": {"RANGE_API_KEY": "<key>", optional}}
+//! -> {"instruction_data": "<base64>", "quote": <quote::Quote>}
+//!
+//! POST /gateway/api/v1/store
+//! {"feed": "<base64 feed::encode bytes>"}
+//! -> {"feed_hash": "<hex>"}
+//! ```
+//!
+//! `store` pins a feed under its id, like Crossbar's `storeOracleFeed`;
+//! storing a feed again is a no-op.
+//!
+//! `slot` and `slot_hash` must be an entry of the cluster's SlotHashes for the
+//! program to accept the quote; the caller reads them from its cluster. The
+//! feed ids are hashed from the bytes as sent, like the programs do. Errors
+//! are a 400 (or 404) with `{"error": "<message>"}`, a 502 when a feed
+//! can't be run against the Range mock, and a 429 when Range rate-limited the
+//! API key, for the caller to retry with another one.
+//!
+//! [`proxy`] fronts a gateway speaking these endpoints for services sharing
+//! one Range API key: it deduplicates and caches their quote requests and
+//! enforces per-team quotas.
+
+pub mod http;
+pub mod oracle;
+pub mod proxy;
+pub mod range;
+
+use std::collections::HashMap;
+use std::io;
+use std::net::TcpListener;
+use std::sync::Mutex;
+
+use anchor_client::solana_sdk::signature::Keypair;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use prost::Message;
+use risk_oracle_client::fixtures;
+use risk_oracle_client::quote::{self, Quote, QuoteFeed};
+use risk_oracle_shared::proto::OracleFeed;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::http::{Request, Response};
+use crate::oracle::{Oracle, RateLimited};
+
+/// Path of the quote endpoint.
+pub const QUOTE_PATH: &str = "/gateway/api/v1/quote";
+/// Path of the endpoint pinning feeds.
+pub const STORE_PATH: &str = "/gateway/api/v1/store";
+/// Path answering `ok` while the gateway is up.
+pub const HEALTH_PATH: &str = "/health";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuoteRequest {
+    /// Feeds as `feed::encode` bytes, base64.
+    pub feeds: Vec<String>,
+    pub slot: u64,
+    /// Hash of `slot`, hex.
+    pub slot_hash: String,
+    /// Oracles signing the quote, all of them by default.
+    #[serde(default)]
+    pub num_signatures: Option<u8>,
+    /// `${NAME}` variables of the feeds, like Crossbar's
+    /// `variableOverrides`: `RANGE_API_KEY` replaces the configured key.
+    #[serde(default)]
+    pub variable_overrides: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuoteResponse {
+    /// Data of the Ed25519 quote instruction, base64.
+    pub instruction_data: String,
+    pub quote: Quote,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoreRequest {
+    /// Feed as `feed::encode` bytes, base64.
+    pub feed: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoreResponse {
+    /// Id the feed is pinned under, hex: the hash of its bytes as sent.
+    pub feed_hash: String,
+}
+
+/// Where the feed values come from.
+enum Values {
+    Constant(i128),
+    Range(Oracle),
+}
+
+/// A feed the gateway couldn't run: answered with a 502 rather than a 400.
+#[derive(Debug)]
+struct FeedFailed(anyhow::Error);
+
+impl std::fmt::Display for FeedFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:#}", self.0)
+    }
+}
+
+impl std::error::Error for FeedFailed {}
+
+pub struct Gateway {
+    oracles: Vec<Keypair>,
+    values: Values,
+    /// Feeds pinned through `store`, by id.
+    pinned: Mutex<HashMap<[u8; 32], OracleFeed>>,
+}
+
+impl Gateway {
+    /// Gateway signing with `fixtures::oracle_keypair` 0..`oracles` and
+    /// answering every feed with `value` (scaled by 10^18).
+    pub fn new(oracles: u8, value: i128) -> Self {
+        Self {
+            oracles: (0..oracles).map(fixtures::oracle_keypair).collect(),
+            values: Values::Constant(value),
+            pinned: Mutex::default(),
+        }
+    }
+
+    /// Gateway running the feeds against the Range mock at `range_url`,
+    /// sending `api_key` as `X-API-KEY`.
+    pub fn with_range(oracles: u8, range_url: &str, api_key: &str) -> Self {
+        Self {
+            oracles: (0..oracles).map(fixtures::oracle_keypair).collect(),
+            values: Values::Range(Oracle::new(range_url, api_key)),
+            pinned: Mutex::default(),
+        }
+    }
+
+    /// Signs a quote for `request`.
+    pub fn quote(&self, request: &QuoteRequest) -> Result<QuoteResponse> {
+        if request.feeds.is_empty() {
+            bail!("no feeds");
+        }
+        let signers = usize::from(request.num_signatures.unwrap_or(self.oracles.len() as u8));
+        if signers == 0 || signers > self.oracles.len() {
+            bail!("num_signatures must be 1..={}", self.oracles.len());
+        }
+        let slot_hash = parse_hash(&request.slot_hash)?;
+
+        let feeds = request
+            .feeds
+            .iter()
+            .enumerate()
+            .map(|(i, encoded)| {
+                let bytes = STANDARD
+                    .decode(encoded)
+                    .with_context(|| format!("feed {} is not base64", i))?;
+                let feed = OracleFeed::decode_length_delimited(bytes.as_slice())
+                    .with_context(|| format!("feed {} is not an encoded feed", i))?;
+                let value = match &self.values {
+                    Values::Constant(value) => *value,
+                    Values::Range(oracle) => {
+                        let value = oracle
+                            .feed_value_with(&feed, &request.variable_overrides)
+                            .with_context(|| format!("feed {}", i))
+                            .map_err(FeedFailed)?;
+                        quote::parse_value(&format!("{:.18}", value))
+                            .ok_or_else(|| anyhow!("feed {} value {} is out of range", i, value))?
+                    }
+                };
+                Ok(QuoteFeed {
+                    feed_id: Sha256::digest(&bytes).into(),
+                    value,
+                    min_oracle_samples: feed.min_oracle_samples.unwrap_or(1) as u8,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let data =
+            fixtures::quote_data(&self.oracles[..signers], slot_hash, request.slot, &feeds);
+        Ok(QuoteResponse {
+            quote: quote::decode(&data)?,
+            instruction_data: STANDARD.encode(&data),
+        })
+    }
+
+    /// Pins the feed of `request` under its id.
+    pub fn store(&self, request: &StoreRequest) -> Result<StoreResponse> {
+        let bytes = STANDARD.decode(&request.feed).context("feed is not base64")?;
+        let feed = OracleFeed::decode_length_delimited(bytes.as_slice())
+            .context("feed is not an encoded feed")?;
+        let feed_id: [u8; 32] = Sha256::digest(&bytes).into();
+        self.pinned().entry(feed_id).or_insert(feed);
+        Ok(StoreResponse {
+            feed_hash: feed_id.iter().map(|byte| format!("{:02x}", byte)).collect(),
+        })
+    }
+
+    /// The feed pinned under `feed_id`, if any.
+    pub fn pinned_feed(&self, feed_id: &[u8; 32]) -> Option<OracleFeed> {
+        self.pinned().get(feed_id).cloned()
+    }
+
+    fn pinned(&self) -> std::sync::MutexGuard<'_, HashMap<[u8; 32], OracleFeed>> {
+        self.pinned.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn store_body(&self, body: &[u8]) -> Result<StoreResponse> {
+        let request: StoreRequest =
+            serde_json::from_slice(body).map_err(|error| anyhow!("invalid request: {}", error))?;
+        self.store(&request)
+    }
+
+    fn quote_body(&self, body: &[u8]) -> Result<QuoteResponse> {
+        let request: QuoteRequest =
+            serde_json::from_slice(body).map_err(|error| anyhow!("invalid request: {}", error))?;
+        self.quote(&request)
+    }
+
+    /// Answers requests on `listener` until accepting fails.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        http::serve(listener, |request| self.handle(request))
+    }
+
+    fn handle(&self, request: &Request) -> Response {
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", HEALTH_PATH) => Response::json(200, &json!("ok")),
+            ("POST", QUOTE_PATH) => match self.quote_body(&request.body) {
+                Ok(quote) => Response::json(200, &json!(quote)),
+                Err(error) => {
+                    let status = match error.downcast_ref::<FeedFailed>() {
+                        Some(failed) if failed.0.is::<RateLimited>() => 429,
+                        Some(_) => 502,
+                        None => 400,
+                    };
+                    Response::error(status, &format!("{:#}", error))
+                }
+            },
+            ("POST", STORE_PATH) => match self.store_body(&request.body) {
+                Ok(stored) => Response::json(200, &json!(stored)),
+                Err(error) => Response::error(400, &format!("{:#}", error)),
+            },
+            _ => Response::error(404, "not found"),
+        }
+    }
+}
+
+fn parse_hash(hex: &str) -> Result<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        bail!("slot_hash must be 32 hex bytes");
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).context("invalid slot_hash")?;
+    }
+    Ok(hash)
+}
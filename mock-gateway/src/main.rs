@@ -0,0 +1,57 @@
+//! `risk-oracle-mock-gateway`: serves quotes signed by the test oracles, see
+//! the library docs.
+
+use std::net::TcpListener;
+
+use anchor_client::solana_sdk::signature::Signer;
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use risk_oracle_client::{fixtures, quote};
+use risk_oracle_mock_gateway::{Gateway, QUOTE_PATH};
+
+#[derive(Parser)]
+#[command(name = "risk-oracle-mock-gateway", version, about)]
+struct Args {
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8787")]
+    bind: String,
+    /// Sign with the `fixtures::oracle_keypair` keys 0..N; load the queue
+    /// written by `risk-oracle-cli fixture queue --oracles N`.
+    #[arg(long, default_value_t = 1)]
+    oracles: u8,
+    /// Value of every feed, in provider units (the Range score is 0-10).
+    #[arg(long, default_value = "2", conflicts_with = "range_url")]
+    value: String,
+    /// Run the feeds against the Range mock (`risk-oracle-mock-range`) at
+    /// this URL instead of answering `--value`.
+    #[arg(long)]
+    range_url: Option<String>,
+    /// `X-API-KEY` sent to the Range mock.
+    #[arg(long, default_value = "test")]
+    range_api_key: String,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let value = quote::parse_value(&args.value)
+        .ok_or_else(|| anyhow!("`{}` is not a feed value", args.value))?;
+    if args.oracles == 0 || usize::from(args.oracles) > fixtures::MAX_QUEUE_ORACLES {
+        return Err(anyhow!("--oracles must be 1..={}", fixtures::MAX_QUEUE_ORACLES));
+    }
+    let listener = TcpListener::bind(&args.bind)
+        .with_context(|| format!("failed to listen on {}", args.bind))?;
+
+    eprintln!("quotes on http://{}{}", listener.local_addr()?, QUOTE_PATH);
+    for index in 0..args.oracles {
+        eprintln!("  oracle {} {}", index, fixtures::oracle_keypair(index).pubkey());
+    }
+    let gateway = match &args.range_url {
+        Some(url) => {
+            eprintln!("feeds run against {}", url);
+            Gateway::with_range(args.oracles, url, &args.range_api_key)
+        }
+        None => Gateway::new(args.oracles, value),
+    };
+    gateway.serve(listener)?;
+    Ok(())
+}
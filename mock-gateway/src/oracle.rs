@@ -0,0 +1,312 @@
+//! Runs a feed the way an oracle would, for the tasks the feed templates use:
+//! HTTP (to the Range mock only), JSON parse, multiply, bound, max, cache,
+//! value, comparison and conditional. Multi-job feeds report the median.
+//!
+//! The HTTP tasks are redirected: a URL under the profile's
+//! `range_risk_url` is sent to the Range mock instead, and `${RANGE_API_KEY}`
+//! in the headers is the configured key, unless the request overrides the
+//! variable. Any other URL is refused, the mock never goes online.
+//!
+//! A 429 from Range fails the whole feed with [`RateLimited`], skipping the
+//! fail-closed fallbacks: a quota problem is the caller's to handle (with
+//! another key), not a risk signal.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use reqwest::blocking::Client;
+use risk_oracle_shared::profiles;
+use risk_oracle_shared::proto::oracle_job::{comparison_task, multiply_task, task, value_task, Task};
+use risk_oracle_shared::proto::{OracleFeed, OracleJob};
+use serde_json::Value;
+
+use crate::range::RANGE_PATH;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const API_KEY_VARIABLE: &str = "RANGE_API_KEY";
+
+/// Range refused the request with a 429.
+#[derive(Debug)]
+pub struct RateLimited;
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Range answered 429 Too Many Requests")
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// Output of the previous task, the input of the next one.
+#[derive(Clone, Debug)]
+enum Input {
+    None,
+    Text(String),
+    Number(f64),
+}
+
+impl Input {
+    fn number(&self) -> Result<f64> {
+        match self {
+            Input::Number(value) => Ok(*value),
+            Input::Text(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("`{}` is not a number", text)),
+            Input::None => bail!("the job produced no value"),
+        }
+    }
+}
+
+pub struct Oracle {
+    http: Client,
+    /// Base URL of the Range mock.
+    range_url: String,
+    api_key: String,
+}
+
+impl Oracle {
+    pub fn new(range_url: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self {
+            http: Client::builder()
+                .timeout(TIMEOUT)
+                .build()
+                .expect("reqwest client"),
+            range_url: range_url.into().trim_end_matches('/').to_string(),
+            api_key: api_key.into(),
+        }
+    }
+
+    /// Value the feed resolves to.
+    pub fn feed_value(&self, feed: &OracleFeed) -> Result<f64> {
+        self.feed_value_with(feed, &HashMap::new())
+    }
+
+    /// [`Oracle::feed_value`] with `${NAME}` variables set by the request,
+    /// e.g. another `RANGE_API_KEY`.
+    pub fn feed_value_with(
+        &self,
+        feed: &OracleFeed,
+        overrides: &HashMap<String, String>,
+    ) -> Result<f64> {
+        let mut values = feed
+            .jobs
+            .iter()
+            .map(|job| self.job(job, Input::None, &mut overrides.clone())?.number())
+            .collect::<Result<Vec<_>>>()?;
+        if values.is_empty() {
+            bail!("the feed has no job");
+        }
+        values.sort_by(f64::total_cmp);
+        let middle = values.len() / 2;
+        Ok(if values.len() % 2 == 0 {
+            (values[middle - 1] + values[middle]) / 2.0
+        } else {
+            values[middle]
+        })
+    }
+
+    fn job(
+        &self,
+        job: &OracleJob,
+        input: Input,
+        variables: &mut HashMap<String, String>,
+    ) -> Result<Input> {
+        self.tasks(&job.tasks, input, variables)
+    }
+
+    fn tasks(
+        &self,
+        tasks: &[Task],
+        mut input: Input,
+        variables: &mut HashMap<String, String>,
+    ) -> Result<Input> {
+        for task in tasks {
+            let task = task.task.as_ref().context("empty task")?;
+            input = self.task(task, input, variables)?;
+        }
+        Ok(input)
+    }
+
+    fn task(
+        &self,
+        task: &task::Task,
+        input: Input,
+        variables: &mut HashMap<String, String>,
+    ) -> Result<Input> {
+        match task {
+            task::Task::HttpTask(http) => {
+                let url = http.url.as_deref().context("HTTP task without URL")?;
+                let path = url
+                    .strip_prefix(profiles::ACTIVE.range_risk_url)
+                    .ok_or_else(|| anyhow!("refusing to fetch {} offline", url))?;
+                let mut request = self
+                    .http
+                    .get(format!("{}{}{}", self.range_url, RANGE_PATH, path));
+                for header in &http.headers {
+                    if let (Some(key), Some(value)) = (&header.key, &header.value) {
+                        request = request.header(key, self.expand(value, variables));
+                    }
+                }
+                let response = request.send()?;
+                let status = response.status();
+                if status.as_u16() == 429 {
+                    return Err(RateLimited.into());
+                }
+                let body = response.text()?;
+                if !status.is_success() {
+                    bail!("Range answered {}: {}", status, body);
+                }
+                Ok(Input::Text(body))
+            }
+            task::Task::JsonParseTask(parse) => {
+                let Input::Text(text) = input else {
+                    bail!("JSON parse task without a response");
+                };
+                let path = parse.path.as_deref().context("JSON parse task without path")?;
+                let mut value: Value =
+                    serde_json::from_str(&text).context("the response is not JSON")?;
+                for key in path.trim_start_matches('$').split('.').filter(|k| !k.is_empty()) {
+                    value = value
+                        .get_mut(key)
+                        .map(Value::take)
+                        .ok_or_else(|| anyhow!("no `{}` in the response", path))?;
+                }
+                match value {
+                    Value::Number(number) => number
+                        .as_f64()
+                        .map(Input::Number)
+                        .context("unreadable number"),
+                    Value::Bool(flag) => Ok(Input::Number(if flag { 1.0 } else { 0.0 })),
+                    Value::String(text) => Ok(Input::Text(text)),
+                    other => bail!("`{}` is {}, not a value", path, other),
+                }
+            }
+            task::Task::MultiplyTask(multiply) => {
+                let factor = match &multiply.multiple {
+                    Some(multiply_task::Multiple::Scalar(factor)) => *factor,
+                    Some(multiply_task::Multiple::Big(big)) => self.number(big, variables)?,
+                    Some(multiply_task::Multiple::Job(job)) => {
+                        self.job(job, Input::None, variables)?.number()?
+                    }
+                    _ => bail!("unsupported multiply task"),
+                };
+                Ok(Input::Number(input.number()? * factor))
+            }
+            task::Task::BoundTask(bound) => {
+                let value = input.number()?;
+                let bound_value = |value: &Option<String>| -> Result<Option<f64>> {
+                    value.as_deref().map(|v| self.number(v, variables)).transpose()
+                };
+                if let Some(lower) = bound_value(&bound.lower_bound_value)? {
+                    if value < lower {
+                        let replaced = bound_value(&bound.on_exceeds_lower_bound_value)?;
+                        return Ok(Input::Number(replaced.unwrap_or(lower)));
+                    }
+                }
+                if let Some(upper) = bound_value(&bound.upper_bound_value)? {
+                    if value > upper {
+                        let replaced = bound_value(&bound.on_exceeds_upper_bound_value)?;
+                        return Ok(Input::Number(replaced.unwrap_or(upper)));
+                    }
+                }
+                Ok(Input::Number(value))
+            }
+            task::Task::MaxTask(max) => {
+                let mut values = Vec::new();
+                for job in &max.jobs {
+                    values.push(self.job(job, Input::None, variables)?.number()?);
+                }
+                for task in &max.tasks {
+                    let output = self.tasks(std::slice::from_ref(task), input.clone(), variables)?;
+                    values.push(output.number()?);
+                }
+                values
+                    .into_iter()
+                    .reduce(f64::max)
+                    .map(Input::Number)
+                    .context("max task without jobs")
+            }
+            task::Task::CacheTask(cache) => {
+                for item in &cache.cache_items {
+                    let name = item
+                        .variable_name
+                        .as_deref()
+                        .context("cache item without name")?;
+                    let job = item.job.as_ref().context("cache item without job")?;
+                    let value = self.job(job, input.clone(), variables)?.number()?;
+                    variables.insert(name.to_string(), value.to_string());
+                }
+                Ok(input)
+            }
+            task::Task::ValueTask(value) => match &value.value {
+                Some(value_task::Value::Value(value)) => Ok(Input::Number(*value)),
+                Some(value_task::Value::Big(big)) => {
+                    Ok(Input::Number(self.number(big, variables)?))
+                }
+                _ => bail!("unsupported value task"),
+            },
+            task::Task::ComparisonTask(comparison) => {
+                let outcome = (|| -> Result<bool> {
+                    let lhs = match &comparison.lhs {
+                        Some(comparison_task::Lhs::Lhs(job)) => {
+                            self.job(job, Input::None, variables)?.number()?
+                        }
+                        Some(comparison_task::Lhs::LhsValue(value)) => {
+                            self.number(value, variables)?
+                        }
+                        None => input.number()?,
+                    };
+                    let rhs = match &comparison.rhs {
+                        Some(comparison_task::Rhs::Rhs(job)) => {
+                            self.job(job, Input::None, variables)?.number()?
+                        }
+                        Some(comparison_task::Rhs::RhsValue(value)) => {
+                            self.number(value, variables)?
+                        }
+                        None => bail!("comparison without rhs"),
+                    };
+                    Ok(match comparison_task::Operation::try_from(comparison.op.unwrap_or(0)) {
+                        Ok(comparison_task::Operation::Gt) => lhs > rhs,
+                        Ok(comparison_task::Operation::Lt) => lhs < rhs,
+                        _ => lhs == rhs,
+                    })
+                })();
+                let result = match outcome {
+                    Ok(true) => &comparison.on_true_value,
+                    Ok(false) => &comparison.on_false_value,
+                    Err(error) if error.is::<RateLimited>() => return Err(error),
+                    Err(error) => match &comparison.on_failure_value {
+                        Some(_) => &comparison.on_failure_value,
+                        None => return Err(error),
+                    },
+                };
+                let result = result.as_deref().context("comparison without result value")?;
+                Ok(Input::Number(self.number(result, variables)?))
+            }
+            task::Task::ConditionalTask(conditional) => {
+                match self.tasks(&conditional.attempt, input.clone(), variables) {
+                    Ok(output) => Ok(output),
+                    Err(error) if error.is::<RateLimited>() => Err(error),
+                    Err(_) => self.tasks(&conditional.on_failure, input, variables),
+                }
+            }
+            _ => bail!("the mock oracle doesn't run this task"),
+        }
+    }
+
+    /// `text` with `${NAME}` replaced by the variables (cached or
+    /// overridden), then the API key.
+    fn expand(&self, text: &str, variables: &HashMap<String, String>) -> String {
+        let mut expanded = text.to_string();
+        for (name, value) in variables {
+            expanded = expanded.replace(&format!("${{{}}}", name), value);
+        }
+        expanded.replace(&format!("${{{}}}", API_KEY_VARIABLE), &self.api_key)
+    }
+
+    fn number(&self, text: &str, variables: &HashMap<String, String>) -> Result<f64> {
+        Input::Text(self.expand(text, variables)).number()
+    }
+}
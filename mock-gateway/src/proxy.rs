@@ -0,0 +1,391 @@
+//! Caching proxy in front of a gateway, for several services sharing one
+//! Range API key.
+//!
+//! Services send their quote requests to the proxy instead of the gateway,
+//! with their team's token as `X-Team-Token`. Identical requests (same feeds,
+//! slot, slot hash and signature count) are deduplicated: the first one is
+//! forwarded, requests arriving while it is in flight wait for its answer,
+//! and later ones get the cached answer until it is `ttl` old. Only
+//! successful answers are cached. Requests that reach the gateway count
+//! against their team's `quotes_per_minute`; over it, the proxy answers 429
+//! without forwarding. With a `range_api_key`, the proxy sets
+//! `RANGE_API_KEY` on every forwarded request, so the key lives in one
+//! place.
+//!
+//! `POST /gateway/api/v1/store` is forwarded as is. `GET /metrics` serves
+//! request, cache hit, quota rejection and gateway counters in the
+//! Prometheus text format. Teams are JSON:
+//!
+//! ```json
+//! {
+//!   "payments": { "token": "<secret>", "quotes_per_minute": 120 },
+//!   "screening": { "token": "<secret>" }
+//! }
+//! ```
+//!
+//! A team without `quotes_per_minute` is unlimited. Without teams, requests
+//! need no token and count as the `default` team.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::io;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::http::{self, Request, Response};
+use crate::{QuoteRequest, HEALTH_PATH, QUOTE_PATH, STORE_PATH};
+
+/// Path of the Prometheus metrics.
+pub const METRICS_PATH: &str = "/metrics";
+/// Header carrying the team's token.
+pub const TEAM_HEADER: &str = "X-Team-Token";
+/// Team of the requests when no teams are configured.
+pub const DEFAULT_TEAM: &str = "default";
+
+const API_KEY_VARIABLE: &str = "RANGE_API_KEY";
+const TIMEOUT: Duration = Duration::from_secs(30);
+const QUOTA_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Team {
+    /// Sent as `X-Team-Token`.
+    pub token: String,
+    /// Requests forwarded to the gateway per minute, unlimited by default.
+    #[serde(default)]
+    pub quotes_per_minute: Option<u32>,
+}
+
+/// A gateway answer, as relayed to the services.
+#[derive(Clone, Debug)]
+struct Answer {
+    status: u16,
+    body: String,
+}
+
+impl Answer {
+    fn response(&self) -> Response {
+        Response {
+            status: self.status,
+            content_type: "application/json",
+            body: self.body.clone(),
+        }
+    }
+}
+
+/// A quote request's answer, fetched once.
+struct Entry {
+    created: Instant,
+    answer: OnceLock<Answer>,
+}
+
+#[derive(Default)]
+struct TeamMetrics {
+    requests: AtomicU64,
+    cache_hits: AtomicU64,
+    quota_rejections: AtomicU64,
+}
+
+/// A team's quota window.
+struct Window {
+    started: Instant,
+    forwarded: u32,
+}
+
+pub struct Proxy {
+    http: Client,
+    /// Gateway base URL.
+    upstream: String,
+    range_api_key: Option<String>,
+    ttl: Duration,
+    teams: BTreeMap<String, Team>,
+    cache: Mutex<HashMap<[u8; 32], Arc<Entry>>>,
+    windows: Mutex<HashMap<String, Window>>,
+    team_metrics: BTreeMap<String, TeamMetrics>,
+    upstream_requests: AtomicU64,
+    upstream_errors: AtomicU64,
+}
+
+impl Proxy {
+    /// Proxy forwarding to the gateway at `upstream`, caching answers for
+    /// `ttl`, open to `teams` (anyone when empty).
+    pub fn new(upstream: impl Into<String>, ttl: Duration, teams: BTreeMap<String, Team>) -> Self {
+        let mut names: Vec<String> = teams.keys().cloned().collect();
+        if names.is_empty() {
+            names.push(DEFAULT_TEAM.to_string());
+        }
+        Self {
+            http: Client::builder()
+                .timeout(TIMEOUT)
+                .build()
+                .expect("reqwest client"),
+            upstream: upstream.into().trim_end_matches('/').to_string(),
+            range_api_key: None,
+            ttl,
+            teams,
+            cache: Mutex::default(),
+            windows: Mutex::default(),
+            team_metrics: names
+                .into_iter()
+                .map(|name| (name, TeamMetrics::default()))
+                .collect(),
+            upstream_requests: AtomicU64::new(0),
+            upstream_errors: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets `api_key` as `RANGE_API_KEY` of every forwarded quote request.
+    pub fn with_range_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.range_api_key = Some(api_key.into());
+        self
+    }
+
+    /// Answers requests on `listener` until accepting fails.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        http::serve(listener, |request| self.handle(request))
+    }
+
+    fn handle(&self, request: &Request) -> Response {
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", HEALTH_PATH) => Response::json(200, &json!("ok")),
+            ("GET", METRICS_PATH) => Response {
+                status: 200,
+                content_type: "text/plain; version=0.0.4",
+                body: self.metrics(),
+            },
+            ("POST", QUOTE_PATH) => match self.team(request) {
+                Some(team) => self.quote(team, &request.body),
+                None => Response::error(401, "unknown team token"),
+            },
+            ("POST", STORE_PATH) => match self.team(request) {
+                Some(_) => self.forward(STORE_PATH, request.body.clone()).response(),
+                None => Response::error(401, "unknown team token"),
+            },
+            _ => Response::error(404, "not found"),
+        }
+    }
+
+    /// Name of the team the request's token belongs to.
+    fn team(&self, request: &Request) -> Option<&str> {
+        if self.teams.is_empty() {
+            return Some(DEFAULT_TEAM);
+        }
+        let token = request.header(TEAM_HEADER)?;
+        self.teams
+            .iter()
+            .find(|(_, team)| team.token == token)
+            .map(|(name, _)| name.as_str())
+    }
+
+    fn quote(&self, team: &str, body: &[u8]) -> Response {
+        let metrics = &self.team_metrics[team];
+        metrics.requests.fetch_add(1, Ordering::Relaxed);
+        let mut request: QuoteRequest = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(error) => return Response::error(400, &format!("invalid request: {}", error)),
+        };
+        if let Some(api_key) = &self.range_api_key {
+            request
+                .variable_overrides
+                .insert(API_KEY_VARIABLE.to_string(), api_key.clone());
+        }
+        let key = cache_key(&request);
+
+        let entry = {
+            let mut cache = lock(&self.cache);
+            match cache.get(&key) {
+                Some(entry) if entry.created.elapsed() < self.ttl => {
+                    metrics.cache_hits.fetch_add(1, Ordering::Relaxed);
+                    Arc::clone(entry)
+                }
+                _ => {
+                    if !self.take_quota(team) {
+                        metrics.quota_rejections.fetch_add(1, Ordering::Relaxed);
+                        return Response::error(429, &format!("team {} is over its quota", team));
+                    }
+                    cache.retain(|_, entry| entry.created.elapsed() < self.ttl);
+                    let entry = Arc::new(Entry {
+                        created: Instant::now(),
+                        answer: OnceLock::new(),
+                    });
+                    cache.insert(key, Arc::clone(&entry));
+                    entry
+                }
+            }
+        };
+
+        // Requests for the same key wait here for the one forwarding it.
+        let answer = entry.answer.get_or_init(|| {
+            let body = serde_json::to_vec(&request).expect("request serializes");
+            self.forward(QUOTE_PATH, body)
+        });
+        if answer.status != 200 {
+            let mut cache = lock(&self.cache);
+            if cache
+                .get(&key)
+                .is_some_and(|cached| Arc::ptr_eq(cached, &entry))
+            {
+                cache.remove(&key);
+            }
+        }
+        answer.response()
+    }
+
+    /// Counts a forwarded request against `team`'s quota, false when over.
+    fn take_quota(&self, team: &str) -> bool {
+        let Some(limit) = self.teams.get(team).and_then(|team| team.quotes_per_minute) else {
+            return true;
+        };
+        let mut windows = lock(&self.windows);
+        let window = windows.entry(team.to_string()).or_insert(Window {
+            started: Instant::now(),
+            forwarded: 0,
+        });
+        if window.started.elapsed() >= QUOTA_WINDOW {
+            *window = Window {
+                started: Instant::now(),
+                forwarded: 0,
+            };
+        }
+        if window.forwarded >= limit {
+            return false;
+        }
+        window.forwarded += 1;
+        true
+    }
+
+    fn forward(&self, path: &str, body: Vec<u8>) -> Answer {
+        self.upstream_requests.fetch_add(1, Ordering::Relaxed);
+        let answer = self
+            .http
+            .post(format!("{}{}", self.upstream, path))
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .and_then(|response| {
+                let status = response.status().as_u16();
+                Ok(Answer {
+                    status,
+                    body: response.text()?,
+                })
+            })
+            .unwrap_or_else(|error| Answer {
+                status: 502,
+                body: json!({ "error": format!("gateway unreachable: {}", error) }).to_string(),
+            });
+        if answer.status != 200 {
+            self.upstream_errors.fetch_add(1, Ordering::Relaxed);
+        }
+        answer
+    }
+
+    /// The counters, in the Prometheus text format.
+    pub fn metrics(&self) -> String {
+        let mut out = String::new();
+        let team_counters: [(&str, &str, fn(&TeamMetrics) -> &AtomicU64); 3] = [
+            ("requests", "Quote requests received.", |m| &m.requests),
+            (
+                "cache_hits",
+                "Quote requests answered without the gateway.",
+                |m| &m.cache_hits,
+            ),
+            (
+                "quota_rejections",
+                "Quote requests refused over quota.",
+                |m| &m.quota_rejections,
+            ),
+        ];
+        for (name, help, counter) in team_counters {
+            let _ = writeln!(out, "# HELP risk_oracle_proxy_{}_total {}", name, help);
+            let _ = writeln!(out, "# TYPE risk_oracle_proxy_{}_total counter", name);
+            for (team, metrics) in &self.team_metrics {
+                let _ = writeln!(
+                    out,
+                    "risk_oracle_proxy_{}_total{{team=\"{}\"}} {}",
+                    name,
+                    team,
+                    counter(metrics).load(Ordering::Relaxed)
+                );
+            }
+        }
+        let upstream = [
+            (
+                "upstream_requests",
+                "Requests forwarded to the gateway.",
+                &self.upstream_requests,
+            ),
+            (
+                "upstream_errors",
+                "Gateway answers other than 200.",
+                &self.upstream_errors,
+            ),
+        ];
+        for (name, help, counter) in upstream {
+            let _ = writeln!(out, "# HELP risk_oracle_proxy_{}_total {}", name, help);
+            let _ = writeln!(out, "# TYPE risk_oracle_proxy_{}_total counter", name);
+            let _ = writeln!(
+                out,
+                "risk_oracle_proxy_{}_total {}",
+                name,
+                counter.load(Ordering::Relaxed)
+            );
+        }
+        let _ = writeln!(
+            out,
+            "# HELP risk_oracle_proxy_cached_quotes Answers in the cache."
+        );
+        let _ = writeln!(out, "# TYPE risk_oracle_proxy_cached_quotes gauge");
+        let _ = writeln!(
+            out,
+            "risk_oracle_proxy_cached_quotes {}",
+            lock(&self.cache).len()
+        );
+        out
+    }
+}
+
+/// Teams of a teams file.
+pub fn parse_teams(text: &str) -> Result<BTreeMap<String, Team>> {
+    let teams: BTreeMap<String, Team> =
+        serde_json::from_str(text).map_err(|error| anyhow!("invalid teams: {}", error))?;
+    let mut tokens: Vec<&str> = teams.values().map(|team| team.token.as_str()).collect();
+    tokens.sort_unstable();
+    if tokens.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(anyhow!("two teams share a token"));
+    }
+    Ok(teams)
+}
+
+/// Identity of a quote request: everything the gateway's answer depends on.
+fn cache_key(request: &QuoteRequest) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for feed in &request.feeds {
+        hasher.update(feed.as_bytes());
+        hasher.update([0]);
+    }
+    hasher.update(request.slot.to_le_bytes());
+    hasher.update(request.slot_hash.to_ascii_lowercase().as_bytes());
+    hasher.update([request.num_signatures.unwrap_or(0)]);
+    let overrides: BTreeMap<_, _> = request.variable_overrides.iter().collect();
+    for (name, value) in overrides {
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(value.as_bytes());
+        hasher.update([0]);
+    }
+    hasher.finalize().into()
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
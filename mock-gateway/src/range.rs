@@ -0,0 +1,194 @@
+//! Mock of the Range risk API, answering from a scripted scenario.
+//!
+//! `GET /v1/risk/address?address=<base58>&network=<network>` answers
+//! `{"riskScore": <0-10>, "isBlacklisted": <bool>, "timestamp": <unix seconds>,
+//! "address": ..., "network": ...}` like Range does, with the score, flag,
+//! scoring time, latency or injected error of the scenario step the request
+//! falls in. A scenario is JSON:
+//!
+//! ```json
+//! {
+//!   "api_key": "test",
+//!   "default": { "score": 1 },
+//!   "addresses": {
+//!     "<address>": [{ "score": 2 }, { "from_request": 3, "score": 9.5 }],
+//!     "<address>:eclipse": [{ "blacklisted": true, "latency_ms": 500 }],
+//!     "<address 3>": [{ "score": 4, "scored_at": 1700000000 }],
+//!     "<address 2>": [{ "status": 500 }, { "from_request": 1, "body": "not json" }]
+//!   }
+//! }
+//! ```
+//!
+//! Steps are keyed by `<address>:<network>`, then `<address>`. Each key counts
+//! its requests from 0; a request uses the last step whose `from_request` it
+//! has reached, and fields the step leaves out come from `default`. The first
+//! example answers 2 (a score of 20) to the first three requests and 9.5 from
+//! the fourth on: a score jumping mid-session.
+//!
+//! `POST /mock/scenario` replaces the scenario and restarts the counts, for
+//! tests scripting changes as they go. With an `api_key`, requests without
+//! that `X-API-KEY` are refused with a 401.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::TcpListener;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::http::{self, Request, Response};
+
+/// Path of the risk endpoint, as in the profiles' `range_risk_url`.
+pub const RANGE_PATH: &str = "/v1/risk/address";
+/// Path replacing the scenario.
+pub const SCENARIO_PATH: &str = "/mock/scenario";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Scenario {
+    /// `X-API-KEY` the requests must carry, any by default.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Answer to addresses without steps, and for the fields steps leave out.
+    #[serde(default)]
+    pub default: Step,
+    /// Steps per `<address>:<network>` or `<address>`, in `from_request`
+    /// order.
+    #[serde(default)]
+    pub addresses: HashMap<String, Vec<Step>>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Step {
+    /// First request (counted from 0 per key) the step applies to.
+    #[serde(default)]
+    pub from_request: u64,
+    /// Range score, 0-10. 0 by default.
+    #[serde(default)]
+    pub score: Option<f64>,
+    #[serde(default)]
+    pub blacklisted: Option<bool>,
+    /// Unix time Range computed the score at, the time of the request by
+    /// default.
+    #[serde(default)]
+    pub scored_at: Option<u64>,
+    /// Delay before answering.
+    #[serde(default)]
+    pub latency_ms: Option<u64>,
+    /// HTTP status to fail with instead of answering (429, 500, ...).
+    #[serde(default)]
+    pub status: Option<u16>,
+    /// Raw body replacing the JSON answer, e.g. a malformed one.
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+pub struct RangeMock {
+    scenario: Mutex<Scenario>,
+    requests: Mutex<HashMap<String, u64>>,
+}
+
+impl RangeMock {
+    pub fn new(scenario: Scenario) -> Self {
+        Self {
+            scenario: Mutex::new(scenario),
+            requests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Answers requests on `listener` until accepting fails.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        http::serve(listener, |request| self.handle(request))
+    }
+
+    fn handle(&self, request: &Request) -> Response {
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/health") => Response::json(200, &json!("ok")),
+            ("GET", RANGE_PATH) => self.risk(request),
+            ("POST", SCENARIO_PATH) => match serde_json::from_slice(&request.body) {
+                Ok(scenario) => {
+                    *self.scenario.lock().unwrap() = scenario;
+                    self.requests.lock().unwrap().clear();
+                    Response::json(200, &json!("ok"))
+                }
+                Err(error) => Response::error(400, &format!("invalid scenario: {}", error)),
+            },
+            _ => Response::error(404, "not found"),
+        }
+    }
+
+    fn risk(&self, request: &Request) -> Response {
+        let (Some(address), Some(network)) = (request.query("address"), request.query("network"))
+        else {
+            return Response::error(400, "address and network are required");
+        };
+        let step = {
+            let scenario = self.scenario.lock().unwrap();
+            if let Some(key) = &scenario.api_key {
+                if request.header("X-API-KEY") != Some(key.as_str()) {
+                    return Response::error(401, "invalid API key");
+                }
+            }
+            let network_key = format!("{}:{}", address, network);
+            let (key, steps) = match scenario.addresses.get(&network_key) {
+                Some(steps) => (network_key, Some(steps)),
+                None => (address.to_string(), scenario.addresses.get(address)),
+            };
+            let mut requests = self.requests.lock().unwrap();
+            let count = requests.entry(key).or_insert(0);
+            let index = *count;
+            *count += 1;
+            let step = steps
+                .and_then(|steps| steps.iter().rev().find(|step| index >= step.from_request));
+            resolve(&scenario.default, step)
+        };
+
+        if let Some(latency) = step.latency_ms {
+            thread::sleep(Duration::from_millis(latency));
+        }
+        let status = step.status.unwrap_or(200);
+        if let Some(body) = step.body {
+            return Response {
+                status,
+                content_type: "application/json",
+                body,
+            };
+        }
+        if status != 200 {
+            return Response::error(status, "injected error");
+        }
+        Response::json(
+            200,
+            &json!({
+                "riskScore": step.score.unwrap_or(0.0),
+                "isBlacklisted": step.blacklisted.unwrap_or(false),
+                "timestamp": step.scored_at.unwrap_or_else(now),
+                "address": address,
+                "network": network,
+            }),
+        )
+    }
+}
+
+/// `step` with the fields it leaves out taken from `default`.
+fn resolve(default: &Step, step: Option<&Step>) -> Step {
+    let step = step.cloned().unwrap_or_default();
+    Step {
+        from_request: step.from_request,
+        score: step.score.or(default.score),
+        blacklisted: step.blacklisted.or(default.blacklisted),
+        scored_at: step.scored_at.or(default.scored_at),
+        latency_ms: step.latency_ms.or(default.latency_ms),
+        status: step.status.or(default.status),
+        body: step.body.or_else(|| default.body.clone()),
+    }
+}
+
+/// Current Unix time, in seconds.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
@@ -0,0 +1,125 @@
+//! Requests a quote from the mock gateway over HTTP and checks what the
+//! programs check: the feed id, the value and the oracles' signatures.
+
+use std::net::TcpListener;
+use std::thread;
+
+use anchor_client::solana_sdk::signature::{Signature, Signer};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use risk_oracle_client::{fixtures, quote};
+use risk_oracle_mock_gateway::{
+    Gateway, QuoteRequest, QuoteResponse, StoreRequest, StoreResponse, HEALTH_PATH, QUOTE_PATH,
+    STORE_PATH,
+};
+use risk_oracle_shared::feed;
+use sha2::{Digest, Sha256};
+
+const SLOT_HASH: [u8; 32] = [7; 32];
+
+fn start(oracles: u8, value: &str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let gateway = Gateway::new(oracles, quote::parse_value(value).unwrap());
+    thread::spawn(move || gateway.serve(listener));
+    format!("http://{}", address)
+}
+
+fn request(feeds: &[Vec<u8>], num_signatures: Option<u8>) -> QuoteRequest {
+    QuoteRequest {
+        feeds: feeds.iter().map(|feed| STANDARD.encode(feed)).collect(),
+        slot: 1_000,
+        slot_hash: SLOT_HASH.iter().map(|byte| format!("{:02x}", byte)).collect(),
+        num_signatures,
+        variable_overrides: Default::default(),
+    }
+}
+
+#[test]
+fn serves_signed_quotes_for_the_requested_feed() {
+    let url = start(3, "7.5");
+    let encoded = feed::encode(&feed::risk_score_feed(&[9; 32]));
+
+    let response: QuoteResponse = reqwest::blocking::Client::new()
+        .post(format!("{}{}", url, QUOTE_PATH))
+        .json(&request(&[encoded.clone()], Some(2)))
+        .send()
+        .unwrap()
+        .error_for_status()
+        .unwrap()
+        .json()
+        .unwrap();
+
+    let data = STANDARD.decode(&response.instruction_data).unwrap();
+    let decoded = quote::decode(&data).unwrap();
+    assert_eq!(decoded, response.quote);
+    assert_eq!(decoded.slot, Some(1_000));
+    assert_eq!(decoded.slot_hash, SLOT_HASH);
+    assert_eq!(decoded.feeds.len(), 1);
+    assert_eq!(decoded.feeds[0].feed_id, <[u8; 32]>::from(Sha256::digest(&encoded)));
+    assert_eq!(quote::format_value(decoded.feeds[0].value), "7.5");
+
+    let mut message = SLOT_HASH.to_vec();
+    message.extend_from_slice(&decoded.feeds[0].feed_id);
+    message.extend_from_slice(&decoded.feeds[0].value.to_le_bytes());
+    message.push(decoded.feeds[0].min_oracle_samples);
+    assert_eq!(decoded.signatures.len(), 2);
+    for (index, signed) in decoded.signatures.iter().enumerate() {
+        let oracle = fixtures::oracle_keypair(index as u8).pubkey();
+        assert_eq!(signed.signer, oracle.to_bytes());
+        assert!(Signature::from(signed.signature).verify(oracle.as_ref(), &message));
+    }
+}
+
+#[test]
+fn rejects_malformed_requests() {
+    let url = start(1, "2");
+    let client = reqwest::blocking::Client::new();
+
+    let health = client.get(format!("{}{}", url, HEALTH_PATH)).send().unwrap();
+    assert_eq!(health.status(), 200);
+
+    for body in [
+        request(&[], None),
+        request(&[b"not a feed".to_vec()], None),
+        request(&[feed::encode(&feed::test_feed())], Some(2)),
+    ] {
+        let response = client
+            .post(format!("{}{}", url, QUOTE_PATH))
+            .json(&body)
+            .send()
+            .unwrap();
+        assert_eq!(response.status(), 400);
+    }
+}
+
+#[test]
+fn pins_stored_feeds_under_their_id() {
+    let url = start(1, "2");
+    let client = reqwest::blocking::Client::new();
+    let encoded = feed::encode(&feed::risk_score_feed(&[9; 32]));
+    let store = |feed: &[u8]| {
+        client
+            .post(format!("{}{}", url, STORE_PATH))
+            .json(&StoreRequest {
+                feed: STANDARD.encode(feed),
+            })
+            .send()
+            .unwrap()
+    };
+
+    let id: String = Sha256::digest(&encoded).iter().map(|byte| format!("{:02x}", byte)).collect();
+    for _ in 0..2 {
+        let response: StoreResponse = store(&encoded).error_for_status().unwrap().json().unwrap();
+        assert_eq!(response.feed_hash, id);
+    }
+    assert_eq!(store(b"not a feed").status(), 400);
+
+    let gateway = Gateway::new(1, 0);
+    let request = StoreRequest {
+        feed: STANDARD.encode(&encoded),
+    };
+    gateway.store(&request).unwrap();
+    let pinned = gateway.pinned_feed(&Sha256::digest(&encoded).into()).unwrap();
+    assert_eq!(feed::encode(&pinned), encoded);
+}
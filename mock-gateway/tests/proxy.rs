@@ -0,0 +1,179 @@
+//! The proxy in front of the mock gateway: deduplication, team quotas and
+//! the shared API key, observed through the gateway's answers and the
+//! proxy's metrics.
+
+use std::collections::BTreeMap;
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use risk_oracle_client::quote;
+use risk_oracle_mock_gateway::proxy::{Proxy, Team, METRICS_PATH, TEAM_HEADER};
+use risk_oracle_mock_gateway::range::{RangeMock, Scenario};
+use risk_oracle_mock_gateway::{Gateway, QuoteRequest, QuoteResponse, QUOTE_PATH};
+use risk_oracle_shared::feed;
+use serde_json::json;
+
+const TTL: Duration = Duration::from_secs(60);
+
+fn listen() -> (TcpListener, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    (listener, url)
+}
+
+fn start_gateway() -> String {
+    let (listener, url) = listen();
+    let gateway = Gateway::new(1, quote::parse_value("2").unwrap());
+    thread::spawn(move || gateway.serve(listener));
+    url
+}
+
+fn start_proxy(proxy: Proxy) -> String {
+    let (listener, url) = listen();
+    thread::spawn(move || proxy.serve(listener));
+    url
+}
+
+fn teams() -> BTreeMap<String, Team> {
+    let team = |token: &str, quotes_per_minute| Team {
+        token: token.to_string(),
+        quotes_per_minute,
+    };
+    BTreeMap::from([
+        ("payments".to_string(), team("payments-token", Some(1))),
+        ("screening".to_string(), team("screening-token", None)),
+    ])
+}
+
+fn request(address: u8, slot: u64) -> QuoteRequest {
+    QuoteRequest {
+        feeds: vec![STANDARD.encode(feed::encode(&feed::risk_score_feed(&[address; 32])))],
+        slot,
+        slot_hash: "07".repeat(32),
+        num_signatures: None,
+        variable_overrides: Default::default(),
+    }
+}
+
+/// Status of the quote request, with its instruction data on success.
+fn send(proxy: &str, token: &str, request: &QuoteRequest) -> (u16, Option<String>) {
+    let response = reqwest::blocking::Client::new()
+        .post(format!("{}{}", proxy, QUOTE_PATH))
+        .header(TEAM_HEADER, token)
+        .json(request)
+        .send()
+        .unwrap();
+    let status = response.status().as_u16();
+    if status != 200 {
+        return (status, None);
+    }
+    let response: QuoteResponse = response.json().unwrap();
+    (status, Some(response.instruction_data))
+}
+
+/// Value of `risk_oracle_proxy_<series>`.
+fn metric(proxy: &str, series: &str) -> u64 {
+    let series = format!("risk_oracle_proxy_{}", series);
+    let text = reqwest::blocking::get(format!("{}{}", proxy, METRICS_PATH))
+        .unwrap()
+        .text()
+        .unwrap();
+    text.lines()
+        .find_map(|line| line.strip_prefix(&series)?.strip_prefix(' '))
+        .unwrap_or_else(|| panic!("no {} in\n{}", series, text))
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn identical_requests_reach_the_gateway_once() {
+    let proxy = start_proxy(Proxy::new(start_gateway(), TTL, teams()));
+    let request = request(1, 1_000);
+
+    let requests: Vec<_> = (0..4)
+        .map(|_| {
+            let proxy = proxy.clone();
+            let request = request.clone();
+            thread::spawn(move || send(&proxy, "screening-token", &request))
+        })
+        .collect();
+    let answers: Vec<_> = requests.into_iter().map(|r| r.join().unwrap()).collect();
+    let (status, data) = send(&proxy, "payments-token", &request);
+
+    assert_eq!(status, 200);
+    assert!(answers.iter().all(|answer| answer == &(200, data.clone())));
+    assert_eq!(metric(&proxy, "upstream_requests_total"), 1);
+    assert_eq!(metric(&proxy, "cache_hits_total{team=\"screening\"}"), 3);
+    assert_eq!(metric(&proxy, "cache_hits_total{team=\"payments\"}"), 1);
+}
+
+#[test]
+fn another_slot_is_another_request() {
+    let proxy = start_proxy(Proxy::new(start_gateway(), TTL, teams()));
+
+    let (_, first) = send(&proxy, "screening-token", &request(1, 1_000));
+    let (_, second) = send(&proxy, "screening-token", &request(1, 1_001));
+
+    assert_ne!(first, second);
+    assert_eq!(metric(&proxy, "upstream_requests_total"), 2);
+}
+
+#[test]
+fn a_team_over_its_quota_still_gets_cached_answers() {
+    let proxy = start_proxy(Proxy::new(start_gateway(), TTL, teams()));
+
+    assert_eq!(send(&proxy, "payments-token", &request(1, 1_000)).0, 200);
+    assert_eq!(send(&proxy, "payments-token", &request(2, 1_000)).0, 429);
+    assert_eq!(send(&proxy, "payments-token", &request(1, 1_000)).0, 200);
+    assert_eq!(send(&proxy, "screening-token", &request(2, 1_000)).0, 200);
+
+    assert_eq!(
+        metric(&proxy, "quota_rejections_total{team=\"payments\"}"),
+        1
+    );
+    assert_eq!(metric(&proxy, "upstream_requests_total"), 2);
+}
+
+#[test]
+fn unknown_tokens_are_refused() {
+    let proxy = start_proxy(Proxy::new(start_gateway(), TTL, teams()));
+
+    assert_eq!(send(&proxy, "guessed", &request(1, 1_000)).0, 401);
+    assert_eq!(metric(&proxy, "upstream_requests_total"), 0);
+}
+
+#[test]
+fn failed_answers_are_not_cached() {
+    let (listener, gateway) = listen();
+    drop(listener);
+    let proxy = start_proxy(Proxy::new(gateway, TTL, BTreeMap::new()));
+
+    assert_eq!(send(&proxy, "", &request(1, 1_000)).0, 502);
+    assert_eq!(send(&proxy, "", &request(1, 1_000)).0, 502);
+    assert_eq!(metric(&proxy, "upstream_errors_total"), 2);
+    assert_eq!(metric(&proxy, "cached_quotes"), 0);
+}
+
+#[test]
+fn the_proxy_sends_its_api_key() {
+    let scenario: Scenario = serde_json::from_value(json!({
+        "api_key": "shared-key",
+        "default": { "score": 3 },
+    }))
+    .unwrap();
+    let (range, range_url) = listen();
+    thread::spawn(move || RangeMock::new(scenario).serve(range));
+    let (listener, gateway) = listen();
+    let oracle = Gateway::with_range(1, &range_url, "wrong-key");
+    thread::spawn(move || oracle.serve(listener));
+
+    let without_key = start_proxy(Proxy::new(&gateway, TTL, BTreeMap::new()));
+    let with_key =
+        start_proxy(Proxy::new(&gateway, TTL, BTreeMap::new()).with_range_api_key("shared-key"));
+
+    assert_eq!(send(&without_key, "", &request(1, 1_000)).0, 502);
+    assert_eq!(send(&with_key, "", &request(1, 1_000)).0, 200);
+}
@@ -0,0 +1,214 @@
+//! Scenario tests: the gateway runs the feed templates against the Range
+//! mock, so the quoted values follow the scripted scores, flags and failures.
+
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::thread;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use risk_oracle_client::quote;
+use risk_oracle_client::range::{RangeApi, RangeError};
+use risk_oracle_mock_gateway::range::{RangeMock, Scenario, RANGE_PATH, SCENARIO_PATH};
+use risk_oracle_mock_gateway::{Gateway, QuoteRequest, QuoteResponse, QUOTE_PATH};
+use risk_oracle_shared::feed::{self, NetworkSet};
+use risk_oracle_shared::proto::OracleFeed;
+use serde_json::json;
+
+const API_KEY: &str = "test";
+const STEADY: [u8; 32] = [1; 32];
+const FAILING: [u8; 32] = [2; 32];
+
+fn base58(address: &[u8; 32]) -> String {
+    bs58::encode(address).into_string()
+}
+
+/// Starts the Range mock with `scenario` and a gateway running feeds against
+/// it, returning their URLs.
+fn start(scenario: serde_json::Value) -> (String, String) {
+    let scenario: Scenario = serde_json::from_value(scenario).unwrap();
+    let range = TcpListener::bind("127.0.0.1:0").unwrap();
+    let range_url = format!("http://{}", range.local_addr().unwrap());
+    thread::spawn(move || RangeMock::new(scenario).serve(range));
+
+    let gateway = TcpListener::bind("127.0.0.1:0").unwrap();
+    let gateway_url = format!("http://{}", gateway.local_addr().unwrap());
+    let oracle = Gateway::with_range(1, &range_url, API_KEY);
+    thread::spawn(move || oracle.serve(gateway));
+    (range_url, gateway_url)
+}
+
+/// Status of the quote request, and the quoted values on success.
+fn quote_values(gateway: &str, feeds: &[OracleFeed]) -> (u16, Vec<String>) {
+    quote_values_with(gateway, feeds, HashMap::new())
+}
+
+fn quote_values_with(
+    gateway: &str,
+    feeds: &[OracleFeed],
+    variable_overrides: HashMap<String, String>,
+) -> (u16, Vec<String>) {
+    let request = QuoteRequest {
+        feeds: feeds.iter().map(|f| STANDARD.encode(feed::encode(f))).collect(),
+        slot: 1,
+        slot_hash: "00".repeat(32),
+        num_signatures: None,
+        variable_overrides,
+    };
+    let response = reqwest::blocking::Client::new()
+        .post(format!("{}{}", gateway, QUOTE_PATH))
+        .json(&request)
+        .send()
+        .unwrap();
+    let status = response.status().as_u16();
+    if status != 200 {
+        return (status, Vec::new());
+    }
+    let response: QuoteResponse = response.json().unwrap();
+    let values = response
+        .quote
+        .feeds
+        .iter()
+        .map(|feed| quote::format_value(feed.value))
+        .collect();
+    (status, values)
+}
+
+#[test]
+fn score_jumps_mid_session() {
+    let (range, gateway) = start(json!({
+        "api_key": API_KEY,
+        "addresses": {
+            base58(&STEADY): [{ "score": 2 }, { "from_request": 2, "score": 9.5 }],
+        },
+    }));
+    let feed = feed::risk_score_feed(&STEADY);
+
+    assert_eq!(quote_values(&gateway, &[feed.clone()]), (200, vec!["20".to_string()]));
+    assert_eq!(quote_values(&gateway, &[feed.clone()]), (200, vec!["20".to_string()]));
+    assert_eq!(quote_values(&gateway, &[feed.clone()]), (200, vec!["95".to_string()]));
+
+    // A new scenario restarts the counts.
+    let scenario = json!({ "addresses": { base58(&STEADY): [{ "score": 0.5 }] } });
+    reqwest::blocking::Client::new()
+        .post(format!("{}{}", range, SCENARIO_PATH))
+        .json(&scenario)
+        .send()
+        .unwrap()
+        .error_for_status()
+        .unwrap();
+    assert_eq!(quote_values(&gateway, &[feed]), (200, vec!["5".to_string()]));
+}
+
+#[test]
+fn offchain_score_reads_what_the_feed_quotes() {
+    let (range, gateway) = start(json!({
+        "api_key": API_KEY,
+        "addresses": {
+            base58(&STEADY): [{ "score": 4.75, "blacklisted": true }],
+        },
+    }));
+    let api = RangeApi::new(API_KEY).with_risk_url(format!("{}{}", range, RANGE_PATH));
+
+    let response = api.fetch_offchain_score(&STEADY, "solana").unwrap();
+    assert_eq!(response.risk_score, 4.75);
+    assert!(response.blacklisted());
+    // Same scale and rounding as the quoted feed value read on-chain.
+    assert_eq!(response.score(), 48);
+    let quoted = quote_values(&gateway, &[feed::risk_score_feed(&STEADY)]).1;
+    assert_eq!(feed::score_from_feed_value(quote::parse_value(&quoted[0]).unwrap()), 48);
+
+    let refused = RangeApi::new("other").with_risk_url(format!("{}{}", range, RANGE_PATH));
+    assert!(matches!(
+        refused.fetch_offchain_score(&STEADY, "solana"),
+        Err(RangeError::Status { status: 401, .. })
+    ));
+}
+
+#[test]
+fn composite_feed_reports_the_riskiest_network() {
+    let (_, gateway) = start(json!({
+        "addresses": {
+            base58(&STEADY): [{ "score": 3 }],
+            format!("{}:eclipse", base58(&STEADY)): [{ "score": 8 }],
+        },
+    }));
+    let feed =
+        feed::composite_risk_score_feed(&STEADY, NetworkSet::from_bits(0b11).unwrap());
+    assert_eq!(quote_values(&gateway, &[feed]), (200, vec!["80".to_string()]));
+}
+
+#[test]
+fn blacklist_flag_is_quoted_with_the_score() {
+    let (_, gateway) = start(json!({
+        "addresses": { base58(&STEADY): [{ "score": 4, "blacklisted": true }] },
+    }));
+    let feeds = feed::score_and_flag_feeds(&STEADY);
+    assert_eq!(
+        quote_values(&gateway, &feeds),
+        (200, vec!["40".to_string(), "1".to_string()])
+    );
+}
+
+#[test]
+fn as_of_is_quoted_with_the_score() {
+    let (_, gateway) = start(json!({
+        "addresses": { base58(&STEADY): [{ "score": 4, "scored_at": 1_700_000_000 }] },
+    }));
+    let feeds = feed::score_and_as_of_feeds(&STEADY);
+    assert_eq!(
+        quote_values(&gateway, &feeds),
+        (200, vec!["40".to_string(), "1700000000".to_string()])
+    );
+}
+
+#[test]
+fn injected_errors_fail_the_quote_or_fail_closed() {
+    let (_, gateway) = start(json!({
+        "addresses": {
+            base58(&FAILING): [{ "status": 500 }, { "from_request": 2, "body": "not json" }],
+        },
+    }));
+
+    assert_eq!(quote_values(&gateway, &[feed::risk_score_feed(&FAILING)]).0, 502);
+    // The pass/fail feed reports FAIL when the score can't be fetched.
+    assert_eq!(
+        quote_values(&gateway, &[feed::pass_fail_feed(&FAILING, 50)]),
+        (200, vec![feed::FAIL.to_string()])
+    );
+    // From the third request on, the body is malformed.
+    assert_eq!(quote_values(&gateway, &[feed::risk_score_feed(&FAILING)]).0, 502);
+}
+
+#[test]
+fn requests_without_the_api_key_are_refused() {
+    let (range, _) = start(json!({ "api_key": "other" }));
+    let response = reqwest::blocking::get(format!(
+        "{}/v1/risk/address?address={}&network=solana",
+        range,
+        base58(&STEADY)
+    ))
+    .unwrap();
+    assert_eq!(response.status(), 401);
+}
+
+#[test]
+fn overridden_api_key_is_sent_to_range() {
+    let (_, gateway) = start(json!({ "api_key": "other" }));
+    let feeds = [feed::risk_score_feed(&STEADY)];
+
+    assert_eq!(quote_values(&gateway, &feeds).0, 502);
+    let overrides = HashMap::from([("RANGE_API_KEY".to_string(), "other".to_string())]);
+    assert_eq!(quote_values_with(&gateway, &feeds, overrides).0, 200);
+}
+
+#[test]
+fn rate_limits_answer_429_instead_of_failing_closed() {
+    let (_, gateway) = start(json!({
+        "addresses": { base58(&FAILING): [{ "status": 429 }] },
+    }));
+
+    assert_eq!(quote_values(&gateway, &[feed::risk_score_feed(&FAILING)]).0, 429);
+    // No FAIL quoted for a quota problem.
+    assert_eq!(quote_values(&gateway, &[feed::pass_fail_feed(&FAILING, 50)]).0, 429);
+}
@@ -1,5 +1,5 @@
 import {
-  PublicKey, Keypair, TransactionInstruction, SYSVAR_CLOCK_PUBKEY,
+  Connection, PublicKey, Keypair, TransactionInstruction, SYSVAR_CLOCK_PUBKEY,
   SYSVAR_SLOT_HASHES_PUBKEY,
   SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
@@ -8,33 +8,214 @@ import * as sb from "@switchboard-xyz/on-demand";
 import { getDefaultQueue } from "@switchboard-xyz/on-demand";
 
 // The deployed Pinocchio program ID.
-export const PROGRAM_ID = new PublicKey("CR8mpiY9eEbNkU8w4VJkGB4gzEnozp739jwvTiXRmACc");
+// Reassigned by `selectProgramId` to the id registered for the detected cluster.
+export let PROGRAM_ID = new PublicKey("CR8mpiY9eEbNkU8w4VJkGB4gzEnozp739jwvTiXRmACc");
 
+export type Cluster = "mainnet" | "devnet" | "testnet" | "localnet";
+
+// Genesis hash of each public cluster; any other genesis is a local validator.
+const GENESIS_HASHES: Record<string, Cluster> = {
+  "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d": "mainnet",
+  "EtWTRABZaYq6iMfeYKouRuZWdNs7nLQbRHwXBuajRjMp": "devnet",
+  "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY": "testnet",
+};
+
+// Program id deployed on each cluster, mirroring the `[programs]` config of the
+// Rust binaries. Edit for your own deployments.
+export const PROGRAM_IDS: Partial<Record<Cluster, PublicKey>> = {
+  mainnet: PROGRAM_ID,
+  devnet: PROGRAM_ID,
+  localnet: PROGRAM_ID,
+};
+
+export async function detectCluster(connection: Connection): Promise<Cluster> {
+  return GENESIS_HASHES[await connection.getGenesisHash()] ?? "localnet";
+}
+
+// Point the SDK at the program deployed on the cluster `connection` serves,
+// so transactions can't target another cluster's deployment. Throws when the
+// cluster has no registered deployment or doesn't match `ACTIVE_PROFILE`
+// (the mainnet profile only runs on mainnet, the others anywhere else).
+export async function selectProgramId(connection: Connection): Promise<PublicKey> {
+  const cluster = await detectCluster(connection);
+  const programId = PROGRAM_IDS[cluster];
+  if (!programId) throw new Error(`no program id registered for ${cluster}`);
+  if ((cluster === "mainnet") !== (ACTIVE_PROFILE.name === "mainnet")) {
+    throw new Error(`the RPC endpoint serves ${cluster}, ORACLE_PROFILE is ${ACTIVE_PROFILE.name}`);
+  }
+  PROGRAM_ID = programId;
+  return programId;
+}
+
+// Deployment profiles, mirroring `shared/src/profiles.rs`.
+// The Range URL is part of the feed definition, so these values must match the
+// profile the program was built with or the feed ids will differ.
+export type ProfileName = "mainnet" | "devnet" | "staging";
+
+// How the provider's score is extracted from the response, mirroring
+// `ResponseTemplate` in `shared/src/feed.rs` (part of the feed id).
+export type Extraction = { jsonPath: string } | { regex: string; group: number };
+
+export interface ResponseTemplate {
+  accept: string;
+  extraction: Extraction;
+}
+
+// Range's JSON response, `{"riskScore": <0–10>, ...}`.
+export const RANGE_JSON: ResponseTemplate = {
+  accept: "application/json",
+  extraction: { jsonPath: "$.riskScore" },
+};
+
+// The parse task of a job.
+function extractionTask(extraction: Extraction) {
+  return "jsonPath" in extraction
+    ? { jsonParseTask: { path: extraction.jsonPath } }
+    : { regexExtractTask: { pattern: extraction.regex, groupNumber: extraction.group } };
+}
+
+export interface Profile {
+  name: ProfileName;
+  queue: PublicKey;
+  // Switchboard On-Demand program owning the queue.
+  switchboardProgram: PublicKey;
+  rangeRiskUrl: string;
+  rangeNetwork: string;
+  response: ResponseTemplate;
+  verifierMaxAge: number;
+  maxQuoteAgeSlots: number;
+  // Fewest oracle signatures the programs accept per quote.
+  minOracleSamples: number;
+  // Largest quote instruction and feed count the programs accept.
+  maxQuoteDataLen: number;
+  maxQuoteFeeds: number;
+  // Whether the programs accept the constant test feed (`getTestFeed`).
+  testFeed: boolean;
+  // Lowest scores the programs tag "warn" and "critical" (see `shared/src/severity.rs`).
+  severityBands: SeverityBands;
+}
+
+export interface SeverityBands {
+  warn: number;
+  critical: number;
+}
+
+export const PROFILES: Record<ProfileName, Profile> = {
+  mainnet: {
+    name: "mainnet",
+    queue: new PublicKey("A43DyUGA7s8eXPxqEjJY6EBu1KKbNgfxF8h17VAHn13w"),
+    switchboardProgram: new PublicKey("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv"),
+    rangeRiskUrl: "https://api.range.org/v1/risk/address",
+    rangeNetwork: "solana",
+    response: RANGE_JSON,
+    verifierMaxAge: 30,
+    maxQuoteAgeSlots: 50,
+    minOracleSamples: 1,
+    maxQuoteDataLen: 1024,
+    maxQuoteFeeds: 8,
+    testFeed: false,
+    severityBands: { warn: 40, critical: 70 },
+  },
+  devnet: {
+    name: "devnet",
+    queue: new PublicKey("EYiAmGSdsQTuCw413V5BzaruWuCCSDgTPtBGvLkXHbe7"),
+    switchboardProgram: new PublicKey("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2"),
+    rangeRiskUrl: "https://api.range.org/v1/risk/address",
+    rangeNetwork: "solana",
+    response: RANGE_JSON,
+    verifierMaxAge: 30,
+    maxQuoteAgeSlots: 50,
+    minOracleSamples: 1,
+    maxQuoteDataLen: 1024,
+    maxQuoteFeeds: 8,
+    testFeed: true,
+    severityBands: { warn: 40, critical: 70 },
+  },
+  // Devnet queue with mainnet freshness limits.
+  staging: {
+    name: "staging",
+    queue: new PublicKey("EYiAmGSdsQTuCw413V5BzaruWuCCSDgTPtBGvLkXHbe7"),
+    switchboardProgram: new PublicKey("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2"),
+    rangeRiskUrl: "https://api.range.org/v1/risk/address",
+    rangeNetwork: "solana",
+    response: RANGE_JSON,
+    verifierMaxAge: 30,
+    maxQuoteAgeSlots: 50,
+    minOracleSamples: 1,
+    maxQuoteDataLen: 1024,
+    maxQuoteFeeds: 8,
+    testFeed: false,
+    severityBands: { warn: 40, critical: 70 },
+  },
+};
+
+// Selected with the `ORACLE_PROFILE` env var, defaults to devnet like the programs.
+export const ACTIVE_PROFILE: Profile = PROFILES[(process.env.ORACLE_PROFILE ?? "devnet") as ProfileName];
+
+// Deployment domain folded into feed names, must match the
+// `RISK_ORACLE_FEED_DOMAIN` the program was built with (see `shared/src/feed.rs`).
+export const FEED_DOMAIN: string | undefined = process.env.RISK_ORACLE_FEED_DOMAIN;
+
+export function feedName(base: string): string {
+  return FEED_DOMAIN ? `${base} @${FEED_DOMAIN}` : base;
+}
+
+
+// Address screened by the example when none is given.
+export const DEFAULT_QUERY_ACCOUNT = new PublicKey("5PAhQiYdLBd6SVdjzBQDxUAEFyDdF5ExNPQfcscnPRj5");
+
+const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+const TOKEN_ACCOUNT_LEN = 165;
+
+// Resolve the wallet the program will actually screen for `queryAccount`,
+// mirroring `shared/src/query_account.rs`:
+//   - wallets (system owned, funded or not) are screened as is
+//   - token accounts are screened through their owner
+//   - programs and other program-owned accounts are rejected on-chain
+// The feed must be built for the returned address or the feed ids won't match.
+export async function resolveScreenedAddress(connection: Connection, queryAccount: PublicKey): Promise<PublicKey> {
+  const info = await connection.getAccountInfo(queryAccount);
+  if (!info || info.owner.equals(PublicKey.default)) return queryAccount;
+  if (info.executable) throw new Error(`${queryAccount.toBase58()} is a program`);
+
+  const isTokenAccount =
+    (info.owner.equals(TOKEN_PROGRAM_ID) && info.data.length === TOKEN_ACCOUNT_LEN) ||
+    (info.owner.equals(TOKEN_2022_PROGRAM_ID) &&
+      (info.data.length === TOKEN_ACCOUNT_LEN || info.data[TOKEN_ACCOUNT_LEN] === 2));
+  if (!isTokenAccount) throw new Error(`${queryAccount.toBase58()} is a program-owned account`);
+
+  return new PublicKey(info.data.subarray(32, 64));
+}
 
 // Example Oracle Job to fetch Range Risk Score for a given address
 // The oracle job uses a HTTP task to fetch the risk score from Range API
-// and then parses the JSON response to extract the riskScore field.
+// and then parses the response to extract the riskScore field (the JSON path
+// `$.riskScore` with the default `RANGE_JSON` template).
 // The riskScore is then multiplied by 10 to convert it to a scale of 0-100
 // and bounded between 0 and 100.
 // The API key is passed as a variable override to the oracle job.
 //
 // Note that this job is designed to be used with the Pinocchio program
 // which neeeds to match the feed hash on-chain to ensure the integrity of the data.
-export function getRangeRiskScoreJob(): OracleJob {
+export function getRangeRiskScoreJob(
+  address: PublicKey = DEFAULT_QUERY_ACCOUNT,
+  response: ResponseTemplate = ACTIVE_PROFILE.response,
+): OracleJob {
   const job = OracleJob.fromObject({
     tasks: [
       {
         httpTask: {
-          url: "https://api.range.org/v1/risk/address?address=5PAhQiYdLBd6SVdjzBQDxUAEFyDdF5ExNPQfcscnPRj5&network=solana",
+          url: `${ACTIVE_PROFILE.rangeRiskUrl}?address=${address.toBase58()}&network=${ACTIVE_PROFILE.rangeNetwork}`,
           headers: [
-            { key: "accept", value: "application/json" },
+            { key: "accept", value: response.accept },
             // Resolved on-oracle by Variable Override
             { key: "X-API-KEY", value: "${RANGE_API_KEY}" },
           ],
         },
       },
       // Only accept numeric riskScore >= 0; null => no match => failure so no Risk
-      { jsonParseTask: { path: "$.riskScore" } },
+      extractionTask(response.extraction),
       { multiplyTask: { scalar: 10 } }, // 0–10 => 0–100
       {
         boundTask: {
@@ -49,6 +230,56 @@ export function getRangeRiskScoreJob(): OracleJob {
   return job;
 }
 
+// Score always reported by the test feed (`feed::TEST_SCORE`).
+export const TEST_SCORE = 42;
+
+// Constant feed for integration tests (mirrors `feed::test_feed`): a single
+// `valueTask`, no HTTP call and no API key. Only accepted by devnet builds
+// (`ACTIVE_PROFILE.testFeed`).
+export function getTestFeed(): IOracleFeed {
+  return {
+    name: feedName("Risk Score Test"),
+    jobs: [OracleJob.fromObject({ tasks: [{ valueTask: { value: TEST_SCORE } }] })],
+    minJobResponses: 1,
+    minOracleSamples: 1,
+    maxJobRangePct: 100,
+  };
+}
+
+// Pass/fail feed (mirrors `feed::pass_fail_feed`): the oracle compares the
+// score with `threshold` (0–100) and reports 1 (safe, at or below it) or 0
+// (risky, or the score couldn't be fetched). Each threshold is its own feed.
+export function getPassFailFeed(address: PublicKey, threshold: number): IOracleFeed {
+  const comparisonTask = {
+    comparisonTask: {
+      op: 1, // OPERATION_GT
+      lhs: getRangeRiskScoreJob(address),
+      rhsValue: threshold.toString(),
+      onTrueValue: "0",
+      onFalseValue: "1",
+      onFailureValue: "0",
+    },
+  };
+  return {
+    name: feedName(`Risk Pass/Fail ${threshold}`),
+    jobs: [
+      OracleJob.fromObject({
+        tasks: [
+          {
+            conditionalTask: {
+              attempt: [comparisonTask],
+              onFailure: [{ valueTask: { value: 0 } }],
+            },
+          },
+        ],
+      }),
+    ],
+    minJobResponses: 1,
+    minOracleSamples: 1,
+    maxJobRangePct: 100,
+  };
+}
+
 // Fetch a signed oracle quote **and** build the Ed25519 signature verification
 // Flow:
 // 1) Choose the queue (devnet in this example)
@@ -58,13 +289,21 @@ export function getRangeRiskScoreJob(): OracleJob {
 //    and passing `variableOverrides` so oracles can resolve `${RANGE_API_KEY}`
 //
 // The returned `sigVerifyIx` is the Ed25519 signature verification
-export async function getOracleJobSignature(payer: Keypair): Promise<{ queue_account: PublicKey; sigVerifyIx: TransactionInstruction }> {
+//
+// Pass `customFeed` (e.g. `getTestFeed()`) to quote another template.
+export async function getOracleJobSignature(
+  payer: Keypair,
+  screenedAddress: PublicKey = DEFAULT_QUERY_ACCOUNT,
+  customFeed?: IOracleFeed,
+): Promise<{ queue_account: PublicKey; sigVerifyIx: TransactionInstruction }> {
   const { gateway, rpcUrl } = await sb.AnchorUtils.loadEnv();
 
   // Get the queue for the network you're deploying on
   //
-  // Devnet queue (use `getDefaultQueue(rpcUrl)` for mainnet)
-  let queue = await sb.getDefaultDevnetQueue(rpcUrl);
+  // Mainnet uses `getDefaultQueue(rpcUrl)`, devnet and staging the devnet queue
+  let queue = ACTIVE_PROFILE.name === "mainnet"
+    ? await getDefaultQueue(rpcUrl)
+    : await sb.getDefaultDevnetQueue(rpcUrl);
   let queue_account = queue.pubkey;
 
 
@@ -77,9 +316,9 @@ export async function getOracleJobSignature(payer: Keypair): Promise<{ queue_acc
 
   // Build  IOracleFeed (feed proto) from your job(s)
   // Keep values minimal and consistent; defaults vs explicit values can change the hash.
-  const feed: IOracleFeed = {
-    name: "Risk Score",
-    jobs: [getRangeRiskScoreJob()],
+  const feed: IOracleFeed = customFeed ?? {
+    name: feedName("Risk Score"),
+    jobs: [getRangeRiskScoreJob(screenedAddress)],
     minJobResponses: 1,
     minOracleSamples: 1,
     maxJobRangePct: 100,
@@ -106,26 +345,192 @@ export async function getOracleJobSignature(payer: Keypair): Promise<{ queue_acc
   return { queue_account, sigVerifyIx };
 }
 
+// Approximate slot duration, to express validity windows in seconds.
+export const SLOT_DURATION_MS = 400;
+
+// Default safety margin of `assertQuoteValid`/`getFreshOracleJobSignature`:
+// time for the transaction to land.
+export const DEFAULT_QUOTE_BUFFER_SLOTS = 10;
+
+// Slot of a quote instruction (`sigVerifyIx`): the u64 LE right after the last
+// signed message, key or signature of the Ed25519 instruction.
+export function quoteSlot(sigVerifyIx: TransactionInstruction): bigint {
+  const data = sigVerifyIx.data;
+  const count = data.length > 0 ? data[0] : 0;
+  let end = 2 + count * 14;
+  for (let i = 0; i < count && 2 + i * 14 + 14 <= data.length; i++) {
+    const at = 2 + i * 14;
+    const signatureOffset = data.readUInt16LE(at);
+    const publicKeyOffset = data.readUInt16LE(at + 4);
+    const messageOffset = data.readUInt16LE(at + 8);
+    const messageSize = data.readUInt16LE(at + 10);
+    end = Math.max(end, messageOffset + messageSize, publicKeyOffset + 32, signatureOffset + 64);
+  }
+  if (count === 0 || data.length < end + 8) {
+    throw new Error("the quote instruction carries no slot");
+  }
+  return data.readBigUInt64LE(end);
+}
+
+// How long a quote stays acceptable on-chain at `currentSlot`.
+//   - maxAgeSlots: the tighter of the profile's `verifierMaxAge` and
+//     `maxQuoteAgeSlots`, the oldest quote both checks accept
+//   - remainingSlots: slots left before the quote is rejected, negative once
+//     it is
+//   - remainingSeconds: the same at `SLOT_DURATION_MS`
+export interface QuoteValidity {
+  quoteSlot: bigint;
+  currentSlot: bigint;
+  maxAgeSlots: bigint;
+  remainingSlots: bigint;
+  remainingSeconds: number;
+}
+
+export function quoteValidity(
+  slot: bigint,
+  currentSlot: bigint,
+  profile: Profile = ACTIVE_PROFILE,
+): QuoteValidity {
+  const maxAgeSlots = BigInt(Math.min(profile.verifierMaxAge, profile.maxQuoteAgeSlots));
+  const remainingSlots = slot + maxAgeSlots - currentSlot;
+  return {
+    quoteSlot: slot,
+    currentSlot,
+    maxAgeSlots,
+    remainingSlots,
+    remainingSeconds: (Number(remainingSlots) * SLOT_DURATION_MS) / 1000,
+  };
+}
+
+// Refuses to send a quote that would leave less than `bufferSlots` of
+// validity at the current slot; returns the validity otherwise.
+export async function assertQuoteValid(
+  connection: Connection,
+  sigVerifyIx: TransactionInstruction,
+  bufferSlots: number = DEFAULT_QUOTE_BUFFER_SLOTS,
+): Promise<QuoteValidity> {
+  const validity = quoteValidity(quoteSlot(sigVerifyIx), BigInt(await connection.getSlot()));
+  if (validity.remainingSlots < BigInt(bufferSlots)) {
+    throw new Error(
+      `quote from slot ${validity.quoteSlot} has ${validity.remainingSlots} slots ` +
+        `(~${validity.remainingSeconds.toFixed(1)}s) of validity left, below the ` +
+        `${bufferSlots}-slot buffer`,
+    );
+  }
+  return validity;
+}
+
+// `getOracleJobSignature`, refetching (at most `attempts` times in all) while
+// the quote would leave less than `bufferSlots` of validity.
+export async function getFreshOracleJobSignature(
+  connection: Connection,
+  payer: Keypair,
+  screenedAddress: PublicKey = DEFAULT_QUERY_ACCOUNT,
+  customFeed?: IOracleFeed,
+  opts: { bufferSlots?: number; attempts?: number } = {},
+): Promise<{ queue_account: PublicKey; sigVerifyIx: TransactionInstruction; validity: QuoteValidity }> {
+  const attempts = opts.attempts ?? 3;
+  for (let attempt = 1; ; attempt++) {
+    const quote = await getOracleJobSignature(payer, screenedAddress, customFeed);
+    try {
+      const validity = await assertQuoteValid(connection, quote.sigVerifyIx, opts.bufferSlots);
+      return { ...quote, validity };
+    } catch (err) {
+      if (attempt >= attempts) throw err;
+    }
+  }
+}
+
+// Options of the get-risk-score builders:
+//   - omitClock: leave the clock sysvar out
+//   - notAfterSlot: deadline slot, past which the program rejects the
+//     instruction
+//   - screenPrograms: reject the transaction when one of its instructions
+//     targets a program of the program's `DENIED_PROGRAMS`
+export interface GetRiskScoreOptions {
+  omitClock?: boolean;
+  notAfterSlot?: bigint;
+  screenPrograms?: boolean;
+}
+
+// Prefixes of the instruction data, in this order: the deadline
+// `[4, not_after_slot (u64 LE)]`, then program screening `[5]`.
+function withPrefixes(data: Buffer, opts: GetRiskScoreOptions): Buffer {
+  if (opts.screenPrograms) data = Buffer.concat([Buffer.from([5]), data]);
+  if (opts.notAfterSlot === undefined) return data;
+  const slot = Buffer.alloc(8);
+  slot.writeBigUInt64LE(opts.notAfterSlot);
+  return Buffer.concat([Buffer.from([4]), slot, data]);
+}
+
 //  
 // This instruction passes the accounts your program needs:
 //   - queue (to verify the quote)
 //   - sysvars (clock, slot hashes, instructions)
 //   - query_account (the address you want to fetch the risk score for)
 //
-// Note: no data is sent to the program in this example; all info is in accounts
-export function buildGetRiskScoreIx(queue: PublicKey, query_account: PublicKey): TransactionInstruction {
+// The clock is optional: with `omitClock` the program reads the slot through
+// the clock syscall, one account less for integrators calling it via CPI.
+//
+// With `notAfterSlot`, the program rejects the instruction once the slot is
+// past it, even with a fresh quote. With `screenPrograms`, it rejects
+// transactions routing through a denied program (mixer routers and the like).
+//
+// Note: by default no data is sent to the program; all info is in accounts.
+// Pass `ata` to use ATA mode: `query_account` must then be the wallet, the
+// program derives its associated token account for `ata.mint` and screens the
+// wallet (build the feed for the wallet, not the ATA).
+export function buildGetRiskScoreIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  ata?: { mint: PublicKey; tokenProgram: PublicKey },
+  opts: GetRiskScoreOptions = {},
+): TransactionInstruction {
+  const mode = ata
+    ? Buffer.concat([Buffer.from([1]), ata.mint.toBuffer(), ata.tokenProgram.toBuffer()])
+    : Buffer.alloc(0);
+  const data = withPrefixes(mode, opts);
+
+  const clock = opts.omitClock
+    ? []
+    : [{ pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false }]; // clock_sysvar_info
 
   return new TransactionInstruction({
     programId: PROGRAM_ID,
     keys: [
       // payer_info
       { pubkey: queue, isSigner: false, isWritable: false }, // queue
-      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false }, // clock_sysvar_info
+      ...clock,
       { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false }, // slothashes_sysvar_info
       { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false }, // instructions_sysvar_info
       { pubkey: query_account, isSigner: false, isWritable: false }, // query_account_info
 
     ],
-    data: Buffer.alloc(0), // no data to send
+    data, // empty unless ATA mode or a prefix
   });
 }
+
+// Test feed mode (`[2]`): verify a quote of `getTestFeed()` for
+// `query_account` (devnet builds only).
+export function buildGetRiskScoreTestIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  opts: GetRiskScoreOptions = {},
+): TransactionInstruction {
+  const ix = buildGetRiskScoreIx(queue, query_account, undefined, opts);
+  ix.data = withPrefixes(Buffer.from([2]), opts);
+  return ix;
+}
+
+// Pass/fail mode (`[3, threshold]`): succeeds only if the quote of
+// `getPassFailFeed(query_account, threshold)` reports 1.
+export function buildGetRiskScorePassFailIx(
+  queue: PublicKey,
+  query_account: PublicKey,
+  threshold: number,
+  opts: GetRiskScoreOptions = {},
+): TransactionInstruction {
+  const ix = buildGetRiskScoreIx(queue, query_account, undefined, opts);
+  ix.data = withPrefixes(Buffer.from([3, threshold]), opts);
+  return ix;
+}
@@ -13,7 +13,15 @@ import {
 } from "@solana/web3.js";
 import "mocha";
 import * as path from "path";
-import { buildGetRiskScoreIx, getOracleJobSignature, PROGRAM_ID } from "./sdk.ts";
+import {
+  ACTIVE_PROFILE,
+  buildGetRiskScoreIx,
+  buildGetRiskScoreTestIx,
+  getOracleJobSignature,
+  getTestFeed,
+  PROGRAM_ID,
+  TEST_SCORE,
+} from "./sdk.ts";
 import "dotenv/config";
 
 // Load a Keypair from a JSON file
@@ -80,7 +88,26 @@ describe("Initialize Oracle Example", function () {
 
     // Some basic assertion to ensure it went through can be added here
   });
-});
 
+  it("verifies the constant test feed without an API key", async function () {
+    if (!ACTIVE_PROFILE.testFeed) this.skip();
 
+    // A quote of the ValueTask-only test feed: deterministic, no HTTP call.
+    const query_account = new PublicKey("5PAhQiYdLBd6SVdjzBQDxUAEFyDdF5ExNPQfcscnPRj5");
+    const { queue_account, sigVerifyIx } = await getOracleJobSignature(
+      DEV_WALLET,
+      query_account,
+      getTestFeed(),
+    );
 
+    const tx = new Transaction().add(sigVerifyIx, buildGetRiskScoreTestIx(queue_account, query_account));
+    const signature = await sendAndConfirmTransaction(connection, tx, [DEV_WALLET]);
+
+    const confirmed = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const logs = confirmed?.meta?.logMessages ?? [];
+    assert(logs.some((log) => log.includes(`Risk Score ${TEST_SCORE}`)), logs.join("\n"));
+  });
+});
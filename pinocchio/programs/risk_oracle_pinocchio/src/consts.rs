@@ -1,6 +1,22 @@
 use pinocchio::pubkey::Pubkey;
 use pinocchio_pubkey::pubkey;
+use risk_oracle_shared::feed_id::FeedIdDerivation;
 
 /// Public key for the targeted program.
 ///
 pub const BLACKNOTE_PROGRAM_ID: Pubkey = pubkey!("FH4YSCbf3vBKZKMJjtSqAeRQmXDM7HCNVaUuDiivPgYA");
+
+/// Programs no instruction of the transaction may target when the
+/// verification screens programs (the `[5]` prefix of the instruction data,
+/// see `risk_oracle_shared::program_screen`). The program keeps no state, so
+/// the list is fixed at build time; the Anchor program keeps its counterpart
+/// in an on-chain registry managed by its authority.
+pub const DENIED_PROGRAMS: &[Pubkey] = &[BLACKNOTE_PROGRAM_ID];
+
+/// How the program derives feed ids: as the gateway's `FeedRequestV2` with
+/// the `feed-request-v2-ids` feature, length-delimited otherwise (see
+/// `risk_oracle_shared::feed_id`).
+#[cfg(not(feature = "feed-request-v2-ids"))]
+pub const FEED_ID_DERIVATION: FeedIdDerivation = FeedIdDerivation::LengthDelimited;
+#[cfg(feature = "feed-request-v2-ids")]
+pub const FEED_ID_DERIVATION: FeedIdDerivation = FeedIdDerivation::FeedRequestV2;
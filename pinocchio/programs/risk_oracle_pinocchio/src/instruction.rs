@@ -0,0 +1,100 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+use risk_oracle_shared::feed::MAX_SCORE;
+
+/// Tag of the ATA mode instruction data.
+pub const ATA_MODE_TAG: u8 = 1;
+
+/// Tag of the test feed mode instruction data.
+pub const TEST_FEED_TAG: u8 = 2;
+
+/// Tag of the pass/fail mode instruction data.
+pub const PASS_FAIL_TAG: u8 = 3;
+
+/// Tag of the deadline prefix of the instruction data.
+pub const DEADLINE_TAG: u8 = 4;
+
+/// Tag of the program screening prefix of the instruction data.
+pub const PROGRAM_SCREEN_TAG: u8 = 5;
+
+/// Splits the optional `[4, not_after_slot (u64 LE)]` prefix off the
+/// instruction data: the verification fails once the slot is past
+/// `not_after_slot`. The rest is the [`QueryMode`] data.
+pub fn split_deadline(data: &[u8]) -> (Option<u64>, &[u8]) {
+    match data {
+        [DEADLINE_TAG, rest @ ..] if rest.len() >= 8 => {
+            let (not_after_slot, mode) = rest.split_at(8);
+            (Some(u64::from_le_bytes(not_after_slot.try_into().unwrap())), mode)
+        }
+        _ => (None, data),
+    }
+}
+
+/// Splits the optional `[5]` prefix off the instruction data, after the
+/// deadline: the verification fails when an instruction of the transaction
+/// targets one of `consts::DENIED_PROGRAMS`. The rest is the [`QueryMode`]
+/// data.
+pub fn split_program_screen(data: &[u8]) -> (bool, &[u8]) {
+    match data {
+        [PROGRAM_SCREEN_TAG, mode @ ..] => (true, mode),
+        _ => (false, data),
+    }
+}
+
+/// How the program picks the pubkey that ends up in the Range URL.
+///
+/// See the table in `risk_oracle_shared::query_account` for the exact pubkey
+/// screened in each mode.
+pub enum QueryMode {
+    /// Empty instruction data: `query_account` is classified, wallets are
+    /// screened as is and token accounts through their owner.
+    Classify,
+    /// `[1, mint (32 bytes), token_program (32 bytes)]`: `query_account` must be
+    /// a wallet. The program derives its associated token account for `mint`
+    /// and screens the wallet.
+    AssociatedTokenAccount { mint: Pubkey, token_program: Pubkey },
+    /// `[2]`: like `Classify`, but the quote must be for the constant
+    /// `feed::test_feed` (devnet profile only).
+    TestFeed,
+    /// `[3, threshold]`: like `Classify`, but the quote must be for
+    /// `feed::pass_fail_feed` with `threshold` (0–100), and its value must be
+    /// `feed::PASS`. The oracle compared the score, the program doesn't.
+    PassFail { threshold: u8 },
+}
+
+/// Feed template verified by a [`QueryMode`].
+#[derive(Clone, Copy)]
+pub enum Template {
+    RiskScore,
+    Test,
+    PassFail { threshold: u8 },
+}
+
+impl QueryMode {
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        match data {
+            [] => Ok(Self::Classify),
+            [TEST_FEED_TAG] => Ok(Self::TestFeed),
+            [PASS_FAIL_TAG, threshold] if *threshold <= MAX_SCORE => Ok(Self::PassFail {
+                threshold: *threshold,
+            }),
+            [ATA_MODE_TAG, rest @ ..] if rest.len() == 64 => {
+                let (mint, token_program) = rest.split_at(32);
+                Ok(Self::AssociatedTokenAccount {
+                    mint: mint.try_into().unwrap(),
+                    token_program: token_program.try_into().unwrap(),
+                })
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    pub fn template(&self) -> Template {
+        match self {
+            Self::Classify | Self::AssociatedTokenAccount { .. } => Template::RiskScore,
+            Self::TestFeed => Template::Test,
+            Self::PassFail { threshold } => Template::PassFail {
+                threshold: *threshold,
+            },
+        }
+    }
+}
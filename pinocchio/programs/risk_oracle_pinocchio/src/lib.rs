@@ -6,5 +6,7 @@
 pub mod consts;
 #[cfg(feature = "bpf-entrypoint")]
 pub mod entrypoint;
+pub mod instruction;
+pub mod verify;
 
 pinocchio_pubkey::declare_id!("CR8mpiY9eEbNkU8w4VJkGB4gzEnozp739jwvTiXRmACc");
@@ -0,0 +1,430 @@
+//! The verification routine, callable in process.
+//!
+//! The program's instruction is a thin wrapper around [`verify`]: it splits
+//! the accounts, verifies, and returns the oracle count as return data.
+//! Programs embedding the verification instead of invoking this program
+//! depend on the crate without `bpf-entrypoint` and call [`verify`] on their
+//! own accounts; it logs and fails exactly as the instruction does.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, instructions::INSTRUCTIONS_ID, slot_hashes::SLOTHASHES_ID, Sysvar},
+};
+use pinocchio_log::log;
+use risk_oracle_shared::{
+    accounts::AccountsError,
+    diagnostics, feed,
+    feed_match::{self, MatchResult},
+    hash, profiles,
+    program_screen::{self, ProgramScreenError},
+    query_account::{self as query, QueryAccountKind},
+    quote_limits, verifier_inputs,
+};
+use rust_decimal::Decimal;
+use switchboard_on_demand::{get_slot, QuoteVerifier};
+
+use crate::consts;
+use crate::instruction::{self, QueryMode, Template};
+
+/// Accounts read by [`verify`], in the order of the instruction.
+pub struct VerifyAccounts<'a> {
+    pub queue: &'a AccountInfo,
+    /// The Clock sysvar, or `None` to read the slot through the syscall.
+    pub clock: Option<&'a AccountInfo>,
+    pub slothashes: &'a AccountInfo,
+    pub instructions: &'a AccountInfo,
+    pub query_account: &'a AccountInfo,
+}
+
+/// A successful verification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Verified {
+    /// Score of the matched feed, 0–100 (`feed::score_from_feed_value`).
+    pub score: u8,
+    /// Oracle signatures backing the quote.
+    pub oracle_samples: u8,
+}
+
+/// Recreate the Switchboard feed on-chain as a protobuf structure
+/// that matches the client feed byte-for-byte (same tasks, same fields,
+/// same order). We then encode it and SHA-256 hash the bytes to
+/// derive the canonical **feed id**.
+///
+/// Verify the oracle quote signatures by requiring an Ed25519
+/// verification instruction at index 0 (supplied by the client), and
+/// validate freshness (SlotHashes) & queue.
+/// This yields a `quote` with one or more `feeds()`.
+///
+/// Compare our derived feed id with the `feed_id()` inside the verified
+/// quote. If they match, we trust the `value()` and log it.
+///
+/// Note: Any change to the client's feed definition (URL, headers, task
+/// ordering, bounds, etc.) changes the hash → mismatch → instruction fails.
+///
+/// `instruction_data` is the instruction's: the optional deadline, then the
+/// query mode (see [`instruction`]).
+#[inline(never)]
+pub fn verify(accounts: VerifyAccounts, instruction_data: &[u8]) -> Result<Verified, ProgramError> {
+    let VerifyAccounts {
+        queue,
+        clock: clock_sysvar,
+        slothashes: slothashes_sysvar,
+        instructions: instructions_sysvar,
+        query_account,
+    } = accounts;
+
+    // The queue is checked here rather than left to the verifier: it must be
+    // the profile's queue, owned by the Switchboard On-Demand program.
+    if !queue.is_owned_by(&profiles::ACTIVE.switchboard_program) {
+        return Err(OracleError::InvalidQueueOwner.into());
+    }
+    if queue.key() != &profiles::ACTIVE.queue {
+        return Err(OracleError::QueueMismatch.into());
+    }
+    // The verifier asserts on the sysvar addresses instead of failing.
+    if slothashes_sysvar.key() != &SLOTHASHES_ID || instructions_sysvar.key() != &INSTRUCTIONS_ID
+    {
+        return Err(OracleError::InvalidSysvar.into());
+    }
+
+    // ===== Recreate the feed proto on-chain (same as client) =====
+
+    // Classify the query account first: token accounts are screened through
+    // their owner wallet, programs and program-owned accounts are rejected.
+    let kind = query::classify(
+        query_account.owner(),
+        query_account.executable(),
+        &query_account.try_borrow_data()?,
+    );
+    let (not_after_slot, mode_data) = instruction::split_deadline(instruction_data);
+    let (screen_programs, mode_data) = instruction::split_program_screen(mode_data);
+    let mode = QueryMode::unpack(mode_data)?;
+    let template = mode.template();
+    if matches!(template, Template::Test) && !profiles::ACTIVE.test_feed {
+        return Err(OracleError::TestFeedDisabled.into());
+    }
+    let screened_address = match (mode, kind) {
+        (QueryMode::AssociatedTokenAccount { mint, token_program }, QueryAccountKind::Wallet) => {
+            if token_program != query::TOKEN_PROGRAM_ID
+                && token_program != query::TOKEN_2022_PROGRAM_ID
+            {
+                return Err(OracleError::InvalidTokenProgram.into());
+            }
+            // ATA mode screens the wallet itself, never the ATA. Deriving and
+            // base58-encoding the ATA costs compute on every verification, so
+            // only `logging` builds name it.
+            #[cfg(feature = "logging")]
+            {
+                let (ata, _) = pinocchio::pubkey::find_program_address(
+                    &[query_account.key().as_ref(), token_program.as_ref(), mint.as_ref()],
+                    &query::ASSOCIATED_TOKEN_PROGRAM_ID,
+                );
+                log!(
+                    "Screening wallet for associated token account {}",
+                    bs58::encode(ata).into_string().as_str()
+                );
+            }
+            #[cfg(not(feature = "logging"))]
+            let _ = mint;
+            *query_account.key()
+        }
+        (QueryMode::AssociatedTokenAccount { .. }, _) => {
+            return Err(OracleError::QueryAccountNotWallet.into())
+        }
+        (_, QueryAccountKind::Wallet) => *query_account.key(),
+        (_, QueryAccountKind::TokenAccount { owner }) => owner,
+        (_, QueryAccountKind::ProgramOwned) => {
+            return Err(OracleError::QueryAccountIsProgramOwned.into())
+        }
+        (_, QueryAccountKind::Program) => return Err(OracleError::QueryAccountIsProgram.into()),
+    };
+
+    #[cfg(feature = "compute-log")]
+    log_compute(risk_oracle_shared::compute::ACCOUNTS);
+
+    // Build the same feed as the client for the screened pubkey. The template
+    // (URL, headers, tasks) lives in `risk_oracle_shared::feed`.
+    let feed = match template {
+        Template::RiskScore => feed::risk_score_feed(&screened_address),
+        Template::Test => feed::test_feed(),
+        Template::PassFail { threshold } => feed::pass_fail_feed(&screened_address, threshold),
+    };
+
+    // Encode to protobuf bytes: length-delimited, or the bare message with
+    // the `feed-request-v2-ids` feature (see `risk_oracle_shared::feed_id`)
+    let bytes = consts::FEED_ID_DERIVATION.preimage(&feed);
+
+    // Hash to 32-byte feed id (Switchboard uses SHA-256 of the encoded bytes),
+    // through the `sol_sha256` syscall (see `risk_oracle_shared::hash`)
+    let derived_feed_hash = hash::hash(&bytes);
+
+    #[cfg(feature = "compute-log")]
+    log_compute(risk_oracle_shared::compute::FEED);
+
+    #[cfg(feature = "audit")]
+    log!("{}", risk_oracle_shared::audit::feed_id(&derived_feed_hash).as_str());
+
+    // --------  Verify the quote signatures / freshness / queue --------
+
+    // The client must prepend an Ed25519-program instruction at **index 0** that
+    // verifies the guardian signatures over the quote. QuoteVerifier checks:
+    //   - Ed25519 ix at idx 0 matches the signatures in the quote
+    //   - SlotHashes sysvar → the quote is fresh enough (max_age)
+    //   - Queue account is the expected Switchboard queue
+    // Returns a decoded quote with one or more `feeds()`.
+
+    // - `get_slot` reads current slot from Clock sysvar (Pinocchio-friendly),
+    //   `Clock::get` through the syscall when the account is omitted.
+    let slot = match clock_sysvar {
+        Some(clock_sysvar) => get_slot(clock_sysvar),
+        None => Clock::get()?.slot,
+    };
+
+    // The client's deadline binds the verification even when the quote is
+    // still fresh.
+    if let Some(not_after_slot) = not_after_slot.filter(|&deadline| slot > deadline) {
+        log!("{}", diagnostics::deadline_passed(slot, not_after_slot).as_str());
+        return Err(OracleError::DeadlinePassed.into());
+    }
+
+    // Transaction shape: no instruction may target a denied program, checked
+    // before paying for the quote verification.
+    if screen_programs {
+        let screened = program_screen::screen_programs(
+            &instructions_sysvar.try_borrow_data()?,
+            |program_id| consts::DENIED_PROGRAMS.contains(program_id),
+        );
+        match screened {
+            Ok(()) => {}
+            Err(ProgramScreenError::Denied { index, program_id }) => {
+                log!("{}", diagnostics::denied_program(index, &program_id).as_str());
+                return Err(OracleError::DeniedProgramInTransaction.into());
+            }
+            Err(ProgramScreenError::Malformed) => return Err(OracleError::InvalidSysvar.into()),
+        }
+    }
+
+    // Bound the work before the verifier parses the quote: reject quote
+    // instructions padded past the profile's limit. A missing quote
+    // instruction is left for the verifier to report.
+    // The quote is at index 0, or with `quote-discovery` after the leading
+    // ComputeBudget instructions.
+    let quote_index = if cfg!(feature = "quote-discovery") {
+        quote_limits::discover_quote_index(&instructions_sysvar.try_borrow_data()?)
+    } else {
+        0
+    };
+    let quote_len =
+        quote_limits::instruction_data_len(&instructions_sysvar.try_borrow_data()?, quote_index);
+    if let Some(len) = quote_len.filter(|&len| len > profiles::ACTIVE.max_quote_data_len) {
+        log!(
+            "{}",
+            diagnostics::quote_too_large(len, profiles::ACTIVE.max_quote_data_len).as_str()
+        );
+        return Err(OracleError::QuoteTooLarge.into());
+    }
+
+    // The verifier panics on quotes it can't read or whose slot hash and
+    // signers don't match SlotHashes and the queue; fail with a code instead.
+    if let Err(error) = verifier_inputs::check_quote(
+        &instructions_sysvar.try_borrow_data()?,
+        quote_index,
+        &queue.try_borrow_data()?,
+        &slothashes_sysvar.try_borrow_data()?,
+    ) {
+        log!("{}", diagnostics::verification_failed(error.reason()).as_str());
+        return Err(OracleError::InvalidQuote.into());
+    }
+
+    // - `QuoteVerifier` verifies the Ed25519 signature ix and decodes the quote.
+    let quote_index =
+        i64::try_from(quote_index).map_err(|_| OracleError::InstructionQuoteMissing)?;
+    let mut quote_verifier = QuoteVerifier::new();
+    let quote_data = quote_verifier
+        .slothash_sysvar(slothashes_sysvar) // Sets the slot hash sysvar account for verification.
+        .ix_sysvar(instructions_sysvar) // Sets the instructions sysvar account for verification.
+        .clock_slot(slot) // Sets the current slot for freshness verification.
+        .queue(queue) // Sets the oracle queue account.
+        .max_age(profiles::ACTIVE.verifier_max_age) // Sets the maximum age of the quote in seconds.
+        .verify_instruction_at(quote_index) // Verifies the quote at its instruction index.
+        .map_err(|_| OracleError::InstructionQuoteMissing)?;
+
+    let quote_slot = quote_data.slot();
+
+    #[cfg(feature = "audit")]
+    log!(
+        "{}",
+        risk_oracle_shared::audit::quote(
+            queue.key(),
+            slot,
+            quote_slot,
+            profiles::ACTIVE.max_quote_age_slots
+        )
+        .as_str()
+    );
+
+    // Ensure the quote is recent enough (within the profile's slot limit).
+    if slot.saturating_sub(quote_slot) > profiles::ACTIVE.max_quote_age_slots {
+        log!(
+            "{}",
+            diagnostics::stale_quote(slot, quote_slot, profiles::ACTIVE.max_quote_age_slots)
+                .as_str()
+        );
+        return Err(OracleError::StaleQuote.into());
+    }
+
+    let feed_count = quote_data.feeds().len();
+    if feed_count > profiles::ACTIVE.max_quote_feeds {
+        log!(
+            "{}",
+            diagnostics::too_many_quote_feeds(feed_count, profiles::ACTIVE.max_quote_feeds)
+                .as_str()
+        );
+        return Err(OracleError::TooManyQuoteFeeds.into());
+    }
+
+    #[cfg(feature = "audit")]
+    for (index, feed_info) in quote_data.feeds().iter().enumerate() {
+        let feed_matched =
+            risk_oracle_shared::constant_time::eq_32(feed_info.feed_id(), &derived_feed_hash);
+        log!(
+            "{}",
+            risk_oracle_shared::audit::quote_feed(index, feed_info.feed_id(), feed_matched)
+                .as_str()
+        );
+    }
+
+    // Find our derived feed id among the verified feeds. If matched, we trust
+    // its `value()` and can act on it.
+    // If no feed matched, fail. This usually means the client feed proto is not
+    // identical (different headers/order/fields) or quote wasn’t fetched for
+    // this exact feed.
+    let feeds = quote_data.feeds();
+    let ids = feeds.iter().map(|feed_info| feed_info.feed_id());
+    let MatchResult::Matched {
+        positions: [position],
+    } = feed_match::match_feeds(ids, &[derived_feed_hash])
+    else {
+        let first_feed_id = feeds.first().map(|feed_info| feed_info.feed_id());
+        log!(
+            "{}",
+            diagnostics::feed_mismatch(&derived_feed_hash, first_feed_id).as_str()
+        );
+        return Err(OracleError::FeedIdMismatch.into());
+    };
+    let feed_info = &feeds[position];
+    #[cfg(feature = "audit")]
+    log!("{}", risk_oracle_shared::audit::value(feed_info.feed_value()).as_str());
+    // The score, not the formatted `Decimal`: this runs on every verification.
+    let score = feed::score_from_feed_value(feed_info.feed_value());
+    log!("Risk Score {}", score);
+    // On its own line so readers of `Risk Score` keep parsing a number.
+    log!("Severity {}", profiles::ACTIVE.severity_bands.classify(score).as_str());
+
+    // The feed's `min_oracle_samples` is part of its id; the profile can only
+    // raise it.
+    let required = feed_info
+        .min_oracle_samples()
+        .max(profiles::ACTIVE.min_oracle_samples);
+    if quote_data.oracle_count < required {
+        log!(
+            "{}",
+            diagnostics::insufficient_samples(quote_data.oracle_count, required).as_str()
+        );
+        return Err(OracleError::InsufficientOracleSamples.into());
+    }
+    // Pass/fail mode: the oracle already compared the score with the
+    // threshold baked into the feed.
+    if matches!(template, Template::PassFail { .. })
+        && feed_info.value() != Decimal::from(feed::PASS)
+    {
+        return Err(OracleError::RiskCheckFailed.into());
+    }
+
+    #[cfg(feature = "compute-log")]
+    log_compute(risk_oracle_shared::compute::VERIFIED);
+
+    Ok(Verified {
+        score,
+        oracle_samples: quote_data.oracle_count,
+    })
+}
+
+/// Logs the compute units left at `checkpoint` (`risk_oracle_shared::compute`).
+#[cfg(feature = "compute-log")]
+fn log_compute(checkpoint: &str) {
+    #[cfg(target_os = "solana")]
+    // SAFETY: the syscall takes no arguments and only reads the meter.
+    let remaining = unsafe { pinocchio::syscalls::sol_remaining_compute_units() };
+    #[cfg(not(target_os = "solana"))]
+    let remaining = 0;
+    log!(
+        "{}",
+        risk_oracle_shared::compute::checkpoint(checkpoint, remaining).as_str()
+    );
+}
+
+#[derive(Clone, PartialEq)]
+pub enum OracleError {
+    // feed id mismatch
+    FeedIdMismatch,
+    // invalid quote
+    InvalidQuote,
+    // stale quote
+    StaleQuote,
+    // instruction quote missing
+    InstructionQuoteMissing,
+    // query account is an executable program
+    QueryAccountIsProgram,
+    // query account is a program-owned data account (PDA, mint, ...)
+    QueryAccountIsProgramOwned,
+    // ATA mode requires the query account to be a wallet
+    QueryAccountNotWallet,
+    // token program is neither SPL Token nor Token-2022
+    InvalidTokenProgram,
+    // test feed mode in a profile without the test feed
+    TestFeedDisabled,
+    // pass/fail mode and the oracle reported a fail
+    RiskCheckFailed,
+    // quote signed by fewer oracles than the feed or profile requires
+    InsufficientOracleSamples,
+    // quote instruction larger than the profile allows
+    QuoteTooLarge,
+    // quote carrying more feeds than the profile allows
+    TooManyQuoteFeeds,
+    // queue account not owned by the Switchboard On-Demand program
+    InvalidQueueOwner,
+    // queue account is not the profile's queue
+    QueueMismatch,
+    // slot past the verification deadline
+    DeadlinePassed,
+    // SlotHashes or Instructions account is not the sysvar
+    InvalidSysvar,
+    // accounts passed after the ones the instruction reads
+    UnexpectedAccounts,
+    // an instruction of the transaction targets a denied program
+    DeniedProgramInTransaction,
+}
+
+// Off-chain tooling names the codes through `risk_oracle_shared::errors::PINOCCHIO`:
+// append new variants there too.
+const _: () = assert!(
+    risk_oracle_shared::errors::PINOCCHIO.len()
+        == OracleError::DeniedProgramInTransaction as usize + 1
+);
+
+/// Error code of a failed account split (`From` can't be implemented: both
+/// types are foreign).
+pub fn accounts_error(e: AccountsError) -> ProgramError {
+    match e {
+        AccountsError::NotEnoughAccounts { .. } => ProgramError::NotEnoughAccountKeys,
+        AccountsError::UnexpectedAccounts { .. } => OracleError::UnexpectedAccounts.into(),
+    }
+}
+
+impl From<OracleError> for ProgramError {
+    fn from(e: OracleError) -> Self {
+        Self::Custom(e as u32)
+    }
+}
@@ -0,0 +1,29 @@
+//! Cost of `feed_match::match_feeds` per quote size, best case (the derived id
+//! is the first feed) and worst case (last feed, or missing).
+//!
+//! `cargo bench -p risk-oracle-shared --features mainnet --bench match_feeds`
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use risk_oracle_shared::feed_match::match_feeds;
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn bench(label: &str, feed_ids: &[[u8; 32]], expected: &[[u8; 32]; 1]) {
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(match_feeds(black_box(feed_ids).iter(), black_box(expected)));
+    }
+    let per_call = started.elapsed() / ITERATIONS;
+    println!("{:<24} {:>2} feeds  {:>6?}/call", label, feed_ids.len(), per_call);
+}
+
+fn main() {
+    for feeds in [1u8, 2, 4, 8] {
+        let feed_ids: Vec<[u8; 32]> = (0..feeds).map(|i| [i; 32]).collect();
+        bench("first", &feed_ids, &[feed_ids[0]]);
+        bench("last", &feed_ids, &[feed_ids[feeds as usize - 1]]);
+        bench("missing", &feed_ids, &[[u8::MAX; 32]]);
+    }
+}
@@ -0,0 +1,43 @@
+//! Cost per verified feed of the success log line: the score read with
+//! `feed::score_from_feed_value` against formatting the `Decimal` value the
+//! programs used to log.
+//!
+//! `cargo bench -p risk-oracle-shared --features mainnet --bench score_log`
+//!
+//! Host timings; build a program with `compute-log` for the on-chain units.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use risk_oracle_shared::feed::{score_from_feed_value, VALUE_DECIMALS};
+use rust_decimal::Decimal;
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn bench(label: &str, value: i128, log: fn(i128) -> String) {
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(log(black_box(value)));
+    }
+    let per_call = started.elapsed() / ITERATIONS;
+    println!("{:<10} {:>6?}/feed", label, per_call);
+}
+
+fn decimal(value: i128) -> String {
+    Decimal::from_i128_with_scale(value, VALUE_DECIMALS)
+        .normalize()
+        .to_string()
+}
+
+fn score(value: i128) -> String {
+    format!("{}", score_from_feed_value(value))
+}
+
+fn main() {
+    for whole in [0i128, 42, 100] {
+        let value = whole * 10i128.pow(VALUE_DECIMALS);
+        println!("value {}", whole);
+        bench("decimal", value, decimal);
+        bench("score", value, score);
+    }
+}
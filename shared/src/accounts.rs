@@ -0,0 +1,99 @@
+//! Positional account parsing for the instructions of the Pinocchio program,
+//! generic over the account type so it is exercised on the host.
+//!
+//! Required accounts come first and are destructured as an array, the
+//! optional or variable accounts after them (extra query accounts, cache PDAs)
+//! are taken one by one from [`Trailing`]:
+//!
+//! ```ignore
+//! let ([queue, slothashes, instructions], mut trailing) = accounts::split(accounts)?;
+//! let history = trailing.next_if(|account| account.key() == &history_pda);
+//! trailing.finish()?;
+//! ```
+
+/// Accounts that don't fit an instruction's layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountsError {
+    /// Fewer accounts than the instruction requires.
+    NotEnoughAccounts { expected: usize, found: usize },
+    /// Accounts left over after the instruction took the ones it reads.
+    UnexpectedAccounts { count: usize },
+}
+
+/// Splits `accounts` into the `N` required ones, in order, and the accounts
+/// after them.
+pub fn split<T, const N: usize>(
+    accounts: &[T],
+) -> Result<(&[T; N], Trailing<'_, T>), AccountsError> {
+    if accounts.len() < N {
+        return Err(AccountsError::NotEnoughAccounts {
+            expected: N,
+            found: accounts.len(),
+        });
+    }
+    let (required, rest) = accounts.split_at(N);
+    let required = required.try_into().expect("split at N");
+    Ok((required, Trailing { accounts: rest }))
+}
+
+/// Accounts following the required ones, consumed from the front.
+#[derive(Debug)]
+pub struct Trailing<'a, T> {
+    accounts: &'a [T],
+}
+
+impl<'a, T> Trailing<'a, T> {
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// Takes the next account when `is_present` accepts it, for optional
+    /// accounts recognized by their key. Nothing is taken otherwise.
+    pub fn next_if(&mut self, is_present: impl FnOnce(&T) -> bool) -> Option<&'a T> {
+        match self.accounts.split_first() {
+            Some((first, rest)) if is_present(first) => {
+                self.accounts = rest;
+                Some(first)
+            }
+            _ => None,
+        }
+    }
+
+    /// Takes the next `N` accounts, failing when fewer are left.
+    pub fn required<const N: usize>(&mut self) -> Result<&'a [T; N], AccountsError> {
+        let (taken, rest) = split::<T, N>(self.accounts)?;
+        self.accounts = rest.accounts;
+        Ok(taken)
+    }
+
+    /// Takes every account left, for variable-length lists.
+    pub fn rest(&mut self) -> &'a [T] {
+        core::mem::take(&mut self.accounts)
+    }
+
+    /// Fails when accounts are left, for layouts without a variable tail.
+    pub fn finish(self) -> Result<(), AccountsError> {
+        match self.accounts.len() {
+            0 => Ok(()),
+            count => Err(AccountsError::UnexpectedAccounts { count }),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Trailing<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let (first, rest) = self.accounts.split_first()?;
+        self.accounts = rest;
+        Some(first)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.accounts.len(), Some(self.accounts.len()))
+    }
+}
@@ -0,0 +1,63 @@
+//! Evidence trail of a verification, logged by builds with the programs'
+//! `audit` feature.
+//!
+//! One line per step, in the same `key=value` format as [`diagnostics`]
+//! (and parsed by [`diagnostics::parse_with`]), so an auditor replaying a
+//! transaction can check each intermediate value:
+//!
+//! ```text
+//! risk-oracle-audit: step=feed_id derived=<hex>
+//! risk-oracle-audit: step=quote queue=<base58> slot=<u64> quote_slot=<u64> age=<u64> max_age=<u64>
+//! risk-oracle-audit: step=quote_feed index=<usize> feed_id=<hex> matched=<bool>
+//! risk-oracle-audit: step=value value=<i128, 18 decimals> score=<u8>
+//! ```
+//!
+//! [`diagnostics`]: crate::diagnostics
+//! [`diagnostics::parse_with`]: crate::diagnostics::parse_with
+
+use alloc::{format, string::String};
+
+use crate::diagnostics::hex;
+
+/// Prefix of every audit line.
+pub const PREFIX: &str = "risk-oracle-audit:";
+
+/// The feed id derived (or read from a record) for the screened address.
+pub fn feed_id(derived: &[u8; 32]) -> String {
+    format!("{} step=feed_id derived={}", PREFIX, hex(derived))
+}
+
+/// The verified quote: the queue it was checked against and its age in slots.
+pub fn quote(queue: &[u8; 32], slot: u64, quote_slot: u64, max_age: u64) -> String {
+    format!(
+        "{} step=quote queue={} slot={} quote_slot={} age={} max_age={}",
+        PREFIX,
+        bs58::encode(queue).into_string(),
+        slot,
+        quote_slot,
+        slot.saturating_sub(quote_slot),
+        max_age
+    )
+}
+
+/// A feed found in the quote, and whether it matched the derived id.
+pub fn quote_feed(index: usize, feed_id: &[u8; 32], matched: bool) -> String {
+    format!(
+        "{} step=quote_feed index={} feed_id={} matched={}",
+        PREFIX,
+        index,
+        hex(feed_id),
+        matched
+    )
+}
+
+/// The matched feed's exact value, which the success path only logs as a
+/// score.
+pub fn value(value: i128) -> String {
+    format!(
+        "{} step=value value={} score={}",
+        PREFIX,
+        value,
+        crate::feed::score_from_feed_value(value)
+    )
+}
@@ -0,0 +1,36 @@
+//! Compute unit checkpoints, logged by builds with the programs'
+//! `compute-log` feature to find the most expensive phase of a verification.
+//!
+//! One line per checkpoint with the compute units left at that point, in the
+//! same `key=value` format as [`diagnostics`]:
+//!
+//! ```text
+//! risk-oracle-compute: checkpoint=accounts remaining=<u64>
+//! risk-oracle-compute: checkpoint=feed remaining=<u64>
+//! risk-oracle-compute: checkpoint=verified remaining=<u64>
+//! ```
+//!
+//! The units spent by a phase are the difference with the previous line.
+//! The logging itself costs compute units, so only compare builds that both
+//! have the feature.
+//!
+//! [`diagnostics`]: crate::diagnostics
+
+use alloc::{format, string::String};
+
+/// Prefix of every checkpoint line.
+pub const PREFIX: &str = "risk-oracle-compute:";
+
+/// The accounts are parsed and the screened address is resolved.
+pub const ACCOUNTS: &str = "accounts";
+
+/// The feed template is built, encoded and hashed into the feed id.
+pub const FEED: &str = "feed";
+
+/// The quote is verified and its feed matched.
+pub const VERIFIED: &str = "verified";
+
+/// Line of `checkpoint` with `remaining` compute units.
+pub fn checkpoint(checkpoint: &str, remaining: u64) -> String {
+    format!("{} checkpoint={} remaining={}", PREFIX, checkpoint, remaining)
+}
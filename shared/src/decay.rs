@@ -0,0 +1,49 @@
+//! Penalty on cached scores as their quote ages.
+//!
+//! A cache is refreshed by a keeper between uses, so the score a gate reads
+//! from it can be arbitrarily old within the consumer's maximum age. Instead
+//! of trusting it fully up to that age and not at all past it, a [`Decay`]
+//! adds a penalty growing with the age: an old 40 gates as 40 plus the
+//! penalty, and the riskier reading is what fails first as the cache goes
+//! stale. Scores only ever move up, so decay never lets through a score that
+//! would fail as cached.
+
+use crate::feed::MAX_SCORE;
+
+/// Penalty of `penalty` per started `interval_slots` past `grace_slots`,
+/// capped at `max_penalty`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Decay {
+    /// Age up to which a cached score gates as is.
+    pub grace_slots: u64,
+    pub interval_slots: u64,
+    pub penalty: u8,
+    pub max_penalty: u8,
+}
+
+impl Decay {
+    /// Whether the parameters are usable: a nonzero interval and penalties on
+    /// the feed scale.
+    pub fn is_valid(&self) -> bool {
+        self.interval_slots != 0 && self.penalty <= MAX_SCORE && self.max_penalty <= MAX_SCORE
+    }
+
+    /// Penalty of a score whose quote is `age_slots` old. The first interval
+    /// past the grace period already counts in full.
+    pub fn penalty_at(&self, age_slots: u64) -> u8 {
+        let late = age_slots.saturating_sub(self.grace_slots);
+        if late == 0 {
+            return 0;
+        }
+        let intervals = late.div_ceil(self.interval_slots.max(1));
+        let penalty = intervals.saturating_mul(u64::from(self.penalty));
+        penalty.min(u64::from(self.max_penalty)) as u8
+    }
+
+    /// `score` with the penalty of `age_slots`, at most [`MAX_SCORE`].
+    pub fn apply(&self, score: u8, age_slots: u64) -> u8 {
+        score
+            .saturating_add(self.penalty_at(age_slots))
+            .min(MAX_SCORE)
+    }
+}
@@ -0,0 +1,71 @@
+//! Packed denylist segments, for denylists too large for one PDA per address.
+//!
+//! Addresses are spread over [`SEGMENT_COUNT`] segments by the leading
+//! [`PREFIX_BITS`] bits of `H(address)`, so vanity addresses don't crowd one
+//! segment. A segment stores its listed addresses packed, 32 bytes each,
+//! sorted ascending: a lookup derives the one segment an address can be in
+//! and binary searches it, at most `log2(MAX_SEGMENT_ENTRIES)` comparisons
+//! whatever the list size. Full addresses are stored, so there are no false
+//! positives.
+//!
+//! The hash function is a parameter, as for [`merkle`](crate::merkle).
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::merkle::Hashv;
+
+/// Bits of the address hash picking its segment.
+pub const PREFIX_BITS: u32 = 12;
+
+/// Number of segments.
+pub const SEGMENT_COUNT: u16 = 1 << PREFIX_BITS;
+
+/// Maximum number of addresses in one segment (16 KiB of entries).
+pub const MAX_SEGMENT_ENTRIES: usize = 512;
+
+/// Segment `address` is listed in.
+pub fn segment_of(hashv: Hashv, address: &[u8; 32]) -> u16 {
+    let hash = hashv(&[address]);
+    u16::from_be_bytes([hash[0], hash[1]]) >> (16 - PREFIX_BITS)
+}
+
+/// Whether `address` is in `entries`, packed sorted 32-byte addresses as
+/// stored by a segment. Reads the packed bytes without decoding them.
+pub fn contains(entries: &[u8], address: &[u8; 32]) -> bool {
+    let mut low = 0;
+    let mut high = entries.len() / 32;
+    while low < high {
+        let middle = (low + high) / 2;
+        match entries[middle * 32..(middle + 1) * 32].cmp(address.as_slice()) {
+            Ordering::Less => low = middle + 1,
+            Ordering::Greater => high = middle,
+            Ordering::Equal => return true,
+        }
+    }
+    false
+}
+
+/// Adds `address` to the sorted `entries`, of any key type ordered as its
+/// bytes. Returns false when it is already listed.
+pub fn insert<K: Ord>(entries: &mut Vec<K>, address: K) -> bool {
+    match entries.binary_search(&address) {
+        Ok(_) => false,
+        Err(index) => {
+            entries.insert(index, address);
+            true
+        }
+    }
+}
+
+/// Removes `address` from the sorted `entries`. Returns false when it isn't
+/// listed.
+pub fn remove<K: Ord>(entries: &mut Vec<K>, address: &K) -> bool {
+    match entries.binary_search(address) {
+        Ok(index) => {
+            entries.remove(index);
+            true
+        }
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,163 @@
+//! Self-describing encoding of deployment state, returned by `describe`.
+//!
+//! Every entry carries its name and the kind of its value, so explorers and
+//! the CLI render the config without knowing its account layout, and keep
+//! rendering it as fields are added. The encoding is a version byte, then
+//! entries of
+//!
+//! ```text
+//! [name_len: u8][name: UTF-8][kind: u8][len: u8][value: len bytes]
+//! ```
+//!
+//! Unsigned values are little-endian over 1 to 8 bytes, booleans one byte
+//! (0 or 1), public keys 32 bytes and text UTF-8. Decoders hand entries of a
+//! kind they don't know over as raw bytes rather than failing, so kinds can be
+//! added without breaking them.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Version byte the encoding starts with.
+pub const VERSION: u8 = 1;
+
+/// Kind byte of each value.
+pub mod kind {
+    pub const UNSIGNED: u8 = 0;
+    pub const BOOL: u8 = 1;
+    pub const PUBKEY: u8 = 2;
+    pub const TEXT: u8 = 3;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Value<'a> {
+    Unsigned(u64),
+    Bool(bool),
+    Pubkey(&'a [u8; 32]),
+    Text(&'a str),
+    /// Value of a kind this decoder doesn't know.
+    Unknown {
+        kind: u8,
+        bytes: &'a [u8],
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entry<'a> {
+    pub name: &'a str,
+    pub value: Value<'a>,
+}
+
+/// Why a description can't be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DescribeError {
+    /// Empty, or an entry runs past the end.
+    Truncated,
+    /// Written by a newer encoding.
+    UnsupportedVersion(u8),
+    /// A name or text isn't UTF-8, or a value doesn't have its kind's length.
+    InvalidEntry,
+}
+
+/// Builds a description. Names and texts are at most 255 bytes.
+pub struct Writer {
+    data: Vec<u8>,
+}
+
+impl Default for Writer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self {
+            data: vec![VERSION],
+        }
+    }
+
+    /// Writes `value` over the fewest bytes holding it.
+    pub fn unsigned(&mut self, name: &str, value: u64) -> &mut Self {
+        let len = (8 - value.leading_zeros() as usize / 8).max(1);
+        self.entry(name, kind::UNSIGNED, &value.to_le_bytes()[..len])
+    }
+
+    pub fn bool(&mut self, name: &str, value: bool) -> &mut Self {
+        self.entry(name, kind::BOOL, &[value as u8])
+    }
+
+    pub fn pubkey(&mut self, name: &str, value: &[u8; 32]) -> &mut Self {
+        self.entry(name, kind::PUBKEY, value)
+    }
+
+    pub fn text(&mut self, name: &str, value: &str) -> &mut Self {
+        self.entry(name, kind::TEXT, value.as_bytes())
+    }
+
+    pub fn finish(&mut self) -> Vec<u8> {
+        core::mem::take(&mut self.data)
+    }
+
+    fn entry(&mut self, name: &str, kind: u8, value: &[u8]) -> &mut Self {
+        debug_assert!(name.len() <= u8::MAX as usize && value.len() <= u8::MAX as usize);
+        self.data.push(name.len() as u8);
+        self.data.extend_from_slice(name.as_bytes());
+        self.data.push(kind);
+        self.data.push(value.len() as u8);
+        self.data.extend_from_slice(value);
+        self
+    }
+}
+
+/// Entries of a description, in the order they were written.
+pub fn entries(data: &[u8]) -> Result<Vec<Entry<'_>>, DescribeError> {
+    let (&version, mut rest) = data.split_first().ok_or(DescribeError::Truncated)?;
+    if version != VERSION {
+        return Err(DescribeError::UnsupportedVersion(version));
+    }
+
+    let mut entries = Vec::new();
+    while !rest.is_empty() {
+        let name = take_prefixed(&mut rest)?;
+        let (&kind, after_kind) = rest.split_first().ok_or(DescribeError::Truncated)?;
+        rest = after_kind;
+        let bytes = take_prefixed(&mut rest)?;
+        entries.push(Entry {
+            name: core::str::from_utf8(name).map_err(|_| DescribeError::InvalidEntry)?,
+            value: value(kind, bytes)?,
+        });
+    }
+    Ok(entries)
+}
+
+/// Splits a `u8` length-prefixed slice off `data`.
+fn take_prefixed<'a>(data: &mut &'a [u8]) -> Result<&'a [u8], DescribeError> {
+    let (&len, rest) = data.split_first().ok_or(DescribeError::Truncated)?;
+    if rest.len() < len as usize {
+        return Err(DescribeError::Truncated);
+    }
+    let (taken, rest) = rest.split_at(len as usize);
+    *data = rest;
+    Ok(taken)
+}
+
+fn value(kind: u8, bytes: &[u8]) -> Result<Value<'_>, DescribeError> {
+    Ok(match kind {
+        kind::UNSIGNED if (1..=8).contains(&bytes.len()) => {
+            let mut le = [0; 8];
+            le[..bytes.len()].copy_from_slice(bytes);
+            Value::Unsigned(u64::from_le_bytes(le))
+        }
+        kind::BOOL => match bytes {
+            [0] => Value::Bool(false),
+            [1] => Value::Bool(true),
+            _ => return Err(DescribeError::InvalidEntry),
+        },
+        kind::PUBKEY => Value::Pubkey(bytes.try_into().map_err(|_| DescribeError::InvalidEntry)?),
+        kind::TEXT => {
+            Value::Text(core::str::from_utf8(bytes).map_err(|_| DescribeError::InvalidEntry)?)
+        }
+        kind::UNSIGNED => return Err(DescribeError::InvalidEntry),
+        kind => Value::Unknown { kind, bytes },
+    })
+}
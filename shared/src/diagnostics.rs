@@ -0,0 +1,148 @@
+//! Machine-parseable error context.
+//!
+//! Alongside their error codes, the programs log one line per failure in a
+//! fixed format so off-chain tooling (simulation, log indexers) can explain
+//! the failure without guessing:
+//!
+//! ```text
+//! risk-oracle-error: code=FeedMismatch derived=<hex> quote=<hex|none>
+//! risk-oracle-error: code=NoOracleFeeds derived=<hex>
+//! risk-oracle-error: code=StaleQuote slot=<u64> quote_slot=<u64> max_age=<u64>
+//! risk-oracle-error: code=InsufficientOracleSamples samples=<u8> required=<u8>
+//! risk-oracle-error: code=QuoteTooLarge len=<usize> max=<usize>
+//! risk-oracle-error: code=TooManyQuoteFeeds feeds=<usize> max=<usize>
+//! risk-oracle-error: code=UnexpectedQuoteFeed feeds=<usize> expected=<usize> feed=<hex|none>
+//! risk-oracle-error: code=DeadlinePassed slot=<u64> not_after_slot=<u64>
+//! risk-oracle-error: code=VerificationFailed reason=<reason>
+//! risk-oracle-error: code=VerifierError queue_len=<usize> expected=<usize>
+//! risk-oracle-error: code=DeniedProgram index=<usize> program=<hex>
+//! ```
+//!
+//! Fields are `key=value` pairs separated by a single space, values never
+//! contain spaces. New fields are only ever appended.
+
+use alloc::{format, string::String};
+
+/// Prefix of every error context line.
+pub const PREFIX: &str = "risk-oracle-error:";
+
+/// Context for a quote whose feed ids don't match the derived one. `quote` is
+/// the first feed id found in the quote, if any.
+pub fn feed_mismatch(derived: &[u8; 32], quote: Option<&[u8; 32]>) -> String {
+    let quote = match quote {
+        Some(id) => hex(id),
+        None => String::from("none"),
+    };
+    format!("{} code=FeedMismatch derived={} quote={}", PREFIX, hex(derived), quote)
+}
+
+/// Context for a verified quote carrying no feed at all.
+pub fn no_oracle_feeds(derived: &[u8; 32]) -> String {
+    format!("{} code=NoOracleFeeds derived={}", PREFIX, hex(derived))
+}
+
+/// Context for a quote older than the allowed slot distance.
+pub fn stale_quote(slot: u64, quote_slot: u64, max_age: u64) -> String {
+    format!(
+        "{} code=StaleQuote slot={} quote_slot={} max_age={}",
+        PREFIX, slot, quote_slot, max_age
+    )
+}
+
+/// Context for a quote signed by fewer oracles than required.
+pub fn insufficient_samples(samples: u8, required: u8) -> String {
+    format!(
+        "{} code=InsufficientOracleSamples samples={} required={}",
+        PREFIX, samples, required
+    )
+}
+
+/// Context for a quote instruction larger than the profile allows.
+pub fn quote_too_large(len: usize, max: usize) -> String {
+    format!("{} code=QuoteTooLarge len={} max={}", PREFIX, len, max)
+}
+
+/// Context for a quote carrying more feeds than the profile allows.
+pub fn too_many_quote_feeds(feeds: usize, max: usize) -> String {
+    format!("{} code=TooManyQuoteFeeds feeds={} max={}", PREFIX, feeds, max)
+}
+
+/// Context for a quote carrying other feeds than the expected ones (strict
+/// mode). `feed` is the first unexpected feed id, none when the quote only
+/// repeats expected feeds.
+pub fn unexpected_quote_feed(feeds: usize, expected: usize, feed: Option<&[u8; 32]>) -> String {
+    let feed = match feed {
+        Some(id) => hex(id),
+        None => String::from("none"),
+    };
+    format!(
+        "{} code=UnexpectedQuoteFeed feeds={} expected={} feed={}",
+        PREFIX, feeds, expected, feed
+    )
+}
+
+/// Context for a verification executed past the client's deadline.
+pub fn deadline_passed(slot: u64, not_after_slot: u64) -> String {
+    format!(
+        "{} code=DeadlinePassed slot={} not_after_slot={}",
+        PREFIX, slot, not_after_slot
+    )
+}
+
+/// Context for a quote the verifier can't read or rejects. `reason` is a
+/// `verifier_inputs::QuoteInstructionError::reason`, or `rejected` when the
+/// verifier returned an error (logged before this line).
+pub fn verification_failed(reason: &str) -> String {
+    format!("{} code=VerificationFailed reason={}", PREFIX, reason)
+}
+
+/// Context for a queue account the verifier can't read.
+pub fn verifier_error(queue_len: usize, expected: usize) -> String {
+    format!(
+        "{} code=VerifierError queue_len={} expected={}",
+        PREFIX, queue_len, expected
+    )
+}
+
+/// Context for a transaction with an instruction, at `index`, targeting a
+/// denied program.
+pub fn denied_program(index: usize, program: &[u8; 32]) -> String {
+    format!(
+        "{} code=DeniedProgram index={} program={}",
+        PREFIX,
+        index,
+        hex(program)
+    )
+}
+
+/// Lowercase hex encoding, no `0x` prefix.
+pub fn hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    out
+}
+
+/// Parses a log line produced by this module into its `key=value` fields.
+/// Returns `None` for any other line (program logs are prefixed with
+/// `Program log: `, which is skipped).
+pub fn parse(line: &str) -> Option<impl Iterator<Item = (&str, &str)>> {
+    parse_with(PREFIX, line)
+}
+
+/// [`parse`] for lines with another prefix, e.g. [`crate::audit::PREFIX`].
+pub fn parse_with<'a>(
+    prefix: &str,
+    line: &'a str,
+) -> Option<impl Iterator<Item = (&'a str, &'a str)>> {
+    let line = line.strip_prefix("Program log: ").unwrap_or(line);
+    let fields = line.strip_prefix(prefix)?;
+    Some(
+        fields
+            .split(' ')
+            .filter_map(|field| field.split_once('=')),
+    )
+}
@@ -0,0 +1,312 @@
+//! Custom error codes of both programs.
+//!
+//! A failed instruction surfaces off-chain as `custom program error: 0x<code>`
+//! (transaction errors, RPC simulation failures). [`lookup`] and [`describe`]
+//! turn the code back into the error name, so the CLI, the keeper and log
+//! indexers print `StaleQuote` rather than a number; [`explain`](crate::explain)
+//! says what to do about it.
+//!
+//! The tables are stable: codes are the variant order of the program enums,
+//! variants are only ever appended, and each program checks at compile time
+//! that its table covers its enum. The code ranges don't overlap (Anchor
+//! errors start at [`ANCHOR_ERROR_OFFSET`]), so a code alone identifies the
+//! program.
+
+use alloc::{format, string::String};
+
+/// A custom program error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorInfo {
+    pub code: u32,
+    /// Variant name in the program's error enum.
+    pub name: &'static str,
+    pub message: &'static str,
+}
+
+/// First custom error code of Anchor programs (`ERROR_CODE_OFFSET`).
+pub const ANCHOR_ERROR_OFFSET: u32 = 6000;
+
+const fn error(code: u32, name: &'static str, message: &'static str) -> ErrorInfo {
+    ErrorInfo {
+        code,
+        name,
+        message,
+    }
+}
+
+/// `ErrorCode` of the Anchor program, in code order.
+pub const ANCHOR: &[ErrorInfo] = &[
+    error(6000, "NoOracleFeeds", "No oracle feeds available"),
+    error(
+        6001,
+        "FeedMismatch",
+        "Feed hash mismatch - oracle feed does not match expected configuration",
+    ),
+    error(6002, "InvalidFeedJson", "Invalid feed JSON"),
+    error(6003, "VerifierError", "Failed to create quote verifier"),
+    error(6004, "VerificationFailed", "Failed to verify Ed25519 instruction"),
+    error(6005, "StaleQuote", "Stale quote - the quote is too old"),
+    error(
+        6006,
+        "InvalidThreshold",
+        "Risk threshold must be 0–10 in provider units or 0–100 in score units",
+    ),
+    error(6007, "UnknownFeatureFlag", "Unknown feature flag"),
+    error(6008, "InvalidSysvar", "Sysvar account does not match the expected sysvar id"),
+    error(6009, "RiskThresholdExceeded", "Risk score does not pass the configured threshold"),
+    error(6010, "MissingReplayRecord", "Anti-replay is enabled but no replay record was provided"),
+    error(
+        6011,
+        "QuoteReplayed",
+        "Quote is not newer than the last accepted quote for this address",
+    ),
+    error(
+        6012,
+        "MissingFeeAccounts",
+        "Fee collection is enabled but the payer, treasury or system program is missing",
+    ),
+    error(
+        6013,
+        "QueryAccountIsProgram",
+        "Query account is an executable program, there is no wallet to screen",
+    ),
+    error(
+        6014,
+        "QueryAccountIsProgramOwned",
+        "Query account is a program-owned data account (PDA, mint, ...), not a wallet",
+    ),
+    error(6015, "QueryAccountNotWallet", "ATA mode requires the query account to be a wallet"),
+    error(6016, "InvalidTokenProgram", "Token program must be SPL Token or Token-2022"),
+    error(6017, "InvalidNetworks", "Network set is empty or contains unknown networks"),
+    error(6018, "FeedAlreadyRegistered", "Feed id is already registered"),
+    error(6019, "RegistryFull", "Feed registry is full"),
+    error(6020, "FeedNotRegistered", "Feed id is not registered"),
+    error(6021, "MissingRegistry", "Pinned mode requires the registry account"),
+    error(6022, "FeedAddressMismatch", "Registered feed id belongs to another address"),
+    error(
+        6023,
+        "MissingFeedIdRecord",
+        "Hybrid mode requires the feed id record, create it with derive_feed_id",
+    ),
+    error(
+        6024,
+        "StaleFeedIdRecord",
+        "Feed id record was derived for another wallet, call derive_feed_id again",
+    ),
+    error(
+        6025,
+        "StaleQuoteMarker",
+        "Quote marker was not written for the quote of this transaction",
+    ),
+    error(6026, "ReceiptNotExpired", "Verification receipt has not expired yet"),
+    error(6027, "ConsumerMismatch", "Verification was made for another consuming program"),
+    error(6028, "NoHistoryInWindow", "No score recorded in the requested window"),
+    error(
+        6029,
+        "InvalidDenylistBatch",
+        "Denylist batch is empty, too large, or its accounts don't match the addresses",
+    ),
+    error(
+        6030,
+        "InvalidDenylistEntry",
+        "Denylist entry account is not the PDA of the screened address",
+    ),
+    error(6031, "MissingDenylistEntry", "Denylist checks require the denylist entry account"),
+    error(6032, "AddressDenylisted", "Address is denylisted"),
+    error(6033, "InvalidDenylistProof", "Denylist merkle proof is invalid"),
+    error(6034, "TestFeedDisabled", "The test feed is disabled in this deployment profile"),
+    error(6035, "AddressBlacklisted", "The provider flags the address as blacklisted"),
+    error(
+        6036,
+        "InsufficientOracleSamples",
+        "Quote is signed by fewer oracles than the feed or profile requires",
+    ),
+    error(6037, "QuoteTooLarge", "Quote instruction is larger than the profile allows"),
+    error(6038, "TooManyQuoteFeeds", "Quote carries more feeds than the profile allows"),
+    error(6039, "InvalidSla", "Freshness SLA must allow at least one slot"),
+    error(6040, "AccessListTooLarge", "Consumer access list is full"),
+    error(6041, "ConsumerNotAllowed", "Consuming program is not in the access list"),
+    error(6042, "CommitmentMismatch", "Score and salt don't open the stored commitment"),
+    error(6043, "MissingIntegrator", "Integrator account is required by integrator accounting"),
+    error(6044, "IntegratorMismatch", "Integrator account belongs to another consuming program"),
+    error(
+        6045,
+        "IntegratorQuotaExhausted",
+        "Integrator's prepaid balance doesn't cover the verification",
+    ),
+    error(6046, "UnexpectedQuoteFeed", "Quote carries feeds other than the expected ones"),
+    error(6047, "DeadlinePassed", "Slot is past the verification deadline"),
+    error(6048, "NoCacheInQuote", "None of the score caches passed has a feed in the quote"),
+    error(
+        6049,
+        "MissingBypassVoucher",
+        "No Ed25519 voucher instruction right before the redemption",
+    ),
+    error(
+        6050,
+        "InvalidBypassVoucher",
+        "Voucher isn't signed by the compliance officer for this address",
+    ),
+    error(6051, "BypassVoucherExpired", "Slot is outside the voucher's window"),
+    error(
+        6052,
+        "HealthCheckValueMismatch",
+        "Test feed verified with another value than the test score",
+    ),
+    error(6053, "MissingProgramDenylist", "Program denylist account required"),
+    error(
+        6054,
+        "DeniedProgramInTransaction",
+        "An instruction of the transaction targets a denied program",
+    ),
+    error(6055, "ProgramDenylistTooLarge", "Program denylist has too many entries"),
+    error(6056, "ProgramAlreadyFlagged", "Program is already in the program denylist"),
+    error(6057, "ProgramNotFlagged", "Program isn't in the program denylist"),
+    error(6058, "InvalidBatchSize", "Batch must hold 1 to MAX_BATCH_ADDRESSES query accounts"),
+    error(6059, "InvalidAsOfTime", "As-of feed value isn't a Unix time"),
+    error(
+        6060,
+        "InvalidTemplateVariables",
+        "Template variables must be at most MAX_TEMPLATE_VARIABLES valid names",
+    ),
+    error(6061, "UndeclaredFeedVariable", "Feed uses a variable its template doesn't declare"),
+    error(6062, "MalformedFeedEncoding", "Encoded feed doesn't start with its length"),
+    error(6063, "EmptyQuoteAccount", "Quote account holds no quote yet"),
+    error(
+        6064,
+        "InvalidRiskCeiling",
+        "Risk ceiling account is not the PDA of the consuming program",
+    ),
+    error(6065, "MissingRiskCeiling", "Risk ceilings require the risk ceiling account"),
+    error(6066, "RiskCeilingExceeded", "Risk score exceeds the integrator's risk ceiling"),
+    error(
+        6067,
+        "InvalidEmergencyExpiry",
+        "Emergency threshold expiry must be after the current slot, at most MAX_EMERGENCY_SLOTS ahead",
+    ),
+    error(
+        6068,
+        "InvalidThresholdRamp",
+        "Threshold ramp must start before it ends, and end after the current slot",
+    ),
+    error(
+        6069,
+        "InvalidAddressPolicy",
+        "Address policy account is not the PDA of the screened wallet",
+    ),
+    error(6070, "MissingAddressPolicy", "Address policies require the address policy account"),
+    error(6071, "AddressPolicyDenied", "Address policy denies the screened wallet"),
+    error(
+        6072,
+        "InvalidDenylistSegment",
+        "Denylist segment account is not the PDA of the screened wallet's segment",
+    ),
+    error(
+        6073,
+        "MissingDenylistSegment",
+        "Segmented denylist requires the denylist segment account",
+    ),
+    error(6074, "DenylistSegmentFull", "Denylist segment is full"),
+    error(
+        6075,
+        "InvalidScoreDecay",
+        "Score decay needs a nonzero interval and penalties on the feed scale",
+    ),
+    error(6076, "MissingScoreDecay", "Score decay requires the score decay account"),
+    error(6077, "InvalidFeedExpiry", "Feed expiry must be a future slot"),
+    error(6078, "FeedExpired", "Registered feed has expired, verify against its newer template"),
+    error(
+        6079,
+        "NotUpgradeAuthority",
+        "Only the program's upgrade authority can initialize the config",
+    ),
+];
+
+/// `OracleError` of the Pinocchio program, in code order.
+pub const PINOCCHIO: &[ErrorInfo] = &[
+    error(0, "FeedIdMismatch", "Feed id mismatch"),
+    error(1, "InvalidQuote", "Invalid quote"),
+    error(2, "StaleQuote", "Stale quote"),
+    error(3, "InstructionQuoteMissing", "Instruction quote missing"),
+    error(4, "QueryAccountIsProgram", "Query account is an executable program"),
+    error(
+        5,
+        "QueryAccountIsProgramOwned",
+        "Query account is a program-owned data account (PDA, mint, ...)",
+    ),
+    error(6, "QueryAccountNotWallet", "ATA mode requires the query account to be a wallet"),
+    error(7, "InvalidTokenProgram", "Token program is neither SPL Token nor Token-2022"),
+    error(8, "TestFeedDisabled", "Test feed mode in a profile without the test feed"),
+    error(9, "RiskCheckFailed", "Pass/fail mode and the oracle reported a fail"),
+    error(
+        10,
+        "InsufficientOracleSamples",
+        "Quote signed by fewer oracles than the feed or profile requires",
+    ),
+    error(11, "QuoteTooLarge", "Quote instruction larger than the profile allows"),
+    error(12, "TooManyQuoteFeeds", "Quote carrying more feeds than the profile allows"),
+    error(
+        13,
+        "InvalidQueueOwner",
+        "Queue account not owned by the Switchboard On-Demand program",
+    ),
+    error(14, "QueueMismatch", "Queue account is not the profile's queue"),
+    error(15, "DeadlinePassed", "Slot past the verification deadline"),
+    error(16, "InvalidSysvar", "SlotHashes or Instructions account is not the sysvar"),
+    error(17, "UnexpectedAccounts", "Accounts passed after the ones the instruction reads"),
+    error(
+        18,
+        "DeniedProgramInTransaction",
+        "An instruction of the transaction targets a denied program",
+    ),
+];
+
+/// The error of either program with custom error `code`.
+pub fn lookup(code: u32) -> Option<&'static ErrorInfo> {
+    let (table, index) = match code.checked_sub(ANCHOR_ERROR_OFFSET) {
+        Some(index) => (ANCHOR, index),
+        None => (PINOCCHIO, code),
+    };
+    table.get(index as usize)
+}
+
+const CUSTOM_ERROR: &str = "custom program error: 0x";
+
+/// The code of the first `custom program error: 0x<code>` in `text`, e.g. the
+/// `Display` of a transaction or RPC error.
+pub fn custom_code(text: &str) -> Option<u32> {
+    let (_, rest) = text.split_once(CUSTOM_ERROR)?;
+    let end = rest
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .unwrap_or(rest.len());
+    u32::from_str_radix(&rest[..end], 16).ok()
+}
+
+/// `text` with every known `custom program error: 0x<code>` replaced by the
+/// error name and code, e.g. `custom program error: StaleQuote (0x1775)`.
+/// Unknown codes are left as they are.
+pub fn describe(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(CUSTOM_ERROR) {
+        let (before, after) = rest.split_at(start + CUSTOM_ERROR.len());
+        let end = after
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .unwrap_or(after.len());
+        let (digits, tail) = after.split_at(end);
+        let known = u32::from_str_radix(digits, 16).ok().and_then(lookup);
+        match known {
+            Some(error) => {
+                out.push_str(&before[..start]);
+                out.push_str(&format!("custom program error: {} (0x{})", error.name, digits));
+            }
+            None => {
+                out.push_str(before);
+                out.push_str(digits);
+            }
+        }
+        rest = tail;
+    }
+    out.push_str(rest);
+    out
+}
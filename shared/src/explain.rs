@@ -0,0 +1,723 @@
+//! What to do about a custom program error.
+//!
+//! [`errors`](crate::errors) names a code; [`explain`] adds where it comes
+//! from and how to act on it: the program module raising it, the usual
+//! client-side mistakes behind it and the CLI command narrowing it down. The
+//! tables run parallel to [`errors::ANCHOR`](crate::errors::ANCHOR) and
+//! [`errors::PINOCCHIO`](crate::errors::PINOCCHIO), one entry per code in the
+//! same order, and are appended to with them.
+
+use crate::errors::ANCHOR_ERROR_OFFSET;
+
+/// Causes and next step of one error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    /// Module of the program raising the error.
+    pub module: &'static str,
+    /// Likely client-side mistakes, most common first.
+    pub causes: &'static [&'static str],
+    /// CLI command to run next, with `<signature>`, `<query account>` or
+    /// `<consumer>` to fill in.
+    pub next: &'static str,
+}
+
+const fn explanation(
+    module: &'static str,
+    causes: &'static [&'static str],
+    next: &'static str,
+) -> Explanation {
+    Explanation {
+        module,
+        causes,
+        next,
+    }
+}
+
+const INSPECT: &str = "risk-oracle-cli inspect-tx <signature>";
+const DUMP_CONFIG: &str = "risk-oracle-cli dump config";
+
+/// Explanations of `errors::ANCHOR`, in code order.
+pub const ANCHOR: &[Explanation] = &[
+    // 6000 NoOracleFeeds
+    explanation(
+        "verify",
+        &["The quote was fetched for an empty or failing feed on the gateway"],
+        INSPECT,
+    ),
+    // 6001 FeedMismatch
+    explanation(
+        "verify",
+        &[
+            "The client feed differs from the one the program derives: header order, \
+             URL, network or extraction path changed the hash",
+            "The quote was fetched for another address than the query account",
+            "The SDK and the program were built with different RISK_ORACLE_FEED_DOMAIN \
+             or deployment profiles",
+            "The gateway derives feed ids from the bare encoding (FeedRequestV2): \
+             enable FEED_REQUEST_V2_IDS",
+        ],
+        INSPECT,
+    ),
+    // 6002 InvalidFeedJson
+    explanation(
+        "verify",
+        &["No longer raised; the program is older than this table"],
+        INSPECT,
+    ),
+    // 6003 VerifierError
+    explanation(
+        "verify",
+        &["The queue account passed is not a Switchboard queue of this profile"],
+        INSPECT,
+    ),
+    // 6004 VerificationFailed
+    explanation(
+        "verify",
+        &[
+            "The Ed25519 quote instruction is not at the index the program reads",
+            "Signatures from oracles outside the queue, or a quote for another queue",
+        ],
+        INSPECT,
+    ),
+    // 6005 StaleQuote
+    explanation(
+        "verify",
+        &[
+            "Too many slots between fetching the quote and landing the transaction",
+            "Retries re-sent an old quote instead of fetching a new one",
+        ],
+        INSPECT,
+    ),
+    // 6006 InvalidThreshold
+    explanation(
+        "instructions::config",
+        &["Threshold passed on the wrong scale: 0–10 for the provider, 0–100 for scores"],
+        DUMP_CONFIG,
+    ),
+    // 6007 UnknownFeatureFlag
+    explanation(
+        "instructions::config",
+        &["A flag bit this program version doesn't define"],
+        DUMP_CONFIG,
+    ),
+    // 6008 InvalidSysvar
+    explanation(
+        "verify",
+        &["SlotHashes or Instructions sysvar accounts swapped or replaced"],
+        INSPECT,
+    ),
+    // 6009 RiskThresholdExceeded
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &[
+            "Not a client mistake: the address scored above the configured threshold",
+            "Score equal to the threshold with EXCLUSIVE_THRESHOLD enabled",
+        ],
+        "risk-oracle-cli dump cache <query account>",
+    ),
+    // 6010 MissingReplayRecord
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["Anti-replay is enabled and the replay record account was omitted"],
+        DUMP_CONFIG,
+    ),
+    // 6011 QuoteReplayed
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["The same or an older quote was submitted again for the address"],
+        INSPECT,
+    ),
+    // 6012 MissingFeeAccounts
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["Fee collection is enabled and the payer, treasury or system program was omitted"],
+        DUMP_CONFIG,
+    ),
+    // 6013 QueryAccountIsProgram
+    explanation(
+        "verify",
+        &["A program id was passed as the query account instead of a wallet"],
+        INSPECT,
+    ),
+    // 6014 QueryAccountIsProgramOwned
+    explanation(
+        "verify",
+        &[
+            "A PDA, mint or token account was passed instead of a wallet",
+            "A token account verified without ATA mode",
+        ],
+        INSPECT,
+    ),
+    // 6015 QueryAccountNotWallet
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["ATA mode got a token account as the owner instead of the wallet"],
+        INSPECT,
+    ),
+    // 6016 InvalidTokenProgram
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["The token program account is neither SPL Token nor Token-2022"],
+        INSPECT,
+    ),
+    // 6017 InvalidNetworks
+    explanation(
+        "instructions::verify_composite_risk_score",
+        &["Network set of 0, or with bits this program doesn't know"],
+        INSPECT,
+    ),
+    // 6018 FeedAlreadyRegistered
+    explanation(
+        "instructions::registry",
+        &["The feed id was pinned before"],
+        "risk-oracle-cli dump registry",
+    ),
+    // 6019 RegistryFull
+    explanation(
+        "instructions::registry",
+        &["Every registry slot is taken; unregister unused feeds first"],
+        "risk-oracle-cli dump registry",
+    ),
+    // 6020 FeedNotRegistered
+    explanation(
+        "instructions::registry",
+        &["Pinned mode with a feed that was never registered, or was unregistered"],
+        "risk-oracle-cli dump registry",
+    ),
+    // 6021 MissingRegistry
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["Pinned mode called without the registry account"],
+        "risk-oracle-cli dump registry",
+    ),
+    // 6022 FeedAddressMismatch
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["The quote's feed is registered for another wallet than the query account"],
+        "risk-oracle-cli dump registry",
+    ),
+    // 6023 MissingFeedIdRecord
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["Hybrid mode before derive_feed_id created the record"],
+        INSPECT,
+    ),
+    // 6024 StaleFeedIdRecord
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["The feed id record was derived for another wallet; call derive_feed_id again"],
+        INSPECT,
+    ),
+    // 6025 StaleQuoteMarker
+    explanation(
+        "instructions::consume_verified_score",
+        &["The consume instruction is not in the transaction that verified the quote"],
+        INSPECT,
+    ),
+    // 6026 ReceiptNotExpired
+    explanation(
+        "instructions::close_receipt",
+        &["The receipt was closed before its time to live ran out"],
+        INSPECT,
+    ),
+    // 6027 ConsumerMismatch
+    explanation(
+        "instructions::consume_verified_score",
+        &["The verification was made through CPI from another program"],
+        INSPECT,
+    ),
+    // 6028 NoHistoryInWindow
+    explanation(
+        "instructions::check_risk_score_twa",
+        &["No score verified within the window; refresh the score or widen the window"],
+        "risk-oracle-cli dump history <query account>",
+    ),
+    // 6029 InvalidDenylistBatch
+    explanation(
+        "instructions::denylist",
+        &["Empty or oversized batch, or entry accounts not matching the addresses"],
+        "risk-oracle-cli dump denylist",
+    ),
+    // 6030 InvalidDenylistEntry
+    explanation(
+        "instructions::denylist",
+        &["The denylist entry PDA was derived from another address"],
+        "risk-oracle-cli dump denylist",
+    ),
+    // 6031 MissingDenylistEntry
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["Denylist checks are enabled and the entry account was omitted"],
+        DUMP_CONFIG,
+    ),
+    // 6032 AddressDenylisted
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["Not a client mistake: the address is on the denylist"],
+        "risk-oracle-cli dump denylist",
+    ),
+    // 6033 InvalidDenylistProof
+    explanation(
+        "instructions::merkle_denylist",
+        &["The proof was built against another root than the one on-chain"],
+        "risk-oracle-cli dump denylist-root",
+    ),
+    // 6034 TestFeedDisabled
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["verify_test_feed or health_check on a profile without the test feed, e.g. mainnet"],
+        DUMP_CONFIG,
+    ),
+    // 6035 AddressBlacklisted
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["Not a client mistake: the provider flags the address as blacklisted"],
+        INSPECT,
+    ),
+    // 6036 InsufficientOracleSamples
+    explanation(
+        "verify",
+        &["The quote was fetched from fewer oracles than the feed or profile requires"],
+        INSPECT,
+    ),
+    // 6037 QuoteTooLarge
+    explanation(
+        "verify",
+        &["The quote bundles more or longer feeds than the profile allows"],
+        INSPECT,
+    ),
+    // 6038 TooManyQuoteFeeds
+    explanation(
+        "verify",
+        &["The quote bundles more feeds than the profile allows; split it"],
+        INSPECT,
+    ),
+    // 6039 InvalidSla
+    explanation(
+        "instructions::freshness_sla",
+        &["A freshness SLA of 0 slots"],
+        "risk-oracle-cli dump sla <query account>",
+    ),
+    // 6040 AccessListTooLarge
+    explanation(
+        "instructions::confidential_score",
+        &["More consumers than the access list holds"],
+        "risk-oracle-cli dump access-list",
+    ),
+    // 6041 ConsumerNotAllowed
+    explanation(
+        "instructions::confidential_score",
+        &["The revealing program is not in the access list"],
+        "risk-oracle-cli dump access-list",
+    ),
+    // 6042 CommitmentMismatch
+    explanation(
+        "instructions::confidential_score",
+        &["Wrong salt, or a score from another quote than the committed one"],
+        "risk-oracle-cli dump confidential <query account>",
+    ),
+    // 6043 MissingIntegrator
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["Integrator accounting is enabled and the integrator account was omitted"],
+        "risk-oracle-cli dump integrator <consumer>",
+    ),
+    // 6044 IntegratorMismatch
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["The integrator account of another consuming program was passed"],
+        "risk-oracle-cli dump integrator <consumer>",
+    ),
+    // 6045 IntegratorQuotaExhausted
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["The integrator's prepaid balance ran out; top it up"],
+        "risk-oracle-cli dump integrator <consumer>",
+    ),
+    // 6046 UnexpectedQuoteFeed
+    explanation(
+        "verify",
+        &["The quote bundles feeds the instruction doesn't expect, or in another order"],
+        INSPECT,
+    ),
+    // 6047 DeadlinePassed
+    explanation(
+        "verify",
+        &["The deadline slot was set too close to the fetch, or the transaction landed late"],
+        INSPECT,
+    ),
+    // 6048 NoCacheInQuote
+    explanation(
+        "instructions::refresh_score_caches",
+        &["The caches passed are not the ones of the quote's feeds"],
+        INSPECT,
+    ),
+    // 6049 MissingBypassVoucher
+    explanation(
+        "instructions::bypass_voucher",
+        &["The Ed25519 voucher instruction is not right before the redemption"],
+        INSPECT,
+    ),
+    // 6050 InvalidBypassVoucher
+    explanation(
+        "instructions::bypass_voucher",
+        &[
+            "The voucher was signed by a former compliance officer",
+            "The voucher fields or the program id differ from the signed message",
+        ],
+        INSPECT,
+    ),
+    // 6051 BypassVoucherExpired
+    explanation(
+        "instructions::bypass_voucher",
+        &["The voucher was redeemed outside its slot window"],
+        INSPECT,
+    ),
+    // 6052 HealthCheckValueMismatch
+    explanation(
+        "instructions::health_check",
+        &["Not a program failure: the oracles answered the constant test feed wrongly"],
+        INSPECT,
+    ),
+    // 6053 MissingProgramDenylist
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["Program denylist checks are enabled and the program_denylist account was omitted"],
+        DUMP_CONFIG,
+    ),
+    // 6054 DeniedProgramInTransaction
+    explanation(
+        "instructions::program_screening",
+        &[
+            "Not a client mistake: the transaction routes through a denylisted program",
+            "A wallet or relayer added an instruction of a denied program",
+        ],
+        INSPECT,
+    ),
+    // 6055 ProgramDenylistTooLarge
+    explanation(
+        "instructions::program_screening",
+        &[
+            "More programs than MAX_DENIED_PROGRAMS passed to set_program_denylist",
+            "flag_program on a full program denylist",
+        ],
+        INSPECT,
+    ),
+    // 6056 ProgramAlreadyFlagged
+    explanation(
+        "instructions::program_screening",
+        &["flag_program for a program already listed"],
+        "risk-oracle-cli dump program-denylist",
+    ),
+    // 6057 ProgramNotFlagged
+    explanation(
+        "instructions::program_screening",
+        &["unflag_program for a program that isn't listed"],
+        "risk-oracle-cli dump program-denylist",
+    ),
+    // 6058 InvalidBatchSize
+    explanation(
+        "instructions::verify_risk_score_batch",
+        &[
+            "verify_risk_score_batch without query accounts in the remaining accounts",
+            "More query accounts than MAX_BATCH_ADDRESSES",
+        ],
+        INSPECT,
+    ),
+    // 6059 InvalidAsOfTime
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["The as-of feed reported a negative or out-of-range time: check the provider response"],
+        INSPECT,
+    ),
+    // 6060 InvalidTemplateVariables
+    explanation(
+        "instructions::template_variables",
+        &[
+            "More names than MAX_TEMPLATE_VARIABLES passed to set_template_variables",
+            "A name isn't upper-case letters, digits and underscores, or is too long",
+        ],
+        INSPECT,
+    ),
+    // 6061 UndeclaredFeedVariable
+    explanation(
+        "instructions::template_variables",
+        &[
+            "A misspelled ${...} placeholder in the feed (e.g. RANGE_API_KY): the logs name it",
+            "The feed was registered under the wrong template schema",
+            "The template's variables were never set or are incomplete",
+        ],
+        INSPECT,
+    ),
+    // 6062 MalformedFeedEncoding
+    explanation(
+        "instructions::template_variables",
+        &["The feed was encoded without its length prefix: use feed::encode"],
+        INSPECT,
+    ),
+    // 6063 EmptyQuoteAccount
+    explanation(
+        "instructions::quote_account",
+        &[
+            "No write_quote_account landed since the quote account was created",
+            "The keeper posting quotes to it is down or failing",
+        ],
+        INSPECT,
+    ),
+    // 6064 InvalidRiskCeiling
+    explanation(
+        "instructions::risk_ceiling",
+        &[
+            "The risk ceiling PDA was derived from another program than the consumer",
+            "Called through CPI with the ceiling of the top-level program",
+        ],
+        INSPECT,
+    ),
+    // 6065 MissingRiskCeiling
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["Risk ceilings are enabled and the ceiling account was omitted"],
+        DUMP_CONFIG,
+    ),
+    // 6066 RiskCeilingExceeded
+    explanation(
+        "instructions::risk_ceiling",
+        &["Not a client mistake: the integrator's risk officer caps scores below this one"],
+        "risk-oracle-cli dump risk-ceiling <consumer>",
+    ),
+    // 6067 InvalidEmergencyExpiry
+    explanation(
+        "instructions::config",
+        &[
+            "The expiry slot has already passed when the transaction landed",
+            "The expiry is more than MAX_EMERGENCY_SLOTS ahead: set it again to extend",
+        ],
+        INSPECT,
+    ),
+    // 6068 InvalidThresholdRamp
+    explanation(
+        "instructions::config",
+        &[
+            "The start slot is not before the end slot",
+            "The end slot has already passed when the transaction landed",
+        ],
+        INSPECT,
+    ),
+    // 6069 InvalidAddressPolicy
+    explanation(
+        "instructions::address_policy",
+        &["The address policy PDA was derived from another wallet than the screened one"],
+        INSPECT,
+    ),
+    // 6070 MissingAddressPolicy
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["Address policies are enabled and the policy account was omitted"],
+        DUMP_CONFIG,
+    ),
+    // 6071 AddressPolicyDenied
+    explanation(
+        "instructions::address_policy",
+        &["Not a client mistake: the authority set a deny policy for this wallet"],
+        "risk-oracle-cli dump address-policy <address>",
+    ),
+    // 6072 InvalidDenylistSegment
+    explanation(
+        "instructions::denylist_segment",
+        &[
+            "The segment index was computed from another address than the screened wallet",
+            "The segment PDA was derived with a little-endian index",
+        ],
+        INSPECT,
+    ),
+    // 6073 MissingDenylistSegment
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &["The segmented denylist is enabled and the segment account was omitted"],
+        DUMP_CONFIG,
+    ),
+    // 6074 DenylistSegmentFull
+    explanation(
+        "instructions::denylist_segment",
+        &["The segment holds MAX_SEGMENT_ENTRIES addresses; list the rest as denylist entries"],
+        "risk-oracle-cli dump denylist-segment <address>",
+    ),
+    // 6075 InvalidScoreDecay
+    explanation(
+        "instructions::score_decay",
+        &[
+            "The interval is 0 slots",
+            "A penalty was given on the provider's 0–10 scale or above 100",
+        ],
+        DUMP_CONFIG,
+    ),
+    // 6076 MissingScoreDecay
+    explanation(
+        "instructions::check_or_verify_score",
+        &["Score decay is enabled and the score decay account was omitted"],
+        DUMP_CONFIG,
+    ),
+    // 6077 InvalidFeedExpiry
+    explanation(
+        "instructions::registry",
+        &["The expiry slot is not after the current slot; unregister the feed to retire it now"],
+        "risk-oracle-cli dump registry",
+    ),
+    // 6078 FeedExpired
+    explanation(
+        "instructions::verify_risk_score_feed",
+        &[
+            "The authority retired the template of the pinned feed",
+            "Register the feed of the newer template and verify against it",
+        ],
+        "risk-oracle-cli dump registry",
+    ),
+    // 6079 NotUpgradeAuthority
+    explanation(
+        "instructions::config",
+        &[
+            "The signer is not the upgrade authority recorded in the program's ProgramData",
+            "The program was deployed immutable: it has no upgrade authority",
+        ],
+        "risk-oracle-cli dump upgrade-authority",
+    ),
+];
+
+/// Explanations of `errors::PINOCCHIO`, in code order.
+pub const PINOCCHIO: &[Explanation] = &[
+    // 0 FeedIdMismatch
+    explanation(
+        "entrypoint",
+        &[
+            "The client feed differs from the one the program derives: header order, \
+             URL, network or extraction path changed the hash",
+            "The quote was fetched for another address than the query account",
+            "The gateway derives feed ids from the bare encoding (FeedRequestV2): \
+             build with feed-request-v2-ids",
+        ],
+        INSPECT,
+    ),
+    // 1 InvalidQuote
+    explanation(
+        "entrypoint",
+        &[
+            "The Ed25519 quote instruction is not at the index the program reads",
+            "Signatures from oracles outside the queue, or a quote for another queue",
+        ],
+        INSPECT,
+    ),
+    // 2 StaleQuote
+    explanation(
+        "entrypoint",
+        &["Too many slots between fetching the quote and landing the transaction"],
+        INSPECT,
+    ),
+    // 3 InstructionQuoteMissing
+    explanation(
+        "entrypoint",
+        &["No quote instruction at the index the program reads, or not a quote"],
+        INSPECT,
+    ),
+    // 4 QueryAccountIsProgram
+    explanation(
+        "entrypoint",
+        &["A program id was passed as the query account instead of a wallet"],
+        INSPECT,
+    ),
+    // 5 QueryAccountIsProgramOwned
+    explanation(
+        "entrypoint",
+        &["A PDA, mint or token account was passed instead of a wallet"],
+        INSPECT,
+    ),
+    // 6 QueryAccountNotWallet
+    explanation(
+        "entrypoint",
+        &["ATA mode got a token account as the owner instead of the wallet"],
+        INSPECT,
+    ),
+    // 7 InvalidTokenProgram
+    explanation(
+        "entrypoint",
+        &["The token program account is neither SPL Token nor Token-2022"],
+        INSPECT,
+    ),
+    // 8 TestFeedDisabled
+    explanation(
+        "entrypoint",
+        &["Test feed mode on a profile without the test feed, e.g. mainnet"],
+        INSPECT,
+    ),
+    // 9 RiskCheckFailed
+    explanation(
+        "entrypoint",
+        &["Not a client mistake: the oracle reported the address above the feed's threshold"],
+        INSPECT,
+    ),
+    // 10 InsufficientOracleSamples
+    explanation(
+        "entrypoint",
+        &["The quote was fetched from fewer oracles than the feed or profile requires"],
+        INSPECT,
+    ),
+    // 11 QuoteTooLarge
+    explanation(
+        "entrypoint",
+        &["The quote bundles more or longer feeds than the profile allows"],
+        INSPECT,
+    ),
+    // 12 TooManyQuoteFeeds
+    explanation(
+        "entrypoint",
+        &["The quote bundles more feeds than the profile allows; split it"],
+        INSPECT,
+    ),
+    // 13 InvalidQueueOwner
+    explanation(
+        "entrypoint",
+        &["The queue account passed is not a Switchboard On-Demand account"],
+        INSPECT,
+    ),
+    // 14 QueueMismatch
+    explanation(
+        "entrypoint",
+        &["The queue of another profile, e.g. a devnet queue against a mainnet build"],
+        INSPECT,
+    ),
+    // 15 DeadlinePassed
+    explanation(
+        "entrypoint",
+        &["The deadline slot was set too close to the fetch, or the transaction landed late"],
+        INSPECT,
+    ),
+    // 16 InvalidSysvar
+    explanation(
+        "entrypoint",
+        &["SlotHashes or Instructions sysvar accounts swapped or replaced"],
+        INSPECT,
+    ),
+    // 17 UnexpectedAccounts
+    explanation(
+        "entrypoint",
+        &["Accounts of another mode left after the ones the instruction reads"],
+        INSPECT,
+    ),
+    // 18 DeniedProgramInTransaction
+    explanation(
+        "verify",
+        &[
+            "Not a client mistake: the transaction routes through a denied program",
+            "A wallet or relayer added an instruction of a denied program",
+        ],
+        INSPECT,
+    ),
+];
+
+/// Explanation of the error of either program with custom error `code`.
+pub fn explain(code: u32) -> Option<&'static Explanation> {
+    let (table, index) = match code.checked_sub(ANCHOR_ERROR_OFFSET) {
+        Some(index) => (ANCHOR, index),
+        None => (PINOCCHIO, code),
+    };
+    table.get(index as usize)
+}
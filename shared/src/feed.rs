@@ -0,0 +1,522 @@
+//! Feed templates.
+//!
+//! Recreates the Switchboard feeds on-chain as protobuf structures that match
+//! the client feeds byte-for-byte (same tasks, same fields, same order). The
+//! encoded bytes are hashed by the programs to derive the canonical
+//! **feed id**.
+//!
+//! Note: Any change to a template (URL, headers, task ordering, bounds, etc.)
+//! changes the hash → mismatch → instruction fails. The client SDKs build the
+//! same feeds in `getRangeRiskScoreJob` / `getCompositeRiskScoreFeed`.
+//!
+//! Domain separation: building with `RISK_ORACLE_FEED_DOMAIN=<domain>` appends
+//! ` @<domain>` to every feed name. The name is inert for the oracles but part
+//! of the hash, so deployments with different domains get different feed ids
+//! and can't consume each other's quotes. The SDKs read the same variable.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use prost::Message;
+
+use crate::profiles;
+use crate::proto::oracle_job::{
+    cache_task::CacheItem, comparison_task, http_task::Header, multiply_task, task, value_task,
+    BoundTask, CacheTask, ComparisonTask, ConditionalTask, HttpTask, JsonParseTask, MaxTask,
+    MultiplyTask, RegexExtractTask, Task, ValueTask,
+};
+use crate::proto::{OracleFeed, OracleJob};
+
+/// Name of the single-network feed.
+pub const RISK_SCORE_FEED_NAME: &str = "Risk Score";
+
+/// Name of the multi-network composite feed.
+pub const COMPOSITE_RISK_SCORE_FEED_NAME: &str = "Composite Risk Score";
+
+/// Name of the pass/fail feed, followed by its threshold.
+pub const PASS_FAIL_FEED_NAME: &str = "Risk Pass/Fail";
+
+/// Pass/fail feed value of a wallet at or below the threshold.
+pub const PASS: u8 = 1;
+
+/// Pass/fail feed value of a wallet above the threshold, or when the score
+/// can't be fetched.
+pub const FAIL: u8 = 0;
+
+/// Name of the score feed of the [`score_and_flag_feeds`] pair.
+pub const SHARED_SCORE_FEED_NAME: &str = "Risk Score (shared)";
+
+/// Name of the blacklist flag feed of the [`score_and_flag_feeds`] pair.
+pub const BLACKLIST_FLAG_FEED_NAME: &str = "Blacklist Flag";
+
+/// JSON path of Range's blacklist flag (1 listed, 0 not).
+pub const BLACKLIST_FLAG_PATH: &str = "$.isBlacklisted";
+
+/// Name of the as-of feed of the [`score_and_as_of_feeds`] pair.
+pub const AS_OF_FEED_NAME: &str = "Risk Score As-Of";
+
+/// JSON path of the time Range computed the score, in Unix seconds.
+pub const AS_OF_PATH: &str = "$.timestamp";
+
+/// Variable override carrying the Range API key, resolved by the oracle.
+pub const API_KEY_VARIABLE: &str = "RANGE_API_KEY";
+
+/// Oracle variables set by the cache task of [`score_and_flag_feeds`] and
+/// [`score_and_as_of_feeds`].
+pub const SCORE_VARIABLE: &str = "RANGE_RISK_SCORE";
+pub const FLAG_VARIABLE: &str = "RANGE_BLACKLISTED";
+pub const AS_OF_VARIABLE: &str = "RANGE_SCORED_AT";
+
+/// Name of the constant test feed.
+pub const TEST_FEED_NAME: &str = "Risk Score Test";
+
+/// Score always reported by the [`test_feed`].
+pub const TEST_SCORE: u8 = 42;
+
+/// Highest score reported by the provider (Range scores risk 0–10).
+pub const MAX_NATIVE_SCORE: u8 = 10;
+
+/// Factor applied by the job's multiply task, from the provider's 0–10 scale to
+/// the feed's 0–100 score.
+pub const SCORE_SCALE: u8 = 10;
+
+/// Highest feed value (score).
+pub const MAX_SCORE: u8 = MAX_NATIVE_SCORE * SCORE_SCALE;
+
+/// Decimals of the feed values carried by a quote.
+pub const VALUE_DECIMALS: u32 = 18;
+
+/// Score (0–[`MAX_SCORE`]) of a quote's feed value, scaled by
+/// 10^[`VALUE_DECIMALS`], with any fraction rounded up so that the score never
+/// reads less risky than the value (see [`threshold`](crate::threshold)).
+/// Values out of range, negative ones included, read as [`MAX_SCORE`], the
+/// riskiest score. Integer division only: cheaper on chain than going through
+/// `Decimal`.
+pub fn score_from_feed_value(value: i128) -> u8 {
+    let scale = 10i128.pow(VALUE_DECIMALS);
+    let whole = value / scale + i128::from(value % scale > 0);
+    if value >= 0 && whole <= MAX_SCORE as i128 {
+        whole as u8
+    } else {
+        MAX_SCORE
+    }
+}
+
+/// Converts a provider-native score (0–10) to the feed scale (0–100). Returns
+/// `None` when `native` is out of the provider's range.
+pub fn native_to_score(native: u8) -> Option<u8> {
+    (native <= MAX_NATIVE_SCORE).then(|| native * SCORE_SCALE)
+}
+
+/// Deployment-specific domain folded into the feed names, set at build time.
+pub const FEED_DOMAIN: Option<&str> = option_env!("RISK_ORACLE_FEED_DOMAIN");
+
+/// `base` with the [`FEED_DOMAIN`] suffix, if any.
+pub fn feed_name(base: &str) -> String {
+    match FEED_DOMAIN {
+        Some(domain) if !domain.is_empty() => format!("{} @{}", base, domain),
+        _ => base.to_string(),
+    }
+}
+
+/// How the provider's score is extracted from the response body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Extraction {
+    /// JSON path into a JSON body, e.g. `$.riskScore`.
+    JsonPath(&'static str),
+    /// Regular expression over a text body; capture group `group` is the
+    /// score (0 is the whole match).
+    Regex { pattern: &'static str, group: i32 },
+}
+
+impl Extraction {
+    /// The parse task of the job.
+    pub fn task(self) -> Task {
+        let task = match self {
+            Extraction::JsonPath(path) => task::Task::JsonParseTask(JsonParseTask {
+                path: Some(path.to_string()),
+                // aggregation_method: Some(1), // optional; not needed for single value
+                ..Default::default()
+            }),
+            Extraction::Regex { pattern, group } => {
+                task::Task::RegexExtractTask(RegexExtractTask {
+                    pattern: Some(pattern.to_string()),
+                    group_number: Some(group),
+                })
+            }
+        };
+        Task { task: Some(task) }
+    }
+}
+
+/// Response format of a provider: the `accept` header sent and how the score
+/// is extracted. Selected per deployment by [`profiles::Profile::response`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResponseTemplate {
+    pub accept: &'static str,
+    pub extraction: Extraction,
+}
+
+/// Range's JSON response, `{"riskScore": <0–10>, ...}`.
+pub const RANGE_JSON: ResponseTemplate = ResponseTemplate {
+    accept: "application/json",
+    extraction: Extraction::JsonPath("$.riskScore"),
+};
+
+/// Range networks sharing Solana's base58 address representation, in bit order
+/// of [`NetworkSet`].
+const NETWORKS: [&str; 2] = ["solana", "eclipse"];
+
+/// Set of Range networks queried by a composite feed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NetworkSet(u8);
+
+impl NetworkSet {
+    pub const SOLANA: Self = Self(1 << 0);
+    pub const ECLIPSE: Self = Self(1 << 1);
+
+    /// Returns `None` for an empty set or unknown bits.
+    pub fn from_bits(bits: u8) -> Option<Self> {
+        let known = (1u8 << NETWORKS.len()) - 1;
+        (bits != 0 && bits & !known == 0).then_some(Self(bits))
+    }
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Range network identifiers in the set, in bit order.
+    pub fn networks(self) -> impl Iterator<Item = &'static str> {
+        NETWORKS
+            .into_iter()
+            .enumerate()
+            .filter(move |(bit, _)| self.0 & (1 << bit) != 0)
+            .map(|(_, network)| network)
+    }
+}
+
+/// Range risk endpoint for `address` on `network`.
+pub fn risk_score_url(address: &[u8; 32], network: &str) -> String {
+    let addr_b58 = bs58::encode(address).into_string();
+    format!(
+        "{}?address={}&network={}",
+        profiles::ACTIVE.range_risk_url,
+        addr_b58,
+        network
+    )
+}
+
+/// The risk score job for `address` on `network`, parsing the response with
+/// the profile's template: HTTP → parse → multiply → bound.
+pub fn risk_score_job(address: &[u8; 32], network: &str) -> OracleJob {
+    risk_score_job_with(address, network, profiles::ACTIVE.response)
+}
+
+/// [`risk_score_job`] with an explicit response template.
+pub fn risk_score_job_with(
+    address: &[u8; 32],
+    network: &str,
+    response: ResponseTemplate,
+) -> OracleJob {
+    // Build the HTTP task: GET the Range endpoint with headers.
+    // The header order and values must match the client.
+    // Note: `${RANGE_API_KEY}` is a placeholder resolved by the oracle via variable overide.
+    let http_task = Task {
+        task: Some(task::Task::HttpTask(HttpTask {
+            url: Some(risk_score_url(address, network)),
+            headers: [
+                Header {
+                    key: Some("accept".to_string()),
+                    value: Some(response.accept.to_string()),
+                },
+                Header {
+                    key: Some("X-API-KEY".to_string()),
+                    value: Some(format!("${{{}}}", API_KEY_VARIABLE)),
+                },
+            ]
+            .into(),
+            ..Default::default()
+        })),
+    };
+
+    // Extract the score, e.g. the JSON response at the path `$.riskScore`.
+    let parse_task = response.extraction.task();
+
+    // Multiply the risk score (0–10) by 10 to get a 0–100 range.
+    // Note: The MultiplyTask is optional; we could just change the bounds below to 0–10.
+    // but it has to match the client exactly.
+    let multiply_task = Task {
+        task: Some(task::Task::MultiplyTask(MultiplyTask {
+            multiple: Some(multiply_task::Multiple::Scalar(SCORE_SCALE as f64)), // 0–10 => 0–100
+        })),
+    };
+
+    // Bound the result to [0,100]. If out of bounds, set to nearest bound.
+    let bound_task = Task {
+        task: Some(task::Task::BoundTask(BoundTask {
+            lower_bound_value: Some("0".into()),
+            upper_bound_value: Some(MAX_SCORE.to_string()),
+            on_exceeds_lower_bound_value: Some("0".into()),
+            on_exceeds_upper_bound_value: Some(MAX_SCORE.to_string()),
+            ..Default::default()
+        })),
+    };
+
+    // Note: The `weight` field is optional and should be None to match
+    // the client canonicalization. Setting it to Some(1) changes the hash.
+    OracleJob {
+        tasks: vec![http_task, parse_task, multiply_task, bound_task],
+        weight: None, // keep None to match client canonicalization; using Some(1) changes hash
+    }
+}
+
+/// Single-network risk score feed for `address` on the profile's network.
+pub fn risk_score_feed(address: &[u8; 32]) -> OracleFeed {
+    // Note: The `name` field is optional but we set it to match the client.
+    OracleFeed {
+        name: Some(feed_name(RISK_SCORE_FEED_NAME)),
+        jobs: vec![risk_score_job(address, profiles::ACTIVE.range_network)],
+        min_job_responses: Some(1),
+        min_oracle_samples: Some(1),
+        max_job_range_pct: Some(100),
+    }
+}
+
+/// Composite feed querying `address` on every network of `networks`.
+///
+/// The per-network jobs are wrapped in a single `MaxTask` so the oracle
+/// reports the highest (riskiest) score across networks rather than the
+/// median a multi-job feed would produce.
+pub fn composite_risk_score_feed(address: &[u8; 32], networks: NetworkSet) -> OracleFeed {
+    let max_task = Task {
+        task: Some(task::Task::MaxTask(MaxTask {
+            tasks: Vec::new(),
+            jobs: networks
+                .networks()
+                .map(|network| risk_score_job(address, network))
+                .collect(),
+        })),
+    };
+
+    OracleFeed {
+        name: Some(feed_name(COMPOSITE_RISK_SCORE_FEED_NAME)),
+        jobs: vec![OracleJob {
+            tasks: vec![max_task],
+            weight: None,
+        }],
+        min_job_responses: Some(1),
+        min_oracle_samples: Some(1),
+        max_job_range_pct: Some(100),
+    }
+}
+
+/// The score and blacklist flag of `address` from one Range response, as a
+/// pair of feeds sharing one job definition: the HTTP call, then a
+/// `CacheTask` parsing the response twice into [`SCORE_VARIABLE`] (the
+/// score, as in [`risk_score_job`]) and [`FLAG_VARIABLE`]. Each feed only
+/// differs by its name and final `ValueTask` reading one variable, so a quote
+/// of both needs a single HTTP call per oracle.
+///
+/// Returns `[score, flag]`, the order the programs expect in the quote.
+pub fn score_and_flag_feeds(address: &[u8; 32]) -> [OracleFeed; 2] {
+    score_and_companion_feeds(
+        address,
+        BLACKLIST_FLAG_FEED_NAME,
+        FLAG_VARIABLE,
+        BLACKLIST_FLAG_PATH,
+    )
+}
+
+/// The score of `address` and the time Range computed it ([`AS_OF_PATH`],
+/// Unix seconds) from one Range response, built like
+/// [`score_and_flag_feeds`] with [`AS_OF_VARIABLE`] in place of the flag.
+/// The quote slot tells when the oracles attested the score; the as-of feed
+/// tells how old the data they attested was.
+///
+/// Returns `[score, as_of]`, the order the programs expect in the quote.
+pub fn score_and_as_of_feeds(address: &[u8; 32]) -> [OracleFeed; 2] {
+    score_and_companion_feeds(address, AS_OF_FEED_NAME, AS_OF_VARIABLE, AS_OF_PATH)
+}
+
+/// The shared score feed of `address` and a companion feed `name` reading the
+/// response at `path` through `variable`, both from one HTTP call.
+fn score_and_companion_feeds(
+    address: &[u8; 32],
+    name: &str,
+    variable: &str,
+    path: &str,
+) -> [OracleFeed; 2] {
+    let job = risk_score_job(address, profiles::ACTIVE.range_network);
+    let (http_task, score_tasks) = job.tasks.split_first().expect("job starts with HTTP");
+
+    let companion_task = Task {
+        task: Some(task::Task::JsonParseTask(JsonParseTask {
+            path: Some(path.to_string()),
+            ..Default::default()
+        })),
+    };
+    let cache_task = Task {
+        task: Some(task::Task::CacheTask(CacheTask {
+            cache_items: vec![
+                CacheItem {
+                    variable_name: Some(SCORE_VARIABLE.to_string()),
+                    job: Some(OracleJob {
+                        tasks: score_tasks.to_vec(),
+                        weight: None,
+                    }),
+                },
+                CacheItem {
+                    variable_name: Some(variable.to_string()),
+                    job: Some(OracleJob {
+                        tasks: vec![companion_task],
+                        weight: None,
+                    }),
+                },
+            ],
+        })),
+    };
+
+    let feed = |name: &str, variable: &str| OracleFeed {
+        name: Some(feed_name(name)),
+        jobs: vec![OracleJob {
+            tasks: vec![
+                http_task.clone(),
+                cache_task.clone(),
+                Task {
+                    task: Some(task::Task::ValueTask(ValueTask {
+                        value: Some(value_task::Value::Big(format!("${{{}}}", variable))),
+                    })),
+                },
+            ],
+            weight: None,
+        }],
+        min_job_responses: Some(1),
+        min_oracle_samples: Some(1),
+        max_job_range_pct: Some(100),
+    };
+
+    [
+        feed(SHARED_SCORE_FEED_NAME, SCORE_VARIABLE),
+        feed(name, variable),
+    ]
+}
+
+/// Pass/fail feed for `address`: the oracle compares the score (0–100) with
+/// `threshold` itself and reports [`PASS`] when it is at or below it, [`FAIL`]
+/// otherwise. The threshold is part of the feed (name and task), so each
+/// threshold has its own feed id and the program only checks `value == 1`.
+///
+/// Fail-closed: a score the oracle can't fetch or parse reports [`FAIL`]
+/// through the `ConditionalTask` fallback instead of failing the quote.
+pub fn pass_fail_feed(address: &[u8; 32], threshold: u8) -> OracleFeed {
+    // score > threshold ? FAIL : PASS, FAIL when the comparison fails.
+    let comparison_task = Task {
+        task: Some(task::Task::ComparisonTask(ComparisonTask {
+            op: Some(comparison_task::Operation::Gt as i32),
+            lhs: Some(comparison_task::Lhs::Lhs(risk_score_job(
+                address,
+                profiles::ACTIVE.range_network,
+            ))),
+            rhs: Some(comparison_task::Rhs::RhsValue(threshold.to_string())),
+            on_true_value: Some(FAIL.to_string()),
+            on_false_value: Some(PASS.to_string()),
+            on_failure_value: Some(FAIL.to_string()),
+            ..Default::default()
+        })),
+    };
+    let conditional_task = Task {
+        task: Some(task::Task::ConditionalTask(ConditionalTask {
+            attempt: vec![comparison_task],
+            on_failure: vec![Task {
+                task: Some(task::Task::ValueTask(ValueTask {
+                    value: Some(value_task::Value::Value(FAIL as f64)),
+                })),
+            }],
+        })),
+    };
+
+    OracleFeed {
+        name: Some(feed_name(&format!("{} {}", PASS_FAIL_FEED_NAME, threshold))),
+        jobs: vec![OracleJob {
+            tasks: vec![conditional_task],
+            weight: None,
+        }],
+        min_job_responses: Some(1),
+        min_oracle_samples: Some(1),
+        max_job_range_pct: Some(100),
+    }
+}
+
+/// Deterministic feed for integration tests: a single constant `ValueTask`
+/// reporting [`TEST_SCORE`], no HTTP call and no API key. Only accepted by
+/// builds whose profile enables [`profiles::Profile::test_feed`].
+pub fn test_feed() -> OracleFeed {
+    let value_task = Task {
+        task: Some(task::Task::ValueTask(ValueTask {
+            value: Some(value_task::Value::Value(TEST_SCORE as f64)),
+        })),
+    };
+
+    OracleFeed {
+        name: Some(feed_name(TEST_FEED_NAME)),
+        jobs: vec![OracleJob {
+            tasks: vec![value_task],
+            weight: None,
+        }],
+        min_job_responses: Some(1),
+        min_oracle_samples: Some(1),
+        max_job_range_pct: Some(100),
+    }
+}
+
+/// Encodes `feed` as length-delimited protobuf bytes, the input of the feed id hash.
+pub fn encode(feed: &OracleFeed) -> Vec<u8> {
+    OracleFeed::encode_length_delimited_to_vec(feed)
+}
+
+/// Encodes `feed` as bare protobuf bytes, without the length prefix: the
+/// input of the `FeedRequestV2` feed id hash (see [`crate::feed_id`]).
+pub fn encode_message(feed: &OracleFeed) -> Vec<u8> {
+    feed.encode_to_vec()
+}
+
+/// Tags of the feed templates, recorded with registered feed ids so a feed id
+/// can be traced back to the template version that produced it. A template
+/// change that alters the encoding gets a new tag.
+pub mod schema {
+    /// [`risk_score_feed`](super::risk_score_feed), single network.
+    pub const RISK_SCORE_V1: u8 = 1;
+    /// [`composite_risk_score_feed`](super::composite_risk_score_feed).
+    pub const COMPOSITE_RISK_SCORE_V1: u8 = 2;
+    /// [`pass_fail_feed`](super::pass_fail_feed).
+    pub const PASS_FAIL_V1: u8 = 3;
+    /// [`score_and_flag_feeds`](super::score_and_flag_feeds).
+    pub const SCORE_AND_FLAG_V1: u8 = 4;
+    /// [`score_and_as_of_feeds`](super::score_and_as_of_feeds).
+    pub const SCORE_AND_AS_OF_V1: u8 = 5;
+}
+
+/// Ids of the score providers, recorded with stored scores so a score can be
+/// traced back to its source once other providers are added.
+pub mod provider {
+    /// Range's risk API.
+    pub const RANGE: u8 = 1;
+}
+
+/// Version of what a stored score means, recorded with it. Version 1: the
+/// 0–[`MAX_SCORE`] feed scale, the provider's 0–[`MAX_NATIVE_SCORE`] score
+/// times [`SCORE_SCALE`], higher is riskier. A change of scale or of how
+/// scores are derived from feed values gets a new version.
+pub const SCORE_SCHEMA_VERSION: u8 = 1;
+
+/// First 8 bytes of an [`encode`]d feed: the length varint, then the start of
+/// the feed name. Zero-padded for shorter encodings.
+pub fn encoded_prefix(encoded: &[u8]) -> [u8; 8] {
+    let mut prefix = [0u8; 8];
+    let len = encoded.len().min(8);
+    prefix[..len].copy_from_slice(&encoded[..len]);
+    prefix
+}
@@ -0,0 +1,67 @@
+//! The two feed id derivations.
+//!
+//! A feed id is the SHA-256 of an encoded `OracleFeed`, but two encodings are
+//! in use: the length-delimited protobuf bytes ([`feed::encode`]), which the
+//! programs, the clients and the mock gateway hash, and the bare message
+//! bytes ([`feed::encode_message`]), which the gateway's
+//! `FeedRequestV2::feed_id()` hashes. Both ids of a feed differ, so a quote
+//! only verifies when the program derives with the gateway's derivation.
+//!
+//! [`FeedIdDerivation::LengthDelimited`] stays the default. The Anchor program
+//! derives with `FeedRequestV2` under its `FEED_REQUEST_V2_IDS` flag, the
+//! Pinocchio program when built with the `feed-request-v2-ids` feature.
+
+use alloc::vec::Vec;
+
+use crate::feed;
+use crate::merkle::Hashv;
+use crate::proto::OracleFeed;
+
+/// Which encoding of a feed its id hashes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FeedIdDerivation {
+    /// SHA-256 of the length-delimited encoding.
+    #[default]
+    LengthDelimited,
+    /// SHA-256 of the bare message encoding, as `FeedRequestV2::feed_id()`.
+    FeedRequestV2,
+}
+
+impl FeedIdDerivation {
+    pub const ALL: [Self; 2] = [Self::LengthDelimited, Self::FeedRequestV2];
+
+    /// The bytes the feed id of `feed` hashes.
+    pub fn preimage(self, feed: &OracleFeed) -> Vec<u8> {
+        match self {
+            Self::LengthDelimited => feed::encode(feed),
+            Self::FeedRequestV2 => feed::encode_message(feed),
+        }
+    }
+
+    /// The bytes the feed id hashes, out of a feed encoded with
+    /// [`feed::encode`]: all of them, or those after the length prefix.
+    /// `None` when the prefix doesn't give the length of the rest.
+    pub fn preimage_of_encoded(self, encoded: &[u8]) -> Option<&[u8]> {
+        match self {
+            Self::LengthDelimited => Some(encoded),
+            Self::FeedRequestV2 => {
+                let mut message = encoded;
+                let len = prost::encoding::decode_varint(&mut message).ok()?;
+                (usize::try_from(len).ok()? == message.len()).then_some(message)
+            }
+        }
+    }
+
+    /// Feed id of `feed`, hashed with `hashv` (SHA-256).
+    pub fn feed_id(self, hashv: Hashv, feed: &OracleFeed) -> [u8; 32] {
+        hashv(&[&self.preimage(feed)])
+    }
+
+    /// Lowercase name, as used in logs and configuration.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::LengthDelimited => "length-delimited",
+            Self::FeedRequestV2 => "feed-request-v2",
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Lookup of the derived feed ids among the feeds of a verified quote.
+//!
+//! Both programs run the same scan: compare each feed id of the quote with
+//! the ids they derived, through [`constant_time::eq_32`], and stop once every
+//! expected id is found. The scan only reads feed ids; the caller reads the
+//! value (and formats it for logs) of the matched feeds alone.
+//!
+//! [`constant_time::eq_32`]: crate::constant_time::eq_32
+
+use crate::constant_time;
+
+/// Outcome of [`match_feeds`] for `N` expected feed ids.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchResult<const N: usize> {
+    /// Every expected id is in the quote: `positions[i]` is the index of the
+    /// first feed with id `expected[i]`.
+    Matched { positions: [usize; N] },
+    /// The quote carries no feed.
+    NoFeeds,
+    /// `missing` of the expected ids are not in the quote.
+    Mismatch { missing: usize },
+}
+
+/// Finds each of `expected` among `feed_ids`, the feed ids of a quote in
+/// quote order. Stops reading `feed_ids` as soon as every id is found.
+pub fn match_feeds<'a, const N: usize>(
+    feed_ids: impl IntoIterator<Item = &'a [u8; 32]>,
+    expected: &[[u8; 32]; N],
+) -> MatchResult<N> {
+    let mut positions = [None; N];
+    let mut found = 0;
+    let mut scanned = 0;
+    for (position, feed_id) in feed_ids.into_iter().enumerate() {
+        scanned += 1;
+        for (slot, id) in positions.iter_mut().zip(expected) {
+            if slot.is_none() && constant_time::eq_32(feed_id, id) {
+                *slot = Some(position);
+                found += 1;
+            }
+        }
+        if found == N {
+            break;
+        }
+    }
+
+    if scanned == 0 {
+        MatchResult::NoFeeds
+    } else if found == N {
+        MatchResult::Matched {
+            positions: positions.map(Option::unwrap_or_default),
+        }
+    } else {
+        MatchResult::Mismatch { missing: N - found }
+    }
+}
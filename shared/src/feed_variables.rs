@@ -0,0 +1,69 @@
+//! Variable override names of the feed templates.
+//!
+//! Templates carry `${NAME}` placeholders the oracle resolves at quote time:
+//! `${RANGE_API_KEY}` from the gateway's variable overrides, the cache task
+//! variables of the feed pairs from the job itself. The placeholder is part of
+//! the encoded feed, so a typo in its name doesn't fail anywhere but changes
+//! the feed id, and shows up as a `FeedMismatch` once a quote is verified
+//! against the pinned id. The Anchor program keeps the names each template may
+//! use and checks the encoded feed against them when it is registered with
+//! `register_feed_template`.
+
+use crate::feed;
+
+/// Longest variable name accepted.
+pub const MAX_VARIABLE_LEN: usize = 32;
+
+/// Whether `name` can name a variable: 1 to [`MAX_VARIABLE_LEN`] upper-case
+/// ASCII letters, digits and underscores, not starting with a digit.
+pub fn is_variable_name(name: &[u8]) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_VARIABLE_LEN
+        && !name[0].is_ascii_digit()
+        && name
+            .iter()
+            .all(|&byte| byte.is_ascii_uppercase() || byte.is_ascii_digit() || byte == b'_')
+}
+
+/// Variables the templates of `schema` (a `feed::schema` tag) use, in the
+/// order they appear. Empty for unknown tags.
+pub fn template_variables(schema: u8) -> &'static [&'static str] {
+    match schema {
+        feed::schema::RISK_SCORE_V1
+        | feed::schema::COMPOSITE_RISK_SCORE_V1
+        | feed::schema::PASS_FAIL_V1 => &[feed::API_KEY_VARIABLE],
+        feed::schema::SCORE_AND_FLAG_V1 => &[
+            feed::API_KEY_VARIABLE,
+            feed::SCORE_VARIABLE,
+            feed::FLAG_VARIABLE,
+        ],
+        feed::schema::SCORE_AND_AS_OF_V1 => &[
+            feed::API_KEY_VARIABLE,
+            feed::SCORE_VARIABLE,
+            feed::AS_OF_VARIABLE,
+        ],
+        _ => &[],
+    }
+}
+
+/// Names of the `${NAME}` placeholders of an encoded feed, in order and
+/// repeated as they appear. An unterminated placeholder yields the rest of
+/// the bytes, which no declared name matches.
+pub fn placeholders(encoded: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut rest = encoded;
+    core::iter::from_fn(move || {
+        let start = rest.windows(2).position(|window| window == b"${")? + 2;
+        let after = &rest[start..];
+        let len = after
+            .iter()
+            .position(|&byte| byte == b'}')
+            .unwrap_or(after.len());
+        rest = &after[len..];
+        Some(&after[..len])
+    })
+}
+
+/// First placeholder of `encoded` that `is_declared` rejects.
+pub fn first_undeclared(encoded: &[u8], is_declared: impl Fn(&[u8]) -> bool) -> Option<&[u8]> {
+    placeholders(encoded).find(|name| !is_declared(name))
+}
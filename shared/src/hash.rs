@@ -0,0 +1,85 @@
+//! SHA-256, through the cheapest implementation of the target.
+//!
+//! Feed ids, merkle nodes and commitments are SHA-256 everywhere, but what
+//! computes it matters on-chain: the `sol_sha256` syscall costs 85 CU plus
+//! 1 CU per 2 bytes hashed, while the `sha2` crate compiled to SBF runs its
+//! compression function as program instructions, thousands of CU for a feed
+//! encoding. [`hashv`] picks the [`Sha256Backend`] of the target: the
+//! syscall on-chain, `sha2` off-chain (tests, client, keeper), so both sides
+//! derive the same bytes from the same helper.
+//!
+//! The `sha2-hash` feature keeps `sha2` on-chain too, only to measure what
+//! the syscall saves (`risk-oracle-bench --sha2-baseline`).
+
+/// A SHA-256 implementation.
+pub trait Sha256Backend {
+    /// Hash of the concatenation of `data`.
+    fn hashv(data: &[&[u8]]) -> [u8; 32];
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        Self::hashv(&[data])
+    }
+}
+
+/// The runtime's `sol_sha256` syscall.
+#[cfg(target_os = "solana")]
+pub struct Syscall;
+
+#[cfg(target_os = "solana")]
+extern "C" {
+    fn sol_sha256(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64;
+}
+
+#[cfg(target_os = "solana")]
+impl Sha256Backend for Syscall {
+    fn hashv(data: &[&[u8]]) -> [u8; 32] {
+        let mut hash = [0u8; 32];
+        // SAFETY: on SBF a `&[u8]` is a (pointer, length) pair of two 64-bit
+        // words, so `data` is the array of `data.len()` pairs the runtime
+        // reads, each pointing at memory borrowed for the call. `hash` is 32
+        // writable bytes, the digest the syscall writes.
+        unsafe {
+            sol_sha256(
+                data.as_ptr() as *const u8,
+                data.len() as u64,
+                hash.as_mut_ptr(),
+            );
+        }
+        hash
+    }
+}
+
+/// The `sha2` crate.
+#[cfg(any(not(target_os = "solana"), feature = "sha2-hash"))]
+pub struct Sha2;
+
+#[cfg(any(not(target_os = "solana"), feature = "sha2-hash"))]
+impl Sha256Backend for Sha2 {
+    fn hashv(data: &[&[u8]]) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        for part in data {
+            hasher.update(part);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Backend of the target.
+#[cfg(all(target_os = "solana", not(feature = "sha2-hash")))]
+pub type Native = Syscall;
+/// Backend of the target.
+#[cfg(any(not(target_os = "solana"), feature = "sha2-hash"))]
+pub type Native = Sha2;
+
+/// [`Sha256Backend::hashv`] of the target's backend, a
+/// [`Hashv`](crate::merkle::Hashv).
+pub fn hashv(data: &[&[u8]]) -> [u8; 32] {
+    Native::hashv(data)
+}
+
+/// [`Sha256Backend::hash`] of the target's backend.
+pub fn hash(data: &[u8]) -> [u8; 32] {
+    Native::hash(data)
+}
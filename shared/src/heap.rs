@@ -0,0 +1,31 @@
+//! Heap usage of a verification, logged by builds of either program with its
+//! `heap-log` feature and bounded by `shared/tests/heap_budget.rs`.
+//!
+//! One line at the end of a successful verification, in the same `key=value`
+//! format as [`diagnostics`]:
+//!
+//! ```text
+//! risk-oracle-heap: used=<usize> budget=<usize>
+//! ```
+//!
+//! The programs' bump allocators never reuse freed memory, so `used` is
+//! every byte allocated by the instruction, which is also its peak.
+//!
+//! [`diagnostics`]: crate::diagnostics
+
+use alloc::{format, string::String};
+
+/// Prefix of the heap usage line.
+pub const PREFIX: &str = "risk-oracle-heap:";
+
+/// Heap of an SBF program without a `RequestHeapFrame` instruction.
+pub const BUDGET: usize = 32 * 1024;
+
+/// Share of [`BUDGET`] building and encoding a feed template may allocate;
+/// the rest is left to the quote verifier.
+pub const FEED_BUDGET: usize = BUDGET / 2;
+
+/// Line of `used` heap bytes.
+pub fn usage(used: usize) -> String {
+    format!("{} used={} budget={}", PREFIX, used, BUDGET)
+}
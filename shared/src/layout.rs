@@ -0,0 +1,276 @@
+//! Byte layouts of the Anchor program's accounts, for indexers decoding them
+//! without the IDL or the program crate.
+//!
+//! Every account starts with Anchor's 8-byte discriminator,
+//! `sha256("account:<Name>")[..8]`, followed by the Borsh encoding of its
+//! `state.rs` struct: fixed-width little-endian integers, no padding, enums as
+//! a 1-byte variant index. Offsets are computed from the field types in
+//! order, so a layout here is checked field by field against the program's
+//! own encoding (`tests/layout.rs` of the Anchor program), and the README
+//! table is [`markdown`] (`cargo xtask layout`).
+
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Length of the discriminator every account starts with.
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// Type of an account field, as Borsh encodes it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    Pubkey,
+    U8,
+    /// Little-endian.
+    U64,
+    Bytes32,
+    /// A fieldless enum: the index of the variant, in this order.
+    Enum(&'static [&'static str]),
+}
+
+impl FieldType {
+    /// Encoded length, in bytes.
+    pub const fn size(&self) -> usize {
+        match self {
+            FieldType::Pubkey | FieldType::Bytes32 => 32,
+            FieldType::U8 | FieldType::Enum(_) => 1,
+            FieldType::U64 => 8,
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            FieldType::Pubkey => "Pubkey",
+            FieldType::U8 => "u8",
+            FieldType::U64 => "u64",
+            FieldType::Bytes32 => "[u8; 32]",
+            FieldType::Enum(_) => "enum (u8)",
+        }
+    }
+}
+
+/// A field of an account, nested struct fields flattened as `outer.inner`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub name: &'static str,
+    /// From the start of the account data, discriminator included.
+    pub offset: usize,
+    pub ty: FieldType,
+}
+
+impl Field {
+    /// Bytes of the field in `data`, `None` when `data` is too short.
+    pub fn read<'a>(&self, data: &'a [u8]) -> Option<&'a [u8]> {
+        data.get(self.offset..self.offset + self.ty.size())
+    }
+}
+
+/// Discriminator and fields of an account type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountLayout {
+    /// Name of the `state.rs` struct.
+    pub name: &'static str,
+    pub discriminator: [u8; 8],
+    pub fields: &'static [Field],
+}
+
+impl AccountLayout {
+    /// Length of the account data, discriminator included.
+    pub const fn size(&self) -> usize {
+        match self.fields.last() {
+            Some(last) => last.offset + last.ty.size(),
+            None => DISCRIMINATOR_LEN,
+        }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Whether `data` starts with this account's discriminator.
+    pub fn matches(&self, data: &[u8]) -> bool {
+        data.starts_with(&self.discriminator)
+    }
+}
+
+/// Lays `spec` out in order after the discriminator.
+const fn fields<const N: usize>(spec: [(&'static str, FieldType); N]) -> [Field; N] {
+    let mut fields = [Field {
+        name: "",
+        offset: 0,
+        ty: FieldType::U8,
+    }; N];
+    let mut offset = DISCRIMINATOR_LEN;
+    let mut i = 0;
+    while i < N {
+        fields[i] = Field {
+            name: spec[i].0,
+            offset,
+            ty: spec[i].1,
+        };
+        offset += spec[i].1.size();
+        i += 1;
+    }
+    fields
+}
+
+/// Variants of `Momentum`.
+pub const MOMENTUM: FieldType =
+    FieldType::Enum(&["Unknown", "Stable", "Improving", "Deteriorating"]);
+
+const CONFIG_FIELDS: [Field; 11] = fields([
+    ("authority", FieldType::Pubkey),
+    ("treasury", FieldType::Pubkey),
+    ("flags", FieldType::U64),
+    ("risk_threshold", FieldType::U8),
+    ("fee_lamports", FieldType::U64),
+    ("bump", FieldType::U8),
+    ("emergency_threshold", FieldType::U8),
+    ("emergency_expiry_slot", FieldType::U64),
+    ("ramp_from", FieldType::U8),
+    ("ramp_start_slot", FieldType::U64),
+    ("ramp_end_slot", FieldType::U64),
+]);
+
+/// `Config`, the `["config"]` PDA.
+pub const CONFIG: AccountLayout = AccountLayout {
+    name: "Config",
+    discriminator: [155, 12, 170, 224, 30, 250, 204, 130],
+    fields: &CONFIG_FIELDS,
+};
+
+const REPLAY_RECORD_FIELDS: [Field; 1] = fields([("last_quote_slot", FieldType::U64)]);
+
+/// `ReplayRecord`, the anti-replay record of a query account.
+pub const REPLAY_RECORD: AccountLayout = AccountLayout {
+    name: "ReplayRecord",
+    discriminator: [208, 145, 144, 224, 117, 234, 216, 165],
+    fields: &REPLAY_RECORD_FIELDS,
+};
+
+const SCORE_CACHE_FIELDS: [Field; 14] = fields([
+    ("address", FieldType::Pubkey),
+    ("score", FieldType::U8),
+    ("quote_slot", FieldType::U64),
+    ("updated_slot", FieldType::U64),
+    ("feed_id", FieldType::Bytes32),
+    ("networks", FieldType::U8),
+    ("bump", FieldType::U8),
+    ("momentum", MOMENTUM),
+    ("provenance.provider", FieldType::U8),
+    ("provenance.template", FieldType::U8),
+    ("provenance.schema_version", FieldType::U8),
+    ("provenance.quote_slot", FieldType::U64),
+    ("provenance.oracle_samples", FieldType::U8),
+    ("updated_by", FieldType::Pubkey),
+]);
+
+/// `ScoreCache`, the last verified score of a query account.
+pub const SCORE_CACHE: AccountLayout = AccountLayout {
+    name: "ScoreCache",
+    discriminator: [113, 162, 7, 152, 40, 45, 28, 47],
+    fields: &SCORE_CACHE_FIELDS,
+};
+
+const FEED_ID_RECORD_FIELDS: [Field; 4] = fields([
+    ("feed_id", FieldType::Bytes32),
+    ("address", FieldType::Pubkey),
+    ("derived_slot", FieldType::U64),
+    ("bump", FieldType::U8),
+]);
+
+/// `FeedIdRecord`, the feed id derived on-chain for a query account.
+pub const FEED_ID_RECORD: AccountLayout = AccountLayout {
+    name: "FeedIdRecord",
+    discriminator: [201, 214, 35, 225, 80, 135, 14, 228],
+    fields: &FEED_ID_RECORD_FIELDS,
+};
+
+const QUOTE_MARKER_FIELDS: [Field; 7] = fields([
+    ("address", FieldType::Pubkey),
+    ("score", FieldType::U8),
+    ("feed_id", FieldType::Bytes32),
+    ("slot", FieldType::U64),
+    ("quote_hash", FieldType::Bytes32),
+    ("consumer", FieldType::Pubkey),
+    ("bump", FieldType::U8),
+]);
+
+/// `QuoteMarker`, the last verification of a query account within a
+/// transaction.
+pub const QUOTE_MARKER: AccountLayout = AccountLayout {
+    name: "QuoteMarker",
+    discriminator: [169, 196, 101, 39, 193, 133, 226, 55],
+    fields: &QUOTE_MARKER_FIELDS,
+};
+
+const VERIFICATION_RECEIPT_FIELDS: [Field; 9] = fields([
+    ("address", FieldType::Pubkey),
+    ("score", FieldType::U8),
+    ("feed_id", FieldType::Bytes32),
+    ("quote_slot", FieldType::U64),
+    ("issued_slot", FieldType::U64),
+    ("expires_slot", FieldType::U64),
+    ("rent_payer", FieldType::Pubkey),
+    ("consumer", FieldType::Pubkey),
+    ("bump", FieldType::U8),
+]);
+
+/// `VerificationReceipt`, the proof of a verification for CPI consumers.
+pub const VERIFICATION_RECEIPT: AccountLayout = AccountLayout {
+    name: "VerificationReceipt",
+    discriminator: [32, 125, 3, 222, 137, 31, 118, 83],
+    fields: &VERIFICATION_RECEIPT_FIELDS,
+};
+
+/// Every described account, in README order.
+pub const ALL: &[AccountLayout] = &[
+    CONFIG,
+    REPLAY_RECORD,
+    SCORE_CACHE,
+    FEED_ID_RECORD,
+    QUOTE_MARKER,
+    VERIFICATION_RECEIPT,
+];
+
+/// Layout of the account whose discriminator `data` starts with.
+pub fn identify(data: &[u8]) -> Option<&'static AccountLayout> {
+    ALL.iter().find(|layout| layout.matches(data))
+}
+
+/// The README tables of [`ALL`]: one per account, offset, field and type.
+pub fn markdown() -> String {
+    let mut out = String::new();
+    for (i, layout) in ALL.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let _ = writeln!(
+            out,
+            "`{}`, {} bytes, discriminator `{:?}`:\n",
+            layout.name,
+            layout.size(),
+            layout.discriminator
+        );
+        out.push_str("| Offset | Field | Type |\n|---|---|---|\n");
+        for field in layout.fields {
+            let _ = write!(
+                out,
+                "| {} | `{}` | {}",
+                field.offset,
+                field.name,
+                field.ty.name()
+            );
+            if let FieldType::Enum(variants) = field.ty {
+                out.push_str(": ");
+                for (index, variant) in variants.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(", ");
+                    }
+                    let _ = write!(out, "{index} `{variant}`");
+                }
+            }
+            out.push_str(" |\n");
+        }
+    }
+    out
+}
@@ -0,0 +1,36 @@
+#![no_std]
+
+extern crate alloc;
+
+pub mod accounts;
+pub mod audit;
+pub mod compute;
+pub mod constant_time;
+pub mod decay;
+pub mod denylist_segment;
+pub mod describe;
+pub mod diagnostics;
+pub mod errors;
+pub mod explain;
+pub mod feed;
+pub mod feed_id;
+pub mod feed_match;
+pub mod feed_variables;
+pub mod hash;
+pub mod heap;
+pub mod layout;
+pub mod merkle;
+#[cfg(feature = "solana-program")]
+pub mod native;
+pub mod profiles;
+pub mod program_screen;
+pub mod proto;
+pub mod query_account;
+pub mod quote_limits;
+pub mod seeds;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod severity;
+pub mod threshold;
+pub mod verifier_inputs;
+pub mod voucher;
@@ -0,0 +1,181 @@
+//! Sorted merkle tree over 32-byte keys, used for the merkle denylist.
+//!
+//! Leaves are the keys sorted ascending and deduplicated, hashed as
+//! `H(0x00 || key)`; inner nodes are `H(0x01 || left || right)`. The leaf
+//! level is padded to a power of two with [`EMPTY`] nodes, so every proof has
+//! exactly [`depth`] siblings and the leaf index alone fixes the hashing order.
+//!
+//! Because leaves are sorted, a key is proven absent by two adjacent leaves
+//! around it (or a single boundary leaf at index 0 or `leaf_count - 1`), see
+//! [`verify_non_member`].
+//!
+//! The hash function is a parameter: the program uses the `sol_sha256`
+//! syscall, off-chain code any SHA-256 implementation.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::constant_time;
+
+/// SHA-256 over the concatenation of the given slices.
+pub type Hashv = fn(&[&[u8]]) -> [u8; 32];
+
+/// Padding node of the leaf level.
+pub const EMPTY: [u8; 32] = [0; 32];
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf_hash(hashv: Hashv, key: &[u8; 32]) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, key])
+}
+
+pub fn node_hash(hashv: Hashv, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[NODE_PREFIX, left, right])
+}
+
+/// Number of siblings in a proof of a tree of `leaf_count` leaves.
+pub fn depth(leaf_count: u32) -> usize {
+    leaf_count.max(1).next_power_of_two().trailing_zeros() as usize
+}
+
+/// Root reached from `key` at `index` with `siblings`, or `None` when the
+/// proof can't belong to a tree of `leaf_count` leaves.
+pub fn root_from_proof(
+    hashv: Hashv,
+    key: &[u8; 32],
+    index: u32,
+    leaf_count: u32,
+    siblings: &[[u8; 32]],
+) -> Option<[u8; 32]> {
+    if index >= leaf_count || siblings.len() != depth(leaf_count) {
+        return None;
+    }
+    let mut node = leaf_hash(hashv, key);
+    let mut position = index;
+    for sibling in siblings {
+        node = match position & 1 {
+            0 => node_hash(hashv, &node, sibling),
+            _ => node_hash(hashv, sibling, &node),
+        };
+        position >>= 1;
+    }
+    Some(node)
+}
+
+/// A key of the tree with its position and siblings, as a proof carries it.
+#[derive(Clone, Copy, Debug)]
+pub struct Leaf<'a> {
+    pub key: [u8; 32],
+    pub index: u32,
+    pub siblings: &'a [[u8; 32]],
+}
+
+impl Leaf<'_> {
+    /// Whether the proof leads to `root` of a tree of `leaf_count` leaves.
+    pub fn verify(&self, hashv: Hashv, root: &[u8; 32], leaf_count: u32) -> bool {
+        root_from_proof(hashv, &self.key, self.index, leaf_count, self.siblings)
+            .is_some_and(|computed| constant_time::eq_32(&computed, root))
+    }
+}
+
+/// Whether `lower` and `upper` prove `key` absent from the tree of `root`:
+/// adjacent leaves around it, `lower` omitted when `upper` is the first leaf,
+/// `upper` when `lower` is the last, both for an empty tree.
+pub fn verify_non_member(
+    hashv: Hashv,
+    root: &[u8; 32],
+    leaf_count: u32,
+    key: &[u8; 32],
+    lower: Option<Leaf>,
+    upper: Option<Leaf>,
+) -> bool {
+    let last = leaf_count.checked_sub(1);
+    let adjacent = match (&lower, &upper) {
+        (None, None) => last.is_none(),
+        (Some(lower), None) => last == Some(lower.index),
+        (None, Some(upper)) => upper.index == 0,
+        (Some(lower), Some(upper)) => lower.index.checked_add(1) == Some(upper.index),
+    };
+    if !adjacent {
+        return false;
+    }
+    if let Some(lower) = lower {
+        if !(&lower.key < key && lower.verify(hashv, root, leaf_count)) {
+            return false;
+        }
+    }
+    if let Some(upper) = upper {
+        if !(key < &upper.key && upper.verify(hashv, root, leaf_count)) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Tree built off-chain, kept level by level to extract proofs.
+pub struct Tree {
+    keys: Vec<[u8; 32]>,
+    /// Leaf level first, root level last.
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl Tree {
+    /// Sorts and deduplicates `keys` before building the tree.
+    pub fn new(hashv: Hashv, mut keys: Vec<[u8; 32]>) -> Self {
+        keys.sort_unstable();
+        keys.dedup();
+
+        let width = keys.len().max(1).next_power_of_two();
+        let mut level: Vec<[u8; 32]> = keys.iter().map(|key| leaf_hash(hashv, key)).collect();
+        level.resize(width, EMPTY);
+
+        let mut levels = vec![level];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| node_hash(hashv, &pair[0], &pair[1]))
+                .collect();
+            levels.push(next);
+        }
+        Self { keys, levels }
+    }
+
+    /// Root to commit on-chain; [`EMPTY`] for an empty tree.
+    pub fn root(&self) -> [u8; 32] {
+        if self.keys.is_empty() {
+            return EMPTY;
+        }
+        self.levels[self.levels.len() - 1][0]
+    }
+
+    pub fn leaf_count(&self) -> u32 {
+        self.keys.len() as u32
+    }
+
+    /// Sorted keys.
+    pub fn keys(&self) -> &[[u8; 32]] {
+        &self.keys
+    }
+
+    /// Siblings of the leaf at `index`, leaf level first.
+    pub fn proof(&self, index: u32) -> Vec<[u8; 32]> {
+        let mut position = index as usize;
+        self.levels[..self.levels.len() - 1]
+            .iter()
+            .map(|level| {
+                let sibling = level[position ^ 1];
+                position >>= 1;
+                sibling
+            })
+            .collect()
+    }
+
+    /// `Ok(index)` of `key`, or `Err(index)` of the first key above it.
+    pub fn search(&self, key: &[u8; 32]) -> Result<u32, u32> {
+        self.keys
+            .binary_search(key)
+            .map(|index| index as u32)
+            .map_err(|index| index as u32)
+    }
+}
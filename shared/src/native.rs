@@ -0,0 +1,154 @@
+//! The programs' account checks for native `solana-program` programs.
+//!
+//! The rest of the crate works on raw bytes so both programs can use it.
+//! Programs written directly against `solana-program` (neither Anchor nor
+//! Pinocchio) can embed the same checks on their `AccountInfo`s with the
+//! `solana-program` feature, before handing the accounts to
+//! `QuoteVerifier`:
+//!
+//! ```ignore
+//! native::check_queue(queue)?;
+//! native::check_sysvars(slothashes, instructions)?;
+//! native::check_quote(queue, slothashes, instructions, 0)?;
+//! let wallet = native::screened_wallet(query_account)?;
+//! ```
+//!
+//! Failures are [`AccountCheckError`]s, to log or map to the program's own
+//! error enum; `?` converts them to the closest builtin `ProgramError`.
+
+use solana_program::account_info::AccountInfo;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar;
+
+use crate::profiles;
+use crate::query_account::{self, QueryAccountKind};
+use crate::quote_limits;
+use crate::verifier_inputs::{self, QuoteInstructionError};
+
+/// Why an account can't be passed on to the verifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountCheckError {
+    /// The queue isn't the active profile's queue.
+    QueueAddress,
+    /// The queue isn't owned by the profile's Switchboard program.
+    QueueOwner,
+    /// The queue isn't [`verifier_inputs::QUEUE_ACCOUNT_LEN`] bytes.
+    QueueLength(usize),
+    /// A sysvar account isn't the sysvar it stands for.
+    InvalidSysvar,
+    /// The quote instruction is larger than the profile allows.
+    QuoteTooLarge(usize),
+    /// The quote instruction would make the verifier panic.
+    QuoteInstruction(QuoteInstructionError),
+    /// The query account has no wallet to screen (program or data account).
+    QueryAccount(QueryAccountKind),
+    /// The account's data is mutably borrowed elsewhere.
+    Borrowed,
+}
+
+impl From<AccountCheckError> for ProgramError {
+    fn from(error: AccountCheckError) -> Self {
+        match error {
+            AccountCheckError::QueueAddress | AccountCheckError::InvalidSysvar => {
+                ProgramError::InvalidArgument
+            }
+            AccountCheckError::QueueOwner => ProgramError::IllegalOwner,
+            AccountCheckError::QueueLength(_) | AccountCheckError::QueryAccount(_) => {
+                ProgramError::InvalidAccountData
+            }
+            AccountCheckError::QuoteTooLarge(_) | AccountCheckError::QuoteInstruction(_) => {
+                ProgramError::InvalidInstructionData
+            }
+            AccountCheckError::Borrowed => ProgramError::AccountBorrowFailed,
+        }
+    }
+}
+
+/// Checks the queue's address, owner and size against the active profile.
+pub fn check_queue(queue: &AccountInfo) -> Result<(), AccountCheckError> {
+    if queue.key.to_bytes() != profiles::ACTIVE.queue {
+        return Err(AccountCheckError::QueueAddress);
+    }
+    if queue.owner.to_bytes() != profiles::ACTIVE.switchboard_program {
+        return Err(AccountCheckError::QueueOwner);
+    }
+    if queue.data_len() != verifier_inputs::QUEUE_ACCOUNT_LEN {
+        return Err(AccountCheckError::QueueLength(queue.data_len()));
+    }
+    Ok(())
+}
+
+/// Checks that the SlotHashes and Instructions accounts are the sysvars. The
+/// verifier asserts on SlotHashes instead of failing.
+pub fn check_sysvars(
+    slothashes: &AccountInfo,
+    instructions: &AccountInfo,
+) -> Result<(), AccountCheckError> {
+    if *slothashes.key != sysvar::slot_hashes::ID || *instructions.key != sysvar::instructions::ID
+    {
+        return Err(AccountCheckError::InvalidSysvar);
+    }
+    Ok(())
+}
+
+/// Checks the quote instruction at `index` against the profile's size limit
+/// and for what would make the verifier panic.
+pub fn check_quote_instruction(
+    instructions: &AccountInfo,
+    index: usize,
+) -> Result<(), AccountCheckError> {
+    let data = instructions
+        .try_borrow_data()
+        .map_err(|_| AccountCheckError::Borrowed)?;
+    if let Some(len) = quote_limits::instruction_data_len(&data, index)
+        .filter(|&len| len > profiles::ACTIVE.max_quote_data_len)
+    {
+        return Err(AccountCheckError::QuoteTooLarge(len));
+    }
+    verifier_inputs::check_quote_instruction(&data, index)
+        .map_err(AccountCheckError::QuoteInstruction)
+}
+
+/// [`check_quote_instruction`], then checks the quote's slot hash and signers
+/// against SlotHashes and the queue, which the verifier asserts on. Run after
+/// [`check_queue`] and [`check_sysvars`].
+pub fn check_quote(
+    queue: &AccountInfo,
+    slothashes: &AccountInfo,
+    instructions: &AccountInfo,
+    index: usize,
+) -> Result<(), AccountCheckError> {
+    check_quote_instruction(instructions, index)?;
+    let instructions = instructions
+        .try_borrow_data()
+        .map_err(|_| AccountCheckError::Borrowed)?;
+    let queue = queue
+        .try_borrow_data()
+        .map_err(|_| AccountCheckError::Borrowed)?;
+    let slothashes = slothashes
+        .try_borrow_data()
+        .map_err(|_| AccountCheckError::Borrowed)?;
+    verifier_inputs::check_quote(&instructions, index, &queue, &slothashes)
+        .map_err(AccountCheckError::QuoteInstruction)
+}
+
+/// Wallet whose score the feed asks for: the query account itself, or the
+/// owner of a token account (see `query_account`).
+pub fn screened_wallet(query_account: &AccountInfo) -> Result<Pubkey, AccountCheckError> {
+    let data = query_account
+        .try_borrow_data()
+        .map_err(|_| AccountCheckError::Borrowed)?;
+    let kind = query_account::classify(
+        &query_account.owner.to_bytes(),
+        query_account.executable,
+        &data,
+    );
+    match kind {
+        QueryAccountKind::Wallet => Ok(*query_account.key),
+        QueryAccountKind::TokenAccount { owner } => Ok(Pubkey::new_from_array(owner)),
+        QueryAccountKind::ProgramOwned | QueryAccountKind::Program => {
+            Err(AccountCheckError::QueryAccount(kind))
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Deployment profiles.
+//!
+//! Every environment-specific value used by the programs (queue, Range
+//! endpoint, freshness and quote size limits, severity bands) lives here,
+//! grouped into one constant set per deployment target. Exactly one of the
+//! `mainnet`, `devnet` or `staging` cargo features must be enabled; the
+//! selected set is re-exported as [`ACTIVE`] so a build is fully determined
+//! by its feature flags.
+//!
+//! The client SDKs mirror these values in `PROFILES` (`*/client/sdk.ts`). Any
+//! change here must be reflected there, since the Range URL is part of the
+//! feed definition and therefore of the feed id.
+
+use crate::feed::{ResponseTemplate, RANGE_JSON};
+use crate::severity::SeverityBands;
+
+/// Constant set for a single deployment target.
+pub struct Profile {
+    /// Human readable name, matches the cargo feature.
+    pub name: &'static str,
+    /// Switchboard On-Demand queue the quotes must come from.
+    pub queue: [u8; 32],
+    /// Switchboard On-Demand program owning [`Profile::queue`].
+    pub switchboard_program: [u8; 32],
+    /// Range risk endpoint, without query parameters.
+    pub range_risk_url: &'static str,
+    /// Value of the `network` query parameter sent to Range.
+    pub range_network: &'static str,
+    /// Format of the provider's response and how the score is extracted.
+    pub response: ResponseTemplate,
+    /// Maximum age handed to `QuoteVerifier::max_age`.
+    pub verifier_max_age: u64,
+    /// Maximum distance (in slots) between the current slot and the quote slot.
+    pub max_quote_age_slots: u64,
+    /// Oldest cached score (in slots since its quote) `check_or_verify_score`
+    /// uses, whatever age its caller accepts.
+    pub max_cache_age_slots: u64,
+    /// Fewest oracle signatures a quote must carry, on top of the
+    /// `min_oracle_samples` its feed asks for.
+    pub min_oracle_samples: u8,
+    /// Largest quote instruction (Ed25519 instruction data, in bytes) the
+    /// programs hand to the verifier, see `quote_limits`.
+    pub max_quote_data_len: usize,
+    /// Most feeds a quote may carry, see `quote_limits`.
+    pub max_quote_feeds: usize,
+    /// Whether the programs accept the constant `feed::test_feed`. Only for
+    /// devnet and local validators, never for real screening.
+    pub test_feed: bool,
+    /// Score bands the programs tag verified scores with, see `severity`.
+    pub severity_bands: SeverityBands,
+}
+
+/// Solana mainnet-beta.
+pub const MAINNET: Profile = Profile {
+    name: "mainnet",
+    queue: five8_const::decode_32_const("A43DyUGA7s8eXPxqEjJY6EBu1KKbNgfxF8h17VAHn13w"),
+    switchboard_program: five8_const::decode_32_const(
+        "SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv",
+    ),
+    range_risk_url: "https://api.range.org/v1/risk/address",
+    range_network: "solana",
+    response: RANGE_JSON,
+    verifier_max_age: 30,
+    max_quote_age_slots: 50,
+    max_cache_age_slots: 1_500,
+    min_oracle_samples: 1,
+    max_quote_data_len: 1024,
+    max_quote_feeds: 8,
+    test_feed: false,
+    severity_bands: SeverityBands {
+        warn: 40,
+        critical: 70,
+    },
+};
+
+/// Solana devnet.
+pub const DEVNET: Profile = Profile {
+    name: "devnet",
+    queue: five8_const::decode_32_const("EYiAmGSdsQTuCw413V5BzaruWuCCSDgTPtBGvLkXHbe7"),
+    switchboard_program: five8_const::decode_32_const(
+        "Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2",
+    ),
+    range_risk_url: "https://api.range.org/v1/risk/address",
+    range_network: "solana",
+    response: RANGE_JSON,
+    verifier_max_age: 30,
+    max_quote_age_slots: 50,
+    max_cache_age_slots: 1_500,
+    min_oracle_samples: 1,
+    max_quote_data_len: 1024,
+    max_quote_feeds: 8,
+    test_feed: true,
+    severity_bands: SeverityBands {
+        warn: 40,
+        critical: 70,
+    },
+};
+
+/// Devnet queue with mainnet freshness limits, used to rehearse a mainnet
+/// release against devnet infrastructure.
+pub const STAGING: Profile = Profile {
+    name: "staging",
+    queue: DEVNET.queue,
+    switchboard_program: DEVNET.switchboard_program,
+    range_risk_url: MAINNET.range_risk_url,
+    range_network: MAINNET.range_network,
+    response: MAINNET.response,
+    verifier_max_age: MAINNET.verifier_max_age,
+    max_quote_age_slots: MAINNET.max_quote_age_slots,
+    max_cache_age_slots: MAINNET.max_cache_age_slots,
+    min_oracle_samples: MAINNET.min_oracle_samples,
+    max_quote_data_len: MAINNET.max_quote_data_len,
+    max_quote_feeds: MAINNET.max_quote_feeds,
+    test_feed: MAINNET.test_feed,
+    severity_bands: MAINNET.severity_bands,
+};
+
+#[cfg(any(
+    all(feature = "mainnet", feature = "devnet"),
+    all(feature = "mainnet", feature = "staging"),
+    all(feature = "devnet", feature = "staging"),
+))]
+compile_error!("only one of the `mainnet`, `devnet` and `staging` features can be enabled");
+
+#[cfg(not(any(feature = "mainnet", feature = "devnet", feature = "staging")))]
+compile_error!("one of the `mainnet`, `devnet` or `staging` features must be enabled");
+
+/// The profile selected by cargo features.
+#[cfg(feature = "mainnet")]
+pub const ACTIVE: Profile = MAINNET;
+#[cfg(feature = "devnet")]
+pub const ACTIVE: Profile = DEVNET;
+#[cfg(feature = "staging")]
+pub const ACTIVE: Profile = STAGING;
@@ -0,0 +1,40 @@
+//! Transaction-shape screening: whether an instruction of the transaction
+//! targets a denied program.
+//!
+//! A wallet can score low while the transaction around the verification
+//! routes its funds through a mixer. With program screening enabled (the
+//! Anchor `PROGRAM_DENYLIST` flag, the Pinocchio `[5]` prefix), the programs
+//! read the program id of every instruction of the transaction from the
+//! Instructions sysvar and fail when one is denied, so one gate covers both
+//! the address and the shape of the transaction. Only top-level
+//! instructions are visible: a denied program invoked through CPI by an
+//! allowed one is not.
+
+use crate::quote_limits::{instruction_program_id, read_u16};
+
+/// Why a transaction fails program screening.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgramScreenError {
+    /// The sysvar data can't be read as a list of instructions.
+    Malformed,
+    /// Instruction `index` targets the denied `program_id`.
+    Denied { index: usize, program_id: [u8; 32] },
+}
+
+/// Checks the program id of every instruction of the raw Instructions sysvar
+/// against `is_denied`, stopping at the first denied one (layout: see
+/// `quote_limits::instruction_data_len`).
+pub fn screen_programs(
+    sysvar_data: &[u8],
+    is_denied: impl Fn(&[u8; 32]) -> bool,
+) -> Result<(), ProgramScreenError> {
+    let count = read_u16(sysvar_data, 0).ok_or(ProgramScreenError::Malformed)?;
+    for index in 0..count {
+        let program_id =
+            instruction_program_id(sysvar_data, index).ok_or(ProgramScreenError::Malformed)?;
+        if is_denied(program_id) {
+            return Err(ProgramScreenError::Denied { index, program_id: *program_id });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+//! Vendored protobuf types of the Switchboard job schemas (`OracleFeed`,
+//! `OracleJob` and the tasks).
+//!
+//! Feed ids are hashes of the encoded feeds, so the encoding must not change
+//! under our feet. `oracle_job.rs` is the prost-build 0.13 output for the
+//! `job_schemas.proto` of switchboard-protos 0.2.6, checked in rather than
+//! generated at build time: upgrading `prost` or the Switchboard crates can't
+//! silently change the feed bytes. `tests/feed_encoding.rs` pins the encoding
+//! of every feed template against recorded bytes. The one edit to the output
+//! tags the untagged code blocks of its comments `text`, which rustdoc would
+//! otherwise run as doctests.
+//!
+//! To pick up a new schema, regenerate the file from the new proto with
+//! prost-build and tag its code blocks again, then run the fixture test: any
+//! template whose bytes changed has a new feed id and needs a new
+//! `feed::schema` tag.
+
+#[allow(clippy::all, clippy::pedantic)]
+mod generated {
+    include!("proto/oracle_job.rs");
+}
+
+pub use generated::*;
@@ -0,0 +1,86 @@
+//! Classification of the account passed as `query_account`.
+//!
+//! The Range endpoint scores wallets. Base58-encoding whatever pubkey the
+//! caller passes gives meaningless results for token accounts (the wallet is
+//! their owner), and for programs or program-owned data accounts there is no
+//! wallet to score at all. Both programs classify the account first and only
+//! derive the feed for the resolved wallet.
+//!
+//! Which pubkey ends up in the Range URL:
+//!
+//! | query account            | mode      | screened pubkey          |
+//! | ------------------------ | --------- | ------------------------ |
+//! | wallet (system owned)    | default   | the wallet               |
+//! | SPL Token / 2022 account | default   | the token account owner  |
+//! | PDA, mint, other data    | default   | rejected                 |
+//! | program                  | default   | rejected                 |
+//! | wallet + mint            | ATA mode  | the wallet (never the ATA) |
+//!
+//! In ATA mode the program derives the wallet's associated token account
+//! itself (logged for reference) so integrators holding an ATA can't screen
+//! it by mistake.
+
+/// System program id (all zeroes).
+pub const SYSTEM_PROGRAM_ID: [u8; 32] = [0; 32];
+
+/// SPL Token program id.
+pub const TOKEN_PROGRAM_ID: [u8; 32] =
+    five8_const::decode_32_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// SPL Token-2022 program id.
+pub const TOKEN_2022_PROGRAM_ID: [u8; 32] =
+    five8_const::decode_32_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Associated Token Account program id.
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: [u8; 32] =
+    five8_const::decode_32_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Size of a token account without extensions.
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Offset of the `owner` field in a token account.
+const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+
+/// Token-2022 `AccountType::Account` marker, stored right after the base layout.
+const TOKEN_2022_ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+/// What the query account turned out to be.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryAccountKind {
+    /// System-owned account (funded or not): screened as is.
+    Wallet,
+    /// SPL Token / Token-2022 account: its `owner` wallet is screened instead.
+    TokenAccount { owner: [u8; 32] },
+    /// Data account owned by another program (PDA, mint, ...): rejected.
+    ProgramOwned,
+    /// Executable account: rejected.
+    Program,
+}
+
+/// Classifies an account from its owner program, executable bit and data.
+pub fn classify(owner: &[u8; 32], executable: bool, data: &[u8]) -> QueryAccountKind {
+    if executable {
+        return QueryAccountKind::Program;
+    }
+    if *owner == SYSTEM_PROGRAM_ID {
+        return QueryAccountKind::Wallet;
+    }
+
+    let is_token_account = match *owner {
+        TOKEN_PROGRAM_ID => data.len() == TOKEN_ACCOUNT_LEN,
+        TOKEN_2022_PROGRAM_ID => {
+            data.len() == TOKEN_ACCOUNT_LEN
+                || data.get(TOKEN_ACCOUNT_LEN) == Some(&TOKEN_2022_ACCOUNT_TYPE_ACCOUNT)
+        }
+        _ => false,
+    };
+    if !is_token_account {
+        return QueryAccountKind::ProgramOwned;
+    }
+
+    let mut token_owner = [0u8; 32];
+    token_owner.copy_from_slice(
+        &data[TOKEN_ACCOUNT_OWNER_OFFSET..TOKEN_ACCOUNT_OWNER_OFFSET + 32],
+    );
+    QueryAccountKind::TokenAccount { owner: token_owner }
+}
@@ -0,0 +1,72 @@
+//! Bounds on the quote a verification processes.
+//!
+//! The quote instruction is supplied by whoever relays the transaction. Left
+//! unbounded, a relayer could pad it (extra feeds, extra bytes) so that
+//! parsing and iterating it pushes the consuming program over its compute
+//! budget. The programs check the instruction data size before handing the
+//! quote to the verifier, and the feed count before iterating the feeds,
+//! against the limits of the active profile (`max_quote_data_len`,
+//! `max_quote_feeds`).
+//!
+//! The quote instruction is expected at index 0. Programs built with their
+//! `quote-discovery` feature also find it after leading ComputeBudget
+//! instructions ([`discover_quote_index`]), so wallets and relayers that put
+//! the priority fee first don't break the verification.
+
+/// ComputeBudget program, whose instructions may precede the quote under
+/// `quote-discovery`.
+pub const COMPUTE_BUDGET_PROGRAM_ID: [u8; 32] =
+    five8_const::decode_32_const("ComputeBudget111111111111111111111111111111");
+
+/// ComputeBudget instructions a transaction can carry: one each of the heap
+/// frame, unit limit, unit price and loaded data size (the runtime rejects
+/// duplicates).
+pub const MAX_COMPUTE_BUDGET_INSTRUCTIONS: usize = 4;
+
+/// Index of the quote instruction under `quote-discovery`: the first
+/// instruction that isn't one of at most
+/// [`MAX_COMPUTE_BUDGET_INSTRUCTIONS`] leading ComputeBudget ones. Whether it
+/// is a quote is left to the quote checks.
+pub fn discover_quote_index(sysvar_data: &[u8]) -> usize {
+    (0..MAX_COMPUTE_BUDGET_INSTRUCTIONS)
+        .find(|&index| {
+            instruction_program_id(sysvar_data, index) != Some(&COMPUTE_BUDGET_PROGRAM_ID)
+        })
+        .unwrap_or(MAX_COMPUTE_BUDGET_INSTRUCTIONS)
+}
+
+/// Length of the data of instruction `index`, read from the raw Instructions
+/// sysvar without copying the instruction out. Returns `None` when the sysvar
+/// has no such instruction or is malformed.
+///
+/// Layout: `u16` instruction count, one `u16` offset per instruction, then at
+/// each offset a `u16` account count, 33 bytes per account (flags, pubkey),
+/// the 32-byte program id, a `u16` data length and the data.
+pub fn instruction_data_len(sysvar_data: &[u8], index: usize) -> Option<usize> {
+    let count = read_u16(sysvar_data, 0)?;
+    if index >= count {
+        return None;
+    }
+    let start = read_u16(sysvar_data, 2 + index * 2)?;
+    let accounts = read_u16(sysvar_data, start)?;
+    let data_len_at = start + 2 + accounts * 33 + 32;
+    let data_len = read_u16(sysvar_data, data_len_at)?;
+    (data_len_at + 2 + data_len <= sysvar_data.len()).then_some(data_len)
+}
+
+/// Program id of instruction `index` (layout: see [`instruction_data_len`]).
+pub fn instruction_program_id(sysvar_data: &[u8], index: usize) -> Option<&[u8; 32]> {
+    let count = read_u16(sysvar_data, 0)?;
+    if index >= count {
+        return None;
+    }
+    let start = read_u16(sysvar_data, 2 + index * 2)?;
+    let accounts = read_u16(sysvar_data, start)?;
+    let program_id_at = start + 2 + accounts * 33;
+    sysvar_data.get(program_id_at..program_id_at + 32)?.try_into().ok()
+}
+
+pub(crate) fn read_u16(data: &[u8], at: usize) -> Option<usize> {
+    let bytes = data.get(at..at + 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+}
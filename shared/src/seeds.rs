@@ -0,0 +1,298 @@
+//! PDA seeds of the program's accounts, and their address derivations.
+//!
+//! The program's account constraints and every client derive addresses from
+//! these constants, so an integrator deriving a PDA itself can't drift from
+//! the program. Address keys are the raw 32 bytes; integers are encoded as
+//! the program encodes them (noted per seed).
+//!
+//! The `find_*_address` helpers take the runtime's `find_program_address` as
+//! a parameter, as [`merkle`](crate::merkle) takes its hash function, so the
+//! same helpers serve the program, the Rust client and native programs
+//! whatever their `Pubkey` type:
+//!
+//! ```ignore
+//! let (cache, bump) =
+//!     seeds::find_score_cache_address(Pubkey::find_program_address, &ID, &wallet.to_bytes());
+//! ```
+
+/// Seed of the singleton `Config` PDA.
+pub const CONFIG_SEED: &[u8] = b"config";
+
+/// Seed prefix of the per-address `ReplayRecord` PDA.
+pub const REPLAY_SEED: &[u8] = b"replay";
+
+/// Seed prefix of the per-address `ScoreCache` PDA.
+pub const CACHE_SEED: &[u8] = b"cache";
+
+/// Seed of the singleton `FeedRegistry` PDA.
+pub const REGISTRY_SEED: &[u8] = b"registry";
+
+/// Seed prefix of the per-address `FeedIdRecord` PDA.
+pub const FEED_ID_SEED: &[u8] = b"feed_id";
+
+/// Seed prefix of the per-address `QuoteMarker` PDA.
+pub const MARKER_SEED: &[u8] = b"marker";
+
+/// Seed prefix of the per-address `VerificationReceipt` PDA.
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+
+/// Seed prefix of the per-address `ScoreHistory` PDA.
+pub const HISTORY_SEED: &[u8] = b"history";
+
+/// Seed prefix of the per-address `DenylistEntry` PDA.
+pub const DENYLIST_SEED: &[u8] = b"denylist";
+
+/// Seed of the singleton `DenylistRoot` PDA.
+pub const DENYLIST_ROOT_SEED: &[u8] = b"denylist_root";
+
+/// Seed prefix of the `DenylistSegment` PDAs (then the big-endian segment
+/// index).
+pub const DENYLIST_SEGMENT_SEED: &[u8] = b"denylist_segment";
+
+/// Seed prefix of the per-address `FreshnessSla` PDA.
+pub const SLA_SEED: &[u8] = b"sla";
+
+/// Seed prefix of the per-address `ConfidentialScore` PDA.
+pub const CONFIDENTIAL_SEED: &[u8] = b"confidential";
+
+/// Seed prefix of the per-address `ThresholdProof` PDA.
+pub const THRESHOLD_PROOF_SEED: &[u8] = b"threshold_proof";
+
+/// Seed of the singleton `ConsumerAccessList` PDA.
+pub const ACCESS_LIST_SEED: &[u8] = b"access_list";
+
+/// Seed of the singleton `ProgramDenylist` PDA.
+pub const PROGRAM_DENYLIST_SEED: &[u8] = b"program_denylist";
+
+/// Seed prefix of the per-consumer `Integrator` PDA.
+pub const INTEGRATOR_SEED: &[u8] = b"integrator";
+
+/// Seed of the singleton `ComplianceOfficer` PDA.
+pub const COMPLIANCE_OFFICER_SEED: &[u8] = b"compliance_officer";
+
+/// Seed prefix of the per-voucher `BypassRecord` PDA (address, then the
+/// little-endian nonce).
+pub const BYPASS_SEED: &[u8] = b"bypass";
+
+/// Seed prefix of the per-template `TemplateVariables` PDA (then the
+/// `feed::schema` tag).
+pub const TEMPLATE_VARIABLES_SEED: &[u8] = b"template_variables";
+
+/// Seed prefix of the per-owner `QuoteAccount` PDA (owner, then the
+/// little-endian id).
+pub const QUOTE_ACCOUNT_SEED: &[u8] = b"quote_account";
+
+/// Seed prefix of the per-consumer `RiskCeiling` PDA.
+pub const RISK_CEILING_SEED: &[u8] = b"risk_ceiling";
+
+/// Seed prefix of the per-address `AddressPolicy` PDA.
+pub const ADDRESS_POLICY_SEED: &[u8] = b"address_policy";
+
+/// Seed of the singleton `ScoreDecay` PDA.
+pub const SCORE_DECAY_SEED: &[u8] = b"score_decay";
+
+/// Every seed, for tooling checking them (they must be distinct).
+pub const ALL: [&[u8]; 24] = [
+    CONFIG_SEED,
+    REPLAY_SEED,
+    CACHE_SEED,
+    REGISTRY_SEED,
+    FEED_ID_SEED,
+    MARKER_SEED,
+    RECEIPT_SEED,
+    HISTORY_SEED,
+    DENYLIST_SEED,
+    DENYLIST_ROOT_SEED,
+    DENYLIST_SEGMENT_SEED,
+    SLA_SEED,
+    CONFIDENTIAL_SEED,
+    THRESHOLD_PROOF_SEED,
+    ACCESS_LIST_SEED,
+    PROGRAM_DENYLIST_SEED,
+    INTEGRATOR_SEED,
+    COMPLIANCE_OFFICER_SEED,
+    BYPASS_SEED,
+    TEMPLATE_VARIABLES_SEED,
+    QUOTE_ACCOUNT_SEED,
+    RISK_CEILING_SEED,
+    ADDRESS_POLICY_SEED,
+    SCORE_DECAY_SEED,
+];
+
+/// The runtime's `find_program_address`, over its own `Pubkey` type.
+pub type FindProgramAddress<P> = fn(&[&[u8]], &P) -> (P, u8);
+
+pub fn find_config_address<P>(find: FindProgramAddress<P>, program_id: &P) -> (P, u8) {
+    find(&[CONFIG_SEED], program_id)
+}
+
+pub fn find_registry_address<P>(find: FindProgramAddress<P>, program_id: &P) -> (P, u8) {
+    find(&[REGISTRY_SEED], program_id)
+}
+
+pub fn find_denylist_root_address<P>(find: FindProgramAddress<P>, program_id: &P) -> (P, u8) {
+    find(&[DENYLIST_ROOT_SEED], program_id)
+}
+
+pub fn find_access_list_address<P>(find: FindProgramAddress<P>, program_id: &P) -> (P, u8) {
+    find(&[ACCESS_LIST_SEED], program_id)
+}
+
+pub fn find_program_denylist_address<P>(find: FindProgramAddress<P>, program_id: &P) -> (P, u8) {
+    find(&[PROGRAM_DENYLIST_SEED], program_id)
+}
+
+pub fn find_compliance_officer_address<P>(find: FindProgramAddress<P>, program_id: &P) -> (P, u8) {
+    find(&[COMPLIANCE_OFFICER_SEED], program_id)
+}
+
+pub fn find_score_decay_address<P>(find: FindProgramAddress<P>, program_id: &P) -> (P, u8) {
+    find(&[SCORE_DECAY_SEED], program_id)
+}
+
+/// Keyed by the `feed::schema` tag of the template.
+pub fn find_template_variables_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    schema: u8,
+) -> (P, u8) {
+    find(&[TEMPLATE_VARIABLES_SEED, &[schema]], program_id)
+}
+
+pub fn find_replay_record_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    query_account: &[u8; 32],
+) -> (P, u8) {
+    find(&[REPLAY_SEED, query_account], program_id)
+}
+
+pub fn find_score_cache_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    query_account: &[u8; 32],
+) -> (P, u8) {
+    find(&[CACHE_SEED, query_account], program_id)
+}
+
+pub fn find_feed_id_record_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    query_account: &[u8; 32],
+) -> (P, u8) {
+    find(&[FEED_ID_SEED, query_account], program_id)
+}
+
+pub fn find_quote_marker_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    query_account: &[u8; 32],
+) -> (P, u8) {
+    find(&[MARKER_SEED, query_account], program_id)
+}
+
+pub fn find_receipt_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    query_account: &[u8; 32],
+) -> (P, u8) {
+    find(&[RECEIPT_SEED, query_account], program_id)
+}
+
+pub fn find_score_history_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    query_account: &[u8; 32],
+) -> (P, u8) {
+    find(&[HISTORY_SEED, query_account], program_id)
+}
+
+pub fn find_freshness_sla_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    query_account: &[u8; 32],
+) -> (P, u8) {
+    find(&[SLA_SEED, query_account], program_id)
+}
+
+pub fn find_confidential_score_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    query_account: &[u8; 32],
+) -> (P, u8) {
+    find(&[CONFIDENTIAL_SEED, query_account], program_id)
+}
+
+pub fn find_threshold_proof_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    query_account: &[u8; 32],
+) -> (P, u8) {
+    find(&[THRESHOLD_PROOF_SEED, query_account], program_id)
+}
+
+/// Keyed by the screened wallet, not the query account.
+pub fn find_denylist_entry_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    address: &[u8; 32],
+) -> (P, u8) {
+    find(&[DENYLIST_SEED, address], program_id)
+}
+
+/// Keyed by the segment index, see
+/// [`denylist_segment::segment_of`](crate::denylist_segment::segment_of).
+pub fn find_denylist_segment_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    segment: u16,
+) -> (P, u8) {
+    find(&[DENYLIST_SEGMENT_SEED, &segment.to_be_bytes()], program_id)
+}
+
+/// Keyed by the consuming program, not the query account.
+pub fn find_integrator_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    consumer: &[u8; 32],
+) -> (P, u8) {
+    find(&[INTEGRATOR_SEED, consumer], program_id)
+}
+
+/// Keyed by the consuming program, not the query account.
+pub fn find_risk_ceiling_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    consumer: &[u8; 32],
+) -> (P, u8) {
+    find(&[RISK_CEILING_SEED, consumer], program_id)
+}
+
+/// Keyed by the screened wallet. For token accounts, this is the owner.
+pub fn find_address_policy_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    address: &[u8; 32],
+) -> (P, u8) {
+    find(&[ADDRESS_POLICY_SEED, address], program_id)
+}
+
+/// Keyed by the screened wallet and the voucher nonce.
+pub fn find_bypass_record_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    address: &[u8; 32],
+    nonce: u64,
+) -> (P, u8) {
+    find(&[BYPASS_SEED, address, &nonce.to_le_bytes()], program_id)
+}
+
+/// Keyed by the owner and its own id for the account.
+pub fn find_quote_account_address<P>(
+    find: FindProgramAddress<P>,
+    program_id: &P,
+    owner: &[u8; 32],
+    id: u64,
+) -> (P, u8) {
+    find(&[QUOTE_ACCOUNT_SEED, owner, &id.to_le_bytes()], program_id)
+}
@@ -0,0 +1,29 @@
+//! `serialize_with` helpers for the `serde` feature, so account dumps show
+//! keys and hashes the way explorers do instead of arrays of numbers.
+
+use serde::Serializer;
+
+/// Base58 string, for pubkeys.
+pub fn base58<S: Serializer>(bytes: &impl AsRef<[u8]>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&bs58::encode(bytes).into_string())
+}
+
+/// Base58 strings, for lists of pubkeys.
+pub fn base58_vec<S: Serializer, K: AsRef<[u8]>>(
+    keys: &impl AsRef<[K]>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(keys.as_ref().iter().map(|key| bs58::encode(key).into_string()))
+}
+
+/// Lowercase hex string, for feed ids and hashes.
+pub fn hex<S: Serializer>(bytes: &impl AsRef<[u8]>, serializer: S) -> Result<S::Ok, S::Error> {
+    use alloc::string::String;
+    use core::fmt::Write;
+
+    let mut out = String::with_capacity(bytes.as_ref().len() * 2);
+    for byte in bytes.as_ref() {
+        let _ = write!(out, "{byte:02x}");
+    }
+    serializer.serialize_str(&out)
+}
@@ -0,0 +1,47 @@
+//! Severity of a verified score, so indexers and alerting can filter events
+//! without re-implementing the thresholds.
+//!
+//! Scores (0–100) are classified by the bands of the deployment profile
+//! (`Profile::severity_bands`): below `warn` is [`Severity::Info`], from
+//! `warn` [`Severity::Warn`], from `critical` [`Severity::Critical`]. The
+//! Anchor program tags its score events with it, the Pinocchio program logs
+//! it next to the score, and off-chain code reads it from there.
+
+/// Score bands of a profile, on the feed scale. `warn <= critical`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeverityBands {
+    /// Lowest score classified [`Severity::Warn`].
+    pub warn: u8,
+    /// Lowest score classified [`Severity::Critical`].
+    pub critical: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warn,
+    Critical,
+}
+
+impl SeverityBands {
+    pub fn classify(&self, score: u8) -> Severity {
+        if score >= self.critical {
+            Severity::Critical
+        } else if score >= self.warn {
+            Severity::Warn
+        } else {
+            Severity::Info
+        }
+    }
+}
+
+impl Severity {
+    /// Lowercase name, as used in logs and configuration.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Critical => "critical",
+        }
+    }
+}
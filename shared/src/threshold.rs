@@ -0,0 +1,75 @@
+//! Comparison of a score against a risk threshold, shared by every gate so
+//! that "is 50 allowed at threshold 50" has one answer.
+//!
+//! Gates compare whole scores (0–100), never the quote's exact value:
+//! `verify_risk_score_feed` and its variants, the confidential mode,
+//! threshold proofs, TWA gating, cached scores and the `gate` helpers. A
+//! value can fall between two scores (the median of an even number of oracle
+//! samples, a fractional provider score), so it is rounded up to the next
+//! score ([`feed::score_from_feed_value`](crate::feed::score_from_feed_value)):
+//! a score never gates below its exact value, and a cached score gates as the
+//! quote it was read from. Whether the threshold itself passes is the
+//! [`Boundary`], a setting of the Anchor config.
+//!
+//! Pass/fail feeds compare oracle-side and are always [`Boundary::Inclusive`]
+//! on the exact value, which the rounding makes equivalent.
+//!
+//! A threshold change can also be phased in over a slot range instead of
+//! applied at once ([`Ramp`]), so tightening the policy doesn't suddenly block
+//! every active user between the two thresholds.
+
+/// Whether a score equal to the threshold passes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Boundary {
+    /// Scores at the threshold pass: `score <= threshold`.
+    #[default]
+    Inclusive,
+    /// Scores at the threshold fail: `score < threshold`.
+    Exclusive,
+}
+
+impl Boundary {
+    /// Whether `score` passes `threshold`.
+    pub fn passes(self, score: u8, threshold: u8) -> bool {
+        match self {
+            Self::Inclusive => score <= threshold,
+            Self::Exclusive => score < threshold,
+        }
+    }
+
+    /// Lowercase name, as used in logs and configuration.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Inclusive => "inclusive",
+            Self::Exclusive => "exclusive",
+        }
+    }
+}
+
+/// Threshold moving linearly from `from` to `to` between two slots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ramp {
+    pub from: u8,
+    pub to: u8,
+    pub start_slot: u64,
+    /// First slot at which the threshold is `to`.
+    pub end_slot: u64,
+}
+
+impl Ramp {
+    /// Threshold at `slot`: `from` up to `start_slot`, `to` from `end_slot`,
+    /// and in between the linear interpolation rounded down, so the ramp is
+    /// never looser than the straight line between the two thresholds.
+    pub fn at(&self, slot: u64) -> u8 {
+        if slot <= self.start_slot {
+            return self.from;
+        }
+        if slot >= self.end_slot {
+            return self.to;
+        }
+        let span = (self.end_slot - self.start_slot) as u128;
+        let elapsed = (slot - self.start_slot) as u128;
+        let weighted = self.from as u128 * (span - elapsed) + self.to as u128 * elapsed;
+        (weighted / span) as u8
+    }
+}
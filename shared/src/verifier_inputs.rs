@@ -0,0 +1,280 @@
+//! Inputs of the Switchboard quote verifier, checked before it runs.
+//!
+//! The verifier asserts (panics) instead of returning an error when the
+//! instruction it is pointed at is missing, isn't an Ed25519 instruction or
+//! reads its signature, key or message from another instruction, and when the
+//! queue account isn't the size it expects. It also trusts the offsets of the
+//! quote, looks the quote slot up in SlotHashes without bounds checks and
+//! asserts that the signed slot hash and the signers match SlotHashes and the
+//! queue. A malformed transaction would then abort the program without an
+//! error code, or read past the quote. The programs check the same conditions
+//! first and fail with their own codes.
+//!
+//! A quote is laid out as the Ed25519 header and offsets, the signed parts,
+//! then a suffix the verifier reads from the end of the data:
+//!
+//! ```text
+//! [oracle_idxs: num_signatures][slot: u64 LE][version: u8]["SBOD"]
+//! ```
+
+use crate::quote_limits::read_u16;
+
+/// Ed25519 signature verification program.
+pub const ED25519_PROGRAM_ID: [u8; 32] =
+    five8_const::decode_32_const("Ed25519SigVerify111111111111111111111111111");
+
+/// Size of the Switchboard queue account the verifier reads, discriminator
+/// included.
+pub const QUEUE_ACCOUNT_LEN: usize = 6280;
+
+/// Ed25519 header and first signature offsets.
+const MIN_QUOTE_DATA_LEN: usize = 16;
+/// Ed25519 header: signature count and padding.
+const HEADER_LEN: usize = 2;
+/// Offsets of one signature.
+const OFFSETS_LEN: usize = 14;
+/// Signatures the verifier reads at most.
+const MAX_SIGNATURES: usize = 8;
+/// Slot, version and tail of the suffix.
+const SUFFIX_LEN: usize = 8 + 1 + 4;
+/// Last bytes of a quote.
+const QUOTE_TAIL: &[u8; 4] = b"SBOD";
+/// Signed slot hash starting the quote message.
+const MESSAGE_HEADER_LEN: usize = 32;
+/// Feed id, `i128` value and `min_oracle_samples` of a message feed.
+const MESSAGE_FEED_LEN: usize = 32 + 16 + 1;
+/// Feeds the verifier reads at most.
+const MAX_MESSAGE_FEEDS: usize = 8;
+/// Offset of the Ed25519 signing keys in the queue account.
+const QUEUE_SIGNING_KEYS_OFFSET: usize = 8 + 4192;
+/// Signing key slots of a queue; the verifier reduces oracle indexes modulo
+/// this.
+const QUEUE_ORACLES: usize = 30;
+/// Entries the verifier's SlotHashes lookup wraps at.
+const SLOT_HASHES_LEN: u64 = 512;
+/// Slot and hash of a SlotHashes entry.
+const SLOT_HASH_ENTRY_LEN: usize = 8 + 32;
+
+/// Why instruction `index` can't be handed to the verifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuoteInstructionError {
+    /// No instruction at `index`, or a malformed Instructions sysvar.
+    Missing,
+    /// Not an Ed25519 program instruction.
+    NotEd25519,
+    /// Too short for the Ed25519 header and the first signature offsets.
+    Truncated,
+    /// The first signature's offsets point into another instruction.
+    ExternalReference,
+    /// No signature, more than the verifier reads, or no room for the
+    /// suffix.
+    SignatureCount,
+    /// A signature, public key or the message lies outside the signed parts.
+    OffsetOutOfBounds,
+    /// The message isn't a slot hash followed by whole feeds, at most 8.
+    MalformedMessage,
+    /// The data doesn't end with the quote tail: truncated or extra bytes.
+    Tail,
+    /// The quote slot isn't in SlotHashes where the verifier looks for it.
+    UnknownSlot,
+    /// The signed slot hash isn't the SlotHashes hash of the quote slot.
+    SlotHashMismatch,
+    /// A signer isn't the queue's oracle at its index: a quote for another
+    /// queue.
+    UnknownOracle,
+}
+
+impl QuoteInstructionError {
+    /// Value of the `reason` field of `diagnostics::verification_failed`.
+    pub fn reason(self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::NotEd25519 => "not_ed25519",
+            Self::Truncated => "truncated",
+            Self::ExternalReference => "external_reference",
+            Self::SignatureCount => "signature_count",
+            Self::OffsetOutOfBounds => "offset_out_of_bounds",
+            Self::MalformedMessage => "malformed_message",
+            Self::Tail => "tail",
+            Self::UnknownSlot => "unknown_slot",
+            Self::SlotHashMismatch => "slot_hash_mismatch",
+            Self::UnknownOracle => "unknown_oracle",
+        }
+    }
+}
+
+/// Checks that instruction `index` of the raw Instructions sysvar is a quote
+/// the verifier can read without panicking or reading past it (layout: see
+/// `quote_limits::instruction_data_len`).
+pub fn check_quote_instruction(
+    sysvar_data: &[u8],
+    index: usize,
+) -> Result<(), QuoteInstructionError> {
+    check_layout(quote_data(sysvar_data, index)?, index)
+}
+
+/// [`check_quote_instruction`] on the data of an instruction that is to be
+/// at `index`, e.g. to check a transaction before sending it.
+pub fn check_quote_data(data: &[u8], index: usize) -> Result<(), QuoteInstructionError> {
+    check_layout(data, index)
+}
+
+/// [`check_quote_instruction`], then checks the quote against the accounts
+/// the verifier asserts on: the signed slot hash must be the SlotHashes hash
+/// of the quote slot and every signer the queue's signing key at its oracle
+/// index. `queue_data` must be [`QUEUE_ACCOUNT_LEN`] bytes.
+pub fn check_quote(
+    sysvar_data: &[u8],
+    index: usize,
+    queue_data: &[u8],
+    slothashes_data: &[u8],
+) -> Result<(), QuoteInstructionError> {
+    let data = quote_data(sysvar_data, index)?;
+    check_layout(data, index)?;
+
+    let signatures = data[0] as usize;
+    let suffix_at = data.len() - SUFFIX_LEN - signatures;
+    let slot = read_u64(data, suffix_at + signatures).ok_or(QuoteInstructionError::Tail)?;
+    let slot_hash = find_slot_hash(slothashes_data, slot)?;
+    let message_at = read_u16(data, 10).ok_or(QuoteInstructionError::Truncated)?;
+    if data.get(message_at..message_at + MESSAGE_HEADER_LEN) != Some(slot_hash) {
+        return Err(QuoteInstructionError::SlotHashMismatch);
+    }
+
+    check_signers(data, queue_data)
+}
+
+/// [`check_quote_data`], then checks that every signer is the signing key of
+/// `queue_data`'s oracle at its index, e.g. to check a gateway's quotes
+/// against another queue than the profile's. `queue_data` must be
+/// [`QUEUE_ACCOUNT_LEN`] bytes.
+pub fn check_quote_signers(
+    data: &[u8],
+    index: usize,
+    queue_data: &[u8],
+) -> Result<(), QuoteInstructionError> {
+    check_layout(data, index)?;
+    check_signers(data, queue_data)
+}
+
+/// Checks the signers of a quote whose layout was checked.
+fn check_signers(data: &[u8], queue_data: &[u8]) -> Result<(), QuoteInstructionError> {
+    let signatures = data[0] as usize;
+    let suffix_at = data.len() - SUFFIX_LEN - signatures;
+    for i in 0..signatures {
+        let public_key_at = read_u16(data, HEADER_LEN + i * OFFSETS_LEN + 4)
+            .ok_or(QuoteInstructionError::Truncated)?;
+        let oracle = data[suffix_at + i] as usize % QUEUE_ORACLES;
+        let key_at = QUEUE_SIGNING_KEYS_OFFSET + oracle * 32;
+        if queue_data.get(key_at..key_at + 32) != data.get(public_key_at..public_key_at + 32) {
+            return Err(QuoteInstructionError::UnknownOracle);
+        }
+    }
+    Ok(())
+}
+
+/// Data of instruction `index`, once it is an Ed25519 instruction.
+fn quote_data(sysvar_data: &[u8], index: usize) -> Result<&[u8], QuoteInstructionError> {
+    let count = read_u16(sysvar_data, 0).ok_or(QuoteInstructionError::Missing)?;
+    if index >= count {
+        return Err(QuoteInstructionError::Missing);
+    }
+    let start = read_u16(sysvar_data, 2 + index * 2).ok_or(QuoteInstructionError::Missing)?;
+    let accounts = read_u16(sysvar_data, start).ok_or(QuoteInstructionError::Missing)?;
+    let program_id_at = start + 2 + accounts * 33;
+    let program_id = sysvar_data
+        .get(program_id_at..program_id_at + 32)
+        .ok_or(QuoteInstructionError::Missing)?;
+    if program_id != ED25519_PROGRAM_ID {
+        return Err(QuoteInstructionError::NotEd25519);
+    }
+
+    let data_len =
+        read_u16(sysvar_data, program_id_at + 32).ok_or(QuoteInstructionError::Missing)?;
+    let data_at = program_id_at + 34;
+    sysvar_data
+        .get(data_at..data_at + data_len)
+        .ok_or(QuoteInstructionError::Missing)
+}
+
+/// Checks the parts of `data` the verifier reads without bounds checks.
+fn check_layout(data: &[u8], index: usize) -> Result<(), QuoteInstructionError> {
+    if data.len() < MIN_QUOTE_DATA_LEN {
+        return Err(QuoteInstructionError::Truncated);
+    }
+    // Signature, public key and message instruction indexes of the first
+    // signature.
+    for at in [4, 8, 14] {
+        if read_u16(data, at) != Some(index) {
+            return Err(QuoteInstructionError::ExternalReference);
+        }
+    }
+
+    let signatures = data[0] as usize;
+    if signatures == 0 || signatures > MAX_SIGNATURES {
+        return Err(QuoteInstructionError::SignatureCount);
+    }
+    if !data.ends_with(QUOTE_TAIL) {
+        return Err(QuoteInstructionError::Tail);
+    }
+    // The verifier reads the offsets, keys, signatures and message from the
+    // signed parts, everything before the suffix.
+    let signed_len = data
+        .len()
+        .checked_sub(SUFFIX_LEN + signatures)
+        .filter(|&len| len >= HEADER_LEN + signatures * OFFSETS_LEN)
+        .ok_or(QuoteInstructionError::SignatureCount)?;
+    let signed = &data[..signed_len];
+    let in_bounds =
+        |at: usize, len: usize| at.checked_add(len).is_some_and(|end| end <= signed_len);
+
+    for i in 0..signatures {
+        let at = HEADER_LEN + i * OFFSETS_LEN;
+        let [signature, public_key, message, message_len] =
+            [at, at + 4, at + 8, at + 10].map(|at| read_u16(signed, at).unwrap_or(usize::MAX));
+        if !in_bounds(signature, 64)
+            || !in_bounds(public_key, 32)
+            || !in_bounds(message, message_len)
+        {
+            return Err(QuoteInstructionError::OffsetOutOfBounds);
+        }
+    }
+
+    let message_len = read_u16(signed, 12).ok_or(QuoteInstructionError::Truncated)?;
+    let feeds_len = message_len
+        .checked_sub(MESSAGE_HEADER_LEN)
+        .ok_or(QuoteInstructionError::MalformedMessage)?;
+    if feeds_len % MESSAGE_FEED_LEN != 0 || feeds_len / MESSAGE_FEED_LEN > MAX_MESSAGE_FEEDS {
+        return Err(QuoteInstructionError::MalformedMessage);
+    }
+    Ok(())
+}
+
+/// Hash of `slot` in the SlotHashes data (a `u64` entry count, then slot and
+/// hash entries, newest first), found where the verifier's lookup finds it:
+/// at or before entry `(newest - slot) % 512`.
+fn find_slot_hash(slothashes_data: &[u8], slot: u64) -> Result<&[u8], QuoteInstructionError> {
+    let count = read_u64(slothashes_data, 0).ok_or(QuoteInstructionError::UnknownSlot)?;
+    let entries = (slothashes_data.len() - 8) / SLOT_HASH_ENTRY_LEN;
+    let entries = entries.min(usize::try_from(count).unwrap_or(usize::MAX));
+    let entry_slot = |i: usize| read_u64(slothashes_data, 8 + i * SLOT_HASH_ENTRY_LEN);
+    let newest = entry_slot(0)
+        .filter(|_| entries > 0)
+        .ok_or(QuoteInstructionError::UnknownSlot)?;
+    let last = newest
+        .checked_sub(slot)
+        .map(|age| (age % SLOT_HASHES_LEN) as usize)
+        .filter(|&last| last < entries)
+        .ok_or(QuoteInstructionError::UnknownSlot)?;
+    let found = (0..=last)
+        .rev()
+        .find(|&i| entry_slot(i) == Some(slot))
+        .ok_or(QuoteInstructionError::UnknownSlot)?;
+    let at = 8 + found * SLOT_HASH_ENTRY_LEN + 8;
+    Ok(&slothashes_data[at..at + 32])
+}
+
+fn read_u64(data: &[u8], at: usize) -> Option<u64> {
+    let bytes = data.get(at..at + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
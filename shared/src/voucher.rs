@@ -0,0 +1,137 @@
+//! Bypass vouchers: a compliance officer's off-chain signature letting one
+//! address through the risk check once, within a slot window.
+//!
+//! The officer signs [`BypassVoucher::message`] with the key configured
+//! on-chain; the signature travels as an Ed25519 program instruction placed
+//! right before the instruction redeeming the voucher. The runtime verifies
+//! the signature, the program only checks that the instruction signs the
+//! expected message with the expected key ([`signed_message`]):
+//!
+//! ```text
+//! ["risk-oracle-bypass-v1"][program id: 32][address: 32]
+//! [not_before_slot: u64 LE][not_after_slot: u64 LE][nonce: u64 LE]
+//! ```
+//!
+//! The program id keeps a voucher for one deployment from being redeemed on
+//! another; the nonce tells apart vouchers issued for the same address, each
+//! redeemable once.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::quote_limits::read_u16;
+
+/// Domain separator starting every voucher message.
+pub const DOMAIN: &[u8] = b"risk-oracle-bypass-v1";
+
+/// Length of [`BypassVoucher::message`].
+pub const MESSAGE_LEN: usize = DOMAIN.len() + 32 + 32 + 8 + 8 + 8;
+
+/// Ed25519 header: signature count and padding.
+const HEADER_LEN: usize = 2;
+/// Offsets of one signature.
+const OFFSETS_LEN: usize = 14;
+/// Instruction index of offsets pointing into the Ed25519 instruction itself.
+const CURRENT_IX: usize = u16::MAX as usize;
+
+/// One-time exception for `address`, valid from `not_before_slot` to
+/// `not_after_slot` inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BypassVoucher {
+    pub address: [u8; 32],
+    pub not_before_slot: u64,
+    pub not_after_slot: u64,
+    pub nonce: u64,
+}
+
+impl BypassVoucher {
+    /// Bytes the compliance officer signs for a redemption by `program_id`.
+    pub fn message(&self, program_id: &[u8; 32]) -> [u8; MESSAGE_LEN] {
+        let mut message = [0u8; MESSAGE_LEN];
+        let fields: [&[u8]; 6] = [
+            DOMAIN,
+            program_id,
+            &self.address,
+            &self.not_before_slot.to_le_bytes(),
+            &self.not_after_slot.to_le_bytes(),
+            &self.nonce.to_le_bytes(),
+        ];
+        let mut at = 0;
+        for field in fields {
+            message[at..at + field.len()].copy_from_slice(field);
+            at += field.len();
+        }
+        message
+    }
+
+    pub fn is_valid_at(&self, slot: u64) -> bool {
+        self.not_before_slot <= slot && slot <= self.not_after_slot
+    }
+}
+
+/// Why an Ed25519 instruction doesn't carry a voucher signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoucherError {
+    /// Too short for the Ed25519 header and the signature offsets.
+    Truncated,
+    /// Not exactly one signature.
+    SignatureCount,
+    /// The offsets point into another instruction.
+    ExternalReference,
+    /// The public key or the message lies outside the instruction data.
+    OffsetOutOfBounds,
+}
+
+/// Signer and message of the single signature of Ed25519 instruction `data`,
+/// all read from the instruction itself so nothing else in the transaction
+/// can stand in for them.
+pub fn signed_message(data: &[u8]) -> Result<(&[u8; 32], &[u8]), VoucherError> {
+    let read = |at| read_u16(data, at).ok_or(VoucherError::Truncated);
+    if data.len() < HEADER_LEN + OFFSETS_LEN {
+        return Err(VoucherError::Truncated);
+    }
+    if data[0] != 1 {
+        return Err(VoucherError::SignatureCount);
+    }
+    for ix_index in [HEADER_LEN + 2, HEADER_LEN + 6, HEADER_LEN + 12] {
+        if read(ix_index)? != CURRENT_IX {
+            return Err(VoucherError::ExternalReference);
+        }
+    }
+
+    let public_key_at = read(HEADER_LEN + 4)?;
+    let message_at = read(HEADER_LEN + 8)?;
+    let message_len = read(HEADER_LEN + 10)?;
+    let public_key = data
+        .get(public_key_at..public_key_at + 32)
+        .and_then(|key| key.try_into().ok())
+        .ok_or(VoucherError::OffsetOutOfBounds)?;
+    let message = data
+        .get(message_at..message_at + message_len)
+        .ok_or(VoucherError::OffsetOutOfBounds)?;
+    Ok((public_key, message))
+}
+
+/// Ed25519 instruction data signing `message` with `public_key`, laid out
+/// as offsets, key, signature then message.
+pub fn instruction_data(public_key: &[u8; 32], signature: &[u8; 64], message: &[u8]) -> Vec<u8> {
+    let public_key_at = (HEADER_LEN + OFFSETS_LEN) as u16;
+    let signature_at = public_key_at + 32;
+    let message_at = signature_at + 64;
+    let mut data = vec![1u8, 0];
+    for field in [
+        signature_at,
+        u16::MAX,
+        public_key_at,
+        u16::MAX,
+        message_at,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(public_key);
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    data
+}
@@ -0,0 +1,72 @@
+//! `accounts::split` and `Trailing` on plain values standing in for accounts.
+
+use risk_oracle_shared::accounts::{self, AccountsError};
+
+#[test]
+fn split_takes_the_required_accounts_in_order() {
+    let ([a, b], trailing) = accounts::split::<_, 2>(&[1, 2]).unwrap();
+    assert_eq!((*a, *b), (1, 2));
+    assert!(trailing.is_empty());
+    trailing.finish().unwrap();
+}
+
+#[test]
+fn split_fails_without_every_required_account() {
+    assert_eq!(
+        accounts::split::<_, 3>(&[1, 2]).unwrap_err(),
+        AccountsError::NotEnoughAccounts {
+            expected: 3,
+            found: 2,
+        }
+    );
+}
+
+#[test]
+fn optional_accounts_are_taken_when_present() {
+    // Layout `[queue, clock?, slothashes]`, the clock recognized by its value.
+    let is_clock = |account: &i32| *account == 9;
+
+    let ([queue], mut rest) = accounts::split::<_, 1>(&[1, 9, 2]).unwrap();
+    assert_eq!(*queue, 1);
+    assert_eq!(rest.next_if(is_clock), Some(&9));
+    assert_eq!(rest.required::<1>().unwrap(), &[2]);
+    rest.finish().unwrap();
+
+    let (_, mut rest) = accounts::split::<_, 1>(&[1, 2]).unwrap();
+    assert_eq!(rest.next_if(is_clock), None);
+    assert_eq!(rest.required::<1>().unwrap(), &[2]);
+    rest.finish().unwrap();
+}
+
+#[test]
+fn required_leaves_the_accounts_when_too_few_are_left() {
+    let (_, mut rest) = accounts::split::<_, 1>(&[1, 2]).unwrap();
+    assert_eq!(
+        rest.required::<2>().unwrap_err(),
+        AccountsError::NotEnoughAccounts {
+            expected: 2,
+            found: 1,
+        }
+    );
+    assert_eq!(rest.len(), 1);
+}
+
+#[test]
+fn remaining_accounts_are_iterated_in_order() {
+    let (_, rest) = accounts::split::<_, 1>(&[1, 2, 3, 4]).unwrap();
+    assert_eq!(rest.size_hint(), (3, Some(3)));
+    assert_eq!(rest.copied().collect::<Vec<_>>(), [2, 3, 4]);
+
+    let (_, mut rest) = accounts::split::<_, 1>(&[1, 2, 3]).unwrap();
+    assert_eq!(rest.rest(), &[2, 3]);
+    rest.finish().unwrap();
+}
+
+#[test]
+fn finish_rejects_leftover_accounts() {
+    let (_, rest) = accounts::split::<_, 1>(&[1, 2, 3]).unwrap();
+    assert_eq!(
+        rest.finish().unwrap_err(),
+        AccountsError::UnexpectedAccounts { count: 2 }
+    );
+}
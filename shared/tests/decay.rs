@@ -0,0 +1,47 @@
+//! Cached score decay: no penalty within the grace period, a step per started
+//! interval past it, capped, and never past the riskiest score.
+
+use risk_oracle_shared::decay::Decay;
+use risk_oracle_shared::feed::MAX_SCORE;
+
+const DECAY: Decay = Decay {
+    grace_slots: 100,
+    interval_slots: 50,
+    penalty: 5,
+    max_penalty: 20,
+};
+
+#[test]
+fn penalty_steps_per_started_interval_past_the_grace_period() {
+    assert_eq!(DECAY.penalty_at(0), 0);
+    assert_eq!(DECAY.penalty_at(100), 0);
+    assert_eq!(DECAY.penalty_at(101), 5);
+    assert_eq!(DECAY.penalty_at(150), 5);
+    assert_eq!(DECAY.penalty_at(151), 10);
+    assert_eq!(DECAY.penalty_at(300), 20);
+    assert_eq!(DECAY.penalty_at(u64::MAX), 20);
+}
+
+#[test]
+fn decayed_scores_stay_on_the_feed_scale() {
+    assert_eq!(DECAY.apply(40, 100), 40);
+    assert_eq!(DECAY.apply(40, 160), 50);
+    assert_eq!(DECAY.apply(90, 1_000), MAX_SCORE);
+    assert_eq!(DECAY.apply(MAX_SCORE, 1_000), MAX_SCORE);
+}
+
+#[test]
+fn decay_needs_an_interval_and_penalties_on_the_feed_scale() {
+    assert!(DECAY.is_valid());
+    assert!(!Decay {
+        interval_slots: 0,
+        ..DECAY
+    }
+    .is_valid());
+    assert!(!Decay {
+        max_penalty: MAX_SCORE + 1,
+        ..DECAY
+    }
+    .is_valid());
+    assert_eq!(Decay::default().penalty_at(u64::MAX), 0);
+}
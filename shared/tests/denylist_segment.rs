@@ -0,0 +1,73 @@
+//! Packed denylist segments: the segment an address maps to, and lookups in
+//! the packed bytes against the sorted entries they are written from.
+
+use risk_oracle_shared::denylist_segment::{self, SEGMENT_COUNT};
+use sha2::{Digest, Sha256};
+
+fn sha256(slices: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for slice in slices {
+        hasher.update(slice);
+    }
+    hasher.finalize().into()
+}
+
+fn address(seed: u16) -> [u8; 32] {
+    sha256(&[b"address", &seed.to_le_bytes()])
+}
+
+fn packed(entries: &[[u8; 32]]) -> Vec<u8> {
+    entries.concat()
+}
+
+#[test]
+fn segments_stay_in_range_and_use_all_of_it() {
+    let mut seen = vec![false; SEGMENT_COUNT as usize];
+    for seed in 0..u16::MAX {
+        let segment = denylist_segment::segment_of(sha256, &address(seed));
+        assert!(segment < SEGMENT_COUNT);
+        seen[segment as usize] = true;
+    }
+    assert!(seen.iter().filter(|&&seen| seen).count() > SEGMENT_COUNT as usize * 9 / 10);
+}
+
+#[test]
+fn insert_keeps_entries_sorted_and_unique() {
+    let mut entries = Vec::new();
+    for seed in (0..64).rev() {
+        assert!(denylist_segment::insert(&mut entries, address(seed)));
+    }
+    assert!(!denylist_segment::insert(&mut entries, address(7)));
+    assert_eq!(entries.len(), 64);
+    assert!(entries.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn contains_finds_listed_addresses_only() {
+    let mut entries = Vec::new();
+    for seed in (0..100).step_by(2) {
+        denylist_segment::insert(&mut entries, address(seed));
+    }
+    let bytes = packed(&entries);
+    for seed in 0..100 {
+        assert_eq!(
+            denylist_segment::contains(&bytes, &address(seed)),
+            seed % 2 == 0,
+            "address {}",
+            seed
+        );
+    }
+    assert!(!denylist_segment::contains(&[], &address(0)));
+}
+
+#[test]
+fn remove_unlists_the_address() {
+    let mut entries = vec![];
+    for seed in 0..8 {
+        denylist_segment::insert(&mut entries, address(seed));
+    }
+    assert!(denylist_segment::remove(&mut entries, &address(3)));
+    assert!(!denylist_segment::remove(&mut entries, &address(3)));
+    assert!(!denylist_segment::contains(&packed(&entries), &address(3)));
+    assert_eq!(entries.len(), 7);
+}
@@ -0,0 +1,90 @@
+//! Self-describing state encoding: entries decode as written, values of
+//! unknown kinds pass through, and malformed descriptions are rejected.
+
+use risk_oracle_shared::describe::{self, DescribeError, Entry, Value, Writer};
+
+const AUTHORITY: [u8; 32] = [0xa3; 32];
+
+#[test]
+fn entries_decode_as_written() {
+    let data = Writer::new()
+        .pubkey("authority", &AUTHORITY)
+        .unsigned("risk_threshold", 70)
+        .unsigned("fee_lamports", 5_000_000_000)
+        .unsigned("ramp_end_slot", 0)
+        .bool("flags.denylist", true)
+        .text("profile", "devnet")
+        .finish();
+    assert_eq!(
+        describe::entries(&data).unwrap(),
+        [
+            Entry {
+                name: "authority",
+                value: Value::Pubkey(&AUTHORITY),
+            },
+            Entry {
+                name: "risk_threshold",
+                value: Value::Unsigned(70),
+            },
+            Entry {
+                name: "fee_lamports",
+                value: Value::Unsigned(5_000_000_000),
+            },
+            Entry {
+                name: "ramp_end_slot",
+                value: Value::Unsigned(0),
+            },
+            Entry {
+                name: "flags.denylist",
+                value: Value::Bool(true),
+            },
+            Entry {
+                name: "profile",
+                value: Value::Text("devnet"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn unsigned_values_take_the_fewest_bytes() {
+    let len = |value| Writer::new().unsigned("n", value).finish().len() - 5;
+    assert_eq!(len(0), 1);
+    assert_eq!(len(255), 1);
+    assert_eq!(len(256), 2);
+    assert_eq!(len(u64::MAX), 8);
+}
+
+#[test]
+fn unknown_kinds_pass_through() {
+    let data = [describe::VERSION, 1, b'x', 0x7f, 2, 0xde, 0xad];
+    assert_eq!(
+        describe::entries(&data).unwrap(),
+        [Entry {
+            name: "x",
+            value: Value::Unknown {
+                kind: 0x7f,
+                bytes: &[0xde, 0xad],
+            },
+        }]
+    );
+}
+
+#[test]
+fn malformed_descriptions_are_rejected() {
+    let data = Writer::new().unsigned("risk_threshold", 70).finish();
+    assert_eq!(describe::entries(&[]), Err(DescribeError::Truncated));
+    assert_eq!(
+        describe::entries(&data[..data.len() - 1]),
+        Err(DescribeError::Truncated)
+    );
+    assert_eq!(
+        describe::entries(&[2, 0]),
+        Err(DescribeError::UnsupportedVersion(2))
+    );
+    let bool_of_two = [describe::VERSION, 1, b'b', describe::kind::BOOL, 1, 2];
+    assert_eq!(
+        describe::entries(&bool_of_two),
+        Err(DescribeError::InvalidEntry)
+    );
+}
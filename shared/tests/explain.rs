@@ -0,0 +1,35 @@
+//! `explain` tables against the error tables.
+
+use risk_oracle_shared::{errors, explain};
+
+#[test]
+fn every_error_is_explained() {
+    assert_eq!(explain::ANCHOR.len(), errors::ANCHOR.len());
+    assert_eq!(explain::PINOCCHIO.len(), errors::PINOCCHIO.len());
+    for error in errors::ANCHOR.iter().chain(errors::PINOCCHIO) {
+        let explanation = explain::explain(error.code).unwrap();
+        assert!(!explanation.causes.is_empty(), "{}", error.name);
+        assert!(
+            explanation.next.starts_with("risk-oracle-cli "),
+            "{}",
+            error.name
+        );
+    }
+}
+
+#[test]
+fn codes_pick_the_program_table() {
+    assert_eq!(explain::explain(1), Some(&explain::PINOCCHIO[1]));
+    assert_eq!(explain::explain(0x1771), Some(&explain::ANCHOR[1]));
+    assert_eq!(explain::explain(0x1771).unwrap().module, "verify");
+}
+
+#[test]
+fn unknown_codes_have_no_explanation() {
+    assert_eq!(explain::explain(errors::PINOCCHIO.len() as u32), None);
+    assert_eq!(explain::explain(5999), None);
+    assert_eq!(
+        explain::explain(errors::ANCHOR_ERROR_OFFSET + errors::ANCHOR.len() as u32),
+        None
+    );
+}
@@ -0,0 +1,83 @@
+//! Recorded encodings of the feed templates.
+//!
+//! Feed ids are hashes of these bytes, so a failure here means every feed id
+//! built from the changed template moved: an upgrade of the vendored protos or
+//! of `prost` changed the wire format, or a template changed. Templates are
+//! only meant to change with a new `feed::schema` tag and re-recorded fixtures.
+//!
+//! The fixtures are hex, 32 bytes per line, of `feed::encode` without a
+//! `RISK_ORACLE_FEED_DOMAIN`; the tests are skipped when one is set.
+
+use risk_oracle_shared::feed::{self, NetworkSet};
+use risk_oracle_shared::proto::OracleFeed;
+
+/// Queried address of the recorded feeds.
+const ADDRESS: [u8; 32] = [0x11; 32];
+
+fn decode_hex(hex: &str) -> Vec<u8> {
+    let digits: Vec<u8> = hex.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let pair = core::str::from_utf8(pair).expect("ascii");
+            u8::from_str_radix(pair, 16).expect("hex fixture")
+        })
+        .collect()
+}
+
+fn assert_recorded(name: &str, feed: &OracleFeed, recorded: &str) {
+    if feed::FEED_DOMAIN.is_some_and(|domain| !domain.is_empty()) {
+        return;
+    }
+    assert_eq!(
+        feed::encode(feed),
+        decode_hex(recorded),
+        "encoding of `{}` changed",
+        name
+    );
+}
+
+#[test]
+fn test_feed() {
+    assert_recorded(
+        "test_feed",
+        &feed::test_feed(),
+        include_str!("fixtures/test_feed.hex"),
+    );
+}
+
+#[test]
+fn risk_score_feed() {
+    assert_recorded(
+        "risk_score_feed",
+        &feed::risk_score_feed(&ADDRESS),
+        include_str!("fixtures/risk_score_feed.hex"),
+    );
+}
+
+#[test]
+fn composite_risk_score_feed() {
+    let networks = NetworkSet::from_bits(NetworkSet::SOLANA.bits() | NetworkSet::ECLIPSE.bits())
+        .expect("known networks");
+    assert_recorded(
+        "composite_risk_score_feed",
+        &feed::composite_risk_score_feed(&ADDRESS, networks),
+        include_str!("fixtures/composite_risk_score_feed.hex"),
+    );
+}
+
+#[test]
+fn pass_fail_feed() {
+    assert_recorded(
+        "pass_fail_feed",
+        &feed::pass_fail_feed(&ADDRESS, 50),
+        include_str!("fixtures/pass_fail_feed.hex"),
+    );
+}
+
+#[test]
+fn score_and_flag_feeds() {
+    let [score, flag] = feed::score_and_flag_feeds(&ADDRESS);
+    assert_recorded("score_feed", &score, include_str!("fixtures/score_feed.hex"));
+    assert_recorded("flag_feed", &flag, include_str!("fixtures/flag_feed.hex"));
+}
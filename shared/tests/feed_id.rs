@@ -0,0 +1,102 @@
+//! The two feed id derivations of every template, pinned in
+//! `fixtures/feed_ids.txt`.
+//!
+//! The fixtures hold this crate's own ids of the recorded encodings, not ids
+//! recorded from the gateway: they catch a change to either derivation, not a
+//! mismatch with the gateway, which only a live gateway can tell (`cargo
+//! xtask smoke`). Like the encoding fixtures, the tests are skipped when a
+//! `RISK_ORACLE_FEED_DOMAIN` is set.
+
+use risk_oracle_shared::feed::{self, NetworkSet};
+use risk_oracle_shared::feed_id::FeedIdDerivation;
+use risk_oracle_shared::proto::OracleFeed;
+use sha2::{Digest, Sha256};
+
+/// Queried address of the recorded feeds.
+const ADDRESS: [u8; 32] = [0x11; 32];
+
+fn sha256(slices: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for slice in slices {
+        hasher.update(slice);
+    }
+    hasher.finalize().into()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// The templates of the fixtures, by name.
+fn templates() -> Vec<(&'static str, OracleFeed)> {
+    let networks = NetworkSet::from_bits(NetworkSet::SOLANA.bits() | NetworkSet::ECLIPSE.bits())
+        .expect("known networks");
+    let [score, flag] = feed::score_and_flag_feeds(&ADDRESS);
+    vec![
+        ("test_feed", feed::test_feed()),
+        ("risk_score_feed", feed::risk_score_feed(&ADDRESS)),
+        (
+            "composite_risk_score_feed",
+            feed::composite_risk_score_feed(&ADDRESS, networks),
+        ),
+        ("pass_fail_feed", feed::pass_fail_feed(&ADDRESS, 50)),
+        ("score_feed", score),
+        ("flag_feed", flag),
+    ]
+}
+
+/// Recorded id of `template` under `derivation`.
+fn recorded(template: &str, derivation: FeedIdDerivation) -> String {
+    include_str!("fixtures/feed_ids.txt")
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .find(|fields| fields[0] == template && fields[1] == derivation.as_str())
+        .map(|fields| fields[2].to_string())
+        .unwrap_or_else(|| panic!("no {} id recorded for `{}`", derivation.as_str(), template))
+}
+
+fn domain_set() -> bool {
+    feed::FEED_DOMAIN.is_some_and(|domain| !domain.is_empty())
+}
+
+#[test]
+fn both_derivations_match_the_recorded_ids() {
+    if domain_set() {
+        return;
+    }
+    for (template, feed) in templates() {
+        for derivation in FeedIdDerivation::ALL {
+            assert_eq!(
+                hex(&derivation.feed_id(sha256, &feed)),
+                recorded(template, derivation),
+                "{} id of `{}` changed",
+                derivation.as_str(),
+                template
+            );
+        }
+    }
+}
+
+#[test]
+fn the_v2_preimage_is_the_encoding_without_its_length_prefix() {
+    for (template, feed) in templates() {
+        let delimited = FeedIdDerivation::LengthDelimited.preimage(&feed);
+        let bare = FeedIdDerivation::FeedRequestV2.preimage(&feed);
+        assert_eq!(delimited, feed::encode(&feed), "{}", template);
+        assert!(delimited.ends_with(&bare), "{}", template);
+        assert_ne!(delimited.len(), bare.len(), "{}", template);
+    }
+}
+
+#[test]
+fn the_derivations_never_agree() {
+    for (template, feed) in templates() {
+        assert_ne!(
+            FeedIdDerivation::LengthDelimited.feed_id(sha256, &feed),
+            FeedIdDerivation::FeedRequestV2.feed_id(sha256, &feed),
+            "{}",
+            template
+        );
+    }
+}
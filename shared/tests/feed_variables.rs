@@ -0,0 +1,83 @@
+//! `feed_variables` on the encoded templates.
+
+use risk_oracle_shared::feed::{self, schema, NetworkSet};
+use risk_oracle_shared::feed_variables::{
+    first_undeclared, is_variable_name, placeholders, template_variables,
+};
+use risk_oracle_shared::proto::OracleFeed;
+
+const ADDRESS: [u8; 32] = [0x11; 32];
+
+/// Distinct placeholder names of `feed`, in order of first appearance.
+fn names(feed: &OracleFeed) -> Vec<String> {
+    let encoded = feed::encode(feed);
+    let mut names: Vec<String> = Vec::new();
+    for name in placeholders(&encoded) {
+        let name = String::from_utf8(name.to_vec()).unwrap();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+#[test]
+fn templates_use_the_variables_they_declare() {
+    let [score, flag] = feed::score_and_flag_feeds(&ADDRESS);
+    let [as_of_score, as_of] = feed::score_and_as_of_feeds(&ADDRESS);
+    let templates = [
+        (schema::RISK_SCORE_V1, feed::risk_score_feed(&ADDRESS)),
+        (
+            schema::COMPOSITE_RISK_SCORE_V1,
+            feed::composite_risk_score_feed(&ADDRESS, NetworkSet::SOLANA),
+        ),
+        (schema::PASS_FAIL_V1, feed::pass_fail_feed(&ADDRESS, 50)),
+        (schema::SCORE_AND_FLAG_V1, score),
+        (schema::SCORE_AND_FLAG_V1, flag),
+        (schema::SCORE_AND_AS_OF_V1, as_of_score),
+        (schema::SCORE_AND_AS_OF_V1, as_of),
+    ];
+    for (schema, template) in templates {
+        let declared = template_variables(schema);
+        for name in names(&template) {
+            assert!(declared.contains(&name.as_str()), "{} in {}", name, schema);
+        }
+    }
+    assert_eq!(names(&feed::risk_score_feed(&ADDRESS)), ["RANGE_API_KEY"]);
+}
+
+#[test]
+fn a_misspelled_placeholder_is_undeclared() {
+    let encoded = feed::encode(&feed::risk_score_feed(&ADDRESS));
+    let at = encoded
+        .windows(13)
+        .position(|window| window == b"RANGE_API_KEY")
+        .unwrap();
+    let mut misspelled = encoded.clone();
+    misspelled[at..at + 13].copy_from_slice(b"RANGE_API_KYE");
+    let declared = template_variables(schema::RISK_SCORE_V1);
+    let is_declared = |name: &[u8]| declared.iter().any(|d| d.as_bytes() == name);
+
+    assert_eq!(first_undeclared(&encoded, is_declared), None);
+    assert_eq!(
+        first_undeclared(&misspelled, is_declared),
+        Some(&b"RANGE_API_KYE"[..])
+    );
+}
+
+#[test]
+fn an_unterminated_placeholder_yields_the_rest() {
+    assert_eq!(
+        placeholders(b"a${KEY}b${REST").collect::<Vec<_>>(),
+        [&b"KEY"[..], &b"REST"[..]]
+    );
+}
+
+#[test]
+fn variable_names_are_upper_case_identifiers() {
+    assert!(is_variable_name(b"RANGE_API_KEY"));
+    assert!(!is_variable_name(b""));
+    assert!(!is_variable_name(b"range_api_key"));
+    assert!(!is_variable_name(b"1KEY"));
+    assert!(!is_variable_name(&[b'A'; 33]));
+}
@@ -0,0 +1,31 @@
+//! The off-chain backend of `hash` against the FIPS 180-2 test vectors, so
+//! the helper both sides derive feed ids with stays plain SHA-256.
+
+use risk_oracle_shared::hash::{self, Sha2, Sha256Backend};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+#[test]
+fn hash_matches_the_test_vectors() {
+    assert_eq!(hex(&hash::hash(b"abc")), ABC);
+    assert_eq!(
+        hex(&hash::hash(b"")),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+}
+
+#[test]
+fn hashv_hashes_the_concatenation() {
+    assert_eq!(hex(&hash::hashv(&[b"a", b"", b"bc"])), ABC);
+    assert_eq!(hash::hashv(&[]), hash::hash(b""));
+}
+
+#[test]
+fn hashv_is_the_native_backend() {
+    let parts: &[&[u8]] = &[b"risk", b"oracle"];
+    assert_eq!(hash::hashv(parts), Sha2::hashv(parts));
+}
@@ -0,0 +1,75 @@
+//! Heap allocated deriving the feed id of each Pinocchio verification mode,
+//! held under `heap::FEED_BUDGET`.
+//!
+//! The program's bump allocator never reuses memory, so every byte a mode
+//! allocates building and encoding its template counts against the 32 KiB
+//! heap, temporaries included. The allocator below counts the same way: the
+//! bytes handed out, frees ignored. Build the program with `heap-log` for the
+//! on-chain figure of a whole verification.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use risk_oracle_shared::feed;
+use risk_oracle_shared::heap;
+
+/// [`System`], counting the bytes allocated by the current thread (tests run
+/// in parallel).
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.with(|allocated| allocated.set(allocated.get() + layout.size()));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const ADDRESS: [u8; 32] = [0x11; 32];
+
+/// Bytes allocated by `derive`, with what it returns dropped inside the
+/// measurement.
+fn allocated<T>(derive: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATED.with(Cell::get);
+    drop(derive());
+    ALLOCATED.with(Cell::get) - before
+}
+
+fn assert_within_budget(mode: &str, used: usize) {
+    println!("{:<10} {:>6} bytes", mode, used);
+    assert!(
+        used <= heap::FEED_BUDGET,
+        "`{}` allocates {} bytes deriving its feed id, over the budget of {}",
+        mode,
+        used,
+        heap::FEED_BUDGET
+    );
+}
+
+#[test]
+fn classify_mode_stays_within_budget() {
+    let used = allocated(|| feed::encode(&feed::risk_score_feed(&ADDRESS)));
+    assert_within_budget("classify", used);
+}
+
+#[test]
+fn pass_fail_mode_stays_within_budget() {
+    let used = allocated(|| feed::encode(&feed::pass_fail_feed(&ADDRESS, 50)));
+    assert_within_budget("pass-fail", used);
+}
+
+#[test]
+fn test_feed_mode_stays_within_budget() {
+    let used = allocated(|| feed::encode(&feed::test_feed()));
+    assert_within_budget("test-feed", used);
+}
@@ -0,0 +1,85 @@
+//! The account layouts indexers decode with: Anchor's discriminators, offsets
+//! packed after them, and the README tables generated from them. The Anchor
+//! program's `tests/layout.rs` checks them against the program's encoding.
+
+use std::collections::HashSet;
+
+use risk_oracle_shared::layout::{self, FieldType, DISCRIMINATOR_LEN};
+use sha2::{Digest, Sha256};
+
+#[test]
+fn discriminators_are_anchors() {
+    for account in layout::ALL {
+        let hash = Sha256::digest(format!("account:{}", account.name));
+        assert_eq!(account.discriminator, hash[..8], "{}", account.name);
+    }
+}
+
+#[test]
+fn fields_are_packed_after_the_discriminator() {
+    for account in layout::ALL {
+        let mut offset = DISCRIMINATOR_LEN;
+        for field in account.fields {
+            assert_eq!(field.offset, offset, "{}.{}", account.name, field.name);
+            offset += field.ty.size();
+        }
+        assert_eq!(account.size(), offset, "{}", account.name);
+    }
+}
+
+#[test]
+fn field_names_are_unique() {
+    for account in layout::ALL {
+        let mut names = HashSet::new();
+        for field in account.fields {
+            assert!(names.insert(field.name), "{}.{}", account.name, field.name);
+        }
+    }
+}
+
+#[test]
+fn known_sizes() {
+    assert_eq!(layout::CONFIG.size(), 116);
+    assert_eq!(layout::REPLAY_RECORD.size(), 16);
+    assert_eq!(layout::SCORE_CACHE.size(), 136);
+    assert_eq!(layout::FEED_ID_RECORD.size(), 81);
+    assert_eq!(layout::QUOTE_MARKER.size(), 146);
+    assert_eq!(layout::VERIFICATION_RECEIPT.size(), 162);
+}
+
+#[test]
+fn identify_by_discriminator() {
+    for account in layout::ALL {
+        let mut data = vec![0; account.size()];
+        data[..DISCRIMINATOR_LEN].copy_from_slice(&account.discriminator);
+        assert_eq!(layout::identify(&data), Some(account));
+    }
+    assert_eq!(layout::identify(&[0; 16]), None);
+    assert_eq!(layout::identify(&layout::CONFIG.discriminator[..4]), None);
+}
+
+#[test]
+fn read_takes_the_bytes_of_a_field() {
+    let mut data = vec![0; layout::SCORE_CACHE.size()];
+    data[40] = 72;
+    data[95..103].copy_from_slice(&1_000u64.to_le_bytes());
+    let score = layout::SCORE_CACHE.field("score").unwrap();
+    assert_eq!(score.read(&data), Some(&[72][..]));
+    let quote_slot = layout::SCORE_CACHE.field("provenance.quote_slot").unwrap();
+    assert_eq!(quote_slot.ty, FieldType::U64);
+    assert_eq!(quote_slot.read(&data), Some(&1_000u64.to_le_bytes()[..]));
+    assert_eq!(quote_slot.read(&data[..100]), None);
+}
+
+#[test]
+fn readme_tables_are_generated() {
+    let readme = include_str!("../../README.md");
+    let tables = format!(
+        "<!-- layout:start -->\n{}<!-- layout:end -->",
+        layout::markdown()
+    );
+    assert!(
+        readme.contains(&tables),
+        "README layout tables are stale, run `cargo xtask layout`"
+    );
+}
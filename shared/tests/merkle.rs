@@ -0,0 +1,211 @@
+//! The merkle denylist proofs: every listed key proves membership, unlisted
+//! keys prove absence through their neighbours at both edges and in between,
+//! and forged, reordered or truncated proofs fail.
+
+use risk_oracle_shared::hash::hashv;
+use risk_oracle_shared::merkle::{self, Leaf, Tree, EMPTY};
+
+fn key(byte: u8) -> [u8; 32] {
+    [byte; 32]
+}
+
+/// 10, 20, 30, 40, 50: five leaves padded to eight.
+fn tree() -> Tree {
+    Tree::new(hashv, [50, 10, 40, 20, 30].map(key).to_vec())
+}
+
+fn leaf_at<'a>(tree: &Tree, index: u32, siblings: &'a [[u8; 32]]) -> Leaf<'a> {
+    Leaf {
+        key: tree.keys()[index as usize],
+        index,
+        siblings,
+    }
+}
+
+fn verify(tree: &Tree, key: [u8; 32], index: u32, siblings: &[[u8; 32]]) -> bool {
+    Leaf {
+        key,
+        index,
+        siblings,
+    }
+    .verify(hashv, &tree.root(), tree.leaf_count())
+}
+
+/// Absence of `key`, proven by the leaves at `lower` and `upper`.
+fn non_member(tree: &Tree, key: [u8; 32], lower: Option<u32>, upper: Option<u32>) -> bool {
+    let lower_siblings = lower.map(|index| tree.proof(index)).unwrap_or_default();
+    let upper_siblings = upper.map(|index| tree.proof(index)).unwrap_or_default();
+    merkle::verify_non_member(
+        hashv,
+        &tree.root(),
+        tree.leaf_count(),
+        &key,
+        lower.map(|index| leaf_at(tree, index, &lower_siblings)),
+        upper.map(|index| leaf_at(tree, index, &upper_siblings)),
+    )
+}
+
+#[test]
+fn every_key_proves_membership() {
+    for count in 1..=9u8 {
+        let tree = Tree::new(hashv, (1..=count).map(key).collect());
+        assert_eq!(tree.leaf_count(), count as u32);
+        for index in 0..tree.leaf_count() {
+            let siblings = tree.proof(index);
+            assert_eq!(siblings.len(), merkle::depth(tree.leaf_count()));
+            assert!(verify(&tree, tree.keys()[index as usize], index, &siblings));
+        }
+    }
+}
+
+#[test]
+fn keys_are_sorted_and_deduplicated() {
+    let tree = Tree::new(hashv, [30, 10, 30, 20, 10].map(key).to_vec());
+    assert_eq!(tree.keys(), &[10, 20, 30].map(key));
+    assert_eq!(
+        tree.root(),
+        Tree::new(hashv, [10, 20, 30].map(key).to_vec()).root()
+    );
+    assert_eq!(tree.search(&key(20)), Ok(1));
+    assert_eq!(tree.search(&key(25)), Err(2));
+}
+
+#[test]
+fn member_proof_of_another_key_fails() {
+    let tree = tree();
+    let siblings = tree.proof(1);
+    assert!(!verify(&tree, key(25), 1, &siblings));
+    assert!(!verify(&tree, key(10), 1, &siblings));
+}
+
+#[test]
+fn absent_below_the_first_leaf() {
+    let tree = tree();
+    assert!(non_member(&tree, key(5), None, Some(0)));
+    assert!(!non_member(&tree, key(5), None, Some(1)));
+    // The first leaf is not below the key.
+    assert!(!non_member(&tree, key(10), None, Some(0)));
+}
+
+#[test]
+fn absent_above_the_last_leaf() {
+    let tree = tree();
+    assert!(non_member(&tree, key(55), Some(4), None));
+    assert!(!non_member(&tree, key(55), Some(3), None));
+    assert!(!non_member(&tree, key(50), Some(4), None));
+}
+
+#[test]
+fn absent_between_neighbours() {
+    let tree = tree();
+    for (absent, lower) in [(15, 0), (25, 1), (35, 2), (45, 3)] {
+        assert!(non_member(&tree, key(absent), Some(lower), Some(lower + 1)));
+    }
+    // Not adjacent: 20 sits between them.
+    assert!(!non_member(&tree, key(25), Some(0), Some(2)));
+    // Adjacent, but not around the key.
+    assert!(!non_member(&tree, key(25), Some(2), Some(3)));
+    // A listed key has no neighbours around it.
+    assert!(!non_member(&tree, key(20), Some(0), Some(1)));
+    // An edge proof is not enough inside the tree.
+    assert!(!non_member(&tree, key(25), None, Some(2)));
+    assert!(!non_member(&tree, key(25), Some(1), None));
+}
+
+#[test]
+fn empty_tree() {
+    let empty = Tree::new(hashv, Vec::new());
+    assert_eq!(empty.root(), EMPTY);
+    assert_eq!(empty.leaf_count(), 0);
+    assert_eq!(merkle::depth(0), 0);
+    assert!(non_member(&empty, key(1), None, None));
+    // No index is within a tree of no leaves, padding included.
+    assert!(!verify(&empty, key(1), 0, &[]));
+    assert_eq!(merkle::root_from_proof(hashv, &key(1), 0, 0, &[]), None);
+    // Nothing proves an empty answer against a non-empty tree.
+    assert!(!non_member(&tree(), key(1), None, None));
+}
+
+#[test]
+fn single_leaf_tree() {
+    let tree = Tree::new(hashv, vec![key(7)]);
+    assert_eq!(merkle::depth(1), 0);
+    assert!(tree.proof(0).is_empty());
+    assert_eq!(tree.root(), merkle::leaf_hash(hashv, &key(7)));
+    assert!(verify(&tree, key(7), 0, &[]));
+    assert!(!verify(&tree, key(8), 0, &[]));
+    assert!(non_member(&tree, key(6), None, Some(0)));
+    assert!(non_member(&tree, key(8), Some(0), None));
+    assert!(!non_member(&tree, key(8), None, None));
+}
+
+#[test]
+fn forged_sibling_fails() {
+    let tree = tree();
+    for index in 0..tree.leaf_count() {
+        let siblings = tree.proof(index);
+        for level in 0..siblings.len() {
+            let mut forged = siblings.clone();
+            forged[level][0] ^= 1;
+            assert!(!verify(&tree, tree.keys()[index as usize], index, &forged));
+        }
+    }
+}
+
+#[test]
+fn proof_length_must_match_the_depth() {
+    let tree = tree();
+    let siblings = tree.proof(2);
+    assert!(!verify(&tree, key(30), 2, &siblings[..siblings.len() - 1]));
+    let mut longer = siblings.clone();
+    longer.push(EMPTY);
+    assert!(!verify(&tree, key(30), 2, &longer));
+}
+
+#[test]
+fn swapped_order_fails() {
+    let tree = tree();
+    // The index fixes left and right at each level: the same siblings under
+    // the neighbouring index hash in the other order.
+    let siblings = tree.proof(2);
+    assert!(verify(&tree, key(30), 2, &siblings));
+    assert!(!verify(&tree, key(30), 3, &siblings));
+    // Reversed levels.
+    let mut reversed = siblings.clone();
+    reversed.reverse();
+    assert!(!verify(&tree, key(30), 2, &reversed));
+    // Neighbours given the other way around.
+    assert!(!non_member(&tree, key(25), Some(2), Some(1)));
+}
+
+#[test]
+fn padding_is_not_a_leaf() {
+    let tree = tree();
+    // Index 5 is an `EMPTY` pad of the five-leaf tree, past `leaf_count`.
+    let siblings = tree.proof(5);
+    assert!(!verify(&tree, EMPTY, 5, &siblings));
+    assert_ne!(merkle::leaf_hash(hashv, &EMPTY), EMPTY);
+}
+
+#[test]
+fn inner_node_is_not_a_leaf() {
+    // Four leaves: root = node(node(l0, l1), node(l2, l3)).
+    let keys = [10, 20, 30, 40].map(key);
+    let tree = Tree::new(hashv, keys.to_vec());
+    let leaves = keys.map(|key| merkle::leaf_hash(hashv, &key));
+    let left = merkle::node_hash(hashv, &leaves[0], &leaves[1]);
+    let right = merkle::node_hash(hashv, &leaves[2], &leaves[3]);
+    assert_eq!(tree.root(), merkle::node_hash(hashv, &left, &right));
+
+    // The inner node passed off as a key of a two-leaf tree: hashed as a
+    // leaf, it no longer reaches the root.
+    let inner = Leaf {
+        key: left,
+        index: 0,
+        siblings: &[right],
+    };
+    assert!(!inner.verify(hashv, &tree.root(), 2));
+    assert!(!inner.verify(hashv, &tree.root(), tree.leaf_count()));
+    // Without the leaf prefix it would hash to `hashv(left)`.
+    assert_ne!(merkle::leaf_hash(hashv, &left), hashv(&[&left[..]]));
+}
@@ -0,0 +1,65 @@
+//! `program_screen::screen_programs` on raw Instructions sysvars.
+
+use risk_oracle_shared::program_screen::{screen_programs, ProgramScreenError};
+
+const ED25519_PROGRAM_ID: [u8; 32] = [0x03; 32];
+const ORACLE_PROGRAM_ID: [u8; 32] = [0x07; 32];
+const MIXER_PROGRAM_ID: [u8; 32] = [0x6d; 32];
+
+/// Instructions sysvar data for instructions of `programs`, each without
+/// accounts and with a byte of data.
+fn sysvar(programs: &[[u8; 32]]) -> Vec<u8> {
+    let mut data = (programs.len() as u16).to_le_bytes().to_vec();
+    let mut offset = 2 + programs.len() * 2;
+    for _ in programs {
+        data.extend_from_slice(&(offset as u16).to_le_bytes());
+        offset += 2 + 32 + 2 + 1;
+    }
+    for program_id in programs {
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(program_id);
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.push(0);
+    }
+    data
+}
+
+fn is_mixer(program_id: &[u8; 32]) -> bool {
+    *program_id == MIXER_PROGRAM_ID
+}
+
+#[test]
+fn transactions_without_denied_programs_pass() {
+    let data = sysvar(&[ED25519_PROGRAM_ID, ORACLE_PROGRAM_ID]);
+    assert_eq!(screen_programs(&data, is_mixer), Ok(()));
+}
+
+#[test]
+fn a_denied_program_anywhere_fails() {
+    for index in 0..3 {
+        let mut programs = vec![ED25519_PROGRAM_ID, ORACLE_PROGRAM_ID];
+        programs.insert(index, MIXER_PROGRAM_ID);
+        assert_eq!(
+            screen_programs(&sysvar(&programs), is_mixer),
+            Err(ProgramScreenError::Denied { index, program_id: MIXER_PROGRAM_ID })
+        );
+    }
+}
+
+#[test]
+fn the_first_denied_instruction_is_reported() {
+    let data = sysvar(&[ED25519_PROGRAM_ID, MIXER_PROGRAM_ID, ORACLE_PROGRAM_ID]);
+    let denied = |program_id: &[u8; 32]| *program_id != ED25519_PROGRAM_ID;
+    assert_eq!(
+        screen_programs(&data, denied),
+        Err(ProgramScreenError::Denied { index: 1, program_id: MIXER_PROGRAM_ID })
+    );
+}
+
+#[test]
+fn unreadable_sysvars_fail() {
+    let mut data = sysvar(&[ED25519_PROGRAM_ID, ORACLE_PROGRAM_ID]);
+    data.truncate(data.len() - 10);
+    assert_eq!(screen_programs(&data, is_mixer), Err(ProgramScreenError::Malformed));
+    assert_eq!(screen_programs(&[], is_mixer), Err(ProgramScreenError::Malformed));
+}
@@ -0,0 +1,97 @@
+//! `query_account::classify` on the accounts integrators pass as
+//! `query_account`.
+
+use risk_oracle_shared::query_account::{
+    classify, QueryAccountKind, ASSOCIATED_TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID,
+    TOKEN_2022_PROGRAM_ID, TOKEN_ACCOUNT_LEN, TOKEN_PROGRAM_ID,
+};
+
+const MINT: [u8; 32] = [0x4d; 32];
+const WALLET: [u8; 32] = [0xa3; 32];
+const OTHER_PROGRAM_ID: [u8; 32] = [0x07; 32];
+
+/// Token account data of `WALLET`, `extensions` bytes longer than the base
+/// layout.
+fn token_account(extensions: usize) -> Vec<u8> {
+    let mut data = vec![0; TOKEN_ACCOUNT_LEN + extensions];
+    data[..32].copy_from_slice(&MINT);
+    data[32..64].copy_from_slice(&WALLET);
+    data
+}
+
+fn id(base58: &str) -> [u8; 32] {
+    bs58::decode(base58).into_vec().unwrap().try_into().unwrap()
+}
+
+#[test]
+fn program_ids_decode() {
+    assert_eq!(SYSTEM_PROGRAM_ID, id("11111111111111111111111111111111"));
+    assert_eq!(
+        TOKEN_PROGRAM_ID,
+        id("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")
+    );
+    assert_eq!(
+        TOKEN_2022_PROGRAM_ID,
+        id("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb")
+    );
+    assert_eq!(
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        id("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")
+    );
+}
+
+#[test]
+fn wallets_are_screened_as_is() {
+    assert_eq!(
+        classify(&SYSTEM_PROGRAM_ID, false, &[]),
+        QueryAccountKind::Wallet
+    );
+}
+
+#[test]
+fn spl_token_accounts_resolve_to_their_owner() {
+    assert_eq!(
+        classify(&TOKEN_PROGRAM_ID, false, &token_account(0)),
+        QueryAccountKind::TokenAccount { owner: WALLET }
+    );
+    // Mints and multisigs have other sizes.
+    assert_eq!(
+        classify(&TOKEN_PROGRAM_ID, false, &[0; 82]),
+        QueryAccountKind::ProgramOwned
+    );
+}
+
+#[test]
+fn token_2022_accounts_resolve_to_their_owner() {
+    assert_eq!(
+        classify(&TOKEN_2022_PROGRAM_ID, false, &token_account(0)),
+        QueryAccountKind::TokenAccount { owner: WALLET }
+    );
+    let mut data = token_account(8);
+    data[TOKEN_ACCOUNT_LEN] = 2;
+    assert_eq!(
+        classify(&TOKEN_2022_PROGRAM_ID, false, &data),
+        QueryAccountKind::TokenAccount { owner: WALLET }
+    );
+    // A mint with extensions is padded to the same length but marked 1.
+    data[TOKEN_ACCOUNT_LEN] = 1;
+    assert_eq!(
+        classify(&TOKEN_2022_PROGRAM_ID, false, &data),
+        QueryAccountKind::ProgramOwned
+    );
+}
+
+#[test]
+fn other_data_accounts_are_rejected() {
+    assert_eq!(
+        classify(&OTHER_PROGRAM_ID, false, &token_account(0)),
+        QueryAccountKind::ProgramOwned
+    );
+}
+
+#[test]
+fn programs_are_rejected() {
+    for owner in [SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID, OTHER_PROGRAM_ID] {
+        assert_eq!(classify(&owner, true, &[]), QueryAccountKind::Program);
+    }
+}
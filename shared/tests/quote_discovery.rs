@@ -0,0 +1,69 @@
+//! `quote_limits::discover_quote_index` on raw Instructions sysvars.
+
+use risk_oracle_shared::quote_limits::{self, COMPUTE_BUDGET_PROGRAM_ID};
+
+const ED25519_PROGRAM_ID: [u8; 32] = [0x03; 32];
+const ORACLE_PROGRAM_ID: [u8; 32] = [0x07; 32];
+
+/// Instructions sysvar data for instructions of `programs`, each without
+/// accounts and with a byte of data.
+fn sysvar(programs: &[[u8; 32]]) -> Vec<u8> {
+    let mut data = (programs.len() as u16).to_le_bytes().to_vec();
+    let mut offset = 2 + programs.len() * 2;
+    for _ in programs {
+        data.extend_from_slice(&(offset as u16).to_le_bytes());
+        offset += 2 + 32 + 2 + 1;
+    }
+    for program_id in programs {
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(program_id);
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.push(0);
+    }
+    data
+}
+
+#[test]
+fn quote_first_is_at_index_zero() {
+    let data = sysvar(&[ED25519_PROGRAM_ID, ORACLE_PROGRAM_ID]);
+    assert_eq!(quote_limits::discover_quote_index(&data), 0);
+    assert_eq!(quote_limits::instruction_data_len(&data, 1), Some(1));
+}
+
+#[test]
+fn leading_compute_budget_instructions_are_skipped() {
+    let data = sysvar(&[
+        COMPUTE_BUDGET_PROGRAM_ID,
+        COMPUTE_BUDGET_PROGRAM_ID,
+        ED25519_PROGRAM_ID,
+        ORACLE_PROGRAM_ID,
+    ]);
+    assert_eq!(quote_limits::discover_quote_index(&data), 2);
+}
+
+#[test]
+fn only_leading_compute_budget_instructions_count() {
+    // Another instruction first: the quote checks reject it at index 0.
+    let data = sysvar(&[
+        ORACLE_PROGRAM_ID,
+        COMPUTE_BUDGET_PROGRAM_ID,
+        ED25519_PROGRAM_ID,
+    ]);
+    assert_eq!(quote_limits::discover_quote_index(&data), 0);
+}
+
+#[test]
+fn compute_budget_instructions_are_capped() {
+    let mut programs = [COMPUTE_BUDGET_PROGRAM_ID; 6].to_vec();
+    programs.push(ED25519_PROGRAM_ID);
+    let data = sysvar(&programs);
+    assert_eq!(
+        quote_limits::discover_quote_index(&data),
+        quote_limits::MAX_COMPUTE_BUDGET_INSTRUCTIONS
+    );
+}
+
+#[test]
+fn malformed_sysvar_reads_index_zero() {
+    assert_eq!(quote_limits::discover_quote_index(&[]), 0);
+}
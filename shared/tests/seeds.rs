@@ -0,0 +1,48 @@
+//! PDA seeds: distinct, and passed by the address derivations in the order
+//! and encoding the program uses.
+
+use risk_oracle_shared::seeds;
+use sha2::{Digest, Sha256};
+
+const PROGRAM_ID: [u8; 32] = [0x0b; 32];
+const ADDRESS: [u8; 32] = [0xa3; 32];
+
+/// Stand-in for `find_program_address`: hashes the seeds and the program id,
+/// so equal derivations are exactly those with equal seeds.
+fn find(seeds: &[&[u8]], program_id: &[u8; 32]) -> ([u8; 32], u8) {
+    let mut hasher = Sha256::new();
+    for seed in seeds {
+        hasher.update((seed.len() as u8).to_le_bytes());
+        hasher.update(seed);
+    }
+    hasher.update(program_id);
+    (hasher.finalize().into(), 255)
+}
+
+#[test]
+fn seeds_are_distinct() {
+    for (i, seed) in seeds::ALL.iter().enumerate() {
+        assert!(!seed.is_empty());
+        assert!(!seeds::ALL[i + 1..].contains(seed), "{:?}", seed);
+    }
+}
+
+#[test]
+fn derivations_pass_the_program_seeds() {
+    assert_eq!(
+        seeds::find_score_cache_address(find, &PROGRAM_ID, &ADDRESS),
+        find(&[seeds::CACHE_SEED, &ADDRESS], &PROGRAM_ID)
+    );
+    assert_eq!(
+        seeds::find_denylist_segment_address(find, &PROGRAM_ID, 0x0123),
+        find(&[seeds::DENYLIST_SEGMENT_SEED, &[0x01, 0x23]], &PROGRAM_ID)
+    );
+    assert_eq!(
+        seeds::find_bypass_record_address(find, &PROGRAM_ID, &ADDRESS, 7),
+        find(&[seeds::BYPASS_SEED, &ADDRESS, &7u64.to_le_bytes()], &PROGRAM_ID)
+    );
+    assert_ne!(
+        seeds::find_score_cache_address(find, &PROGRAM_ID, &ADDRESS),
+        seeds::find_score_history_address(find, &PROGRAM_ID, &ADDRESS)
+    );
+}
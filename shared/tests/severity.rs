@@ -0,0 +1,44 @@
+//! `severity` bands and the profiles' values.
+
+use risk_oracle_shared::profiles;
+use risk_oracle_shared::severity::{Severity, SeverityBands};
+
+const BANDS: SeverityBands = SeverityBands {
+    warn: 40,
+    critical: 70,
+};
+
+#[test]
+fn band_edges_belong_to_the_higher_band() {
+    assert_eq!(BANDS.classify(0), Severity::Info);
+    assert_eq!(BANDS.classify(39), Severity::Info);
+    assert_eq!(BANDS.classify(40), Severity::Warn);
+    assert_eq!(BANDS.classify(69), Severity::Warn);
+    assert_eq!(BANDS.classify(70), Severity::Critical);
+    assert_eq!(BANDS.classify(100), Severity::Critical);
+}
+
+#[test]
+fn equal_bands_skip_warn() {
+    let bands = SeverityBands {
+        warn: 50,
+        critical: 50,
+    };
+    assert_eq!(bands.classify(49), Severity::Info);
+    assert_eq!(bands.classify(50), Severity::Critical);
+}
+
+#[test]
+fn severities_are_ordered_by_risk() {
+    assert!(Severity::Info < Severity::Warn && Severity::Warn < Severity::Critical);
+    assert_eq!(Severity::Warn.as_str(), "warn");
+}
+
+#[test]
+fn profile_bands_are_ordered_on_the_score_scale() {
+    for profile in [profiles::MAINNET, profiles::DEVNET, profiles::STAGING] {
+        let bands = profile.severity_bands;
+        assert!(bands.warn <= bands.critical, "{}", profile.name);
+        assert!(bands.critical <= 100, "{}", profile.name);
+    }
+}
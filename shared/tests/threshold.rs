@@ -0,0 +1,166 @@
+//! `threshold` boundaries, exhaustively over the score scale, the rounding
+//! of quote values to scores that the gates compare, and threshold ramps.
+
+use risk_oracle_shared::feed::{score_from_feed_value, MAX_SCORE, VALUE_DECIMALS};
+use risk_oracle_shared::threshold::{Boundary, Ramp};
+
+const ONE: i128 = 10i128.pow(VALUE_DECIMALS);
+
+#[test]
+fn inclusive_passes_scores_up_to_the_threshold() {
+    for threshold in 0..=MAX_SCORE {
+        for score in 0..=MAX_SCORE {
+            assert_eq!(
+                Boundary::Inclusive.passes(score, threshold),
+                score <= threshold,
+                "score {} at threshold {}",
+                score,
+                threshold
+            );
+        }
+    }
+}
+
+#[test]
+fn exclusive_passes_scores_below_the_threshold() {
+    for threshold in 0..=MAX_SCORE {
+        for score in 0..=MAX_SCORE {
+            assert_eq!(
+                Boundary::Exclusive.passes(score, threshold),
+                score < threshold,
+                "score {} at threshold {}",
+                score,
+                threshold
+            );
+        }
+    }
+}
+
+#[test]
+fn the_boundaries_differ_only_at_the_threshold() {
+    for threshold in 0..=MAX_SCORE {
+        for score in 0..=MAX_SCORE {
+            let differ = Boundary::Inclusive.passes(score, threshold)
+                != Boundary::Exclusive.passes(score, threshold);
+            assert_eq!(
+                differ,
+                score == threshold,
+                "score {} at threshold {}",
+                score,
+                threshold
+            );
+        }
+    }
+}
+
+#[test]
+fn exclusive_at_zero_passes_nothing() {
+    assert!((0..=MAX_SCORE).all(|score| !Boundary::Exclusive.passes(score, 0)));
+    assert!(Boundary::Inclusive.passes(0, 0));
+}
+
+#[test]
+fn inclusive_is_the_default() {
+    assert_eq!(Boundary::default(), Boundary::Inclusive);
+    assert_eq!(Boundary::Inclusive.as_str(), "inclusive");
+    assert_eq!(Boundary::Exclusive.as_str(), "exclusive");
+}
+
+#[test]
+fn values_between_scores_round_up() {
+    assert_eq!(score_from_feed_value(0), 0);
+    assert_eq!(score_from_feed_value(1), 1);
+    assert_eq!(score_from_feed_value(50 * ONE), 50);
+    assert_eq!(score_from_feed_value(50 * ONE + 1), 51);
+    assert_eq!(score_from_feed_value(49 * ONE + ONE / 2), 50);
+    assert_eq!(score_from_feed_value(51 * ONE - 1), 51);
+    assert_eq!(score_from_feed_value(100 * ONE), 100);
+}
+
+#[test]
+fn values_out_of_range_read_as_the_riskiest_score() {
+    assert_eq!(score_from_feed_value(100 * ONE + 1), MAX_SCORE);
+    assert_eq!(score_from_feed_value(-1), MAX_SCORE);
+    assert_eq!(score_from_feed_value(-ONE), MAX_SCORE);
+    assert_eq!(score_from_feed_value(i128::MAX), MAX_SCORE);
+}
+
+/// Values on a quarter-score grid, plus one unit either side of each score.
+fn values() -> impl Iterator<Item = i128> {
+    let quarters = (0..=400).map(|quarter| quarter * ONE / 4);
+    let around = (0..=100).flat_map(|score| [score * ONE - 1, score * ONE + 1]);
+    quarters.chain(around.filter(|value| (0..=100 * ONE).contains(value)))
+}
+
+#[test]
+fn inclusive_gating_of_the_score_matches_the_exact_value() {
+    for threshold in 0..=MAX_SCORE {
+        for value in values() {
+            let score = score_from_feed_value(value);
+            assert_eq!(
+                Boundary::Inclusive.passes(score, threshold),
+                value <= i128::from(threshold) * ONE,
+                "value {} at threshold {}",
+                value,
+                threshold
+            );
+        }
+    }
+}
+
+#[test]
+fn exclusive_gating_of_the_score_never_passes_the_exact_threshold() {
+    for threshold in 0..=MAX_SCORE {
+        for value in values() {
+            let score = score_from_feed_value(value);
+            if Boundary::Exclusive.passes(score, threshold) {
+                assert!(
+                    value < i128::from(threshold) * ONE,
+                    "value {} at threshold {}",
+                    value,
+                    threshold
+                );
+            }
+        }
+    }
+}
+
+const TIGHTENING: Ramp = Ramp {
+    from: 80,
+    to: 50,
+    start_slot: 1_000,
+    end_slot: 1_300,
+};
+
+#[test]
+fn a_ramp_holds_its_ends_outside_its_slots() {
+    assert_eq!(TIGHTENING.at(0), 80);
+    assert_eq!(TIGHTENING.at(1_000), 80);
+    assert_eq!(TIGHTENING.at(1_300), 50);
+    assert_eq!(TIGHTENING.at(u64::MAX), 50);
+}
+
+#[test]
+fn a_ramp_interpolates_rounding_down() {
+    assert_eq!(TIGHTENING.at(1_150), 65);
+    // 80 - 30 / 300 = 79.9
+    assert_eq!(TIGHTENING.at(1_001), 79);
+    let loosening = Ramp {
+        from: 50,
+        to: 80,
+        ..TIGHTENING
+    };
+    // 50 + 30 / 300 = 50.1
+    assert_eq!(loosening.at(1_001), 50);
+    assert_eq!(loosening.at(1_299), 79);
+}
+
+#[test]
+fn a_ramp_moves_monotonically() {
+    let mut previous = TIGHTENING.at(TIGHTENING.start_slot);
+    for slot in TIGHTENING.start_slot..=TIGHTENING.end_slot {
+        let threshold = TIGHTENING.at(slot);
+        assert!(threshold <= previous, "slot {}", slot);
+        previous = threshold;
+    }
+}
@@ -0,0 +1,108 @@
+//! Bypass voucher messages and the Ed25519 instructions carrying them.
+
+use risk_oracle_shared::voucher::{self, BypassVoucher, VoucherError};
+
+const PROGRAM_ID: [u8; 32] = [0x0b; 32];
+const OFFICER: [u8; 32] = [0xc0; 32];
+const SIGNATURE: [u8; 64] = [0x51; 64];
+
+fn bypass_voucher() -> BypassVoucher {
+    BypassVoucher {
+        address: [0xa3; 32],
+        not_before_slot: 100,
+        not_after_slot: 200,
+        nonce: 7,
+    }
+}
+
+#[test]
+fn message_binds_every_field_and_the_program() {
+    let voucher = bypass_voucher();
+    let message = voucher.message(&PROGRAM_ID);
+    assert_eq!(message.len(), voucher::MESSAGE_LEN);
+    assert!(message.starts_with(voucher::DOMAIN));
+    assert!(message.ends_with(&7u64.to_le_bytes()));
+
+    assert_ne!(message, voucher.message(&[0x0c; 32]));
+    for other in [
+        BypassVoucher {
+            address: [0xa4; 32],
+            ..voucher
+        },
+        BypassVoucher {
+            not_before_slot: 99,
+            ..voucher
+        },
+        BypassVoucher {
+            not_after_slot: 201,
+            ..voucher
+        },
+        BypassVoucher {
+            nonce: 8,
+            ..voucher
+        },
+    ] {
+        assert_ne!(message, other.message(&PROGRAM_ID));
+    }
+}
+
+#[test]
+fn window_is_inclusive() {
+    let voucher = bypass_voucher();
+    assert!(!voucher.is_valid_at(99));
+    assert!(voucher.is_valid_at(100));
+    assert!(voucher.is_valid_at(200));
+    assert!(!voucher.is_valid_at(201));
+}
+
+#[test]
+fn signed_message_reads_back_the_instruction_data() {
+    let message = bypass_voucher().message(&PROGRAM_ID);
+    let data = voucher::instruction_data(&OFFICER, &SIGNATURE, &message);
+    let (signer, signed) = voucher::signed_message(&data).unwrap();
+    assert_eq!(signer, &OFFICER);
+    assert_eq!(signed, message);
+}
+
+#[test]
+fn signed_message_rejects_other_signature_counts() {
+    let message = bypass_voucher().message(&PROGRAM_ID);
+    let mut data = voucher::instruction_data(&OFFICER, &SIGNATURE, &message);
+    for count in [0, 2] {
+        data[0] = count;
+        assert_eq!(
+            voucher::signed_message(&data),
+            Err(VoucherError::SignatureCount)
+        );
+    }
+}
+
+#[test]
+fn signed_message_rejects_data_of_another_instruction() {
+    // A key or message read from another instruction could be anything the
+    // relayer put there.
+    let message = bypass_voucher().message(&PROGRAM_ID);
+    for ix_index in [4, 8, 14] {
+        let mut data = voucher::instruction_data(&OFFICER, &SIGNATURE, &message);
+        data[ix_index..ix_index + 2].copy_from_slice(&0u16.to_le_bytes());
+        assert_eq!(
+            voucher::signed_message(&data),
+            Err(VoucherError::ExternalReference)
+        );
+    }
+}
+
+#[test]
+fn signed_message_rejects_offsets_past_the_data() {
+    let message = bypass_voucher().message(&PROGRAM_ID);
+    let mut data = voucher::instruction_data(&OFFICER, &SIGNATURE, &message);
+    data.truncate(data.len() - 1);
+    assert_eq!(
+        voucher::signed_message(&data),
+        Err(VoucherError::OffsetOutOfBounds)
+    );
+    assert_eq!(
+        voucher::signed_message(&data[..10]),
+        Err(VoucherError::Truncated)
+    );
+}
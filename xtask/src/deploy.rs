@@ -0,0 +1,234 @@
+//! `cargo xtask deploy`: builds both programs, deploys them, initializes the
+//! Anchor program and records its id in the config file.
+//!
+//! The cluster is detected from the RPC endpoint's genesis hash, like the
+//! binaries do, and picks the deployment profile the Anchor program is built
+//! with (`mainnet` on mainnet, `devnet` elsewhere). The Anchor program is
+//! built verifiably (`anchor build --verifiable`, in Docker) unless
+//! `--no-verifiable`; the Pinocchio program has no verifiable build and always
+//! goes through `cargo build-sbf`. Every step is skipped when already done, so
+//! a failed run can be started again: `solana program deploy` upgrades a
+//! deployed program, and the config and registry are only created when
+//! missing.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::instruction::Instruction;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use anchor_client::solana_sdk::transaction::Transaction;
+use anyhow::{anyhow, bail, Context, Result};
+use risk_oracle_client::cluster::{self, ClusterKind};
+use risk_oracle_client::{instructions, pda, ConfigParams, RiskThreshold, ID};
+
+const ANCHOR_MANIFEST: &str = "anchor/programs/risk_oracle_anchor/Cargo.toml";
+const ANCHOR_PROGRAM: &str = "anchor_oracle_example";
+const PINOCCHIO_MANIFEST: &str = "pinocchio/programs/risk_oracle_pinocchio/Cargo.toml";
+const PINOCCHIO_PROGRAM: &str = "risk_oracle";
+/// Output of `cargo build-sbf`, and where it keeps the program keypairs.
+const DEPLOY_DIR: &str = "target/deploy";
+/// Output of `anchor build --verifiable`, relative to `anchor/`.
+const VERIFIABLE_DIR: &str = "anchor/target/verifiable";
+
+pub struct DeployArgs {
+    pub url: String,
+    pub keypair: PathBuf,
+    pub config_file: PathBuf,
+    pub treasury: Option<Pubkey>,
+    pub risk_threshold: u8,
+    pub verifiable: bool,
+    pub build: bool,
+}
+
+pub fn deploy(root: &Path, args: &DeployArgs) -> Result<()> {
+    let rpc = RpcClient::new_with_commitment(args.url.clone(), CommitmentConfig::confirmed());
+    let cluster = cluster::detect(&rpc).context("failed to detect the cluster")?;
+    if cluster == ClusterKind::Testnet {
+        bail!("the programs have no testnet profile");
+    }
+    let authority = read_keypair_file(&args.keypair)
+        .map_err(|error| anyhow!("failed to read {}: {}", args.keypair.display(), error))?;
+
+    let anchor_so = match args.verifiable {
+        true => root.join(VERIFIABLE_DIR),
+        false => root.join(DEPLOY_DIR),
+    }
+    .join(format!("{ANCHOR_PROGRAM}.so"));
+    let pinocchio_so = root
+        .join(DEPLOY_DIR)
+        .join(format!("{PINOCCHIO_PROGRAM}.so"));
+    if args.build {
+        build_anchor(root, cluster, args.verifiable)?;
+        build_pinocchio(root)?;
+    }
+
+    let anchor_keypair = program_keypair(root, ANCHOR_PROGRAM)?;
+    if anchor_keypair.pubkey() != ID {
+        bail!(
+            "{} is {}, the Anchor program declares {}",
+            keypair_path(root, ANCHOR_PROGRAM).display(),
+            anchor_keypair.pubkey(),
+            ID
+        );
+    }
+    let pinocchio_keypair = program_keypair(root, PINOCCHIO_PROGRAM)?;
+
+    println!("deploying to {} ({})", cluster, args.url);
+    for (so, program) in [
+        (&anchor_so, ANCHOR_PROGRAM),
+        (&pinocchio_so, PINOCCHIO_PROGRAM),
+    ] {
+        run(
+            Command::new("solana")
+                .args(["program", "deploy", "--url", &args.url])
+                .arg("--keypair")
+                .arg(&args.keypair)
+                .arg("--program-id")
+                .arg(keypair_path(root, program))
+                .arg(so),
+            "solana program deploy",
+        )?;
+    }
+
+    let treasury = args.treasury.unwrap_or_else(|| authority.pubkey());
+    if rpc.get_account(&pda::config().0).is_err() {
+        let params = ConfigParams {
+            treasury,
+            flags: 0,
+            risk_threshold: RiskThreshold::Score(args.risk_threshold),
+            fee_lamports: 0,
+        };
+        send(
+            &rpc,
+            &authority,
+            instructions::initialize_config(authority.pubkey(), params),
+        )
+        .context("initialize_config failed")?;
+        println!("initialized the config ({})", pda::config().0);
+    }
+    if rpc.get_account(&pda::registry().0).is_err() {
+        send(
+            &rpc,
+            &authority,
+            instructions::initialize_registry(authority.pubkey()),
+        )
+        .context("initialize_registry failed")?;
+        println!("initialized the feed registry ({})", pda::registry().0);
+    }
+
+    record_program_id(&args.config_file, cluster, &ID)?;
+    println!("Anchor program:    {}", ID);
+    println!("Pinocchio program: {}", pinocchio_keypair.pubkey());
+    println!(
+        "recorded programs.{} in {}",
+        cluster,
+        args.config_file.display()
+    );
+    Ok(())
+}
+
+/// The keypair `program` is deployed at, which `cargo build-sbf` generates on
+/// the first build. The ids the programs declare only match the maintainers'
+/// keypairs: deploying elsewhere needs `declare_id!` updated first.
+fn program_keypair(root: &Path, program: &str) -> Result<Keypair> {
+    let path = keypair_path(root, program);
+    read_keypair_file(&path)
+        .map_err(|error| anyhow!("failed to read {}: {}", path.display(), error))
+}
+
+fn keypair_path(root: &Path, program: &str) -> PathBuf {
+    root.join(DEPLOY_DIR)
+        .join(format!("{program}-keypair.json"))
+}
+
+fn build_anchor(root: &Path, cluster: ClusterKind, verifiable: bool) -> Result<()> {
+    let profile = match cluster {
+        ClusterKind::Mainnet => "mainnet",
+        _ => "devnet",
+    };
+    if verifiable {
+        run(
+            Command::new("anchor")
+                .current_dir(root.join("anchor"))
+                .args([
+                    "build",
+                    "--verifiable",
+                    "--program-name",
+                    ANCHOR_PROGRAM,
+                    "--",
+                ])
+                .args(["--no-default-features", "--features", profile]),
+            "anchor build --verifiable",
+        )
+    } else {
+        run(
+            Command::new("cargo")
+                .current_dir(root)
+                .args(["build-sbf", "--manifest-path", ANCHOR_MANIFEST])
+                .args(["--sbf-out-dir", DEPLOY_DIR])
+                .args(["--no-default-features", "--features", profile]),
+            "cargo build-sbf",
+        )
+    }
+}
+
+fn build_pinocchio(root: &Path) -> Result<()> {
+    run(
+        Command::new("cargo")
+            .current_dir(root)
+            .args(["build-sbf", "--manifest-path", PINOCCHIO_MANIFEST])
+            .args(["--sbf-out-dir", DEPLOY_DIR])
+            .args(["--features", crate::BASE_FEATURES]),
+        "cargo build-sbf",
+    )
+}
+
+fn run(command: &mut Command, name: &str) -> Result<()> {
+    let status = command
+        .status()
+        .with_context(|| format!("failed to run `{name}`"))?;
+    if !status.success() {
+        bail!("`{name}` failed");
+    }
+    Ok(())
+}
+
+fn send(rpc: &RpcClient, payer: &Keypair, instruction: Instruction) -> Result<()> {
+    let blockhash = rpc.get_latest_blockhash()?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer],
+        blockhash,
+    );
+    let signature = rpc.send_and_confirm_transaction(&transaction)?;
+    println!("  {}", signature);
+    Ok(())
+}
+
+/// Sets `programs.<cluster>` of the config file, creating it when missing.
+/// The file is rewritten from its parsed values, without its comments.
+fn record_program_id(path: &Path, cluster: ClusterKind, program_id: &Pubkey) -> Result<()> {
+    let mut table = match path.exists() {
+        true => fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?
+            .parse::<toml::Table>()
+            .with_context(|| format!("failed to parse {}", path.display()))?,
+        false => toml::Table::new(),
+    };
+    let programs = table
+        .entry("programs")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        .as_table_mut()
+        .with_context(|| format!("`programs` of {} is not a table", path.display()))?;
+    programs.insert(
+        cluster.to_string(),
+        toml::Value::String(program_id.to_string()),
+    );
+    fs::write(path, toml::to_string_pretty(&table)?)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
@@ -0,0 +1,323 @@
+//! `cargo xtask`: repository tasks that need more than a `cargo` invocation.
+//!
+//! `cargo xtask size` builds the Pinocchio program, prints its section sizes
+//! and fails when `risk_oracle.so` exceeds the budget of
+//! `xtask/size-budget.toml`. It then builds it once per optional feature of the
+//! file's `matrix` and prints what each one adds, so a change that bloats the
+//! binary shows up together with the subsystem responsible.
+//!
+//! `cargo xtask deploy` builds both programs, deploys them to the cluster of
+//! an RPC endpoint, initializes the Anchor program and records its id in the
+//! config file (see [`deploy`]).
+//!
+//! `cargo xtask smoke` verifies a real quote with both deployed programs, the
+//! release gate before publishing new builds (see [`smoke`]).
+//!
+//! `cargo xtask layout` regenerates the account layout tables of the README
+//! from `risk_oracle_shared::layout`; `--check` fails when they are stale
+//! instead.
+
+mod deploy;
+mod smoke;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use risk_oracle_bench::elf::{self, Section, STACK_FRAME};
+use serde::Deserialize;
+
+const PROGRAM_MANIFEST: &str = "pinocchio/programs/risk_oracle_pinocchio/Cargo.toml";
+const PROGRAM_FILE: &str = "risk_oracle.so";
+/// Features every build has: the program needs its entrypoint.
+const BASE_FEATURES: &str = "bpf-entrypoint";
+/// Sections printed, when present.
+const SECTIONS: &[&str] = &[".text", ".rodata", ".data.rel.ro", ".dynsym", ".dynstr"];
+/// Markers around the generated layout tables of the README.
+const LAYOUT_START: &str = "<!-- layout:start -->\n";
+const LAYOUT_END: &str = "<!-- layout:end -->";
+/// Wallet screened by `smoke`, the SDK's `DEFAULT_QUERY_ACCOUNT`.
+const DEFAULT_SMOKE_ADDRESS: &str = "5PAhQiYdLBd6SVdjzBQDxUAEFyDdF5ExNPQfcscnPRj5";
+
+#[derive(Parser)]
+#[command(name = "xtask")]
+struct Cli {
+    #[command(subcommand)]
+    command: Task,
+}
+
+#[derive(Subcommand)]
+enum Task {
+    /// Checks the Pinocchio program against its size budget.
+    Size {
+        /// Budget and feature matrix.
+        #[arg(long, default_value = "xtask/size-budget.toml")]
+        budget_file: PathBuf,
+        /// Measures the builds already in `target/size` instead of building.
+        #[arg(long)]
+        no_build: bool,
+        /// Only checks the default build.
+        #[arg(long)]
+        no_matrix: bool,
+    },
+    /// Builds, deploys and initializes both programs.
+    Deploy {
+        /// RPC endpoint of the cluster deployed to.
+        #[arg(long, env = "RPC_URL", default_value = "https://api.devnet.solana.com")]
+        url: String,
+        /// Payer, upgrade authority and config authority.
+        #[arg(long, default_value = "~/.config/solana/id.json")]
+        keypair: PathBuf,
+        /// Config file the Anchor program id is recorded in, created when
+        /// missing.
+        #[arg(long, default_value = "risk-oracle.toml")]
+        config_file: PathBuf,
+        /// Fee treasury of the config, the authority by default.
+        #[arg(long)]
+        treasury: Option<Pubkey>,
+        /// Risk threshold of the config, on the feed scale (0–100).
+        #[arg(long, default_value_t = 100)]
+        risk_threshold: u8,
+        /// Builds the Anchor program with `cargo build-sbf` instead of
+        /// `anchor build --verifiable`.
+        #[arg(long)]
+        no_verifiable: bool,
+        /// Deploys the builds already in `target/deploy` (and
+        /// `anchor/target/verifiable`) instead of building.
+        #[arg(long)]
+        no_build: bool,
+    },
+    /// Verifies a quote with both deployed programs and compares the scores.
+    Smoke {
+        /// RPC endpoint of the cluster the programs are deployed to.
+        #[arg(long, env = "RPC_URL", default_value = "https://api.devnet.solana.com")]
+        url: String,
+        /// Base URL of the gateway pinning the feed and signing the quotes.
+        #[arg(long, env = "GATEWAY_URL")]
+        gateway_url: String,
+        /// Payer of the verifications.
+        #[arg(long, default_value = "~/.config/solana/id.json")]
+        keypair: PathBuf,
+        /// Screened wallet.
+        #[arg(long, env = "SMOKE_ADDRESS", default_value = DEFAULT_SMOKE_ADDRESS)]
+        address: Pubkey,
+        /// Range API key the oracles fetch the score with.
+        #[arg(long, env = "RANGE_API_KEY", hide_env_values = true)]
+        api_key: String,
+        /// Also fails unless the programs logged this score.
+        #[arg(long, env = "SMOKE_EXPECTED_SCORE")]
+        expected_score: Option<u8>,
+    },
+    /// Regenerates the account layout tables of the README.
+    Layout {
+        /// Fails when the tables are stale instead of rewriting them.
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Budget {
+    /// Bytes.
+    budget: u64,
+    #[serde(default)]
+    matrix: Vec<String>,
+}
+
+/// A measured build.
+struct Build {
+    bytes: u64,
+    sections: Vec<Section>,
+    largest_stack_frame: u64,
+}
+
+impl Build {
+    fn section(&self, name: &str) -> u64 {
+        self.sections
+            .iter()
+            .find(|section| section.name == name)
+            .map_or(0, |section| section.size)
+    }
+}
+
+fn main() -> Result<()> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .context("xtask is not in a workspace")?;
+    match Cli::parse().command {
+        Task::Size {
+            budget_file,
+            no_build,
+            no_matrix,
+        } => size(root, &root.join(budget_file), !no_build, !no_matrix),
+        Task::Deploy {
+            url,
+            keypair,
+            config_file,
+            treasury,
+            risk_threshold,
+            no_verifiable,
+            no_build,
+        } => deploy::deploy(
+            root,
+            &deploy::DeployArgs {
+                url,
+                keypair: expand_home(keypair),
+                config_file: root.join(config_file),
+                treasury,
+                risk_threshold,
+                verifiable: !no_verifiable,
+                build: !no_build,
+            },
+        ),
+        Task::Smoke {
+            url,
+            gateway_url,
+            keypair,
+            address,
+            api_key,
+            expected_score,
+        } => smoke::smoke(&smoke::SmokeArgs {
+            url,
+            gateway_url,
+            keypair: expand_home(keypair),
+            address,
+            api_key,
+            expected_score,
+        }),
+        Task::Layout { check } => layout(&root.join("README.md"), check),
+    }
+}
+
+/// Resolves a leading `~/` against `$HOME`, as the shell would have.
+fn expand_home(path: PathBuf) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path,
+    }
+}
+
+fn size(root: &Path, budget_file: &Path, build: bool, matrix: bool) -> Result<()> {
+    let text = fs::read_to_string(budget_file)
+        .with_context(|| format!("failed to read {}", budget_file.display()))?;
+    let budget: Budget = toml::from_str(&text)
+        .with_context(|| format!("failed to parse {}", budget_file.display()))?;
+
+    let default = measure(root, "default", None, build)?;
+    println!(
+        "{} (default features): {} bytes, budget {} ({:.1}%)",
+        PROGRAM_FILE,
+        default.bytes,
+        budget.budget,
+        default.bytes as f64 * 100.0 / budget.budget as f64
+    );
+    for name in SECTIONS {
+        if default.sections.iter().any(|section| section.name == *name) {
+            println!("  {:<14} {:>9}", name, default.section(name));
+        }
+    }
+    println!(
+        "  {:<14} {:>9} / {}",
+        "stack frame", default.largest_stack_frame, STACK_FRAME
+    );
+
+    if matrix && !budget.matrix.is_empty() {
+        println!(
+            "\n{:<16} {:>9} {:>9} {:>12}",
+            "feature", "bytes", "+bytes", "+.text"
+        );
+        for feature in &budget.matrix {
+            let with = measure(root, feature, Some(feature), build)?;
+            println!(
+                "{:<16} {:>9} {:>+9} {:>+12}",
+                feature,
+                with.bytes,
+                with.bytes as i64 - default.bytes as i64,
+                with.section(".text") as i64 - default.section(".text") as i64
+            );
+        }
+    }
+
+    if default.bytes > budget.budget {
+        bail!(
+            "{} is {} bytes over its {} byte budget ({})",
+            PROGRAM_FILE,
+            default.bytes - budget.budget,
+            budget.budget,
+            budget_file.display()
+        );
+    }
+    Ok(())
+}
+
+/// Replaces the README between the layout markers with
+/// `risk_oracle_shared::layout::markdown`, or only compares them when `check`.
+fn layout(readme: &Path, check: bool) -> Result<()> {
+    let text = fs::read_to_string(readme)
+        .with_context(|| format!("failed to read {}", readme.display()))?;
+    let start = text
+        .find(LAYOUT_START)
+        .with_context(|| format!("no `{}` in {}", LAYOUT_START.trim(), readme.display()))?
+        + LAYOUT_START.len();
+    let end = start
+        + text[start..]
+            .find(LAYOUT_END)
+            .with_context(|| format!("no `{}` in {}", LAYOUT_END, readme.display()))?;
+    let tables = risk_oracle_shared::layout::markdown();
+    if text[start..end] == tables {
+        println!("{}: layout tables up to date", readme.display());
+        return Ok(());
+    }
+    if check {
+        bail!(
+            "the layout tables of {} are stale, run `cargo xtask layout`",
+            readme.display()
+        );
+    }
+    fs::write(
+        readme,
+        format!("{}{}{}", &text[..start], tables, &text[end..]),
+    )
+    .with_context(|| format!("failed to write {}", readme.display()))?;
+    println!("{}: layout tables regenerated", readme.display());
+    Ok(())
+}
+
+/// Builds the program with `feature` into `target/size/<name>`, unless
+/// `build` is off, and measures it.
+fn measure(root: &Path, name: &str, feature: Option<&str>, build: bool) -> Result<Build> {
+    let out_dir = root.join("target/size").join(name);
+    if build {
+        let features = match feature {
+            Some(feature) => format!("{},{}", BASE_FEATURES, feature),
+            None => BASE_FEATURES.to_string(),
+        };
+        let status = Command::new("cargo")
+            .current_dir(root)
+            .args(["build-sbf", "--manifest-path", PROGRAM_MANIFEST])
+            .arg("--sbf-out-dir")
+            .arg(&out_dir)
+            .args(["--features", &features])
+            .status()
+            .context("failed to run `cargo build-sbf`")?;
+        if !status.success() {
+            bail!("`cargo build-sbf --features {}` failed", features);
+        }
+    }
+
+    let path = out_dir.join(PROGRAM_FILE);
+    let bytes = fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    let sections = elf::sections(&bytes).with_context(|| format!("{}", path.display()))?;
+    let largest_stack_frame = elf::section_data(&bytes, &sections, ".text")
+        .map(elf::largest_stack_frame)
+        .unwrap_or(0);
+    Ok(Build {
+        bytes: bytes.len() as u64,
+        sections,
+        largest_stack_frame,
+    })
+}
@@ -0,0 +1,155 @@
+//! `cargo xtask smoke`: the release gate, run against devnet before
+//! publishing new program builds.
+//!
+//! Pins the Risk Score feed of the screened address with the gateway and
+//! checks it was pinned under the id the programs derive, fetches a quote for
+//! it with the given Range API key, verifies it with the deployed Anchor and
+//! Pinocchio programs and asserts both logged the same score
+//! (`--expected-score` to also assert its value). Quotes and transactions go
+//! through the keeper's [`GatewayQuoteSource`] and [`RpcPool`], so the gate
+//! exercises the code the keeper runs; a quote is fetched again whenever a
+//! transaction has to be resent.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::instruction::{AccountMeta, Instruction};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use anchor_client::solana_sdk::{pubkey, sysvar};
+use anyhow::{anyhow, bail, Context, Result};
+use risk_oracle_client::instructions::{self, VerifyOptions};
+use risk_oracle_client::transaction::{self, TransactionOptions};
+use risk_oracle_client::{pda, queue, ID};
+use risk_oracle_keeper::credentials::ApiKeys;
+use risk_oracle_keeper::gateway::GatewayQuoteSource;
+use risk_oracle_keeper::refresh::QuoteSource;
+use risk_oracle_keeper::rpc::RpcPool;
+use risk_oracle_shared::diagnostics::hex;
+use risk_oracle_shared::feed;
+use risk_oracle_shared::proto::OracleFeed;
+
+/// Program id of the Pinocchio program (`pinocchio/.../lib.rs`).
+const PINOCCHIO_ID: Pubkey = pubkey!("CR8mpiY9eEbNkU8w4VJkGB4gzEnozp739jwvTiXRmACc");
+/// Score lines of the programs, after `Program log: `.
+const ANCHOR_SCORE_LOG: &str = "Verified risk score feed! Value: ";
+const PINOCCHIO_SCORE_LOG: &str = "Risk Score ";
+/// Name of the API key in the gateway client; a 429 rests it this long, which
+/// fails the run since there is no other key.
+const API_KEY_NAME: &str = "smoke";
+const RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(60);
+
+pub struct SmokeArgs {
+    pub url: String,
+    pub gateway_url: String,
+    pub keypair: PathBuf,
+    pub address: Pubkey,
+    pub api_key: String,
+    pub expected_score: Option<u8>,
+}
+
+pub fn smoke(args: &SmokeArgs) -> Result<()> {
+    let mut pool = RpcPool::new([args.url.as_str()], CommitmentConfig::confirmed());
+    let payer = read_keypair_file(&args.keypair)
+        .map_err(|error| anyhow!("failed to read {}: {}", args.keypair.display(), error))?;
+    println!("smoke test of {} on {}", args.address, args.url);
+
+    let [anchor, pinocchio, config] = pool
+        .accounts(&[ID, PINOCCHIO_ID, pda::config().0])?
+        .try_into()
+        .map_err(|_| anyhow!("the RPC endpoint answered another number of accounts"))?;
+    for (program, account) in [(ID, anchor), (PINOCCHIO_ID, pinocchio)] {
+        if !account.is_some_and(|account| account.executable) {
+            bail!("{} is not deployed", program);
+        }
+    }
+    if config.is_none() {
+        bail!("the Anchor program's config is not initialized");
+    }
+
+    // 1) Pin the feed: the gateway must store it under the id both programs
+    // derive.
+    let api_keys = ApiKeys::new(
+        [(API_KEY_NAME.to_string(), args.api_key.clone())],
+        RATE_LIMIT_COOLDOWN,
+    );
+    let gateway =
+        GatewayQuoteSource::new(&args.gateway_url, args.url.clone()).with_api_keys(api_keys);
+    let feed = feed::risk_score_feed(&args.address.to_bytes());
+    let feed_id = gateway.pin_feed(&feed)?;
+    println!("pinned feed {}", hex(&feed_id));
+
+    // 2) Verify a quote with each program.
+    let anchor_verify =
+        instructions::verify_risk_score_feed(args.address, &VerifyOptions::default());
+    let (signature, logs) = verify(&mut pool, &gateway, &payer, &feed, anchor_verify)?;
+    let anchor_score = logged_score(&logs, ANCHOR_SCORE_LOG)
+        .with_context(|| format!("Anchor verification {signature}"))?;
+    println!("Anchor:    score {anchor_score} ({signature})");
+
+    let pinocchio_verify = pinocchio_verify_instruction(args.address);
+    let (signature, logs) = verify(&mut pool, &gateway, &payer, &feed, pinocchio_verify)?;
+    let pinocchio_score = logged_score(&logs, PINOCCHIO_SCORE_LOG)
+        .with_context(|| format!("Pinocchio verification {signature}"))?;
+    println!("Pinocchio: score {pinocchio_score} ({signature})");
+
+    // 3) Assert the logged scores.
+    if pinocchio_score != anchor_score {
+        bail!("the programs logged different scores ({anchor_score} and {pinocchio_score})");
+    }
+    if let Some(expected) = args.expected_score {
+        if anchor_score != expected {
+            bail!("unexpected score {anchor_score}, expected {expected}");
+        }
+    }
+    println!("smoke test passed");
+    Ok(())
+}
+
+/// Sends `[quote, verify]` and returns the transaction's logs.
+fn verify(
+    pool: &mut RpcPool,
+    gateway: &GatewayQuoteSource,
+    payer: &Keypair,
+    feed: &OracleFeed,
+    verify_ix: Instruction,
+) -> Result<(Signature, Vec<String>)> {
+    let signature = pool.send_and_confirm(|blockhash| {
+        let quote_ix = gateway.quote_instruction(feed, &payer.pubkey())?;
+        Ok(transaction::transaction(
+            &[payer],
+            &[quote_ix, verify_ix.clone()],
+            blockhash,
+            &TransactionOptions::default(),
+        )?)
+    })?;
+    let logs = pool.transaction_logs(&signature)?;
+    Ok((signature, logs))
+}
+
+/// The Pinocchio program's wallet mode: no instruction data, no clock.
+fn pinocchio_verify_instruction(address: Pubkey) -> Instruction {
+    Instruction {
+        program_id: PINOCCHIO_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(queue(), false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+            AccountMeta::new_readonly(address, false),
+        ],
+        data: Vec::new(),
+    }
+}
+
+/// Score of the first `Program log: <prefix><score>` line, 0–100.
+fn logged_score(logs: &[String], prefix: &str) -> Result<u8> {
+    let line = logs
+        .iter()
+        .find_map(|log| log.strip_prefix("Program log: ")?.strip_prefix(prefix))
+        .ok_or_else(|| anyhow!("no `{prefix}` line in the logs:\n{}", logs.join("\n")))?;
+    match line.trim().parse() {
+        Ok(score) if score <= 100 => Ok(score),
+        _ => bail!("invalid score in `{prefix}{line}`"),
+    }
+}